@@ -0,0 +1,31 @@
+//! Benchmark example for AgenticOptioRS
+//!
+//! Compares local Ollama models on a small prompt suite:
+//! cargo run --example benchmark -- llama3.2 qwen2.5:7b
+
+use agentic_optio_rs::eval::Benchmark;
+use agentic_optio_rs::OllamaChat;
+use std::sync::Arc;
+
+#[tokio::main]
+async fn main() {
+    let mut models: Vec<String> = std::env::args().skip(1).collect();
+    if models.is_empty() {
+        models.push("llama3.2".to_string());
+    }
+
+    let mut benchmark = Benchmark::new()
+        .prompts([
+            "Explain what a hash map is in one paragraph.",
+            "Write a haiku about autumn.",
+            "List three uses of the Rust borrow checker.",
+        ])
+        .repetitions(3);
+    for model in &models {
+        benchmark = benchmark.model(model, Arc::new(OllamaChat::new(model.as_str())));
+    }
+
+    println!("Benchmarking {} model(s)...\n", models.len());
+    let report = benchmark.run().await;
+    println!("{}", report.to_table());
+}
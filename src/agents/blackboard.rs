@@ -0,0 +1,209 @@
+//! Shared blackboard workspace for AgenticOptio squads.
+//!
+//! A [`Blackboard`] is a concurrent key-value store every agent in a squad can
+//! read and write. Each entry carries a version number; writes may pass the
+//! version they last read so concurrent updates fail with a conflict instead of
+//! silently overwriting each other. [`Blackboard::tools`] surfaces the store to
+//! agents as `blackboard_read`, `blackboard_write`, and `blackboard_list` tools.
+
+use crate::tools::{FunctionTool, ToolError, ToolRegistry};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+/// Error type for blackboard operations
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BlackboardError {
+    #[error("Version conflict on '{key}': expected {expected}, found {actual}")]
+    VersionConflict {
+        key: String,
+        expected: u64,
+        actual: u64,
+    },
+}
+
+/// Versioned blackboard value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlackboardEntry {
+    pub value: serde_json::Value,
+    /// Starts at 1 and increments on every write
+    pub version: u64,
+    /// Name of the last writer
+    pub author: String,
+}
+
+/// Concurrent key-value workspace with optimistic versioning
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::agents::Blackboard;
+///
+/// let board = Blackboard::new();
+/// let v1 = board.write("plan", serde_json::json!(["research"]), "lead", None).unwrap();
+/// // A stale writer that expected version 0 (absent) is rejected.
+/// assert!(board.write("plan", serde_json::json!([]), "helper", Some(0)).is_err());
+/// board.write("plan", serde_json::json!(["research", "draft"]), "helper", Some(v1)).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Blackboard {
+    entries: Arc<RwLock<BTreeMap<String, BlackboardEntry>>>,
+}
+
+impl Blackboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(&self, key: &str) -> Option<BlackboardEntry> {
+        self.entries.read().unwrap().get(key).cloned()
+    }
+
+    /// Current version of a key, 0 if absent
+    pub fn version(&self, key: &str) -> u64 {
+        self.read(key).map_or(0, |e| e.version)
+    }
+
+    /// Write a value, returning the new version.
+    ///
+    /// With `expected_version`, the write only succeeds if the key is still at that
+    /// version (use 0 to require the key be absent).
+    pub fn write(
+        &self,
+        key: &str,
+        value: serde_json::Value,
+        author: &str,
+        expected_version: Option<u64>,
+    ) -> Result<u64, BlackboardError> {
+        let mut entries = self.entries.write().unwrap();
+        let actual = entries.get(key).map_or(0, |e| e.version);
+        if let Some(expected) = expected_version {
+            if expected != actual {
+                return Err(BlackboardError::VersionConflict {
+                    key: key.to_string(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        let version = actual + 1;
+        entries.insert(
+            key.to_string(),
+            BlackboardEntry {
+                value,
+                version,
+                author: author.to_string(),
+            },
+        );
+        Ok(version)
+    }
+
+    /// Remove a key, returning the removed entry
+    pub fn delete(
+        &self,
+        key: &str,
+        expected_version: Option<u64>,
+    ) -> Result<Option<BlackboardEntry>, BlackboardError> {
+        let mut entries = self.entries.write().unwrap();
+        let actual = entries.get(key).map_or(0, |e| e.version);
+        match expected_version {
+            Some(expected) if expected != actual => Err(BlackboardError::VersionConflict {
+                key: key.to_string(),
+                expected,
+                actual,
+            }),
+            _ => Ok(entries.remove(key)),
+        }
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        self.entries.read().unwrap().keys().cloned().collect()
+    }
+
+    /// Copy of every entry
+    pub fn snapshot(&self) -> BTreeMap<String, BlackboardEntry> {
+        self.entries.read().unwrap().clone()
+    }
+
+    /// Tools giving an agent access to the blackboard; writes are attributed to
+    /// `author`
+    pub fn tools(&self, author: impl Into<String>) -> ToolRegistry {
+        let author = author.into();
+        let mut registry = ToolRegistry::new();
+
+        let board = self.clone();
+        registry.register(
+            FunctionTool::new(
+                "blackboard_read",
+                "Read a value and its version from the shared team blackboard.",
+                move |args| {
+                    let board = board.clone();
+                    async move {
+                        let key = required_key(&args)?;
+                        Ok(match board.read(&key) {
+                            Some(entry) => serde_json::to_string(&entry)
+                                .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?,
+                            None => format!("No entry for '{}' (version 0)", key),
+                        })
+                    }
+                },
+            )
+            .with_parameters(serde_json::json!({
+                "type": "object",
+                "properties": {"key": {"type": "string"}},
+                "required": ["key"]
+            })),
+        );
+
+        let board = self.clone();
+        registry.register(
+            FunctionTool::new(
+                "blackboard_write",
+                "Write a value to the shared team blackboard. Pass the version you last \
+                 read as expected_version to avoid overwriting a teammate's update.",
+                move |args| {
+                    let board = board.clone();
+                    let author = author.clone();
+                    async move {
+                        let key = required_key(&args)?;
+                        let value = args.get("value").cloned().unwrap_or_default();
+                        let expected = args.get("expected_version").and_then(|v| v.as_u64());
+                        let version = board
+                            .write(&key, value, &author, expected)
+                            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+                        Ok(format!("Wrote '{}' at version {}", key, version))
+                    }
+                },
+            )
+            .with_parameters(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "key": {"type": "string"},
+                    "value": {"description": "Any JSON value"},
+                    "expected_version": {"type": "integer"}
+                },
+                "required": ["key", "value"]
+            })),
+        );
+
+        let board = self.clone();
+        registry.register(FunctionTool::new(
+            "blackboard_list",
+            "List the keys on the shared team blackboard.",
+            move |_| {
+                let board = board.clone();
+                async move { Ok(board.keys().join(", ")) }
+            },
+        ));
+
+        registry
+    }
+}
+
+fn required_key(args: &serde_json::Value) -> Result<String, ToolError> {
+    args.get("key")
+        .and_then(|k| k.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| ToolError::InvalidArguments("missing 'key'".to_string()))
+}
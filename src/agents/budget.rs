@@ -0,0 +1,178 @@
+//! Budgets for AgenticOptio agent runs.
+//!
+//! A [`Budget`] caps the tokens, cost, wall-clock time, and model calls an agent
+//! run may consume. The executor checks it before every model call and stops the
+//! run with [`StopReason::BudgetExceeded`](crate::agents::StopReason) once a
+//! limit is hit. The wall-clock limit also cuts off a model or tool call still
+//! in progress; tool calls it leaves unanswered get a result saying they were
+//! cancelled, so the run's messages can be sent to a model again.
+
+use crate::core::messages::Usage;
+use std::time::{Duration, Instant};
+
+/// Resource limit that ended a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetLimit {
+    Tokens,
+    Cost,
+    WallClock,
+    ModelCalls,
+}
+
+impl std::fmt::Display for BudgetLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            BudgetLimit::Tokens => "token",
+            BudgetLimit::Cost => "cost",
+            BudgetLimit::WallClock => "wall-clock",
+            BudgetLimit::ModelCalls => "model call",
+        };
+        write!(f, "{} budget exceeded", name)
+    }
+}
+
+/// Resource limits for a single agent run
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::agents::Budget;
+/// use std::time::Duration;
+///
+/// let budget = Budget::new()
+///     .max_tokens(50_000)
+///     .max_model_calls(20)
+///     .max_duration(Duration::from_secs(120));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    pub max_tokens: Option<u64>,
+    pub max_cost: Option<f64>,
+    pub max_duration: Option<Duration>,
+    pub max_model_calls: Option<usize>,
+    /// Cost per 1K input tokens, used to compute spend against `max_cost`
+    pub input_cost_per_1k: f64,
+    /// Cost per 1K output tokens, used to compute spend against `max_cost`
+    pub output_cost_per_1k: f64,
+}
+
+impl Budget {
+    /// Create an unlimited budget
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_tokens(mut self, max_tokens: u64) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn max_cost(mut self, max_cost: f64) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Stop the run this long after it starts, cutting off any model or tool
+    /// call still in progress
+    pub fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    pub fn max_model_calls(mut self, max_model_calls: usize) -> Self {
+        self.max_model_calls = Some(max_model_calls);
+        self
+    }
+
+    /// Set token pricing used to compute cost
+    pub fn pricing(mut self, input_cost_per_1k: f64, output_cost_per_1k: f64) -> Self {
+        self.input_cost_per_1k = input_cost_per_1k;
+        self.output_cost_per_1k = output_cost_per_1k;
+        self
+    }
+
+    /// Cost of the given usage under this budget's pricing
+    pub fn cost_of(&self, usage: &Usage) -> f64 {
+        (usage.input_tokens as f64 * self.input_cost_per_1k
+            + usage.output_tokens as f64 * self.output_cost_per_1k)
+            / 1000.0
+    }
+}
+
+/// Tracks consumption against a [`Budget`] during a run
+#[derive(Debug, Clone)]
+pub struct BudgetTracker {
+    budget: Budget,
+    started: Instant,
+    usage: Usage,
+    cost: f64,
+    model_calls: usize,
+}
+
+impl BudgetTracker {
+    pub fn new(budget: Budget) -> Self {
+        Self {
+            budget,
+            started: Instant::now(),
+            usage: Usage::default(),
+            cost: 0.0,
+            model_calls: 0,
+        }
+    }
+
+    /// Record a completed model call
+    pub fn record(&mut self, usage: Option<Usage>) {
+        self.model_calls += 1;
+        if let Some(usage) = usage {
+            self.cost += self.budget.cost_of(&usage);
+            self.usage += usage;
+        }
+    }
+
+    pub fn usage(&self) -> Usage {
+        self.usage
+    }
+
+    pub fn cost(&self) -> f64 {
+        self.cost
+    }
+
+    pub fn model_calls(&self) -> usize {
+        self.model_calls
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Wall-clock time left, if the budget has a duration limit
+    pub fn remaining_time(&self) -> Option<Duration> {
+        self.budget
+            .max_duration
+            .map(|max| max.saturating_sub(self.elapsed()))
+    }
+
+    /// First limit that has been reached, if any
+    pub fn exceeded(&self) -> Option<BudgetLimit> {
+        let budget = &self.budget;
+        if budget
+            .max_tokens
+            .is_some_and(|max| self.usage.total_tokens as u64 >= max)
+        {
+            return Some(BudgetLimit::Tokens);
+        }
+        if budget.max_cost.is_some_and(|max| self.cost >= max) {
+            return Some(BudgetLimit::Cost);
+        }
+        if budget
+            .max_model_calls
+            .is_some_and(|max| self.model_calls >= max)
+        {
+            return Some(BudgetLimit::ModelCalls);
+        }
+        if self.remaining_time() == Some(Duration::ZERO) {
+            return Some(BudgetLimit::WallClock);
+        }
+        None
+    }
+}
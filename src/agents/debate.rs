@@ -0,0 +1,148 @@
+//! Structured debates for AgenticOptio.
+//!
+//! [`Debate`] has two agents argue opposite sides of a topic in alternating turns
+//! for a fixed number of rounds, then asks a judge model for a verdict. The full
+//! transcript is returned for audit.
+
+use crate::agents::executor::{Agent, AgentResult};
+use crate::core::messages::Message;
+use crate::models::base::BaseChatModel;
+use std::sync::Arc;
+
+/// Single argument made during a debate
+#[derive(Debug, Clone)]
+pub struct DebateTurn {
+    pub round: usize,
+    pub speaker: String,
+    pub argument: String,
+}
+
+/// Outcome of a debate
+#[derive(Debug, Clone)]
+pub struct DebateResult {
+    pub topic: String,
+    pub turns: Vec<DebateTurn>,
+    /// Judge's full verdict
+    pub verdict: String,
+    /// Name of the winning agent, if the judge named one
+    pub winner: Option<String>,
+}
+
+/// Two-agent debate with a judge
+///
+/// Agents are identified by [`Agent::name`], so give them distinct names.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::agents::{Agent, Debate};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let model = Arc::new(OllamaChat::new("llama3.2"));
+///     let pro = Agent::builder(model.clone()).name("pro").build();
+///     let con = Agent::builder(model.clone()).name("con").build();
+///     let result = Debate::new(pro, con, model)
+///         .rounds(2)
+///         .run("Tabs are better than spaces")
+///         .await?;
+///     println!("{}", result.verdict);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Debate {
+    proponent: Agent,
+    opponent: Agent,
+    judge: Arc<dyn BaseChatModel>,
+    rounds: usize,
+}
+
+impl Debate {
+    pub fn new(proponent: Agent, opponent: Agent, judge: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            proponent,
+            opponent,
+            judge,
+            rounds: 3,
+        }
+    }
+
+    /// Number of rounds; each round is one turn per agent
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    pub async fn run(&self, topic: impl Into<String>) -> AgentResult<DebateResult> {
+        let topic = topic.into();
+        let mut turns = Vec::with_capacity(self.rounds * 2);
+
+        for round in 1..=self.rounds {
+            for (agent, side) in [(&self.proponent, "FOR"), (&self.opponent, "AGAINST")] {
+                let prompt = format!(
+                    "Debate topic: {}\nYou are arguing {} the topic.\n\n{}\n\n\
+                     Make your next argument. Rebut the other side where relevant and be concise.",
+                    topic,
+                    side,
+                    render_turns(&turns)
+                );
+                let run = agent.run(prompt).await?;
+                turns.push(DebateTurn {
+                    round,
+                    speaker: agent.name().to_string(),
+                    argument: run.output,
+                });
+            }
+        }
+
+        let verdict = self
+            .judge
+            .invoke(&[
+                Message::system(format!(
+                    "You are judging a debate between '{}' (for) and '{}' (against). \
+                     Start your reply with 'Winner: <name>' and then explain your reasoning.",
+                    self.proponent.name(),
+                    self.opponent.name()
+                )),
+                Message::user(format!("Topic: {}\n\n{}", topic, render_turns(&turns))),
+            ])
+            .await?
+            .content;
+
+        let winner = parse_winner(&verdict, &[self.proponent.name(), self.opponent.name()]);
+
+        Ok(DebateResult {
+            topic,
+            turns,
+            verdict,
+            winner,
+        })
+    }
+}
+
+fn render_turns(turns: &[DebateTurn]) -> String {
+    if turns.is_empty() {
+        return "No arguments have been made yet.".to_string();
+    }
+
+    let rendered = turns
+        .iter()
+        .map(|t| format!("[Round {}] {}: {}", t.round, t.speaker, t.argument))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    format!("Debate so far:\n{}", rendered)
+}
+
+fn parse_winner(verdict: &str, names: &[&str]) -> Option<String> {
+    let line = verdict
+        .lines()
+        .find(|l| l.trim().to_lowercase().starts_with("winner"))?
+        .to_lowercase();
+    names
+        .iter()
+        .find(|name| line.contains(&name.to_lowercase()))
+        .map(|name| name.to_string())
+}
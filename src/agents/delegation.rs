@@ -0,0 +1,220 @@
+//! Delegation limits for AgenticOptio squads.
+//!
+//! A [`Coordinator`] turns sub-agents into tools a parent agent can call, and
+//! enforces global [`DelegationLimits`]: how deep delegation chains may nest and
+//! how many sub-agent calls a single agent run may make. Calls past a limit are
+//! refused before the sub-agent starts, and the parent model receives an error
+//! explaining which limit was hit, so runaway recursive agents cannot spawn work
+//! indefinitely.
+
+use crate::agents::executor::{Agent, AgentResult, AgentRun};
+use crate::tools::{BaseTool, ToolError, ToolResult};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+tokio::task_local! {
+    static FRAME: DelegationFrame;
+}
+
+/// Position of the running agent in the delegation tree
+#[derive(Clone)]
+struct DelegationFrame {
+    depth: usize,
+    spawned: Arc<AtomicUsize>,
+}
+
+impl DelegationFrame {
+    fn root() -> Self {
+        Self {
+            depth: 0,
+            spawned: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+/// Error type for delegation limit violations
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DelegationError {
+    #[error("Delegation depth limit of {max} reached; answer without delegating further")]
+    DepthExceeded { max: usize },
+
+    #[error("Fan-out limit of {max} sub-agent calls reached for this run")]
+    FanOutExceeded { max: usize },
+}
+
+impl From<DelegationError> for ToolError {
+    fn from(err: DelegationError) -> Self {
+        ToolError::ExecutionFailed(err.to_string())
+    }
+}
+
+/// Global limits on delegation chains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DelegationLimits {
+    /// Maximum nesting of sub-agents below the root agent
+    pub max_depth: usize,
+    /// Maximum sub-agent calls a single agent run may make
+    pub max_fan_out: usize,
+}
+
+impl DelegationLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn max_fan_out(mut self, max_fan_out: usize) -> Self {
+        self.max_fan_out = max_fan_out;
+        self
+    }
+}
+
+impl Default for DelegationLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_fan_out: 5,
+        }
+    }
+}
+
+/// Enforces delegation limits across a squad of agents
+///
+/// Sub-agents are exposed to their parent through [`Coordinator::delegate`].
+/// Start the root agent with [`Coordinator::run`] so fan-out is counted per run;
+/// delegation tools called outside it share one root-level counter.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::agents::{Agent, Coordinator, DelegationLimits};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let model = Arc::new(OllamaChat::new("llama3.2"));
+///     let coordinator = Coordinator::new(DelegationLimits::new().max_depth(2).max_fan_out(3));
+///
+///     let researcher = Agent::builder(model.clone()).name("researcher").build();
+///     let lead = Agent::builder(model)
+///         .tool(coordinator.delegate(researcher, "Research a question in depth"))
+///         .build();
+///
+///     let run = coordinator.run(&lead, "Summarize recent Rust async work").await?;
+///     println!("{}", run.output);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Coordinator {
+    limits: DelegationLimits,
+    root_spawned: Arc<AtomicUsize>,
+}
+
+impl Coordinator {
+    pub fn new(limits: DelegationLimits) -> Self {
+        Self {
+            limits,
+            root_spawned: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn limits(&self) -> DelegationLimits {
+        self.limits
+    }
+
+    /// Expose a sub-agent as a tool named after the agent
+    pub fn delegate(&self, agent: Agent, description: impl Into<String>) -> DelegateTool {
+        DelegateTool {
+            agent,
+            description: description.into(),
+            coordinator: self.clone(),
+        }
+    }
+
+    /// Run a root agent with fresh delegation accounting
+    pub async fn run(&self, agent: &Agent, input: impl Into<String>) -> AgentResult<AgentRun> {
+        FRAME.scope(DelegationFrame::root(), agent.run(input)).await
+    }
+
+    /// Reserve a sub-agent call from the current frame
+    fn enter(&self) -> Result<DelegationFrame, DelegationError> {
+        let current = FRAME
+            .try_with(|f| f.clone())
+            .unwrap_or_else(|_| DelegationFrame {
+                depth: 0,
+                spawned: self.root_spawned.clone(),
+            });
+
+        if current.depth >= self.limits.max_depth {
+            return Err(DelegationError::DepthExceeded {
+                max: self.limits.max_depth,
+            });
+        }
+        if current.spawned.fetch_add(1, Ordering::SeqCst) >= self.limits.max_fan_out {
+            return Err(DelegationError::FanOutExceeded {
+                max: self.limits.max_fan_out,
+            });
+        }
+
+        Ok(DelegationFrame {
+            depth: current.depth + 1,
+            spawned: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+}
+
+impl Default for Coordinator {
+    fn default() -> Self {
+        Self::new(DelegationLimits::default())
+    }
+}
+
+/// Tool that hands a task to a sub-agent under a [`Coordinator`]'s limits
+pub struct DelegateTool {
+    agent: Agent,
+    description: String,
+    coordinator: Coordinator,
+}
+
+#[async_trait]
+impl BaseTool for DelegateTool {
+    fn name(&self) -> &str {
+        self.agent.name()
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "task": {"type": "string", "description": "Task for the sub-agent"}
+            },
+            "required": ["task"]
+        })
+    }
+
+    async fn call(&self, args: serde_json::Value) -> ToolResult<String> {
+        let task = args
+            .get("task")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| ToolError::InvalidArguments("missing 'task'".to_string()))?
+            .to_string();
+
+        let frame = self.coordinator.enter()?;
+        let run = FRAME
+            .scope(frame, self.agent.run(task))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+        Ok(run.output)
+    }
+}
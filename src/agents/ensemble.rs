@@ -0,0 +1,210 @@
+//! Multi-model ensembles for AgenticOptio.
+//!
+//! [`Ensemble`] sends the same prompt to several chat models in parallel and
+//! resolves a single answer by majority vote, a judge model, or a scoring callback.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BoxStream, ModelError, ModelResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+type ScoreFn = dyn Fn(&AIMessage) -> f64 + Send + Sync;
+
+/// How the ensemble picks a winning answer
+#[derive(Clone)]
+pub enum VoteStrategy {
+    /// Most common answer after normalizing case and whitespace; ties go to the
+    /// earliest model
+    Majority,
+    /// A judge model picks the best candidate
+    Judge(Arc<dyn BaseChatModel>),
+    /// Highest score from a callback wins
+    Scoring(Arc<ScoreFn>),
+}
+
+impl VoteStrategy {
+    pub fn scoring(score: impl Fn(&AIMessage) -> f64 + Send + Sync + 'static) -> Self {
+        VoteStrategy::Scoring(Arc::new(score))
+    }
+}
+
+impl std::fmt::Debug for VoteStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VoteStrategy::Majority => write!(f, "Majority"),
+            VoteStrategy::Judge(_) => write!(f, "Judge"),
+            VoteStrategy::Scoring(_) => write!(f, "Scoring"),
+        }
+    }
+}
+
+/// Resolved answer with all candidates for inspection
+#[derive(Debug, Clone)]
+pub struct EnsembleResult {
+    pub answer: AIMessage,
+    /// Successful answers, in model order
+    pub candidates: Vec<AIMessage>,
+    /// Index of the winning candidate
+    pub winner: usize,
+    /// Number of models that failed
+    pub failures: usize,
+}
+
+/// Parallel multi-model voting
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::agents::{Ensemble, VoteStrategy};
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let ensemble = Ensemble::new(VoteStrategy::Majority)
+///         .model(Arc::new(OllamaChat::new("llama3.2")))
+///         .model(Arc::new(OllamaChat::new("qwen2.5")))
+///         .model(Arc::new(OllamaChat::new("mistral")));
+///     let answer = ensemble.invoke(&[Message::user("Is 91 prime? Answer yes or no.")]).await?;
+///     println!("{}", answer.content);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Ensemble {
+    models: Vec<Arc<dyn BaseChatModel>>,
+    strategy: VoteStrategy,
+}
+
+impl Ensemble {
+    pub fn new(strategy: VoteStrategy) -> Self {
+        Self {
+            models: Vec::new(),
+            strategy,
+        }
+    }
+
+    pub fn model(mut self, model: Arc<dyn BaseChatModel>) -> Self {
+        self.models.push(model);
+        self
+    }
+
+    pub async fn run(&self, messages: &[Message]) -> ModelResult<EnsembleResult> {
+        let results =
+            futures::future::join_all(self.models.iter().map(|m| m.invoke(messages))).await;
+
+        let mut candidates = Vec::new();
+        let mut first_error = None;
+        let mut failures = 0;
+        for result in results {
+            match result {
+                Ok(answer) => candidates.push(answer),
+                Err(e) => {
+                    failures += 1;
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if candidates.is_empty() {
+            return Err(first_error.unwrap_or_else(|| {
+                ModelError::InvalidResponse("Ensemble has no models".to_string())
+            }));
+        }
+
+        let winner = match &self.strategy {
+            VoteStrategy::Majority => majority(&candidates),
+            VoteStrategy::Judge(judge) => judge_pick(judge.as_ref(), messages, &candidates).await?,
+            VoteStrategy::Scoring(score) => candidates
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| score(a).total_cmp(&score(b)))
+                .map(|(i, _)| i)
+                .unwrap_or(0),
+        };
+
+        Ok(EnsembleResult {
+            answer: candidates[winner].clone(),
+            candidates,
+            winner,
+            failures,
+        })
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches('.')
+        .to_lowercase()
+}
+
+fn majority(candidates: &[AIMessage]) -> usize {
+    let normalized: Vec<String> = candidates.iter().map(|c| normalize(&c.content)).collect();
+    let mut best = (0, 0);
+    for (i, answer) in normalized.iter().enumerate() {
+        let votes = normalized.iter().filter(|a| *a == answer).count();
+        if votes > best.1 {
+            best = (i, votes);
+        }
+    }
+    best.0
+}
+
+async fn judge_pick(
+    judge: &dyn BaseChatModel,
+    messages: &[Message],
+    candidates: &[AIMessage],
+) -> ModelResult<usize> {
+    let task = messages
+        .iter()
+        .map(|m| format!("{}: {}", m.role(), m.content()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let options = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!("Candidate {}:\n{}", i + 1, c.content))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let verdict = judge
+        .invoke(&[
+            Message::system(
+                "You are an impartial judge. Pick the candidate that best answers the \
+                 conversation. Reply with the candidate number only.",
+            ),
+            Message::user(format!("Conversation:\n{}\n\n{}", task, options)),
+        ])
+        .await?;
+
+    let choice = verdict
+        .content
+        .split(|c: char| !c.is_ascii_digit())
+        .find_map(|n| n.parse::<usize>().ok())
+        .filter(|n| (1..=candidates.len()).contains(n))
+        .ok_or_else(|| {
+            ModelError::InvalidResponse(format!(
+                "Judge did not pick a valid candidate: {}",
+                verdict.content
+            ))
+        })?;
+
+    Ok(choice - 1)
+}
+
+#[async_trait]
+impl BaseChatModel for Ensemble {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        Ok(self.run(messages).await?.answer)
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let answer = self.invoke(messages).await?;
+        Ok(Box::pin(futures::stream::once(async move { Ok(answer) })))
+    }
+}
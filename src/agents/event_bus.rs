@@ -0,0 +1,158 @@
+//! Inter-agent event bus for AgenticOptio.
+//!
+//! [`EventBus`] is an async publish/subscribe channel that lets agents in a squad
+//! react to each other's events instead of only reporting up a supervisor tree.
+//! The bus is generic over the event type; [`AgentEvent`] covers the common cases.
+//! Agents built with [`AgentBuilder::event_bus`](crate::agents::AgentBuilder::event_bus)
+//! publish [`AgentEvent::TaskCompleted`] when a run finishes.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+const DEFAULT_CAPACITY: usize = 256;
+
+/// Standard events emitted by agents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentEvent {
+    TaskCompleted {
+        agent: String,
+        task: String,
+        output: String,
+    },
+    ResourceDiscovered {
+        agent: String,
+        uri: String,
+        description: String,
+    },
+    Custom {
+        agent: String,
+        kind: String,
+        payload: serde_json::Value,
+    },
+}
+
+impl AgentEvent {
+    /// Name of the agent that emitted the event
+    pub fn agent(&self) -> &str {
+        match self {
+            AgentEvent::TaskCompleted { agent, .. }
+            | AgentEvent::ResourceDiscovered { agent, .. }
+            | AgentEvent::Custom { agent, .. } => agent,
+        }
+    }
+}
+
+type Filter<E> = Arc<dyn Fn(&E) -> bool + Send + Sync>;
+
+/// Async publish/subscribe bus shared by a squad of agents
+///
+/// Cloning the bus yields another handle to the same channel. Subscribers only
+/// see events published after they subscribe; slow subscribers that fall more
+/// than the channel capacity behind skip the oldest events.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::agents::{AgentEvent, EventBus};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let bus = EventBus::new();
+///     let mut discoveries =
+///         bus.subscribe_filtered(|e| matches!(e, AgentEvent::ResourceDiscovered { .. }));
+///
+///     bus.publish(AgentEvent::ResourceDiscovered {
+///         agent: "scout".to_string(),
+///         uri: "https://example.com/data.csv".to_string(),
+///         description: "Raw sales data".to_string(),
+///     });
+///
+///     let event = discoveries.recv().await.unwrap();
+///     assert_eq!(event.agent(), "scout");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventBus<E: Clone + Send + 'static = AgentEvent> {
+    sender: broadcast::Sender<E>,
+}
+
+impl<E: Clone + Send + 'static> EventBus<E> {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Create a bus buffering up to `capacity` events per subscriber
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Publish an event, returning how many subscribers will receive it
+    pub fn publish(&self, event: E) -> usize {
+        self.sender.send(event).unwrap_or(0)
+    }
+
+    pub fn subscribe(&self) -> EventSubscriber<E> {
+        EventSubscriber {
+            receiver: self.sender.subscribe(),
+            filter: None,
+        }
+    }
+
+    /// Subscribe to events matching a predicate
+    pub fn subscribe_filtered(
+        &self,
+        filter: impl Fn(&E) -> bool + Send + Sync + 'static,
+    ) -> EventSubscriber<E> {
+        EventSubscriber {
+            receiver: self.sender.subscribe(),
+            filter: Some(Arc::new(filter)),
+        }
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl<E: Clone + Send + 'static> Default for EventBus<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Receiving end of an [`EventBus`] subscription
+pub struct EventSubscriber<E: Clone + Send + 'static = AgentEvent> {
+    receiver: broadcast::Receiver<E>,
+    filter: Option<Filter<E>>,
+}
+
+impl<E: Clone + Send + 'static> EventSubscriber<E> {
+    /// Wait for the next matching event; `None` once every bus handle is dropped
+    pub async fn recv(&mut self) -> Option<E> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if self.matches(&event) => return Some(event),
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Next matching event if one is already queued
+    pub fn try_recv(&mut self) -> Option<E> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) if self.matches(&event) => return Some(event),
+                Ok(_) | Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                Err(_) => return None,
+            }
+        }
+    }
+
+    fn matches(&self, event: &E) -> bool {
+        self.filter.as_ref().map_or(true, |f| f(event))
+    }
+}
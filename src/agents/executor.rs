@@ -0,0 +1,606 @@
+//! Agent executor for AgenticOptio.
+//!
+//! Runs the tool-calling loop: invoke the model, execute any requested tools,
+//! feed the results back, and repeat until the model answers without tools.
+
+use crate::agents::budget::{Budget, BudgetLimit, BudgetTracker};
+use crate::agents::event_bus::{AgentEvent, EventBus};
+use crate::agents::loop_detection::{LoopDetection, LoopDetector};
+use crate::agents::middleware::AgentMiddleware;
+use crate::agents::transcript::{TranscriptRecorder, TranscriptReplayer};
+use crate::callbacks::{CallbackHandler, Callbacks};
+use crate::core::messages::{AIMessage, Message, ToolCall, Usage};
+use crate::core::run::RunContext;
+use crate::guardrails::{GuardedChatModel, Guardrail, GuardrailAction, InjectionScanner};
+use crate::models::base::{BaseChatModel, ModelError};
+use crate::tools::{BaseTool, ToolError, ToolRegistry};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Result given to tool calls left unanswered when the wall-clock budget runs
+/// out
+const BUDGET_CANCELLED: &str = "Cancelled: the run's time budget ran out before this tool finished";
+
+/// Error type for agent operations
+#[derive(Debug, thiserror::Error)]
+pub enum AgentError {
+    #[error("Model error: {0}")]
+    Model(#[from] ModelError),
+
+    #[error("Tool error: {0}")]
+    Tool(#[from] ToolError),
+
+    #[error("Agent exceeded {0} iterations without a final answer")]
+    MaxIterations(usize),
+
+    #[error("Replay diverged from transcript: {0}")]
+    ReplayDivergence(String),
+
+    #[error("Agent stuck in a tool-call loop: {0}")]
+    ToolLoop(String),
+
+    #[error("Rejected by middleware: {0}")]
+    Middleware(String),
+}
+
+pub type AgentResult<T> = Result<T, AgentError>;
+
+/// Why an agent run stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The model produced a final answer
+    Completed,
+    /// A budget limit was hit before the model finished
+    BudgetExceeded(BudgetLimit),
+}
+
+/// Outcome of a completed agent run
+#[derive(Debug, Clone)]
+pub struct AgentRun {
+    /// Final answer from the model, or the last partial answer if stopped early
+    pub output: String,
+    /// Full conversation including tool calls and results
+    pub messages: Vec<Message>,
+    /// Number of model calls made
+    pub iterations: usize,
+    /// Token usage summed over all model calls
+    pub usage: Usage,
+    pub stop_reason: StopReason,
+    /// Ids of this run and its place in the run tree
+    pub context: RunContext,
+}
+
+impl AgentRun {
+    /// Whether the model produced a final answer
+    pub fn is_complete(&self) -> bool {
+        self.stop_reason == StopReason::Completed
+    }
+}
+
+/// Tool-calling agent
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::agents::Agent;
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let agent = Agent::builder(Arc::new(OllamaChat::new("llama3.2")))
+///         .system_prompt("You are a helpful assistant.")
+///         .build();
+///     let run = agent.run("What is 2 + 2?").await?;
+///     println!("{}", run.output);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Agent {
+    name: String,
+    model: Arc<dyn BaseChatModel>,
+    tools: ToolRegistry,
+    system_prompt: Option<String>,
+    max_iterations: usize,
+    budget: Option<Budget>,
+    recorder: Option<TranscriptRecorder>,
+    injection_scanner: Option<InjectionScanner>,
+    loop_detection: Option<LoopDetection>,
+    middleware: Vec<Arc<dyn AgentMiddleware>>,
+    event_bus: Option<EventBus>,
+    callbacks: Callbacks,
+    metadata: BTreeMap<String, String>,
+}
+
+impl Agent {
+    /// Create a builder for configuring the agent
+    pub fn builder(model: Arc<dyn BaseChatModel>) -> AgentBuilder {
+        AgentBuilder::new(model)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn tools(&self) -> &ToolRegistry {
+        &self.tools
+    }
+
+    /// Run the agent on a user input
+    pub async fn run(&self, input: impl Into<String>) -> AgentResult<AgentRun> {
+        self.run_messages(vec![Message::user(input)]).await
+    }
+
+    /// Event bus this agent publishes to, if any
+    pub fn event_bus(&self) -> Option<&EventBus> {
+        self.event_bus.as_ref()
+    }
+
+    /// Run the agent on an existing conversation
+    pub async fn run_messages(&self, messages: Vec<Message>) -> AgentResult<AgentRun> {
+        self.execute(messages, None).await
+    }
+
+    /// Re-run the agent feeding recorded model responses and tool outputs
+    /// instead of calling the model and tools.
+    pub async fn replay(
+        &self,
+        input: impl Into<String>,
+        replayer: &TranscriptReplayer,
+    ) -> AgentResult<AgentRun> {
+        self.execute(vec![Message::user(input)], Some(replayer))
+            .await
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "agent_run",
+            skip_all,
+            err,
+            fields(
+                agent = %self.name,
+                replay = replayer.is_some(),
+                run_id = tracing::field::Empty,
+                trace_id = tracing::field::Empty,
+                iterations = tracing::field::Empty,
+                input_tokens = tracing::field::Empty,
+                output_tokens = tracing::field::Empty,
+                stop_reason = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    async fn execute(
+        &self,
+        input: Vec<Message>,
+        replayer: Option<&TranscriptReplayer>,
+    ) -> AgentResult<AgentRun> {
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+        let mut context = RunContext::new();
+        context.metadata.extend(self.metadata.clone());
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("run_id", context.run_id.as_str());
+            span.record("trace_id", context.trace_id.as_str());
+        }
+        let result = context
+            .clone()
+            .scope(async {
+                self.callbacks.on_agent_start(&self.name, &input);
+                let result = self.run_loop(input, replayer, &context).await;
+                self.callbacks.on_agent_end(&self.name, &result);
+                result
+            })
+            .await;
+        #[cfg(feature = "tracing")]
+        {
+            if let Ok(run) = &result {
+                let span = tracing::Span::current();
+                span.record("iterations", run.iterations);
+                span.record("input_tokens", run.usage.input_tokens);
+                span.record("output_tokens", run.usage.output_tokens);
+                span.record("stop_reason", tracing::field::debug(run.stop_reason));
+            }
+            crate::telemetry::spans::record_elapsed(started);
+        }
+        result
+    }
+
+    async fn run_loop(
+        &self,
+        input: Vec<Message>,
+        replayer: Option<&TranscriptReplayer>,
+        context: &RunContext,
+    ) -> AgentResult<AgentRun> {
+        let mut messages = Vec::with_capacity(input.len() + 1);
+        if let Some(prompt) = &self.system_prompt {
+            messages.push(Message::system(prompt.clone()));
+        }
+        messages.extend(input);
+
+        let schemas = self.tools.schemas();
+        let mut tracker = BudgetTracker::new(self.budget.clone().unwrap_or_default());
+        let mut loop_detector = self.loop_detection.clone().map(LoopDetector::new);
+
+        for iteration in 1..=self.max_iterations {
+            if let Some(limit) = tracker.exceeded() {
+                return Ok(Self::stopped(messages, &tracker, limit, context));
+            }
+
+            let call = self.call_model(&messages, &schemas, replayer);
+            let Some(response) = within_budget(&tracker, call).await else {
+                return Ok(Self::stopped(
+                    messages,
+                    &tracker,
+                    BudgetLimit::WallClock,
+                    context,
+                ));
+            };
+            let response = response?;
+            tracker.record(response.usage);
+            messages.push(Message::AI(response.clone()));
+
+            if response.tool_calls.is_empty() {
+                if let Some(bus) = &self.event_bus {
+                    let task = messages
+                        .iter()
+                        .find(|m| matches!(m, Message::Human(_)))
+                        .map(|m| m.content().to_string())
+                        .unwrap_or_default();
+                    bus.publish(AgentEvent::TaskCompleted {
+                        agent: self.name.clone(),
+                        task,
+                        output: response.content.clone(),
+                    });
+                }
+
+                return Ok(AgentRun {
+                    output: response.content,
+                    messages,
+                    iterations: iteration,
+                    usage: tracker.usage(),
+                    stop_reason: StopReason::Completed,
+                    context: context.clone(),
+                });
+            }
+
+            if let Some(detector) = loop_detector.as_mut() {
+                if let Some(kind) = detector.observe(&response.tool_calls) {
+                    let nudge = detector
+                        .next_nudge()
+                        .ok_or_else(|| AgentError::ToolLoop(kind.to_string()))?;
+                    for call in &response.tool_calls {
+                        messages.push(Message::tool(nudge.clone(), call.id.clone()));
+                    }
+                    continue;
+                }
+            }
+
+            for (index, call) in response.tool_calls.iter().enumerate() {
+                let execute = self.call_tool(call, replayer);
+                let Some(output) = within_budget(&tracker, execute).await else {
+                    // Answer every call so the history stays valid to replay
+                    for call in &response.tool_calls[index..] {
+                        messages.push(Message::tool(BUDGET_CANCELLED, call.id.clone()));
+                    }
+                    return Ok(Self::stopped(
+                        messages,
+                        &tracker,
+                        BudgetLimit::WallClock,
+                        context,
+                    ));
+                };
+                messages.push(Message::tool(output?, call.id.clone()));
+            }
+        }
+
+        Err(AgentError::MaxIterations(self.max_iterations))
+    }
+
+    fn stopped(
+        messages: Vec<Message>,
+        tracker: &BudgetTracker,
+        limit: BudgetLimit,
+        context: &RunContext,
+    ) -> AgentRun {
+        let output = messages
+            .iter()
+            .rev()
+            .find_map(|m| match m {
+                Message::AI(ai) if !ai.content.is_empty() => Some(ai.content.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        AgentRun {
+            output,
+            messages,
+            iterations: tracker.model_calls(),
+            usage: tracker.usage(),
+            stop_reason: StopReason::BudgetExceeded(limit),
+            context: context.clone(),
+        }
+    }
+
+    async fn call_model(
+        &self,
+        messages: &[Message],
+        schemas: &[serde_json::Value],
+        replayer: Option<&TranscriptReplayer>,
+    ) -> AgentResult<AIMessage> {
+        let mut request = messages.to_vec();
+        let mut short_circuit = None;
+        for middleware in &self.middleware {
+            short_circuit = middleware.before_model(&mut request).await?;
+            if short_circuit.is_some() {
+                break;
+            }
+        }
+
+        let mut response = match (short_circuit, replayer) {
+            (Some(response), _) => response,
+            (None, Some(replayer)) => replayer.next_model_response()?,
+            (None, None) => {
+                let model = self.model.model_name();
+                let step = async {
+                    self.callbacks.on_llm_start(model, &request);
+                    let result = self.model.invoke_with_tools(&request, schemas).await;
+                    match &result {
+                        Ok(response) => self.callbacks.on_llm_end(model, response),
+                        Err(e) => self.callbacks.on_error(e),
+                    }
+                    result
+                };
+                RunContext::new().scope(step).await?
+            }
+        };
+
+        for middleware in &self.middleware {
+            middleware.after_model(&request, &mut response).await?;
+        }
+
+        if let Some(recorder) = &self.recorder {
+            recorder.record_model_call(&request, &response);
+        }
+
+        Ok(response)
+    }
+
+    /// Execute a tool call, turning tool failures into error text for the model.
+    ///
+    /// Transcripts record the raw output; the injection scanner only affects what
+    /// the model sees.
+    async fn call_tool(
+        &self,
+        call: &ToolCall,
+        replayer: Option<&TranscriptReplayer>,
+    ) -> AgentResult<String> {
+        let mut call = call.clone();
+        let mut short_circuit = None;
+        for middleware in &self.middleware {
+            short_circuit = middleware.before_tool(&mut call).await?;
+            if short_circuit.is_some() {
+                break;
+            }
+        }
+
+        let (mut output, is_error) = match (short_circuit, replayer) {
+            (Some(output), _) => (output, false),
+            (None, Some(replayer)) => replayer.next_tool_output(&call)?,
+            (None, None) => {
+                let step = async {
+                    self.callbacks.on_tool_start(&call);
+                    match self.tools.call(&call).await {
+                        Ok(output) => {
+                            self.callbacks.on_tool_end(&call, &output);
+                            (output, false)
+                        }
+                        Err(e) => {
+                            self.callbacks.on_error(&e);
+                            (format!("Error: {}", e), true)
+                        }
+                    }
+                };
+                RunContext::new().scope(step).await
+            }
+        };
+
+        for middleware in &self.middleware {
+            middleware.after_tool(&call, &mut output).await?;
+        }
+
+        if let Some(recorder) = &self.recorder {
+            recorder.record_tool_call(&call, &output, is_error);
+        }
+
+        match &self.injection_scanner {
+            Some(scanner) => Ok(scanner.sanitize(&output)),
+            None => Ok(output),
+        }
+    }
+}
+
+/// Output of `future`, or `None` if the budget's wall-clock time runs out
+/// first
+async fn within_budget<F: std::future::Future>(
+    tracker: &BudgetTracker,
+    future: F,
+) -> Option<F::Output> {
+    match tracker.remaining_time() {
+        Some(remaining) => tokio::time::timeout(remaining, future).await.ok(),
+        None => Some(future.await),
+    }
+}
+
+impl std::fmt::Debug for Agent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Agent")
+            .field("name", &self.name)
+            .field("tools", &self.tools)
+            .field("system_prompt", &self.system_prompt)
+            .field("max_iterations", &self.max_iterations)
+            .field("budget", &self.budget)
+            .finish()
+    }
+}
+
+/// Builder for Agent
+pub struct AgentBuilder {
+    name: String,
+    model: Arc<dyn BaseChatModel>,
+    tools: ToolRegistry,
+    system_prompt: Option<String>,
+    max_iterations: usize,
+    budget: Option<Budget>,
+    recorder: Option<TranscriptRecorder>,
+    guardrails: Vec<(Arc<dyn Guardrail>, GuardrailAction)>,
+    injection_scanner: Option<InjectionScanner>,
+    loop_detection: Option<LoopDetection>,
+    middleware: Vec<Arc<dyn AgentMiddleware>>,
+    event_bus: Option<EventBus>,
+    callbacks: Callbacks,
+    metadata: BTreeMap<String, String>,
+}
+
+impl AgentBuilder {
+    pub fn new(model: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            name: "agent".to_string(),
+            model,
+            tools: ToolRegistry::new(),
+            system_prompt: None,
+            max_iterations: 10,
+            budget: None,
+            recorder: None,
+            guardrails: Vec::new(),
+            injection_scanner: None,
+            loop_detection: None,
+            middleware: Vec::new(),
+            event_bus: None,
+            callbacks: Callbacks::new(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    pub fn tool(mut self, tool: impl BaseTool + 'static) -> Self {
+        self.tools.register(tool);
+        self
+    }
+
+    /// Add every tool in a registry
+    pub fn tools(mut self, tools: ToolRegistry) -> Self {
+        self.tools.extend(tools);
+        self
+    }
+
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Limit tokens, cost, time, and model calls per run
+    pub fn budget(mut self, budget: Budget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Record every model call and tool invocation into a transcript
+    pub fn recorder(mut self, recorder: TranscriptRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Validate model output before the agent acts on it
+    pub fn guardrail(
+        mut self,
+        guardrail: impl Guardrail + 'static,
+        action: GuardrailAction,
+    ) -> Self {
+        self.guardrails.push((Arc::new(guardrail), action));
+        self
+    }
+
+    /// Scan tool outputs for prompt injection before the model sees them
+    pub fn injection_scanner(mut self, scanner: InjectionScanner) -> Self {
+        self.injection_scanner = Some(scanner);
+        self
+    }
+
+    /// Break out of repeated or oscillating tool calls
+    pub fn loop_detection(mut self, loop_detection: LoopDetection) -> Self {
+        self.loop_detection = Some(loop_detection);
+        self
+    }
+
+    /// Add hooks around model calls and tool executions
+    pub fn middleware(mut self, middleware: impl AgentMiddleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Publish `TaskCompleted` events to a shared bus
+    pub fn event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Report model calls and tool executions to a callback handler
+    ///
+    /// Only calls that actually reach the model or tool are reported, not
+    /// middleware short-circuits or replays.
+    pub fn callback(mut self, handler: impl CallbackHandler + 'static) -> Self {
+        self.callbacks.push(Arc::new(handler));
+        self
+    }
+
+    /// Tag every run with a metadata entry, such as the prompt version in use;
+    /// see [`RunContext::metadata`]
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn build(self) -> Agent {
+        let model: Arc<dyn BaseChatModel> = if self.guardrails.is_empty() {
+            self.model
+        } else {
+            Arc::new(
+                self.guardrails
+                    .into_iter()
+                    .fold(GuardedChatModel::new(self.model), |model, (g, action)| {
+                        model.guardrail_arc(g, action)
+                    }),
+            )
+        };
+
+        Agent {
+            name: self.name,
+            model,
+            tools: self.tools,
+            system_prompt: self.system_prompt,
+            max_iterations: self.max_iterations,
+            budget: self.budget,
+            recorder: self.recorder,
+            injection_scanner: self.injection_scanner,
+            loop_detection: self.loop_detection,
+            middleware: self.middleware,
+            event_bus: self.event_bus,
+            callbacks: self.callbacks,
+            metadata: self.metadata,
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Tool-call loop detection for AgenticOptio agents.
+//!
+//! Agents sometimes get stuck repeating the same tool call with the same
+//! arguments, or bouncing between two calls. [`LoopDetection`] spots both patterns
+//! and either nudges the model with a corrective tool result or stops the run.
+
+use crate::core::messages::ToolCall;
+
+const DEFAULT_NUDGE: &str = "You have already made this exact tool call repeatedly and it will \
+                             not produce anything new. Try a different approach or give your \
+                             final answer with the information you have.";
+
+/// What the executor does when a loop is detected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoopStrategy {
+    /// Answer the looping tool calls with a nudge message instead of executing
+    /// them; stop with an error after `max_nudges` nudges
+    Nudge { message: String, max_nudges: usize },
+    /// Stop the run with `AgentError::ToolLoop`
+    Stop,
+}
+
+impl LoopStrategy {
+    /// Nudge with the default message, allowing up to `max_nudges` nudges
+    pub fn nudge(max_nudges: usize) -> Self {
+        LoopStrategy::Nudge {
+            message: DEFAULT_NUDGE.to_string(),
+            max_nudges,
+        }
+    }
+}
+
+/// Loop detection settings for an agent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopDetection {
+    /// Identical consecutive tool-call turns that count as a loop
+    pub max_repeats: usize,
+    /// A-B-A-B cycles that count as oscillation; 0 disables oscillation checks
+    pub oscillation_cycles: usize,
+    pub strategy: LoopStrategy,
+}
+
+impl Default for LoopDetection {
+    fn default() -> Self {
+        Self {
+            max_repeats: 3,
+            oscillation_cycles: 2,
+            strategy: LoopStrategy::nudge(1),
+        }
+    }
+}
+
+impl LoopDetection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_repeats(mut self, max_repeats: usize) -> Self {
+        self.max_repeats = max_repeats;
+        self
+    }
+
+    pub fn oscillation_cycles(mut self, oscillation_cycles: usize) -> Self {
+        self.oscillation_cycles = oscillation_cycles;
+        self
+    }
+
+    pub fn strategy(mut self, strategy: LoopStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+}
+
+/// Kind of loop found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoopKind {
+    Repeated { signature: String, times: usize },
+    Oscillating { first: String, second: String },
+}
+
+impl std::fmt::Display for LoopKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoopKind::Repeated { signature, times } => {
+                write!(f, "{} repeated {} times", signature, times)
+            }
+            LoopKind::Oscillating { first, second } => {
+                write!(f, "oscillating between {} and {}", first, second)
+            }
+        }
+    }
+}
+
+/// Per-run tracker of tool-call history
+#[derive(Debug, Clone)]
+pub struct LoopDetector {
+    config: LoopDetection,
+    history: Vec<String>,
+    nudges: usize,
+}
+
+impl LoopDetector {
+    pub fn new(config: LoopDetection) -> Self {
+        Self {
+            config,
+            history: Vec::new(),
+            nudges: 0,
+        }
+    }
+
+    pub fn config(&self) -> &LoopDetection {
+        &self.config
+    }
+
+    /// Record one model turn's tool calls and report a loop if one has formed
+    pub fn observe(&mut self, calls: &[ToolCall]) -> Option<LoopKind> {
+        let signature = calls
+            .iter()
+            .map(|c| {
+                format!(
+                    "{}({})",
+                    c.name,
+                    serde_json::to_string(&c.args).unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.history.push(signature);
+
+        let detected = self.repeated().or_else(|| self.oscillating());
+        if detected.is_some() {
+            self.history.clear();
+        }
+        detected
+    }
+
+    /// Nudge message to send, or `None` once the run should stop
+    pub fn next_nudge(&mut self) -> Option<String> {
+        match &self.config.strategy {
+            LoopStrategy::Nudge {
+                message,
+                max_nudges,
+            } if self.nudges < *max_nudges => {
+                self.nudges += 1;
+                Some(message.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn repeated(&self) -> Option<LoopKind> {
+        let n = self.config.max_repeats;
+        if n < 2 || self.history.len() < n {
+            return None;
+        }
+
+        let tail = &self.history[self.history.len() - n..];
+        tail.iter()
+            .all(|s| *s == tail[0])
+            .then(|| LoopKind::Repeated {
+                signature: tail[0].clone(),
+                times: n,
+            })
+    }
+
+    fn oscillating(&self) -> Option<LoopKind> {
+        let window = self.config.oscillation_cycles * 2;
+        if window == 0 || self.history.len() < window {
+            return None;
+        }
+
+        let tail = &self.history[self.history.len() - window..];
+        let (first, second) = (&tail[0], &tail[1]);
+        let alternates = first != second
+            && tail
+                .iter()
+                .enumerate()
+                .all(|(i, s)| s == if i % 2 == 0 { first } else { second });
+
+        alternates.then(|| LoopKind::Oscillating {
+            first: first.clone(),
+            second: second.clone(),
+        })
+    }
+}
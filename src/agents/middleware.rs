@@ -0,0 +1,65 @@
+//! Middleware hooks for AgenticOptio agents.
+//!
+//! [`AgentMiddleware`] runs around every model call and tool execution in the
+//! agent executor. Hooks can observe (logging), mutate requests and results,
+//! short-circuit calls (caching), or reject them (policy checks) without forking
+//! the executor. Middleware runs in registration order.
+
+use crate::agents::executor::AgentResult;
+use crate::core::messages::{AIMessage, Message, ToolCall};
+use async_trait::async_trait;
+
+/// Hooks around agent model calls and tool executions
+///
+/// Every hook has a no-op default, so implementations only override what they
+/// need. Returning an error aborts the run with that error; use
+/// `AgentError::Middleware` for policy rejections.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::agents::{AgentMiddleware, AgentResult};
+/// use agentic_optio_rs::core::messages::ToolCall;
+/// use async_trait::async_trait;
+///
+/// struct LogTools;
+///
+/// #[async_trait]
+/// impl AgentMiddleware for LogTools {
+///     async fn after_tool(&self, call: &ToolCall, output: &mut String) -> AgentResult<()> {
+///         println!("{} -> {}", call.name, output);
+///         Ok(())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait AgentMiddleware: Send + Sync {
+    /// Called with the messages about to be sent to the model.
+    ///
+    /// Mutations change the request only, not the recorded conversation. Returning
+    /// `Some(response)` skips the model call and uses that response instead.
+    async fn before_model(&self, messages: &mut Vec<Message>) -> AgentResult<Option<AIMessage>> {
+        let _ = messages;
+        Ok(None)
+    }
+
+    /// Called with the model response before the agent acts on it
+    async fn after_model(&self, messages: &[Message], response: &mut AIMessage) -> AgentResult<()> {
+        let _ = (messages, response);
+        Ok(())
+    }
+
+    /// Called before a tool runs; arguments may be rewritten.
+    ///
+    /// Returning `Some(output)` skips the tool and uses that output instead.
+    async fn before_tool(&self, call: &mut ToolCall) -> AgentResult<Option<String>> {
+        let _ = call;
+        Ok(None)
+    }
+
+    /// Called with the tool output before it is sent back to the model
+    async fn after_tool(&self, call: &ToolCall, output: &mut String) -> AgentResult<()> {
+        let _ = (call, output);
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+//! Agents for AgenticOptio.
+//!
+//! This module contains the agent executor and supporting run infrastructure.
+
+pub mod blackboard;
+pub mod budget;
+pub mod debate;
+pub mod delegation;
+pub mod ensemble;
+pub mod event_bus;
+pub mod executor;
+pub mod loop_detection;
+pub mod middleware;
+pub mod persona;
+pub mod reflexion;
+pub mod scheduler;
+pub mod transcript;
+
+pub use blackboard::{Blackboard, BlackboardEntry, BlackboardError};
+pub use budget::{Budget, BudgetLimit, BudgetTracker};
+pub use debate::{Debate, DebateResult, DebateTurn};
+pub use delegation::{Coordinator, DelegateTool, DelegationError, DelegationLimits};
+pub use ensemble::{Ensemble, EnsembleResult, VoteStrategy};
+pub use event_bus::{AgentEvent, EventBus, EventSubscriber};
+pub use executor::{Agent, AgentBuilder, AgentError, AgentResult, AgentRun, StopReason};
+pub use loop_detection::{LoopDetection, LoopDetector, LoopKind, LoopStrategy};
+pub use middleware::AgentMiddleware;
+pub use persona::Persona;
+pub use reflexion::{Critique, Reflexion, ReflexionResult};
+pub use scheduler::{
+    CronSchedule, JobState, OverlapPolicy, Schedule, ScheduledJob, Scheduler, SchedulerError,
+    SchedulerHandle,
+};
+pub use transcript::{Transcript, TranscriptEvent, TranscriptRecorder, TranscriptReplayer};
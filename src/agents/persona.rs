@@ -0,0 +1,111 @@
+//! Agent personas for AgenticOptio.
+//!
+//! A [`Persona`] declares who an agent is — name, role, goals, constraints, and
+//! preferred model — as plain data. It compiles into a system prompt and a model
+//! binding, so squads can be defined in configuration rather than by string
+//! concatenation.
+
+use crate::agents::executor::AgentBuilder;
+use crate::models::base::BaseChatModel;
+#[cfg(feature = "ollama")]
+use crate::models::ollama::OllamaChat;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Declarative agent role definition
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::agents::Persona;
+///
+/// let reviewer = Persona::new("reviewer", "Senior Rust code reviewer")
+///     .goal("Find correctness bugs before style issues")
+///     .constraint("Never approve code without tests");
+/// assert!(reviewer.system_prompt().contains("Senior Rust code reviewer"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Persona {
+    pub name: String,
+    pub role: String,
+    #[serde(default)]
+    pub goals: Vec<String>,
+    #[serde(default)]
+    pub constraints: Vec<String>,
+    /// Ollama model name to bind instead of the caller's default model; needs
+    /// the `ollama` feature and is ignored without it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_model: Option<String>,
+}
+
+impl Persona {
+    pub fn new(name: impl Into<String>, role: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            role: role.into(),
+            goals: Vec::new(),
+            constraints: Vec::new(),
+            preferred_model: None,
+        }
+    }
+
+    pub fn goal(mut self, goal: impl Into<String>) -> Self {
+        self.goals.push(goal.into());
+        self
+    }
+
+    pub fn constraint(mut self, constraint: impl Into<String>) -> Self {
+        self.constraints.push(constraint.into());
+        self
+    }
+
+    pub fn preferred_model(mut self, model: impl Into<String>) -> Self {
+        self.preferred_model = Some(model.into());
+        self
+    }
+
+    /// Render the persona as a system prompt
+    pub fn system_prompt(&self) -> String {
+        let mut prompt = format!("You are {}, {}.", self.name, self.role);
+
+        if !self.goals.is_empty() {
+            prompt.push_str("\n\nYour goals:");
+            for goal in &self.goals {
+                prompt.push_str(&format!("\n- {}", goal));
+            }
+        }
+
+        if !self.constraints.is_empty() {
+            prompt.push_str("\n\nYou must follow these constraints:");
+            for constraint in &self.constraints {
+                prompt.push_str(&format!("\n- {}", constraint));
+            }
+        }
+
+        prompt
+    }
+
+    /// Model for the preferred model name, if one is set
+    #[cfg(feature = "ollama")]
+    pub fn bind_model(&self) -> Option<Arc<dyn BaseChatModel>> {
+        self.preferred_model
+            .as_ref()
+            .map(|name| Arc::new(OllamaChat::new(name.as_str())) as Arc<dyn BaseChatModel>)
+    }
+
+    /// Model for the preferred model name; always `None` without the `ollama`
+    /// feature
+    #[cfg(not(feature = "ollama"))]
+    pub fn bind_model(&self) -> Option<Arc<dyn BaseChatModel>> {
+        None
+    }
+
+    /// Agent builder configured with this persona's name, system prompt, and
+    /// model, falling back to `default_model` when no preferred model is set
+    pub fn agent(&self, default_model: Arc<dyn BaseChatModel>) -> AgentBuilder {
+        let model = self.bind_model().unwrap_or(default_model);
+        AgentBuilder::new(model)
+            .name(self.name.clone())
+            .system_prompt(self.system_prompt())
+    }
+}
@@ -0,0 +1,180 @@
+//! Self-critique loop for AgenticOptio.
+//!
+//! [`Reflexion`] generates an answer, asks a critic model to review it against a
+//! set of criteria, and regenerates with the critique as feedback until the critic
+//! approves or the round limit is reached.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BoxStream, ModelResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+const APPROVED: &str = "APPROVED";
+
+/// One critic review of a candidate answer
+#[derive(Debug, Clone)]
+pub struct Critique {
+    pub round: usize,
+    pub answer: String,
+    pub feedback: String,
+    pub approved: bool,
+}
+
+/// Final answer plus the critique trail that produced it
+#[derive(Debug, Clone)]
+pub struct ReflexionResult {
+    pub answer: AIMessage,
+    pub critiques: Vec<Critique>,
+    /// Whether the critic approved the final answer
+    pub approved: bool,
+}
+
+/// Generate-critique-revise wrapper around a chat model
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::agents::Reflexion;
+/// use agentic_optio_rs::{Message, OllamaChat};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let reflexion = Reflexion::new(Arc::new(OllamaChat::new("llama3.2")))
+///         .criterion("Cites at least one concrete example")
+///         .max_rounds(2);
+///     let result = reflexion.run(&[Message::user("Why use Rust?")]).await?;
+///     println!("{} ({} critiques)", result.answer.content, result.critiques.len());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Reflexion {
+    generator: Arc<dyn BaseChatModel>,
+    critic: Arc<dyn BaseChatModel>,
+    criteria: Vec<String>,
+    max_rounds: usize,
+}
+
+impl Reflexion {
+    /// Create a reflexion loop where the model critiques its own answers
+    pub fn new(model: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            critic: model.clone(),
+            generator: model,
+            criteria: Vec::new(),
+            max_rounds: 2,
+        }
+    }
+
+    /// Use a separate model as the critic
+    pub fn critic(mut self, critic: Arc<dyn BaseChatModel>) -> Self {
+        self.critic = critic;
+        self
+    }
+
+    pub fn criterion(mut self, criterion: impl Into<String>) -> Self {
+        self.criteria.push(criterion.into());
+        self
+    }
+
+    /// Maximum number of critique-and-revise rounds
+    pub fn max_rounds(mut self, max_rounds: usize) -> Self {
+        self.max_rounds = max_rounds;
+        self
+    }
+
+    pub async fn run(&self, messages: &[Message]) -> ModelResult<ReflexionResult> {
+        let mut conversation = messages.to_vec();
+        let mut answer = self.generator.invoke(&conversation).await?;
+        let mut critiques = Vec::new();
+
+        for round in 1..=self.max_rounds {
+            let critique = self.critique(round, messages, &answer.content).await?;
+            let approved = critique.approved;
+            let feedback = critique.feedback.clone();
+            critiques.push(critique);
+
+            if approved {
+                return Ok(ReflexionResult {
+                    answer,
+                    critiques,
+                    approved: true,
+                });
+            }
+
+            conversation.push(Message::AI(answer));
+            conversation.push(Message::user(format!(
+                "A reviewer found problems with your answer:\n{}\n\n\
+                 Write an improved answer that addresses every point.",
+                feedback
+            )));
+            answer = self.generator.invoke(&conversation).await?;
+        }
+
+        Ok(ReflexionResult {
+            answer,
+            critiques,
+            approved: false,
+        })
+    }
+
+    async fn critique(
+        &self,
+        round: usize,
+        messages: &[Message],
+        answer: &str,
+    ) -> ModelResult<Critique> {
+        let task = messages
+            .iter()
+            .rev()
+            .find(|m| matches!(m, Message::Human(_)))
+            .map(|m| m.content())
+            .unwrap_or_default();
+
+        let criteria = if self.criteria.is_empty() {
+            "- The answer is correct, complete, and directly addresses the task".to_string()
+        } else {
+            self.criteria
+                .iter()
+                .map(|c| format!("- {}", c))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let review = [
+            Message::system(format!(
+                "You are a strict reviewer. Judge the answer against these criteria:\n{}\n\n\
+                 If the answer satisfies every criterion, reply with exactly {}. \
+                 Otherwise list the specific problems.",
+                criteria, APPROVED
+            )),
+            Message::user(format!("Task:\n{}\n\nAnswer:\n{}", task, answer)),
+        ];
+
+        let feedback = self.critic.invoke(&review).await?.content;
+        let approved = feedback.trim().to_uppercase().starts_with(APPROVED);
+
+        Ok(Critique {
+            round,
+            answer: answer.to_string(),
+            feedback,
+            approved,
+        })
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for Reflexion {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        Ok(self.run(messages).await?.answer)
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let answer = self.invoke(messages).await?;
+        Ok(Box::pin(futures::stream::once(async move { Ok(answer) })))
+    }
+}
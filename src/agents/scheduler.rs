@@ -0,0 +1,468 @@
+//! Scheduled agent runs for AgenticOptio.
+//!
+//! [`Scheduler`] triggers agent runs in-process on a fixed interval or a
+//! cron-like expression, for monitoring and automation agents. Each job has an
+//! [`OverlapPolicy`] deciding what happens when a run is still going at the next
+//! tick. Per-job [`JobState`] (last run, last result, counters) can be persisted
+//! to a JSON file so restarts resume the schedule instead of starting over.
+
+use crate::agents::executor::Agent;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Error type for scheduler operations
+#[derive(Debug, thiserror::Error)]
+pub enum SchedulerError {
+    #[error("Invalid cron expression '{expr}': {reason}")]
+    InvalidCron { expr: String, reason: String },
+
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+
+    #[error("State file error: {0}")]
+    State(String),
+}
+
+/// Five-field cron expression (`minute hour day-of-month month day-of-week`),
+/// evaluated in UTC
+///
+/// Fields accept `*`, single values, ranges (`1-5`), lists (`1,15`), and steps
+/// (`*/10`, `9-17/2`). Day-of-week runs 0-7 with both 0 and 7 meaning Sunday.
+/// As in standard cron, when both day fields are restricted a day matches if
+/// either does.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::agents::CronSchedule;
+/// use chrono::{TimeZone, Utc};
+///
+/// let weekday_mornings: CronSchedule = "30 9 * * 1-5".parse().unwrap();
+/// let friday = Utc.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+/// let next = weekday_mornings.next_after(friday).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2024, 3, 18, 9, 30, 0).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expr: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expr: &str) -> Result<Self, SchedulerError> {
+        let invalid = |reason: String| SchedulerError::InvalidCron {
+            expr: expr.to_string(),
+            reason,
+        };
+
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(invalid(format!(
+                "expected 5 fields, found {}",
+                fields.len()
+            )));
+        }
+
+        let mut weekdays = parse_field(fields[4], 0, 7).map_err(invalid)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            expr: expr.to_string(),
+            minutes: parse_field(fields[0], 0, 59).map_err(invalid)?,
+            hours: parse_field(fields[1], 0, 23).map_err(invalid)?,
+            days: parse_field(fields[2], 1, 31).map_err(invalid)?,
+            months: parse_field(fields[3], 1, 12).map_err(invalid)?,
+            weekdays,
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })
+    }
+
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+
+    /// First matching minute strictly after `after`, searching up to five years
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let limit = after + ChronoDuration::days(5 * 366);
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + ChronoDuration::minutes(1);
+
+        while t <= limit {
+            if !has(self.months, t.month()) {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+            } else if !self.matches_day(&t) {
+                let midnight = t.date_naive().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                t = Utc.from_utc_datetime(&midnight);
+            } else if !has(self.hours, t.hour()) {
+                t = t.with_minute(0)? + ChronoDuration::hours(1);
+            } else if !has(self.minutes, t.minute()) {
+                t += ChronoDuration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, t: &DateTime<Utc>) -> bool {
+        let day = has(self.days, t.day());
+        let weekday = has(self.weekdays, t.weekday().num_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = SchedulerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn has(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// Parse one cron field into a bitmask of allowed values
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let number = |s: &str| {
+        s.parse::<u32>()
+            .map_err(|_| format!("'{}' is not a number", s))
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(number(step)?)),
+            None => (part, None),
+        };
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((lo, hi)) = range.split_once('-') {
+            (number(lo)?, number(hi)?)
+        } else {
+            let value = number(range)?;
+            (value, if step.is_some() { max } else { value })
+        };
+
+        if lo < min || hi > max || lo > hi {
+            return Err(format!("'{}' is outside {}-{}", part, min, max));
+        }
+        let step = step.unwrap_or(1);
+        if step == 0 {
+            return Err(format!("'{}' has a zero step", part));
+        }
+        for value in (lo..=hi).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+/// When a job fires
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    /// Fixed delay between runs
+    Interval(Duration),
+    Cron(CronSchedule),
+}
+
+impl Schedule {
+    pub fn every(interval: Duration) -> Self {
+        Schedule::Interval(interval)
+    }
+
+    pub fn cron(expr: &str) -> Result<Self, SchedulerError> {
+        CronSchedule::parse(expr).map(Schedule::Cron)
+    }
+
+    /// Next fire time after `after`
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Interval(interval) => Some(after + ChronoDuration::from_std(*interval).ok()?),
+            Schedule::Cron(cron) => cron.next_after(after),
+        }
+    }
+}
+
+/// What to do when a job fires while its previous run is still going
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Drop the new run
+    #[default]
+    Skip,
+    /// Start the new run once the previous one finishes
+    Queue,
+    /// Start the new run alongside the previous one
+    Concurrent,
+}
+
+/// Persisted state of a scheduled job
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobState {
+    /// When the most recent run started
+    pub last_run: Option<DateTime<Utc>>,
+    pub last_output: Option<String>,
+    pub last_error: Option<String>,
+    pub runs: u64,
+    /// Ticks dropped by [`OverlapPolicy::Skip`]
+    pub skipped: u64,
+}
+
+/// Agent run triggered on a schedule
+#[derive(Clone)]
+pub struct ScheduledJob {
+    name: String,
+    schedule: Schedule,
+    agent: Agent,
+    input: String,
+    overlap: OverlapPolicy,
+}
+
+impl ScheduledJob {
+    pub fn new(
+        name: impl Into<String>,
+        schedule: Schedule,
+        agent: Agent,
+        input: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            schedule,
+            agent,
+            input: input.into(),
+            overlap: OverlapPolicy::default(),
+        }
+    }
+
+    pub fn overlap(mut self, overlap: OverlapPolicy) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// In-process scheduler for recurring agent runs
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::agents::{Agent, OverlapPolicy, Schedule, ScheduledJob, Scheduler};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let agent = Agent::builder(Arc::new(OllamaChat::new("llama3.2"))).build();
+///     let schedule = Schedule::cron("0 8 * * *")?;
+///     let digest = ScheduledJob::new("digest", schedule, agent, "Summarize overnight alerts")
+///         .overlap(OverlapPolicy::Skip);
+///     let handle = Scheduler::new()
+///         .job(digest)
+///         .state_file("scheduler-state.json")
+///         .start()?;
+///
+///     tokio::signal::ctrl_c().await?;
+///     handle.shutdown().await;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Scheduler {
+    jobs: Vec<ScheduledJob>,
+    state_file: Option<PathBuf>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn job(mut self, job: ScheduledJob) -> Self {
+        self.jobs.push(job);
+        self
+    }
+
+    /// Persist job state to a JSON file, loading it on start if present
+    pub fn state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_file = Some(path.into());
+        self
+    }
+
+    /// Spawn every job on the current Tokio runtime.
+    ///
+    /// Jobs stop when the returned handle is shut down or dropped.
+    pub fn start(self) -> Result<SchedulerHandle, SchedulerError> {
+        for job in &self.jobs {
+            if matches!(job.schedule, Schedule::Interval(d) if d.is_zero()) {
+                return Err(SchedulerError::InvalidSchedule(format!(
+                    "job '{}' has a zero interval",
+                    job.name
+                )));
+            }
+        }
+
+        let state = match &self.state_file {
+            Some(path) if path.exists() => {
+                let json = std::fs::read_to_string(path)
+                    .map_err(|e| SchedulerError::State(e.to_string()))?;
+                serde_json::from_str(&json).map_err(|e| SchedulerError::State(e.to_string()))?
+            }
+            _ => BTreeMap::new(),
+        };
+        let shared = Arc::new(SharedState {
+            jobs: Mutex::new(state),
+            path: self.state_file,
+        });
+
+        let (shutdown, receiver) = watch::channel(false);
+        let tasks = self
+            .jobs
+            .into_iter()
+            .map(|job| tokio::spawn(run_job(Arc::new(job), shared.clone(), receiver.clone())))
+            .collect();
+
+        Ok(SchedulerHandle {
+            shared,
+            shutdown,
+            tasks,
+        })
+    }
+}
+
+/// Handle to a running [`Scheduler`]
+pub struct SchedulerHandle {
+    shared: Arc<SharedState>,
+    shutdown: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl SchedulerHandle {
+    pub fn state(&self, job: &str) -> Option<JobState> {
+        self.shared.jobs.lock().unwrap().get(job).cloned()
+    }
+
+    /// State of every job that has run at least once
+    pub fn states(&self) -> BTreeMap<String, JobState> {
+        self.shared.jobs.lock().unwrap().clone()
+    }
+
+    /// Stop scheduling new runs; runs already in flight are left to finish
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        for task in self.tasks {
+            let _ = task.await;
+        }
+    }
+}
+
+struct SharedState {
+    jobs: Mutex<BTreeMap<String, JobState>>,
+    path: Option<PathBuf>,
+}
+
+impl SharedState {
+    fn update(&self, job: &str, f: impl FnOnce(&mut JobState)) {
+        let mut jobs = self.jobs.lock().unwrap();
+        f(jobs.entry(job.to_string()).or_default());
+        if let Some(path) = &self.path {
+            // Persistence is best effort; a failed write must not stop the schedule.
+            if let Ok(json) = serde_json::to_string_pretty(&*jobs) {
+                let _ = std::fs::write(path, json);
+            }
+        }
+    }
+}
+
+async fn run_job(
+    job: Arc<ScheduledJob>,
+    shared: Arc<SharedState>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let running = Arc::new(AtomicBool::new(false));
+    let mut last = shared
+        .jobs
+        .lock()
+        .unwrap()
+        .get(&job.name)
+        .and_then(|s| s.last_run);
+
+    loop {
+        let now = Utc::now();
+        let Some(next) = job.schedule.next_after(last.unwrap_or(now)) else {
+            return;
+        };
+        let wait = (next - now).to_std().unwrap_or_default();
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = shutdown.changed() => return,
+        }
+
+        // A missed tick (e.g. from before a restart) fires once, now.
+        let fired = next.max(now);
+        last = Some(fired);
+
+        match job.overlap {
+            OverlapPolicy::Queue => execute(&job, &shared, fired).await,
+            OverlapPolicy::Skip if running.swap(true, Ordering::SeqCst) => {
+                shared.update(&job.name, |s| s.skipped += 1);
+            }
+            OverlapPolicy::Skip => {
+                let (job, shared, running) = (job.clone(), shared.clone(), running.clone());
+                tokio::spawn(async move {
+                    execute(&job, &shared, fired).await;
+                    running.store(false, Ordering::SeqCst);
+                });
+            }
+            OverlapPolicy::Concurrent => {
+                let (job, shared) = (job.clone(), shared.clone());
+                tokio::spawn(async move { execute(&job, &shared, fired).await });
+            }
+        }
+    }
+}
+
+async fn execute(job: &ScheduledJob, shared: &SharedState, fired: DateTime<Utc>) {
+    shared.update(&job.name, |s| {
+        s.last_run = Some(fired);
+        s.runs += 1;
+    });
+
+    let result = job.agent.run(job.input.clone()).await;
+    shared.update(&job.name, |s| match result {
+        Ok(run) => {
+            s.last_output = Some(run.output);
+            s.last_error = None;
+        }
+        Err(e) => s.last_error = Some(e.to_string()),
+    });
+}
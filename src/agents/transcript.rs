@@ -0,0 +1,163 @@
+//! Run transcripts for AgenticOptio agents.
+//!
+//! A transcript captures every model request/response and tool invocation of an
+//! agent run. Transcripts serialize to JSON so failing runs can be saved and
+//! replayed offline with [`Agent::replay`](crate::agents::Agent::replay).
+
+use crate::agents::executor::{AgentError, AgentResult};
+use crate::core::messages::{AIMessage, Message, ToolCall};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Single recorded step of an agent run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TranscriptEvent {
+    ModelCall {
+        request: Vec<Message>,
+        response: AIMessage,
+    },
+    ToolCall {
+        call: ToolCall,
+        output: String,
+        #[serde(default)]
+        is_error: bool,
+    },
+}
+
+/// Ordered record of an agent run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Transcript {
+    pub events: Vec<TranscriptEvent>,
+}
+
+impl Transcript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Write the transcript as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json()?)
+    }
+
+    /// Read a transcript previously written with [`Transcript::save`]
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(Self::from_json(&json)?)
+    }
+}
+
+/// Shared handle that records events while an agent runs
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptRecorder {
+    transcript: Arc<Mutex<Transcript>>,
+}
+
+impl TranscriptRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_model_call(&self, request: &[Message], response: &AIMessage) {
+        self.push(TranscriptEvent::ModelCall {
+            request: request.to_vec(),
+            response: response.clone(),
+        });
+    }
+
+    pub fn record_tool_call(&self, call: &ToolCall, output: &str, is_error: bool) {
+        self.push(TranscriptEvent::ToolCall {
+            call: call.clone(),
+            output: output.to_string(),
+            is_error,
+        });
+    }
+
+    /// Copy of everything recorded so far
+    pub fn transcript(&self) -> Transcript {
+        self.transcript.lock().unwrap().clone()
+    }
+
+    /// Discard recorded events
+    pub fn clear(&self) {
+        self.transcript.lock().unwrap().events.clear();
+    }
+
+    fn push(&self, event: TranscriptEvent) {
+        self.transcript.lock().unwrap().events.push(event);
+    }
+}
+
+/// Feeds recorded responses back to an agent in order
+#[derive(Debug)]
+pub struct TranscriptReplayer {
+    events: Mutex<VecDeque<TranscriptEvent>>,
+}
+
+impl TranscriptReplayer {
+    pub fn new(transcript: Transcript) -> Self {
+        Self {
+            events: Mutex::new(transcript.events.into()),
+        }
+    }
+
+    /// Number of events not yet replayed
+    pub fn remaining(&self) -> usize {
+        self.events.lock().unwrap().len()
+    }
+
+    pub(crate) fn next_model_response(&self) -> AgentResult<AIMessage> {
+        match self.events.lock().unwrap().pop_front() {
+            Some(TranscriptEvent::ModelCall { response, .. }) => Ok(response),
+            Some(TranscriptEvent::ToolCall { call, .. }) => Err(AgentError::ReplayDivergence(
+                format!("expected a model call, found tool call '{}'", call.name),
+            )),
+            None => Err(AgentError::ReplayDivergence(
+                "transcript exhausted before model call".to_string(),
+            )),
+        }
+    }
+
+    pub(crate) fn next_tool_output(&self, expected: &ToolCall) -> AgentResult<(String, bool)> {
+        match self.events.lock().unwrap().pop_front() {
+            Some(TranscriptEvent::ToolCall {
+                call,
+                output,
+                is_error,
+            }) => {
+                if call.name != expected.name {
+                    return Err(AgentError::ReplayDivergence(format!(
+                        "expected tool call '{}', found '{}'",
+                        expected.name, call.name
+                    )));
+                }
+                Ok((output, is_error))
+            }
+            Some(TranscriptEvent::ModelCall { .. }) => Err(AgentError::ReplayDivergence(format!(
+                "expected tool call '{}', found a model call",
+                expected.name
+            ))),
+            None => Err(AgentError::ReplayDivergence(format!(
+                "transcript exhausted before tool call '{}'",
+                expected.name
+            ))),
+        }
+    }
+}
+
+impl From<Transcript> for TranscriptReplayer {
+    fn from(transcript: Transcript) -> Self {
+        Self::new(transcript)
+    }
+}
@@ -0,0 +1,115 @@
+//! In-memory LRU cache.
+
+use crate::cache::{Cache, CacheResult};
+use crate::core::messages::AIMessage;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+/// Bounded in-memory cache evicting the least recently used entry
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::cache::{Cache, LruCache};
+/// use agentic_optio_rs::AIMessage;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let cache = LruCache::new(2);
+///     cache.put("a", &AIMessage::new("first")).await?;
+///     cache.put("b", &AIMessage::new("second")).await?;
+///     cache.get("a").await?;
+///     cache.put("c", &AIMessage::new("third")).await?;
+///
+///     // "b" was the least recently used
+///     assert!(cache.get("b").await?.is_none());
+///     assert_eq!(cache.len(), 2);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct LruCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+/// Entries with their last use, and keys ordered by last use
+#[derive(Debug, Default)]
+struct Entries {
+    values: HashMap<String, (AIMessage, u64)>,
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl Entries {
+    fn touch(&mut self, key: &str) -> Option<&AIMessage> {
+        self.clock += 1;
+        let (_, used) = self.values.get_mut(key)?;
+        self.recency.remove(used);
+        *used = self.clock;
+        self.recency.insert(self.clock, key.to_string());
+        self.values.get(key).map(|(value, _)| value)
+    }
+}
+
+impl LruCache {
+    /// Cache holding at most `capacity` responses
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl Cache for LruCache {
+    async fn get(&self, key: &str) -> CacheResult<Option<AIMessage>> {
+        Ok(self.entries.lock().unwrap().touch(key).cloned())
+    }
+
+    async fn put(&self, key: &str, response: &AIMessage) -> CacheResult<()> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.clock += 1;
+        let clock = entries.clock;
+        if let Some((_, used)) = entries
+            .values
+            .insert(key.to_string(), (response.clone(), clock))
+        {
+            entries.recency.remove(&used);
+        }
+        entries.recency.insert(clock, key.to_string());
+
+        while entries.values.len() > self.capacity {
+            let Some((_, oldest)) = entries.recency.pop_first() else {
+                break;
+            };
+            entries.values.remove(&oldest);
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> CacheResult<()> {
+        let mut entries = self.entries.lock().unwrap();
+        if let Some((_, used)) = entries.values.remove(key) {
+            entries.recency.remove(&used);
+        }
+        Ok(())
+    }
+
+    async fn clear(&self) -> CacheResult<()> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.values.clear();
+        entries.recency.clear();
+        Ok(())
+    }
+}
@@ -0,0 +1,270 @@
+//! Response caching for AgenticOptio.
+//!
+//! A [`Cache`] stores model responses under a key derived from the model, the
+//! request messages, and any options that change the output, so identical
+//! prompts (common in evals and retries) return instantly without calling the
+//! backend. Wrap any chat model with [`CachedChatModel`] to use one, or with
+//! a [`SemanticCache`] to also answer prompts that are merely similar.
+
+pub mod memory;
+#[cfg(feature = "redis")]
+pub mod redis;
+pub mod semantic;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use memory::LruCache;
+#[cfg(feature = "redis")]
+pub use redis::{RedisCache, RedisCacheBuilder};
+pub use semantic::SemanticCache;
+#[cfg(feature = "sqlite")]
+pub use sqlite::{SqliteCache, SqliteCacheBuilder};
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BoxStream, ModelResult};
+use crate::vectorstores::VectorStoreError;
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Arc;
+
+/// Error type for cache operations
+#[derive(Debug, thiserror::Error)]
+pub enum CacheError {
+    #[error("Cache backend error: {0}")]
+    Backend(String),
+
+    #[error("Serialization failed: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Vector store error: {0}")]
+    VectorStore(#[from] VectorStoreError),
+}
+
+pub type CacheResult<T> = Result<T, CacheError>;
+
+/// Key-value store for model responses
+#[async_trait]
+pub trait Cache: Send + Sync {
+    async fn get(&self, key: &str) -> CacheResult<Option<AIMessage>>;
+
+    async fn put(&self, key: &str, response: &AIMessage) -> CacheResult<()>;
+
+    async fn remove(&self, key: &str) -> CacheResult<()>;
+
+    async fn clear(&self) -> CacheResult<()>;
+}
+
+/// Stable cache key for a request
+///
+/// Hashes the model, provider, messages in chat API format, and `options`
+/// (tool schemas, sampling parameters, a namespace) with 128-bit FNV-1a, so the
+/// key is the same across processes and Rust versions.
+pub fn cache_key(
+    model: &str,
+    provider: &str,
+    messages: &[Message],
+    options: &serde_json::Value,
+) -> String {
+    let request = serde_json::json!({
+        "model": model,
+        "provider": provider,
+        "messages": crate::core::messages::messages_to_dict(messages),
+        "options": options,
+    });
+    format!("{:032x}", fnv1a_128(request.to_string().as_bytes()))
+}
+
+pub(crate) fn fnv1a_128(bytes: &[u8]) -> u128 {
+    const OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+    bytes.iter().fold(OFFSET, |hash, &byte| {
+        (hash ^ byte as u128).wrapping_mul(PRIME)
+    })
+}
+
+/// Chat model wrapper serving repeated requests from a cache
+///
+/// Cache failures never fail a call: a failed lookup counts as a miss and a
+/// failed store is ignored. Streams are cached once they finish, and a cached
+/// response streams back as a single chunk.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::cache::{CachedChatModel, LruCache};
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let llm = CachedChatModel::new(
+///         Arc::new(OllamaChat::new("llama3.2")),
+///         Arc::new(LruCache::new(1000)),
+///     );
+///
+///     let messages = [Message::user("What is the capital of France?")];
+///     llm.invoke(&messages).await?;
+///     // Answered from the cache
+///     llm.invoke(&messages).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CachedChatModel {
+    inner: Arc<dyn BaseChatModel>,
+    backend: Backend,
+    options: serde_json::Value,
+    namespace: String,
+}
+
+#[derive(Clone)]
+enum Backend {
+    Exact(Arc<dyn Cache>),
+    Semantic(Arc<SemanticCache>),
+}
+
+impl CachedChatModel {
+    /// Serve requests identical to earlier ones from `cache`
+    pub fn new(inner: Arc<dyn BaseChatModel>, cache: Arc<dyn Cache>) -> Self {
+        Self::with_backend(inner, Backend::Exact(cache))
+    }
+
+    /// Serve requests whose conversation is similar to an earlier one from
+    /// `cache`; the model, options, and tools must still match exactly
+    pub fn semantic(inner: Arc<dyn BaseChatModel>, cache: Arc<SemanticCache>) -> Self {
+        Self::with_backend(inner, Backend::Semantic(cache))
+    }
+
+    fn with_backend(inner: Arc<dyn BaseChatModel>, backend: Backend) -> Self {
+        Self {
+            inner,
+            backend,
+            options: serde_json::Value::Null,
+            namespace: String::new(),
+        }
+    }
+
+    /// Include settings of the inner model that change its output (temperature,
+    /// system configuration) in the cache key
+    pub fn options(mut self, options: serde_json::Value) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Keep this model's entries apart from those of models sharing the cache
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    fn key(&self, messages: &[Message], tools: &[serde_json::Value]) -> String {
+        let options = serde_json::json!({
+            "namespace": self.namespace,
+            "options": self.options,
+            "tools": tools,
+        });
+        cache_key(
+            self.inner.model_name(),
+            self.inner.provider_name(),
+            messages,
+            &options,
+        )
+    }
+
+    /// Semantic cache namespace and the conversation text to embed
+    fn semantic_key(&self, messages: &[Message], tools: &[serde_json::Value]) -> (String, String) {
+        let namespace = format!("{}/{}", self.namespace, self.key(&[], tools));
+        let prompt = messages
+            .iter()
+            .map(|m| format!("{}: {}", m.role(), m.content()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        (namespace, prompt)
+    }
+
+    async fn lookup(&self, messages: &[Message], tools: &[serde_json::Value]) -> Option<AIMessage> {
+        match &self.backend {
+            Backend::Exact(cache) => cache.get(&self.key(messages, tools)).await.ok().flatten(),
+            Backend::Semantic(cache) => {
+                let (namespace, prompt) = self.semantic_key(messages, tools);
+                let hit = cache.lookup(&namespace, &prompt).await.ok().flatten();
+                hit.map(|(response, _)| response)
+            }
+        }
+    }
+
+    async fn store(&self, messages: &[Message], tools: &[serde_json::Value], response: &AIMessage) {
+        let _ = match &self.backend {
+            Backend::Exact(cache) => cache.put(&self.key(messages, tools), response).await,
+            Backend::Semantic(cache) => {
+                let (namespace, prompt) = self.semantic_key(messages, tools);
+                cache.update(&namespace, &prompt, response).await
+            }
+        };
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for CachedChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        if let Some(response) = self.lookup(messages, &[]).await {
+            return Ok(response);
+        }
+        let response = self.inner.invoke(messages).await?;
+        self.store(messages, &[], &response).await;
+        Ok(response)
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        if let Some(response) = self.lookup(messages, tools).await {
+            return Ok(response);
+        }
+        let response = self.inner.invoke_with_tools(messages, tools).await?;
+        self.store(messages, tools, &response).await;
+        Ok(response)
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        if let Some(response) = self.lookup(messages, &[]).await {
+            return Ok(Box::pin(futures::stream::once(async { Ok(response) })));
+        }
+
+        let inner = self.inner.stream(messages).await?;
+        let stream =
+            futures::stream::unfold(Some((inner, String::new())), move |state| async move {
+                let (mut inner, mut content) = state?;
+                match inner.next().await {
+                    Some(Ok(chunk)) => {
+                        content.push_str(&chunk.content);
+                        Some((Ok(chunk), Some((inner, content))))
+                    }
+                    // A failed stream is not cached
+                    Some(Err(e)) => Some((Err(e), None)),
+                    None => {
+                        self.store(messages, &[], &AIMessage::new(content)).await;
+                        None
+                    }
+                }
+            });
+        Ok(Box::pin(stream))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
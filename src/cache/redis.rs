@@ -0,0 +1,188 @@
+//! Redis response cache.
+//!
+//! Lets several worker processes share one completion cache in production
+//! deployments. Enabled with the `redis` feature.
+
+use crate::cache::{Cache, CacheError, CacheResult};
+use crate::core::messages::AIMessage;
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::time::Duration;
+
+/// Response cache stored in Redis
+///
+/// Each response is a JSON string under `prefix` followed by the cache key, so
+/// several caches can share a database. Size limits are left to the server's
+/// `maxmemory` eviction policy.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::cache::{CachedChatModel, RedisCache};
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let cache = RedisCache::connect("redis://127.0.0.1/")
+///         .await?
+///         .ttl(Duration::from_secs(60 * 60))
+///         .build();
+///     let llm = CachedChatModel::new(Arc::new(OllamaChat::new("llama3.2")), Arc::new(cache));
+///
+///     llm.invoke(&[Message::user("Classify this ticket")]).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RedisCache {
+    connection: ConnectionManager,
+    prefix: String,
+    ttl: Option<Duration>,
+}
+
+impl RedisCache {
+    pub fn builder(connection: ConnectionManager) -> RedisCacheBuilder {
+        RedisCacheBuilder::new(connection)
+    }
+
+    /// Connect to the server at `url` and start a builder
+    pub async fn connect(url: &str) -> CacheResult<RedisCacheBuilder> {
+        let client = redis::Client::open(url).map_err(backend_error)?;
+        let connection = ConnectionManager::new(client)
+            .await
+            .map_err(backend_error)?;
+        Ok(RedisCacheBuilder::new(connection))
+    }
+
+    pub fn connection(&self) -> &ConnectionManager {
+        &self.connection
+    }
+
+    fn redis_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+#[async_trait]
+impl Cache for RedisCache {
+    async fn get(&self, key: &str) -> CacheResult<Option<AIMessage>> {
+        let mut connection = self.connection.clone();
+        let response: Option<String> = connection
+            .get(self.redis_key(key))
+            .await
+            .map_err(backend_error)?;
+        response
+            .map(|response| serde_json::from_str(&response))
+            .transpose()
+            .map_err(CacheError::from)
+    }
+
+    async fn put(&self, key: &str, response: &AIMessage) -> CacheResult<()> {
+        let mut connection = self.connection.clone();
+        let key = self.redis_key(key);
+        let response = serde_json::to_string(response)?;
+        match self.ttl {
+            Some(ttl) => connection
+                .pset_ex(key, response, (ttl.as_millis() as u64).max(1))
+                .await
+                .map_err(backend_error),
+            None => connection.set(key, response).await.map_err(backend_error),
+        }
+    }
+
+    async fn remove(&self, key: &str) -> CacheResult<()> {
+        let mut connection = self.connection.clone();
+        connection
+            .del(self.redis_key(key))
+            .await
+            .map_err(backend_error)
+    }
+
+    /// Delete every key under this cache's prefix
+    async fn clear(&self) -> CacheResult<()> {
+        let mut scan = self.connection.clone();
+        let mut keys: Vec<String> = Vec::new();
+        let mut iter = scan
+            .scan_match::<_, String>(format!("{}*", escape_glob(&self.prefix)))
+            .await
+            .map_err(backend_error)?;
+        while let Some(key) = iter.next_item().await {
+            keys.push(key);
+        }
+        drop(iter);
+
+        let mut connection = self.connection.clone();
+        for batch in keys.chunks(500) {
+            connection
+                .del::<_, ()>(batch)
+                .await
+                .map_err(backend_error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for RedisCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisCache")
+            .field("prefix", &self.prefix)
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Escape glob metacharacters so a prefix matches literally in `SCAN MATCH`
+pub(crate) fn escape_glob(prefix: &str) -> String {
+    let mut escaped = String::with_capacity(prefix.len());
+    for c in prefix.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn backend_error(err: redis::RedisError) -> CacheError {
+    CacheError::Backend(err.to_string())
+}
+
+/// Builder for RedisCache
+pub struct RedisCacheBuilder {
+    connection: ConnectionManager,
+    prefix: String,
+    ttl: Option<Duration>,
+}
+
+impl RedisCacheBuilder {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self {
+            connection,
+            prefix: "agentic_optio:cache:".to_string(),
+            ttl: None,
+        }
+    }
+
+    /// Namespace prepended to every key (default: `agentic_optio:cache:`)
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Expire entries this long after they are stored (default: never)
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn build(self) -> RedisCache {
+        RedisCache {
+            connection: self.connection,
+            prefix: self.prefix,
+            ttl: self.ttl,
+        }
+    }
+}
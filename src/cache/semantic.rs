@@ -0,0 +1,117 @@
+//! Semantic response cache.
+
+use crate::cache::{fnv1a_128, CacheResult};
+use crate::core::documents::Document;
+use crate::core::messages::AIMessage;
+use crate::models::base::BaseEmbedding;
+use crate::vectorstores::{InMemoryVectorStore, MetadataFilter, VectorStore};
+use std::sync::Arc;
+
+/// Cache returning the response to an earlier prompt similar enough to a new one
+///
+/// Prompts are embedded and kept in a [`VectorStore`], with their responses in
+/// document metadata; persistent stores make the cache survive restarts.
+/// Entries belong to a namespace, and lookups only match prompts in the same
+/// namespace.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::cache::{CachedChatModel, SemanticCache};
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat, OllamaEmbedding};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let cache = SemanticCache::in_memory(Arc::new(OllamaEmbedding::new("nomic-embed-text")))
+///         .threshold(0.92);
+///     let llm = CachedChatModel::semantic(Arc::new(OllamaChat::new("llama3.2")), Arc::new(cache))
+///         .namespace("support-bot");
+///
+///     llm.invoke(&[Message::user("How do I reset my password?")]).await?;
+///     // Close enough to be answered from the cache
+///     llm.invoke(&[Message::user("How can I reset my password?")]).await?;
+///     Ok(())
+/// }
+/// ```
+pub struct SemanticCache {
+    store: Arc<dyn VectorStore>,
+    threshold: f32,
+}
+
+impl SemanticCache {
+    /// Cache over `store`, matching prompts with a similarity of at least 0.95
+    pub fn new(store: Arc<dyn VectorStore>) -> Self {
+        Self {
+            store,
+            threshold: 0.95,
+        }
+    }
+
+    /// Cache kept in memory, embedding prompts with `embeddings`
+    pub fn in_memory(embeddings: Arc<dyn BaseEmbedding>) -> Self {
+        Self::new(Arc::new(InMemoryVectorStore::new(embeddings)))
+    }
+
+    /// Minimum similarity for a cached prompt to match
+    pub fn threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn store(&self) -> &Arc<dyn VectorStore> {
+        &self.store
+    }
+
+    /// Response to the most similar prompt in `namespace`, with its similarity,
+    /// if it reaches the threshold
+    pub async fn lookup(
+        &self,
+        namespace: &str,
+        prompt: &str,
+    ) -> CacheResult<Option<(AIMessage, f32)>> {
+        let filter = MetadataFilter::new().eq("cache_namespace", namespace);
+        let hits = self
+            .store
+            .similarity_search_with_filter(prompt, 1, &filter)
+            .await?;
+        let Some((document, score)) = hits.into_iter().next() else {
+            return Ok(None);
+        };
+        if score < self.threshold {
+            return Ok(None);
+        }
+        match document.metadata.get("cache_response") {
+            Some(response) => Ok(Some((serde_json::from_value(response.clone())?, score))),
+            None => Ok(None),
+        }
+    }
+
+    /// Cache `response` for `prompt` in `namespace`, replacing any response to
+    /// the same prompt
+    pub async fn update(
+        &self,
+        namespace: &str,
+        prompt: &str,
+        response: &AIMessage,
+    ) -> CacheResult<()> {
+        let id = format!(
+            "{:032x}",
+            fnv1a_128(format!("{}\0{}", namespace, prompt).as_bytes())
+        );
+        let document = Document::new(prompt)
+            .with_id(id)
+            .with_metadata("cache_namespace", namespace)
+            .with_metadata("cache_response", serde_json::to_value(response)?);
+        self.store.add_documents(vec![document]).await?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for SemanticCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SemanticCache")
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
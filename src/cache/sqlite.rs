@@ -0,0 +1,284 @@
+//! SQLite response cache.
+//!
+//! Cached completions persist in a single database file and survive restarts,
+//! which saves repeated calls to paid APIs during iterative development.
+//! Enabled with the `sqlite` feature.
+
+use crate::cache::{Cache, CacheError, CacheResult};
+use crate::core::messages::AIMessage;
+use crate::vectorstores::is_sql_identifier;
+use async_trait::async_trait;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Response cache persisted in a SQLite database file
+///
+/// Entries can expire after a time-to-live, and the cache can be capped at a
+/// number of entries, evicting the least recently used ones.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::cache::{CachedChatModel, SqliteCache};
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let cache = SqliteCache::open("responses.db")
+///         .await?
+///         .ttl(Duration::from_secs(24 * 60 * 60))
+///         .max_entries(10_000)
+///         .build();
+///     let llm = CachedChatModel::new(Arc::new(OllamaChat::new("llama3.2")), Arc::new(cache));
+///
+///     llm.invoke(&[Message::user("Summarize the design doc")]).await?;
+///     Ok(())
+/// }
+/// ```
+pub struct SqliteCache {
+    pool: SqlitePool,
+    table: String,
+    ttl: Option<Duration>,
+    max_entries: Option<usize>,
+    table_ready: AtomicBool,
+}
+
+impl SqliteCache {
+    pub fn builder(pool: SqlitePool) -> SqliteCacheBuilder {
+        SqliteCacheBuilder::new(pool)
+    }
+
+    /// Open (creating if needed) the database file at `path` and start a builder
+    pub async fn open(path: impl AsRef<Path>) -> CacheResult<SqliteCacheBuilder> {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .connect_with(options)
+            .await
+            .map_err(backend_error)?;
+        Ok(SqliteCacheBuilder::new(pool))
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Number of stored entries, including expired ones not yet purged
+    pub async fn len(&self) -> CacheResult<usize> {
+        self.ensure_table().await?;
+        let count: i64 = sqlx::query(&format!("SELECT COUNT(*) FROM {}", self.table))
+            .fetch_one(&self.pool)
+            .await
+            .and_then(|row| row.try_get(0))
+            .map_err(backend_error)?;
+        Ok(count as usize)
+    }
+
+    pub async fn is_empty(&self) -> CacheResult<bool> {
+        Ok(self.len().await? == 0)
+    }
+
+    /// Delete expired entries, returning how many were removed
+    pub async fn purge_expired(&self) -> CacheResult<u64> {
+        self.ensure_table().await?;
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE expires_at IS NOT NULL AND expires_at <= ?",
+            self.table
+        ))
+        .bind(now_millis())
+        .execute(&self.pool)
+        .await
+        .map_err(backend_error)?;
+        Ok(result.rows_affected())
+    }
+
+    async fn ensure_table(&self) -> CacheResult<()> {
+        if self.table_ready.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (\
+             key TEXT PRIMARY KEY, \
+             response TEXT NOT NULL, \
+             expires_at INTEGER, \
+             accessed_at INTEGER NOT NULL)",
+            self.table
+        ))
+        .execute(&self.pool)
+        .await
+        .map_err(backend_error)?;
+        self.table_ready.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Drop the least recently used entries beyond `max_entries`
+    async fn evict(&self) -> CacheResult<()> {
+        let Some(max_entries) = self.max_entries else {
+            return Ok(());
+        };
+        sqlx::query(&format!(
+            "DELETE FROM {table} WHERE key IN \
+             (SELECT key FROM {table} ORDER BY accessed_at DESC LIMIT -1 OFFSET ?)",
+            table = self.table
+        ))
+        .bind(max_entries as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(backend_error)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Cache for SqliteCache {
+    async fn get(&self, key: &str) -> CacheResult<Option<AIMessage>> {
+        self.ensure_table().await?;
+        let now = now_millis();
+        let row = sqlx::query(&format!(
+            "SELECT response, expires_at FROM {} WHERE key = ?",
+            self.table
+        ))
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(backend_error)?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let expires_at: Option<i64> = row.try_get("expires_at").map_err(backend_error)?;
+        if expires_at.is_some_and(|expires_at| expires_at <= now) {
+            self.remove(key).await?;
+            return Ok(None);
+        }
+
+        sqlx::query(&format!(
+            "UPDATE {} SET accessed_at = ? WHERE key = ?",
+            self.table
+        ))
+        .bind(now)
+        .bind(key)
+        .execute(&self.pool)
+        .await
+        .map_err(backend_error)?;
+
+        let response: String = row.try_get("response").map_err(backend_error)?;
+        Ok(Some(serde_json::from_str(&response)?))
+    }
+
+    async fn put(&self, key: &str, response: &AIMessage) -> CacheResult<()> {
+        self.ensure_table().await?;
+        let now = now_millis();
+        let expires_at = self.ttl.map(|ttl| now + ttl.as_millis() as i64);
+        sqlx::query(&format!(
+            "INSERT OR REPLACE INTO {} (key, response, expires_at, accessed_at) \
+             VALUES (?, ?, ?, ?)",
+            self.table
+        ))
+        .bind(key)
+        .bind(serde_json::to_string(response)?)
+        .bind(expires_at)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(backend_error)?;
+        self.evict().await
+    }
+
+    async fn remove(&self, key: &str) -> CacheResult<()> {
+        self.ensure_table().await?;
+        sqlx::query(&format!("DELETE FROM {} WHERE key = ?", self.table))
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(backend_error)?;
+        Ok(())
+    }
+
+    async fn clear(&self) -> CacheResult<()> {
+        self.ensure_table().await?;
+        sqlx::query(&format!("DELETE FROM {}", self.table))
+            .execute(&self.pool)
+            .await
+            .map_err(backend_error)?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for SqliteCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteCache")
+            .field("table", &self.table)
+            .field("ttl", &self.ttl)
+            .field("max_entries", &self.max_entries)
+            .finish_non_exhaustive()
+    }
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+fn backend_error(err: sqlx::Error) -> CacheError {
+    CacheError::Backend(err.to_string())
+}
+
+/// Builder for SqliteCache
+pub struct SqliteCacheBuilder {
+    pool: SqlitePool,
+    table: String,
+    ttl: Option<Duration>,
+    max_entries: Option<usize>,
+}
+
+impl SqliteCacheBuilder {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            table: "response_cache".to_string(),
+            ttl: None,
+            max_entries: None,
+        }
+    }
+
+    /// Table to store responses in; must be a plain SQL identifier
+    pub fn table(mut self, table: impl Into<String>) -> CacheResult<Self> {
+        let table = table.into();
+        if !is_sql_identifier(&table) {
+            return Err(CacheError::Backend(format!(
+                "invalid table name '{}'",
+                table
+            )));
+        }
+        self.table = table;
+        Ok(self)
+    }
+
+    /// Expire entries this long after they are stored (default: never)
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Keep at most this many entries, evicting the least recently used
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries.max(1));
+        self
+    }
+
+    pub fn build(self) -> SqliteCache {
+        SqliteCache {
+            pool: self.pool,
+            table: self.table,
+            ttl: self.ttl,
+            max_entries: self.max_entries,
+            table_ready: AtomicBool::new(false),
+        }
+    }
+}
@@ -0,0 +1,353 @@
+//! Callbacks for AgenticOptio.
+//!
+//! A [`CallbackHandler`] observes agent runs, model calls, streamed tokens, and
+//! tool executions without changing them, so logging and analytics can be added in
+//! one place. Attach handlers to any chat model with [`CallbackChatModel`], or
+//! to an agent with [`AgentBuilder::callback`](crate::agents::AgentBuilder::callback).
+//!
+//! Hooks are synchronous and run inline on the calling task; handlers that do
+//! slow work (network export, disk writes) should hand it off to a channel or
+//! background task. Each hook runs inside the context of the run it reports, so
+//! [`RunContext::current`] identifies the agent run, model call, or tool
+//! execution and its parent.
+
+use crate::agents::{AgentResult, AgentRun};
+use crate::core::messages::{AIMessage, Message, ToolCall};
+use crate::core::run::RunContext;
+use crate::models::base::{BaseChatModel, BoxStream, ModelResult};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Arc;
+
+/// Observer of model and tool execution
+///
+/// Every hook has a no-op default, so implementations only override what they
+/// need.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::callbacks::CallbackHandler;
+/// use agentic_optio_rs::core::messages::{AIMessage, ToolCall};
+///
+/// struct PrintUsage;
+///
+/// impl CallbackHandler for PrintUsage {
+///     fn on_llm_end(&self, model: &str, response: &AIMessage) {
+///         let tokens = response.usage.map_or(0, |u| u.total_tokens);
+///         println!("{}: {} tokens", model, tokens);
+///     }
+///
+///     fn on_tool_start(&self, call: &ToolCall) {
+///         println!("calling {}", call.name);
+///     }
+/// }
+/// ```
+pub trait CallbackHandler: Send + Sync {
+    /// An agent run is starting with `messages` as input
+    fn on_agent_start(&self, agent: &str, messages: &[Message]) {
+        let _ = (agent, messages);
+    }
+
+    /// An agent run finished, successfully or not
+    fn on_agent_end(&self, agent: &str, result: &AgentResult<AgentRun>) {
+        let _ = (agent, result);
+    }
+
+    /// A model is about to be called with `messages`
+    fn on_llm_start(&self, model: &str, messages: &[Message]) {
+        let _ = (model, messages);
+    }
+
+    /// A streamed chunk arrived
+    fn on_token(&self, model: &str, token: &str) {
+        let _ = (model, token);
+    }
+
+    /// A model call finished; streamed responses are reported whole
+    fn on_llm_end(&self, model: &str, response: &AIMessage) {
+        let _ = (model, response);
+    }
+
+    /// A tool is about to run
+    fn on_tool_start(&self, call: &ToolCall) {
+        let _ = call;
+    }
+
+    /// A tool returned `output`
+    fn on_tool_end(&self, call: &ToolCall, output: &str) {
+        let _ = (call, output);
+    }
+
+    /// A model call or tool execution failed
+    fn on_error(&self, error: &dyn std::error::Error) {
+        let _ = error;
+    }
+}
+
+/// Shared handlers, so the caller can keep a handle to inspect them later
+impl<T: CallbackHandler + ?Sized> CallbackHandler for Arc<T> {
+    fn on_agent_start(&self, agent: &str, messages: &[Message]) {
+        (**self).on_agent_start(agent, messages)
+    }
+
+    fn on_agent_end(&self, agent: &str, result: &AgentResult<AgentRun>) {
+        (**self).on_agent_end(agent, result)
+    }
+
+    fn on_llm_start(&self, model: &str, messages: &[Message]) {
+        (**self).on_llm_start(model, messages)
+    }
+
+    fn on_token(&self, model: &str, token: &str) {
+        (**self).on_token(model, token)
+    }
+
+    fn on_llm_end(&self, model: &str, response: &AIMessage) {
+        (**self).on_llm_end(model, response)
+    }
+
+    fn on_tool_start(&self, call: &ToolCall) {
+        (**self).on_tool_start(call)
+    }
+
+    fn on_tool_end(&self, call: &ToolCall, output: &str) {
+        (**self).on_tool_end(call, output)
+    }
+
+    fn on_error(&self, error: &dyn std::error::Error) {
+        (**self).on_error(error)
+    }
+}
+
+/// Ordered set of handlers, itself a [`CallbackHandler`] that fans out to each
+#[derive(Clone, Default)]
+pub struct Callbacks {
+    handlers: Vec<Arc<dyn CallbackHandler>>,
+}
+
+impl Callbacks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handler(mut self, handler: impl CallbackHandler + 'static) -> Self {
+        self.push(Arc::new(handler));
+        self
+    }
+
+    pub fn push(&mut self, handler: Arc<dyn CallbackHandler>) {
+        self.handlers.push(handler);
+    }
+
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+}
+
+impl std::fmt::Debug for Callbacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Callbacks")
+            .field("handlers", &self.handlers.len())
+            .finish()
+    }
+}
+
+impl CallbackHandler for Callbacks {
+    fn on_agent_start(&self, agent: &str, messages: &[Message]) {
+        for handler in &self.handlers {
+            handler.on_agent_start(agent, messages);
+        }
+    }
+
+    fn on_agent_end(&self, agent: &str, result: &AgentResult<AgentRun>) {
+        for handler in &self.handlers {
+            handler.on_agent_end(agent, result);
+        }
+    }
+
+    fn on_llm_start(&self, model: &str, messages: &[Message]) {
+        for handler in &self.handlers {
+            handler.on_llm_start(model, messages);
+        }
+    }
+
+    fn on_token(&self, model: &str, token: &str) {
+        for handler in &self.handlers {
+            handler.on_token(model, token);
+        }
+    }
+
+    fn on_llm_end(&self, model: &str, response: &AIMessage) {
+        for handler in &self.handlers {
+            handler.on_llm_end(model, response);
+        }
+    }
+
+    fn on_tool_start(&self, call: &ToolCall) {
+        for handler in &self.handlers {
+            handler.on_tool_start(call);
+        }
+    }
+
+    fn on_tool_end(&self, call: &ToolCall, output: &str) {
+        for handler in &self.handlers {
+            handler.on_tool_end(call, output);
+        }
+    }
+
+    fn on_error(&self, error: &dyn std::error::Error) {
+        for handler in &self.handlers {
+            handler.on_error(error);
+        }
+    }
+}
+
+/// Chat model wrapper that reports every call to its handlers
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::callbacks::{CallbackChatModel, CallbackHandler};
+/// use agentic_optio_rs::core::messages::AIMessage;
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+/// use std::sync::Arc;
+///
+/// struct LogEnd;
+///
+/// impl CallbackHandler for LogEnd {
+///     fn on_llm_end(&self, model: &str, response: &AIMessage) {
+///         println!("{} answered {} chars", model, response.content.len());
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let llm = CallbackChatModel::new(Arc::new(OllamaChat::new("llama3.2"))).callback(LogEnd);
+///     llm.invoke(&[Message::user("Hello!")]).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CallbackChatModel {
+    inner: Arc<dyn BaseChatModel>,
+    callbacks: Callbacks,
+}
+
+impl CallbackChatModel {
+    pub fn new(inner: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            inner,
+            callbacks: Callbacks::new(),
+        }
+    }
+
+    pub fn callback(mut self, handler: impl CallbackHandler + 'static) -> Self {
+        self.callbacks.push(Arc::new(handler));
+        self
+    }
+
+    /// Report to every handler in `callbacks` as well
+    pub fn callbacks(mut self, callbacks: Callbacks) -> Self {
+        self.callbacks.handlers.extend(callbacks.handlers);
+        self
+    }
+
+    fn finish(&self, result: ModelResult<AIMessage>) -> ModelResult<AIMessage> {
+        match &result {
+            Ok(response) => self.callbacks.on_llm_end(self.model_name(), response),
+            Err(e) => self.callbacks.on_error(e),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for CallbackChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        RunContext::new()
+            .scope(async {
+                self.callbacks.on_llm_start(self.model_name(), messages);
+                self.finish(self.inner.invoke(messages).await)
+            })
+            .await
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        RunContext::new()
+            .scope(async {
+                self.callbacks.on_llm_start(self.model_name(), messages);
+                self.finish(self.inner.invoke_with_tools(messages, tools).await)
+            })
+            .await
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let context = RunContext::new();
+        let inner = context
+            .clone()
+            .scope(async {
+                self.callbacks.on_llm_start(self.model_name(), messages);
+                self.inner.stream(messages).await.map_err(|e| {
+                    self.callbacks.on_error(&e);
+                    e
+                })
+            })
+            .await?;
+
+        // Report each chunk as it passes, then the concatenated response, in
+        // the context of the call wherever the stream is polled
+        let stream = futures::stream::unfold(
+            (inner, String::new(), false),
+            move |(mut inner, mut content, failed)| {
+                let context = context.clone();
+                async move {
+                    if failed {
+                        return None;
+                    }
+                    let next = inner.next().await;
+                    context.sync_scope(|| match next {
+                        Some(Ok(chunk)) => {
+                            self.callbacks.on_token(self.model_name(), &chunk.content);
+                            content.push_str(&chunk.content);
+                            Some((Ok(chunk), (inner, content, false)))
+                        }
+                        Some(Err(e)) => {
+                            self.callbacks.on_error(&e);
+                            Some((Err(e), (inner, content, true)))
+                        }
+                        None => {
+                            let response = AIMessage::new(content);
+                            self.callbacks.on_llm_end(self.model_name(), &response);
+                            None
+                        }
+                    })
+                }
+            },
+        );
+        Ok(Box::pin(stream))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
@@ -0,0 +1,217 @@
+//! Classification chain with described labels.
+
+use crate::chains::ChainResult;
+use crate::core::messages::Message;
+use crate::models::base::BaseChatModel;
+use crate::output_parsers::{
+    JsonParser, LabelParser, OutputParser, ParseError, ParseResult, ParsedChatModel,
+};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Label chosen by a [`ClassificationChain`], with how sure the model was and
+/// why
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classification<T> {
+    pub label: String,
+    pub value: T,
+    /// The model's own confidence, from 0 to 1; 1 when it gave none
+    pub confidence: f64,
+    pub rationale: String,
+}
+
+#[derive(Debug, Clone)]
+struct Label<T> {
+    name: String,
+    description: String,
+    value: T,
+}
+
+/// Classify text into one of a set of described labels, for routing and
+/// triage
+///
+/// Each label's description is shown to the model, along with any few-shot
+/// examples. The model answers with the label, a confidence, and a one-line
+/// rationale; an answer naming no label is sent back with corrective
+/// feedback, twice by default.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::chains::ClassificationChain;
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Queue {
+///     Billing,
+///     Technical,
+///     Sales,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let triage = ClassificationChain::new(Arc::new(OllamaChat::new("llama3.2")))
+///         .instructions("Route the support ticket to the right queue.")
+///         .label("billing", "Invoices, refunds, and payment methods", Queue::Billing)
+///         .label("technical", "Bugs, outages, and how-to questions", Queue::Technical)
+///         .label("sales", "Pricing, upgrades, and new accounts", Queue::Sales)
+///         .example("I was charged twice this month", "billing");
+///
+///     let ticket = triage.classify("The export button does nothing").await?;
+///     if ticket.confidence < 0.6 {
+///         eprintln!("needs a human: {}", ticket.rationale);
+///     }
+///     println!("{:?}", ticket.value);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ClassificationChain<T> {
+    model: Arc<dyn BaseChatModel>,
+    instructions: String,
+    labels: Vec<Label<T>>,
+    examples: Vec<(String, String)>,
+    max_retries: u32,
+}
+
+impl<T: Clone + Send + Sync + 'static> ClassificationChain<T> {
+    pub fn new(model: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            model,
+            instructions: "Classify the text the user sends.".to_string(),
+            labels: Vec::new(),
+            examples: Vec::new(),
+            max_retries: 2,
+        }
+    }
+
+    /// What to classify the text by, like `Route the support ticket.`
+    pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = instructions.into();
+        self
+    }
+
+    /// Add a label the model may choose, explained by `description`
+    pub fn label(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        value: T,
+    ) -> Self {
+        self.labels.push(Label {
+            name: name.into(),
+            description: description.into(),
+            value,
+        });
+        self
+    }
+
+    /// Few-shot example: `text` belongs under the label named `label`
+    pub fn example(mut self, text: impl Into<String>, label: impl Into<String>) -> Self {
+        self.examples.push((text.into(), label.into()));
+        self
+    }
+
+    /// Times to ask again after an answer naming no label (default 2)
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub async fn classify(&self, text: &str) -> ChainResult<Classification<T>> {
+        let parser = ClassificationParser {
+            labels: LabelParser::new(
+                self.labels
+                    .iter()
+                    .enumerate()
+                    .map(|(i, label)| (label.name.clone(), i)),
+            ),
+        };
+        let model = ParsedChatModel::new(self.model.clone(), parser).max_retries(self.max_retries);
+        let messages = [Message::system(self.system_prompt()), Message::user(text)];
+        let (index, confidence, rationale) = model.invoke_parsed(&messages).await?;
+
+        let label = &self.labels[index];
+        Ok(Classification {
+            label: label.name.clone(),
+            value: label.value.clone(),
+            confidence,
+            rationale,
+        })
+    }
+
+    fn system_prompt(&self) -> String {
+        let mut prompt = format!("{}\n\nLabels:", self.instructions);
+        for label in &self.labels {
+            prompt.push_str(&format!("\n- {}: {}", label.name, label.description));
+        }
+        if !self.examples.is_empty() {
+            prompt.push_str("\n\nExamples:");
+            for (text, label) in &self.examples {
+                prompt.push_str(&format!("\nText: {text}\nLabel: {label}"));
+            }
+        }
+        prompt
+    }
+}
+
+impl<T> std::fmt::Debug for ClassificationChain<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClassificationChain")
+            .field("model", &self.model.model_name())
+            .field(
+                "labels",
+                &self.labels.iter().map(|l| &l.name).collect::<Vec<_>>(),
+            )
+            .field("examples", &self.examples.len())
+            .field("max_retries", &self.max_retries)
+            .finish()
+    }
+}
+
+/// Parses `{"label", "confidence", "rationale"}` into the label's index, the
+/// confidence, and the rationale
+struct ClassificationParser {
+    labels: LabelParser<usize>,
+}
+
+impl OutputParser<(usize, f64, String)> for ClassificationParser {
+    fn parse(&self, text: &str) -> ParseResult<(usize, f64, String)> {
+        let json: Value = JsonParser::new().parse(text)?;
+        let label = json
+            .get("label")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ParseError::invalid("missing \"label\"", text))?;
+        let index = match self.labels.parse(label) {
+            Ok(index) => index,
+            Err(ParseError::Invalid { reason, .. }) => {
+                return Err(ParseError::invalid(reason, text))
+            }
+            Err(e) => return Err(e),
+        };
+        let confidence = match json.get("confidence") {
+            Some(Value::Number(n)) => n.as_f64(),
+            Some(Value::String(s)) => s.trim().trim_end_matches('%').parse().ok(),
+            _ => None,
+        }
+        .map(|c| if c > 1.0 { c / 100.0 } else { c })
+        .unwrap_or(1.0)
+        .clamp(0.0, 1.0);
+        let rationale = json
+            .get("rationale")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        Ok((index, confidence, rationale))
+    }
+
+    fn format_instructions(&self) -> Option<String> {
+        Some(format!(
+            "Respond with only a JSON object like {{\"label\": \"...\", \"confidence\": 0.9, \
+             \"rationale\": \"...\"}}, where label is exactly one of: {}; confidence is a \
+             number from 0 to 1; and rationale is one sentence explaining the choice.",
+            self.labels.labels().collect::<Vec<_>>().join(", ")
+        ))
+    }
+}
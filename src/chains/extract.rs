@@ -0,0 +1,169 @@
+//! Schema-driven extraction chain.
+
+use crate::chains::ChainResult;
+use crate::core::messages::Message;
+use crate::models::base::BaseChatModel;
+use crate::output_parsers::{JsonParser, ParseError, ParsedChatModel};
+use crate::text_splitter::{TextSplitter, TokenSplitter};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::sync::Arc;
+
+const DEFAULT_INSTRUCTIONS: &str = "Extract the requested information from the text \
+the user sends. Use null for anything the text does not mention, and never guess.";
+
+/// Extract a typed value from text of any length
+///
+/// The text is split into chunks, each chunk is extracted into JSON
+/// concurrently, and the partial extractions are merged in document order:
+/// objects field by field, arrays concatenated without duplicates, and for
+/// any other value the first non-null one wins. The merged JSON is then
+/// deserialized into `T`, so fields a chunk may lack should be `Option`s,
+/// `Vec`s, or `#[serde(default)]`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::chains::ExtractionChain;
+/// use agentic_optio_rs::OllamaChat;
+/// use serde::Deserialize;
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Contract {
+///     parties: Vec<String>,
+///     effective_date: Option<String>,
+///     governing_law: Option<String>,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let contract = std::fs::read_to_string("contract.txt")?;
+///     let chain = ExtractionChain::new(Arc::new(OllamaChat::new("llama3.2"))).schema(
+///         r#"{"parties": ["..."], "effective_date": "YYYY-MM-DD", "governing_law": "..."}"#,
+///     );
+///     let extracted = chain.extract::<Contract>(&contract).await?;
+///     println!("{:?}", extracted.parties);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ExtractionChain {
+    model: Arc<dyn BaseChatModel>,
+    splitter: Arc<dyn TextSplitter>,
+    instructions: String,
+    schema: Option<String>,
+    concurrency: usize,
+    max_retries: u32,
+}
+
+impl ExtractionChain {
+    /// Chain splitting into 2000-token chunks and retrying a chunk once if
+    /// its response is not JSON
+    pub fn new(model: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            model,
+            splitter: Arc::new(TokenSplitter::new(2000).chunk_overlap(100)),
+            instructions: DEFAULT_INSTRUCTIONS.to_string(),
+            schema: None,
+            concurrency: 4,
+            max_retries: 1,
+        }
+    }
+
+    pub fn splitter(mut self, splitter: impl TextSplitter + 'static) -> Self {
+        self.splitter = Arc::new(splitter);
+        self
+    }
+
+    /// System prompt describing what to extract
+    pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = instructions.into();
+        self
+    }
+
+    /// Example JSON, or a JSON Schema, showing the model the shape to return
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Chunks extracted at once (default 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Times to ask again about a chunk whose response is not JSON (default 1)
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Merged extraction from every chunk of `text`
+    pub async fn extract<T: DeserializeOwned>(&self, text: &str) -> ChainResult<T> {
+        let merged = self.extract_json(text).await?;
+        serde_json::from_value(merged.clone())
+            .map_err(|e| ParseError::invalid(e.to_string(), merged.to_string()).into())
+    }
+
+    /// Merged extraction as JSON, before it is deserialized
+    pub async fn extract_json(&self, text: &str) -> ChainResult<Value> {
+        let mut parser = JsonParser::<Value>::new();
+        if let Some(schema) = &self.schema {
+            parser = parser.example(schema.clone());
+        }
+        let extractor =
+            ParsedChatModel::new(self.model.clone(), parser).max_retries(self.max_retries);
+
+        let partials: Vec<Value> = stream::iter(self.splitter.split_text(text))
+            .map(|chunk| {
+                let messages = [
+                    Message::system(self.instructions.clone()),
+                    Message::user(chunk),
+                ];
+                let extractor = &extractor;
+                async move { extractor.invoke_parsed(&messages).await }
+            })
+            .buffered(self.concurrency)
+            .try_collect()
+            .await?;
+
+        Ok(partials.into_iter().fold(Value::Null, merge))
+    }
+}
+
+/// `next` merged into `merged`, which comes from earlier chunks
+fn merge(merged: Value, next: Value) -> Value {
+    match (merged, next) {
+        (Value::Null, next) => next,
+        (Value::Object(mut merged), Value::Object(next)) => {
+            for (key, value) in next {
+                let existing = merged.remove(&key).unwrap_or(Value::Null);
+                merged.insert(key, merge(existing, value));
+            }
+            Value::Object(merged)
+        }
+        (Value::Array(mut merged), Value::Array(next)) => {
+            for item in next {
+                if !merged.contains(&item) {
+                    merged.push(item);
+                }
+            }
+            Value::Array(merged)
+        }
+        (merged, _) => merged,
+    }
+}
+
+impl std::fmt::Debug for ExtractionChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtractionChain")
+            .field("model", &self.model.model_name())
+            .field("schema", &self.schema)
+            .field("concurrency", &self.concurrency)
+            .field("max_retries", &self.max_retries)
+            .finish()
+    }
+}
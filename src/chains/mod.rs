@@ -0,0 +1,36 @@
+//! Chains for AgenticOptio.
+//!
+//! Chains compose retrievers, prompts, and chat models into fixed pipelines for
+//! common tasks.
+
+pub mod classify;
+pub mod extract;
+pub mod rag;
+pub mod summarize;
+
+pub use classify::{Classification, ClassificationChain};
+pub use extract::ExtractionChain;
+pub use rag::{RagChain, RagResponse};
+pub use summarize::{SummarizeChain, Summary, SummaryProgress};
+
+use crate::models::base::ModelError;
+use crate::output_parsers::ParseError;
+use crate::retrievers::RetrieverError;
+
+/// Error type for chain execution
+#[derive(Debug, thiserror::Error)]
+pub enum ChainError {
+    #[error("Retrieval failed: {0}")]
+    Retriever(#[from] RetrieverError),
+
+    #[error("Model error: {0}")]
+    Model(#[from] ModelError),
+
+    #[error("Parse error: {0}")]
+    Parse(#[from] ParseError),
+
+    #[error("Chain failed: {0}")]
+    Other(String),
+}
+
+pub type ChainResult<T> = Result<T, ChainError>;
@@ -0,0 +1,118 @@
+//! Retrieval-augmented generation chain.
+
+use crate::chains::ChainResult;
+use crate::core::documents::Document;
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::BaseChatModel;
+use crate::retrievers::Retriever;
+use std::sync::Arc;
+
+const DEFAULT_PROMPT: &str = "Answer the question using only the context below. \
+If the context does not contain the answer, say that you don't know. \
+Cite the numbered sources you used, like [1].\n\n\
+Context:\n{context}\n\nQuestion: {question}";
+
+/// Answer plus the documents it was grounded on
+#[derive(Debug, Clone)]
+pub struct RagResponse {
+    pub answer: AIMessage,
+    /// Retrieved documents, in the order they were numbered in the prompt
+    pub sources: Vec<Document>,
+}
+
+/// Retrieve, ground, and answer in one call
+///
+/// The prompt template must contain `{context}` and `{question}` placeholders.
+/// Retrieved documents are numbered `[1]`, `[2]`, ... in the context, with their
+/// `source` metadata when present.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::chains::RagChain;
+/// use agentic_optio_rs::retrievers::VectorStoreRetriever;
+/// use agentic_optio_rs::vectorstores::{InMemoryVectorStore, VectorStore};
+/// use agentic_optio_rs::{Document, OllamaChat, OllamaEmbedding};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = Arc::new(InMemoryVectorStore::new(Arc::new(OllamaEmbedding::new(
+///         "nomic-embed-text",
+///     ))));
+///     store
+///         .add_documents(vec![Document::new("Refunds are issued within 14 days.")])
+///         .await?;
+///
+///     let chain = RagChain::new(
+///         Arc::new(VectorStoreRetriever::new(store).k(3)),
+///         Arc::new(OllamaChat::new("llama3.2")),
+///     );
+///     let response = chain.invoke("How long do refunds take?").await?;
+///     println!("{} ({} sources)", response.answer.content, response.sources.len());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RagChain {
+    retriever: Arc<dyn Retriever>,
+    model: Arc<dyn BaseChatModel>,
+    prompt: String,
+    system_prompt: Option<String>,
+}
+
+impl RagChain {
+    pub fn new(retriever: Arc<dyn Retriever>, model: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            retriever,
+            model,
+            prompt: DEFAULT_PROMPT.to_string(),
+            system_prompt: None,
+        }
+    }
+
+    /// Replace the grounding template; use `{context}` and `{question}`
+    pub fn prompt(mut self, template: impl Into<String>) -> Self {
+        self.prompt = template.into();
+        self
+    }
+
+    pub fn system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    pub async fn invoke(&self, question: &str) -> ChainResult<RagResponse> {
+        let sources = self.retriever.retrieve(question).await?;
+
+        let mut messages = Vec::new();
+        if let Some(system) = &self.system_prompt {
+            messages.push(Message::system(system.clone()));
+        }
+        messages.push(Message::user(self.render(question, &sources)));
+
+        let answer = self.model.invoke(&messages).await?;
+        Ok(RagResponse { answer, sources })
+    }
+
+    fn render(&self, question: &str, sources: &[Document]) -> String {
+        let context = sources
+            .iter()
+            .enumerate()
+            .map(
+                |(i, doc)| match doc.metadata.get("source").and_then(|s| s.as_str()) {
+                    Some(source) => format!("[{}] (source: {})\n{}", i + 1, source, doc.content),
+                    None => format!("[{}]\n{}", i + 1, doc.content),
+                },
+            )
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        // Split first so placeholder text inside documents is left alone
+        self.prompt
+            .split("{context}")
+            .map(|part| part.replace("{question}", question))
+            .collect::<Vec<_>>()
+            .join(&context)
+    }
+}
@@ -0,0 +1,248 @@
+//! Map-reduce summarization chain.
+
+use crate::chains::{ChainError, ChainResult};
+use crate::core::messages::Message;
+use crate::models::base::BaseChatModel;
+use crate::text_splitter::{TextSplitter, TokenSplitter};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use regex::Regex;
+use std::sync::Arc;
+
+const DEFAULT_MAP_PROMPT: &str = "Write a concise summary of the following text. \
+Keep key facts, names, and numbers.\n\n{text}";
+
+const DEFAULT_REDUCE_PROMPT: &str = "The following are summaries of consecutive parts \
+of one document. Combine them into a single concise summary. Keep key facts, names, \
+and numbers.\n\n{text}";
+
+type TokenCounter = dyn Fn(&str) -> usize + Send + Sync;
+type ProgressFn = dyn Fn(&SummaryProgress) + Send + Sync;
+
+/// Stage reached by a [`SummarizeChain`], reported to its progress callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummaryProgress {
+    /// The text was split into `chunks` pieces
+    Split { chunks: usize },
+    /// `done` of `total` chunks are summarized
+    Mapped { done: usize, total: usize },
+    /// Reduce round `round` left `summaries` summaries
+    Reduced { round: usize, summaries: usize },
+}
+
+/// Summary plus the partial summaries it was reduced from
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub summary: String,
+    /// Summary of each chunk, in document order
+    pub chunk_summaries: Vec<String>,
+    /// Reduce rounds needed, 0 when the text fit in one chunk
+    pub rounds: usize,
+}
+
+/// Summarize text of any length by splitting it, summarizing the chunks
+/// concurrently, and combining the summaries
+///
+/// Summaries are combined in groups that fit the token budget, round after
+/// round, until one group is left to combine into the final summary. If the
+/// summaries still do not fit after [`max_rounds`](Self::max_rounds) rounds
+/// the chain fails rather than sending an oversized request. Both prompt
+/// templates must contain a `{text}` placeholder.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::chains::{SummarizeChain, SummaryProgress};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let report = std::fs::read_to_string("annual_report.txt")?;
+///     let chain = SummarizeChain::new(Arc::new(OllamaChat::new("llama3.2")))
+///         .token_budget(4000)
+///         .concurrency(8)
+///         .on_progress(|progress| {
+///             if let SummaryProgress::Mapped { done, total } = progress {
+///                 eprintln!("summarized {done}/{total} chunks");
+///             }
+///         });
+///     let summary = chain.invoke(&report).await?;
+///     println!("{}", summary.summary);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SummarizeChain {
+    model: Arc<dyn BaseChatModel>,
+    splitter: Arc<dyn TextSplitter>,
+    map_prompt: String,
+    reduce_prompt: String,
+    token_budget: usize,
+    concurrency: usize,
+    max_rounds: usize,
+    token_counter: Arc<TokenCounter>,
+    on_progress: Option<Arc<ProgressFn>>,
+}
+
+impl SummarizeChain {
+    /// Chain splitting into 2000-token chunks and combining up to 3000
+    /// tokens of summaries at a time
+    pub fn new(model: Arc<dyn BaseChatModel>) -> Self {
+        let token = Regex::new(r"\w+|[^\w\s]").expect("valid token regex");
+        Self {
+            model,
+            splitter: Arc::new(TokenSplitter::new(2000).chunk_overlap(100)),
+            map_prompt: DEFAULT_MAP_PROMPT.to_string(),
+            reduce_prompt: DEFAULT_REDUCE_PROMPT.to_string(),
+            token_budget: 3000,
+            concurrency: 4,
+            max_rounds: 4,
+            token_counter: Arc::new(move |text: &str| token.find_iter(text).count()),
+            on_progress: None,
+        }
+    }
+
+    pub fn splitter(mut self, splitter: impl TextSplitter + 'static) -> Self {
+        self.splitter = Arc::new(splitter);
+        self
+    }
+
+    /// Template for summarizing one chunk; use `{text}`
+    pub fn map_prompt(mut self, template: impl Into<String>) -> Self {
+        self.map_prompt = template.into();
+        self
+    }
+
+    /// Template for combining summaries; use `{text}`
+    pub fn reduce_prompt(mut self, template: impl Into<String>) -> Self {
+        self.reduce_prompt = template.into();
+        self
+    }
+
+    /// Most tokens of summaries combined in one request (default 3000)
+    pub fn token_budget(mut self, tokens: usize) -> Self {
+        self.token_budget = tokens.max(1);
+        self
+    }
+
+    /// Requests in flight at once (default 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Reduce rounds allowed before the final one (default 4)
+    pub fn max_rounds(mut self, rounds: usize) -> Self {
+        self.max_rounds = rounds;
+        self
+    }
+
+    /// Count tokens with a model tokenizer instead of the default estimate
+    pub fn token_counter(
+        mut self,
+        counter: impl Fn(&str) -> usize + Send + Sync + 'static,
+    ) -> Self {
+        self.token_counter = Arc::new(counter);
+        self
+    }
+
+    pub fn on_progress(
+        mut self,
+        callback: impl Fn(&SummaryProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    pub async fn invoke(&self, text: &str) -> ChainResult<Summary> {
+        let chunks = self.splitter.split_text(text);
+        self.report(SummaryProgress::Split {
+            chunks: chunks.len(),
+        });
+
+        let total = chunks.len();
+        let mut done = 0;
+        let chunk_summaries: Vec<String> = stream::iter(&chunks)
+            .map(|chunk| self.summarize(&self.map_prompt, chunk))
+            .buffered(self.concurrency)
+            .inspect_ok(|_| {
+                done += 1;
+                self.report(SummaryProgress::Mapped { done, total });
+            })
+            .try_collect()
+            .await?;
+
+        let mut summaries = chunk_summaries.clone();
+        let mut rounds = 0;
+        while summaries.len() > 1 {
+            let groups = self.group(&summaries);
+            if groups.len() > 1 && rounds == self.max_rounds {
+                return Err(ChainError::Other(format!(
+                    "{} summaries still exceed the token budget after {rounds} reduce rounds",
+                    summaries.len()
+                )));
+            }
+            summaries = stream::iter(groups)
+                .map(|group| self.summarize(&self.reduce_prompt, group))
+                .buffered(self.concurrency)
+                .try_collect()
+                .await?;
+            rounds += 1;
+            self.report(SummaryProgress::Reduced {
+                round: rounds,
+                summaries: summaries.len(),
+            });
+        }
+
+        Ok(Summary {
+            summary: summaries.pop().unwrap_or_default(),
+            chunk_summaries,
+            rounds,
+        })
+    }
+
+    /// Consecutive summaries joined into groups within the token budget; a
+    /// summary over the budget on its own gets its own group
+    fn group(&self, summaries: &[String]) -> Vec<String> {
+        let mut groups: Vec<String> = Vec::new();
+        let mut tokens = 0;
+        for summary in summaries {
+            let size = (self.token_counter)(summary);
+            match groups.last_mut() {
+                Some(group) if tokens + size <= self.token_budget => {
+                    group.push_str("\n\n");
+                    group.push_str(summary);
+                    tokens += size;
+                }
+                _ => {
+                    groups.push(summary.clone());
+                    tokens = size;
+                }
+            }
+        }
+        groups
+    }
+
+    async fn summarize(&self, template: &str, text: impl AsRef<str>) -> ChainResult<String> {
+        let prompt = template.replace("{text}", text.as_ref());
+        let response = self.model.invoke(&[Message::user(prompt)]).await?;
+        Ok(response.content.trim().to_string())
+    }
+
+    fn report(&self, progress: SummaryProgress) {
+        if let Some(callback) = &self.on_progress {
+            callback(&progress);
+        }
+    }
+}
+
+impl std::fmt::Debug for SummarizeChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SummarizeChain")
+            .field("model", &self.model.model_name())
+            .field("token_budget", &self.token_budget)
+            .field("concurrency", &self.concurrency)
+            .field("max_rounds", &self.max_rounds)
+            .finish()
+    }
+}
@@ -0,0 +1,494 @@
+//! Declarative configuration for AgenticOptio.
+//!
+//! A [`Config`] file declares models, tools, budgets, memory backends, and
+//! agents by name, and [`Config::materialize`] turns it into a [`Runtime`]
+//! holding the live objects, so a deployment changes models, prompts, or limits
+//! by editing a file instead of recompiling.
+
+use crate::agents::{Agent, AgentBuilder, Budget};
+use crate::models::base::{BaseChatModel, BaseEmbedding, ModelError, ModelResult};
+#[cfg(feature = "ollama")]
+use crate::models::ollama::{OllamaChat, OllamaEmbedding};
+#[cfg(feature = "openai")]
+use crate::models::openai::OpenAIChat;
+use crate::prompts::repository::{interpolate_env, parse_value};
+use crate::prompts::PromptError;
+use crate::tools::ToolRegistry;
+use crate::vectorstores::{InMemoryVectorStore, VectorStore, VectorStoreError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Error type for configuration loading
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to load config: {0}")]
+    Load(String),
+
+    #[error("Invalid config: {0}")]
+    Invalid(String),
+
+    #[error("Model error: {0}")]
+    Model(#[from] ModelError),
+
+    #[error("Vector store error: {0}")]
+    VectorStore(#[from] VectorStoreError),
+}
+
+pub type ConfigResult<T> = Result<T, ConfigError>;
+
+/// Chat model declared in a config file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelConfig {
+    /// `provider:model` spec, as for [`init_chat_model`](crate::init_chat_model)
+    pub model: String,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    /// Ollama host or OpenAI-compatible base URL, overriding the environment
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Deadline for a whole non-streaming request
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    /// Longest wait for a response to start and between stream chunks
+    #[serde(default)]
+    pub idle_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+}
+
+impl ModelConfig {
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Chat model for this configuration, with unset options taken from the
+    /// environment
+    #[cfg_attr(
+        not(any(feature = "ollama", feature = "openai")),
+        allow(unused_variables)
+    )]
+    pub fn build(&self) -> ModelResult<Box<dyn BaseChatModel>> {
+        let (provider, model) = split_spec(&self.model)?;
+        match provider {
+            #[cfg(feature = "ollama")]
+            "ollama" => {
+                let mut builder = OllamaChat::builder(model);
+                if let Some(host) = &self.base_url {
+                    builder = builder.host(host.clone());
+                }
+                if let Some(temperature) = self.temperature {
+                    builder = builder.temperature(temperature);
+                }
+                if let Some(max_tokens) = self.max_tokens {
+                    builder = builder.max_tokens(max_tokens);
+                }
+                if let Some(timeout) = self.timeout_secs {
+                    builder = builder.timeout(Duration::from_secs(timeout));
+                }
+                if let Some(timeout) = self.connect_timeout_secs {
+                    builder = builder
+                        .http_options(|http| http.connect_timeout(Duration::from_secs(timeout)));
+                }
+                if let Some(timeout) = self.idle_timeout_secs {
+                    builder = builder.idle_timeout(Duration::from_secs(timeout));
+                }
+                if let Some(max_retries) = self.max_retries {
+                    builder = builder.max_retries(max_retries);
+                }
+                Ok(Box::new(builder.try_build()?))
+            }
+            #[cfg(feature = "openai")]
+            "openai" => {
+                if std::env::var_os("OPENAI_API_KEY").is_none()
+                    && std::env::var_os("OPENAI_BASE_URL").is_none()
+                    && self.base_url.is_none()
+                {
+                    return Err(ModelError::ConfigError(
+                        "OPENAI_API_KEY is not set".to_string(),
+                    ));
+                }
+                let mut builder = OpenAIChat::builder(model);
+                if let Some(base_url) = &self.base_url {
+                    builder = builder.base_url(base_url.clone());
+                }
+                if let Some(temperature) = self.temperature {
+                    builder = builder.temperature(temperature);
+                }
+                if let Some(max_tokens) = self.max_tokens {
+                    builder = builder.max_tokens(max_tokens);
+                }
+                if let Some(timeout) = self.timeout_secs {
+                    builder = builder.timeout(Duration::from_secs(timeout));
+                }
+                if let Some(timeout) = self.connect_timeout_secs {
+                    builder = builder
+                        .http_options(|http| http.connect_timeout(Duration::from_secs(timeout)));
+                }
+                if let Some(timeout) = self.idle_timeout_secs {
+                    builder = builder.idle_timeout(Duration::from_secs(timeout));
+                }
+                if let Some(max_retries) = self.max_retries {
+                    builder = builder.max_retries(max_retries);
+                }
+                Ok(Box::new(builder.try_build()?))
+            }
+            other if matches!(other, "ollama" | "openai") => Err(ModelError::ConfigError(format!(
+                "the {other} provider needs the `{other}` feature"
+            ))),
+            other => Err(ModelError::ConfigError(format!(
+                "unknown model provider '{other}'"
+            ))),
+        }
+    }
+}
+
+/// Provider and model of a `provider:model` spec
+fn split_spec(spec: &str) -> ModelResult<(&str, &str)> {
+    match spec.split_once(':') {
+        Some((_, "")) => Err(ModelError::ConfigError(format!(
+            "model spec '{spec}' has no model name"
+        ))),
+        Some(parts) => Ok(parts),
+        None => Err(ModelError::ConfigError(format!(
+            "model spec '{spec}' is not of the form provider:model"
+        ))),
+    }
+}
+
+/// Settings for a tool implemented in code and registered at materialization
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolConfig {
+    /// Disabled tools are left out of every agent that lists them
+    #[serde(default = "enabled")]
+    pub enabled: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+/// Run limits declared in a config file; see [`Budget`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BudgetConfig {
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+    #[serde(default)]
+    pub max_cost: Option<f64>,
+    #[serde(default)]
+    pub max_duration_secs: Option<u64>,
+    #[serde(default)]
+    pub max_model_calls: Option<usize>,
+    #[serde(default)]
+    pub input_cost_per_1k: f64,
+    #[serde(default)]
+    pub output_cost_per_1k: f64,
+}
+
+impl BudgetConfig {
+    pub fn budget(&self) -> Budget {
+        Budget {
+            max_tokens: self.max_tokens,
+            max_cost: self.max_cost,
+            max_duration: self.max_duration_secs.map(Duration::from_secs),
+            max_model_calls: self.max_model_calls,
+            input_cost_per_1k: self.input_cost_per_1k,
+            output_cost_per_1k: self.output_cost_per_1k,
+        }
+    }
+}
+
+/// Vector store backing long-term memory, declared in a config file
+///
+/// `embedding` is an Ollama embedding model name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case", deny_unknown_fields)]
+pub enum MemoryConfig {
+    /// [`InMemoryVectorStore`], loaded from and persisted to `path` if set
+    InMemory {
+        embedding: String,
+        #[serde(default)]
+        path: Option<PathBuf>,
+    },
+    /// `SqliteVectorStore` in the database file at `path`; needs the `sqlite`
+    /// feature
+    Sqlite {
+        embedding: String,
+        path: PathBuf,
+        #[serde(default)]
+        table: Option<String>,
+    },
+}
+
+impl MemoryConfig {
+    pub async fn build(&self) -> ConfigResult<Arc<dyn VectorStore>> {
+        match self {
+            MemoryConfig::InMemory { embedding, path } => {
+                let embeddings = embedding_model(embedding)?;
+                let store = match path {
+                    Some(path) if path.exists() => InMemoryVectorStore::load(path, embeddings)?,
+                    Some(path) => InMemoryVectorStore::new(embeddings).with_path(path),
+                    None => InMemoryVectorStore::new(embeddings),
+                };
+                Ok(Arc::new(store))
+            }
+            #[cfg(feature = "sqlite")]
+            MemoryConfig::Sqlite {
+                embedding,
+                path,
+                table,
+            } => {
+                let mut builder =
+                    crate::vectorstores::SqliteVectorStore::open(path, embedding_model(embedding)?)
+                        .await?;
+                if let Some(table) = table {
+                    builder = builder.table(table.clone())?;
+                }
+                Ok(Arc::new(builder.build()))
+            }
+            #[cfg(not(feature = "sqlite"))]
+            MemoryConfig::Sqlite { .. } => Err(ConfigError::Invalid(
+                "the sqlite memory backend needs the `sqlite` feature".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "ollama")]
+fn embedding_model(model: &str) -> ConfigResult<Arc<dyn BaseEmbedding>> {
+    Ok(Arc::new(OllamaEmbedding::builder(model).try_build()?))
+}
+
+#[cfg(not(feature = "ollama"))]
+fn embedding_model(_model: &str) -> ConfigResult<Arc<dyn BaseEmbedding>> {
+    Err(ConfigError::Invalid(
+        "memory embeddings need the `ollama` feature".to_string(),
+    ))
+}
+
+/// Agent declared in a config file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AgentConfig {
+    /// Name of a declared model; defaults to the model named `default`
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Names of registered tools to give the agent
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// Name of a declared budget
+    #[serde(default)]
+    pub budget: Option<String>,
+    #[serde(default)]
+    pub max_iterations: Option<usize>,
+}
+
+/// Models, tools, budgets, memory backends, and agents declared in a file
+///
+/// Files are JSON, or TOML and YAML with the `toml` and `yaml` features, with
+/// optional top-level tables, each keyed by name:
+///
+/// ```toml
+/// [models.default]
+/// model = "ollama:llama3.2"
+/// temperature = 0.2
+///
+/// [models.fast]
+/// model = "openai:gpt-4o-mini"
+///
+/// [tools.shell]
+/// enabled = false
+///
+/// [budgets.standard]
+/// max_tokens = 50000
+/// max_duration_secs = 120
+///
+/// [memory.notes]
+/// backend = "in_memory"
+/// embedding = "nomic-embed-text"
+/// path = "notes.json"
+///
+/// [agents.researcher]
+/// model = "fast"
+/// system_prompt = "You research questions for ${TEAM:-the team}."
+/// tools = ["search", "shell"]
+/// budget = "standard"
+/// ```
+///
+/// `${VAR}` and `${VAR:-default}` in string values are replaced from the
+/// environment, so secrets and per-environment settings stay out of the file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::config::Config;
+/// use agentic_optio_rs::tools::ToolRegistry;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let runtime = Config::from_path("optio.toml")?
+///         .materialize(&ToolRegistry::new())
+///         .await?;
+///     let run = runtime.agent("researcher").unwrap().run("What is an optio?").await?;
+///     println!("{}", run.output);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub models: BTreeMap<String, ModelConfig>,
+    pub tools: BTreeMap<String, ToolConfig>,
+    pub budgets: BTreeMap<String, BudgetConfig>,
+    pub memory: BTreeMap<String, MemoryConfig>,
+    pub agents: BTreeMap<String, AgentConfig>,
+}
+
+impl Config {
+    /// Read a config file, choosing the format by its extension
+    pub fn from_path(path: impl AsRef<Path>) -> ConfigResult<Self> {
+        let path = path.as_ref();
+        let load_error =
+            |message: String| ConfigError::Load(format!("{}: {}", path.display(), message));
+
+        let text = std::fs::read_to_string(path).map_err(|e| load_error(e.to_string()))?;
+        let mut value = parse_value(path, &text).map_err(load_error)?;
+        interpolate_env(&mut value).map_err(|e| match e {
+            PromptError::MissingVariable(name) => {
+                load_error(format!("environment variable {} is not set", name))
+            }
+            other => load_error(other.to_string()),
+        })?;
+        serde_json::from_value(value).map_err(|e| load_error(e.to_string()))
+    }
+
+    /// Build every declared object, taking tool implementations from `tools`
+    ///
+    /// Fails on the first reference to an undeclared model or budget, or to a
+    /// tool missing from `tools`, so a broken file is caught at startup.
+    pub async fn materialize(&self, tools: &ToolRegistry) -> ConfigResult<Runtime> {
+        for name in self.tools.keys() {
+            if tools.get(name).is_none() {
+                return Err(ConfigError::Invalid(format!("unknown tool '{}'", name)));
+            }
+        }
+
+        let mut models = HashMap::new();
+        for (name, model) in &self.models {
+            let model: Arc<dyn BaseChatModel> = Arc::from(model.build()?);
+            models.insert(name.clone(), model);
+        }
+        let budgets: HashMap<String, Budget> = self
+            .budgets
+            .iter()
+            .map(|(name, budget)| (name.clone(), budget.budget()))
+            .collect();
+        let mut memory = HashMap::new();
+        for (name, backend) in &self.memory {
+            memory.insert(name.clone(), backend.build().await?);
+        }
+
+        let mut agents = HashMap::new();
+        for (name, agent) in &self.agents {
+            let model_name = agent.model.as_deref().unwrap_or("default");
+            let model = models.get(model_name).ok_or_else(|| {
+                ConfigError::Invalid(format!(
+                    "agent '{}' uses unknown model '{}'",
+                    name, model_name
+                ))
+            })?;
+            let mut builder = AgentBuilder::new(model.clone()).name(name.clone());
+            if let Some(system_prompt) = &agent.system_prompt {
+                builder = builder.system_prompt(system_prompt.clone());
+            }
+            if let Some(max_iterations) = agent.max_iterations {
+                builder = builder.max_iterations(max_iterations);
+            }
+            if let Some(budget) = &agent.budget {
+                let budget = budgets.get(budget).ok_or_else(|| {
+                    ConfigError::Invalid(format!(
+                        "agent '{}' uses unknown budget '{}'",
+                        name, budget
+                    ))
+                })?;
+                builder = builder.budget(budget.clone());
+            }
+            let mut selected = ToolRegistry::new();
+            for tool in &agent.tools {
+                let implementation = tools.get(tool).ok_or_else(|| {
+                    ConfigError::Invalid(format!("agent '{}' uses unknown tool '{}'", name, tool))
+                })?;
+                if self.tools.get(tool).map_or(true, |t| t.enabled) {
+                    selected.register_arc(implementation.clone());
+                }
+            }
+            agents.insert(name.clone(), builder.tools(selected).build());
+        }
+
+        Ok(Runtime {
+            models,
+            budgets,
+            memory,
+            agents,
+        })
+    }
+}
+
+/// Live objects built from a [`Config`]
+#[derive(Clone, Default)]
+pub struct Runtime {
+    models: HashMap<String, Arc<dyn BaseChatModel>>,
+    budgets: HashMap<String, Budget>,
+    memory: HashMap<String, Arc<dyn VectorStore>>,
+    agents: HashMap<String, Agent>,
+}
+
+impl Runtime {
+    pub fn model(&self, name: &str) -> Option<Arc<dyn BaseChatModel>> {
+        self.models.get(name).cloned()
+    }
+
+    pub fn budget(&self, name: &str) -> Option<&Budget> {
+        self.budgets.get(name)
+    }
+
+    pub fn memory(&self, name: &str) -> Option<Arc<dyn VectorStore>> {
+        self.memory.get(name).cloned()
+    }
+
+    pub fn agent(&self, name: &str) -> Option<&Agent> {
+        self.agents.get(name)
+    }
+
+    /// Names of the built agents, sorted
+    pub fn agent_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.agents.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl std::fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Runtime")
+            .field("models", &self.models.keys().collect::<Vec<_>>())
+            .field("budgets", &self.budgets)
+            .field("memory", &self.memory.keys().collect::<Vec<_>>())
+            .field("agents", &self.agents.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
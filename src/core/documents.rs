@@ -0,0 +1,43 @@
+//! Document type for AgenticOptio.
+//!
+//! A [`Document`] is a piece of text plus arbitrary metadata. It is the unit that
+//! flows between loaders, text splitters, and vector stores.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Text content with metadata
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Document {
+    /// Identifier assigned by the vector store holding the document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub content: String,
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+impl Document {
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            content: content.into(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Add a metadata entry
+    pub fn with_metadata(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
@@ -3,15 +3,71 @@
 //! Lightweight message implementations compatible with standard chat API formats.
 
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Tool call information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ToolCall {
     pub id: String,
     pub name: String,
     pub args: serde_json::Value,
 }
 
+/// Token usage reported by the model for a single call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Usage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub total_tokens: u32,
+}
+
+impl Usage {
+    pub fn new(input_tokens: u32, output_tokens: u32) -> Self {
+        Self {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens + output_tokens,
+        }
+    }
+}
+
+/// Throughput of a streamed response
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct StreamStats {
+    /// Time from the request to the first chunk with content
+    pub time_to_first_token: Duration,
+    /// Time from the request to the end of the stream
+    pub duration: Duration,
+    /// Output tokens as reported by the provider, or else the number of
+    /// chunks with content
+    pub output_tokens: u32,
+}
+
+impl StreamStats {
+    /// Generation speed after the first token
+    pub fn tokens_per_second(&self) -> f64 {
+        let generating = self.duration.saturating_sub(self.time_to_first_token);
+        let seconds = if generating.is_zero() {
+            self.duration.as_secs_f64()
+        } else {
+            generating.as_secs_f64()
+        };
+        if seconds > 0.0 {
+            self.output_tokens as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+impl std::ops::AddAssign for Usage {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.total_tokens += other.total_tokens;
+    }
+}
+
 /// Base message trait
 pub trait BaseMessage {
     fn role(&self) -> &str;
@@ -87,6 +143,11 @@ pub struct AIMessage {
     pub content: String,
     #[serde(default)]
     pub tool_calls: Vec<ToolCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+    /// Set only on the trailing, otherwise empty chunk of a measured stream
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_stats: Option<StreamStats>,
 }
 
 impl AIMessage {
@@ -94,6 +155,8 @@ impl AIMessage {
         Self {
             content: content.into(),
             tool_calls: Vec::new(),
+            usage: None,
+            stream_stats: None,
         }
     }
 
@@ -101,8 +164,16 @@ impl AIMessage {
         Self {
             content: content.into(),
             tool_calls,
+            usage: None,
+            stream_stats: None,
         }
     }
+
+    /// Attach token usage reported by the model
+    pub fn with_usage(mut self, usage: Usage) -> Self {
+        self.usage = Some(usage);
+        self
+    }
 }
 
 impl BaseMessage for AIMessage {
@@ -177,11 +248,16 @@ impl BaseMessage for ToolMessage {
 }
 
 /// Unified message enum for easier handling
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "role")]
 pub enum Message {
+    #[serde(rename = "system")]
     System(SystemMessage),
+    #[serde(rename = "user")]
     Human(HumanMessage),
+    #[serde(rename = "assistant")]
     AI(AIMessage),
+    #[serde(rename = "tool")]
     Tool(ToolMessage),
 }
 
@@ -1,8 +1,14 @@
 //! Core components for AgenticOptio.
 //!
-//! This module provides the core message types and base classes used throughout
+//! This module provides the core message and document types and base classes used throughout
 //! the AgenticOptio library.
 
+pub mod documents;
 pub mod messages;
+pub mod run;
 
-pub use messages::{AIMessage, BaseMessage, HumanMessage, Message, SystemMessage, ToolMessage};
+pub use documents::Document;
+pub use messages::{
+    AIMessage, BaseMessage, HumanMessage, Message, StreamStats, SystemMessage, ToolMessage, Usage,
+};
+pub use run::RunContext;
@@ -0,0 +1,105 @@
+//! Run identifiers for AgenticOptio.
+//!
+//! Every agent run, model call, and tool execution is assigned a [`RunContext`]
+//! holding its own id, the id of the run that started it, and the id of the
+//! trace shared by the whole tree. The current context is task-local: nested
+//! calls made while a run is in progress become its children, and callback
+//! handlers can read it with [`RunContext::current`] to correlate logs from
+//! multi-agent runs. Contexts also carry metadata, such as prompt versions,
+//! which child runs inherit.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use uuid::Uuid;
+
+tokio::task_local! {
+    static CURRENT: RunContext;
+}
+
+/// Identity of a run and its place in the run tree
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::core::RunContext;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let root = RunContext::root();
+///     let child = root
+///         .clone()
+///         .scope(async { RunContext::new() })
+///         .await;
+///
+///     assert_eq!(child.parent_run_id.as_deref(), Some(root.run_id.as_str()));
+///     assert_eq!(child.trace_id, root.trace_id);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunContext {
+    pub run_id: String,
+    pub parent_run_id: Option<String>,
+    pub trace_id: String,
+    /// Tags describing the run, inherited by its children
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl RunContext {
+    /// Child of the current run, or a new root outside any run
+    pub fn new() -> Self {
+        match Self::current() {
+            Some(parent) => parent.child(),
+            None => Self::root(),
+        }
+    }
+
+    /// Context starting a new trace
+    pub fn root() -> Self {
+        let run_id = Uuid::new_v4().to_string();
+        Self {
+            trace_id: run_id.clone(),
+            run_id,
+            parent_run_id: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Context for a run started by this one
+    pub fn child(&self) -> Self {
+        Self {
+            run_id: Uuid::new_v4().to_string(),
+            parent_run_id: Some(self.run_id.clone()),
+            trace_id: self.trace_id.clone(),
+            metadata: self.metadata.clone(),
+        }
+    }
+
+    /// Tag this run, and the runs it starts, with a metadata entry
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Context of the run in progress on this task, if any
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(Self::clone).ok()
+    }
+
+    /// Run `future` with this context as the current one
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// Run `f` with this context as the current one
+    pub fn sync_scope<R>(self, f: impl FnOnce() -> R) -> R {
+        CURRENT.sync_scope(self, f)
+    }
+}
+
+impl Default for RunContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,344 @@
+//! Credential resolution for AgenticOptio.
+//!
+//! A [`CredentialProvider`] looks up secrets such as API keys by name, so every
+//! backend resolves its keys the same way and an application decides where they
+//! live: environment variables ([`EnvCredentials`]), a `.env` file
+//! ([`DotenvCredentials`]), the OS keyring (`KeyringCredentials`, behind the
+//! `keyring` feature), or any async lookup ([`FnCredentials`]).
+//! [`CredentialChain`] tries several in order.
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Error type for credential lookups
+#[derive(Debug, thiserror::Error)]
+pub enum CredentialError {
+    #[error("Credential {0} not found")]
+    NotFound(String),
+
+    #[error("Credential store error: {0}")]
+    Backend(String),
+}
+
+pub type CredentialResult<T> = Result<T, CredentialError>;
+
+/// Source of secrets, looked up by name such as `OPENAI_API_KEY`
+#[async_trait]
+pub trait CredentialProvider: Send + Sync {
+    /// The secret named `name`, or `None` if this provider does not hold it
+    async fn get(&self, name: &str) -> CredentialResult<Option<String>>;
+
+    /// The secret named `name`, failing with [`CredentialError::NotFound`] if
+    /// this provider does not hold it
+    async fn require(&self, name: &str) -> CredentialResult<String> {
+        self.get(name)
+            .await?
+            .ok_or_else(|| CredentialError::NotFound(name.to_string()))
+    }
+}
+
+/// Shared providers, so one provider can serve several backends
+#[async_trait]
+impl<T: CredentialProvider + ?Sized> CredentialProvider for Arc<T> {
+    async fn get(&self, name: &str) -> CredentialResult<Option<String>> {
+        (**self).get(name).await
+    }
+}
+
+/// Secrets from the process environment
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvCredentials;
+
+#[async_trait]
+impl CredentialProvider for EnvCredentials {
+    async fn get(&self, name: &str) -> CredentialResult<Option<String>> {
+        Ok(std::env::var(name).ok())
+    }
+}
+
+/// Secrets from a `.env` file
+///
+/// Lines are `KEY=value`, optionally prefixed with `export`. Values may be
+/// single-quoted (taken literally) or double-quoted (with `\n`, `\"`, and `\\`
+/// escapes); unquoted values end at a ` #` comment. The file is read once and
+/// the process environment is left untouched.
+#[derive(Clone, Default)]
+pub struct DotenvCredentials {
+    values: HashMap<String, String>,
+}
+
+impl DotenvCredentials {
+    /// Read the `.env` file at `path`
+    pub fn load(path: impl AsRef<Path>) -> CredentialResult<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| CredentialError::Backend(format!("{}: {}", path.display(), e)))?;
+        Self::parse(&text)
+            .map_err(|e| CredentialError::Backend(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Parse `.env` file contents
+    pub fn parse(text: &str) -> CredentialResult<Self> {
+        let mut values = HashMap::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                CredentialError::Backend(format!("line {}: expected KEY=value", number + 1))
+            })?;
+            let value = parse_value(value.trim()).ok_or_else(|| {
+                CredentialError::Backend(format!("line {}: unterminated quote", number + 1))
+            })?;
+            values.insert(key.trim().to_string(), value);
+        }
+        Ok(Self { values })
+    }
+
+    /// Names of the secrets in the file, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.values.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(rest) = value.strip_prefix('\'') {
+        return rest.strip_suffix('\'').map(String::from);
+    }
+    if let Some(rest) = value.strip_prefix('"') {
+        let rest = rest.strip_suffix('"')?;
+        let mut output = String::with_capacity(rest.len());
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match (c, chars.clone().next()) {
+                ('\\', Some('n')) => output.push('\n'),
+                ('\\', Some(escaped @ ('"' | '\\'))) => output.push(escaped),
+                _ => {
+                    output.push(c);
+                    continue;
+                }
+            }
+            chars.next();
+        }
+        return Some(output);
+    }
+    let value = value.split(" #").next().unwrap_or_default();
+    Some(value.trim_end().to_string())
+}
+
+/// Lists the names only, never the secrets
+impl std::fmt::Debug for DotenvCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DotenvCredentials")
+            .field("names", &self.names())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for DotenvCredentials {
+    async fn get(&self, name: &str) -> CredentialResult<Option<String>> {
+        Ok(self.values.get(name).cloned())
+    }
+}
+
+/// Secrets from the OS keyring: the macOS Keychain, Windows Credential
+/// Manager, or the Linux kernel keyring
+///
+/// Each secret is stored under `service` with its name as the user.
+#[cfg(feature = "keyring")]
+#[derive(Debug, Clone)]
+pub struct KeyringCredentials {
+    service: String,
+}
+
+#[cfg(feature = "keyring")]
+impl KeyringCredentials {
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    /// Store `secret` under `name`, replacing any existing one
+    pub async fn set(&self, name: &str, secret: &str) -> CredentialResult<()> {
+        let (service, name, secret) = (self.service.clone(), name.to_string(), secret.to_string());
+        tokio::task::spawn_blocking(move || {
+            keyring::Entry::new(&service, &name)?.set_password(&secret)
+        })
+        .await
+        .map_err(|e| CredentialError::Backend(e.to_string()))?
+        .map_err(|e| CredentialError::Backend(e.to_string()))
+    }
+}
+
+#[cfg(feature = "keyring")]
+#[async_trait]
+impl CredentialProvider for KeyringCredentials {
+    async fn get(&self, name: &str) -> CredentialResult<Option<String>> {
+        let (service, name) = (self.service.clone(), name.to_string());
+        let password = tokio::task::spawn_blocking(move || {
+            keyring::Entry::new(&service, &name)?.get_password()
+        })
+        .await
+        .map_err(|e| CredentialError::Backend(e.to_string()))?;
+        match password {
+            Ok(password) => Ok(Some(password)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(CredentialError::Backend(e.to_string())),
+        }
+    }
+}
+
+type ResolverFn =
+    dyn Fn(String) -> BoxFuture<'static, CredentialResult<Option<String>>> + Send + Sync;
+
+/// Secrets from an async closure, e.g. a call to a secrets manager
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::credentials::FnCredentials;
+///
+/// let vault = FnCredentials::new(|name| async move {
+///     // Fetch `name` from a secrets manager
+///     Ok((name == "OPENAI_API_KEY").then(|| "sk-...".to_string()))
+/// });
+/// ```
+#[derive(Clone)]
+pub struct FnCredentials {
+    resolver: Arc<ResolverFn>,
+}
+
+impl FnCredentials {
+    pub fn new<F, Fut>(resolver: F) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = CredentialResult<Option<String>>> + Send + 'static,
+    {
+        Self {
+            resolver: Arc::new(move |name| Box::pin(resolver(name))),
+        }
+    }
+}
+
+impl std::fmt::Debug for FnCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FnCredentials").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for FnCredentials {
+    async fn get(&self, name: &str) -> CredentialResult<Option<String>> {
+        (self.resolver)(name.to_string()).await
+    }
+}
+
+/// Providers tried in order, returning the first secret found
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::credentials::{CredentialChain, DotenvCredentials, EnvCredentials};
+/// use agentic_optio_rs::models::OpenAIChat;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // The environment wins over the .env file
+/// let credentials = CredentialChain::new()
+///     .with(EnvCredentials)
+///     .with(DotenvCredentials::load(".env")?);
+/// let llm = OpenAIChat::builder("gpt-4o-mini").credentials(credentials).build();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct CredentialChain {
+    providers: Vec<Arc<dyn CredentialProvider>>,
+}
+
+impl CredentialChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Try `provider` after the ones already in the chain
+    pub fn with(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+}
+
+impl std::fmt::Debug for CredentialChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialChain")
+            .field("providers", &self.providers.len())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for CredentialChain {
+    async fn get(&self, name: &str) -> CredentialResult<Option<String>> {
+        for provider in &self.providers {
+            if let Some(secret) = provider.get(name).await? {
+                return Ok(Some(secret));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// API key set explicitly or looked up by name on every request, so rotated
+/// keys are picked up without rebuilding the client
+#[cfg(any(feature = "openai", feature = "qdrant"))]
+#[derive(Clone)]
+pub(crate) struct ApiKey {
+    name: &'static str,
+    key: Option<String>,
+    credentials: Arc<dyn CredentialProvider>,
+}
+
+#[cfg(any(feature = "openai", feature = "qdrant"))]
+impl ApiKey {
+    /// Look up `name` in the environment unless a key or provider is set
+    pub(crate) fn env(name: &'static str) -> Self {
+        Self {
+            name,
+            key: None,
+            credentials: Arc::new(EnvCredentials),
+        }
+    }
+
+    pub(crate) fn set_key(&mut self, key: String) {
+        self.key = Some(key);
+    }
+
+    pub(crate) fn set_credentials(&mut self, credentials: Arc<dyn CredentialProvider>) {
+        self.credentials = credentials;
+    }
+
+    pub(crate) async fn resolve(&self) -> CredentialResult<Option<String>> {
+        match &self.key {
+            Some(key) => Ok(Some(key.clone())),
+            None => self.credentials.get(self.name).await,
+        }
+    }
+}
+
+#[cfg(any(feature = "openai", feature = "qdrant"))]
+impl std::fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.key {
+            Some(_) => f.write_str("<redacted>"),
+            None => write!(f, "<{}>", self.name),
+        }
+    }
+}
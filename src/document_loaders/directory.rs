@@ -0,0 +1,149 @@
+//! Directory loader.
+
+use crate::core::documents::Document;
+use crate::document_loaders::{
+    DocumentLoader, HtmlLoader, LoaderError, LoaderResult, MarkdownLoader, TextLoader,
+};
+use async_trait::async_trait;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Loads every matching file under a directory
+///
+/// Paths are matched relative to the directory using `/` separators. Glob
+/// patterns support `*` (within one path segment), `**` (any number of
+/// segments), and `?`. Files are loaded by extension: `.md`/`.markdown` with
+/// [`MarkdownLoader`], `.html`/`.htm` with [`HtmlLoader`], `.pdf` with
+/// `PdfLoader` when the `pdf` feature is enabled, anything else with
+/// [`TextLoader`]. Results are ordered by path.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::document_loaders::{DirectoryLoader, DocumentLoader};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let docs = DirectoryLoader::new("docs")
+///         .glob("**/*.md")?
+///         .exclude("drafts/**")?
+///         .load()
+///         .await?;
+///     println!("loaded {} documents", docs.len());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DirectoryLoader {
+    root: PathBuf,
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl DirectoryLoader {
+    /// Load every file under `root`; narrow it with [`DirectoryLoader::glob`]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Only load files matching this pattern; may be called repeatedly
+    pub fn glob(mut self, pattern: &str) -> LoaderResult<Self> {
+        self.include.push(glob_to_regex(pattern)?);
+        Ok(self)
+    }
+
+    /// Skip files matching this pattern
+    pub fn exclude(mut self, pattern: &str) -> LoaderResult<Self> {
+        self.exclude.push(glob_to_regex(pattern)?);
+        Ok(self)
+    }
+
+    /// Matching file paths, sorted
+    pub async fn paths(&self) -> LoaderResult<Vec<PathBuf>> {
+        let io_error = |path: &Path, source| LoaderError::Io {
+            path: path.display().to_string(),
+            source,
+        };
+
+        let mut paths = Vec::new();
+        let mut pending = vec![self.root.clone()];
+        while let Some(dir) = pending.pop() {
+            let mut entries = tokio::fs::read_dir(&dir)
+                .await
+                .map_err(|e| io_error(&dir, e))?;
+            while let Some(entry) = entries.next_entry().await.map_err(|e| io_error(&dir, e))? {
+                let path = entry.path();
+                let file_type = entry.file_type().await.map_err(|e| io_error(&path, e))?;
+                if file_type.is_dir() {
+                    pending.push(path);
+                } else if file_type.is_file() && self.matches(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        (self.include.is_empty() || self.include.iter().any(|re| re.is_match(&relative)))
+            && !self.exclude.iter().any(|re| re.is_match(&relative))
+    }
+}
+
+#[async_trait]
+impl DocumentLoader for DirectoryLoader {
+    async fn load(&self) -> LoaderResult<Vec<Document>> {
+        let mut documents = Vec::new();
+        for path in self.paths().await? {
+            let extension = path
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            let loaded = match extension.as_str() {
+                "md" | "markdown" => MarkdownLoader::new(path).load().await?,
+                "html" | "htm" => HtmlLoader::new(path).load().await?,
+                #[cfg(feature = "pdf")]
+                "pdf" => crate::document_loaders::PdfLoader::new(path).load().await?,
+                _ => TextLoader::new(path).load().await?,
+            };
+            documents.extend(loaded);
+        }
+        Ok(documents)
+    }
+}
+
+/// Translate a glob into an anchored regex over `/`-separated relative paths
+fn glob_to_regex(pattern: &str) -> LoaderResult<Regex> {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).map_err(|e| LoaderError::InvalidPattern(format!("{}: {}", pattern, e)))
+}
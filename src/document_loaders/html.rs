@@ -0,0 +1,134 @@
+//! HTML loader.
+
+use crate::core::documents::Document;
+use crate::document_loaders::{document_from, read_to_string, DocumentLoader, LoaderResult};
+use async_trait::async_trait;
+use regex::Regex;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Loads an HTML file as plain text
+///
+/// Tags, comments, scripts, and styles are stripped; block elements become line
+/// breaks. The page `<title>`, if any, is stored as `title` metadata.
+#[derive(Debug, Clone)]
+pub struct HtmlLoader {
+    path: PathBuf,
+}
+
+impl HtmlLoader {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl DocumentLoader for HtmlLoader {
+    async fn load(&self) -> LoaderResult<Vec<Document>> {
+        let html = read_to_string(&self.path).await?;
+        let title = patterns()
+            .title
+            .captures(&html)
+            .map(|c| decode_entities(c[1].trim()));
+
+        let mut document = document_from(&self.path, html_to_text(&html));
+        if let Some(title) = title.filter(|t| !t.is_empty()) {
+            document = document.with_metadata("title", title);
+        }
+        Ok(vec![document])
+    }
+}
+
+struct Patterns {
+    title: Regex,
+    hidden: Regex,
+    block: Regex,
+    tag: Regex,
+    spaces: Regex,
+    blank_lines: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        title: Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap(),
+        hidden: Regex::new(
+            r"(?is)<!--.*?-->|<script\b.*?</script>|<style\b.*?</style>|<head\b.*?</head>",
+        )
+        .unwrap(),
+        block: Regex::new(concat!(
+            r"(?i)<(?:br|/?p|/?div|/?li|/?ul|/?ol|/?tr|/?h[1-6]",
+            r"|/?section|/?article|/?blockquote|/?pre|/?table)\b[^>]*>",
+        ))
+        .unwrap(),
+        tag: Regex::new(r"<[^>]*>").unwrap(),
+        spaces: Regex::new(r"[ \t\r\f\v]+").unwrap(),
+        blank_lines: Regex::new(r"\n{3,}").unwrap(),
+    })
+}
+
+/// Convert HTML to readable plain text
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::document_loaders::html_to_text;
+///
+/// let text = html_to_text("<h1>Title</h1><p>Fish &amp; chips</p><script>x()</script>");
+/// assert_eq!(text, "Title\n\nFish & chips");
+/// ```
+pub fn html_to_text(html: &str) -> String {
+    let p = patterns();
+    let text = p.hidden.replace_all(html, "");
+    let text = p.block.replace_all(&text, "\n\n");
+    let text = p.tag.replace_all(&text, "");
+    let text = decode_entities(&text);
+
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| p.spaces.replace_all(line, " ").trim().to_string())
+        .collect();
+    p.blank_lines
+        .replace_all(&lines.join("\n"), "\n\n")
+        .trim()
+        .to_string()
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,49 @@
+//! Markdown loader.
+
+use crate::core::documents::Document;
+use crate::document_loaders::{document_from, read_to_string, DocumentLoader, LoaderResult};
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// Loads a markdown file as a single document
+///
+/// A leading `---` front matter block is removed from the content. The first
+/// level-one heading, if any, is stored as `title` metadata.
+#[derive(Debug, Clone)]
+pub struct MarkdownLoader {
+    path: PathBuf,
+}
+
+impl MarkdownLoader {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl DocumentLoader for MarkdownLoader {
+    async fn load(&self) -> LoaderResult<Vec<Document>> {
+        let raw = read_to_string(&self.path).await?;
+        let content = strip_front_matter(&raw).to_string();
+        let title = content
+            .lines()
+            .find_map(|line| line.strip_prefix("# "))
+            .map(|title| title.trim().to_string());
+
+        let mut document = document_from(&self.path, content);
+        if let Some(title) = title {
+            document = document.with_metadata("title", title);
+        }
+        Ok(vec![document])
+    }
+}
+
+fn strip_front_matter(text: &str) -> &str {
+    let Some(rest) = text.strip_prefix("---\n") else {
+        return text;
+    };
+    match rest.find("\n---") {
+        Some(end) => rest[end + 4..].trim_start_matches(['\r', '\n']),
+        None => text,
+    }
+}
@@ -0,0 +1,69 @@
+//! Document loaders for AgenticOptio.
+//!
+//! Loaders read files into [`Document`]s ready for text splitters and vector
+//! stores. Every loaded document carries a `source` metadata entry with the file
+//! path it came from.
+
+pub mod directory;
+pub mod html;
+pub mod markdown;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+pub mod text;
+
+pub use directory::DirectoryLoader;
+pub use html::{html_to_text, HtmlLoader};
+pub use markdown::MarkdownLoader;
+#[cfg(feature = "pdf")]
+pub use pdf::PdfLoader;
+pub use text::TextLoader;
+
+use crate::core::documents::Document;
+use crate::text_splitter::TextSplitter;
+use async_trait::async_trait;
+use std::path::Path;
+
+/// Error type for document loading
+#[derive(Debug, thiserror::Error)]
+pub enum LoaderError {
+    #[error("IO error reading {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid glob pattern: {0}")]
+    InvalidPattern(String),
+
+    #[error("Failed to parse {path}: {reason}")]
+    Parse { path: String, reason: String },
+}
+
+pub type LoaderResult<T> = Result<T, LoaderError>;
+
+/// Base trait for all document loaders
+#[async_trait]
+pub trait DocumentLoader: Send + Sync {
+    async fn load(&self) -> LoaderResult<Vec<Document>>;
+
+    /// Load and split into chunks in one step
+    async fn load_and_split(&self, splitter: &dyn TextSplitter) -> LoaderResult<Vec<Document>> {
+        Ok(splitter.split_documents(&self.load().await?))
+    }
+}
+
+/// Read a UTF-8 file, attaching the path to any error
+pub(crate) async fn read_to_string(path: &Path) -> LoaderResult<String> {
+    tokio::fs::read_to_string(path)
+        .await
+        .map_err(|source| LoaderError::Io {
+            path: path.display().to_string(),
+            source,
+        })
+}
+
+/// Document with `source` metadata set to `path`
+pub(crate) fn document_from(path: &Path, content: String) -> Document {
+    Document::new(content).with_metadata("source", path.display().to_string())
+}
@@ -0,0 +1,71 @@
+//! PDF loader.
+
+use crate::core::documents::Document;
+use crate::document_loaders::{document_from, DocumentLoader, LoaderError, LoaderResult};
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// Loads a PDF file as one document per page
+///
+/// Each document carries `page` (1-based) and `total_pages` metadata. Pages
+/// with no extractable text, such as scanned images, are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::document_loaders::{DocumentLoader, PdfLoader};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     for page in PdfLoader::new("handbook.pdf").load().await? {
+///         println!("page {}: {} chars", page.metadata["page"], page.content.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PdfLoader {
+    path: PathBuf,
+}
+
+impl PdfLoader {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl DocumentLoader for PdfLoader {
+    async fn load(&self) -> LoaderResult<Vec<Document>> {
+        let bytes = tokio::fs::read(&self.path)
+            .await
+            .map_err(|source| LoaderError::Io {
+                path: self.path.display().to_string(),
+                source,
+            })?;
+
+        let parse_error = |reason: String| LoaderError::Parse {
+            path: self.path.display().to_string(),
+            reason,
+        };
+        // Extraction is CPU-bound and may panic on malformed files.
+        let pages = tokio::task::spawn_blocking(move || {
+            pdf_extract::extract_text_from_mem_by_pages(&bytes)
+        })
+        .await
+        .map_err(|e| parse_error(format!("extraction aborted: {}", e)))?
+        .map_err(|e| parse_error(e.to_string()))?;
+
+        let total_pages = pages.len();
+        Ok(pages
+            .into_iter()
+            .enumerate()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .map(|(i, text)| {
+                document_from(&self.path, text.trim().to_string())
+                    .with_metadata("page", i + 1)
+                    .with_metadata("total_pages", total_pages)
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,26 @@
+//! Plain text loader.
+
+use crate::core::documents::Document;
+use crate::document_loaders::{document_from, read_to_string, DocumentLoader, LoaderResult};
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// Loads a UTF-8 text file as a single document
+#[derive(Debug, Clone)]
+pub struct TextLoader {
+    path: PathBuf,
+}
+
+impl TextLoader {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl DocumentLoader for TextLoader {
+    async fn load(&self) -> LoaderResult<Vec<Document>> {
+        let content = read_to_string(&self.path).await?;
+        Ok(vec![document_from(&self.path, content)])
+    }
+}
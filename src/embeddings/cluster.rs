@@ -0,0 +1,345 @@
+//! Clustering for embeddings.
+//!
+//! k-means with k-means++ seeding, plus the elbow and silhouette heuristics for
+//! choosing `k`. Everything is deterministic for a given seed.
+
+use crate::embeddings::similarity::{dot, euclidean};
+use crate::embeddings::transform::l2_normalize;
+use std::ops::RangeInclusive;
+
+/// Error type for clustering
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ClusterError {
+    #[error("Cannot form {k} clusters from {points} points")]
+    TooFewPoints { points: usize, k: usize },
+
+    #[error("Number of clusters must be at least 1")]
+    ZeroClusters,
+
+    #[error("Embedding dimension mismatch: expected {expected}, got {actual}")]
+    DimensionMismatch { expected: usize, actual: usize },
+}
+
+/// Output of [`KMeans::fit`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clustering {
+    pub centroids: Vec<Vec<f32>>,
+    /// Cluster index of each input vector
+    pub assignments: Vec<usize>,
+    /// Sum of squared distances from each vector to its centroid
+    pub inertia: f32,
+    pub iterations: usize,
+}
+
+impl Clustering {
+    /// Input indices grouped by cluster
+    pub fn members(&self) -> Vec<Vec<usize>> {
+        let mut members = vec![Vec::new(); self.centroids.len()];
+        for (i, &cluster) in self.assignments.iter().enumerate() {
+            members[cluster].push(i);
+        }
+        members
+    }
+}
+
+/// Lloyd's k-means with k-means++ initialization
+///
+/// With [`spherical`](KMeans::spherical) enabled, vectors and centroids are
+/// unit-normalized so clusters follow cosine similarity, which usually suits
+/// text embeddings better than raw euclidean distance.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::embeddings::KMeans;
+///
+/// let points = vec![
+///     vec![0.0, 0.1], vec![0.1, 0.0],
+///     vec![5.0, 5.1], vec![5.1, 5.0],
+/// ];
+/// let clustering = KMeans::new(2).seed(7).fit(&points).unwrap();
+/// assert_eq!(clustering.assignments[0], clustering.assignments[1]);
+/// assert_ne!(clustering.assignments[0], clustering.assignments[2]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KMeans {
+    k: usize,
+    max_iterations: usize,
+    tolerance: f32,
+    seed: u64,
+    spherical: bool,
+}
+
+impl KMeans {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            max_iterations: 100,
+            tolerance: 1e-4,
+            seed: 42,
+            spherical: false,
+        }
+    }
+
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations.max(1);
+        self
+    }
+
+    /// Stop once no centroid moves further than this
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Cluster by cosine similarity instead of euclidean distance
+    pub fn spherical(mut self, spherical: bool) -> Self {
+        self.spherical = spherical;
+        self
+    }
+
+    pub fn fit<V: AsRef<[f32]>>(&self, vectors: &[V]) -> Result<Clustering, ClusterError> {
+        if self.k == 0 {
+            return Err(ClusterError::ZeroClusters);
+        }
+        if vectors.len() < self.k {
+            return Err(ClusterError::TooFewPoints {
+                points: vectors.len(),
+                k: self.k,
+            });
+        }
+        let dim = vectors[0].as_ref().len();
+        let mut points: Vec<Vec<f32>> = Vec::with_capacity(vectors.len());
+        for vector in vectors {
+            let vector = vector.as_ref();
+            if vector.len() != dim {
+                return Err(ClusterError::DimensionMismatch {
+                    expected: dim,
+                    actual: vector.len(),
+                });
+            }
+            let mut point = vector.to_vec();
+            if self.spherical {
+                l2_normalize(&mut point);
+            }
+            points.push(point);
+        }
+
+        let mut rng = Rng(self.seed.max(1));
+        let mut centroids = self.seed_centroids(&points, &mut rng);
+        let mut assignments = vec![0; points.len()];
+        let mut iterations = 0;
+
+        while iterations < self.max_iterations {
+            iterations += 1;
+            for (point, assignment) in points.iter().zip(assignments.iter_mut()) {
+                *assignment = nearest(point, &centroids).0;
+            }
+
+            let mut sums = vec![vec![0.0f32; dim]; self.k];
+            let mut counts = vec![0usize; self.k];
+            for (point, &cluster) in points.iter().zip(&assignments) {
+                counts[cluster] += 1;
+                for (sum, x) in sums[cluster].iter_mut().zip(point) {
+                    *sum += x;
+                }
+            }
+
+            let mut shift = 0.0f32;
+            for (cluster, (mut sum, count)) in sums.into_iter().zip(counts).enumerate() {
+                if count == 0 {
+                    // Re-seed an empty cluster at the point farthest from its centroid
+                    let (farthest, _) = points
+                        .iter()
+                        .enumerate()
+                        .map(|(i, p)| (i, squared_distance(p, &centroids[assignments[i]])))
+                        .fold((0, f32::MIN), |best, c| if c.1 > best.1 { c } else { best });
+                    sum = points[farthest].clone();
+                    assignments[farthest] = cluster;
+                } else {
+                    for x in sum.iter_mut() {
+                        *x /= count as f32;
+                    }
+                }
+                if self.spherical {
+                    l2_normalize(&mut sum);
+                }
+                shift = shift.max(euclidean(&sum, &centroids[cluster]));
+                centroids[cluster] = sum;
+            }
+
+            if shift <= self.tolerance {
+                break;
+            }
+        }
+
+        let mut inertia = 0.0;
+        for (point, assignment) in points.iter().zip(assignments.iter_mut()) {
+            let (cluster, distance) = nearest(point, &centroids);
+            *assignment = cluster;
+            inertia += distance;
+        }
+
+        Ok(Clustering {
+            centroids,
+            assignments,
+            inertia,
+            iterations,
+        })
+    }
+
+    /// k-means++: each new centroid is drawn with probability proportional to
+    /// its squared distance from the nearest existing one
+    fn seed_centroids(&self, points: &[Vec<f32>], rng: &mut Rng) -> Vec<Vec<f32>> {
+        let mut centroids = vec![points[rng.below(points.len())].clone()];
+        let mut distances: Vec<f32> = points
+            .iter()
+            .map(|p| squared_distance(p, &centroids[0]))
+            .collect();
+
+        while centroids.len() < self.k {
+            let total: f32 = distances.iter().sum();
+            let next = if total > 0.0 {
+                let mut target = rng.unit() * total;
+                distances
+                    .iter()
+                    .position(|&d| {
+                        target -= d;
+                        d > 0.0 && target <= 0.0
+                    })
+                    .unwrap_or(points.len() - 1)
+            } else {
+                rng.below(points.len())
+            };
+            centroids.push(points[next].clone());
+            for (d, p) in distances.iter_mut().zip(points) {
+                *d = d.min(squared_distance(p, &centroids[centroids.len() - 1]));
+            }
+        }
+        centroids
+    }
+}
+
+/// Inertia of a k-means fit for each `k` in `ks`, for an elbow plot
+///
+/// Values of `k` larger than the number of vectors are skipped.
+pub fn elbow<V: AsRef<[f32]>>(
+    vectors: &[V],
+    ks: RangeInclusive<usize>,
+    kmeans: KMeans,
+) -> Result<Vec<(usize, f32)>, ClusterError> {
+    let mut curve = Vec::new();
+    for k in ks.filter(|&k| k >= 1 && k <= vectors.len()) {
+        let fit = KMeans { k, ..kmeans }.fit(vectors)?;
+        curve.push((k, fit.inertia));
+    }
+    Ok(curve)
+}
+
+/// The `k` at the bend of an elbow curve
+///
+/// Picks the point farthest from the straight line joining the first and last
+/// points of the curve. Returns `None` for fewer than three points.
+pub fn elbow_point(curve: &[(usize, f32)]) -> Option<usize> {
+    if curve.len() < 3 {
+        return None;
+    }
+    let (x0, y0) = (curve[0].0 as f32, curve[0].1);
+    let (x1, y1) = (curve[curve.len() - 1].0 as f32, curve[curve.len() - 1].1);
+    // Normalize both axes so inertia's scale does not dominate
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let span_y = if dy.abs() > 0.0 { dy.abs() } else { 1.0 };
+    let span_x = if dx.abs() > 0.0 { dx.abs() } else { 1.0 };
+
+    curve
+        .iter()
+        .map(|&(k, inertia)| {
+            let t = (k as f32 - x0) / span_x;
+            let u = (inertia - y0) / span_y;
+            let (lx, ly) = (dx / span_x, dy / span_y);
+            (k, (lx * u - ly * t).abs() / (lx * lx + ly * ly).sqrt())
+        })
+        .fold(None, |best: Option<(usize, f32)>, c| match best {
+            Some(b) if b.1 >= c.1 => Some(b),
+            _ => Some(c),
+        })
+        .map(|(k, _)| k)
+}
+
+/// Mean silhouette coefficient of a clustering, from -1 to 1 (higher is better)
+///
+/// Uses euclidean distance and takes O(n²) time. Points in single-member
+/// clusters score 0, as does a clustering with one cluster.
+pub fn silhouette_score<V: AsRef<[f32]>>(vectors: &[V], assignments: &[usize]) -> f32 {
+    let clusters = assignments.iter().max().map_or(0, |&m| m + 1);
+    if vectors.is_empty() || clusters < 2 {
+        return 0.0;
+    }
+
+    let mut total = 0.0;
+    for (i, vector) in vectors.iter().enumerate() {
+        let mut sums = vec![0.0f32; clusters];
+        let mut counts = vec![0usize; clusters];
+        for (j, other) in vectors.iter().enumerate() {
+            if i != j {
+                sums[assignments[j]] += euclidean(vector.as_ref(), other.as_ref());
+                counts[assignments[j]] += 1;
+            }
+        }
+
+        let own = assignments[i];
+        if counts[own] == 0 {
+            continue;
+        }
+        let a = sums[own] / counts[own] as f32;
+        let b = (0..clusters)
+            .filter(|&c| c != own && counts[c] > 0)
+            .map(|c| sums[c] / counts[c] as f32)
+            .fold(f32::INFINITY, f32::min);
+        if b.is_finite() && a.max(b) > 0.0 {
+            total += (b - a) / a.max(b);
+        }
+    }
+    total / vectors.len() as f32
+}
+
+fn squared_distance(a: &[f32], b: &[f32]) -> f32 {
+    dot(a, a) + dot(b, b) - 2.0 * dot(a, b)
+}
+
+/// Closest centroid and the squared distance to it
+fn nearest(point: &[f32], centroids: &[Vec<f32>]) -> (usize, f32) {
+    centroids
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i, squared_distance(point, c).max(0.0)))
+        .fold(
+            (0, f32::INFINITY),
+            |best, c| if c.1 < best.1 { c } else { best },
+        )
+}
+
+/// Seeded xorshift generator
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn unit(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
@@ -0,0 +1,193 @@
+//! Near-duplicate detection for embeddings.
+//!
+//! Comparing every pair of vectors is quadratic, so candidates are first
+//! blocked with random-hyperplane locality-sensitive hashing: each vector gets a
+//! few short sign-bit hashes ("bands"), and only vectors sharing at least one
+//! band hash are compared exactly. Similar vectors agree on most sign bits, so
+//! they almost always land in a shared block.
+
+use crate::core::documents::Document;
+use crate::embeddings::similarity::{cosine, dot};
+use crate::models::base::{BaseEmbedding, ModelResult};
+use std::collections::HashMap;
+
+/// Two inputs whose embeddings are at least as similar as the threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DuplicatePair {
+    /// Index of the earlier input
+    pub first: usize,
+    /// Index of the later input
+    pub second: usize,
+    pub similarity: f32,
+}
+
+/// Finds embeddings whose cosine similarity meets a threshold
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::embeddings::NearDuplicateDetector;
+///
+/// let vectors = vec![vec![1.0, 0.0, 0.2], vec![0.0, 1.0, 0.0], vec![1.0, 0.01, 0.2]];
+/// let detector = NearDuplicateDetector::new(0.99);
+/// let pairs = detector.find_duplicates(&vectors);
+/// assert_eq!((pairs[0].first, pairs[0].second), (0, 2));
+/// assert_eq!(detector.unique_indices(&vectors), vec![0, 1]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NearDuplicateDetector {
+    threshold: f32,
+    bands: usize,
+    bits_per_band: usize,
+    seed: u64,
+}
+
+impl NearDuplicateDetector {
+    /// Flag pairs with cosine similarity of at least `threshold`
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold,
+            bands: 16,
+            bits_per_band: 8,
+            seed: 42,
+        }
+    }
+
+    /// Number of hash blocks per vector; more bands catch more pairs near the
+    /// threshold at the cost of more comparisons. Zero compares every pair.
+    pub fn bands(mut self, bands: usize) -> Self {
+        self.bands = bands;
+        self
+    }
+
+    /// Sign bits per band; more bits make blocks smaller and more selective
+    pub fn bits_per_band(mut self, bits: usize) -> Self {
+        self.bits_per_band = bits.clamp(1, 64);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Pairs meeting the threshold, ordered by `(first, second)`
+    pub fn find_duplicates<V: AsRef<[f32]>>(&self, vectors: &[V]) -> Vec<DuplicatePair> {
+        let mut pairs = Vec::new();
+        let mut check = |first: usize, second: usize| {
+            let similarity = cosine(vectors[first].as_ref(), vectors[second].as_ref());
+            if similarity >= self.threshold {
+                pairs.push(DuplicatePair {
+                    first,
+                    second,
+                    similarity,
+                });
+            }
+        };
+
+        if self.bands == 0 || vectors.len() < 2 {
+            for second in 1..vectors.len() {
+                for first in 0..second {
+                    check(first, second);
+                }
+            }
+            return pairs;
+        }
+
+        let planes = self.hyperplanes(vectors[0].as_ref().len());
+        let mut candidates = Vec::new();
+        for band in planes.chunks(self.bits_per_band) {
+            let mut blocks: HashMap<u64, Vec<usize>> = HashMap::new();
+            for (i, vector) in vectors.iter().enumerate() {
+                let hash = band
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, plane)| dot(plane, vector.as_ref()) >= 0.0)
+                    .fold(0u64, |hash, (bit, _)| hash | 1 << bit);
+                blocks.entry(hash).or_default().push(i);
+            }
+            for members in blocks.values() {
+                for (n, &second) in members.iter().enumerate() {
+                    candidates.extend(members[..n].iter().map(|&first| (first, second)));
+                }
+            }
+        }
+
+        candidates.sort_unstable();
+        candidates.dedup();
+        for (first, second) in candidates {
+            check(first, second);
+        }
+        pairs
+    }
+
+    /// Groups of two or more mutually linked duplicates, each sorted, ordered
+    /// by their first member
+    pub fn duplicate_groups<V: AsRef<[f32]>>(&self, vectors: &[V]) -> Vec<Vec<usize>> {
+        let mut parent: Vec<usize> = (0..vectors.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+        for pair in self.find_duplicates(vectors) {
+            let (a, b) = (
+                root(&mut parent, pair.first),
+                root(&mut parent, pair.second),
+            );
+            parent[a.max(b)] = a.min(b);
+        }
+
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for i in 0..vectors.len() {
+            let r = root(&mut parent, i);
+            groups.entry(r).or_default().push(i);
+        }
+        let mut groups: Vec<Vec<usize>> = groups.into_values().filter(|g| g.len() > 1).collect();
+        groups.sort();
+        groups
+    }
+
+    /// Indices to keep: every input except later members of duplicate groups
+    pub fn unique_indices<V: AsRef<[f32]>>(&self, vectors: &[V]) -> Vec<usize> {
+        let mut dropped = vec![false; vectors.len()];
+        for group in self.duplicate_groups(vectors) {
+            for &i in &group[1..] {
+                dropped[i] = true;
+            }
+        }
+        (0..vectors.len()).filter(|&i| !dropped[i]).collect()
+    }
+
+    /// Embed documents and drop near-duplicates, keeping the first of each group
+    pub async fn dedup_documents(
+        &self,
+        embeddings: &dyn BaseEmbedding,
+        documents: Vec<Document>,
+    ) -> ModelResult<Vec<Document>> {
+        let texts: Vec<String> = documents.iter().map(|d| d.content.clone()).collect();
+        let vectors = embeddings.embed(&texts).await?;
+        let keep = self.unique_indices(&vectors);
+        let mut documents: Vec<Option<Document>> = documents.into_iter().map(Some).collect();
+        Ok(keep
+            .into_iter()
+            .filter_map(|i| documents[i].take())
+            .collect())
+    }
+
+    /// Seeded random hyperplanes, `bands * bits_per_band` of them
+    fn hyperplanes(&self, dim: usize) -> Vec<Vec<f32>> {
+        let mut state = self.seed.max(1);
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+        };
+        (0..self.bands * self.bits_per_band)
+            .map(|_| (0..dim).map(|_| next()).collect())
+            .collect()
+    }
+}
@@ -0,0 +1,17 @@
+//! Embedding utilities for AgenticOptio.
+//!
+//! Helpers for working with the vectors produced by [`BaseEmbedding`] models.
+//!
+//! [`BaseEmbedding`]: crate::models::base::BaseEmbedding
+
+pub mod cluster;
+pub mod dedup;
+pub mod similarity;
+pub mod transform;
+
+pub use cluster::{elbow, elbow_point, silhouette_score, ClusterError, Clustering, KMeans};
+pub use dedup::{DuplicatePair, NearDuplicateDetector};
+pub use similarity::{
+    cosine, dot, euclidean, maximal_marginal_relevance, select_top_k, top_k, Metric,
+};
+pub use transform::{l2_normalize, EmbeddingTransform};
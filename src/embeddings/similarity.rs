@@ -0,0 +1,356 @@
+//! Vector similarity for embeddings.
+//!
+//! Cosine, dot product, and euclidean distance over `f32` slices, plus top-k
+//! selection. On x86_64 CPUs with AVX2 and FMA the kernels use 256-bit SIMD,
+//! detected at runtime; elsewhere they use an eight-lane portable loop that the
+//! compiler vectorizes for the target (SSE2, NEON).
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+const LANES: usize = 8;
+
+/// Similarity or distance measure between two embeddings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Metric {
+    #[default]
+    Cosine,
+    Dot,
+    /// Euclidean distance; lower is closer
+    Euclidean,
+}
+
+impl Metric {
+    pub fn compute(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            Metric::Cosine => cosine(a, b),
+            Metric::Dot => dot(a, b),
+            Metric::Euclidean => euclidean(a, b),
+        }
+    }
+
+    /// Whether larger values mean more similar
+    pub fn higher_is_closer(&self) -> bool {
+        !matches!(self, Metric::Euclidean)
+    }
+}
+
+/// Dot product of two vectors
+///
+/// # Panics
+///
+/// Panics if the vectors have different lengths.
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "vectors must have the same length");
+    #[cfg(target_arch = "x86_64")]
+    if has_avx2_fma() {
+        // SAFETY: the CPU supports AVX2 and FMA, checked above.
+        return unsafe { avx::dot(a, b) };
+    }
+    portable::dot(a, b)
+}
+
+/// Cosine similarity of two vectors, or 0.0 if either is all zeros
+///
+/// # Panics
+///
+/// Panics if the vectors have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::embeddings::similarity::cosine;
+///
+/// assert!((cosine(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+/// assert_eq!(cosine(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+/// ```
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "vectors must have the same length");
+    #[cfg(target_arch = "x86_64")]
+    let (dot, norm_a, norm_b) = if has_avx2_fma() {
+        // SAFETY: the CPU supports AVX2 and FMA, checked above.
+        unsafe { avx::cosine_parts(a, b) }
+    } else {
+        portable::cosine_parts(a, b)
+    };
+    #[cfg(not(target_arch = "x86_64"))]
+    let (dot, norm_a, norm_b) = portable::cosine_parts(a, b);
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a.sqrt() * norm_b.sqrt())
+    }
+}
+
+/// Euclidean distance between two vectors
+///
+/// # Panics
+///
+/// Panics if the vectors have different lengths.
+pub fn euclidean(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "vectors must have the same length");
+    #[cfg(target_arch = "x86_64")]
+    if has_avx2_fma() {
+        // SAFETY: the CPU supports AVX2 and FMA, checked above.
+        return unsafe { avx::squared_distance(a, b) }.sqrt();
+    }
+    portable::squared_distance(a, b).sqrt()
+}
+
+/// Indices and scores of the `k` candidates closest to `query`, closest first
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::embeddings::similarity::{top_k, Metric};
+///
+/// let candidates = vec![vec![0.0, 1.0], vec![1.0, 0.1], vec![1.0, 0.0]];
+/// let hits = top_k(&[1.0, 0.0], &candidates, 2, Metric::Cosine);
+/// assert_eq!(hits.iter().map(|h| h.0).collect::<Vec<_>>(), vec![2, 1]);
+/// ```
+pub fn top_k<V: AsRef<[f32]>>(
+    query: &[f32],
+    candidates: &[V],
+    k: usize,
+    metric: Metric,
+) -> Vec<(usize, f32)> {
+    let scores = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i, metric.compute(query, c.as_ref())));
+    if metric.higher_is_closer() {
+        select_top_k(scores, k)
+    } else {
+        select_top_k(scores.map(|(i, d)| (i, -d)), k)
+            .into_iter()
+            .map(|(i, d)| (i, -d))
+            .collect()
+    }
+}
+
+/// The `k` highest-scoring items, highest first, in O(n log k)
+///
+/// Ties keep their input order. NaN scores sort below every number.
+pub fn select_top_k<T>(items: impl IntoIterator<Item = (T, f32)>, k: usize) -> Vec<(T, f32)> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for (seq, (item, score)) in items.into_iter().enumerate() {
+        heap.push(Reverse(Ranked { score, seq, item }));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    let mut ranked: Vec<Ranked<T>> = heap.into_iter().map(|Reverse(r)| r).collect();
+    ranked.sort_by(|a, b| b.cmp(a));
+    ranked.into_iter().map(|r| (r.item, r.score)).collect()
+}
+
+/// Maximal marginal relevance: pick `k` candidates that are relevant but diverse
+///
+/// Each step takes the candidate maximizing
+/// `lambda * cos(query, c) - (1 - lambda) * max cos(c, selected)`. A `lambda` of
+/// 1.0 ranks purely by relevance; 0.0 maximizes diversity. Returns candidate
+/// indices in selection order.
+pub fn maximal_marginal_relevance<V: AsRef<[f32]>>(
+    query: &[f32],
+    candidates: &[V],
+    k: usize,
+    lambda: f32,
+) -> Vec<usize> {
+    let relevance: Vec<f32> = candidates
+        .iter()
+        .map(|c| cosine(query, c.as_ref()))
+        .collect();
+    // Highest similarity of each candidate to anything already selected
+    let mut redundancy = vec![f32::NEG_INFINITY; candidates.len()];
+    let mut selected = Vec::with_capacity(k.min(candidates.len()));
+
+    while selected.len() < k.min(candidates.len()) {
+        let best = (0..candidates.len())
+            .filter(|i| !selected.contains(i))
+            .map(|i| {
+                let penalty = if selected.is_empty() {
+                    0.0
+                } else {
+                    redundancy[i]
+                };
+                (i, lambda * relevance[i] - (1.0 - lambda) * penalty)
+            })
+            .fold(None::<(usize, f32)>, |best, (i, score)| match best {
+                Some((_, top)) if top >= score => best,
+                _ => Some((i, score)),
+            });
+        let Some((chosen, _)) = best else { break };
+
+        selected.push(chosen);
+        for (i, candidate) in candidates.iter().enumerate() {
+            let similarity = cosine(candidates[chosen].as_ref(), candidate.as_ref());
+            redundancy[i] = redundancy[i].max(similarity);
+        }
+    }
+    selected
+}
+
+/// Heap entry ordered by score, then by earliest position
+struct Ranked<T> {
+    score: f32,
+    seq: usize,
+    item: T,
+}
+
+impl<T> Ord for Ranked<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let score = |s: f32| if s.is_nan() { f32::NEG_INFINITY } else { s };
+        score(self.score)
+            .total_cmp(&score(other.score))
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl<T> PartialOrd for Ranked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> PartialEq for Ranked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Ranked<T> {}
+
+#[cfg(target_arch = "x86_64")]
+fn has_avx2_fma() -> bool {
+    is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma")
+}
+
+mod portable {
+    use super::LANES;
+
+    pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+        let mut acc = [0.0f32; LANES];
+        let (ca, cb) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+        let tail: f32 = ca
+            .remainder()
+            .iter()
+            .zip(cb.remainder())
+            .map(|(x, y)| x * y)
+            .sum();
+        for (x, y) in ca.zip(cb) {
+            for i in 0..LANES {
+                acc[i] += x[i] * y[i];
+            }
+        }
+        acc.iter().sum::<f32>() + tail
+    }
+
+    pub fn cosine_parts(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
+        let mut dot = [0.0f32; LANES];
+        let mut norm_a = [0.0f32; LANES];
+        let mut norm_b = [0.0f32; LANES];
+        let (ca, cb) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+        let (ra, rb) = (ca.remainder(), cb.remainder());
+        for (x, y) in ca.zip(cb) {
+            for i in 0..LANES {
+                dot[i] += x[i] * y[i];
+                norm_a[i] += x[i] * x[i];
+                norm_b[i] += y[i] * y[i];
+            }
+        }
+        let mut parts = (
+            dot.iter().sum::<f32>(),
+            norm_a.iter().sum::<f32>(),
+            norm_b.iter().sum::<f32>(),
+        );
+        for (x, y) in ra.iter().zip(rb) {
+            parts.0 += x * y;
+            parts.1 += x * x;
+            parts.2 += y * y;
+        }
+        parts
+    }
+
+    pub fn squared_distance(a: &[f32], b: &[f32]) -> f32 {
+        let mut acc = [0.0f32; LANES];
+        let (ca, cb) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+        let tail: f32 = ca
+            .remainder()
+            .iter()
+            .zip(cb.remainder())
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum();
+        for (x, y) in ca.zip(cb) {
+            for i in 0..LANES {
+                let d = x[i] - y[i];
+                acc[i] += d * d;
+            }
+        }
+        acc.iter().sum::<f32>() + tail
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx {
+    use super::LANES;
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn sum(v: __m256) -> f32 {
+        let mut lanes = [0.0f32; LANES];
+        _mm256_storeu_ps(lanes.as_mut_ptr(), v);
+        lanes.iter().sum()
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    pub unsafe fn dot(a: &[f32], b: &[f32]) -> f32 {
+        let n = a.len() / LANES * LANES;
+        let mut acc = _mm256_setzero_ps();
+        for i in (0..n).step_by(LANES) {
+            let x = _mm256_loadu_ps(a.as_ptr().add(i));
+            let y = _mm256_loadu_ps(b.as_ptr().add(i));
+            acc = _mm256_fmadd_ps(x, y, acc);
+        }
+        sum(acc) + super::portable::dot(&a[n..], &b[n..])
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    pub unsafe fn cosine_parts(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
+        let n = a.len() / LANES * LANES;
+        let mut dot = _mm256_setzero_ps();
+        let mut norm_a = _mm256_setzero_ps();
+        let mut norm_b = _mm256_setzero_ps();
+        for i in (0..n).step_by(LANES) {
+            let x = _mm256_loadu_ps(a.as_ptr().add(i));
+            let y = _mm256_loadu_ps(b.as_ptr().add(i));
+            dot = _mm256_fmadd_ps(x, y, dot);
+            norm_a = _mm256_fmadd_ps(x, x, norm_a);
+            norm_b = _mm256_fmadd_ps(y, y, norm_b);
+        }
+        let tail = super::portable::cosine_parts(&a[n..], &b[n..]);
+        (
+            sum(dot) + tail.0,
+            sum(norm_a) + tail.1,
+            sum(norm_b) + tail.2,
+        )
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    pub unsafe fn squared_distance(a: &[f32], b: &[f32]) -> f32 {
+        let n = a.len() / LANES * LANES;
+        let mut acc = _mm256_setzero_ps();
+        for i in (0..n).step_by(LANES) {
+            let x = _mm256_loadu_ps(a.as_ptr().add(i));
+            let y = _mm256_loadu_ps(b.as_ptr().add(i));
+            let d = _mm256_sub_ps(x, y);
+            acc = _mm256_fmadd_ps(d, d, acc);
+        }
+        sum(acc) + super::portable::squared_distance(&a[n..], &b[n..])
+    }
+}
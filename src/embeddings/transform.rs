@@ -0,0 +1,83 @@
+//! Post-processing for embedding vectors.
+//!
+//! Matryoshka-trained models (nomic-embed-text v1.5, OpenAI text-embedding-3,
+//! mxbai-embed-large) keep most of their quality when the vector is cut to a
+//! prefix, which lets one model target stores with smaller fixed dimensions.
+
+use crate::embeddings::similarity::dot;
+use crate::models::base::{ModelError, ModelResult};
+
+/// Scale a vector to unit length in place; zero vectors are left as is
+pub fn l2_normalize(vector: &mut [f32]) {
+    let norm = dot(vector, vector).sqrt();
+    if norm > 0.0 {
+        for x in vector.iter_mut() {
+            *x /= norm;
+        }
+    }
+}
+
+/// Truncation and normalization applied to every embedding a model returns
+///
+/// Truncation happens first, so a truncated vector is renormalized when both
+/// are enabled.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::embeddings::EmbeddingTransform;
+///
+/// let transform = EmbeddingTransform::default().dimensions(2).normalize(true);
+/// let vector = transform.apply(vec![3.0, 4.0, 12.0]).unwrap();
+/// assert_eq!(vector, vec![0.6, 0.8]);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbeddingTransform {
+    pub normalize: bool,
+    pub dimensions: Option<usize>,
+}
+
+impl EmbeddingTransform {
+    /// L2-normalize every vector
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Keep only the first `dimensions` components
+    pub fn dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    /// Whether `apply` leaves vectors unchanged
+    pub fn is_identity(&self) -> bool {
+        !self.normalize && self.dimensions.is_none()
+    }
+
+    /// Transform one vector, failing if it is shorter than the target dimension
+    pub fn apply(&self, mut vector: Vec<f32>) -> ModelResult<Vec<f32>> {
+        if let Some(dimensions) = self.dimensions {
+            if vector.len() < dimensions {
+                return Err(ModelError::InvalidResponse(format!(
+                    "cannot truncate {}-dimensional embedding to {} dimensions",
+                    vector.len(),
+                    dimensions
+                )));
+            }
+            vector.truncate(dimensions);
+        }
+        if self.normalize {
+            l2_normalize(&mut vector);
+        }
+        Ok(vector)
+    }
+
+    /// Transform a batch of vectors
+    pub fn apply_all(&self, vectors: Vec<Vec<f32>>) -> ModelResult<Vec<Vec<f32>>> {
+        if self.is_identity() {
+            return Ok(vectors);
+        }
+        vectors.into_iter().map(|v| self.apply(v)).collect()
+    }
+}
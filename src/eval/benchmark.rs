@@ -0,0 +1,297 @@
+//! Side-by-side model benchmarking.
+
+use crate::core::messages::{Message, Usage};
+use crate::models::base::{BaseChatModel, ModelResult, StreamMeter};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Fires a prompt suite at several models and compares their speed, cost, and
+/// reliability
+///
+/// Every prompt is streamed to each model [`repetitions`](Self::repetitions)
+/// times. Models are benchmarked one after another so they do not compete for
+/// the same hardware; within a model up to [`concurrency`](Self::concurrency)
+/// requests run at once (default 1, for undisturbed latencies).
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::eval::Benchmark;
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let report = Benchmark::new()
+///         .model("llama3.2", Arc::new(OllamaChat::new("llama3.2")))
+///         .model("qwen2.5:7b", Arc::new(OllamaChat::new("qwen2.5:7b")))
+///         .prompt("Summarize the plot of Hamlet in two sentences.")
+///         .prompt("Write a haiku about the sea.")
+///         .repetitions(3)
+///         .run()
+///         .await;
+///     println!("{}", report.to_table());
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Benchmark {
+    models: Vec<(String, Arc<dyn BaseChatModel>)>,
+    prompts: Vec<String>,
+    /// Cost per 1K input and output tokens, by model label
+    pricing: HashMap<String, (f64, f64)>,
+    repetitions: usize,
+    concurrency: usize,
+}
+
+impl Benchmark {
+    pub fn new() -> Self {
+        Self {
+            models: Vec::new(),
+            prompts: Vec::new(),
+            pricing: HashMap::new(),
+            repetitions: 1,
+            concurrency: 1,
+        }
+    }
+
+    /// Benchmark `model`, reported as `label`
+    pub fn model(mut self, label: impl Into<String>, model: Arc<dyn BaseChatModel>) -> Self {
+        self.models.push((label.into(), model));
+        self
+    }
+
+    /// Price the model `label` per 1K input and output tokens; unpriced models
+    /// cost 0
+    pub fn pricing(
+        mut self,
+        label: impl Into<String>,
+        input_per_1k: f64,
+        output_per_1k: f64,
+    ) -> Self {
+        self.pricing
+            .insert(label.into(), (input_per_1k, output_per_1k));
+        self
+    }
+
+    pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompts.push(prompt.into());
+        self
+    }
+
+    pub fn prompts<I, S>(mut self, prompts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.prompts.extend(prompts.into_iter().map(Into::into));
+        self
+    }
+
+    /// Times each prompt is sent to each model (default 1)
+    pub fn repetitions(mut self, repetitions: usize) -> Self {
+        self.repetitions = repetitions.max(1);
+        self
+    }
+
+    /// Maximum requests in flight per model (default 1)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub async fn run(&self) -> BenchmarkReport {
+        let mut models = Vec::with_capacity(self.models.len());
+        for (label, model) in &self.models {
+            let requests = self
+                .prompts
+                .iter()
+                .flat_map(|prompt| std::iter::repeat(prompt).take(self.repetitions));
+            let samples: Vec<ModelResult<Sample>> = stream::iter(requests)
+                .map(|prompt| sample(model.as_ref(), prompt))
+                .buffer_unordered(self.concurrency)
+                .collect()
+                .await;
+            let pricing = self.pricing.get(label).copied();
+            models.push(ModelBenchmark::from_samples(label, &samples, pricing));
+        }
+        BenchmarkReport { models }
+    }
+}
+
+impl Default for Benchmark {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Measurements of one successful request
+struct Sample {
+    latency: Duration,
+    time_to_first_token: Duration,
+    tokens_per_second: f64,
+    usage: Usage,
+}
+
+async fn sample(model: &dyn BaseChatModel, prompt: &str) -> ModelResult<Sample> {
+    let messages = [Message::user(prompt)];
+    let started = Instant::now();
+    let mut meter = StreamMeter::new(started);
+    let mut usage = Usage::default();
+    let mut stream = model.stream(&messages).await?;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        meter.observe(&chunk);
+        if let Some(chunk_usage) = chunk.usage {
+            usage += chunk_usage;
+        }
+    }
+    let stats = meter.finish();
+    if usage.output_tokens == 0 {
+        usage.output_tokens = stats.output_tokens;
+        usage.total_tokens = usage.input_tokens + usage.output_tokens;
+    }
+    Ok(Sample {
+        latency: stats.duration,
+        time_to_first_token: stats.time_to_first_token,
+        tokens_per_second: stats.tokens_per_second(),
+        usage,
+    })
+}
+
+/// Latency at the 50th, 90th, and 99th percentiles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Percentiles {
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+}
+
+impl Percentiles {
+    /// Nearest-rank percentiles of `durations`, all zero if there are none
+    pub fn from_durations(durations: &[Duration]) -> Self {
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        let rank = |p: f64| {
+            if sorted.is_empty() {
+                return Duration::ZERO;
+            }
+            let index = (p * sorted.len() as f64).ceil() as usize;
+            sorted[index.clamp(1, sorted.len()) - 1]
+        };
+        Self {
+            p50: rank(0.50),
+            p90: rank(0.90),
+            p99: rank(0.99),
+        }
+    }
+}
+
+/// Results for one model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelBenchmark {
+    pub model: String,
+    pub requests: usize,
+    pub failures: usize,
+    /// End-to-end latency of successful requests
+    pub latency: Percentiles,
+    pub time_to_first_token: Percentiles,
+    /// Mean generation speed over successful requests
+    pub tokens_per_second: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost: f64,
+    /// Error messages of the failed requests
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl ModelBenchmark {
+    fn from_samples(
+        model: &str,
+        samples: &[ModelResult<Sample>],
+        pricing: Option<(f64, f64)>,
+    ) -> Self {
+        let ok: Vec<&Sample> = samples.iter().filter_map(|s| s.as_ref().ok()).collect();
+        let input_tokens: u64 = ok.iter().map(|s| s.usage.input_tokens as u64).sum();
+        let output_tokens: u64 = ok.iter().map(|s| s.usage.output_tokens as u64).sum();
+        let cost = pricing.map_or(0.0, |(input, output)| {
+            (input_tokens as f64 * input + output_tokens as f64 * output) / 1000.0
+        });
+        let tokens_per_second = if ok.is_empty() {
+            0.0
+        } else {
+            ok.iter().map(|s| s.tokens_per_second).sum::<f64>() / ok.len() as f64
+        };
+        Self {
+            model: model.to_string(),
+            requests: samples.len(),
+            failures: samples.len() - ok.len(),
+            latency: Percentiles::from_durations(&ok.iter().map(|s| s.latency).collect::<Vec<_>>()),
+            time_to_first_token: Percentiles::from_durations(
+                &ok.iter().map(|s| s.time_to_first_token).collect::<Vec<_>>(),
+            ),
+            tokens_per_second,
+            input_tokens,
+            output_tokens,
+            cost,
+            errors: samples
+                .iter()
+                .filter_map(|s| s.as_ref().err())
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
+    /// Fraction of requests that failed
+    pub fn failure_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.requests as f64
+        }
+    }
+}
+
+/// Results for every model, in the order they were added
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub models: Vec<ModelBenchmark>,
+}
+
+impl BenchmarkReport {
+    /// Results for the model `label`
+    pub fn model(&self, label: &str) -> Option<&ModelBenchmark> {
+        self.models.iter().find(|m| m.model == label)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Markdown table with one row per model
+    pub fn to_table(&self) -> String {
+        let mut table = String::from(
+            "| model | p50 ms | p90 ms | p99 ms | ttft p50 ms | tok/s | cost | failures |\n\
+             |---|---:|---:|---:|---:|---:|---:|---:|\n",
+        );
+        for m in &self.models {
+            table.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {:.1} | {:.4} | {}/{} ({:.0}%) |\n",
+                m.model,
+                m.latency.p50.as_millis(),
+                m.latency.p90.as_millis(),
+                m.latency.p99.as_millis(),
+                m.time_to_first_token.p50.as_millis(),
+                m.tokens_per_second,
+                m.cost,
+                m.failures,
+                m.requests,
+                m.failure_rate() * 100.0
+            ));
+        }
+        table
+    }
+}
@@ -0,0 +1,78 @@
+//! JSONL evaluation datasets.
+
+use crate::eval::{EvalError, EvalResult};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One dataset entry: an input and what a good output looks like
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Example {
+    pub input: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// Passages the output should be grounded in
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
+}
+
+impl Example {
+    pub fn new(input: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+}
+
+/// Examples to evaluate, one JSON object per line
+///
+/// Each line holds an `input` and optionally an `expected` output and a
+/// `context` array of passages; blank lines are skipped.
+///
+/// ```text
+/// {"input": "Capital of France?", "expected": "Paris"}
+/// {"input": "2 + 2?", "expected": "4"}
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dataset {
+    pub examples: Vec<Example>,
+}
+
+impl Dataset {
+    pub fn new(examples: Vec<Example>) -> Self {
+        Self { examples }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> EvalResult<Self> {
+        Self::from_jsonl(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse JSONL text, failing on the first invalid line
+    pub fn from_jsonl(text: &str) -> EvalResult<Self> {
+        let mut examples = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let example = serde_json::from_str(line).map_err(|e| EvalError::Dataset {
+                line: number + 1,
+                message: e.to_string(),
+            })?;
+            examples.push(example);
+        }
+        Ok(Self { examples })
+    }
+
+    pub fn len(&self) -> usize {
+        self.examples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.examples.is_empty()
+    }
+}
@@ -0,0 +1,273 @@
+//! LLM-as-judge grading.
+
+use crate::core::messages::Message;
+use crate::eval::{EvalCase, EvalError, EvalResult, Grader, Score};
+use crate::models::base::BaseChatModel;
+use crate::output_parsers::repair_json;
+use async_trait::async_trait;
+use regex::Regex;
+use std::sync::{Arc, OnceLock};
+
+/// Scoring instructions for a judge model
+///
+/// The judge rates a case on the rubric's levels, numbered from 1; the rating
+/// is scaled so the lowest level scores 0.0 and the highest 1.0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rubric {
+    name: String,
+    criterion: String,
+    levels: Vec<String>,
+    needs_reference: bool,
+    needs_context: bool,
+}
+
+impl Rubric {
+    /// Rubric `name` judging `criterion`, with the levels 1 (fails) to 5
+    /// (fully meets it)
+    pub fn new(name: impl Into<String>, criterion: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            criterion: criterion.into(),
+            levels: vec![
+                "Fails the criterion entirely".to_string(),
+                "Mostly fails the criterion".to_string(),
+                "Partially meets the criterion".to_string(),
+                "Mostly meets the criterion".to_string(),
+                "Fully meets the criterion".to_string(),
+            ],
+            needs_reference: false,
+            needs_context: false,
+        }
+    }
+
+    /// Replace the levels, worst first; at least two are needed
+    pub fn levels<I, S>(mut self, levels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.levels = levels.into_iter().map(Into::into).collect();
+        assert!(self.levels.len() >= 2, "a rubric needs at least two levels");
+        self
+    }
+
+    /// Refuse to grade cases without a reference answer
+    pub fn requires_reference(mut self) -> Self {
+        self.needs_reference = true;
+        self
+    }
+
+    /// Refuse to grade cases without context
+    pub fn requires_context(mut self) -> Self {
+        self.needs_context = true;
+        self
+    }
+
+    /// Whether the response answers the input correctly, compared with the
+    /// reference answer when there is one
+    pub fn correctness() -> Self {
+        Self::new(
+            "correctness",
+            "The response answers the question correctly and completely. If a \
+             reference answer is given, the response agrees with it; wording may \
+             differ.",
+        )
+        .levels([
+            "Wrong or does not answer the question",
+            "Mostly wrong, with major errors",
+            "Partially correct, with significant errors or omissions",
+            "Correct with minor errors or omissions",
+            "Fully correct and complete",
+        ])
+    }
+
+    /// Whether every claim in the response is supported by the context
+    pub fn groundedness() -> Self {
+        Self::new(
+            "groundedness",
+            "Every factual claim in the response is supported by the context. \
+             Claims that may be true but do not appear in the context count as \
+             unsupported.",
+        )
+        .levels([
+            "Mostly unsupported or contradicts the context",
+            "Several unsupported claims",
+            "Some unsupported claims",
+            "One minor unsupported detail",
+            "Every claim is supported by the context",
+        ])
+        .requires_context()
+    }
+
+    /// Whether the response is written in the `desired` tone, e.g. "friendly
+    /// and concise"
+    pub fn tone(desired: &str) -> Self {
+        Self::new(
+            "tone",
+            format!("The response is written in this tone: {desired}. Judge style, not accuracy."),
+        )
+        .levels([
+            "Entirely the wrong tone",
+            "Mostly the wrong tone",
+            "Mixed tone",
+            "Mostly the right tone",
+            "Exactly the right tone throughout",
+        ])
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn check(&self, case: &EvalCase) -> EvalResult<()> {
+        let missing = if self.needs_reference && case.expected.is_none() {
+            Some("a reference answer")
+        } else if self.needs_context && case.context.is_empty() {
+            Some("context")
+        } else {
+            None
+        };
+        match missing {
+            Some(field) => Err(EvalError::MissingField {
+                grader: self.name.clone(),
+                field,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn prompt(&self, case: &EvalCase) -> [Message; 2] {
+        let levels = self
+            .levels
+            .iter()
+            .enumerate()
+            .map(|(i, level)| format!("{}: {}", i + 1, level))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let system = format!(
+            "You are an impartial evaluator. Grade the response against this \
+             criterion:\n{}\n\nScore levels:\n{}\n\nThink about the response, then \
+             reply with only a JSON object: {{\"reasoning\": \"<one or two sentences>\", \
+             \"score\": <1-{}>}}",
+            self.criterion,
+            levels,
+            self.levels.len()
+        );
+
+        let mut user = format!("Input:\n{}\n\n", case.input);
+        if !case.context.is_empty() {
+            user.push_str(&format!("Context:\n{}\n\n", case.context.join("\n\n")));
+        }
+        if let Some(expected) = &case.expected {
+            user.push_str(&format!("Reference answer:\n{}\n\n", expected));
+        }
+        user.push_str(&format!("Response:\n{}", case.output));
+        [Message::system(system), Message::user(user)]
+    }
+
+    /// Read the rating and reasoning from a judge reply, scaled to `0.0..=1.0`
+    fn parse(&self, reply: &str) -> EvalResult<(f32, String)> {
+        let json = repair_json(reply).filter(|json| json.get("score").is_some());
+        let (rating, reasoning) = match json {
+            Some(json) => (
+                json["score"]
+                    .as_f64()
+                    .or_else(|| json["score"].as_str()?.trim().parse().ok()),
+                json["reasoning"].as_str().unwrap_or_default().to_string(),
+            ),
+            None => {
+                static SCORE: OnceLock<Regex> = OnceLock::new();
+                let score =
+                    SCORE.get_or_init(|| Regex::new(r"(?i)score\W*(\d+(?:\.\d+)?)").unwrap());
+                let rating = score.captures(reply).and_then(|c| c[1].parse::<f64>().ok());
+                (rating, reply.trim().to_string())
+            }
+        };
+
+        let top = self.levels.len() as f64;
+        match rating {
+            Some(rating) if (1.0..=top).contains(&rating) => {
+                Ok((((rating - 1.0) / (top - 1.0)) as f32, reasoning))
+            }
+            Some(rating) => Err(EvalError::InvalidJudgement(format!(
+                "score {rating} is outside 1..={top}"
+            ))),
+            None => Err(EvalError::InvalidJudgement(format!(
+                "no score in reply: {}",
+                reply.trim()
+            ))),
+        }
+    }
+}
+
+/// Grader asking a judge model to score cases on a [`Rubric`]
+///
+/// Replies are expected as `{"reasoning": ..., "score": n}`; a bare
+/// `score: n` is also accepted. Replies without a usable score fail with
+/// [`EvalError::InvalidJudgement`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::eval::{EvalCase, Grader, JudgeGrader};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let grader = JudgeGrader::groundedness(Arc::new(OllamaChat::new("llama3.2")));
+///     let case = EvalCase::new("When was Rust 1.0?", "May 2015, in Berlin.")
+///         .with_context("Rust 1.0 was released on 15 May 2015.");
+///     let score = grader.grade(&case).await?;
+///     println!("{:.2}: {}", score.value, score.reasoning);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct JudgeGrader {
+    judge: Arc<dyn BaseChatModel>,
+    rubric: Rubric,
+}
+
+impl JudgeGrader {
+    pub fn new(judge: Arc<dyn BaseChatModel>, rubric: Rubric) -> Self {
+        Self { judge, rubric }
+    }
+
+    /// Judge with [`Rubric::correctness`]
+    pub fn correctness(judge: Arc<dyn BaseChatModel>) -> Self {
+        Self::new(judge, Rubric::correctness())
+    }
+
+    /// Judge with [`Rubric::groundedness`]
+    pub fn groundedness(judge: Arc<dyn BaseChatModel>) -> Self {
+        Self::new(judge, Rubric::groundedness())
+    }
+
+    /// Judge with [`Rubric::tone`]
+    pub fn tone(judge: Arc<dyn BaseChatModel>, desired: &str) -> Self {
+        Self::new(judge, Rubric::tone(desired))
+    }
+
+    pub fn rubric(&self) -> &Rubric {
+        &self.rubric
+    }
+}
+
+#[async_trait]
+impl Grader for JudgeGrader {
+    fn name(&self) -> &str {
+        self.rubric.name()
+    }
+
+    async fn grade(&self, case: &EvalCase) -> EvalResult<Score> {
+        self.rubric.check(case)?;
+        let reply = self.judge.invoke(&self.rubric.prompt(case)).await?.content;
+        let (value, reasoning) = self.rubric.parse(&reply)?;
+        Ok(Score {
+            grader: self.rubric.name.clone(),
+            value,
+            reasoning,
+        })
+    }
+}
@@ -0,0 +1,104 @@
+//! Reference-based graders.
+
+use crate::embeddings::cosine;
+use crate::eval::{EvalCase, EvalError, EvalResult, Grader, Score};
+use crate::models::base::{BaseEmbedding, ModelError};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+fn expected<'a>(grader: &str, case: &'a EvalCase) -> EvalResult<&'a str> {
+    case.expected
+        .as_deref()
+        .ok_or_else(|| EvalError::MissingField {
+            grader: grader.to_string(),
+            field: "a reference answer",
+        })
+}
+
+/// Scores 1.0 when the output equals the reference answer, else 0.0
+///
+/// Surrounding whitespace is ignored, runs of whitespace compare equal, and
+/// case is ignored unless [`case_sensitive`](Self::case_sensitive) is set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExactMatch {
+    case_sensitive: bool,
+}
+
+impl ExactMatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn case_sensitive(mut self) -> Self {
+        self.case_sensitive = true;
+        self
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if self.case_sensitive {
+            text
+        } else {
+            text.to_lowercase()
+        }
+    }
+}
+
+#[async_trait]
+impl Grader for ExactMatch {
+    fn name(&self) -> &str {
+        "exact_match"
+    }
+
+    async fn grade(&self, case: &EvalCase) -> EvalResult<Score> {
+        let expected = expected(self.name(), case)?;
+        let matched = self.normalize(&case.output) == self.normalize(expected);
+        Ok(Score {
+            grader: self.name().to_string(),
+            value: if matched { 1.0 } else { 0.0 },
+            reasoning: String::new(),
+        })
+    }
+}
+
+/// Scores the cosine similarity between the embeddings of the output and the
+/// reference answer, with negative similarities scoring 0.0
+#[derive(Clone)]
+pub struct EmbeddingSimilarity {
+    embeddings: Arc<dyn BaseEmbedding>,
+}
+
+impl EmbeddingSimilarity {
+    pub fn new(embeddings: Arc<dyn BaseEmbedding>) -> Self {
+        Self { embeddings }
+    }
+}
+
+#[async_trait]
+impl Grader for EmbeddingSimilarity {
+    fn name(&self) -> &str {
+        "embedding_similarity"
+    }
+
+    async fn grade(&self, case: &EvalCase) -> EvalResult<Score> {
+        let expected = expected(self.name(), case)?;
+        let vectors = self
+            .embeddings
+            .embed(&[case.output.clone(), expected.to_string()])
+            .await?;
+        let (output, expected) = match vectors.as_slice() {
+            [output, expected] if output.len() == expected.len() => (output, expected),
+            _ => {
+                return Err(ModelError::InvalidResponse(
+                    "expected two embeddings of the same dimension".to_string(),
+                )
+                .into())
+            }
+        };
+        Ok(Score {
+            grader: self.name().to_string(),
+            value: cosine(output, expected).clamp(0.0, 1.0),
+            reasoning: String::new(),
+        })
+    }
+}
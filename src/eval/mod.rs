@@ -0,0 +1,280 @@
+//! Evaluation for AgenticOptio.
+//!
+//! A [`Grader`] scores one [`EvalCase`] — an input, the output under test, and
+//! optionally a reference answer and retrieved context — from 0.0 (worst) to
+//! 1.0 (best). [`JudgeGrader`] asks a judge model to apply a [`Rubric`], with
+//! built-in rubrics for correctness, groundedness, and tone. An [`Evaluator`]
+//! runs several graders over many cases and aggregates the scores into an
+//! [`EvalReport`]; an [`EvalRunner`] first produces the outputs by running a
+//! model or agent over a JSONL [`Dataset`]. A [`Benchmark`] compares the
+//! latency, throughput, cost, and failure rate of several models instead.
+
+pub mod benchmark;
+pub mod dataset;
+pub mod judge;
+pub mod metrics;
+pub mod runner;
+
+pub use benchmark::{Benchmark, BenchmarkReport, ModelBenchmark, Percentiles};
+pub use dataset::{Dataset, Example};
+pub use judge::{JudgeGrader, Rubric};
+pub use metrics::{EmbeddingSimilarity, ExactMatch};
+pub use runner::{EvalRunner, EvalTarget};
+
+use crate::agents::AgentError;
+use crate::models::base::ModelError;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Error type for evaluation
+#[derive(Debug, thiserror::Error)]
+pub enum EvalError {
+    #[error("Model error: {0}")]
+    Model(#[from] ModelError),
+
+    #[error("Agent error: {0}")]
+    Agent(#[from] AgentError),
+
+    #[error("Dataset I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid dataset line {line}: {message}")]
+    Dataset { line: usize, message: String },
+
+    #[error("Unusable judgement: {0}")]
+    InvalidJudgement(String),
+
+    #[error("Grader {grader} needs a case with {field}")]
+    MissingField { grader: String, field: &'static str },
+}
+
+pub type EvalResult<T> = Result<T, EvalError>;
+
+/// One example to grade
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EvalCase {
+    pub input: String,
+    /// Output of the model or agent under test
+    pub output: String,
+    /// Reference answer, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// Retrieved passages the output should be grounded in
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
+}
+
+impl EvalCase {
+    pub fn new(input: impl Into<String>, output: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    pub fn with_context(mut self, passage: impl Into<String>) -> Self {
+        self.context.push(passage.into());
+        self
+    }
+}
+
+/// A grader's verdict on one case
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    pub grader: String,
+    /// From 0.0 (worst) to 1.0 (best)
+    pub value: f32,
+    /// Why the grader chose the score, if it says
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub reasoning: String,
+}
+
+/// Scores one case along one dimension
+#[async_trait]
+pub trait Grader: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn grade(&self, case: &EvalCase) -> EvalResult<Score>;
+}
+
+/// Statistics of one grader's scores across cases
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreSummary {
+    pub count: usize,
+    pub mean: f32,
+    pub min: f32,
+    pub max: f32,
+    pub std_dev: f32,
+}
+
+impl ScoreSummary {
+    /// Summarize `values`, or `None` if there are none
+    pub fn from_values(values: &[f32]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let count = values.len();
+        let mean = values.iter().sum::<f32>() / count as f32;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / count as f32;
+        Some(Self {
+            count,
+            mean,
+            min: values.iter().copied().fold(f32::INFINITY, f32::min),
+            max: values.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            std_dev: variance.sqrt(),
+        })
+    }
+}
+
+/// Scores of every grader for one case
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaseResult {
+    pub case: EvalCase,
+    pub scores: Vec<Score>,
+    /// Graders that failed on this case, with their errors
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<String, String>,
+}
+
+impl CaseResult {
+    /// Score given by the grader `name`
+    pub fn score(&self, name: &str) -> Option<f32> {
+        self.scores
+            .iter()
+            .find(|s| s.grader == name)
+            .map(|s| s.value)
+    }
+}
+
+/// Per-case scores plus aggregates for each grader
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EvalReport {
+    pub results: Vec<CaseResult>,
+}
+
+impl EvalReport {
+    /// Summary of each grader's scores, by grader name
+    pub fn summary(&self) -> BTreeMap<String, ScoreSummary> {
+        let mut values: BTreeMap<String, Vec<f32>> = BTreeMap::new();
+        for score in self.results.iter().flat_map(|r| &r.scores) {
+            values
+                .entry(score.grader.clone())
+                .or_default()
+                .push(score.value);
+        }
+        values
+            .into_iter()
+            .filter_map(|(name, values)| Some((name, ScoreSummary::from_values(&values)?)))
+            .collect()
+    }
+
+    /// Fraction of graded cases where grader `name` scored at least
+    /// `threshold`
+    pub fn pass_rate(&self, name: &str, threshold: f32) -> Option<f32> {
+        let scores: Vec<f32> = self.results.iter().filter_map(|r| r.score(name)).collect();
+        if scores.is_empty() {
+            return None;
+        }
+        let passed = scores.iter().filter(|&&s| s >= threshold).count();
+        Some(passed as f32 / scores.len() as f32)
+    }
+
+    /// Number of grader failures across all cases
+    pub fn error_count(&self) -> usize {
+        self.results.iter().map(|r| r.errors.len()).sum()
+    }
+
+    /// Report with the summary and every case, as JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&serde_json::json!({
+            "summary": self.summary(),
+            "errors": self.error_count(),
+            "results": self.results,
+        }))
+    }
+
+    /// Write the report as JSON
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json()?)
+    }
+}
+
+/// Runs a set of graders over cases
+///
+/// A grader failing on one case is recorded in that case's
+/// [`errors`](CaseResult::errors) rather than aborting the evaluation.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::eval::{EvalCase, Evaluator, JudgeGrader};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let judge = Arc::new(OllamaChat::new("llama3.2"));
+///     let evaluator = Evaluator::new()
+///         .grader(JudgeGrader::correctness(judge.clone()))
+///         .grader(JudgeGrader::tone(judge, "friendly and concise"));
+///
+///     let cases = vec![EvalCase::new("Capital of France?", "Paris.").with_expected("Paris")];
+///     let report = evaluator.evaluate(&cases).await;
+///     for (grader, summary) in report.summary() {
+///         println!("{grader}: {:.2}", summary.mean);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Evaluator {
+    graders: Vec<Arc<dyn Grader>>,
+}
+
+impl Evaluator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn grader(mut self, grader: impl Grader + 'static) -> Self {
+        self.graders.push(Arc::new(grader));
+        self
+    }
+
+    /// Grade one case with every grader
+    pub async fn evaluate_case(&self, case: &EvalCase) -> CaseResult {
+        let mut result = CaseResult {
+            case: case.clone(),
+            scores: Vec::new(),
+            errors: BTreeMap::new(),
+        };
+        for grader in &self.graders {
+            match grader.grade(case).await {
+                Ok(score) => result.scores.push(score),
+                Err(e) => {
+                    result
+                        .errors
+                        .insert(grader.name().to_string(), e.to_string());
+                }
+            }
+        }
+        result
+    }
+
+    /// Grade every case, in order
+    pub async fn evaluate(&self, cases: &[EvalCase]) -> EvalReport {
+        let mut results = Vec::with_capacity(cases.len());
+        for case in cases {
+            results.push(self.evaluate_case(case).await);
+        }
+        EvalReport { results }
+    }
+}
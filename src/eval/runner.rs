@@ -0,0 +1,116 @@
+//! Running a model or agent over a dataset.
+
+use crate::agents::Agent;
+use crate::core::messages::Message;
+use crate::eval::{
+    CaseResult, Dataset, EvalCase, EvalReport, EvalResult, Evaluator, Example, Grader,
+};
+use crate::models::base::BaseChatModel;
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Something that answers an evaluation input
+#[async_trait]
+pub trait EvalTarget: Send + Sync {
+    async fn answer(&self, input: &str) -> EvalResult<String>;
+}
+
+/// Answers with a single model call on the input as a user message
+#[async_trait]
+impl<M: BaseChatModel + ?Sized> EvalTarget for Arc<M> {
+    async fn answer(&self, input: &str) -> EvalResult<String> {
+        Ok(self.invoke(&[Message::user(input)]).await?.content)
+    }
+}
+
+/// Answers with the output of a full agent run, tools included
+#[async_trait]
+impl EvalTarget for Agent {
+    async fn answer(&self, input: &str) -> EvalResult<String> {
+        Ok(self.run(input).await?.output)
+    }
+}
+
+/// Runs a model or agent over every example of a [`Dataset`] and grades the
+/// outputs
+///
+/// Up to [`concurrency`](Self::concurrency) examples are answered and graded
+/// at once; results keep the dataset order. An example the target fails on
+/// gets an empty output and its error under `"target"` in
+/// [`errors`](CaseResult::errors), and is not graded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::eval::{Dataset, EvalRunner, ExactMatch, JudgeGrader};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let dataset = Dataset::load("evals/capitals.jsonl")?;
+///     let report = EvalRunner::new(Arc::new(OllamaChat::new("llama3.2")))
+///         .grader(ExactMatch::new())
+///         .grader(JudgeGrader::correctness(Arc::new(OllamaChat::new("llama3.1:70b"))))
+///         .concurrency(8)
+///         .run(&dataset)
+///         .await;
+///     report.save("evals/capitals-report.json")?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct EvalRunner {
+    target: Arc<dyn EvalTarget>,
+    evaluator: Evaluator,
+    concurrency: usize,
+}
+
+impl EvalRunner {
+    pub fn new(target: impl EvalTarget + 'static) -> Self {
+        Self {
+            target: Arc::new(target),
+            evaluator: Evaluator::new(),
+            concurrency: 4,
+        }
+    }
+
+    pub fn grader(mut self, grader: impl Grader + 'static) -> Self {
+        self.evaluator = self.evaluator.grader(grader);
+        self
+    }
+
+    /// Maximum examples in flight at once (default 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub async fn run(&self, dataset: &Dataset) -> EvalReport {
+        let results = stream::iter(&dataset.examples)
+            .map(|example| self.run_example(example))
+            .buffered(self.concurrency)
+            .collect()
+            .await;
+        EvalReport { results }
+    }
+
+    async fn run_example(&self, example: &Example) -> CaseResult {
+        let mut case = EvalCase::new(example.input.clone(), "");
+        case.expected = example.expected.clone();
+        case.context = example.context.clone();
+        match self.target.answer(&example.input).await {
+            Ok(output) => {
+                case.output = output;
+                self.evaluator.evaluate_case(&case).await
+            }
+            Err(e) => CaseResult {
+                case,
+                scores: Vec::new(),
+                errors: BTreeMap::from([("target".to_string(), e.to_string())]),
+            },
+        }
+    }
+}
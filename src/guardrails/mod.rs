@@ -0,0 +1,424 @@
+//! Output guardrails for AgenticOptio.
+//!
+//! Guardrails validate model output before it reaches the caller. Each guardrail
+//! is paired with a [`GuardrailAction`] deciding what happens on failure: block
+//! the response, retry with the failure as feedback, or redact the offending text.
+//! [`GuardedChatModel`] applies them to any chat model, and agents accept them via
+//! [`AgentBuilder::guardrail`](crate::agents::AgentBuilder::guardrail).
+
+pub mod injection;
+pub mod pii;
+
+pub use injection::{InjectionMatch, InjectionPolicy, InjectionReport, InjectionScanner};
+pub use pii::{PiiRedactingChatModel, PiiRedactor, RedactionMap};
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BoxStream, ModelError, ModelResult};
+use crate::output_parsers::extract_code_blocks;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use regex::Regex;
+use std::sync::Arc;
+
+/// Result of running a guardrail on model output
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuardrailVerdict {
+    Pass,
+    Fail {
+        reason: String,
+        /// Sanitized output, if the guardrail knows how to redact
+        redacted: Option<String>,
+    },
+}
+
+impl GuardrailVerdict {
+    pub fn fail(reason: impl Into<String>) -> Self {
+        GuardrailVerdict::Fail {
+            reason: reason.into(),
+            redacted: None,
+        }
+    }
+
+    pub fn is_pass(&self) -> bool {
+        matches!(self, GuardrailVerdict::Pass)
+    }
+}
+
+/// What to do when a guardrail fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuardrailAction {
+    /// Return a `GuardrailViolation` error
+    Block,
+    /// Re-invoke the model with the failure reason as feedback
+    Retry { max_retries: u32 },
+    /// Replace the output with the guardrail's redacted text, blocking if none
+    Redact,
+}
+
+/// Base trait for output validators
+#[async_trait]
+pub trait Guardrail: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn validate(&self, output: &str) -> GuardrailVerdict;
+}
+
+/// Rejects output matching any deny-listed pattern
+#[derive(Debug, Clone)]
+pub struct RegexGuardrail {
+    name: String,
+    patterns: Vec<Regex>,
+    replacement: String,
+}
+
+impl RegexGuardrail {
+    /// Create a deny-list guardrail, failing if any pattern is invalid
+    pub fn new<I, S>(patterns: I) -> Result<Self, regex::Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let patterns = patterns
+            .into_iter()
+            .map(|p| Regex::new(p.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            name: "regex_deny_list".to_string(),
+            patterns,
+            replacement: "[REDACTED]".to_string(),
+        })
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Text substituted for matches when redacting
+    pub fn with_replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = replacement.into();
+        self
+    }
+}
+
+#[async_trait]
+impl Guardrail for RegexGuardrail {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn validate(&self, output: &str) -> GuardrailVerdict {
+        let matched: Vec<&str> = self
+            .patterns
+            .iter()
+            .filter(|p| p.is_match(output))
+            .map(|p| p.as_str())
+            .collect();
+
+        if matched.is_empty() {
+            return GuardrailVerdict::Pass;
+        }
+
+        let mut redacted = output.to_string();
+        for pattern in &self.patterns {
+            redacted = pattern
+                .replace_all(&redacted, self.replacement.as_str())
+                .into_owned();
+        }
+
+        GuardrailVerdict::Fail {
+            reason: format!("output matched denied pattern(s): {}", matched.join(", ")),
+            redacted: Some(redacted),
+        }
+    }
+}
+
+/// Requires output to be JSON matching a schema
+///
+/// Supports the commonly used subset of JSON Schema: `type`, `enum`,
+/// `properties`, `required`, `additionalProperties: false`, `items`,
+/// `minimum`, and `maximum`. Markdown code fences around the JSON are ignored.
+#[derive(Debug, Clone)]
+pub struct JsonSchemaGuardrail {
+    schema: serde_json::Value,
+}
+
+impl JsonSchemaGuardrail {
+    pub fn new(schema: serde_json::Value) -> Self {
+        Self { schema }
+    }
+}
+
+#[async_trait]
+impl Guardrail for JsonSchemaGuardrail {
+    fn name(&self) -> &str {
+        "json_schema"
+    }
+
+    async fn validate(&self, output: &str) -> GuardrailVerdict {
+        let fenced = output
+            .trim_start()
+            .starts_with("```")
+            .then(|| extract_code_blocks(output).into_iter().next())
+            .flatten();
+        let json = fenced.as_ref().map_or(output.trim(), |block| &block.code);
+        let value: serde_json::Value = match serde_json::from_str(json) {
+            Ok(value) => value,
+            Err(e) => return GuardrailVerdict::fail(format!("output is not valid JSON: {}", e)),
+        };
+
+        match validate_schema(&value, &self.schema, "$") {
+            Ok(()) => GuardrailVerdict::Pass,
+            Err(reason) => GuardrailVerdict::fail(reason),
+        }
+    }
+}
+
+fn type_matches(value: &serde_json::Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn validate_schema(
+    value: &serde_json::Value,
+    schema: &serde_json::Value,
+    path: &str,
+) -> Result<(), String> {
+    match schema.get("type") {
+        Some(serde_json::Value::String(t)) if !type_matches(value, t) => {
+            return Err(format!("{}: expected {}", path, t));
+        }
+        Some(serde_json::Value::Array(types))
+            if !types
+                .iter()
+                .filter_map(|t| t.as_str())
+                .any(|t| type_matches(value, t)) =>
+        {
+            return Err(format!("{}: value does not match any allowed type", path));
+        }
+        _ => {}
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            return Err(format!("{}: value not in enum", path));
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64()) {
+            if number < min {
+                return Err(format!("{}: {} is below minimum {}", path, number, min));
+            }
+        }
+        if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64()) {
+            if number > max {
+                return Err(format!("{}: {} is above maximum {}", path, number, max));
+            }
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+            for key in required.iter().filter_map(|k| k.as_str()) {
+                if !object.contains_key(key) {
+                    return Err(format!("{}: missing required property '{}'", path, key));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+        for (key, child) in object {
+            match properties.and_then(|p| p.get(key)) {
+                Some(child_schema) => {
+                    validate_schema(child, child_schema, &format!("{}.{}", path, key))?
+                }
+                None if schema.get("additionalProperties")
+                    == Some(&serde_json::Value::Bool(false)) =>
+                {
+                    return Err(format!("{}: unexpected property '{}'", path, key));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            validate_schema(item, item_schema, &format!("{}[{}]", path, i))?;
+        }
+    }
+
+    Ok(())
+}
+
+type ValidatorFn = dyn Fn(String) -> BoxFuture<'static, GuardrailVerdict> + Send + Sync;
+
+/// Guardrail backed by a custom async validator
+pub struct FnGuardrail {
+    name: String,
+    func: Arc<ValidatorFn>,
+}
+
+impl FnGuardrail {
+    pub fn new<F, Fut>(name: impl Into<String>, func: F) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = GuardrailVerdict> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            func: Arc::new(move |output| Box::pin(func(output))),
+        }
+    }
+}
+
+#[async_trait]
+impl Guardrail for FnGuardrail {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn validate(&self, output: &str) -> GuardrailVerdict {
+        (self.func)(output.to_string()).await
+    }
+}
+
+/// Chat model wrapper that validates every response against guardrails
+///
+/// Responses that only carry tool calls are passed through unchecked. Streaming
+/// buffers the full response so it can be validated before any text is emitted.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::guardrails::{GuardedChatModel, GuardrailAction, RegexGuardrail};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// let deny = RegexGuardrail::new([r"(?i)password"]).unwrap();
+/// let llm = GuardedChatModel::new(Arc::new(OllamaChat::new("llama3.2")))
+///     .guardrail(deny, GuardrailAction::Redact);
+/// ```
+#[derive(Clone)]
+pub struct GuardedChatModel {
+    inner: Arc<dyn BaseChatModel>,
+    guardrails: Vec<(Arc<dyn Guardrail>, GuardrailAction)>,
+}
+
+impl GuardedChatModel {
+    pub fn new(inner: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            inner,
+            guardrails: Vec::new(),
+        }
+    }
+
+    pub fn guardrail(self, guardrail: impl Guardrail + 'static, action: GuardrailAction) -> Self {
+        self.guardrail_arc(Arc::new(guardrail), action)
+    }
+
+    pub fn guardrail_arc(mut self, guardrail: Arc<dyn Guardrail>, action: GuardrailAction) -> Self {
+        self.guardrails.push((guardrail, action));
+        self
+    }
+
+    async fn guarded(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        let mut conversation = messages.to_vec();
+        let mut retries = 0;
+
+        'attempt: loop {
+            let mut response = self.inner.invoke_with_tools(&conversation, tools).await?;
+            if response.content.is_empty() && !response.tool_calls.is_empty() {
+                return Ok(response);
+            }
+
+            for (guardrail, action) in &self.guardrails {
+                let (reason, redacted) = match guardrail.validate(&response.content).await {
+                    GuardrailVerdict::Pass => continue,
+                    GuardrailVerdict::Fail { reason, redacted } => (reason, redacted),
+                };
+
+                match (action, redacted) {
+                    (GuardrailAction::Redact, Some(redacted)) => response.content = redacted,
+                    (GuardrailAction::Retry { max_retries }, _) if retries < *max_retries => {
+                        retries += 1;
+                        #[cfg(feature = "tracing")]
+                        tracing::info!(
+                            guardrail = guardrail.name(),
+                            attempt = retries,
+                            max_retries = *max_retries,
+                            reason = %reason,
+                            "retrying after guardrail failure"
+                        );
+                        conversation.push(Message::AI(response));
+                        conversation.push(Message::user(format!(
+                            "Your previous response failed the '{}' check: {}. \
+                             Please try again.",
+                            guardrail.name(),
+                            reason
+                        )));
+                        continue 'attempt;
+                    }
+                    _ => {
+                        return Err(ModelError::GuardrailViolation(format!(
+                            "{}: {}",
+                            guardrail.name(),
+                            reason
+                        )))
+                    }
+                }
+            }
+
+            return Ok(response);
+        }
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for GuardedChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        self.guarded(messages, &[]).await
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        self.guarded(messages, tools).await
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let response = self.guarded(messages, &[]).await?;
+        Ok(Box::pin(futures::stream::once(async move { Ok(response) })))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
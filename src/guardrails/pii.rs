@@ -0,0 +1,346 @@
+//! PII redaction for AgenticOptio.
+//!
+//! [`PiiRedactor`] replaces emails, phone numbers, credit card numbers, and custom
+//! patterns with stable placeholders such as `[EMAIL_1]`. The placeholders are
+//! recorded in a [`RedactionMap`] so the original values can be restored
+//! client-side. [`PiiRedactingChatModel`] applies the redactor to outgoing prompts
+//! and incoming completions of any chat model.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::guardrails::{Guardrail, GuardrailVerdict};
+use crate::models::base::{BaseChatModel, BoxStream, ModelResult};
+use async_trait::async_trait;
+use futures::stream::StreamExt;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const EMAIL_PATTERN: &str = r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}";
+const CREDIT_CARD_PATTERN: &str = r"\b(?:\d[ -]?){12,18}\d\b";
+const PHONE_PATTERN: &str = r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{3}\)|\b\d{3})[ .-]?\d{3}[ .-]?\d{4}\b";
+
+#[derive(Debug, Clone)]
+struct PiiRule {
+    label: String,
+    pattern: Regex,
+    validator: Option<fn(&str) -> bool>,
+}
+
+/// Luhn checksum, used to avoid redacting arbitrary digit runs as card numbers
+fn luhn_valid(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Placeholder-to-value table produced by redaction
+#[derive(Debug, Clone, Default)]
+pub struct RedactionMap {
+    originals: HashMap<String, String>,
+    placeholders: HashMap<String, String>,
+    counters: HashMap<String, usize>,
+}
+
+impl RedactionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Placeholder for a value, reusing the existing one if seen before
+    fn placeholder_for(&mut self, label: &str, value: &str) -> String {
+        if let Some(existing) = self.placeholders.get(value) {
+            return existing.clone();
+        }
+
+        let counter = self.counters.entry(label.to_string()).or_insert(0);
+        *counter += 1;
+        let placeholder = format!("[{}_{}]", label, counter);
+        self.placeholders
+            .insert(value.to_string(), placeholder.clone());
+        self.originals
+            .insert(placeholder.clone(), value.to_string());
+        placeholder
+    }
+
+    /// Original value behind a placeholder
+    pub fn get(&self, placeholder: &str) -> Option<&str> {
+        self.originals.get(placeholder).map(String::as_str)
+    }
+
+    /// Replace every known placeholder in `text` with its original value
+    pub fn rehydrate(&self, text: &str) -> String {
+        self.originals
+            .iter()
+            .fold(text.to_string(), |text, (placeholder, original)| {
+                text.replace(placeholder, original)
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.originals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.originals.is_empty()
+    }
+}
+
+/// Regex-based PII scrubber
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::guardrails::{PiiRedactor, RedactionMap};
+///
+/// let redactor = PiiRedactor::new();
+/// let mut map = RedactionMap::new();
+/// let scrubbed = redactor.redact("Mail ada@example.com", &mut map);
+/// assert_eq!(scrubbed, "Mail [EMAIL_1]");
+/// assert_eq!(map.rehydrate(&scrubbed), "Mail ada@example.com");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PiiRedactor {
+    rules: Vec<PiiRule>,
+}
+
+impl PiiRedactor {
+    /// Create a redactor for emails, credit card numbers, and phone numbers
+    pub fn new() -> Self {
+        let rule = |label: &str, pattern: &str, validator: Option<fn(&str) -> bool>| PiiRule {
+            label: label.to_string(),
+            pattern: Regex::new(pattern).expect("built-in PII pattern is valid"),
+            validator,
+        };
+
+        Self {
+            rules: vec![
+                rule("EMAIL", EMAIL_PATTERN, None),
+                rule("CREDIT_CARD", CREDIT_CARD_PATTERN, Some(luhn_valid)),
+                rule("PHONE", PHONE_PATTERN, None),
+            ],
+        }
+    }
+
+    /// Create a redactor with no rules, for custom patterns only
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Add a custom pattern whose matches are replaced with `[LABEL_n]`
+    pub fn with_pattern(
+        mut self,
+        label: impl Into<String>,
+        pattern: &str,
+    ) -> Result<Self, regex::Error> {
+        self.rules.push(PiiRule {
+            label: label.into().to_uppercase(),
+            pattern: Regex::new(pattern)?,
+            validator: None,
+        });
+        Ok(self)
+    }
+
+    /// Replace PII in `text`, recording placeholders in `map`
+    pub fn redact(&self, text: &str, map: &mut RedactionMap) -> String {
+        self.rules.iter().fold(text.to_string(), |text, rule| {
+            rule.pattern
+                .replace_all(&text, |caps: &regex::Captures| {
+                    let value = &caps[0];
+                    match rule.validator {
+                        Some(valid) if !valid(value) => value.to_string(),
+                        _ => map.placeholder_for(&rule.label, value),
+                    }
+                })
+                .into_owned()
+        })
+    }
+
+    /// Whether `text` contains anything this redactor would replace
+    pub fn contains_pii(&self, text: &str) -> bool {
+        self.rules.iter().any(|rule| {
+            rule.pattern
+                .find_iter(text)
+                .any(|m| rule.validator.map_or(true, |valid| valid(m.as_str())))
+        })
+    }
+}
+
+impl Default for PiiRedactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Guardrail for PiiRedactor {
+    fn name(&self) -> &str {
+        "pii"
+    }
+
+    async fn validate(&self, output: &str) -> GuardrailVerdict {
+        if !self.contains_pii(output) {
+            return GuardrailVerdict::Pass;
+        }
+
+        GuardrailVerdict::Fail {
+            reason: "output contains personal data".to_string(),
+            redacted: Some(self.redact(output, &mut RedactionMap::new())),
+        }
+    }
+}
+
+fn redact_message(message: &Message, redactor: &PiiRedactor, map: &mut RedactionMap) -> Message {
+    let mut message = message.clone();
+    match &mut message {
+        Message::System(m) => m.content = redactor.redact(&m.content, map),
+        Message::Human(m) => m.content = redactor.redact(&m.content, map),
+        Message::AI(m) => m.content = redactor.redact(&m.content, map),
+        Message::Tool(m) => m.content = redactor.redact(&m.content, map),
+    }
+    message
+}
+
+/// Chat model wrapper that scrubs PII from prompts and completions
+///
+/// Placeholders are consistent across calls, so a value redacted in one prompt
+/// maps to the same placeholder later in the conversation. With
+/// `rehydrate_responses(true)` placeholders the model echoes back are restored
+/// in completions instead of scrubbing them.
+#[derive(Clone)]
+pub struct PiiRedactingChatModel {
+    inner: Arc<dyn BaseChatModel>,
+    redactor: PiiRedactor,
+    map: Arc<Mutex<RedactionMap>>,
+    redact_prompts: bool,
+    redact_completions: bool,
+    rehydrate_responses: bool,
+}
+
+impl PiiRedactingChatModel {
+    pub fn new(inner: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            inner,
+            redactor: PiiRedactor::new(),
+            map: Arc::new(Mutex::new(RedactionMap::new())),
+            redact_prompts: true,
+            redact_completions: true,
+            rehydrate_responses: false,
+        }
+    }
+
+    pub fn redactor(mut self, redactor: PiiRedactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    pub fn redact_prompts(mut self, redact_prompts: bool) -> Self {
+        self.redact_prompts = redact_prompts;
+        self
+    }
+
+    pub fn redact_completions(mut self, redact_completions: bool) -> Self {
+        self.redact_completions = redact_completions;
+        self
+    }
+
+    pub fn rehydrate_responses(mut self, rehydrate_responses: bool) -> Self {
+        self.rehydrate_responses = rehydrate_responses;
+        self
+    }
+
+    /// Snapshot of the placeholder table built so far
+    pub fn mapping(&self) -> RedactionMap {
+        self.map.lock().unwrap().clone()
+    }
+
+    fn scrub_prompt(&self, messages: &[Message]) -> Vec<Message> {
+        if !self.redact_prompts {
+            return messages.to_vec();
+        }
+
+        let mut map = self.map.lock().unwrap();
+        messages
+            .iter()
+            .map(|m| redact_message(m, &self.redactor, &mut map))
+            .collect()
+    }
+
+    fn scrub_completion(&self, mut response: AIMessage) -> AIMessage {
+        let mut map = self.map.lock().unwrap();
+        if self.rehydrate_responses {
+            response.content = map.rehydrate(&response.content);
+        } else if self.redact_completions {
+            response.content = self.redactor.redact(&response.content, &mut map);
+        }
+        response
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for PiiRedactingChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        let messages = self.scrub_prompt(messages);
+        let response = self.inner.invoke(&messages).await?;
+        Ok(self.scrub_completion(response))
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        let messages = self.scrub_prompt(messages);
+        let response = self.inner.invoke_with_tools(&messages, tools).await?;
+        Ok(self.scrub_completion(response))
+    }
+
+    /// Streams are buffered so PII split across chunks is still caught.
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let messages = self.scrub_prompt(messages);
+        let mut inner = self.inner.stream(&messages).await?;
+
+        let mut content = String::new();
+        while let Some(chunk) = inner.next().await {
+            content.push_str(&chunk?.content);
+        }
+        drop(inner);
+
+        let response = self.scrub_completion(AIMessage::new(content));
+        Ok(Box::pin(futures::stream::once(async move { Ok(response) })))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
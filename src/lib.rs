@@ -3,7 +3,7 @@
 //! Named after the Roman Optio, this framework brings military-grade coordination,
 //! resilience, and execution discipline to multi-model AI operations.
 //!
-//! Currently supports Ollama with OpenAI, Anthropic, and other providers coming soon.
+//! Currently supports Ollama and OpenAI, with Anthropic and other providers coming soon.
 //!
 //! # Examples
 //!
@@ -20,15 +20,41 @@
 //! }
 //! ```
 
+pub mod agents;
+pub mod cache;
+pub mod callbacks;
+pub mod chains;
+pub mod config;
 pub mod core;
+pub mod credentials;
+pub mod document_loaders;
+pub mod embeddings;
+pub mod eval;
+pub mod guardrails;
 pub mod models;
+pub mod output_parsers;
+pub mod prompts;
+pub mod retrievers;
+pub mod runnables;
+pub mod sessions;
+pub mod telemetry;
+pub mod testing;
+pub mod text_splitter;
+pub mod tools;
+pub mod vectorstores;
 
 // Re-export main types
+pub use agents::{Agent, AgentRun, Transcript, TranscriptRecorder, TranscriptReplayer};
+pub use core::documents::Document;
 pub use core::messages::{
     AIMessage, BaseMessage, HumanMessage, Message, SystemMessage, ToolMessage,
 };
 pub use models::base::{BaseChatModel, BaseEmbedding};
+#[cfg(feature = "ollama")]
 pub use models::ollama::{OllamaChat, OllamaEmbedding};
+pub use models::{init_chat_model, ChatModel};
+pub use prompts::PromptTemplate;
+pub use tools::{BaseTool, FunctionTool, ToolRegistry};
 
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
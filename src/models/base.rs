@@ -1,11 +1,15 @@
 //! Base traits for AgenticOptio models.
 //!
-//! Provides abstract base traits for chat and embedding model implementations.
+//! Provides abstract base traits for chat, embedding, and reranking model
+//! implementations.
 
-use crate::core::messages::{AIMessage, Message};
+use crate::core::documents::Document;
+use crate::core::messages::{AIMessage, Message, StreamStats, Usage};
+use crate::credentials::CredentialError;
 use async_trait::async_trait;
-use futures::stream::Stream;
+use futures::stream::{Stream, StreamExt};
 use std::pin::Pin;
+use std::time::{Duration, Instant};
 
 pub type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = T> + Send + 'a>>;
 
@@ -13,7 +17,7 @@ pub type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = T> + Send + 'a>>;
 #[derive(Debug, thiserror::Error)]
 pub enum ModelError {
     #[error("HTTP request failed: {0}")]
-    HttpError(#[from] reqwest::Error),
+    HttpError(reqwest::Error),
 
     #[error("JSON serialization failed: {0}")]
     JsonError(#[from] serde_json::Error),
@@ -21,23 +25,275 @@ pub enum ModelError {
     #[error("API error: {0}")]
     ApiError(String),
 
+    /// The provider answered with a 5xx status
+    #[error("Server error: HTTP {status}: {message}")]
+    Server { status: u16, message: String },
+
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
+
+    #[error("Guardrail violation: {0}")]
+    GuardrailViolation(String),
+
+    #[error("Configuration error: {0}")]
+    ConfigError(String),
+
+    #[error("Credential lookup failed: {0}")]
+    CredentialError(#[from] CredentialError),
+
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        /// How long the provider asked to wait, if it said
+        retry_after: Option<Duration>,
+    },
+
+    #[error("Authentication failed: {0}")]
+    AuthFailed(String),
+
+    #[error("Context length exceeded: {0}")]
+    ContextLengthExceeded(String),
+
+    #[error("Model not found: {0}")]
+    ModelNotFound(String),
+
+    #[error("Content filtered: {0}")]
+    ContentFiltered(String),
+
+    #[error("Request timed out: {0}")]
+    Timeout(String),
+
+    #[error("Stream interrupted: {source}")]
+    StreamInterrupted {
+        /// Content streamed before the failure
+        partial: String,
+        source: Box<ModelError>,
+    },
+}
+
+/// Timeouts become [`ModelError::Timeout`], everything else
+/// [`ModelError::HttpError`]
+impl From<reqwest::Error> for ModelError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            ModelError::Timeout(error.to_string())
+        } else {
+            ModelError::HttpError(error)
+        }
+    }
+}
+
+impl ModelError {
+    /// Short, low-cardinality label for metrics and telemetry
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ModelError::HttpError(_) => "http",
+            ModelError::JsonError(_) => "json",
+            ModelError::ApiError(_) => "api",
+            ModelError::Server { .. } => "server",
+            ModelError::InvalidResponse(_) => "invalid_response",
+            ModelError::GuardrailViolation(_) => "guardrail_violation",
+            ModelError::ConfigError(_) => "config",
+            ModelError::CredentialError(_) => "credential",
+            ModelError::RateLimited { .. } => "rate_limited",
+            ModelError::AuthFailed(_) => "auth_failed",
+            ModelError::ContextLengthExceeded(_) => "context_length_exceeded",
+            ModelError::ModelNotFound(_) => "model_not_found",
+            ModelError::ContentFiltered(_) => "content_filtered",
+            ModelError::Timeout(_) => "timeout",
+            ModelError::StreamInterrupted { .. } => "stream_interrupted",
+        }
+    }
+
+    /// Classify a failed HTTP response from its status and body
+    ///
+    /// Understands OpenAI-style `{"error": {"message", "type", "code"}}` and
+    /// Ollama-style `{"error": "..."}` bodies. Other 5xx statuses become
+    /// [`ModelError::Server`], and errors that fit no other variant
+    /// [`ModelError::ApiError`] with the status and provider message.
+    pub fn from_http_status(status: u16, body: &str, retry_after: Option<Duration>) -> Self {
+        let json: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+        let error = &json["error"];
+        let message = error["message"]
+            .as_str()
+            .or_else(|| error.as_str())
+            .or_else(|| json["message"].as_str())
+            .map_or_else(|| body.trim().to_string(), str::to_string);
+        let code = [&error["code"], &error["type"]]
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        let lower = message.to_lowercase();
+        let mentions = |needles: &[&str]| {
+            needles
+                .iter()
+                .any(|n| code.contains(n) || lower.contains(n))
+        };
+
+        if status == 429 || mentions(&["rate_limit"]) || (status == 503 && retry_after.is_some()) {
+            ModelError::RateLimited {
+                message,
+                retry_after,
+            }
+        } else if status == 401 || status == 403 || mentions(&["invalid_api_key", "authentication"])
+        {
+            ModelError::AuthFailed(message)
+        } else if mentions(&[
+            "context_length",
+            "context length",
+            "context window",
+            "maximum context",
+            "too many tokens",
+            "prompt is too long",
+        ]) {
+            ModelError::ContextLengthExceeded(message)
+        } else if mentions(&["model_not_found"]) || (status == 404 && lower.contains("model")) {
+            ModelError::ModelNotFound(message)
+        } else if mentions(&[
+            "content_filter",
+            "content_policy",
+            "content management policy",
+        ]) {
+            ModelError::ContentFiltered(message)
+        } else if status == 408 || status == 504 {
+            ModelError::Timeout(message)
+        } else if (500..600).contains(&status) {
+            ModelError::Server { status, message }
+        } else {
+            ModelError::ApiError(format!("HTTP {status}: {message}"))
+        }
+    }
 }
 
 pub type ModelResult<T> = Result<T, ModelError>;
 
+/// Running throughput measurement of a response stream
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StreamMeter {
+    started: Instant,
+    first_token: Option<Instant>,
+    chunks: u32,
+    usage: Option<Usage>,
+}
+
+impl StreamMeter {
+    pub(crate) fn new(started: Instant) -> Self {
+        Self {
+            started,
+            first_token: None,
+            chunks: 0,
+            usage: None,
+        }
+    }
+
+    pub(crate) fn observe(&mut self, chunk: &AIMessage) {
+        if !chunk.content.is_empty() {
+            self.first_token.get_or_insert_with(Instant::now);
+            self.chunks += 1;
+        }
+        if let Some(usage) = chunk.usage {
+            *self.usage.get_or_insert_with(Usage::default) += usage;
+        }
+    }
+
+    pub(crate) fn finish(&self) -> StreamStats {
+        let duration = self.started.elapsed();
+        StreamStats {
+            time_to_first_token: self
+                .first_token
+                .map_or(duration, |first| first - self.started),
+            duration,
+            output_tokens: self.usage.map_or(self.chunks, |u| u.output_tokens),
+        }
+    }
+}
+
+/// Measure a response stream started at `started`, ending it with an extra
+/// chunk that carries only the [`StreamStats`]
+///
+/// Providers wrap their streams with this so callers get time-to-first-token
+/// and tokens per second without measuring themselves. Chunks pass through as
+/// they arrive; the stats chunk has empty content and no tool calls or usage,
+/// so callers concatenating chunks can keep it.
+pub fn measure_stream(
+    stream: BoxStream<'_, ModelResult<AIMessage>>,
+    started: Instant,
+) -> BoxStream<'_, ModelResult<AIMessage>> {
+    Box::pin(futures::stream::unfold(
+        Some((stream, StreamMeter::new(started))),
+        |state| async move {
+            let (mut stream, mut meter) = state?;
+            match stream.next().await {
+                Some(Ok(chunk)) => {
+                    meter.observe(&chunk);
+                    Some((Ok(chunk), Some((stream, meter))))
+                }
+                Some(Err(e)) => Some((Err(e), Some((stream, meter)))),
+                None => {
+                    let mut last = AIMessage::new("");
+                    last.stream_stats = Some(meter.finish());
+                    Some((Ok(last), None))
+                }
+            }
+        },
+    ))
+}
+
 /// Base trait for all chat models
 #[async_trait]
 pub trait BaseChatModel: Send + Sync {
     /// Invoke the model asynchronously
     async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage>;
 
+    /// Invoke the model with tool schemas (OpenAI function format) available.
+    ///
+    /// Models without native tool calling fall back to a plain `invoke`.
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        let _ = tools;
+        self.invoke(messages).await
+    }
+
     /// Stream response asynchronously
+    ///
+    /// Streams are pull-based: a chunk is read from the connection only when
+    /// the consumer asks for the next one, so a slow consumer slows the server
+    /// down through TCP flow control instead of growing a buffer, and only
+    /// time spent waiting on the server counts toward the idle timeout. To
+    /// read ahead while the consumer is busy, use
+    /// [`StreamBufferExt::buffer`](crate::models::StreamBufferExt::buffer).
+    ///
+    /// The built-in providers end their streams with an extra chunk that has
+    /// empty content and carries only the [`StreamStats`] in
+    /// [`AIMessage::stream_stats`]; see [`measure_stream`].
     async fn stream<'a>(
         &'a self,
         messages: &'a [Message],
     ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>>;
+
+    /// Model identifier reported to callbacks and telemetry
+    fn model_name(&self) -> &str {
+        "unknown"
+    }
+
+    /// Provider identifier reported to telemetry, e.g. `"ollama"`
+    fn provider_name(&self) -> &str {
+        "unknown"
+    }
+
+    /// Check that the model can take requests: its server is reachable and
+    /// serves this model
+    ///
+    /// Models without a remote backend are always ready. See
+    /// [`HealthMonitor`](crate::models::HealthMonitor) for periodic probing.
+    async fn ready(&self) -> ModelResult<()> {
+        Ok(())
+    }
 }
 
 /// Base trait for all embedding models
@@ -55,8 +311,42 @@ pub trait BaseEmbedding: Send + Sync {
             .ok_or_else(|| ModelError::InvalidResponse("No embedding returned".to_string()))
     }
 
-    /// Get embedding dimension
+    /// Embedding dimension, if known without a request
+    ///
+    /// Returns 0 when the dimension is not known yet; implementations that learn
+    /// it from their first response report it afterwards. Use
+    /// [`detect_dimension`](Self::detect_dimension) when an exact value is
+    /// required, e.g. before creating a vector store schema.
     fn dimension(&self) -> usize {
-        1536 // Default to OpenAI dimension
+        0
+    }
+
+    /// Model identifier reported to telemetry
+    fn model_name(&self) -> &str {
+        "unknown"
+    }
+
+    /// Provider identifier reported to telemetry, e.g. `"ollama"`
+    fn provider_name(&self) -> &str {
+        "unknown"
+    }
+
+    /// Exact embedding dimension, embedding a probe text if it is not known
+    async fn detect_dimension(&self) -> ModelResult<usize> {
+        match self.dimension() {
+            0 => Ok(self.embed_query("dimension probe").await?.len()),
+            known => Ok(known),
+        }
     }
 }
+
+/// Base trait for all rerankers
+#[async_trait]
+pub trait BaseReranker: Send + Sync {
+    /// Score documents against a query, returning them most relevant first
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<Document>,
+    ) -> ModelResult<Vec<(Document, f32)>>;
+}
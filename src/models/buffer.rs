@@ -0,0 +1,85 @@
+//! Bounded read-ahead for streams.
+
+use crate::models::base::BoxStream;
+use futures::{Stream, StreamExt};
+
+/// Bounded read-ahead for `'static` streams, like those of
+/// [`ChatModel::stream`](crate::models::ChatModel::stream)
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::StreamBufferExt;
+/// use agentic_optio_rs::{ChatModel, Message, OllamaChat};
+/// use futures::StreamExt;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let llm = ChatModel::new(OllamaChat::new("llama3.2"));
+///     // Keep receiving up to 32 chunks while a throttled client catches up
+///     let mut stream = llm.stream(vec![Message::user("Write a long story")]).buffer(32);
+///     while let Some(chunk) = stream.next().await {
+///         // send_to_websocket(chunk).await;
+///     }
+/// }
+/// ```
+pub trait StreamBufferExt: Stream + Sized + Send + 'static
+where
+    Self::Item: Send + 'static,
+{
+    /// Read up to `capacity` items ahead of the consumer
+    ///
+    /// The stream is driven by a task on the current Tokio runtime, so the
+    /// server keeps sending while the consumer is busy. Once `capacity` items
+    /// are waiting, plus the one being handed over, the task stops reading
+    /// until the consumer takes one, so memory stays bounded however slow
+    /// the consumer is. Dropping the returned stream stops the task and drops
+    /// this one, cancelling its request.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero, or outside a Tokio runtime.
+    fn buffer(self, capacity: usize) -> BoxStream<'static, Self::Item> {
+        assert!(capacity > 0, "buffer capacity must be non-zero");
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity);
+        let reader = tokio::spawn(async move {
+            let mut stream = std::pin::pin!(self);
+            while let Some(item) = stream.next().await {
+                if sender.send(item).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Box::pin(Buffered { receiver, reader })
+    }
+}
+
+impl<S> StreamBufferExt for S
+where
+    S: Stream + Send + 'static,
+    S::Item: Send + 'static,
+{
+}
+
+struct Buffered<T> {
+    receiver: tokio::sync::mpsc::Receiver<T>,
+    reader: tokio::task::JoinHandle<()>,
+}
+
+impl<T> Stream for Buffered<T> {
+    type Item = T;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<T>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl<T> Drop for Buffered<T> {
+    // Also stops a reader waiting on the source rather than on a full buffer
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
@@ -0,0 +1,143 @@
+//! OpenAI-compatible chat completions wire format.
+//!
+//! Request and response types of the `/v1/chat/completions` API, shared by
+//! every provider that speaks it.
+
+use crate::core::messages::{AIMessage, ToolCall, Usage};
+use crate::models::base::{ModelError, ModelResult};
+use serde::{Deserialize, Serialize};
+
+/// OpenAI-compatible chat completion request
+#[derive(Debug, Serialize)]
+pub(crate) struct ChatRequest {
+    pub(crate) model: String,
+    pub(crate) messages: Vec<serde_json::Value>,
+    pub(crate) temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) tools: Option<Vec<serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) stream: Option<bool>,
+}
+
+/// OpenAI-compatible chat completion response
+#[derive(Debug, Deserialize)]
+pub(crate) struct ChatResponse {
+    pub(crate) choices: Vec<Choice>,
+    #[serde(default)]
+    pub(crate) usage: Option<ResponseUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ResponseUsage {
+    #[serde(default)]
+    pub(crate) prompt_tokens: u32,
+    #[serde(default)]
+    pub(crate) completion_tokens: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Choice {
+    pub(crate) message: ResponseMessage,
+    #[serde(default)]
+    pub(crate) finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ResponseMessage {
+    pub(crate) content: Option<String>,
+    #[serde(default)]
+    pub(crate) tool_calls: Vec<ResponseToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ResponseToolCall {
+    pub(crate) id: String,
+    pub(crate) function: FunctionCall,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct FunctionCall {
+    pub(crate) name: String,
+    pub(crate) arguments: String,
+}
+
+/// Streaming chunk response
+#[derive(Debug, Deserialize)]
+pub(crate) struct StreamChunk {
+    pub(crate) choices: Vec<StreamChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct StreamChoice {
+    pub(crate) delta: Delta,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Delta {
+    pub(crate) content: Option<String>,
+}
+
+/// Response message for the first choice of a completion
+pub(crate) fn parse_response(response: ChatResponse) -> ModelResult<AIMessage> {
+    let choice = response
+        .choices
+        .into_iter()
+        .next()
+        .ok_or_else(|| ModelError::InvalidResponse("No choices in response".to_string()))?;
+
+    let message = choice.message;
+    let content = message.content.unwrap_or_default();
+    if choice.finish_reason.as_deref() == Some("content_filter")
+        && content.is_empty()
+        && message.tool_calls.is_empty()
+    {
+        return Err(ModelError::ContentFiltered(
+            "the provider's content filter withheld the response".to_string(),
+        ));
+    }
+
+    let tool_calls: Vec<ToolCall> = message
+        .tool_calls
+        .into_iter()
+        .map(|tc| {
+            let args = serde_json::from_str(&tc.function.arguments).unwrap_or_default();
+            ToolCall {
+                id: tc.id,
+                name: tc.function.name,
+                args,
+            }
+        })
+        .collect();
+
+    let mut message = AIMessage::with_tool_calls(content, tool_calls);
+    if let Some(usage) = response.usage {
+        message = message.with_usage(Usage::new(usage.prompt_tokens, usage.completion_tokens));
+    }
+
+    Ok(message)
+}
+
+/// Content of one server-sent events chunk of a streamed completion
+pub(crate) fn parse_stream_chunk(bytes: &[u8]) -> AIMessage {
+    let text = String::from_utf8_lossy(bytes);
+
+    // Parse SSE format: "data: {...}\n\n"
+    for line in text.lines() {
+        if let Some(json_str) = line.strip_prefix("data: ") {
+            if json_str == "[DONE]" {
+                continue;
+            }
+            if let Ok(chunk) = serde_json::from_str::<StreamChunk>(json_str) {
+                if let Some(choice) = chunk.choices.first() {
+                    if let Some(content) = &choice.delta.content {
+                        return AIMessage::new(content.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    AIMessage::new("")
+}
@@ -0,0 +1,304 @@
+//! Adaptive concurrency limiting.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BoxStream, ModelError, ModelResult};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// Concurrency limit that adapts to how the server copes, AIMD style
+///
+/// Requests beyond the current limit wait for a slot. Each request that
+/// succeeds promptly raises the limit by `1 / limit`, so about one per full
+/// window of requests (additive increase). A request that fails with an
+/// overload error (rate limit, timeout, connection or server error) or is
+/// slow halves it (multiplicative decrease), at most once per window.
+///
+/// A request is slow when it takes longer than the
+/// [`target_latency`](Self::target_latency), if set, or more than
+/// [`latency_tolerance`](Self::latency_tolerance) times the running average.
+/// For streams the latency is the time to the first chunk.
+///
+/// Clones share the limit, so wrap every model that talks to one server with
+/// the same limiter.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::{ConcurrencyLimiter, LimitedChatModel};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// // One GPU: start at 2 parallel requests and never exceed 8
+/// let limiter = ConcurrencyLimiter::new().initial_limit(2).max_limit(8);
+/// let fast = LimitedChatModel::new(Arc::new(OllamaChat::new("llama3.2")), limiter.clone());
+/// let smart = LimitedChatModel::new(Arc::new(OllamaChat::new("qwen2.5:7b")), limiter);
+/// ```
+#[derive(Clone)]
+pub struct ConcurrencyLimiter {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    released: Notify,
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    limit: f64,
+    in_flight: usize,
+    min_limit: usize,
+    max_limit: usize,
+    target_latency: Option<Duration>,
+    latency_tolerance: f64,
+    average_latency: Option<Duration>,
+    last_decrease: Option<Instant>,
+}
+
+impl ConcurrencyLimiter {
+    /// Limiter starting at 4 concurrent requests, adapting between 1 and 32
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State {
+                    limit: 4.0,
+                    in_flight: 0,
+                    min_limit: 1,
+                    max_limit: 32,
+                    target_latency: None,
+                    latency_tolerance: 2.0,
+                    average_latency: None,
+                    last_decrease: None,
+                }),
+                released: Notify::new(),
+            }),
+        }
+    }
+
+    pub fn initial_limit(self, limit: usize) -> Self {
+        self.configure(|s| s.limit = limit as f64)
+    }
+
+    /// Lowest the limit goes (default 1)
+    pub fn min_limit(self, limit: usize) -> Self {
+        self.configure(|s| s.min_limit = limit.max(1))
+    }
+
+    /// Highest the limit goes (default 32)
+    pub fn max_limit(self, limit: usize) -> Self {
+        self.configure(|s| s.max_limit = limit.max(1))
+    }
+
+    /// Latency above which a request counts as slow
+    pub fn target_latency(self, latency: Duration) -> Self {
+        self.configure(|s| s.target_latency = Some(latency))
+    }
+
+    /// Multiple of the average latency above which a request counts as slow
+    /// (default 2.0)
+    pub fn latency_tolerance(self, tolerance: f64) -> Self {
+        self.configure(|s| s.latency_tolerance = tolerance.max(1.0))
+    }
+
+    fn configure(self, f: impl FnOnce(&mut State)) -> Self {
+        {
+            let mut state = self.shared.state.lock().unwrap();
+            f(&mut state);
+            state.max_limit = state.max_limit.max(state.min_limit);
+            state.limit = state
+                .limit
+                .clamp(state.min_limit as f64, state.max_limit as f64);
+        }
+        self
+    }
+
+    /// Current limit on concurrent requests
+    pub fn limit(&self) -> usize {
+        self.shared.state.lock().unwrap().limit as usize
+    }
+
+    /// Requests holding a slot right now
+    pub fn in_flight(&self) -> usize {
+        self.shared.state.lock().unwrap().in_flight
+    }
+
+    /// Wait for a slot; the slot is released when the permit is dropped
+    pub async fn acquire(&self) -> ConcurrencyPermit {
+        loop {
+            // Registered before checking, so a release in between still wakes
+            // this waiter
+            let released = self.shared.released.notified();
+            {
+                let mut state = self.shared.state.lock().unwrap();
+                if state.in_flight < state.limit as usize {
+                    state.in_flight += 1;
+                    return ConcurrencyPermit {
+                        shared: self.shared.clone(),
+                        started: Instant::now(),
+                    };
+                }
+            }
+            released.await;
+        }
+    }
+}
+
+impl Default for ConcurrencyLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ConcurrencyLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.shared.state.lock().unwrap();
+        f.debug_struct("ConcurrencyLimiter")
+            .field("limit", &(state.limit as usize))
+            .field("in_flight", &state.in_flight)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A slot from a [`ConcurrencyLimiter`]
+pub struct ConcurrencyPermit {
+    shared: Arc<Shared>,
+    started: Instant,
+}
+
+impl ConcurrencyPermit {
+    /// Adjust the limit for the request holding this permit, which ended now
+    /// with `error`, if any
+    ///
+    /// Errors that do not signal overload, like a bad request, leave the
+    /// limit alone.
+    pub fn record(&self, error: Option<&ModelError>) {
+        let latency = self.started.elapsed();
+        let mut state = self.shared.state.lock().unwrap();
+        let overloaded = error.is_some_and(is_overload);
+        if error.is_some() && !overloaded {
+            return;
+        }
+        let slow = !overloaded
+            && (state.target_latency.is_some_and(|target| latency > target)
+                || state.average_latency.is_some_and(|average| {
+                    latency.as_secs_f64() > average.as_secs_f64() * state.latency_tolerance
+                }));
+        if !overloaded {
+            state.average_latency = Some(match state.average_latency {
+                Some(average) => average.mul_f64(0.9) + latency.mul_f64(0.1),
+                None => latency,
+            });
+        }
+
+        if overloaded || slow {
+            // Requests admitted before the last decrease reflect the old limit
+            if state.last_decrease.is_some_and(|at| self.started < at) {
+                return;
+            }
+            state.limit = (state.limit / 2.0).max(state.min_limit as f64);
+            state.last_decrease = Some(Instant::now());
+        } else {
+            state.limit = (state.limit + 1.0 / state.limit).min(state.max_limit as f64);
+            drop(state);
+            // A raised limit may admit a waiter
+            self.shared.released.notify_waiters();
+        }
+    }
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().in_flight -= 1;
+        self.shared.released.notify_waiters();
+    }
+}
+
+/// Whether `error` suggests the server is overwhelmed
+fn is_overload(error: &ModelError) -> bool {
+    matches!(
+        error,
+        ModelError::RateLimited { .. }
+            | ModelError::Timeout(_)
+            | ModelError::HttpError(_)
+            | ModelError::Server { .. }
+    )
+}
+
+/// Chat model wrapper admitting requests through a [`ConcurrencyLimiter`]
+///
+/// A stream holds its slot until it ends or is dropped.
+#[derive(Clone)]
+pub struct LimitedChatModel {
+    inner: Arc<dyn BaseChatModel>,
+    limiter: ConcurrencyLimiter,
+}
+
+impl LimitedChatModel {
+    pub fn new(inner: Arc<dyn BaseChatModel>, limiter: ConcurrencyLimiter) -> Self {
+        Self { inner, limiter }
+    }
+
+    pub fn limiter(&self) -> &ConcurrencyLimiter {
+        &self.limiter
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for LimitedChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        let permit = self.limiter.acquire().await;
+        let result = self.inner.invoke(messages).await;
+        permit.record(result.as_ref().err());
+        result
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        let permit = self.limiter.acquire().await;
+        let result = self.inner.invoke_with_tools(messages, tools).await;
+        permit.record(result.as_ref().err());
+        result
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let permit = self.limiter.acquire().await;
+        let inner = match self.inner.stream(messages).await {
+            Ok(inner) => inner,
+            Err(e) => {
+                permit.record(Some(&e));
+                return Err(e);
+            }
+        };
+        let stream = futures::stream::unfold(Some((inner, permit, false)), |state| async move {
+            let (mut inner, permit, mut recorded) = state?;
+            let chunk = inner.next().await?;
+            if !recorded {
+                permit.record(chunk.as_ref().err());
+                recorded = true;
+            }
+            Some((chunk, Some((inner, permit, recorded))))
+        });
+        Ok(Box::pin(stream))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
@@ -0,0 +1,176 @@
+//! Cheaply clonable chat model handle.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BoxStream, ModelResult};
+use crate::output_parsers::{OutputParser, ParsedChatModel};
+use async_trait::async_trait;
+use futures::{SinkExt, StreamExt};
+use std::sync::Arc;
+
+/// Shared handle to any chat model
+///
+/// Cloning is a reference count bump, and [`stream`](Self::stream) takes its
+/// messages by value and returns a `'static` stream, so it can be returned
+/// from functions, stored, or moved into a spawned task. The handle is itself a
+/// [`BaseChatModel`], so it can go anywhere a model can.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::{ChatModel, Message, OllamaChat};
+/// use futures::StreamExt;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let llm = ChatModel::new(OllamaChat::new("llama3.2"));
+///
+///     let mut stream = llm.stream(vec![Message::user("Tell me a story")]);
+///     let task = tokio::spawn(async move {
+///         while let Some(chunk) = stream.next().await {
+///             print!("{}", chunk?.content);
+///         }
+///         Ok::<_, agentic_optio_rs::models::base::ModelError>(())
+///     });
+///     task.await??;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ChatModel {
+    inner: Arc<dyn BaseChatModel>,
+}
+
+impl ChatModel {
+    pub fn new(model: impl BaseChatModel + 'static) -> Self {
+        Self {
+            inner: Arc::new(model),
+        }
+    }
+
+    pub async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        self.inner.invoke(messages).await
+    }
+
+    pub async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        self.inner.invoke_with_tools(messages, tools).await
+    }
+
+    /// This model with `parser` attached, for typed responses from
+    /// [`invoke_parsed`](ParsedChatModel::invoke_parsed)
+    pub fn with_parser<T>(&self, parser: impl OutputParser<T> + 'static) -> ParsedChatModel<T> {
+        ParsedChatModel::new(self.inner.clone(), parser)
+    }
+
+    /// Check that the model can take requests
+    pub async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+
+    /// Stream a response to `messages`, owning them for the life of the stream
+    ///
+    /// Nothing is sent until the stream is first polled; a failure to start
+    /// the stream is its first item. Chunks are handed over one at a time, as
+    /// the consumer asks for them.
+    pub fn stream(&self, messages: Vec<Message>) -> BoxStream<'static, ModelResult<AIMessage>> {
+        let inner = self.inner.clone();
+        let (mut sender, receiver) = futures::channel::mpsc::channel(0);
+        // Drives the borrowing inner stream from a future that owns the model
+        // and messages, handing chunks to the receiver as it polls for them
+        let driver = async move {
+            let mut stream = match inner.stream(&messages).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let _ = sender.send(Err(e)).await;
+                    return;
+                }
+            };
+            while let Some(chunk) = stream.next().await {
+                if sender.send(chunk).await.is_err() {
+                    return;
+                }
+            }
+        };
+        let driver = futures::stream::once(driver).filter_map(|()| async { None });
+        Box::pin(futures::stream::select(driver, receiver))
+    }
+
+    pub fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    pub fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    /// The shared model, for APIs taking an `Arc<dyn BaseChatModel>`
+    pub fn as_arc(&self) -> &Arc<dyn BaseChatModel> {
+        &self.inner
+    }
+}
+
+impl From<Arc<dyn BaseChatModel>> for ChatModel {
+    fn from(inner: Arc<dyn BaseChatModel>) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<Box<dyn BaseChatModel>> for ChatModel {
+    fn from(inner: Box<dyn BaseChatModel>) -> Self {
+        Self {
+            inner: Arc::from(inner),
+        }
+    }
+}
+
+impl From<ChatModel> for Arc<dyn BaseChatModel> {
+    fn from(model: ChatModel) -> Self {
+        model.inner
+    }
+}
+
+impl std::fmt::Debug for ChatModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChatModel")
+            .field("provider", &self.provider_name())
+            .field("model", &self.model_name())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for ChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        self.inner.invoke(messages).await
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        self.inner.invoke_with_tools(messages, tools).await
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        self.inner.stream(messages).await
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
@@ -0,0 +1,172 @@
+//! Background readiness probing.
+
+use crate::models::base::BaseChatModel;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Result of the latest readiness probe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Health {
+    /// Not probed yet
+    Unknown,
+    Ready,
+    /// The probe failed, with its error
+    Unavailable(String),
+}
+
+impl Health {
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Health::Ready)
+    }
+}
+
+type ChangeCallback = Arc<dyn Fn(&Health) + Send + Sync>;
+
+/// Probes a model with [`BaseChatModel::ready`] on an interval, so work is
+/// only routed to it while its server is up and serving the model
+///
+/// The first probe runs at once. Callbacks registered with
+/// [`on_change`](Self::on_change) run on every change of state, including the
+/// first result.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::HealthMonitor;
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let monitor = HealthMonitor::new(Arc::new(OllamaChat::new("llama3.2")))
+///         .interval(Duration::from_secs(10))
+///         .on_change(|health| println!("llama3.2 is now {health:?}"))
+///         .start();
+///
+///     monitor.wait_ready().await;
+///     assert!(monitor.is_ready());
+/// }
+/// ```
+#[derive(Clone)]
+pub struct HealthMonitor {
+    model: Arc<dyn BaseChatModel>,
+    interval: Duration,
+    timeout: Duration,
+    callbacks: Vec<ChangeCallback>,
+}
+
+impl HealthMonitor {
+    pub fn new(model: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            model,
+            interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(5),
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Time between probes (default 30s)
+    ///
+    /// # Panics
+    ///
+    /// If `interval` is zero.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        assert!(!interval.is_zero(), "probe interval must be non-zero");
+        self.interval = interval;
+        self
+    }
+
+    /// Longest a probe may take before the model counts as unavailable
+    /// (default 5s)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Call `callback` with the new state whenever it changes
+    pub fn on_change(mut self, callback: impl Fn(&Health) + Send + Sync + 'static) -> Self {
+        self.callbacks.push(Arc::new(callback));
+        self
+    }
+
+    /// Check the model once, now
+    pub async fn probe(&self) -> Health {
+        match tokio::time::timeout(self.timeout, self.model.ready()).await {
+            Ok(Ok(())) => Health::Ready,
+            Ok(Err(e)) => Health::Unavailable(e.to_string()),
+            Err(_) => Health::Unavailable(format!(
+                "readiness probe timed out after {:?}",
+                self.timeout
+            )),
+        }
+    }
+
+    /// Start probing on the current Tokio runtime
+    ///
+    /// Probing stops when the returned handle is stopped or dropped.
+    pub fn start(self) -> HealthHandle {
+        let (sender, receiver) = watch::channel(Health::Unknown);
+        let task = tokio::spawn(async move {
+            loop {
+                let health = self.probe().await;
+                let changed = sender.send_if_modified(|current| {
+                    if *current == health {
+                        return false;
+                    }
+                    *current = health.clone();
+                    true
+                });
+                if changed {
+                    for callback in &self.callbacks {
+                        callback(&health);
+                    }
+                }
+                tokio::time::sleep(self.interval).await;
+            }
+        });
+        HealthHandle { receiver, task }
+    }
+}
+
+/// Handle to a running [`HealthMonitor`]
+pub struct HealthHandle {
+    receiver: watch::Receiver<Health>,
+    task: JoinHandle<()>,
+}
+
+impl HealthHandle {
+    /// State from the latest probe
+    pub fn health(&self) -> Health {
+        self.receiver.borrow().clone()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.receiver.borrow().is_ready()
+    }
+
+    /// Receiver notified of every change of state, for routing logic that
+    /// reacts to it
+    pub fn subscribe(&self) -> watch::Receiver<Health> {
+        self.receiver.clone()
+    }
+
+    /// Wait until a probe finds the model ready
+    pub async fn wait_ready(&self) {
+        let mut receiver = self.receiver.clone();
+        // The sender lives as long as the probing task, which only ends
+        // when this handle is dropped
+        let _ = receiver.wait_for(Health::is_ready).await;
+    }
+
+    /// Stop probing
+    pub fn stop(self) {}
+}
+
+impl Drop for HealthHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
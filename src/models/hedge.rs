@@ -0,0 +1,159 @@
+//! Hedged requests across redundant models.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BoxStream, ModelResult};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Chat model wrapper that sends a slow request again to a backup and keeps
+/// whichever answer comes first
+///
+/// The primary model gets every request. If it has not answered after the
+/// [`delay`](Self::delay), the same request also goes to the first backup,
+/// then after another delay to the next one, and so on; a request that fails
+/// brings the next backup in at once. The first successful answer wins and
+/// the requests still in flight are dropped, which cancels them. For streams
+/// the race is to the first chunk. The call fails only once every model has
+/// failed, with the last error.
+///
+/// Hedging trims tail latency on a cluster of identical servers, at the cost
+/// of duplicate work on the slowest requests.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::HedgedChatModel;
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let node = |host: &str| OllamaChat::builder("llama3.2").host(host).build();
+///     let llm = HedgedChatModel::new(Arc::new(node("http://gpu-1:11434")))
+///         .backup(Arc::new(node("http://gpu-2:11434")))
+///         .delay(Duration::from_millis(800));
+///     let response = llm.invoke(&[Message::user("Hello!")]).await?;
+///     println!("{}", response.content);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct HedgedChatModel {
+    /// The primary first, then the backups in order
+    models: Vec<Arc<dyn BaseChatModel>>,
+    delay: Duration,
+}
+
+impl HedgedChatModel {
+    pub fn new(primary: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            models: vec![primary],
+            delay: Duration::from_secs(2),
+        }
+    }
+
+    /// Model that takes the request when the ones before it are slow
+    pub fn backup(mut self, model: Arc<dyn BaseChatModel>) -> Self {
+        self.models.push(model);
+        self
+    }
+
+    /// Time to wait for an answer before hedging with the next backup
+    /// (default 2s); set it near the latency your p90 or p95 requests take
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Run `start` on the primary, hedging with the backups, and return the
+    /// first success
+    async fn race<'a, T: Send + 'a>(
+        &'a self,
+        start: impl Fn(&'a dyn BaseChatModel) -> BoxFuture<'a, ModelResult<T>>,
+    ) -> ModelResult<T> {
+        let mut pending = FuturesUnordered::new();
+        pending.push(start(self.models[0].as_ref()));
+        let mut launched = 1;
+        loop {
+            let hedge = launched < self.models.len();
+            tokio::select! {
+                Some(result) = pending.next() => match result {
+                    Ok(value) => return Ok(value),
+                    Err(e) if !hedge && pending.is_empty() => return Err(e),
+                    Err(_e) if hedge => {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(error = %_e, "hedging after a failed request");
+                        pending.push(start(self.models[launched].as_ref()));
+                        launched += 1;
+                    }
+                    Err(_) => {}
+                },
+                _ = tokio::time::sleep(self.delay), if hedge => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(backup = launched, "hedging a slow request");
+                    pending.push(start(self.models[launched].as_ref()));
+                    launched += 1;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for HedgedChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        self.race(|model| model.invoke(messages)).await
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        self.race(|model| model.invoke_with_tools(messages, tools))
+            .await
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let (first, rest) = self
+            .race(|model| {
+                Box::pin(async move {
+                    let mut stream = model.stream(messages).await?;
+                    match stream.next().await {
+                        Some(Ok(first)) => Ok((Some(first), stream)),
+                        Some(Err(e)) => Err(e),
+                        None => Ok((None, stream)),
+                    }
+                })
+            })
+            .await?;
+        Ok(Box::pin(futures::stream::iter(first.map(Ok)).chain(rest)))
+    }
+
+    fn model_name(&self) -> &str {
+        self.models[0].model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.models[0].provider_name()
+    }
+
+    /// Ready if any of the models is; otherwise fails with the last error
+    async fn ready(&self) -> ModelResult<()> {
+        let mut last = Ok(());
+        for model in &self.models {
+            last = model.ready().await;
+            if last.is_ok() {
+                break;
+            }
+        }
+        last
+    }
+}
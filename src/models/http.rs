@@ -0,0 +1,688 @@
+//! Shared HTTP plumbing for model providers.
+//!
+//! With wire debugging enabled, the exact JSON request and the raw response
+//! body of every call are logged to `tracing` at DEBUG under the
+//! `agentic_optio_rs::wire` target. Headers, and so API keys, are never logged.
+//! Without the `tracing` feature nothing is logged.
+
+use crate::models::base::{ModelError, ModelResult};
+use crate::models::rate_limit::RateLimitInfo;
+use reqwest::header::{HeaderMap, CONTENT_TYPE, RETRY_AFTER};
+use reqwest::{Client, NoProxy, Proxy, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// Time allowed to open a connection unless [`ClientOptions`] sets another
+pub(crate) const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Interval of TCP keepalive probes unless a builder sets another, so pooled
+/// connections are not silently dropped by NATs and load balancers between
+/// the calls of an agent loop
+pub(crate) const DEFAULT_TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+
+/// Client used by every model and store built without one, so they all share
+/// one connection pool; apart from connecting, timeouts are set per request
+///
+/// Like any default reqwest client it sends requests through the proxies in
+/// `HTTPS_PROXY`, `HTTP_PROXY`, and `ALL_PROXY`, except for hosts in `NO_PROXY`.
+pub(crate) fn shared_client() -> Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            Client::builder()
+                .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+                .tcp_keepalive(DEFAULT_TCP_KEEPALIVE)
+                .build()
+                .expect("Failed to build HTTP client")
+        })
+        .clone()
+}
+
+/// Deadlines of a chat model's requests
+#[cfg(any(feature = "ollama", feature = "openai"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Timeouts {
+    /// Whole non-streaming request
+    pub(crate) total: Duration,
+    /// Wait for a response to start, and between stream chunks
+    pub(crate) idle: Duration,
+    /// Whole streaming request, unlimited unless set
+    pub(crate) stream_total: Option<Duration>,
+}
+
+#[cfg(any(feature = "ollama", feature = "openai"))]
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            total: Duration::from_secs(60),
+            idle: Duration::from_secs(60),
+            stream_total: None,
+        }
+    }
+}
+
+#[cfg(any(feature = "ollama", feature = "openai"))]
+impl Timeouts {
+    /// Apply the deadline of a streaming request if `stream`, else of a
+    /// non-streaming one
+    pub(crate) fn apply(&self, request: RequestBuilder, stream: bool) -> RequestBuilder {
+        match (stream, self.stream_total) {
+            (false, _) => request.timeout(self.total),
+            (true, Some(total)) => request.timeout(total),
+            (true, None) => request,
+        }
+    }
+}
+
+/// HTTP client settings of a model, reranker, or store builder, set with its
+/// `http_options` method
+///
+/// Without any of them set, everything built shares one connection pool.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::OllamaChat;
+/// use std::time::Duration;
+///
+/// let llm = OllamaChat::builder("llama3.2")
+///     .http_options(|http| {
+///         http.proxy("http://proxy.corp:3128")
+///             .no_proxy("localhost,.internal")
+///             .pool_idle_timeout(Duration::from_secs(30))
+///     })
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    pub(crate) client: Option<Client>,
+    pub(crate) proxy: Option<String>,
+    pub(crate) no_proxy: Option<String>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) pool: PoolOptions,
+    #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+    pub(crate) root_certificates: Vec<reqwest::Certificate>,
+    #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+    pub(crate) accept_invalid_certs: bool,
+}
+
+/// Connection pool and protocol settings collected by a builder
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PoolOptions {
+    pub(crate) idle_timeout: Option<Duration>,
+    pub(crate) max_idle_per_host: Option<usize>,
+    pub(crate) tcp_keepalive: Option<Duration>,
+    pub(crate) http2_prior_knowledge: bool,
+    pub(crate) http2_keep_alive_interval: Option<Duration>,
+}
+
+impl PoolOptions {
+    fn apply(self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(timeout) = self.idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        builder = builder.tcp_keepalive(self.tcp_keepalive.unwrap_or(DEFAULT_TCP_KEEPALIVE));
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            builder = builder
+                .http2_keep_alive_interval(interval)
+                .http2_keep_alive_while_idle(true);
+        }
+        builder
+    }
+}
+
+impl ClientOptions {
+    /// Send requests through `client` instead of the shared default one, e.g.
+    /// to reuse an application's connection pool or TLS settings; the other
+    /// settings are then the client's own
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Send every request through the proxy at `url`, e.g.
+    /// `http://proxy.corp:3128`, instead of the one in `HTTPS_PROXY`
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Comma-separated hosts, domains, and IP ranges to reach directly instead
+    /// of through the proxy, replacing `NO_PROXY`
+    pub fn no_proxy(mut self, hosts: impl Into<String>) -> Self {
+        self.no_proxy = Some(hosts.into());
+        self
+    }
+
+    /// Time allowed to open a connection (default 10s), so an unreachable
+    /// server fails fast
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Close pooled connections left idle this long (default 90s)
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Most idle connections kept open per host for reuse (default
+    /// unlimited); connections beyond it are closed once their request ends
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool.max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Interval of TCP keepalive probes on open connections (default 60s)
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.pool.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Speak HTTP/2 without negotiating it first, for plaintext servers that
+    /// support it, so concurrent requests share one connection
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.pool.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Ping HTTP/2 connections at this interval, even while idle, so a dead
+    /// connection is noticed before a request is sent on it
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.pool.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Trust `certificate` as a root CA in addition to the built-in ones, e.g.
+    /// for a server behind an internal PKI; load a PEM bundle with
+    /// [`reqwest::Certificate::from_pem_bundle`]
+    #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+    pub fn root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Accept any server certificate, including self-signed and expired ones
+    ///
+    /// This disables protection against impersonation of the server; only use
+    /// it for test servers on trusted networks.
+    #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// The given client, a dedicated one for custom proxy, TLS, connect
+    /// timeout, or pool settings, or the shared one
+    ///
+    /// # Panics
+    ///
+    /// If the proxy URL is invalid, like a failure to build any client.
+    pub(crate) fn build(self) -> Client {
+        self.try_build().expect("Failed to build HTTP client")
+    }
+
+    /// Like [`build`](Self::build), failing instead of panicking
+    pub(crate) fn try_build(self) -> ModelResult<Client> {
+        if let Some(client) = self.client {
+            return Ok(client);
+        }
+        #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+        let custom_tls = !self.root_certificates.is_empty() || self.accept_invalid_certs;
+        #[cfg(not(any(feature = "native-tls", feature = "rustls-tls")))]
+        let custom_tls = false;
+        let custom_proxy = self.proxy.is_some() || self.no_proxy.is_some();
+        let custom_connect = self
+            .connect_timeout
+            .is_some_and(|t| t != DEFAULT_CONNECT_TIMEOUT);
+        let custom_pool = self.pool != PoolOptions::default();
+        if !custom_proxy && !custom_tls && !custom_connect && !custom_pool {
+            return Ok(shared_client());
+        }
+
+        let mut builder = self.pool.apply(
+            Client::builder()
+                .connect_timeout(self.connect_timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT)),
+        );
+        if custom_proxy {
+            let no_proxy = self.no_proxy.as_deref().and_then(NoProxy::from_string);
+            let proxies = match self.proxy {
+                Some(url) => vec![Proxy::all(url)],
+                // Only the exclusions are custom: keep the environment's proxies
+                None => {
+                    let all = env_proxy("ALL_PROXY").map(Proxy::all);
+                    let https = env_proxy("HTTPS_PROXY").map(Proxy::https);
+                    let http = env_proxy("HTTP_PROXY").map(Proxy::http);
+                    [https, http, all].into_iter().flatten().collect()
+                }
+            };
+            builder = builder.no_proxy();
+            for proxy in proxies {
+                let proxy = proxy?;
+                builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+            }
+        }
+        #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+        {
+            for certificate in self.root_certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+            builder = builder.danger_accept_invalid_certs(self.accept_invalid_certs);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Check that `url`, the `name` setting of a builder, is an absolute HTTP(S)
+/// URL
+#[cfg(any(feature = "ollama", feature = "openai"))]
+pub(crate) fn check_url(name: &str, url: &str) -> ModelResult<()> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| ModelError::ConfigError(format!("invalid {name} '{url}': {e}")))?;
+    if !matches!(parsed.scheme(), "http" | "https") || !parsed.has_host() {
+        return Err(ModelError::ConfigError(format!(
+            "invalid {name} '{url}': expected an http or https URL"
+        )));
+    }
+    Ok(())
+}
+
+/// Check that a sampling temperature is within the 0 to 2 range providers
+/// accept
+#[cfg(any(feature = "ollama", feature = "openai"))]
+pub(crate) fn check_temperature(temperature: f32) -> ModelResult<()> {
+    if !(0.0..=2.0).contains(&temperature) {
+        return Err(ModelError::ConfigError(format!(
+            "temperature {temperature} is outside 0.0..=2.0"
+        )));
+    }
+    Ok(())
+}
+
+/// Extra headers sent with every request, e.g. for an authenticating gateway
+///
+/// Debug output lists the names only, since values are often credentials.
+/// Invalid names or values fail the request rather than the builder.
+#[cfg(feature = "ollama")]
+#[derive(Clone, Default)]
+pub(crate) struct Headers(Vec<(String, String)>);
+
+#[cfg(feature = "ollama")]
+impl Headers {
+    /// Set `name`, replacing any earlier value regardless of case
+    pub(crate) fn insert(&mut self, name: String, value: String) {
+        self.0
+            .retain(|(existing, _)| !existing.eq_ignore_ascii_case(&name));
+        self.0.push((name, value));
+    }
+
+    pub(crate) fn apply(&self, mut request: RequestBuilder) -> RequestBuilder {
+        for (name, value) in &self.0 {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request
+    }
+}
+
+#[cfg(feature = "ollama")]
+impl std::fmt::Debug for Headers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(name, _)| name))
+            .finish()
+    }
+}
+
+fn env_proxy(name: &str) -> Option<String> {
+    std::env::var(name)
+        .or_else(|_| std::env::var(name.to_lowercase()))
+        .ok()
+        .filter(|url| !url.is_empty())
+}
+
+/// Longest server-requested wait a retry sleeps through; asked to wait
+/// longer, the call fails with the provider's error instead
+pub(crate) const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Retry budget of a model, and the latest rate-limit headers its provider
+/// sent
+///
+/// Clones share the rate-limit state.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Retry {
+    max_retries: u32,
+    rate_limits: Arc<Mutex<Option<RateLimitInfo>>>,
+}
+
+impl Retry {
+    #[cfg(any(feature = "ollama", feature = "openai"))]
+    pub(crate) fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            rate_limits: Arc::default(),
+        }
+    }
+
+    #[cfg(any(feature = "ollama", feature = "openai"))]
+    pub(crate) fn rate_limits(&self) -> Option<RateLimitInfo> {
+        *self.rate_limits.lock().unwrap()
+    }
+
+    fn observe(&self, headers: &HeaderMap) {
+        if let Some(info) = RateLimitInfo::from_headers(headers) {
+            *self.rate_limits.lock().unwrap() = Some(info);
+        }
+    }
+
+    /// How long to wait before retry number `attempt` (from 0) after `error`,
+    /// or `None` to give up
+    fn delay(&self, attempt: u32, error: &ModelError) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+        let requested = match error {
+            ModelError::RateLimited { retry_after, .. } => *retry_after,
+            ModelError::Timeout(_) => None,
+            ModelError::HttpError(e) if e.is_connect() => None,
+            ModelError::Server { .. } => None,
+            _ => return None,
+        };
+        match requested {
+            Some(delay) => (delay <= MAX_RETRY_DELAY).then_some(delay),
+            None => Some(Duration::from_millis(500) * 2u32.pow(attempt.min(6))),
+        }
+    }
+}
+
+/// Send `body` as JSON and decode the JSON response
+pub(crate) async fn send_json<R: DeserializeOwned + Send + 'static>(
+    request: RequestBuilder,
+    url: &str,
+    body: &impl Serialize,
+    debug_wire: bool,
+    retry: &Retry,
+) -> ModelResult<R> {
+    let response = send(request, url, body, debug_wire, retry, None).await?;
+    if !debug_wire {
+        return parse_json(response).await;
+    }
+    let status = response.status();
+    let text = response.text().await?;
+    log_response(url, status.as_u16(), &text);
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Send `body` as JSON, logging it if wire debugging is enabled, and return the
+/// response for streaming
+#[cfg(any(feature = "ollama", feature = "openai"))]
+pub(crate) async fn send_stream(
+    request: RequestBuilder,
+    url: &str,
+    body: &impl Serialize,
+    debug_wire: bool,
+    retry: &Retry,
+    idle: Duration,
+) -> ModelResult<reqwest::Response> {
+    send(request, url, body, debug_wire, retry, Some(idle)).await
+}
+
+/// End `stream` with [`ModelError::Timeout`] if no item arrives for `idle`
+#[cfg(any(feature = "ollama", feature = "openai"))]
+pub(crate) fn idle_timeout<'a, T: Send + 'a>(
+    stream: impl futures::Stream<Item = ModelResult<T>> + Send + 'a,
+    idle: Duration,
+) -> impl futures::Stream<Item = ModelResult<T>> + Send + 'a {
+    use futures::StreamExt;
+
+    futures::stream::unfold(Some(Box::pin(stream)), move |state| async move {
+        let mut stream = state?;
+        match tokio::time::timeout(idle, stream.next()).await {
+            Ok(item) => Some((item?, Some(stream))),
+            Err(_) => Some((
+                Err(ModelError::Timeout(format!(
+                    "no data received for {idle:?}"
+                ))),
+                None,
+            )),
+        }
+    })
+}
+
+/// Responses declaring at most this many bytes are buffered and parsed at
+/// once; larger ones, and those of unknown length, are parsed as they arrive
+const INCREMENTAL_PARSE_THRESHOLD: u64 = 256 * 1024;
+
+/// Deserialize the JSON body of `response`
+///
+/// A large body is fed chunk by chunk to a parser on a blocking thread, so
+/// the raw text of a big embedding batch or completion is never held in
+/// memory whole, only the values parsed from it.
+async fn parse_json<R: DeserializeOwned + Send + 'static>(
+    response: reqwest::Response,
+) -> ModelResult<R> {
+    use futures::StreamExt;
+
+    if response
+        .content_length()
+        .is_some_and(|length| length <= INCREMENTAL_PARSE_THRESHOLD)
+    {
+        return Ok(response.json::<R>().await?);
+    }
+    let (sender, receiver) = tokio::sync::mpsc::channel(4);
+    let parser = tokio::task::spawn_blocking(move || {
+        serde_json::from_reader::<_, R>(ChunkReader {
+            receiver,
+            chunk: bytes::Bytes::new(),
+        })
+    });
+    let mut body = response.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        // A closed channel means the parser already failed; its error is
+        // the one to report
+        if sender.send(chunk?).await.is_err() {
+            break;
+        }
+    }
+    drop(sender);
+    let parsed = parser
+        .await
+        .map_err(|e| ModelError::InvalidResponse(format!("JSON parser failed: {e}")))?;
+    Ok(parsed?)
+}
+
+/// Blocking reader over body chunks sent from the async side
+struct ChunkReader {
+    receiver: tokio::sync::mpsc::Receiver<bytes::Bytes>,
+    chunk: bytes::Bytes,
+}
+
+impl std::io::Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        use bytes::Buf;
+
+        while !self.chunk.has_remaining() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => self.chunk = chunk,
+                None => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.chunk.remaining());
+        self.chunk.copy_to_slice(&mut buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Fetch a JSON document, once, e.g. for a readiness probe
+#[cfg(any(feature = "ollama", feature = "openai"))]
+pub(crate) async fn get_json<R: DeserializeOwned>(
+    request: RequestBuilder,
+    url: &str,
+    debug_wire: bool,
+) -> ModelResult<R> {
+    let response = check_status(request.send().await?, url, debug_wire).await?;
+    Ok(response.json::<R>().await?)
+}
+
+/// Send `body` until it succeeds or the retries run out
+///
+/// Rate limits, timeouts, connection failures, and server errors are retried,
+/// after the delay the provider asked for in `Retry-After` or its rate-limit
+/// headers, or else after an exponential backoff from 0.5s. With `idle`, an
+/// attempt whose response does not start within it times out.
+async fn send(
+    request: RequestBuilder,
+    url: &str,
+    body: &impl Serialize,
+    debug_wire: bool,
+    retry: &Retry,
+    idle: Option<Duration>,
+) -> ModelResult<reqwest::Response> {
+    let mut attempt = 0;
+    loop {
+        let attempt_request = request
+            .try_clone()
+            .ok_or_else(|| ModelError::ConfigError("request cannot be retried".to_string()))?;
+        let exchange = async {
+            if debug_wire {
+                send_logged(attempt_request, url, body).await
+            } else {
+                attempt_request
+                    .json(body)
+                    .send()
+                    .await
+                    .map_err(ModelError::from)
+            }
+        };
+        let result = match idle {
+            Some(idle) => tokio::time::timeout(idle, exchange)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(ModelError::Timeout(format!(
+                        "no response received for {idle:?}"
+                    )))
+                }),
+            None => exchange.await,
+        };
+        let result = match result {
+            Ok(response) => {
+                retry.observe(response.headers());
+                check_status(response, url, debug_wire).await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Err(error) => match retry.delay(attempt, &error) {
+                Some(delay) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        url,
+                        error = %error,
+                        attempt = attempt + 1,
+                        delay_ms = delay.as_millis() as u64,
+                        "retrying request"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return Err(error),
+            },
+            ok => return ok,
+        }
+    }
+}
+
+/// Pass a successful response through, or read the body of a failed one
+/// into a classified [`ModelError`]
+async fn check_status(
+    response: reqwest::Response,
+    url: &str,
+    debug_wire: bool,
+) -> ModelResult<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let retry_after = retry_after(response.headers());
+    let text = response.text().await.unwrap_or_default();
+    if debug_wire {
+        log_response(url, status.as_u16(), &text);
+    }
+    Err(ModelError::from_http_status(
+        status.as_u16(),
+        &text,
+        retry_after,
+    ))
+}
+
+/// Delay requested by `Retry-After-Ms` or `Retry-After` (in seconds or as an
+/// HTTP date), or else the time until a used-up rate-limit quota refills
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name| headers.get(name)?.to_str().ok().map(str::trim);
+    if let Some(millis) = header(RETRY_AFTER_MS).and_then(|v| v.parse::<f64>().ok()) {
+        return (millis >= 0.0).then(|| Duration::from_secs_f64(millis / 1000.0));
+    }
+    if let Some(value) = header(RETRY_AFTER.as_str()) {
+        if let Ok(seconds) = value.parse::<f64>() {
+            return (seconds >= 0.0).then(|| Duration::from_secs_f64(seconds));
+        }
+        if let Ok(date) = chrono::DateTime::parse_from_rfc2822(value) {
+            return Some(
+                (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+                    .to_std()
+                    .unwrap_or(Duration::ZERO),
+            );
+        }
+    }
+    RateLimitInfo::from_headers(headers)?.wait()
+}
+
+const RETRY_AFTER_MS: &str = "retry-after-ms";
+
+async fn send_logged(
+    request: RequestBuilder,
+    url: &str,
+    body: &impl Serialize,
+) -> ModelResult<reqwest::Response> {
+    let payload = serde_json::to_string(body)?;
+    log_request(url, &payload);
+    Ok(request
+        .header(CONTENT_TYPE, "application/json")
+        .body(payload)
+        .send()
+        .await?)
+}
+
+#[cfg(feature = "tracing")]
+fn log_request(url: &str, body: &str) {
+    tracing::debug!(target: "agentic_optio_rs::wire", url, body, "request");
+}
+
+#[cfg(not(feature = "tracing"))]
+fn log_request(_url: &str, _body: &str) {}
+
+#[cfg(feature = "tracing")]
+fn log_response(url: &str, status: u16, body: &str) {
+    tracing::debug!(target: "agentic_optio_rs::wire", url, status, body, "response");
+}
+
+#[cfg(not(feature = "tracing"))]
+fn log_response(_url: &str, _status: u16, _body: &str) {}
+
+/// Log one raw chunk of a streamed response body
+#[cfg(all(feature = "tracing", any(feature = "ollama", feature = "openai")))]
+pub(crate) fn log_chunk(url: &str, chunk: &[u8]) {
+    let body = String::from_utf8_lossy(chunk);
+    tracing::debug!(target: "agentic_optio_rs::wire", url, body = %body, "response chunk");
+}
+
+#[cfg(all(not(feature = "tracing"), any(feature = "ollama", feature = "openai")))]
+pub(crate) fn log_chunk(_url: &str, _chunk: &[u8]) {}
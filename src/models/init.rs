@@ -0,0 +1,32 @@
+//! Chat model construction from a `provider:model` string.
+
+use crate::config::ModelConfig;
+use crate::models::base::{BaseChatModel, ModelResult};
+
+/// Chat model for a `provider:model` spec, configured from the environment
+///
+/// The provider is everything before the first `:`, so model tags survive:
+/// `"ollama:llama3.2:8b"` is the `llama3.2:8b` model on Ollama. Supported
+/// providers:
+///
+/// - `ollama`: host from `OLLAMA_HOST`, default `http://localhost:11434`
+/// - `openai`: key from `OPENAI_API_KEY` and base URL from `OPENAI_BASE_URL`,
+///   default `https://api.openai.com/v1`; the key may only be omitted for a
+///   custom base URL
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::{init_chat_model, Message};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let llm = init_chat_model("ollama:llama3.2")?;
+///     let response = llm.invoke(&[Message::user("Hello!")]).await?;
+///     println!("{}", response.content);
+///     Ok(())
+/// }
+/// ```
+pub fn init_chat_model(spec: &str) -> ModelResult<Box<dyn BaseChatModel>> {
+    ModelConfig::new(spec).build()
+}
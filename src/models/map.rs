@@ -0,0 +1,97 @@
+//! Ordered concurrent invocation over a collection.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, ModelError, ModelResult};
+use futures::stream::{self, StreamExt};
+
+/// Invoke `model` on the messages `prompt` builds for each of `items`, at most
+/// `concurrency` at a time
+///
+/// Results come back in the order of `items`, whatever order the requests
+/// finish in. A failed item does not stop the others: its error takes its
+/// place in the [`MapResults`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::map_invoke;
+/// use agentic_optio_rs::{Message, OllamaChat};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let llm = OllamaChat::new("llama3.2");
+///     let reviews = ["Loved it", "Broke after a day", "It's fine"];
+///     let labels = map_invoke(
+///         &llm,
+///         reviews,
+///         |review| {
+///             vec![
+///                 Message::system("Answer positive, negative, or neutral."),
+///                 Message::user(format!("Review: {review}")),
+///             ]
+///         },
+///         8,
+///     )
+///     .await;
+///     for (review, label) in reviews.iter().zip(labels.contents()) {
+///         println!("{review}: {}", label.unwrap_or("<failed>"));
+///     }
+///     for (index, error) in labels.errors() {
+///         eprintln!("review {index} failed: {error}");
+///     }
+/// }
+/// ```
+pub async fn map_invoke<T>(
+    model: &(impl BaseChatModel + ?Sized),
+    items: impl IntoIterator<Item = T>,
+    prompt: impl Fn(T) -> Vec<Message>,
+    concurrency: usize,
+) -> MapResults {
+    let results = stream::iter(items)
+        .map(|item| {
+            let messages = prompt(item);
+            async move { model.invoke(&messages).await }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+    MapResults { results }
+}
+
+/// Per-item results of [`map_invoke`], in the order of its items
+#[derive(Debug)]
+pub struct MapResults {
+    pub results: Vec<ModelResult<AIMessage>>,
+}
+
+impl MapResults {
+    /// Content of each response, `None` where the item failed
+    pub fn contents(&self) -> Vec<Option<&str>> {
+        self.results
+            .iter()
+            .map(|r| r.as_ref().ok().map(|m| m.content.as_str()))
+            .collect()
+    }
+
+    /// Index and error of every failed item
+    pub fn errors(&self) -> Vec<(usize, &ModelError)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| r.as_ref().err().map(|e| (i, e)))
+            .collect()
+    }
+
+    pub fn succeeded(&self) -> usize {
+        self.results.iter().filter(|r| r.is_ok()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.succeeded()
+    }
+
+    /// Every response, or the first error
+    pub fn into_result(self) -> ModelResult<Vec<AIMessage>> {
+        self.results.into_iter().collect()
+    }
+}
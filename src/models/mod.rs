@@ -3,7 +3,43 @@
 //! This module contains all model implementations and base classes.
 
 pub mod base;
+mod buffer;
+#[cfg(any(feature = "ollama", feature = "openai"))]
+mod chat_completions;
+mod concurrency;
+mod handle;
+mod health;
+mod hedge;
+pub(crate) mod http;
+mod init;
+mod map;
+#[cfg(feature = "ollama")]
 pub mod ollama;
+#[cfg(feature = "openai")]
+pub mod openai;
+pub mod rate_limit;
+mod registry;
+pub mod rerank;
+mod resume;
+mod router;
+mod trim;
 
-pub use base::{BaseChatModel, BaseEmbedding};
+pub use base::{BaseChatModel, BaseEmbedding, BaseReranker};
+pub use buffer::StreamBufferExt;
+pub use concurrency::{ConcurrencyLimiter, ConcurrencyPermit, LimitedChatModel};
+pub use handle::ChatModel;
+pub use health::{Health, HealthHandle, HealthMonitor};
+pub use hedge::HedgedChatModel;
+pub use http::ClientOptions;
+pub use init::init_chat_model;
+pub use map::{map_invoke, MapResults};
+#[cfg(feature = "ollama")]
 pub use ollama::{OllamaChat, OllamaEmbedding};
+#[cfg(feature = "openai")]
+pub use openai::{OpenAIChat, OpenAIChatBuilder};
+pub use rate_limit::RateLimitInfo;
+pub use registry::ModelRegistry;
+pub use rerank::{HttpReranker, HttpRerankerBuilder, LlmReranker};
+pub use resume::ResumingChatModel;
+pub use router::Router;
+pub use trim::{TrimStrategy, TrimmingChatModel};
@@ -2,75 +2,22 @@
 //!
 //! Ollama runs LLMs locally. Supports Llama, Mistral, Qwen, and other models.
 
-use crate::core::messages::{AIMessage, Message, ToolCall};
-use crate::models::base::{BaseChatModel, BaseEmbedding, BoxStream, ModelError, ModelResult};
+use crate::core::messages::{AIMessage, Message};
+use crate::embeddings::EmbeddingTransform;
+use crate::models::base::{
+    measure_stream, BaseChatModel, BaseEmbedding, BoxStream, ModelError, ModelResult,
+};
+use crate::models::chat_completions::{self, ChatRequest, ChatResponse};
+use crate::models::http;
+use crate::models::rate_limit::RateLimitInfo;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 const DEFAULT_HOST: &str = "http://localhost:11434";
 
-/// OpenAI-compatible chat completion request
-#[derive(Debug, Serialize)]
-struct ChatRequest {
-    model: String,
-    messages: Vec<serde_json::Value>,
-    temperature: f32,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    max_tokens: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    tools: Option<Vec<serde_json::Value>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    stream: Option<bool>,
-}
-
-/// OpenAI-compatible chat completion response
-#[derive(Debug, Deserialize)]
-struct ChatResponse {
-    choices: Vec<Choice>,
-}
-
-#[derive(Debug, Deserialize)]
-struct Choice {
-    message: ResponseMessage,
-}
-
-#[derive(Debug, Deserialize)]
-struct ResponseMessage {
-    content: Option<String>,
-    #[serde(default)]
-    tool_calls: Vec<ResponseToolCall>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ResponseToolCall {
-    id: String,
-    function: FunctionCall,
-}
-
-#[derive(Debug, Deserialize)]
-struct FunctionCall {
-    name: String,
-    arguments: String,
-}
-
-/// Streaming chunk response
-#[derive(Debug, Deserialize)]
-struct StreamChunk {
-    choices: Vec<StreamChoice>,
-}
-
-#[derive(Debug, Deserialize)]
-struct StreamChoice {
-    delta: Delta,
-}
-
-#[derive(Debug, Deserialize)]
-struct Delta {
-    content: Option<String>,
-}
-
 /// Embedding request
 #[derive(Debug, Serialize)]
 struct EmbeddingRequest {
@@ -112,10 +59,10 @@ pub struct OllamaChat {
     host: String,
     temperature: f32,
     max_tokens: Option<u32>,
-    #[allow(dead_code)]
-    timeout: Duration,
-    #[allow(dead_code)]
-    max_retries: u32,
+    timeouts: http::Timeouts,
+    retry: http::Retry,
+    debug_wire: bool,
+    headers: http::Headers,
     client: Client,
 }
 
@@ -130,36 +77,48 @@ impl OllamaChat {
         OllamaChatBuilder::new(model)
     }
 
-    fn parse_response(response: ChatResponse) -> ModelResult<AIMessage> {
-        let choice = response
-            .choices
-            .into_iter()
-            .next()
-            .ok_or_else(|| ModelError::InvalidResponse("No choices in response".to_string()))?;
-
-        let message = choice.message;
-        let content = message.content.unwrap_or_default();
-
-        let tool_calls: Vec<ToolCall> = message
-            .tool_calls
-            .into_iter()
-            .map(|tc| {
-                let args = serde_json::from_str(&tc.function.arguments).unwrap_or_default();
-                ToolCall {
-                    id: tc.id,
-                    name: tc.function.name,
-                    args,
-                }
-            })
-            .collect();
+    /// Quota left according to the latest response's rate-limit headers, if
+    /// the server or a gateway in front of it sends them
+    pub fn rate_limits(&self) -> Option<RateLimitInfo> {
+        self.retry.rate_limits()
+    }
 
-        Ok(AIMessage::with_tool_calls(content, tool_calls))
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "chat",
+            skip_all,
+            err,
+            fields(
+                model = %self.model,
+                provider = "ollama",
+                messages = messages.len(),
+                tools = tools.as_ref().map_or(0, Vec::len),
+                input_tokens = tracing::field::Empty,
+                output_tokens = tracing::field::Empty,
+                tool_calls = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    async fn chat(
+        &self,
+        messages: &[Message],
+        tools: Option<Vec<serde_json::Value>>,
+    ) -> ModelResult<AIMessage> {
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+        let result = self.send_chat(messages, tools).await;
+        #[cfg(feature = "tracing")]
+        crate::telemetry::spans::record_chat(&result, started);
+        result
     }
-}
 
-#[async_trait]
-impl BaseChatModel for OllamaChat {
-    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+    async fn send_chat(
+        &self,
+        messages: &[Message],
+        tools: Option<Vec<serde_json::Value>>,
+    ) -> ModelResult<AIMessage> {
         let url = format!("{}/v1/chat/completions", self.host.trim_end_matches('/'));
 
         let messages_dict: Vec<serde_json::Value> = messages.iter().map(|m| m.to_dict()).collect();
@@ -169,27 +128,65 @@ impl BaseChatModel for OllamaChat {
             messages: messages_dict,
             temperature: self.temperature,
             max_tokens: self.max_tokens,
-            tools: None,
+            tools,
             stream: None,
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?
-            .error_for_status()?
-            .json::<ChatResponse>()
-            .await?;
+        let response: ChatResponse = http::send_json(
+            self.headers
+                .apply(self.timeouts.apply(self.client.post(&url), false)),
+            &url,
+            &request,
+            self.debug_wire,
+            &self.retry,
+        )
+        .await?;
+
+        chat_completions::parse_response(response)
+    }
+}
 
-        Self::parse_response(response)
+#[async_trait]
+impl BaseChatModel for OllamaChat {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        self.chat(messages, None).await
     }
 
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        let tools = if tools.is_empty() {
+            None
+        } else {
+            Some(tools.to_vec())
+        };
+        self.chat(messages, tools).await
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "chat_stream",
+            skip_all,
+            err,
+            fields(
+                model = %self.model,
+                provider = "ollama",
+                messages = messages.len(),
+                chunks = tracing::field::Empty,
+                time_to_first_token_ms = tracing::field::Empty,
+                tokens_per_second = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+            )
+        )
+    )]
     async fn stream<'a>(
         &'a self,
         messages: &'a [Message],
     ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let started = std::time::Instant::now();
         let url = format!("{}/v1/chat/completions", self.host.trim_end_matches('/'));
 
         let messages_dict: Vec<serde_json::Value> = messages.iter().map(|m| m.to_dict()).collect();
@@ -203,43 +200,68 @@ impl BaseChatModel for OllamaChat {
             stream: Some(true),
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?
-            .error_for_status()?;
+        let response = http::send_stream(
+            self.headers
+                .apply(self.timeouts.apply(self.client.post(&url), true)),
+            &url,
+            &request,
+            self.debug_wire,
+            &self.retry,
+            self.timeouts.idle,
+        )
+        .await?;
 
         use bytes::Bytes;
         use futures::stream::TryStreamExt;
 
-        let stream = response
-            .bytes_stream()
-            .map_err(ModelError::HttpError)
-            .and_then(|bytes: Bytes| async move {
-                let text = String::from_utf8_lossy(&bytes);
-
-                // Parse SSE format: "data: {...}\n\n"
-                for line in text.lines() {
-                    if let Some(json_str) = line.strip_prefix("data: ") {
-                        if json_str == "[DONE]" {
-                            continue;
-                        }
-                        if let Ok(chunk) = serde_json::from_str::<StreamChunk>(json_str) {
-                            if let Some(choice) = chunk.choices.first() {
-                                if let Some(content) = &choice.delta.content {
-                                    return Ok(AIMessage::new(content.clone()));
-                                }
-                            }
-                        }
-                    }
-                }
-
-                Ok(AIMessage::new(""))
-            });
-
-        Ok(Box::pin(stream))
+        let debug_wire = self.debug_wire;
+        let stream = http::idle_timeout(
+            response.bytes_stream().map_err(ModelError::from),
+            self.timeouts.idle,
+        )
+        .inspect_ok(move |bytes| {
+            if debug_wire {
+                http::log_chunk(&url, bytes);
+            }
+        })
+        .map_ok(|bytes: Bytes| chat_completions::parse_stream_chunk(&bytes));
+
+        let stream = measure_stream(Box::pin(stream), started);
+        #[cfg(feature = "tracing")]
+        return Ok(crate::telemetry::spans::instrument_stream(stream, started));
+        #[cfg(not(feature = "tracing"))]
+        Ok(stream)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    fn provider_name(&self) -> &str {
+        "ollama"
+    }
+
+    /// Lists the server's models and checks this one has been pulled
+    async fn ready(&self) -> ModelResult<()> {
+        let url = format!("{}/api/tags", self.host.trim_end_matches('/'));
+        let tags: serde_json::Value = http::get_json(
+            self.headers
+                .apply(self.client.get(&url).timeout(self.timeouts.total)),
+            &url,
+            self.debug_wire,
+        )
+        .await?;
+        let pulled = tags["models"].as_array().into_iter().flatten().any(|m| {
+            let name = m["name"].as_str().unwrap_or_default();
+            name == self.model || name.strip_suffix(":latest") == Some(self.model.as_str())
+        });
+        if !pulled {
+            return Err(ModelError::ModelNotFound(format!(
+                "model '{}' is not pulled on {}",
+                self.model, self.host
+            )));
+        }
+        Ok(())
     }
 }
 
@@ -249,8 +271,11 @@ pub struct OllamaChatBuilder {
     host: String,
     temperature: f32,
     max_tokens: Option<u32>,
-    timeout: Duration,
+    timeouts: http::Timeouts,
     max_retries: u32,
+    debug_wire: bool,
+    headers: http::Headers,
+    http: http::ClientOptions,
 }
 
 impl OllamaChatBuilder {
@@ -260,8 +285,11 @@ impl OllamaChatBuilder {
             host: std::env::var("OLLAMA_HOST").unwrap_or_else(|_| DEFAULT_HOST.to_string()),
             temperature: 0.0,
             max_tokens: None,
-            timeout: Duration::from_secs(60),
+            timeouts: http::Timeouts::default(),
             max_retries: 2,
+            debug_wire: false,
+            headers: http::Headers::default(),
+            http: http::ClientOptions::default(),
         }
     }
 
@@ -280,29 +308,94 @@ impl OllamaChatBuilder {
         self
     }
 
+    /// Deadline for a whole non-streaming request (default 60s)
     pub fn timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = timeout;
+        self.timeouts.total = timeout;
         self
     }
 
+    /// Longest wait for a response to start, and between the chunks of a
+    /// stream (default 60s)
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.idle = timeout;
+        self
+    }
+
+    /// Deadline for a whole stream; by default streams run as long as chunks
+    /// keep arriving within the [`idle_timeout`](Self::idle_timeout)
+    pub fn stream_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.stream_total = Some(timeout);
+        self
+    }
+
+    /// Retries after rate limits, timeouts, connection failures, and server
+    /// errors (default 2), waiting as long as the provider asks
     pub fn max_retries(mut self, max_retries: u32) -> Self {
         self.max_retries = max_retries;
         self
     }
 
-    pub fn build(self) -> OllamaChat {
-        let client = Client::builder()
-            .timeout(self.timeout)
-            .build()
-            .expect("Failed to build HTTP client");
+    /// Proxy, connection pool, and TLS settings of the HTTP client; see
+    /// [`ClientOptions`](crate::models::ClientOptions)
+    pub fn http_options(
+        mut self,
+        configure: impl FnOnce(http::ClientOptions) -> http::ClientOptions,
+    ) -> Self {
+        self.http = configure(std::mem::take(&mut self.http));
+        self
+    }
+
+    /// Send header `name` with every request, e.g. for an authenticating
+    /// reverse proxy in front of Ollama
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Send `token` as a bearer token with every request
+    pub fn bearer_token(self, token: impl AsRef<str>) -> Self {
+        let value = format!("Bearer {}", token.as_ref());
+        self.header("Authorization", value)
+    }
+
+    /// Log exact request and raw response bodies to `tracing` at DEBUG under
+    /// the `agentic_optio_rs::wire` target; needs the `tracing` feature
+    pub fn debug_wire(mut self, debug_wire: bool) -> Self {
+        self.debug_wire = debug_wire;
+        self
+    }
+
+    /// Build the model without validating its settings
+    ///
+    /// # Panics
+    ///
+    /// If the HTTP client cannot be built, e.g. for an invalid proxy URL; use
+    /// [`try_build`](Self::try_build) to get an error instead.
+    pub fn build(mut self) -> OllamaChat {
+        let client = std::mem::take(&mut self.http).build();
+        self.finish(client)
+    }
 
+    /// Build the model, failing with [`ModelError::ConfigError`] for an
+    /// invalid host or temperature and [`ModelError::HttpError`] if the HTTP
+    /// client cannot be built
+    pub fn try_build(mut self) -> ModelResult<OllamaChat> {
+        http::check_url("host", &self.host)?;
+        http::check_temperature(self.temperature)?;
+        let client = std::mem::take(&mut self.http).try_build()?;
+        Ok(self.finish(client))
+    }
+
+    fn finish(self, client: Client) -> OllamaChat {
         OllamaChat {
             model: self.model,
             host: self.host,
             temperature: self.temperature,
             max_tokens: self.max_tokens,
-            timeout: self.timeout,
-            max_retries: self.max_retries,
+            timeouts: self.timeouts,
+            retry: http::Retry::new(self.max_retries),
+            debug_wire: self.debug_wire,
+            headers: self.headers,
             client,
         }
     }
@@ -328,11 +421,14 @@ impl OllamaChatBuilder {
 pub struct OllamaEmbedding {
     model: String,
     host: String,
-    #[allow(dead_code)]
     timeout: Duration,
-    #[allow(dead_code)]
-    max_retries: u32,
+    retry: http::Retry,
     batch_size: usize,
+    transform: EmbeddingTransform,
+    /// Output dimension, declared by the builder or learned from a response
+    dimension: Arc<OnceLock<usize>>,
+    debug_wire: bool,
+    headers: http::Headers,
     client: Client,
 }
 
@@ -346,11 +442,64 @@ impl OllamaEmbedding {
     pub fn builder(model: impl Into<String>) -> OllamaEmbeddingBuilder {
         OllamaEmbeddingBuilder::new(model)
     }
+
+    /// Quota left according to the latest response's rate-limit headers, if
+    /// the server or a gateway in front of it sends them
+    pub fn rate_limits(&self) -> Option<RateLimitInfo> {
+        self.retry.rate_limits()
+    }
 }
 
 #[async_trait]
 impl BaseEmbedding for OllamaEmbedding {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "embed",
+            skip_all,
+            err,
+            fields(
+                model = %self.model,
+                provider = "ollama",
+                texts = texts.len(),
+                batch_size = self.batch_size,
+                dimension = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+            )
+        )
+    )]
     async fn embed(&self, texts: &[String]) -> ModelResult<Vec<Vec<f32>>> {
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+        let result = self.embed_batches(texts).await;
+        #[cfg(feature = "tracing")]
+        {
+            if let Ok(embeddings) = &result {
+                let dimension = embeddings.first().map_or(0, Vec::len);
+                tracing::Span::current().record("dimension", dimension);
+            }
+            crate::telemetry::spans::record_elapsed(started);
+        }
+        result
+    }
+
+    /// Declared or truncated dimension, else the length of the first embedding
+    /// returned; 0 before any request
+    fn dimension(&self) -> usize {
+        self.dimension.get().copied().unwrap_or(0)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    fn provider_name(&self) -> &str {
+        "ollama"
+    }
+}
+
+impl OllamaEmbedding {
+    async fn embed_batches(&self, texts: &[String]) -> ModelResult<Vec<Vec<f32>>> {
         let url = format!("{}/v1/embeddings", self.host.trim_end_matches('/'));
 
         let mut all_embeddings = Vec::new();
@@ -361,21 +510,23 @@ impl BaseEmbedding for OllamaEmbedding {
                 input: chunk.to_vec(),
             };
 
-            let mut response = self
-                .client
-                .post(&url)
-                .json(&request)
-                .send()
-                .await?
-                .error_for_status()?
-                .json::<EmbeddingResponse>()
-                .await?;
+            let mut response: EmbeddingResponse = http::send_json(
+                self.headers
+                    .apply(self.client.post(&url).timeout(self.timeout)),
+                &url,
+                &request,
+                self.debug_wire,
+                &self.retry,
+            )
+            .await?;
 
             // Sort by index to maintain order
             response.data.sort_by_key(|d| d.index);
 
             for data in response.data {
-                all_embeddings.push(data.embedding);
+                let embedding = self.transform.apply(data.embedding)?;
+                self.dimension.get_or_init(|| embedding.len());
+                all_embeddings.push(embedding);
             }
         }
 
@@ -390,6 +541,11 @@ pub struct OllamaEmbeddingBuilder {
     timeout: Duration,
     max_retries: u32,
     batch_size: usize,
+    transform: EmbeddingTransform,
+    dimension: Option<usize>,
+    debug_wire: bool,
+    headers: http::Headers,
+    http: http::ClientOptions,
 }
 
 impl OllamaEmbeddingBuilder {
@@ -400,6 +556,11 @@ impl OllamaEmbeddingBuilder {
             timeout: Duration::from_secs(60),
             max_retries: 2,
             batch_size: 100,
+            transform: EmbeddingTransform::default(),
+            dimension: None,
+            debug_wire: false,
+            headers: http::Headers::default(),
+            http: http::ClientOptions::default(),
         }
     }
 
@@ -408,11 +569,14 @@ impl OllamaEmbeddingBuilder {
         self
     }
 
+    /// Deadline for each request (default 60s)
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
         self
     }
 
+    /// Retries after rate limits, timeouts, connection failures, and server
+    /// errors (default 2), waiting as long as the provider asks
     pub fn max_retries(mut self, max_retries: u32) -> Self {
         self.max_retries = max_retries;
         self
@@ -423,18 +587,105 @@ impl OllamaEmbeddingBuilder {
         self
     }
 
-    pub fn build(self) -> OllamaEmbedding {
-        let client = Client::builder()
-            .timeout(self.timeout)
-            .build()
-            .expect("Failed to build HTTP client");
+    /// L2-normalize every returned embedding
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.transform = self.transform.normalize(normalize);
+        self
+    }
+
+    /// Truncate embeddings to their first `dimensions` components
+    ///
+    /// Only meaningful for Matryoshka-trained models such as nomic-embed-text
+    /// v1.5; combine with [`normalize`](Self::normalize) for cosine stores.
+    pub fn dimensions(mut self, dimensions: usize) -> Self {
+        self.transform = self.transform.dimensions(dimensions);
+        self
+    }
+
+    /// Declare the model's output dimension so [`BaseEmbedding::dimension`] is
+    /// accurate before the first request
+    ///
+    /// Ignored when [`dimensions`](Self::dimensions) truncates embeddings.
+    pub fn embedding_dimension(mut self, dimension: usize) -> Self {
+        self.dimension = Some(dimension);
+        self
+    }
+
+    /// Proxy, connection pool, and TLS settings of the HTTP client; see
+    /// [`ClientOptions`](crate::models::ClientOptions)
+    pub fn http_options(
+        mut self,
+        configure: impl FnOnce(http::ClientOptions) -> http::ClientOptions,
+    ) -> Self {
+        self.http = configure(std::mem::take(&mut self.http));
+        self
+    }
+
+    /// Send header `name` with every request, e.g. for an authenticating
+    /// reverse proxy in front of Ollama
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Send `token` as a bearer token with every request
+    pub fn bearer_token(self, token: impl AsRef<str>) -> Self {
+        let value = format!("Bearer {}", token.as_ref());
+        self.header("Authorization", value)
+    }
+
+    /// Log exact request and raw response bodies to `tracing` at DEBUG under
+    /// the `agentic_optio_rs::wire` target; needs the `tracing` feature
+    pub fn debug_wire(mut self, debug_wire: bool) -> Self {
+        self.debug_wire = debug_wire;
+        self
+    }
 
+    /// Build the model without validating its settings
+    ///
+    /// # Panics
+    ///
+    /// If the HTTP client cannot be built, e.g. for an invalid proxy URL; use
+    /// [`try_build`](Self::try_build) to get an error instead.
+    pub fn build(mut self) -> OllamaEmbedding {
+        let client = std::mem::take(&mut self.http).build();
+        self.finish(client)
+    }
+
+    /// Build the model, failing with [`ModelError::ConfigError`] for an
+    /// invalid host, a zero batch size or dimension, and
+    /// [`ModelError::HttpError`] if the HTTP client cannot be built
+    pub fn try_build(mut self) -> ModelResult<OllamaEmbedding> {
+        http::check_url("host", &self.host)?;
+        if self.batch_size == 0 {
+            return Err(ModelError::ConfigError(
+                "batch size must be at least 1".to_string(),
+            ));
+        }
+        if self.transform.dimensions == Some(0) || self.dimension == Some(0) {
+            return Err(ModelError::ConfigError(
+                "embedding dimension must be at least 1".to_string(),
+            ));
+        }
+        let client = std::mem::take(&mut self.http).try_build()?;
+        Ok(self.finish(client))
+    }
+
+    fn finish(self, client: Client) -> OllamaEmbedding {
+        let dimension = OnceLock::new();
+        if let Some(known) = self.transform.dimensions.or(self.dimension) {
+            let _ = dimension.set(known);
+        }
         OllamaEmbedding {
             model: self.model,
             host: self.host,
             timeout: self.timeout,
-            max_retries: self.max_retries,
+            retry: http::Retry::new(self.max_retries),
             batch_size: self.batch_size,
+            transform: self.transform,
+            dimension: Arc::new(dimension),
+            debug_wire: self.debug_wire,
+            headers: self.headers,
             client,
         }
     }
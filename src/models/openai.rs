@@ -0,0 +1,410 @@
+//! OpenAI models for AgenticOptio.
+//!
+//! Chat completions against the OpenAI API or any OpenAI-compatible server,
+//! such as vLLM, LM Studio, or a gateway.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::credentials::{ApiKey, CredentialProvider};
+use crate::models::base::{measure_stream, BaseChatModel, BoxStream, ModelError, ModelResult};
+use crate::models::chat_completions::{
+    parse_response, parse_stream_chunk, ChatRequest, ChatResponse,
+};
+use crate::models::http;
+use crate::models::rate_limit::RateLimitInfo;
+use async_trait::async_trait;
+use reqwest::Client;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// OpenAI chat model
+///
+/// Also works with any server exposing the OpenAI chat completions API: point
+/// [`base_url`](OpenAIChatBuilder::base_url) at it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::OpenAIChat;
+/// use agentic_optio_rs::{BaseChatModel, Message};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Reads OPENAI_API_KEY from the environment
+///     let llm = OpenAIChat::new("gpt-4o-mini");
+///     let messages = vec![Message::user("Hello!")];
+///     let response = llm.invoke(&messages).await?;
+///     println!("{}", response.content);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct OpenAIChat {
+    model: String,
+    base_url: String,
+    api_key: ApiKey,
+    temperature: f32,
+    max_tokens: Option<u32>,
+    timeouts: http::Timeouts,
+    retry: http::Retry,
+    debug_wire: bool,
+    client: Client,
+}
+
+impl std::fmt::Debug for OpenAIChat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpenAIChat")
+            .field("model", &self.model)
+            .field("base_url", &self.base_url)
+            .field("api_key", &self.api_key)
+            .field("temperature", &self.temperature)
+            .field("max_tokens", &self.max_tokens)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OpenAIChat {
+    /// Create a new OpenAI chat model
+    pub fn new(model: impl Into<String>) -> Self {
+        Self::builder(model).build()
+    }
+
+    /// Create a builder for configuring the model
+    pub fn builder(model: impl Into<String>) -> OpenAIChatBuilder {
+        OpenAIChatBuilder::new(model)
+    }
+
+    async fn request(&self, url: &str, stream: bool) -> ModelResult<reqwest::RequestBuilder> {
+        self.authorize(self.timeouts.apply(self.client.post(url), stream))
+            .await
+    }
+
+    async fn authorize(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> ModelResult<reqwest::RequestBuilder> {
+        Ok(match self.api_key.resolve().await? {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        })
+    }
+
+    /// Quota left according to the latest response's rate-limit headers,
+    /// if the server sends them
+    pub fn rate_limits(&self) -> Option<RateLimitInfo> {
+        self.retry.rate_limits()
+    }
+
+    fn url(&self) -> String {
+        format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "chat",
+            skip_all,
+            err,
+            fields(
+                model = %self.model,
+                provider = "openai",
+                messages = messages.len(),
+                tools = tools.as_ref().map_or(0, Vec::len),
+                input_tokens = tracing::field::Empty,
+                output_tokens = tracing::field::Empty,
+                tool_calls = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    async fn chat(
+        &self,
+        messages: &[Message],
+        tools: Option<Vec<serde_json::Value>>,
+    ) -> ModelResult<AIMessage> {
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+        let result = self.send_chat(messages, tools).await;
+        #[cfg(feature = "tracing")]
+        crate::telemetry::spans::record_chat(&result, started);
+        result
+    }
+
+    async fn send_chat(
+        &self,
+        messages: &[Message],
+        tools: Option<Vec<serde_json::Value>>,
+    ) -> ModelResult<AIMessage> {
+        let url = self.url();
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages: messages.iter().map(|m| m.to_dict()).collect(),
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            tools,
+            stream: None,
+        };
+
+        let response: ChatResponse = http::send_json(
+            self.request(&url, false).await?,
+            &url,
+            &request,
+            self.debug_wire,
+            &self.retry,
+        )
+        .await?;
+
+        parse_response(response)
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for OpenAIChat {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        self.chat(messages, None).await
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        let tools = if tools.is_empty() {
+            None
+        } else {
+            Some(tools.to_vec())
+        };
+        self.chat(messages, tools).await
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "chat_stream",
+            skip_all,
+            err,
+            fields(
+                model = %self.model,
+                provider = "openai",
+                messages = messages.len(),
+                chunks = tracing::field::Empty,
+                time_to_first_token_ms = tracing::field::Empty,
+                tokens_per_second = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        use futures::stream::TryStreamExt;
+
+        let started = std::time::Instant::now();
+        let url = self.url();
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages: messages.iter().map(|m| m.to_dict()).collect(),
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            tools: None,
+            stream: Some(true),
+        };
+
+        let response = http::send_stream(
+            self.request(&url, true).await?,
+            &url,
+            &request,
+            self.debug_wire,
+            &self.retry,
+            self.timeouts.idle,
+        )
+        .await?;
+
+        let debug_wire = self.debug_wire;
+        let stream = http::idle_timeout(
+            response.bytes_stream().map_err(ModelError::from),
+            self.timeouts.idle,
+        )
+        .inspect_ok(move |bytes| {
+            if debug_wire {
+                http::log_chunk(&url, bytes);
+            }
+        })
+        .map_ok(|bytes| parse_stream_chunk(&bytes));
+
+        let stream = measure_stream(Box::pin(stream), started);
+        #[cfg(feature = "tracing")]
+        return Ok(crate::telemetry::spans::instrument_stream(stream, started));
+        #[cfg(not(feature = "tracing"))]
+        Ok(stream)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    fn provider_name(&self) -> &str {
+        "openai"
+    }
+
+    /// Lists the server's models and checks this one is among them
+    async fn ready(&self) -> ModelResult<()> {
+        let url = format!("{}/models", self.base_url.trim_end_matches('/'));
+        let request = self
+            .authorize(self.client.get(&url).timeout(self.timeouts.total))
+            .await?;
+        let models: serde_json::Value = http::get_json(request, &url, self.debug_wire).await?;
+        let listed = models["data"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|m| m["id"].as_str() == Some(self.model.as_str()));
+        if !listed {
+            return Err(ModelError::ModelNotFound(format!(
+                "model '{}' is not served by {}",
+                self.model, self.base_url
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Builder for OpenAIChat
+pub struct OpenAIChatBuilder {
+    model: String,
+    base_url: String,
+    api_key: ApiKey,
+    temperature: f32,
+    max_tokens: Option<u32>,
+    timeouts: http::Timeouts,
+    max_retries: u32,
+    debug_wire: bool,
+    http: http::ClientOptions,
+}
+
+impl OpenAIChatBuilder {
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            base_url: std::env::var("OPENAI_BASE_URL")
+                .unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+            api_key: ApiKey::env("OPENAI_API_KEY"),
+            temperature: 0.0,
+            max_tokens: None,
+            timeouts: http::Timeouts::default(),
+            max_retries: 2,
+            debug_wire: false,
+            http: http::ClientOptions::default(),
+        }
+    }
+
+    /// Base URL of the API, up to and including the version, e.g.
+    /// `http://localhost:8000/v1`
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key.set_key(api_key.into());
+        self
+    }
+
+    /// Look up `OPENAI_API_KEY` in `credentials` on every request instead of
+    /// the environment; a key set with [`api_key`](Self::api_key) wins
+    pub fn credentials(mut self, credentials: impl CredentialProvider + 'static) -> Self {
+        self.api_key.set_credentials(Arc::new(credentials));
+        self
+    }
+
+    pub fn temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Deadline for a whole non-streaming request (default 60s)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.total = timeout;
+        self
+    }
+
+    /// Longest wait for a response to start, and between the chunks of a
+    /// stream (default 60s)
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.idle = timeout;
+        self
+    }
+
+    /// Deadline for a whole stream; by default streams run as long as chunks
+    /// keep arriving within the [`idle_timeout`](Self::idle_timeout)
+    pub fn stream_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.stream_total = Some(timeout);
+        self
+    }
+
+    /// Retries after rate limits, timeouts, connection failures, and server
+    /// errors (default 2), waiting as long as the provider asks
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Proxy, connection pool, and TLS settings of the HTTP client; see
+    /// [`ClientOptions`](crate::models::ClientOptions)
+    pub fn http_options(
+        mut self,
+        configure: impl FnOnce(http::ClientOptions) -> http::ClientOptions,
+    ) -> Self {
+        self.http = configure(std::mem::take(&mut self.http));
+        self
+    }
+
+    /// Log exact request and raw response bodies to `tracing` at DEBUG under
+    /// the `agentic_optio_rs::wire` target; needs the `tracing` feature
+    pub fn debug_wire(mut self, debug_wire: bool) -> Self {
+        self.debug_wire = debug_wire;
+        self
+    }
+
+    /// Build the model without validating its settings
+    ///
+    /// # Panics
+    ///
+    /// If the HTTP client cannot be built, e.g. for an invalid proxy URL; use
+    /// [`try_build`](Self::try_build) to get an error instead.
+    pub fn build(mut self) -> OpenAIChat {
+        let client = std::mem::take(&mut self.http).build();
+        self.finish(client)
+    }
+
+    /// Build the model, failing with [`ModelError::ConfigError`] for an
+    /// invalid base URL or temperature and [`ModelError::HttpError`] if the
+    /// HTTP client cannot be built
+    pub fn try_build(mut self) -> ModelResult<OpenAIChat> {
+        http::check_url("base URL", &self.base_url)?;
+        http::check_temperature(self.temperature)?;
+        let client = std::mem::take(&mut self.http).try_build()?;
+        Ok(self.finish(client))
+    }
+
+    fn finish(self, client: Client) -> OpenAIChat {
+        OpenAIChat {
+            model: self.model,
+            base_url: self.base_url,
+            api_key: self.api_key,
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            timeouts: self.timeouts,
+            retry: http::Retry::new(self.max_retries),
+            debug_wire: self.debug_wire,
+            client,
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Provider rate-limit headers.
+
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+/// Remaining quota reported by a provider's rate-limit headers
+///
+/// Read from OpenAI-style `x-ratelimit-{limit,remaining,reset}-{requests,tokens}`
+/// headers; the unsuffixed `x-ratelimit-*` and `ratelimit-*` forms used by
+/// other gateways count as request limits. Fields a provider did not send are
+/// `None`. Reset times are relative to when the response arrived.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::OpenAIChat;
+/// use agentic_optio_rs::{BaseChatModel, Message};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let llm = OpenAIChat::new("gpt-4o-mini");
+///     llm.invoke(&[Message::user("Hello!")]).await?;
+///     if let Some(limits) = llm.rate_limits() {
+///         println!("{:?} requests left", limits.remaining_requests);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    pub limit_requests: Option<u64>,
+    pub remaining_requests: Option<u64>,
+    /// Time until the request quota refills
+    pub reset_requests: Option<Duration>,
+    pub limit_tokens: Option<u64>,
+    pub remaining_tokens: Option<u64>,
+    /// Time until the token quota refills
+    pub reset_tokens: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// Read the rate-limit headers, or `None` if there are none
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok().map(str::trim);
+        let count = |names: &[&str]| names.iter().find_map(|n| header(n)?.parse().ok());
+        let reset = |names: &[&str]| names.iter().find_map(|n| parse_reset(header(n)?));
+
+        let info = Self {
+            limit_requests: count(&[
+                "x-ratelimit-limit-requests",
+                "x-ratelimit-limit",
+                "ratelimit-limit",
+            ]),
+            remaining_requests: count(&[
+                "x-ratelimit-remaining-requests",
+                "x-ratelimit-remaining",
+                "ratelimit-remaining",
+            ]),
+            reset_requests: reset(&[
+                "x-ratelimit-reset-requests",
+                "x-ratelimit-reset",
+                "ratelimit-reset",
+            ]),
+            limit_tokens: count(&["x-ratelimit-limit-tokens"]),
+            remaining_tokens: count(&["x-ratelimit-remaining-tokens"]),
+            reset_tokens: reset(&["x-ratelimit-reset-tokens"]),
+        };
+        (info != Self::default()).then_some(info)
+    }
+
+    /// Whether the request or token quota is used up
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining_requests == Some(0) || self.remaining_tokens == Some(0)
+    }
+
+    /// Time until every used-up quota refills, if one is used up and its
+    /// reset time is known
+    pub fn wait(&self) -> Option<Duration> {
+        let requests = self
+            .reset_requests
+            .filter(|_| self.remaining_requests == Some(0));
+        let tokens = self
+            .reset_tokens
+            .filter(|_| self.remaining_tokens == Some(0));
+        requests.max(tokens)
+    }
+}
+
+/// Parse a reset time: a Go-style duration such as `6m0s` or `20ms`, seconds,
+/// or a Unix timestamp in seconds
+fn parse_reset(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.parse::<f64>() {
+        if seconds < 0.0 || !seconds.is_finite() {
+            return None;
+        }
+        // Large values are absolute times rather than delays
+        if seconds > 1_000_000_000.0 {
+            let now = chrono::Utc::now().timestamp() as f64;
+            return Some(Duration::from_secs_f64((seconds - now).max(0.0)));
+        }
+        return Some(Duration::from_secs_f64(seconds));
+    }
+
+    if value.is_empty() {
+        return None;
+    }
+    let mut total = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .filter(|&i| i > 0)?;
+        let amount: f64 = rest[..split].parse().ok()?;
+        rest = &rest[split..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let scale = match &rest[..unit_len] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            "ms" => 0.001,
+            "us" | "µs" => 0.000_001,
+            "ns" => 0.000_000_001,
+            _ => return None,
+        };
+        total += amount * scale;
+        rest = &rest[unit_len..];
+    }
+    Some(Duration::from_secs_f64(total))
+}
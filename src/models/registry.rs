@@ -0,0 +1,186 @@
+//! Named model registry.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BaseEmbedding, BoxStream, ModelError, ModelResult};
+use crate::models::handle::ChatModel;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Chat and embedding models registered under logical names such as `"fast"`,
+/// `"smart"`, or `"embedder"`
+///
+/// Code asks for a role instead of a backend, and a deployment decides which
+/// backend fills it, swapping it at runtime with another `register` call.
+/// [`global`](Self::global) is a process-wide registry; [`scope`](Self::scope)
+/// creates a child that overrides some names and falls back to its parent for
+/// the rest, e.g. per tenant or per test.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::ModelRegistry;
+/// use agentic_optio_rs::{Agent, OllamaChat};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let registry = ModelRegistry::global();
+/// registry.register("fast", OllamaChat::new("llama3.2"));
+/// registry.register("smart", OllamaChat::new("qwen2.5:32b"));
+///
+/// // Resolved on every call, so re-registering "smart" switches the agent
+/// let agent = Agent::builder(registry.named("smart").into()).build();
+/// agent.run("Plan a migration").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ModelRegistry {
+    chat: RwLock<HashMap<String, ChatModel>>,
+    embeddings: RwLock<HashMap<String, Arc<dyn BaseEmbedding>>>,
+    parent: Option<Arc<ModelRegistry>>,
+}
+
+impl ModelRegistry {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// The process-wide registry
+    pub fn global() -> Arc<Self> {
+        static GLOBAL: OnceLock<Arc<ModelRegistry>> = OnceLock::new();
+        GLOBAL.get_or_init(ModelRegistry::new).clone()
+    }
+
+    /// Child registry whose own registrations shadow this one's
+    pub fn scope(self: &Arc<Self>) -> Arc<Self> {
+        Arc::new(Self {
+            parent: Some(self.clone()),
+            ..Self::default()
+        })
+    }
+
+    /// Register a chat model under `name`, replacing any previous one
+    pub fn register(&self, name: impl Into<String>, model: impl BaseChatModel + 'static) {
+        self.register_chat(name, ChatModel::new(model));
+    }
+
+    /// Register a shared chat model under `name`, replacing any previous one
+    pub fn register_chat(&self, name: impl Into<String>, model: impl Into<ChatModel>) {
+        self.chat.write().unwrap().insert(name.into(), model.into());
+    }
+
+    /// Register an embedding model under `name`, replacing any previous one
+    pub fn register_embedding(&self, name: impl Into<String>, model: impl BaseEmbedding + 'static) {
+        self.embeddings
+            .write()
+            .unwrap()
+            .insert(name.into(), Arc::new(model));
+    }
+
+    /// The chat model currently registered under `name` here or in a parent
+    pub fn chat(&self, name: &str) -> Option<ChatModel> {
+        let own = self.chat.read().unwrap().get(name).cloned();
+        own.or_else(|| self.parent.as_ref()?.chat(name))
+    }
+
+    /// The embedding model currently registered under `name` here or in a
+    /// parent
+    pub fn embedding(&self, name: &str) -> Option<Arc<dyn BaseEmbedding>> {
+        let own = self.embeddings.read().unwrap().get(name).cloned();
+        own.or_else(|| self.parent.as_ref()?.embedding(name))
+    }
+
+    /// Like [`chat`](Self::chat), failing for an unregistered name
+    pub fn require_chat(&self, name: &str) -> ModelResult<ChatModel> {
+        self.chat(name)
+            .ok_or_else(|| ModelError::ConfigError(format!("no model registered as '{}'", name)))
+    }
+
+    /// Chat model that looks `name` up on every call, so it follows later
+    /// registrations; it reports `name` as its model name
+    pub fn named(self: &Arc<Self>, name: impl Into<String>) -> ChatModel {
+        ChatModel::new(NamedModel {
+            registry: self.clone(),
+            name: name.into(),
+        })
+    }
+
+    /// Registered chat model names, including inherited ones, sorted
+    pub fn chat_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.chat.read().unwrap().keys().cloned().collect();
+        if let Some(parent) = &self.parent {
+            names.extend(parent.chat_names());
+        }
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Registered embedding model names, including inherited ones, sorted
+    pub fn embedding_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.embeddings.read().unwrap().keys().cloned().collect();
+        if let Some(parent) = &self.parent {
+            names.extend(parent.embedding_names());
+        }
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+}
+
+impl std::fmt::Debug for ModelRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModelRegistry")
+            .field("chat", &self.chat_names())
+            .field("embeddings", &self.embedding_names())
+            .finish()
+    }
+}
+
+/// Chat model resolved from a registry by name on every call
+struct NamedModel {
+    registry: Arc<ModelRegistry>,
+    name: String,
+}
+
+#[async_trait]
+impl BaseChatModel for NamedModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        self.registry
+            .require_chat(&self.name)?
+            .invoke(messages)
+            .await
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        self.registry
+            .require_chat(&self.name)?
+            .invoke_with_tools(messages, tools)
+            .await
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let model = self.registry.require_chat(&self.name)?;
+        Ok(model.stream(messages.to_vec()))
+    }
+
+    fn model_name(&self) -> &str {
+        &self.name
+    }
+
+    fn provider_name(&self) -> &str {
+        "registry"
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.registry.require_chat(&self.name)?.ready().await
+    }
+}
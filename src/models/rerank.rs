@@ -0,0 +1,273 @@
+//! Reranker implementations.
+//!
+//! [`LlmReranker`] scores each document with a chat model; [`HttpReranker`]
+//! calls a hosted `/rerank` endpoint such as Cohere, Jina, Voyage, or a local
+//! text-embeddings-inference server.
+
+use crate::core::documents::Document;
+use crate::core::messages::Message;
+use crate::models::base::{BaseChatModel, BaseReranker, ModelError, ModelResult};
+use crate::models::http;
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use regex::Regex;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Pointwise reranker that asks a chat model to rate each document
+///
+/// The model rates relevance from 0 to 10; scores are scaled to `0.0..=1.0`.
+/// Replies without a number score 0.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::{BaseReranker, LlmReranker};
+/// use agentic_optio_rs::{Document, OllamaChat};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let reranker = LlmReranker::new(Arc::new(OllamaChat::new("llama3.2")));
+///     let docs = vec![Document::new("Paris is in France."), Document::new("Bread recipe")];
+///     for (doc, score) in reranker.rerank("capital of France", docs).await? {
+///         println!("{:.2} {}", score, doc.content);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct LlmReranker {
+    model: Arc<dyn BaseChatModel>,
+    concurrency: usize,
+}
+
+impl LlmReranker {
+    pub fn new(model: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            model,
+            concurrency: 4,
+        }
+    }
+
+    /// Maximum documents scored at once (default 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    async fn score(&self, query: &str, document: &Document) -> ModelResult<f32> {
+        let messages = [
+            Message::system(
+                "Rate how relevant the document is to the query on a scale from 0 \
+                 (unrelated) to 10 (directly answers it). Reply with only the number.",
+            ),
+            Message::user(format!(
+                "Query: {}\n\nDocument:\n{}",
+                query, document.content
+            )),
+        ];
+        let reply = self.model.invoke(&messages).await?.content;
+
+        static NUMBER: OnceLock<Regex> = OnceLock::new();
+        let number = NUMBER.get_or_init(|| Regex::new(r"\d+(?:\.\d+)?").unwrap());
+        Ok(number
+            .find(&reply)
+            .and_then(|m| m.as_str().parse::<f32>().ok())
+            .map_or(0.0, |rating| (rating / 10.0).clamp(0.0, 1.0)))
+    }
+}
+
+#[async_trait]
+impl BaseReranker for LlmReranker {
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<Document>,
+    ) -> ModelResult<Vec<(Document, f32)>> {
+        let pending: Vec<_> = documents.iter().map(|doc| self.score(query, doc)).collect();
+        let scores: Vec<f32> = stream::iter(pending)
+            .buffered(self.concurrency)
+            .try_collect()
+            .await?;
+
+        let mut ranked: Vec<_> = documents.into_iter().zip(scores).collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(ranked)
+    }
+}
+
+#[derive(Serialize)]
+struct RerankRequest<'a> {
+    model: &'a str,
+    query: &'a str,
+    documents: Vec<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct RerankResponse {
+    results: Vec<RerankResult>,
+}
+
+#[derive(Deserialize)]
+struct RerankResult {
+    index: usize,
+    relevance_score: f32,
+}
+
+/// Reranker backed by a hosted `/rerank` API
+///
+/// Sends `{model, query, documents}` and expects
+/// `{results: [{index, relevance_score}]}`, the shape shared by Cohere, Jina,
+/// Voyage, and text-embeddings-inference.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::HttpReranker;
+///
+/// let reranker = HttpReranker::builder("https://api.cohere.com/v2/rerank", "rerank-v3.5")
+///     .api_key(std::env::var("COHERE_API_KEY").unwrap_or_default())
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct HttpReranker {
+    url: String,
+    model: String,
+    api_key: Option<String>,
+    timeout: Duration,
+    debug_wire: bool,
+    client: Client,
+}
+
+impl std::fmt::Debug for HttpReranker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpReranker")
+            .field("url", &self.url)
+            .field("model", &self.model)
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl HttpReranker {
+    pub fn new(url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self::builder(url, model).build()
+    }
+
+    pub fn builder(url: impl Into<String>, model: impl Into<String>) -> HttpRerankerBuilder {
+        HttpRerankerBuilder::new(url, model)
+    }
+}
+
+#[async_trait]
+impl BaseReranker for HttpReranker {
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<Document>,
+    ) -> ModelResult<Vec<(Document, f32)>> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let request = RerankRequest {
+            model: &self.model,
+            query,
+            documents: documents.iter().map(|d| d.content.as_str()).collect(),
+        };
+        let mut builder = self.client.post(&self.url).timeout(self.timeout);
+        if let Some(key) = &self.api_key {
+            builder = builder.bearer_auth(key);
+        }
+        let response: RerankResponse = http::send_json(
+            builder,
+            &self.url,
+            &request,
+            self.debug_wire,
+            &http::Retry::default(),
+        )
+        .await?;
+
+        let mut slots: Vec<Option<Document>> = documents.into_iter().map(Some).collect();
+        let mut ranked = Vec::with_capacity(response.results.len());
+        for result in response.results {
+            let document = slots
+                .get_mut(result.index)
+                .and_then(Option::take)
+                .ok_or_else(|| {
+                    ModelError::InvalidResponse(format!(
+                        "rerank result index {} out of range",
+                        result.index
+                    ))
+                })?;
+            ranked.push((document, result.relevance_score));
+        }
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(ranked)
+    }
+}
+
+/// Builder for HttpReranker
+pub struct HttpRerankerBuilder {
+    url: String,
+    model: String,
+    api_key: Option<String>,
+    timeout: Duration,
+    debug_wire: bool,
+    http: http::ClientOptions,
+}
+
+impl HttpRerankerBuilder {
+    pub fn new(url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            model: model.into(),
+            api_key: None,
+            timeout: Duration::from_secs(60),
+            debug_wire: false,
+            http: http::ClientOptions::default(),
+        }
+    }
+
+    /// Sent as a bearer token
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Proxy, connection pool, and TLS settings of the HTTP client; see
+    /// [`ClientOptions`](crate::models::ClientOptions)
+    pub fn http_options(
+        mut self,
+        configure: impl FnOnce(http::ClientOptions) -> http::ClientOptions,
+    ) -> Self {
+        self.http = configure(std::mem::take(&mut self.http));
+        self
+    }
+
+    /// Log exact request and raw response bodies to `tracing` at DEBUG under
+    /// the `agentic_optio_rs::wire` target; needs the `tracing` feature
+    pub fn debug_wire(mut self, debug_wire: bool) -> Self {
+        self.debug_wire = debug_wire;
+        self
+    }
+
+    pub fn build(self) -> HttpReranker {
+        HttpReranker {
+            url: self.url,
+            model: self.model,
+            api_key: self.api_key,
+            debug_wire: self.debug_wire,
+            timeout: self.timeout,
+            client: self.http.build(),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Recovery of interrupted response streams.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BoxStream, ModelError, ModelResult};
+use crate::models::handle::ChatModel;
+use crate::models::http::MAX_RETRY_DELAY;
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_CONTINUE_PROMPT: &str =
+    "Your previous response was cut off. Continue it exactly where it stopped, without repeating anything.";
+
+/// Chat model wrapper that keeps the content of a stream that drops
+/// mid-generation
+///
+/// When the wrapped model's stream fails on a transient error (a dropped
+/// connection, a timeout, a malformed chunk, a server error, or a rate limit,
+/// after waiting as long as the provider asked), the request is sent again with
+/// the content streamed so far as a prefilled assistant message, followed by
+/// a prompt to continue it, and the new stream's chunks carry on where the
+/// old ones stopped. Once [`max_resumes`](Self::max_resumes) is used up, or
+/// on an error that retrying cannot fix, the stream ends with
+/// [`ModelError::StreamInterrupted`] holding the partial content.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::base::ModelError;
+/// use agentic_optio_rs::models::ResumingChatModel;
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+/// use futures::StreamExt;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let llm = ResumingChatModel::new(Arc::new(OllamaChat::new("llama3.2"))).max_resumes(2);
+///     let messages = [Message::user("Write a long story")];
+///     let mut stream = llm.stream(&messages).await?;
+///     while let Some(chunk) = stream.next().await {
+///         match chunk {
+///             Ok(chunk) => print!("{}", chunk.content),
+///             Err(ModelError::StreamInterrupted { partial, source }) => {
+///                 eprintln!("\n[stopped after {} bytes: {source}]", partial.len());
+///             }
+///             Err(e) => return Err(e.into()),
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ResumingChatModel {
+    inner: Arc<dyn BaseChatModel>,
+    max_resumes: u32,
+    continue_prompt: Option<String>,
+}
+
+impl ResumingChatModel {
+    pub fn new(inner: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            inner,
+            max_resumes: 1,
+            continue_prompt: Some(DEFAULT_CONTINUE_PROMPT.to_string()),
+        }
+    }
+
+    /// Times one stream may be resumed (default 1); with 0 an interrupted
+    /// stream only reports its partial content
+    pub fn max_resumes(mut self, max_resumes: u32) -> Self {
+        self.max_resumes = max_resumes;
+        self
+    }
+
+    /// User message sent after the prefilled partial response
+    pub fn continue_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.continue_prompt = Some(prompt.into());
+        self
+    }
+
+    /// End resumed requests with the prefilled partial response, for servers
+    /// that continue a trailing assistant message, like Ollama
+    pub fn prefill_only(mut self) -> Self {
+        self.continue_prompt = None;
+        self
+    }
+
+    fn continuation(&self, messages: &[Message], partial: &str) -> Vec<Message> {
+        let mut messages = messages.to_vec();
+        if !partial.is_empty() {
+            messages.push(Message::assistant(partial));
+            if let Some(prompt) = &self.continue_prompt {
+                messages.push(Message::user(prompt.as_str()));
+            }
+        }
+        messages
+    }
+}
+
+/// How long to wait before sending the request again after `error`, the
+/// `resume`th time (from 0), or `None` if that cannot get past it
+///
+/// Rate limits wait as long as the provider asked, or back off exponentially
+/// from 0.5s; other transient errors are resumed at once.
+fn resume_delay(error: &ModelError, resume: u32) -> Option<Duration> {
+    match error {
+        ModelError::RateLimited {
+            retry_after: Some(delay),
+            ..
+        } => (*delay <= MAX_RETRY_DELAY).then_some(*delay),
+        ModelError::RateLimited { .. } => {
+            Some(Duration::from_millis(500) * 2u32.pow(resume.min(6)))
+        }
+        ModelError::HttpError(_)
+        | ModelError::JsonError(_)
+        | ModelError::Server { .. }
+        | ModelError::Timeout(_) => Some(Duration::ZERO),
+        _ => None,
+    }
+}
+
+struct Resumption<'a> {
+    current: BoxStream<'a, ModelResult<AIMessage>>,
+    partial: String,
+    resumes: u32,
+    done: bool,
+}
+
+#[async_trait]
+impl BaseChatModel for ResumingChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        self.inner.invoke(messages).await
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        self.inner.invoke_with_tools(messages, tools).await
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let state = Resumption {
+            current: self.inner.stream(messages).await?,
+            partial: String::new(),
+            resumes: 0,
+            done: false,
+        };
+        let stream = futures::stream::unfold(state, move |mut state| async move {
+            if state.done {
+                return None;
+            }
+            loop {
+                let error = match state.current.next().await? {
+                    Ok(chunk) => {
+                        state.partial.push_str(&chunk.content);
+                        return Some((Ok(chunk), state));
+                    }
+                    Err(error) => error,
+                };
+                let delay = (state.resumes < self.max_resumes)
+                    .then(|| resume_delay(&error, state.resumes))
+                    .flatten();
+                if let Some(delay) = delay {
+                    state.resumes += 1;
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        error = %error,
+                        resume = state.resumes,
+                        partial_len = state.partial.len(),
+                        delay_ms = delay.as_millis() as u64,
+                        "resuming interrupted stream"
+                    );
+                    tokio::time::sleep(delay).await;
+                    // The handle owns the continuation messages for the life
+                    // of its stream
+                    state.current = ChatModel::from(self.inner.clone())
+                        .stream(self.continuation(messages, &state.partial));
+                    continue;
+                }
+                state.done = true;
+                let error = if state.partial.is_empty() {
+                    error
+                } else {
+                    ModelError::StreamInterrupted {
+                        partial: state.partial.clone(),
+                        source: Box::new(error),
+                    }
+                };
+                return Some((Err(error), state));
+            }
+        });
+        Ok(Box::pin(stream))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
@@ -0,0 +1,254 @@
+//! Per-request model routing.
+
+use crate::chains::ClassificationChain;
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BoxStream, ModelResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+type RuleFn = dyn Fn(&[Message]) -> bool + Send + Sync;
+
+/// Most characters of the request shown to the routing classifier
+const CLASSIFIER_INPUT_CHARS: usize = 4000;
+
+#[derive(Clone)]
+struct Route {
+    name: String,
+    description: String,
+    model: Arc<dyn BaseChatModel>,
+}
+
+/// Chat model that sends each request to one of several models, picked by
+/// rules or by a cheap classifier model
+///
+/// Rules are checked in the order they were added and the first match wins.
+/// When none matches, the classifier, if set, picks a route from the route
+/// descriptions; otherwise, or if the classifier fails, the request goes to
+/// the default model.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::Router;
+/// use agentic_optio_rs::{Agent, OllamaChat};
+/// use std::sync::Arc;
+///
+/// let router = Router::new(Arc::new(OllamaChat::new("llama3.2")))
+///     .route(
+///         "code",
+///         "Writing, reviewing, or debugging code",
+///         Arc::new(OllamaChat::new("qwen2.5-coder")),
+///     )
+///     .route(
+///         "long",
+///         "Very long documents",
+///         Arc::new(OllamaChat::new("llama3.1:128k")),
+///     )
+///     .long_context("long", 8000)
+///     .rule("code", |messages| {
+///         messages.iter().any(|m| m.content().contains("```"))
+///     })
+///     .classifier(Arc::new(OllamaChat::new("llama3.2:1b")));
+/// let agent = Agent::builder(Arc::new(router)).build();
+/// ```
+#[derive(Clone)]
+pub struct Router {
+    /// The default route first
+    routes: Vec<Route>,
+    rules: Vec<(Arc<RuleFn>, usize)>,
+    classifier: Option<Arc<dyn BaseChatModel>>,
+}
+
+impl Router {
+    /// Router sending everything to `default` until routes are added
+    pub fn new(default: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            routes: vec![Route {
+                name: "default".to_string(),
+                description: "Anything else".to_string(),
+                model: default,
+            }],
+            rules: Vec::new(),
+            classifier: None,
+        }
+    }
+
+    /// Add a model under `name`, described to the classifier by
+    /// `description`, replacing any route of the same name
+    pub fn route(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        model: Arc<dyn BaseChatModel>,
+    ) -> Self {
+        let route = Route {
+            name: name.into(),
+            description: description.into(),
+            model,
+        };
+        match self.routes.iter_mut().find(|r| r.name == route.name) {
+            Some(existing) => *existing = route,
+            None => self.routes.push(route),
+        }
+        self
+    }
+
+    /// Send requests matching `predicate` to the route `name`
+    ///
+    /// # Panics
+    ///
+    /// If no route is named `name`.
+    pub fn rule(
+        mut self,
+        name: &str,
+        predicate: impl Fn(&[Message]) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        let index = self.index(name);
+        self.rules.push((Arc::new(predicate), index));
+        self
+    }
+
+    /// Send requests of at least `tokens` tokens, estimated at four
+    /// characters each, to the route `name`
+    ///
+    /// # Panics
+    ///
+    /// If no route is named `name`.
+    pub fn long_context(self, name: &str, tokens: usize) -> Self {
+        self.rule(name, move |messages| estimate_tokens(messages) >= tokens)
+    }
+
+    /// Let `model`, ideally a small fast one, pick a route when no rule
+    /// matches
+    pub fn classifier(mut self, model: Arc<dyn BaseChatModel>) -> Self {
+        self.classifier = Some(model);
+        self
+    }
+
+    /// Names of the routes, the default first
+    pub fn routes(&self) -> Vec<&str> {
+        self.routes.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    /// Name of the route `messages` would take
+    pub async fn select(&self, messages: &[Message]) -> &str {
+        &self.routes[self.choose(messages).await].name
+    }
+
+    async fn choose(&self, messages: &[Message]) -> usize {
+        if let Some((_, index)) = self.rules.iter().find(|(rule, _)| rule(messages)) {
+            return *index;
+        }
+        let Some(model) = &self.classifier else {
+            return 0;
+        };
+        let mut classifier = ClassificationChain::new(model.clone()).instructions(
+            "Pick the model best suited to handle the request the user sends. \
+             Judge only what kind of request it is; do not answer it.",
+        );
+        for (i, route) in self.routes.iter().enumerate() {
+            classifier = classifier.label(route.name.clone(), route.description.clone(), i);
+        }
+        match classifier.classify(&classifier_input(messages)).await {
+            Ok(choice) => choice.value,
+            Err(_e) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %_e, "routing classifier failed; using the default model");
+                0
+            }
+        }
+    }
+
+    async fn model(&self, messages: &[Message]) -> &Arc<dyn BaseChatModel> {
+        let route = &self.routes[self.choose(messages).await];
+        #[cfg(feature = "tracing")]
+        tracing::debug!(route = %route.name, model = route.model.model_name(), "routed request");
+        &route.model
+    }
+
+    fn index(&self, name: &str) -> usize {
+        self.routes
+            .iter()
+            .position(|r| r.name == name)
+            .unwrap_or_else(|| panic!("no route named '{name}'; add it with Router::route first"))
+    }
+}
+
+/// Estimated tokens in `messages`, at four characters per token
+fn estimate_tokens(messages: &[Message]) -> usize {
+    messages
+        .iter()
+        .map(|m| (m.content().chars().count() + 3) / 4)
+        .sum()
+}
+
+/// The latest user message, cut to a length cheap to classify
+fn classifier_input(messages: &[Message]) -> String {
+    let latest = messages
+        .iter()
+        .rev()
+        .find(|m| matches!(m, Message::Human(_)))
+        .or(messages.last())
+        .map(|m| m.content())
+        .unwrap_or_default();
+    latest.chars().take(CLASSIFIER_INPUT_CHARS).collect()
+}
+
+impl std::fmt::Debug for Router {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Router")
+            .field(
+                "routes",
+                &self
+                    .routes
+                    .iter()
+                    .map(|r| (r.name.as_str(), r.model.model_name()))
+                    .collect::<Vec<_>>(),
+            )
+            .field("rules", &self.rules.len())
+            .field("classifier", &self.classifier.is_some())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for Router {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        self.model(messages).await.invoke(messages).await
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        self.model(messages)
+            .await
+            .invoke_with_tools(messages, tools)
+            .await
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        self.model(messages).await.stream(messages).await
+    }
+
+    /// The default model's name
+    fn model_name(&self) -> &str {
+        self.routes[0].model.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.routes[0].model.provider_name()
+    }
+
+    /// Ready once every route's model is
+    async fn ready(&self) -> ModelResult<()> {
+        for route in &self.routes {
+            route.model.ready().await?;
+        }
+        Ok(())
+    }
+}
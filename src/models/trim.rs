@@ -0,0 +1,242 @@
+//! Recovery from context-length errors.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::{BaseChatModel, BoxStream, ModelError, ModelResult};
+use crate::models::handle::ChatModel;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use futures::StreamExt;
+use std::sync::Arc;
+
+/// How [`TrimmingChatModel`] shortens a conversation that no longer fits
+#[derive(Clone)]
+pub enum TrimStrategy {
+    /// Drop the oldest half of the turns
+    DropOldest,
+    /// Replace the oldest half of the turns with a summary written by the
+    /// given model, dropping them if it fails
+    Summarize(Arc<dyn BaseChatModel>),
+}
+
+impl std::fmt::Debug for TrimStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrimStrategy::DropOldest => f.write_str("DropOldest"),
+            TrimStrategy::Summarize(model) => f
+                .debug_tuple("Summarize")
+                .field(&model.model_name())
+                .finish(),
+        }
+    }
+}
+
+/// Chat model wrapper that shortens the conversation and tries again when the
+/// model rejects it with [`ModelError::ContextLengthExceeded`]
+///
+/// A turn is a user message with everything after it up to the next one, so
+/// tool calls stay with their results. Leading system messages and the latest
+/// turn are always kept; once nothing else is left the error is returned.
+/// Only the request is shortened: callers keep their full history.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::{TrimStrategy, TrimmingChatModel};
+/// use agentic_optio_rs::{Agent, OllamaChat};
+/// use std::sync::Arc;
+///
+/// let llm = Arc::new(OllamaChat::new("llama3.2"));
+/// let model = TrimmingChatModel::new(llm.clone()).strategy(TrimStrategy::Summarize(llm));
+/// let agent = Agent::builder(Arc::new(model)).build();
+/// ```
+#[derive(Clone)]
+pub struct TrimmingChatModel {
+    inner: Arc<dyn BaseChatModel>,
+    strategy: TrimStrategy,
+    retries: u32,
+}
+
+impl TrimmingChatModel {
+    /// Wrapper dropping the oldest turns and retrying once
+    pub fn new(inner: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            inner,
+            strategy: TrimStrategy::DropOldest,
+            retries: 1,
+        }
+    }
+
+    pub fn strategy(mut self, strategy: TrimStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Times one request is shortened and sent again (default 1)
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// `messages` without the oldest half of the turns, or summarized, or
+    /// `None` if there is nothing left to drop
+    pub async fn trim(&self, messages: &[Message]) -> Option<Vec<Message>> {
+        let system = messages
+            .iter()
+            .take_while(|m| matches!(m, Message::System(_)))
+            .count();
+        let turns: Vec<usize> = (system..messages.len())
+            .filter(|&i| i == system || matches!(messages[i], Message::Human(_)))
+            .collect();
+        if turns.len() < 2 {
+            return None;
+        }
+        let cut = turns[turns.len() / 2];
+        let dropped = &messages[system..cut];
+
+        let mut trimmed = messages[..system].to_vec();
+        if let TrimStrategy::Summarize(summarizer) = &self.strategy {
+            if let Some(summary) = summarize(summarizer.as_ref(), dropped).await {
+                trimmed.push(Message::system(format!(
+                    "Summary of the earlier conversation:\n{summary}"
+                )));
+            }
+        }
+        trimmed.extend_from_slice(&messages[cut..]);
+        Some(trimmed)
+    }
+
+    /// Shorten `messages`, which failed with `error`, and run `call` on them
+    /// again while retries last
+    async fn recover<'a, T>(
+        &'a self,
+        messages: &[Message],
+        mut error: ModelError,
+        call: impl Fn(Vec<Message>) -> BoxFuture<'a, ModelResult<T>>,
+    ) -> ModelResult<T> {
+        let mut current = messages.to_vec();
+        for _attempt in 1..=self.retries {
+            current = match self.trim(&current).await {
+                Some(trimmed) => trimmed,
+                None => break,
+            };
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                attempt = _attempt,
+                messages = current.len(),
+                "retrying with a shortened conversation after a context-length error"
+            );
+            error = match call(current.clone()).await {
+                Err(e @ ModelError::ContextLengthExceeded(_)) => e,
+                result => return result,
+            };
+        }
+        Err(error)
+    }
+}
+
+/// `stream` with its first chunk awaited, so a failure to start surfaces as
+/// an error
+async fn started(
+    mut stream: BoxStream<'static, ModelResult<AIMessage>>,
+) -> ModelResult<BoxStream<'static, ModelResult<AIMessage>>> {
+    let first = match stream.next().await {
+        Some(first) => first?,
+        None => return Ok(stream),
+    };
+    Ok(Box::pin(
+        futures::stream::once(async { Ok(first) }).chain(stream),
+    ))
+}
+
+async fn summarize(summarizer: &dyn BaseChatModel, dropped: &[Message]) -> Option<String> {
+    let transcript = dropped
+        .iter()
+        .filter(|m| !m.content().is_empty())
+        .map(|m| format!("{}: {}", m.role(), m.content()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let prompt = [
+        Message::system(
+            "Summarize this conversation in a few sentences. Keep facts, decisions, \
+             names, and numbers a later reply may need.",
+        ),
+        Message::user(transcript),
+    ];
+    let summary = summarizer.invoke(&prompt).await.ok()?.content;
+    let summary = summary.trim();
+    (!summary.is_empty()).then(|| summary.to_string())
+}
+
+impl std::fmt::Debug for TrimmingChatModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrimmingChatModel")
+            .field("model", &self.inner.model_name())
+            .field("strategy", &self.strategy)
+            .field("retries", &self.retries)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for TrimmingChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        match self.inner.invoke(messages).await {
+            Err(e @ ModelError::ContextLengthExceeded(_)) => {
+                self.recover(messages, e, |messages| {
+                    Box::pin(async move { self.inner.invoke(&messages).await })
+                })
+                .await
+            }
+            result => result,
+        }
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        match self.inner.invoke_with_tools(messages, tools).await {
+            Err(e @ ModelError::ContextLengthExceeded(_)) => {
+                self.recover(messages, e, |messages| {
+                    Box::pin(async move { self.inner.invoke_with_tools(&messages, tools).await })
+                })
+                .await
+            }
+            result => result,
+        }
+    }
+
+    /// Only a stream that fails to start is retried
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        match self.inner.stream(messages).await {
+            Err(e @ ModelError::ContextLengthExceeded(_)) => {
+                // The handle owns the shortened messages for the life of its
+                // stream
+                let handle = ChatModel::from(self.inner.clone());
+                let stream = self
+                    .recover(messages, e, |messages| {
+                        Box::pin(started(handle.stream(messages)))
+                    })
+                    .await?;
+                Ok(stream)
+            }
+            result => result,
+        }
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
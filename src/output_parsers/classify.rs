@@ -0,0 +1,190 @@
+//! Classification into a fixed set of labels.
+
+use super::{OutputParser, ParseError, ParseResult, ParsedChatModel};
+use crate::core::messages::Message;
+use crate::models::base::BaseChatModel;
+use std::sync::Arc;
+
+/// Parses an answer that must be one of a fixed set of labels into the value
+/// paired with it
+///
+/// Matching ignores case, surrounding whitespace, quotes, markdown emphasis,
+/// and a trailing period. An answer that is not a label is still accepted if
+/// exactly one label appears in it as a whole word, as in
+/// `The sentiment is positive.`
+#[derive(Debug, Clone)]
+pub struct LabelParser<T> {
+    labels: Vec<(String, T)>,
+    /// Accepted but not offered to the model
+    aliases: Vec<(String, T)>,
+}
+
+impl<T> LabelParser<T> {
+    /// Parser for the given `(label, value)` pairs
+    pub fn new<I, S>(labels: I) -> Self
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: Into<String>,
+    {
+        Self {
+            labels: labels
+                .into_iter()
+                .map(|(label, value)| (label.into(), value))
+                .collect(),
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Also accept `alias` for `value`, without offering it to the model
+    pub fn alias(mut self, alias: impl Into<String>, value: T) -> Self {
+        self.aliases.push((alias.into(), value));
+        self
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.labels.iter().map(|(label, _)| label.as_str())
+    }
+
+    fn accepted(&self) -> impl Iterator<Item = &(String, T)> {
+        self.labels.iter().chain(&self.aliases)
+    }
+}
+
+impl LabelParser<bool> {
+    /// Parser for `yes` or `no`, also accepting `true` and `false`
+    pub fn yes_no() -> Self {
+        Self::new([("yes", true), ("no", false)])
+            .alias("true", true)
+            .alias("false", false)
+    }
+}
+
+impl<T: Clone + Send + Sync> OutputParser<T> for LabelParser<T> {
+    fn parse(&self, text: &str) -> ParseResult<T> {
+        let answer = text
+            .trim()
+            .trim_matches(|c: char| matches!(c, '"' | '\'' | '`' | '*' | '_' | '.'))
+            .trim();
+        if let Some((_, value)) = self
+            .accepted()
+            .find(|(label, _)| label.eq_ignore_ascii_case(answer))
+        {
+            return Ok(value.clone());
+        }
+
+        let words: Vec<String> = text
+            .split(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-')))
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        let mut found = self.labels.iter().filter(|(label, _)| {
+            let label: Vec<String> = label.split_whitespace().map(str::to_lowercase).collect();
+            !label.is_empty() && words.windows(label.len()).any(|w| w == label.as_slice())
+        });
+        match (found.next(), found.next()) {
+            (Some((_, value)), None) => Ok(value.clone()),
+            _ => Err(ParseError::invalid(
+                format!(
+                    "expected one of: {}",
+                    self.labels().collect::<Vec<_>>().join(", ")
+                ),
+                text,
+            )),
+        }
+    }
+
+    fn format_instructions(&self) -> Option<String> {
+        Some(format!(
+            "Answer with exactly one of: {}. Do not add anything else.",
+            self.labels().collect::<Vec<_>>().join(", ")
+        ))
+    }
+}
+
+/// Classifies text into one of a fixed set of labels with a chat model,
+/// asking again with corrective feedback when the answer is not one of them
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::output_parsers::Classifier;
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Sentiment {
+///     Positive,
+///     Negative,
+///     Neutral,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let classifier = Classifier::new(
+///         Arc::new(OllamaChat::new("llama3.2")),
+///         [
+///             ("positive", Sentiment::Positive),
+///             ("negative", Sentiment::Negative),
+///             ("neutral", Sentiment::Neutral),
+///         ],
+///     )
+///     .instructions("Classify the sentiment of the product review.");
+///     let sentiment = classifier.classify("Broke after a day.").await?;
+///     assert_eq!(sentiment, Sentiment::Negative);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Classifier<T> {
+    model: ParsedChatModel<T>,
+    instructions: Option<String>,
+}
+
+impl<T: Clone + Send + Sync + 'static> Classifier<T> {
+    /// Classifier choosing among the given `(label, value)` pairs, retrying
+    /// twice by default
+    pub fn new<I, S>(model: Arc<dyn BaseChatModel>, labels: I) -> Self
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: Into<String>,
+    {
+        Self::with_parser(model, LabelParser::new(labels))
+    }
+
+    pub fn with_parser(model: Arc<dyn BaseChatModel>, parser: LabelParser<T>) -> Self {
+        Self {
+            model: ParsedChatModel::new(model, parser).max_retries(2),
+            instructions: None,
+        }
+    }
+}
+
+impl Classifier<bool> {
+    /// Classifier answering a yes-or-no question about the text
+    pub fn yes_no(model: Arc<dyn BaseChatModel>, question: impl Into<String>) -> Self {
+        Self::with_parser(model, LabelParser::yes_no()).instructions(question)
+    }
+}
+
+impl<T> Classifier<T> {
+    /// What to classify the text by, like `Is this email spam?`
+    pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Times to ask again after an answer that is not a label (default 2)
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.model = self.model.max_retries(max_retries);
+        self
+    }
+
+    pub async fn classify(&self, text: &str) -> ParseResult<T> {
+        let mut messages = Vec::new();
+        if let Some(instructions) = &self.instructions {
+            messages.push(Message::system(instructions.as_str()));
+        }
+        messages.push(Message::user(text));
+        self.model.invoke_parsed(&messages).await
+    }
+}
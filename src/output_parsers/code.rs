@@ -0,0 +1,145 @@
+//! Markdown code block extraction.
+
+use super::{OutputParser, ParseError, ParseResult};
+
+/// A fenced code block from a markdown response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// First word of the info string, like `python` in ` ```python `
+    pub language: Option<String>,
+    pub code: String,
+}
+
+impl CodeBlock {
+    /// Whether the block is tagged `language`, ignoring case
+    pub fn is_language(&self, language: &str) -> bool {
+        self.language
+            .as_deref()
+            .is_some_and(|l| l.eq_ignore_ascii_case(language))
+    }
+}
+
+/// Fenced code blocks of `text`, in order
+///
+/// Fences are three or more backticks or tildes, indented by at most three
+/// spaces, and a block ends at a fence of the same character at least as
+/// long. A block left open at the end, as in a truncated response, runs to
+/// the end of the text.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::output_parsers::extract_code_blocks;
+///
+/// let text = "Install it:\n```sh\npip install requests\n```\nThen:\n```python\nimport requests\n```";
+/// let blocks = extract_code_blocks(text);
+/// assert_eq!(blocks.len(), 2);
+/// assert!(blocks[1].is_language("python"));
+/// assert_eq!(blocks[1].code, "import requests");
+/// ```
+pub fn extract_code_blocks(text: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(char, usize, Option<String>, Vec<&str>)> = None;
+    for line in text.lines() {
+        let fence = fence(line);
+        match &mut open {
+            None => {
+                if let Some((marker, length, info)) = fence {
+                    let language = info
+                        .split(|c: char| c.is_whitespace() || matches!(c, ',' | '{'))
+                        .next()
+                        .filter(|language| !language.is_empty())
+                        .map(String::from);
+                    open = Some((marker, length, language, Vec::new()));
+                }
+            }
+            Some((marker, length, _, lines)) => match fence {
+                Some((m, l, info)) if m == *marker && l >= *length && info.is_empty() => {
+                    let (_, _, language, lines) = open.take().unwrap();
+                    blocks.push(CodeBlock {
+                        language,
+                        code: lines.join("\n"),
+                    });
+                }
+                _ => lines.push(line),
+            },
+        }
+    }
+    if let Some((_, _, language, lines)) = open {
+        blocks.push(CodeBlock {
+            language,
+            code: lines.join("\n"),
+        });
+    }
+    blocks
+}
+
+/// Marker character, length, and trimmed info string of a fence line
+fn fence(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let length = trimmed.len() - trimmed.trim_start_matches(marker).len();
+    if length < 3 {
+        return None;
+    }
+    let info = trimmed[length..].trim();
+    // Backtick fences cannot have backticks in their info string
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+    Some((marker, length, info))
+}
+
+/// Parses the code out of a response, without the prose around it
+///
+/// The code is the first fenced block, or the first in the
+/// [`language`](Self::language) if one is set. A response without any fenced
+/// block is taken to be all code. Use [`extract_code_blocks`] for every block.
+#[derive(Debug, Clone, Default)]
+pub struct CodeBlockParser {
+    language: Option<String>,
+}
+
+impl CodeBlockParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only take blocks tagged `language`, like `python` or `sql`
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+}
+
+impl OutputParser<String> for CodeBlockParser {
+    fn parse(&self, text: &str) -> ParseResult<String> {
+        let blocks = extract_code_blocks(text);
+        if blocks.is_empty() {
+            return Ok(text.trim().to_string());
+        }
+        let block = match &self.language {
+            Some(language) => blocks.into_iter().find(|b| b.is_language(language)),
+            None => blocks.into_iter().next(),
+        };
+        block.map(|b| b.code).ok_or_else(|| {
+            ParseError::invalid(
+                format!(
+                    "no {} code block",
+                    self.language.as_deref().unwrap_or_default()
+                ),
+                text,
+            )
+        })
+    }
+
+    fn format_instructions(&self) -> Option<String> {
+        Some(format!(
+            "Put the code in a single fenced code block: ```{}",
+            self.language.as_deref().unwrap_or_default()
+        ))
+    }
+}
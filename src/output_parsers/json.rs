@@ -0,0 +1,252 @@
+//! JSON output parsing.
+
+use super::repair::repair_json;
+use super::{OutputParser, ParseError, ParseResult};
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+/// Parses a JSON response into `T`
+///
+/// Text around the JSON value, like a sentence of preamble, is ignored: if
+/// the whole response is not valid JSON, the span from the first `{` or `[`
+/// to the last matching closer is tried, then the response as fixed by
+/// [`repair_json`]. With [`strict`](Self::strict), only valid JSON with
+/// nothing but whitespace around it is accepted.
+///
+/// While a response streams, [`parse_partial`](OutputParser::parse_partial)
+/// gives `T` for the part received so far, completed as described in
+/// [`parse_partial_json`]. Fields that arrive late should be `Option`s or
+/// `#[serde(default)]`, or there is nothing to show until they do.
+pub struct JsonParser<T> {
+    example: Option<String>,
+    strict: bool,
+    _output: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonParser<T> {
+    pub fn new() -> Self {
+        Self {
+            example: None,
+            strict: false,
+            _output: PhantomData,
+        }
+    }
+
+    /// Example JSON shown to the model in the format instructions
+    pub fn example(mut self, example: impl Into<String>) -> Self {
+        self.example = Some(example.into());
+        self
+    }
+
+    /// Reject anything but valid JSON instead of extracting and repairing it
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+}
+
+impl<T> Default for JsonParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for JsonParser<T> {
+    fn clone(&self) -> Self {
+        Self {
+            example: self.example.clone(),
+            strict: self.strict,
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for JsonParser<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonParser")
+            .field("example", &self.example)
+            .field("strict", &self.strict)
+            .finish()
+    }
+}
+
+impl<T: DeserializeOwned> OutputParser<T> for JsonParser<T> {
+    fn parse(&self, text: &str) -> ParseResult<T> {
+        let text = text.trim();
+        let error = match serde_json::from_str(text) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        if self.strict {
+            return Err(ParseError::invalid(format!("invalid JSON: {error}"), text));
+        }
+        if let Some(span) = json_span(text) {
+            if let Ok(value) = serde_json::from_str(span) {
+                return Ok(value);
+            }
+        }
+        if let Some(value) = repair_json(text).and_then(|v| serde_json::from_value(v).ok()) {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("parsed model output after repairing its JSON");
+            return Ok(value);
+        }
+        Err(ParseError::invalid(format!("invalid JSON: {error}"), text))
+    }
+
+    fn parse_partial(&self, text: &str) -> Option<T> {
+        serde_json::from_value(parse_partial_json(text)?).ok()
+    }
+
+    fn format_instructions(&self) -> Option<String> {
+        let mut instructions =
+            "Respond with only a JSON value, without any other text.".to_string();
+        if let Some(example) = &self.example {
+            instructions.push_str(&format!(" For example:\n{example}"));
+        }
+        Some(instructions)
+    }
+}
+
+/// Span from the first `{` or `[` to the last matching closer
+fn json_span(text: &str) -> Option<&str> {
+    let start = text.find(['{', '['])?;
+    let closer = if text[start..].starts_with('{') {
+        '}'
+    } else {
+        ']'
+    };
+    let end = text.rfind(closer)?;
+    (end > start).then(|| &text[start..=end])
+}
+
+/// Open JSON container, innermost last
+enum Frame {
+    Object { expect_key: bool },
+    Array,
+}
+
+impl Frame {
+    fn closer(&self) -> char {
+        match self {
+            Frame::Object { .. } => '}',
+            Frame::Array => ']',
+        }
+    }
+}
+
+fn closers(stack: &[Frame]) -> String {
+    stack.iter().rev().map(Frame::closer).collect()
+}
+
+/// Value of the JSON object or array that `text`, a response still being
+/// generated, starts, with whatever is still open closed
+///
+/// Text before the first `{` or `[` is skipped. A string value cut short is
+/// kept as far as it goes, so a title can be shown as it is written, while
+/// a key, number, `true`, `false`, or `null` cut short is left out with its
+/// key, since its value is not known yet. `None` if there is no object or
+/// array yet.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::output_parsers::parse_partial_json;
+/// use serde_json::json;
+///
+/// let partial = r#"{"title": "Rust in production", "points": ["Fast", "Saf"#;
+/// assert_eq!(
+///     parse_partial_json(partial),
+///     Some(json!({"title": "Rust in production", "points": ["Fast", "Saf"]}))
+/// );
+/// assert_eq!(parse_partial_json(r#"{"title": "Rust", "words": 12"#), Some(json!({"title": "Rust"})));
+/// ```
+pub fn parse_partial_json(text: &str) -> Option<serde_json::Value> {
+    let text = &text[text.find(['{', '['])?..];
+    let mut stack: Vec<Frame> = Vec::new();
+    // Where the text can be cut and closed: after an opened container or a
+    // complete value
+    let mut safe = (0, String::new());
+    let mut in_string = false;
+    let mut string_is_key = false;
+    // Start of an escape sequence still being read, and its characters left
+    let mut escape: Option<usize> = None;
+    let mut escape_left = 0;
+    let mut in_primitive = false;
+
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escape.is_some() => {
+                    escape_left -= 1;
+                    if c == 'u' && escape == Some(i - 1) {
+                        escape_left = 4;
+                    }
+                    if escape_left == 0 {
+                        escape = None;
+                    }
+                }
+                '\\' => {
+                    escape = Some(i);
+                    escape_left = 1;
+                }
+                '"' => {
+                    in_string = false;
+                    if string_is_key {
+                        if let Some(Frame::Object { expect_key }) = stack.last_mut() {
+                            *expect_key = false;
+                        }
+                    } else {
+                        safe = (i + 1, closers(&stack));
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if in_primitive {
+            if !(matches!(c, ',' | '}' | ']') || c.is_whitespace()) {
+                continue;
+            }
+            in_primitive = false;
+            safe = (i, closers(&stack));
+        }
+        match c {
+            '{' => {
+                stack.push(Frame::Object { expect_key: true });
+                safe = (i + 1, closers(&stack));
+            }
+            '[' => {
+                stack.push(Frame::Array);
+                safe = (i + 1, closers(&stack));
+            }
+            '}' | ']' => {
+                stack.pop();
+                if stack.is_empty() {
+                    return serde_json::from_str(&text[..=i]).ok();
+                }
+                safe = (i + 1, closers(&stack));
+            }
+            '"' => {
+                in_string = true;
+                string_is_key = matches!(stack.last(), Some(Frame::Object { expect_key: true }));
+            }
+            ',' => {
+                if let Some(Frame::Object { expect_key }) = stack.last_mut() {
+                    *expect_key = true;
+                }
+            }
+            ':' => {}
+            c if c.is_whitespace() => {}
+            _ => in_primitive = true,
+        }
+    }
+
+    let completed = if in_string && !string_is_key {
+        // An escape sequence cut short cannot be decoded yet
+        let value = &text[..escape.unwrap_or(text.len())];
+        format!("{value}\"{}", closers(&stack))
+    } else {
+        format!("{}{}", &text[..safe.0], safe.1)
+    };
+    serde_json::from_str(&completed).ok()
+}
@@ -0,0 +1,76 @@
+//! List output parsing.
+
+use super::{OutputParser, ParseResult};
+
+/// Parses a comma- or newline-separated list into its items
+///
+/// Items are trimmed and empty ones dropped. Newline lists also lose their
+/// bullets and numbering, so `- a`, `* a`, and `1. a` all give `a`.
+#[derive(Debug, Clone)]
+pub struct ListParser {
+    separator: Separator,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Separator {
+    Comma,
+    Newline,
+}
+
+impl ListParser {
+    /// Items separated by commas, like `red, green, blue`
+    pub fn comma() -> Self {
+        Self {
+            separator: Separator::Comma,
+        }
+    }
+
+    /// One item per line
+    pub fn newline() -> Self {
+        Self {
+            separator: Separator::Newline,
+        }
+    }
+}
+
+impl OutputParser<Vec<String>> for ListParser {
+    fn parse(&self, text: &str) -> ParseResult<Vec<String>> {
+        let items: Vec<&str> = match self.separator {
+            Separator::Comma => text.split(',').collect(),
+            Separator::Newline => text.lines().map(strip_marker).collect(),
+        };
+        Ok(items
+            .into_iter()
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    fn format_instructions(&self) -> Option<String> {
+        Some(
+            match self.separator {
+                Separator::Comma => {
+                    "Respond with only a comma-separated list, like: first, second, third"
+                }
+                Separator::Newline => "Respond with only a list, one item per line.",
+            }
+            .to_string(),
+        )
+    }
+}
+
+/// `line` without a leading bullet or number
+fn strip_marker(line: &str) -> &str {
+    let line = line.trim_start();
+    if let Some(rest) = line.strip_prefix(['-', '*', '•']) {
+        return rest;
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 {
+        if let Some(rest) = line[digits..].strip_prefix(['.', ')']) {
+            return rest;
+        }
+    }
+    line
+}
@@ -0,0 +1,260 @@
+//! Output parsers for AgenticOptio.
+//!
+//! Parsers turn a model's text response into a typed value: JSON into any
+//! deserializable type, lists, regex captures, XML tags, markdown tables, and
+//! fenced code blocks.
+//! [`ParsedChatModel`] attaches a parser to a chat model, so
+//! [`invoke_parsed`](ParsedChatModel::invoke_parsed) returns the value
+//! directly and [`stream_parsed`](ParsedChatModel::stream_parsed) returns it
+//! as it is generated.
+
+pub mod classify;
+pub mod code;
+pub mod json;
+pub mod list;
+pub mod pattern;
+pub mod repair;
+pub mod table;
+pub mod xml;
+
+pub use classify::{Classifier, LabelParser};
+pub use code::{extract_code_blocks, CodeBlock, CodeBlockParser};
+pub use json::{parse_partial_json, JsonParser};
+pub use list::ListParser;
+pub use pattern::RegexParser;
+pub use repair::repair_json;
+pub use table::{MarkdownTable, MarkdownTableParser};
+pub use xml::XmlTagParser;
+
+use crate::core::messages::Message;
+use crate::models::base::{BaseChatModel, BoxStream, ModelError};
+use crate::models::ChatModel;
+use futures::stream::{self, StreamExt};
+use std::sync::Arc;
+
+/// Error type for parsing model output
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    #[error("Model error: {0}")]
+    Model(#[from] ModelError),
+
+    /// The output does not have the expected shape
+    #[error("Could not parse output: {reason}")]
+    Invalid { reason: String, output: String },
+}
+
+impl ParseError {
+    pub fn invalid(reason: impl Into<String>, output: impl Into<String>) -> Self {
+        ParseError::Invalid {
+            reason: reason.into(),
+            output: output.into(),
+        }
+    }
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Base trait for output parsers
+pub trait OutputParser<T>: Send + Sync {
+    fn parse(&self, text: &str) -> ParseResult<T>;
+
+    /// Value from the start of a response still being generated, if the
+    /// parser can tell one yet; none by default
+    fn parse_partial(&self, text: &str) -> Option<T> {
+        let _ = text;
+        None
+    }
+
+    /// Instructions telling the model how to format its response, if the
+    /// parser has any
+    fn format_instructions(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Chat model with an output parser attached
+///
+/// The parser's format instructions, if any, are appended to the leading
+/// system message, or sent as one when the conversation has none.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::output_parsers::{JsonParser, ParsedChatModel};
+/// use agentic_optio_rs::{Message, OllamaChat};
+/// use serde::Deserialize;
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct City {
+///     name: String,
+///     population: u64,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let llm = ParsedChatModel::new(
+///         Arc::new(OllamaChat::new("llama3.2")),
+///         JsonParser::<City>::new().example(r#"{"name": "Oslo", "population": 709000}"#),
+///     );
+///     let city = llm
+///         .invoke_parsed(&[Message::user("What is the largest city in Norway?")])
+///         .await?;
+///     println!("{} has {} people", city.name, city.population);
+///     Ok(())
+/// }
+/// ```
+pub struct ParsedChatModel<T> {
+    model: Arc<dyn BaseChatModel>,
+    parser: Arc<dyn OutputParser<T>>,
+    max_retries: u32,
+}
+
+impl<T> Clone for ParsedChatModel<T> {
+    fn clone(&self) -> Self {
+        Self {
+            model: self.model.clone(),
+            parser: self.parser.clone(),
+            max_retries: self.max_retries,
+        }
+    }
+}
+
+impl<T> ParsedChatModel<T> {
+    pub fn new(model: Arc<dyn BaseChatModel>, parser: impl OutputParser<T> + 'static) -> Self {
+        Self {
+            model,
+            parser: Arc::new(parser),
+            max_retries: 0,
+        }
+    }
+
+    /// Times to ask again, with the parse error as feedback, after a
+    /// response that does not parse (default 0)
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn model(&self) -> &Arc<dyn BaseChatModel> {
+        &self.model
+    }
+
+    pub fn parser(&self) -> &dyn OutputParser<T> {
+        self.parser.as_ref()
+    }
+
+    /// Invoke the model and parse its response
+    pub async fn invoke_parsed(&self, messages: &[Message]) -> ParseResult<T> {
+        let mut conversation = self.instructed(messages);
+        let mut retries = 0;
+        loop {
+            let response = self.model.invoke(&conversation).await?;
+            let reason = match self.parser.parse(&response.content) {
+                Err(ParseError::Invalid { reason, .. }) if retries < self.max_retries => reason,
+                result => return result,
+            };
+            retries += 1;
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                attempt = retries,
+                max_retries = self.max_retries,
+                reason = %reason,
+                "retrying after unparsable output"
+            );
+            conversation.push(Message::AI(response));
+            let mut feedback = format!("Your previous response could not be used: {reason}.");
+            if let Some(instructions) = self.parser.format_instructions() {
+                feedback.push(' ');
+                feedback.push_str(&instructions);
+            }
+            conversation.push(Message::user(feedback));
+        }
+    }
+
+    /// Stream progressively completed values while the response is generated
+    ///
+    /// A value is yielded after every chunk from which the parser can tell
+    /// one (see [`OutputParser::parse_partial`]), and the stream ends with the
+    /// value parsed from the whole response, or the error. Like
+    /// [`ChatModel::stream`](crate::ChatModel::stream), nothing is sent until
+    /// the stream is first polled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use agentic_optio_rs::output_parsers::{JsonParser, ParsedChatModel};
+    /// use agentic_optio_rs::{Message, OllamaChat};
+    /// use futures::StreamExt;
+    /// use serde::Deserialize;
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, Default, Deserialize)]
+    /// #[serde(default)]
+    /// struct Outline {
+    ///     title: String,
+    ///     points: Vec<String>,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let llm = ParsedChatModel::new(
+    ///         Arc::new(OllamaChat::new("llama3.2")),
+    ///         JsonParser::<Outline>::new().example(r#"{"title": "...", "points": ["..."]}"#),
+    ///     );
+    ///     let mut outline = llm.stream_parsed(&[Message::user("Outline a talk on Rust")]);
+    ///     while let Some(Ok(outline)) = outline.next().await {
+    ///         println!("{} ({} points so far)", outline.title, outline.points.len());
+    ///     }
+    /// }
+    /// ```
+    pub fn stream_parsed(&self, messages: &[Message]) -> BoxStream<'static, ParseResult<T>>
+    where
+        T: Send + 'static,
+    {
+        let chunks = ChatModel::from(self.model.clone()).stream(self.instructed(messages));
+        let parser = self.parser.clone();
+        let state = (chunks, String::new(), false);
+        Box::pin(stream::unfold(
+            state,
+            move |(mut chunks, mut text, done)| {
+                let parser = parser.clone();
+                async move {
+                    if done {
+                        return None;
+                    }
+                    loop {
+                        match chunks.next().await {
+                            Some(Ok(chunk)) => {
+                                text.push_str(&chunk.content);
+                                if let Some(value) = parser.parse_partial(&text) {
+                                    return Some((Ok(value), (chunks, text, false)));
+                                }
+                            }
+                            Some(Err(e)) => return Some((Err(e.into()), (chunks, text, true))),
+                            None => {
+                                let parsed = parser.parse(&text);
+                                return Some((parsed, (chunks, text, true)));
+                            }
+                        }
+                    }
+                }
+            },
+        ))
+    }
+
+    /// `messages` with the parser's format instructions
+    fn instructed(&self, messages: &[Message]) -> Vec<Message> {
+        let mut messages = messages.to_vec();
+        let Some(instructions) = self.parser.format_instructions() else {
+            return messages;
+        };
+        match messages.first_mut() {
+            Some(first @ Message::System(_)) => {
+                *first = Message::system(format!("{}\n\n{instructions}", first.content()));
+            }
+            _ => messages.insert(0, Message::system(instructions)),
+        }
+        messages
+    }
+}
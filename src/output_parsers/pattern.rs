@@ -0,0 +1,46 @@
+//! Regex capture output parsing.
+
+use super::{OutputParser, ParseError, ParseResult};
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Parses the named capture groups of the first match of a regex
+///
+/// Groups that did not take part in the match are left out.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::output_parsers::{OutputParser, RegexParser};
+///
+/// let parser = RegexParser::new(r"Score: (?P<score>\d+)/10").unwrap();
+/// let captures = parser.parse("Clear and correct. Score: 8/10").unwrap();
+/// assert_eq!(captures["score"], "8");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegexParser {
+    regex: Regex,
+}
+
+impl RegexParser {
+    /// Create a parser, failing if `pattern` is invalid
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            regex: Regex::new(pattern)?,
+        })
+    }
+}
+
+impl OutputParser<HashMap<String, String>> for RegexParser {
+    fn parse(&self, text: &str) -> ParseResult<HashMap<String, String>> {
+        let captures = self.regex.captures(text).ok_or_else(|| {
+            ParseError::invalid(format!("no match for /{}/", self.regex.as_str()), text)
+        })?;
+        Ok(self
+            .regex
+            .capture_names()
+            .flatten()
+            .filter_map(|name| Some((name.to_string(), captures.name(name)?.as_str().to_string())))
+            .collect())
+    }
+}
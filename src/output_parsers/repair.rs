@@ -0,0 +1,148 @@
+//! Lenient repair of malformed JSON.
+
+use super::code::extract_code_blocks;
+use super::json::parse_partial_json;
+
+/// Value of the JSON object or array in `text`, after repairing the usual
+/// slips of models writing JSON by hand
+///
+/// Repairs:
+///
+/// - markdown code fences and text around the value
+/// - trailing commas
+/// - unquoted and single-quoted keys and strings
+/// - `//` and `/* */` comments
+/// - Python and JavaScript literals: `True`, `False`, `None`, `undefined`
+/// - raw newlines and tabs inside strings
+/// - truncation, by closing what is open and dropping a key or number cut
+///   short, as [`parse_partial_json`] does
+///
+/// `None` if there is no object or array to repair.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::output_parsers::repair_json;
+/// use serde_json::json;
+///
+/// let text = "```json\n{name: 'Oslo', tags: ['capital', 'port',], coastal: True, notes: \"Fjord";
+/// assert_eq!(
+///     repair_json(text),
+///     Some(json!({"name": "Oslo", "tags": ["capital", "port"], "coastal": true, "notes": "Fjord"}))
+/// );
+/// ```
+pub fn repair_json(text: &str) -> Option<serde_json::Value> {
+    let block = extract_code_blocks(text).into_iter().next();
+    let text = block.as_ref().map_or(text, |b| &b.code);
+    let text = &text[text.find(['{', '['])?..];
+    parse_partial_json(&normalize(text))
+}
+
+/// `text` rewritten as strict JSON, as far as it goes
+fn normalize(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' => i = copy_string(&chars, i, &mut out),
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            ',' => {
+                if !matches!(next_significant(&chars, i + 1), Some('}' | ']') | None) {
+                    out.push(',');
+                }
+                i += 1;
+            }
+            // Exponent of a number, like 1e5
+            'e' | 'E' if out.ends_with(|c: char| c.is_ascii_digit()) => {
+                out.push(c);
+                i += 1;
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$' | '-'))
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if next_significant(&chars, i) == Some(':') {
+                    out.push_str(&serde_json::Value::from(word).to_string());
+                } else {
+                    match word.as_str() {
+                        "true" | "True" | "TRUE" => out.push_str("true"),
+                        "false" | "False" | "FALSE" => out.push_str("false"),
+                        "null" | "None" | "NULL" | "undefined" | "NaN" => out.push_str("null"),
+                        // Cut short at the end, or a bare word meant as a
+                        // string
+                        _ if i == chars.len() => out.push_str(&word),
+                        _ => out.push_str(&serde_json::Value::from(word).to_string()),
+                    }
+                }
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Copy the string starting at `chars[start]`, quoted with `"`, returning the
+/// index after it
+fn copy_string(chars: &[char], start: usize, out: &mut String) -> usize {
+    let quote = chars[start];
+    out.push('"');
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => match chars.get(i + 1) {
+                Some('\'') => {
+                    out.push('\'');
+                    i += 2;
+                    continue;
+                }
+                Some(&next) => {
+                    out.push('\\');
+                    out.push(next);
+                    i += 2;
+                    continue;
+                }
+                // Cut short: leave the escape for the partial parser
+                None => out.push('\\'),
+            },
+            c if c == quote => {
+                out.push('"');
+                return i + 1;
+            }
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+        i += 1;
+    }
+    i
+}
+
+/// First character from `chars[from]` on that is not whitespace
+fn next_significant(chars: &[char], from: usize) -> Option<char> {
+    chars[from.min(chars.len())..]
+        .iter()
+        .copied()
+        .find(|c| !c.is_whitespace())
+}
@@ -0,0 +1,94 @@
+//! Markdown table output parsing.
+
+use super::{OutputParser, ParseError, ParseResult};
+
+/// A parsed markdown table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownTable {
+    pub headers: Vec<String>,
+    /// Cells of each row, padded or cut to the number of headers
+    pub rows: Vec<Vec<String>>,
+}
+
+impl MarkdownTable {
+    /// Cells of the column headed `header`
+    pub fn column(&self, header: &str) -> Option<Vec<&str>> {
+        let index = self.headers.iter().position(|h| h == header)?;
+        Some(self.rows.iter().map(|row| row[index].as_str()).collect())
+    }
+}
+
+/// Parses the first markdown table in a response
+///
+/// Text before and after the table is ignored, and the `|` at either end of
+/// a row is optional.
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownTableParser {
+    headers: Vec<String>,
+}
+
+impl MarkdownTableParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the model for a table with these columns
+    pub fn headers<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.headers = headers.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+impl OutputParser<MarkdownTable> for MarkdownTableParser {
+    fn parse(&self, text: &str) -> ParseResult<MarkdownTable> {
+        let lines: Vec<&str> = text.lines().map(str::trim).collect();
+        // The header is the row right above the first delimiter row
+        let delimiter = lines
+            .iter()
+            .position(|line| is_delimiter_row(line))
+            .filter(|&i| i > 0 && lines[i - 1].contains('|'))
+            .ok_or_else(|| ParseError::invalid("no markdown table", text))?;
+
+        let headers = cells(lines[delimiter - 1]);
+        let rows = lines[delimiter + 1..]
+            .iter()
+            .take_while(|line| line.contains('|'))
+            .map(|line| {
+                let mut row = cells(line);
+                row.resize(headers.len(), String::new());
+                row
+            })
+            .collect();
+        Ok(MarkdownTable { headers, rows })
+    }
+
+    fn format_instructions(&self) -> Option<String> {
+        let mut instructions = "Respond with a markdown table".to_string();
+        if !self.headers.is_empty() {
+            instructions.push_str(&format!(" with the columns: {}", self.headers.join(", ")));
+        }
+        instructions.push('.');
+        Some(instructions)
+    }
+}
+
+/// Whether `line` is a header delimiter like `| --- | :-: |`
+fn is_delimiter_row(line: &str) -> bool {
+    line.contains('-')
+        && line.contains('|')
+        && line
+            .chars()
+            .all(|c| matches!(c, '|' | '-' | ':' | ' ' | '\t'))
+}
+
+fn cells(line: &str) -> Vec<String> {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|')
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
@@ -0,0 +1,41 @@
+//! XML tag output parsing.
+
+use super::{OutputParser, ParseError, ParseResult};
+
+/// Parses the content of the first `<tag>...</tag>` element
+///
+/// Models follow tag-delimited formats well, and the content may hold any
+/// text, including quotes and newlines that would need escaping in JSON. The
+/// content is trimmed; nothing in it is unescaped.
+#[derive(Debug, Clone)]
+pub struct XmlTagParser {
+    tag: String,
+}
+
+impl XmlTagParser {
+    pub fn new(tag: impl Into<String>) -> Self {
+        Self { tag: tag.into() }
+    }
+}
+
+impl OutputParser<String> for XmlTagParser {
+    fn parse(&self, text: &str) -> ParseResult<String> {
+        let open = format!("<{}>", self.tag);
+        let close = format!("</{}>", self.tag);
+        let start = text
+            .find(&open)
+            .ok_or_else(|| ParseError::invalid(format!("no <{}> tag", self.tag), text))?
+            + open.len();
+        let end = text[start..]
+            .find(&close)
+            .ok_or_else(|| ParseError::invalid(format!("unclosed <{}> tag", self.tag), text))?;
+        Ok(text[start..start + end].trim().to_string())
+    }
+
+    fn format_instructions(&self) -> Option<String> {
+        Some(format!(
+            "Put your answer between <{tag}> and </{tag}> tags.",
+            tag = self.tag
+        ))
+    }
+}
@@ -0,0 +1,168 @@
+//! Chat prompt templates.
+
+use crate::core::messages::Message;
+use crate::prompts::{PromptError, PromptResult, PromptTemplate};
+use std::collections::HashMap;
+
+/// Variables and message lists to render a [`ChatPromptTemplate`] with
+#[derive(Debug, Clone, Default)]
+pub struct PromptValues {
+    variables: HashMap<String, String>,
+    messages: HashMap<String, Vec<Message>>,
+}
+
+impl PromptValues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Value for a `{name}` variable
+    pub fn set(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.variables.insert(name.into(), value.to_string());
+        self
+    }
+
+    /// Messages for the placeholder called `name`
+    pub fn messages(mut self, name: impl Into<String>, messages: Vec<Message>) -> Self {
+        self.messages.insert(name.into(), messages);
+        self
+    }
+}
+
+/// Role of a templated chat message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ChatPart {
+    Template(ChatRole, PromptTemplate),
+    Placeholder(String),
+}
+
+/// Sequence of role-tagged templates and message placeholders rendering to a
+/// message list
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::prompts::{ChatPromptTemplate, PromptValues};
+/// use agentic_optio_rs::Message;
+///
+/// let prompt = ChatPromptTemplate::new()
+///     .system("You are a {persona}.")?
+///     .placeholder("history")
+///     .user("{question}")?;
+///
+/// let messages = prompt.format_messages(
+///     &PromptValues::new()
+///         .set("persona", "patient tutor")
+///         .set("question", "And 3 + 3?")
+///         .messages("history", vec![Message::user("2 + 2?"), Message::assistant("4")]),
+/// )?;
+///
+/// assert_eq!(messages.len(), 4);
+/// assert_eq!(messages[0].content(), "You are a patient tutor.");
+/// assert_eq!(messages[3].content(), "And 3 + 3?");
+/// # Ok::<(), agentic_optio_rs::prompts::PromptError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChatPromptTemplate {
+    parts: Vec<ChatPart>,
+}
+
+impl ChatPromptTemplate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a message of `role` rendered from `template`
+    pub fn message(mut self, role: ChatRole, template: &str) -> PromptResult<Self> {
+        self.parts
+            .push(ChatPart::Template(role, PromptTemplate::new(template)?));
+        Ok(self)
+    }
+
+    pub fn system(self, template: &str) -> PromptResult<Self> {
+        self.message(ChatRole::System, template)
+    }
+
+    pub fn user(self, template: &str) -> PromptResult<Self> {
+        self.message(ChatRole::User, template)
+    }
+
+    pub fn assistant(self, template: &str) -> PromptResult<Self> {
+        self.message(ChatRole::Assistant, template)
+    }
+
+    /// Insert the messages supplied under `name` at this point
+    pub fn placeholder(mut self, name: impl Into<String>) -> Self {
+        self.parts.push(ChatPart::Placeholder(name.into()));
+        self
+    }
+
+    /// Fix a variable's value in every template ahead of formatting
+    pub fn partial(mut self, name: &str, value: impl ToString) -> Self {
+        let value = value.to_string();
+        for part in &mut self.parts {
+            if let ChatPart::Template(_, template) = part {
+                *template = template.clone().partial(name, &value);
+            }
+        }
+        self
+    }
+
+    /// Variables still to be supplied, in order of first appearance
+    pub fn input_variables(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for part in &self.parts {
+            if let ChatPart::Template(_, template) = part {
+                for name in template.input_variables() {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    /// Names of the message placeholders
+    pub fn placeholders(&self) -> Vec<&str> {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                ChatPart::Placeholder(name) => Some(name.as_str()),
+                ChatPart::Template(..) => None,
+            })
+            .collect()
+    }
+
+    /// Render to messages; a missing variable or placeholder is an error
+    pub fn format_messages(&self, values: &PromptValues) -> PromptResult<Vec<Message>> {
+        let mut messages = Vec::with_capacity(self.parts.len());
+        for part in &self.parts {
+            match part {
+                ChatPart::Template(role, template) => {
+                    let content = template.format(&values.variables)?;
+                    messages.push(match role {
+                        ChatRole::System => Message::system(content),
+                        ChatRole::User => Message::user(content),
+                        ChatRole::Assistant => Message::assistant(content),
+                    });
+                }
+                ChatPart::Placeholder(name) => {
+                    let inserted = values
+                        .messages
+                        .get(name)
+                        .ok_or_else(|| PromptError::MissingVariable(name.clone()))?;
+                    messages.extend(inserted.iter().cloned());
+                }
+            }
+        }
+        Ok(messages)
+    }
+}
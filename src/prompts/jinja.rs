@@ -0,0 +1,83 @@
+//! Jinja prompt templates.
+//!
+//! Conditionals, loops, and filters for prompts written for Jinja-based stacks,
+//! rendered with minijinja. Enabled with the `jinja` feature.
+
+use crate::prompts::{PromptError, PromptResult};
+use minijinja::{Environment, ErrorKind, UndefinedBehavior};
+use serde::Serialize;
+use std::collections::HashSet;
+
+const TEMPLATE_NAME: &str = "prompt";
+
+/// Prompt template in Jinja syntax
+///
+/// Undefined variables are errors, matching [`PromptTemplate`](super::PromptTemplate).
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::prompts::JinjaTemplate;
+/// use serde_json::json;
+///
+/// let template = JinjaTemplate::new(
+///     "Answer using these sources:\n\
+///      {% for doc in docs %}- {{ doc | upper }}\n{% endfor %}\
+///      {% if strict %}Say 'unknown' if unsure.{% endif %}",
+/// )?;
+///
+/// let prompt = template.render(json!({"docs": ["alpha", "beta"], "strict": true}))?;
+/// assert_eq!(
+///     prompt,
+///     "Answer using these sources:\n- ALPHA\n- BETA\nSay 'unknown' if unsure."
+/// );
+/// # Ok::<(), agentic_optio_rs::prompts::PromptError>(())
+/// ```
+#[derive(Debug)]
+pub struct JinjaTemplate {
+    source: String,
+    env: Environment<'static>,
+}
+
+impl JinjaTemplate {
+    /// Compile a template, rejecting syntax errors
+    pub fn new(source: impl Into<String>) -> PromptResult<Self> {
+        let source = source.into();
+        let mut env = Environment::new();
+        env.set_undefined_behavior(UndefinedBehavior::Strict);
+        env.add_template_owned(TEMPLATE_NAME, source.clone())
+            .map_err(jinja_error)?;
+        Ok(Self { source, env })
+    }
+
+    /// Template source text
+    pub fn template(&self) -> &str {
+        &self.source
+    }
+
+    /// Top-level variables the template reads, sorted
+    pub fn input_variables(&self) -> Vec<String> {
+        let names: HashSet<String> = self.compiled().undeclared_variables(false);
+        let mut names: Vec<String> = names.into_iter().collect();
+        names.sort();
+        names
+    }
+
+    /// Render with any serializable context, such as a `serde_json` object
+    pub fn render(&self, context: impl Serialize) -> PromptResult<String> {
+        self.compiled().render(context).map_err(jinja_error)
+    }
+
+    fn compiled(&self) -> minijinja::Template<'_, '_> {
+        self.env
+            .get_template(TEMPLATE_NAME)
+            .expect("template was added on construction")
+    }
+}
+
+fn jinja_error(err: minijinja::Error) -> PromptError {
+    match err.kind() {
+        ErrorKind::SyntaxError => PromptError::Parse(err.to_string()),
+        _ => PromptError::Render(err.to_string()),
+    }
+}
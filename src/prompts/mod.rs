@@ -0,0 +1,48 @@
+//! Prompt templates for AgenticOptio.
+//!
+//! A [`PromptTemplate`] renders text with `{variable}` placeholders filled in,
+//! failing loudly when a variable is missing instead of sending a half-filled
+//! prompt to a model. A [`ChatPromptTemplate`] combines role-tagged templates
+//! and message placeholders into the message list a chat model is invoked with.
+//! With the `jinja` feature, [`JinjaTemplate`] adds conditionals, loops, and
+//! filters. A [`PromptRepository`] loads templates, personas, and agent
+//! definitions from files, so prompts aren't baked into Rust source, and a
+//! [`PromptRegistry`] holds versioned prompts for controlled rollouts.
+//! [`SystemPromptBuilder`] assembles consistent agent system prompts.
+
+pub mod chat;
+#[cfg(feature = "jinja")]
+pub mod jinja;
+pub mod registry;
+pub mod repository;
+pub mod system;
+pub mod template;
+
+pub use chat::{ChatPromptTemplate, ChatRole, PromptValues};
+#[cfg(feature = "jinja")]
+pub use jinja::JinjaTemplate;
+pub use registry::{PromptRegistry, PromptVersion, VersionSelection};
+pub use repository::{AgentDefinition, PromptRepository};
+pub use system::SystemPromptBuilder;
+pub use template::PromptTemplate;
+
+/// Error type for prompt operations
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PromptError {
+    #[error("Invalid template: {0}")]
+    Parse(String),
+
+    #[error("Missing template variable: {0}")]
+    MissingVariable(String),
+
+    #[error("Template rendering failed: {0}")]
+    Render(String),
+
+    #[error("Failed to load prompts: {0}")]
+    Load(String),
+
+    #[error("Prompt version error: {0}")]
+    Version(String),
+}
+
+pub type PromptResult<T> = Result<T, PromptError>;
@@ -0,0 +1,211 @@
+//! Versioned prompts.
+
+use crate::cache::fnv1a_128;
+use crate::prompts::{PromptError, PromptResult, PromptTemplate};
+use std::collections::HashMap;
+
+/// One registered version of a named prompt
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptVersion {
+    pub name: String,
+    /// Semantic version such as `1.2.0`, or the content hash for hashed prompts
+    pub version: String,
+    /// Content hash of the template text
+    pub hash: String,
+    pub template: PromptTemplate,
+}
+
+impl PromptVersion {
+    /// Metadata key and value identifying this version in run metadata, such as
+    /// `("prompt.summarize", "1.2.0")`
+    pub fn tag(&self) -> (String, String) {
+        (format!("prompt.{}", self.name), self.version.clone())
+    }
+
+    pub fn format<I, K, V>(&self, values: I) -> PromptResult<String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: ToString,
+    {
+        self.template.format(values)
+    }
+}
+
+/// Which version of a prompt [`PromptRegistry::select`] returns
+#[derive(Debug, Clone, PartialEq)]
+pub enum VersionSelection {
+    /// The highest semantic version, or the most recently registered one when
+    /// no version is semantic
+    Latest,
+    /// Always this version
+    Pinned(String),
+    /// Versions drawn with probability proportional to their weights
+    Weighted(Vec<(String, f64)>),
+}
+
+/// Named prompts with several versions and a selection strategy per prompt
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::prompts::{PromptRegistry, VersionSelection};
+///
+/// let mut prompts = PromptRegistry::new();
+/// prompts.register("summarize", "1.0.0", "Summarize: {text}")?;
+/// prompts.register("summarize", "1.1.0", "Summarize in three bullets: {text}")?;
+/// assert_eq!(prompts.select("summarize")?.version, "1.1.0");
+///
+/// // Send a tenth of traffic to the old version, sticky per user
+/// prompts.strategy(
+///     "summarize",
+///     VersionSelection::Weighted(vec![("1.0.0".into(), 0.1), ("1.1.0".into(), 0.9)]),
+/// );
+/// let prompt = prompts.select_for("summarize", "user-42")?;
+/// assert_eq!(prompt.version, prompts.select_for("summarize", "user-42")?.version);
+/// println!("{:?}", prompt.tag());
+/// # Ok::<(), agentic_optio_rs::prompts::PromptError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PromptRegistry {
+    versions: HashMap<String, Vec<PromptVersion>>,
+    strategies: HashMap<String, VersionSelection>,
+}
+
+impl PromptRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a version of `name`
+    ///
+    /// Registering the same text under an existing version is a no-op;
+    /// different text under an existing version is an error.
+    pub fn register(&mut self, name: &str, version: &str, template: &str) -> PromptResult<()> {
+        let hash = content_hash(template);
+        let versions = self.versions.entry(name.to_string()).or_default();
+        if let Some(existing) = versions.iter().find(|v| v.version == version) {
+            return if existing.hash == hash {
+                Ok(())
+            } else {
+                Err(PromptError::Version(format!(
+                    "{}@{} is already registered with different text",
+                    name, version
+                )))
+            };
+        }
+        versions.push(PromptVersion {
+            name: name.to_string(),
+            version: version.to_string(),
+            hash,
+            template: PromptTemplate::new(template)?,
+        });
+        Ok(())
+    }
+
+    /// Register a version of `name` identified by its content hash, returning
+    /// the hash
+    pub fn register_hashed(&mut self, name: &str, template: &str) -> PromptResult<String> {
+        let hash = content_hash(template);
+        self.register(name, &hash, template)?;
+        Ok(hash)
+    }
+
+    /// Set how versions of `name` are selected (default: latest)
+    pub fn strategy(&mut self, name: &str, selection: VersionSelection) {
+        self.strategies.insert(name.to_string(), selection);
+    }
+
+    pub fn get(&self, name: &str, version: &str) -> Option<&PromptVersion> {
+        self.versions
+            .get(name)?
+            .iter()
+            .find(|v| v.version == version)
+    }
+
+    /// Versions of `name` in registration order
+    pub fn versions(&self, name: &str) -> &[PromptVersion] {
+        self.versions.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn latest(&self, name: &str) -> Option<&PromptVersion> {
+        self.versions(name)
+            .iter()
+            .enumerate()
+            .max_by_key(|(index, v)| (parse_semver(&v.version), *index))
+            .map(|(_, v)| v)
+    }
+
+    /// Version of `name` chosen by its strategy; weighted draws are random
+    pub fn select(&self, name: &str) -> PromptResult<&PromptVersion> {
+        let draw = (uuid::Uuid::new_v4().as_u128() >> 64) as u64;
+        self.select_with(name, draw)
+    }
+
+    /// Version of `name` chosen by its strategy, with weighted draws fixed by
+    /// `key` so a user or session keeps seeing the same version
+    pub fn select_for(&self, name: &str, key: &str) -> PromptResult<&PromptVersion> {
+        let hash = fnv1a_128(format!("{}\0{}", name, key).as_bytes());
+        self.select_with(name, mix((hash >> 64) as u64 ^ hash as u64))
+    }
+
+    fn select_with(&self, name: &str, draw: u64) -> PromptResult<&PromptVersion> {
+        let unknown = |version: &str| {
+            PromptError::Version(format!("unknown prompt version {}@{}", name, version))
+        };
+        match self
+            .strategies
+            .get(name)
+            .unwrap_or(&VersionSelection::Latest)
+        {
+            VersionSelection::Latest => self
+                .latest(name)
+                .ok_or_else(|| PromptError::Version(format!("unknown prompt '{}'", name))),
+            VersionSelection::Pinned(version) => {
+                self.get(name, version).ok_or_else(|| unknown(version))
+            }
+            VersionSelection::Weighted(weights) => {
+                let total: f64 = weights.iter().map(|(_, w)| w.max(0.0)).sum();
+                if total <= 0.0 {
+                    return Err(PromptError::Version(format!(
+                        "no positive weights for prompt '{}'",
+                        name
+                    )));
+                }
+                let mut point = draw as f64 / u64::MAX as f64 * total;
+                let mut chosen = &weights[0].0;
+                for (version, weight) in weights {
+                    if weight.max(0.0) > 0.0 {
+                        chosen = version;
+                    }
+                    point -= weight.max(0.0);
+                    if point < 0.0 {
+                        break;
+                    }
+                }
+                self.get(name, chosen).ok_or_else(|| unknown(chosen))
+            }
+        }
+    }
+}
+
+/// SplitMix64 finalizer, spreading similar keys evenly over the draw range
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Short content hash identifying a template text
+fn content_hash(template: &str) -> String {
+    format!("{:032x}", fnv1a_128(template.as_bytes()))[..12].to_string()
+}
+
+/// `MAJOR.MINOR.PATCH`, with an optional leading `v`
+fn parse_semver(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.strip_prefix('v').unwrap_or(version).splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.parse().ok()?;
+    Some((major, minor, patch))
+}
@@ -0,0 +1,357 @@
+//! Prompt repositories loaded from files.
+
+use crate::agents::executor::AgentBuilder;
+use crate::agents::Persona;
+use crate::models::base::BaseChatModel;
+#[cfg(feature = "ollama")]
+use crate::models::ollama::OllamaChat;
+use crate::prompts::{PromptError, PromptResult, PromptTemplate};
+use crate::tools::ToolRegistry;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Agent declared in a prompt file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentDefinition {
+    /// Taken from the agent's key in the file
+    #[serde(default)]
+    pub name: String,
+    /// Persona providing the system prompt and preferred model
+    #[serde(default)]
+    pub persona: Option<String>,
+    /// System prompt, replacing the persona's
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Ollama model name, overriding the persona's and the caller's; needs the
+    /// `ollama` feature
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Names of the tools to give the agent
+    #[serde(default)]
+    pub tools: Vec<String>,
+    #[serde(default)]
+    pub max_iterations: Option<usize>,
+}
+
+/// Contents of one prompt file
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PromptFile {
+    include: Vec<String>,
+    templates: BTreeMap<String, String>,
+    personas: BTreeMap<String, serde_json::Value>,
+    agents: BTreeMap<String, serde_json::Value>,
+}
+
+/// Templates, personas, and agent definitions loaded from prompt files
+///
+/// Files are JSON, or TOML and YAML with the `toml` and `yaml` features, with
+/// optional top-level `include`, `templates`, `personas`, and `agents` tables,
+/// each keyed by name:
+///
+/// ```yaml
+/// include: [shared.yaml]
+/// templates:
+///   summarize: "Summarize in ${SUMMARY_LANGUAGE:-English}: {text}"
+/// personas:
+///   reviewer:
+///     role: Senior Rust code reviewer
+///     goals: [Find correctness bugs]
+/// agents:
+///   review-bot:
+///     persona: reviewer
+///     tools: [read_file]
+/// ```
+///
+/// Included paths are relative to the including file, which overrides them.
+/// `${VAR}` and `${VAR:-default}` in string values are replaced from the
+/// environment; an unset variable without a default is an error.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::prompts::PromptRepository;
+/// use agentic_optio_rs::tools::ToolRegistry;
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let prompts = PromptRepository::load_dir("prompts")?;
+///     let summary = prompts.template("summarize").unwrap().format([("text", "...")])?;
+///
+///     let agent = prompts
+///         .agent("review-bot", Arc::new(OllamaChat::new("llama3.2")), &ToolRegistry::new())?
+///         .build();
+///     let run = agent.run(summary).await?;
+///     println!("{}", run.output);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PromptRepository {
+    templates: HashMap<String, PromptTemplate>,
+    personas: HashMap<String, Persona>,
+    agents: HashMap<String, AgentDefinition>,
+}
+
+impl PromptRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every prompt file under `dir`, recursively, in path order
+    ///
+    /// Files already loaded as another file's include are not loaded again, so
+    /// they never override the file that included them.
+    pub fn load_dir(dir: impl AsRef<Path>) -> PromptResult<Self> {
+        let mut repository = Self::new();
+        let mut loaded = HashSet::new();
+        for path in prompt_files(dir.as_ref())? {
+            if !loaded.contains(&canonical(&path)?) {
+                repository.load(&path, &mut Vec::new(), &mut loaded)?;
+            }
+        }
+        Ok(repository)
+    }
+
+    /// Load one prompt file and its includes, overriding existing entries
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> PromptResult<&mut Self> {
+        self.load(path.as_ref(), &mut Vec::new(), &mut HashSet::new())?;
+        Ok(self)
+    }
+
+    fn load(
+        &mut self,
+        path: &Path,
+        including: &mut Vec<PathBuf>,
+        loaded: &mut HashSet<PathBuf>,
+    ) -> PromptResult<()> {
+        let canonical = canonical(path)?;
+        if including.contains(&canonical) {
+            return Err(load_error(path, "include cycle".to_string()));
+        }
+
+        let text = std::fs::read_to_string(path).map_err(|e| load_error(path, e.to_string()))?;
+        let mut value = parse(path, &text)?;
+        interpolate_env(&mut value)?;
+        let file: PromptFile =
+            serde_json::from_value(value).map_err(|e| load_error(path, e.to_string()))?;
+
+        including.push(canonical.clone());
+        let base = path.parent().unwrap_or(Path::new("."));
+        for include in &file.include {
+            self.load(&base.join(include), including, loaded)?;
+        }
+        including.pop();
+        loaded.insert(canonical);
+
+        for (name, template) in file.templates {
+            self.templates.insert(name, PromptTemplate::new(template)?);
+        }
+        for (name, mut persona) in file.personas {
+            if let Some(fields) = persona.as_object_mut() {
+                fields
+                    .entry("name")
+                    .or_insert_with(|| serde_json::Value::String(name.clone()));
+            }
+            let persona =
+                serde_json::from_value(persona).map_err(|e| load_error(path, e.to_string()))?;
+            self.personas.insert(name, persona);
+        }
+        for (name, agent) in file.agents {
+            let mut agent: AgentDefinition =
+                serde_json::from_value(agent).map_err(|e| load_error(path, e.to_string()))?;
+            agent.name = name.clone();
+            self.agents.insert(name, agent);
+        }
+        Ok(())
+    }
+
+    pub fn template(&self, name: &str) -> Option<&PromptTemplate> {
+        self.templates.get(name)
+    }
+
+    pub fn persona(&self, name: &str) -> Option<&Persona> {
+        self.personas.get(name)
+    }
+
+    pub fn agent_definition(&self, name: &str) -> Option<&AgentDefinition> {
+        self.agents.get(name)
+    }
+
+    /// Names of the loaded templates, sorted
+    pub fn template_names(&self) -> Vec<&str> {
+        sorted_keys(&self.templates)
+    }
+
+    /// Names of the loaded personas, sorted
+    pub fn persona_names(&self) -> Vec<&str> {
+        sorted_keys(&self.personas)
+    }
+
+    /// Names of the loaded agent definitions, sorted
+    pub fn agent_names(&self) -> Vec<&str> {
+        sorted_keys(&self.agents)
+    }
+
+    /// Agent builder for a defined agent, with its tools taken from `tools`
+    ///
+    /// The agent uses `default_model` unless it or its persona names a model.
+    pub fn agent(
+        &self,
+        name: &str,
+        default_model: Arc<dyn BaseChatModel>,
+        tools: &ToolRegistry,
+    ) -> PromptResult<AgentBuilder> {
+        let definition = self
+            .agents
+            .get(name)
+            .ok_or_else(|| PromptError::Load(format!("unknown agent '{}'", name)))?;
+        let model = match &definition.model {
+            #[cfg(feature = "ollama")]
+            Some(model) => Arc::new(OllamaChat::new(model.as_str())) as Arc<dyn BaseChatModel>,
+            #[cfg(not(feature = "ollama"))]
+            Some(_) => {
+                return Err(PromptError::Load(format!(
+                    "agent '{}' names a model, which needs the `ollama` feature",
+                    name
+                )))
+            }
+            None => default_model,
+        };
+
+        let mut builder = match &definition.persona {
+            Some(persona) => self
+                .personas
+                .get(persona)
+                .ok_or_else(|| {
+                    PromptError::Load(format!(
+                        "agent '{}' uses unknown persona '{}'",
+                        name, persona
+                    ))
+                })?
+                .agent(model),
+            None => AgentBuilder::new(model),
+        }
+        .name(name);
+
+        if let Some(system_prompt) = &definition.system_prompt {
+            builder = builder.system_prompt(system_prompt.clone());
+        }
+        if let Some(max_iterations) = definition.max_iterations {
+            builder = builder.max_iterations(max_iterations);
+        }
+        let mut selected = ToolRegistry::new();
+        for tool in &definition.tools {
+            let tool = tools.get(tool).ok_or_else(|| {
+                PromptError::Load(format!("agent '{}' uses unknown tool '{}'", name, tool))
+            })?;
+            selected.register_arc(tool.clone());
+        }
+        Ok(builder.tools(selected))
+    }
+}
+
+fn sorted_keys<V>(map: &HashMap<String, V>) -> Vec<&str> {
+    let mut keys: Vec<&str> = map.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    keys
+}
+
+fn canonical(path: &Path) -> PromptResult<PathBuf> {
+    path.canonicalize()
+        .map_err(|e| load_error(path, e.to_string()))
+}
+
+fn load_error(path: &Path, message: String) -> PromptError {
+    PromptError::Load(format!("{}: {}", path.display(), message))
+}
+
+/// Whether files with this extension can be loaded in this build
+fn supported(extension: &str) -> bool {
+    extension == "json"
+        || (extension == "toml" && cfg!(feature = "toml"))
+        || (matches!(extension, "yaml" | "yml") && cfg!(feature = "yaml"))
+}
+
+fn prompt_files(dir: &Path) -> PromptResult<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let entries = std::fs::read_dir(dir).map_err(|e| load_error(dir, e.to_string()))?;
+    for entry in entries {
+        let path = entry.map_err(|e| load_error(dir, e.to_string()))?.path();
+        if path.is_dir() {
+            files.extend(prompt_files(&path)?);
+        } else if path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(supported)
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn parse(path: &Path, text: &str) -> PromptResult<serde_json::Value> {
+    parse_value(path, text).map_err(|message| load_error(path, message))
+}
+
+/// Parse a JSON, TOML, or YAML file by its extension
+pub(crate) fn parse_value(path: &Path, text: &str) -> Result<serde_json::Value, String> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    match extension {
+        "json" => serde_json::from_str(text).map_err(|e| e.to_string()),
+        #[cfg(feature = "toml")]
+        "toml" => toml::from_str(text).map_err(|e| e.to_string()),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => serde_yaml::from_str(text).map_err(|e| e.to_string()),
+        _ => Err(format!("unsupported file type '{}'", extension)),
+    }
+}
+
+/// Replace `${VAR}` and `${VAR:-default}` in every string with the environment
+pub(crate) fn interpolate_env(value: &mut serde_json::Value) -> PromptResult<()> {
+    match value {
+        serde_json::Value::String(text) => *text = interpolate(text)?,
+        serde_json::Value::Array(items) => {
+            for item in items {
+                interpolate_env(item)?;
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values_mut() {
+                interpolate_env(field)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn interpolate(text: &str) -> PromptResult<String> {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let expression = &rest[start + 2..start + end];
+        let (name, default) = match expression.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (expression, None),
+        };
+        match (std::env::var(name), default) {
+            (Ok(value), _) => output.push_str(&value),
+            (Err(_), Some(default)) => output.push_str(default),
+            (Err(_), None) => return Err(PromptError::MissingVariable(name.to_string())),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
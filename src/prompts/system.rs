@@ -0,0 +1,153 @@
+//! System prompt assembly.
+
+use crate::agents::Persona;
+use crate::tools::ToolRegistry;
+use chrono::NaiveDate;
+
+/// Position of the identity section
+pub const IDENTITY_ORDER: i32 = 0;
+/// Default position of custom sections, after the identity
+pub const SECTION_ORDER: i32 = 100;
+/// Position of the generated tool instructions
+pub const TOOLS_ORDER: i32 = 200;
+/// Position of the current date
+pub const DATE_ORDER: i32 = 300;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Section {
+    order: i32,
+    title: Option<String>,
+    body: String,
+}
+
+/// Composable builder for agent system prompts
+///
+/// Sections are rendered in ascending order, and in insertion order among
+/// sections with the same order: the identity first, then custom sections, the
+/// tool instructions, and the current date.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::prompts::SystemPromptBuilder;
+/// use agentic_optio_rs::tools::{FunctionTool, ToolRegistry};
+/// use chrono::NaiveDate;
+///
+/// let mut tools = ToolRegistry::new();
+/// tools.register(FunctionTool::new("search", "Search the docs", |_| async {
+///     Ok(String::new())
+/// }));
+///
+/// let prompt = SystemPromptBuilder::new()
+///     .identity("You are Optio, a support assistant.")
+///     .section("Style", "Answer in at most three sentences.")
+///     .tools(&tools)
+///     .date(NaiveDate::from_ymd_opt(2025, 3, 14).unwrap())
+///     .build();
+///
+/// assert!(prompt.starts_with("You are Optio"));
+/// assert!(prompt.contains("- search: Search the docs"));
+/// assert!(prompt.ends_with("Current date: 2025-03-14"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SystemPromptBuilder {
+    sections: Vec<Section>,
+}
+
+impl SystemPromptBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Who the agent is, rendered first without a title
+    pub fn identity(self, identity: impl Into<String>) -> Self {
+        self.push(IDENTITY_ORDER, None, identity.into())
+    }
+
+    /// Use a persona's rendered prompt as the identity
+    pub fn persona(self, persona: &Persona) -> Self {
+        self.identity(persona.system_prompt())
+    }
+
+    /// Titled section placed after the identity
+    pub fn section(self, title: impl Into<String>, body: impl Into<String>) -> Self {
+        self.section_at(SECTION_ORDER, title, body)
+    }
+
+    /// Titled section at an explicit position; see the `*_ORDER` constants
+    pub fn section_at(self, order: i32, title: impl Into<String>, body: impl Into<String>) -> Self {
+        self.push(order, Some(title.into()), body.into())
+    }
+
+    /// Instructions listing each tool with its arguments and description
+    ///
+    /// Nothing is added for an empty registry.
+    pub fn tools(self, tools: &ToolRegistry) -> Self {
+        if tools.is_empty() {
+            return self;
+        }
+        let mut body =
+            String::from("Call a tool when it helps answer the request. Available tools:");
+        for tool in tools.tools() {
+            let parameters = tool.parameters();
+            let required: Vec<&str> = parameters["required"]
+                .as_array()
+                .map(|names| names.iter().filter_map(|n| n.as_str()).collect())
+                .unwrap_or_default();
+            let arguments: Vec<String> = parameters["properties"]
+                .as_object()
+                .map(|properties| {
+                    properties
+                        .keys()
+                        .map(|name| {
+                            if required.contains(&name.as_str()) {
+                                name.clone()
+                            } else {
+                                format!("{}?", name)
+                            }
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            if arguments.is_empty() {
+                body.push_str(&format!("\n- {}: {}", tool.name(), tool.description()));
+            } else {
+                body.push_str(&format!(
+                    "\n- {}({}): {}",
+                    tool.name(),
+                    arguments.join(", "),
+                    tool.description()
+                ));
+            }
+        }
+        self.push(TOOLS_ORDER, Some("Tools".to_string()), body)
+    }
+
+    /// Today's date in UTC
+    pub fn current_date(self) -> Self {
+        self.date(chrono::Utc::now().date_naive())
+    }
+
+    /// A fixed date, for reproducible prompts
+    pub fn date(self, date: NaiveDate) -> Self {
+        self.push(DATE_ORDER, None, format!("Current date: {}", date))
+    }
+
+    fn push(mut self, order: i32, title: Option<String>, body: String) -> Self {
+        self.sections.push(Section { order, title, body });
+        self
+    }
+
+    pub fn build(mut self) -> String {
+        // Stable sort keeps insertion order within an order
+        self.sections.sort_by_key(|section| section.order);
+        self.sections
+            .into_iter()
+            .map(|section| match section.title {
+                Some(title) => format!("{}:\n{}", title, section.body),
+                None => section.body,
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
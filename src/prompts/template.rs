@@ -0,0 +1,154 @@
+//! String prompt templates.
+
+use crate::prompts::{PromptError, PromptResult};
+use std::collections::HashMap;
+
+/// Text template with `{variable}` placeholders
+///
+/// Variable names are letters, digits, and underscores; write `{{` and `}}` for
+/// literal braces. Formatting fails if any variable is left unfilled, and
+/// values that aren't variables of the template are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::prompts::PromptTemplate;
+///
+/// let template = PromptTemplate::new("Translate to {language}: {text}")?
+///     .partial("language", "French");
+///
+/// assert_eq!(template.input_variables(), vec!["text"]);
+/// assert_eq!(
+///     template.format([("text", "Good morning")])?,
+///     "Translate to French: Good morning"
+/// );
+/// assert!(template.format([("txt", "typo")]).is_err());
+/// # Ok::<(), agentic_optio_rs::prompts::PromptError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptTemplate {
+    template: String,
+    segments: Vec<Segment>,
+    partials: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Variable(String),
+}
+
+impl PromptTemplate {
+    /// Parse a template, rejecting unbalanced braces and invalid variable names
+    pub fn new(template: impl Into<String>) -> PromptResult<Self> {
+        let template = template.into();
+        let segments = parse(&template)?;
+        Ok(Self {
+            template,
+            segments,
+            partials: HashMap::new(),
+        })
+    }
+
+    /// Fix a variable's value ahead of formatting
+    pub fn partial(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.partials.insert(name.into(), value.to_string());
+        self
+    }
+
+    /// Template source text
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Variables still to be supplied to [`format`](Self::format), in order of
+    /// first appearance
+    pub fn input_variables(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for segment in &self.segments {
+            if let Segment::Variable(name) = segment {
+                if !self.partials.contains_key(name) && !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Render the template; values override partials of the same name
+    pub fn format<I, K, V>(&self, values: I) -> PromptResult<String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: ToString,
+    {
+        let values: HashMap<String, String> = values
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.to_string()))
+            .collect();
+
+        let mut output = String::with_capacity(self.template.len());
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Variable(name) => {
+                    let value = values
+                        .get(name)
+                        .or_else(|| self.partials.get(name))
+                        .ok_or_else(|| PromptError::MissingVariable(name.clone()))?;
+                    output.push_str(value);
+                }
+            }
+        }
+        Ok(output)
+    }
+}
+
+fn parse(template: &str) -> PromptResult<Vec<Segment>> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut chars = template.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        match c {
+            '{' if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let rest = &template[position + 1..];
+                let end = rest.find('}').ok_or_else(|| {
+                    PromptError::Parse(format!("unclosed '{{' at byte {}", position))
+                })?;
+                let name = rest[..end].trim();
+                if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(PromptError::Parse(format!(
+                        "invalid variable name '{}' at byte {}",
+                        &rest[..end],
+                        position
+                    )));
+                }
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(Segment::Variable(name.to_string()));
+                while chars.next_if(|(i, _)| *i <= position + 1 + end).is_some() {}
+            }
+            '}' => {
+                return Err(PromptError::Parse(format!(
+                    "unmatched '}}' at byte {}",
+                    position
+                )));
+            }
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    Ok(segments)
+}
@@ -0,0 +1,189 @@
+//! BM25 lexical retrieval.
+
+use crate::core::documents::Document;
+use crate::embeddings::select_top_k;
+use crate::retrievers::{Retriever, RetrieverResult};
+use async_trait::async_trait;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// In-memory Okapi BM25 index
+///
+/// Text is lowercased and split into word tokens, so identifiers such as
+/// `ERR_4012` stay whole and match exactly.
+#[derive(Debug, Clone)]
+pub struct Bm25Index {
+    documents: Vec<Document>,
+    term_freqs: Vec<HashMap<String, usize>>,
+    lengths: Vec<usize>,
+    doc_freqs: HashMap<String, usize>,
+    total_length: usize,
+    k1: f32,
+    b: f32,
+}
+
+impl Default for Bm25Index {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bm25Index {
+    /// Empty index with the usual parameters (k1 = 1.2, b = 0.75)
+    pub fn new() -> Self {
+        Self {
+            documents: Vec::new(),
+            term_freqs: Vec::new(),
+            lengths: Vec::new(),
+            doc_freqs: HashMap::new(),
+            total_length: 0,
+            k1: 1.2,
+            b: 0.75,
+        }
+    }
+
+    /// Index the given documents
+    pub fn from_documents(documents: Vec<Document>) -> Self {
+        let mut index = Self::new();
+        index.add_documents(documents);
+        index
+    }
+
+    /// Term frequency saturation
+    pub fn k1(mut self, k1: f32) -> Self {
+        self.k1 = k1;
+        self
+    }
+
+    /// Document length normalization, from 0 (none) to 1 (full)
+    pub fn b(mut self, b: f32) -> Self {
+        self.b = b;
+        self
+    }
+
+    pub fn add_documents(&mut self, documents: Vec<Document>) {
+        for document in documents {
+            let tokens = tokenize(&document.content);
+            let mut freqs = HashMap::new();
+            for token in &tokens {
+                *freqs.entry(token.clone()).or_insert(0) += 1;
+            }
+            for term in freqs.keys() {
+                *self.doc_freqs.entry(term.clone()).or_insert(0) += 1;
+            }
+            self.total_length += tokens.len();
+            self.lengths.push(tokens.len());
+            self.term_freqs.push(freqs);
+            self.documents.push(document);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Up to `k` documents sharing a term with the query, best first
+    pub fn search(&self, query: &str, k: usize) -> Vec<(Document, f32)> {
+        if self.documents.is_empty() {
+            return Vec::new();
+        }
+
+        let n = self.documents.len() as f32;
+        let avg_length = self.total_length as f32 / n;
+        let mut terms = tokenize(query);
+        terms.sort();
+        terms.dedup();
+
+        let scored = (0..self.documents.len()).filter_map(|i| {
+            let length_norm = 1.0 - self.b + self.b * self.lengths[i] as f32 / avg_length.max(1.0);
+            let score: f32 = terms
+                .iter()
+                .filter_map(|term| {
+                    let tf = *self.term_freqs[i].get(term)? as f32;
+                    let df = self.doc_freqs[term] as f32;
+                    let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+                    Some(idf * tf * (self.k1 + 1.0) / (tf + self.k1 * length_norm))
+                })
+                .sum();
+            (score > 0.0).then_some((i, score))
+        });
+
+        select_top_k(scored, k)
+            .into_iter()
+            .map(|(i, score)| (self.documents[i].clone(), score))
+            .collect()
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    static WORD: OnceLock<Regex> = OnceLock::new();
+    WORD.get_or_init(|| Regex::new(r"\w+").unwrap())
+        .find_iter(&text.to_lowercase())
+        .map(|m| m.as_str().to_string())
+        .collect()
+}
+
+/// Retrieves the top-k documents from a [`Bm25Index`]
+///
+/// Each returned document carries its BM25 score in `score` metadata.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::retrievers::{Bm25Retriever, Retriever};
+/// use agentic_optio_rs::Document;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let retriever = Bm25Retriever::from_documents(vec![
+///         Document::new("Error ERR_4012 means the token expired."),
+///         Document::new("Tokens are refreshed hourly."),
+///     ])
+///     .k(1);
+///     let docs = retriever.retrieve("what is ERR_4012").await?;
+///     assert!(docs[0].content.contains("ERR_4012"));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bm25Retriever {
+    index: Bm25Index,
+    k: usize,
+}
+
+impl Bm25Retriever {
+    pub fn new(index: Bm25Index) -> Self {
+        Self { index, k: 4 }
+    }
+
+    pub fn from_documents(documents: Vec<Document>) -> Self {
+        Self::new(Bm25Index::from_documents(documents))
+    }
+
+    /// Number of documents to return (default 4)
+    pub fn k(mut self, k: usize) -> Self {
+        self.k = k;
+        self
+    }
+
+    pub fn index(&self) -> &Bm25Index {
+        &self.index
+    }
+}
+
+#[async_trait]
+impl Retriever for Bm25Retriever {
+    async fn retrieve(&self, query: &str) -> RetrieverResult<Vec<Document>> {
+        Ok(self
+            .index
+            .search(query, self.k)
+            .into_iter()
+            .map(|(doc, score)| doc.with_metadata("score", score))
+            .collect())
+    }
+}
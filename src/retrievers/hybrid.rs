@@ -0,0 +1,123 @@
+//! Hybrid retrieval with reciprocal rank fusion.
+
+use crate::core::documents::Document;
+use crate::retrievers::{Retriever, RetrieverResult};
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Fuses several retrievers' rankings with reciprocal rank fusion (RRF)
+///
+/// Each document scores `sum(weight / (rrf_k + rank))` over the retrievers
+/// that returned it, so agreement between lexical and vector search rises to
+/// the top without calibrating their raw scores. Documents are matched by `id`,
+/// or by content when they have none. The fused score is stored in
+/// `hybrid_score` metadata.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::retrievers::{
+///     Bm25Retriever, HybridRetriever, Retriever, VectorStoreRetriever,
+/// };
+/// use agentic_optio_rs::vectorstores::{InMemoryVectorStore, VectorStore};
+/// use agentic_optio_rs::{Document, OllamaEmbedding};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let docs = vec![Document::new("ERR_4012: token expired")];
+///     let store = Arc::new(InMemoryVectorStore::new(Arc::new(OllamaEmbedding::new(
+///         "nomic-embed-text",
+///     ))));
+///     store.add_documents(docs.clone()).await?;
+///
+///     let hybrid = HybridRetriever::new()
+///         .retriever(Arc::new(Bm25Retriever::from_documents(docs).k(10)))
+///         .retriever(Arc::new(VectorStoreRetriever::new(store).k(10)))
+///         .k(4);
+///     let results = hybrid.retrieve("ERR_4012").await?;
+///     println!("{} documents", results.len());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct HybridRetriever {
+    retrievers: Vec<(Arc<dyn Retriever>, f32)>,
+    k: usize,
+    rrf_k: f32,
+}
+
+impl Default for HybridRetriever {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HybridRetriever {
+    pub fn new() -> Self {
+        Self {
+            retrievers: Vec::new(),
+            k: 4,
+            rrf_k: 60.0,
+        }
+    }
+
+    /// Add a retriever with weight 1.0
+    pub fn retriever(self, retriever: Arc<dyn Retriever>) -> Self {
+        self.weighted(retriever, 1.0)
+    }
+
+    /// Add a retriever whose ranks count `weight` times as much
+    pub fn weighted(mut self, retriever: Arc<dyn Retriever>, weight: f32) -> Self {
+        self.retrievers.push((retriever, weight));
+        self
+    }
+
+    /// Number of fused documents to return (default 4)
+    pub fn k(mut self, k: usize) -> Self {
+        self.k = k;
+        self
+    }
+
+    /// RRF rank offset; larger values flatten the gap between top ranks (default 60)
+    pub fn rrf_k(mut self, rrf_k: f32) -> Self {
+        self.rrf_k = rrf_k;
+        self
+    }
+}
+
+#[async_trait]
+impl Retriever for HybridRetriever {
+    async fn retrieve(&self, query: &str) -> RetrieverResult<Vec<Document>> {
+        let rankings = try_join_all(self.retrievers.iter().map(|(r, _)| r.retrieve(query))).await?;
+
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let mut fused: Vec<(Document, f32)> = Vec::new();
+        for (ranking, (_, weight)) in rankings.into_iter().zip(&self.retrievers) {
+            for (rank, document) in ranking.into_iter().enumerate() {
+                let score = weight / (self.rrf_k + rank as f32 + 1.0);
+                let key = document
+                    .id
+                    .clone()
+                    .unwrap_or_else(|| document.content.clone());
+                match positions.get(&key) {
+                    Some(&i) => fused[i].1 += score,
+                    None => {
+                        positions.insert(key, fused.len());
+                        fused.push((document, score));
+                    }
+                }
+            }
+        }
+
+        // Stable sort keeps first-seen order for ties
+        fused.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(fused
+            .into_iter()
+            .take(self.k)
+            .map(|(doc, score)| doc.with_metadata("hybrid_score", score))
+            .collect())
+    }
+}
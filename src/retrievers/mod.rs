@@ -0,0 +1,44 @@
+//! Retrievers for AgenticOptio.
+//!
+//! A [`Retriever`] turns a query into the documents most relevant to it. Chains
+//! such as [`RagChain`](crate::chains::RagChain) depend only on this trait, so
+//! any search backend can feed them.
+
+pub mod bm25;
+pub mod hybrid;
+pub mod parent_document;
+pub mod reranking;
+pub mod vector_store;
+
+pub use bm25::{Bm25Index, Bm25Retriever};
+pub use hybrid::HybridRetriever;
+pub use parent_document::{ContextHeaderFn, ParentDocumentRetriever};
+pub use reranking::RerankingRetriever;
+pub use vector_store::VectorStoreRetriever;
+
+use crate::core::documents::Document;
+use crate::models::base::ModelError;
+use crate::vectorstores::VectorStoreError;
+use async_trait::async_trait;
+
+/// Error type for retrieval
+#[derive(Debug, thiserror::Error)]
+pub enum RetrieverError {
+    #[error("Vector store error: {0}")]
+    VectorStore(#[from] VectorStoreError),
+
+    #[error("Model error: {0}")]
+    Model(#[from] ModelError),
+
+    #[error("Retrieval failed: {0}")]
+    Other(String),
+}
+
+pub type RetrieverResult<T> = Result<T, RetrieverError>;
+
+/// Base trait for all retrievers
+#[async_trait]
+pub trait Retriever: Send + Sync {
+    /// Documents relevant to `query`, most relevant first
+    async fn retrieve(&self, query: &str) -> RetrieverResult<Vec<Document>>;
+}
@@ -0,0 +1,259 @@
+//! Parent-document retriever.
+
+use crate::core::documents::Document;
+use crate::retrievers::{Retriever, RetrieverResult};
+use crate::text_splitter::TextSplitter;
+use crate::vectorstores::{document_id, MetadataFilter, VectorStore};
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+/// Builds the context header prepended to each chunk of a parent
+pub type ContextHeaderFn = Arc<dyn Fn(&Document) -> Option<String> + Send + Sync>;
+
+/// Indexes small chunks for precise matching but returns their larger parents
+///
+/// Documents added through [`ParentDocumentRetriever::add_documents`] are
+/// optionally cut into parent sections, each parent is split into child chunks,
+/// and only the children are embedded. A query matches children and returns
+/// their parents, deduplicated and ranked by their best child, with the child
+/// similarity in `score` metadata. Children carry the parent's id in
+/// `parent_id` metadata.
+///
+/// With a [`context_header`](ParentDocumentRetriever::context_header), each
+/// chunk is embedded with a header describing its parent (title, source,
+/// section), so chunks that make little sense alone still match. Use
+/// [`return_chunks`](ParentDocumentRetriever::return_chunks) to get those
+/// contextualized chunks back instead of parents.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::retrievers::{ParentDocumentRetriever, Retriever};
+/// use agentic_optio_rs::text_splitter::RecursiveCharacterSplitter;
+/// use agentic_optio_rs::vectorstores::InMemoryVectorStore;
+/// use agentic_optio_rs::{Document, OllamaEmbedding};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = Arc::new(InMemoryVectorStore::new(Arc::new(OllamaEmbedding::new(
+///         "nomic-embed-text",
+///     ))));
+///     let retriever =
+///         ParentDocumentRetriever::new(store, Arc::new(RecursiveCharacterSplitter::new(200)))
+///             .parent_splitter(Arc::new(RecursiveCharacterSplitter::new(2000)))
+///             .context_header(|parent| {
+///                 parent.metadata.get("title").map(|t| format!("From: {}", t))
+///             });
+///     retriever
+///         .add_documents(vec![Document::new(std::fs::read_to_string("manual.txt")?)
+///             .with_metadata("title", "User manual")])
+///         .await?;
+///
+///     let sections = retriever.retrieve("How do I reset the device?").await?;
+///     println!("{} sections", sections.len());
+///     Ok(())
+/// }
+/// ```
+pub struct ParentDocumentRetriever {
+    store: Arc<dyn VectorStore>,
+    child_splitter: Arc<dyn TextSplitter>,
+    parent_splitter: Option<Arc<dyn TextSplitter>>,
+    context_header: Option<ContextHeaderFn>,
+    return_chunks: bool,
+    k: usize,
+    fetch_k: usize,
+    filter: MetadataFilter,
+    parents: RwLock<Parents>,
+}
+
+/// Parent documents by id, with the ids of the chunks indexed for each
+#[derive(Default)]
+struct Parents {
+    documents: HashMap<String, Document>,
+    children: HashMap<String, Vec<String>>,
+}
+
+impl ParentDocumentRetriever {
+    /// Index chunks from `child_splitter` in `store`; each added document is a
+    /// single parent unless a [`parent_splitter`](Self::parent_splitter) is set
+    pub fn new(store: Arc<dyn VectorStore>, child_splitter: Arc<dyn TextSplitter>) -> Self {
+        Self {
+            store,
+            child_splitter,
+            parent_splitter: None,
+            context_header: None,
+            return_chunks: false,
+            k: 4,
+            fetch_k: 20,
+            filter: MetadataFilter::default(),
+            parents: RwLock::new(Parents::default()),
+        }
+    }
+
+    /// Cut added documents into parent sections before chunking
+    pub fn parent_splitter(mut self, splitter: Arc<dyn TextSplitter>) -> Self {
+        self.parent_splitter = Some(splitter);
+        self
+    }
+
+    /// Prepend a header generated from the parent to every chunk before it is
+    /// embedded; returning `None` leaves the chunk as is
+    pub fn context_header(
+        mut self,
+        header: impl Fn(&Document) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.context_header = Some(Arc::new(header));
+        self
+    }
+
+    /// Return the matching chunks, with their context headers, instead of
+    /// their parents
+    pub fn return_chunks(mut self, return_chunks: bool) -> Self {
+        self.return_chunks = return_chunks;
+        self
+    }
+
+    /// Number of documents to return (default 4)
+    pub fn k(mut self, k: usize) -> Self {
+        self.k = k;
+        self
+    }
+
+    /// Number of chunks to search before grouping by parent (default 20)
+    pub fn fetch_k(mut self, fetch_k: usize) -> Self {
+        self.fetch_k = fetch_k;
+        self
+    }
+
+    /// Only match chunks whose metadata, inherited from the parent, matches
+    pub fn filter(mut self, filter: MetadataFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Split, index and remember documents, returning the parent ids
+    ///
+    /// Re-adding a parent with the same id replaces its chunks.
+    pub async fn add_documents(&self, documents: Vec<Document>) -> RetrieverResult<Vec<String>> {
+        let parents: Vec<Document> = match &self.parent_splitter {
+            Some(splitter) => documents
+                .iter()
+                .flat_map(|document| {
+                    // Sections of a document with an id get stable derived ids
+                    let sections = splitter.split_documents(std::slice::from_ref(document));
+                    sections.into_iter().enumerate().map(|(i, mut section)| {
+                        section.id = document.id.as_ref().map(|id| format!("{}#{}", id, i));
+                        section
+                    })
+                })
+                .collect(),
+            None => documents,
+        };
+
+        let mut ids = Vec::with_capacity(parents.len());
+        let mut stale = Vec::new();
+        let mut children = Vec::new();
+        let mut indexed = Vec::with_capacity(parents.len());
+        for mut parent in parents {
+            let id = document_id(&parent);
+            parent.id = Some(id.clone());
+            if let Some(old) = self.parents.read().unwrap().children.get(&id) {
+                stale.extend(old.iter().cloned());
+            }
+
+            let header = self.context_header.as_ref().and_then(|f| f(&parent));
+            let mut child_ids = Vec::new();
+            for (n, mut child) in self
+                .child_splitter
+                .split_documents(std::slice::from_ref(&parent))
+                .into_iter()
+                .enumerate()
+            {
+                let child_id = format!("{}:{}", id, n);
+                if let Some(header) = &header {
+                    child.content = format!("{}\n\n{}", header, child.content);
+                }
+                child.id = Some(child_id.clone());
+                child
+                    .metadata
+                    .insert("parent_id".to_string(), id.clone().into());
+                child_ids.push(child_id);
+                children.push(child);
+            }
+            indexed.push((parent, child_ids));
+            ids.push(id);
+        }
+
+        // Chunks re-added under the same id are upserted; delete only the rest
+        let fresh: HashSet<&String> = children.iter().filter_map(|c| c.id.as_ref()).collect();
+        stale.retain(|id| !fresh.contains(id));
+        if !stale.is_empty() {
+            self.store.delete(&stale).await?;
+        }
+        self.store.add_documents(children).await?;
+
+        let mut stored = self.parents.write().unwrap();
+        for (parent, child_ids) in indexed {
+            let id = parent.id.clone().unwrap_or_default();
+            stored.children.insert(id.clone(), child_ids);
+            stored.documents.insert(id, parent);
+        }
+        Ok(ids)
+    }
+
+    /// Stored parent document by id
+    pub fn get(&self, id: &str) -> Option<Document> {
+        self.parents.read().unwrap().documents.get(id).cloned()
+    }
+
+    /// Remove parents and their indexed chunks
+    pub async fn delete(&self, ids: &[String]) -> RetrieverResult<()> {
+        let children: Vec<String> = {
+            let mut parents = self.parents.write().unwrap();
+            ids.iter()
+                .flat_map(|id| {
+                    parents.documents.remove(id);
+                    parents.children.remove(id).unwrap_or_default()
+                })
+                .collect()
+        };
+        if !children.is_empty() {
+            self.store.delete(&children).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Retriever for ParentDocumentRetriever {
+    async fn retrieve(&self, query: &str) -> RetrieverResult<Vec<Document>> {
+        let fetch_k = self.fetch_k.max(self.k);
+        let hits = self
+            .store
+            .similarity_search_with_filter(query, fetch_k, &self.filter)
+            .await?;
+
+        if self.return_chunks {
+            return Ok(hits
+                .into_iter()
+                .take(self.k)
+                .map(|(doc, score)| doc.with_metadata("score", score))
+                .collect());
+        }
+
+        let parents = self.parents.read().unwrap();
+        let mut seen = HashSet::new();
+        Ok(hits
+            .into_iter()
+            .filter_map(|(child, score)| {
+                let id = child.metadata.get("parent_id")?.as_str()?.to_string();
+                let parent = parents.documents.get(&id)?;
+                seen.insert(id)
+                    .then(|| parent.clone().with_metadata("score", score))
+            })
+            .take(self.k)
+            .collect())
+    }
+}
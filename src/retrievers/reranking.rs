@@ -0,0 +1,86 @@
+//! Two-stage retrieval with a reranker.
+
+use crate::core::documents::Document;
+use crate::models::base::BaseReranker;
+use crate::retrievers::{Retriever, RetrieverResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Reorders a base retriever's candidates with a [`BaseReranker`]
+///
+/// Fetch generously from the base retriever (say k = 20) and let the reranker
+/// keep the best `top_n`. Each returned document carries its reranker score in
+/// `rerank_score` metadata.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::models::LlmReranker;
+/// use agentic_optio_rs::retrievers::{Retriever, RerankingRetriever, VectorStoreRetriever};
+/// use agentic_optio_rs::vectorstores::InMemoryVectorStore;
+/// use agentic_optio_rs::{OllamaChat, OllamaEmbedding};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = Arc::new(InMemoryVectorStore::new(Arc::new(OllamaEmbedding::new(
+///         "nomic-embed-text",
+///     ))));
+///     let retriever = RerankingRetriever::new(
+///         Arc::new(VectorStoreRetriever::new(store).k(20)),
+///         Arc::new(LlmReranker::new(Arc::new(OllamaChat::new("llama3.2")))),
+///     )
+///     .top_n(4);
+///     let docs = retriever.retrieve("refund policy").await?;
+///     println!("{} documents", docs.len());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RerankingRetriever {
+    base: Arc<dyn Retriever>,
+    reranker: Arc<dyn BaseReranker>,
+    top_n: usize,
+    min_score: Option<f32>,
+}
+
+impl RerankingRetriever {
+    pub fn new(base: Arc<dyn Retriever>, reranker: Arc<dyn BaseReranker>) -> Self {
+        Self {
+            base,
+            reranker,
+            top_n: 4,
+            min_score: None,
+        }
+    }
+
+    /// Number of documents kept after reranking (default 4)
+    pub fn top_n(mut self, top_n: usize) -> Self {
+        self.top_n = top_n;
+        self
+    }
+
+    /// Drop documents the reranker scores below this
+    pub fn min_score(mut self, min_score: f32) -> Self {
+        self.min_score = Some(min_score);
+        self
+    }
+}
+
+#[async_trait]
+impl Retriever for RerankingRetriever {
+    async fn retrieve(&self, query: &str) -> RetrieverResult<Vec<Document>> {
+        let candidates = self.base.retrieve(query).await?;
+        if candidates.is_empty() {
+            return Ok(candidates);
+        }
+
+        let ranked = self.reranker.rerank(query, candidates).await?;
+        Ok(ranked
+            .into_iter()
+            .filter(|(_, score)| self.min_score.map_or(true, |min| *score >= min))
+            .take(self.top_n)
+            .map(|(doc, score)| doc.with_metadata("rerank_score", score))
+            .collect())
+    }
+}
@@ -0,0 +1,106 @@
+//! Vector store retriever.
+
+use crate::core::documents::Document;
+use crate::retrievers::{Retriever, RetrieverResult};
+use crate::vectorstores::{MetadataFilter, MmrParams, VectorStore};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Retrieves the top-k nearest documents from a [`VectorStore`]
+///
+/// Each returned document carries its similarity in `score` metadata, except in
+/// MMR mode, which returns documents in selection order without scores.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::retrievers::{Retriever, VectorStoreRetriever};
+/// use agentic_optio_rs::vectorstores::{InMemoryVectorStore, MetadataFilter};
+/// use agentic_optio_rs::OllamaEmbedding;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = Arc::new(InMemoryVectorStore::new(Arc::new(OllamaEmbedding::new(
+///         "nomic-embed-text",
+///     ))));
+///     let retriever = VectorStoreRetriever::new(store)
+///         .k(3)
+///         .filter(MetadataFilter::new().eq("lang", "en"));
+///     let docs = retriever.retrieve("How do I reset my password?").await?;
+///     println!("{} documents", docs.len());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct VectorStoreRetriever {
+    store: Arc<dyn VectorStore>,
+    k: usize,
+    filter: MetadataFilter,
+    score_threshold: Option<f32>,
+    /// `(fetch_k, lambda)` when using maximal marginal relevance
+    mmr: Option<(usize, f32)>,
+}
+
+impl VectorStoreRetriever {
+    pub fn new(store: Arc<dyn VectorStore>) -> Self {
+        Self {
+            store,
+            k: 4,
+            filter: MetadataFilter::default(),
+            score_threshold: None,
+            mmr: None,
+        }
+    }
+
+    /// Number of documents to return (default 4)
+    pub fn k(mut self, k: usize) -> Self {
+        self.k = k;
+        self
+    }
+
+    /// Only return documents matching this metadata filter
+    pub fn filter(mut self, filter: MetadataFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Drop documents scoring below this similarity; ignored in MMR mode
+    pub fn score_threshold(mut self, threshold: f32) -> Self {
+        self.score_threshold = Some(threshold);
+        self
+    }
+
+    /// Diversify results with maximal marginal relevance over the nearest
+    /// `fetch_k` documents; see [`MmrParams`]
+    pub fn mmr(mut self, fetch_k: usize, lambda: f32) -> Self {
+        self.mmr = Some((fetch_k, lambda));
+        self
+    }
+}
+
+#[async_trait]
+impl Retriever for VectorStoreRetriever {
+    async fn retrieve(&self, query: &str) -> RetrieverResult<Vec<Document>> {
+        if let Some((fetch_k, lambda)) = self.mmr {
+            let params = MmrParams::new(self.k)
+                .fetch_k(fetch_k)
+                .lambda(lambda)
+                .filter(self.filter.clone());
+            return Ok(self
+                .store
+                .max_marginal_relevance_search(query, &params)
+                .await?);
+        }
+
+        let results = self
+            .store
+            .similarity_search_with_filter(query, self.k, &self.filter)
+            .await?;
+        Ok(results
+            .into_iter()
+            .filter(|(_, score)| self.score_threshold.map_or(true, |t| *score >= t))
+            .map(|(doc, score)| doc.with_metadata("score", score))
+            .collect())
+    }
+}
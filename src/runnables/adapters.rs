@@ -0,0 +1,119 @@
+//! Runnable implementations for prompts, models, parsers, and retrievers.
+
+use crate::chains::{Classification, ClassificationChain};
+use crate::core::documents::Document;
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::BaseChatModel;
+use crate::output_parsers::{
+    CodeBlockParser, JsonParser, LabelParser, ListParser, MarkdownTable, MarkdownTableParser,
+    OutputParser, ParsedChatModel, RegexParser, XmlTagParser,
+};
+use crate::prompts::{ChatPromptTemplate, PromptTemplate, PromptValues};
+use crate::retrievers::Retriever;
+use crate::runnables::{Runnable, RunnableResult};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+#[async_trait]
+impl Runnable<HashMap<String, String>, String> for PromptTemplate {
+    async fn run(&self, input: HashMap<String, String>) -> RunnableResult<String> {
+        Ok(self.format(input)?)
+    }
+}
+
+#[async_trait]
+impl Runnable<PromptValues, Vec<Message>> for ChatPromptTemplate {
+    async fn run(&self, input: PromptValues) -> RunnableResult<Vec<Message>> {
+        Ok(self.format_messages(&input)?)
+    }
+}
+
+#[async_trait]
+impl<M> Runnable<Vec<Message>, AIMessage> for M
+where
+    M: BaseChatModel + ?Sized,
+{
+    async fn run(&self, input: Vec<Message>) -> RunnableResult<AIMessage> {
+        Ok(self.invoke(&input).await?)
+    }
+}
+
+#[async_trait]
+impl<T: DeserializeOwned + Send + 'static> Runnable<AIMessage, T> for JsonParser<T> {
+    async fn run(&self, input: AIMessage) -> RunnableResult<T> {
+        Ok(self.parse(&input.content)?)
+    }
+}
+
+#[async_trait]
+impl Runnable<AIMessage, Vec<String>> for ListParser {
+    async fn run(&self, input: AIMessage) -> RunnableResult<Vec<String>> {
+        Ok(self.parse(&input.content)?)
+    }
+}
+
+#[async_trait]
+impl Runnable<AIMessage, HashMap<String, String>> for RegexParser {
+    async fn run(&self, input: AIMessage) -> RunnableResult<HashMap<String, String>> {
+        Ok(self.parse(&input.content)?)
+    }
+}
+
+#[async_trait]
+impl Runnable<AIMessage, String> for XmlTagParser {
+    async fn run(&self, input: AIMessage) -> RunnableResult<String> {
+        Ok(self.parse(&input.content)?)
+    }
+}
+
+#[async_trait]
+impl Runnable<AIMessage, MarkdownTable> for MarkdownTableParser {
+    async fn run(&self, input: AIMessage) -> RunnableResult<MarkdownTable> {
+        Ok(self.parse(&input.content)?)
+    }
+}
+
+#[async_trait]
+impl Runnable<AIMessage, String> for CodeBlockParser {
+    async fn run(&self, input: AIMessage) -> RunnableResult<String> {
+        Ok(self.parse(&input.content)?)
+    }
+}
+
+#[async_trait]
+impl<T: Clone + Send + Sync> Runnable<AIMessage, T> for LabelParser<T> {
+    async fn run(&self, input: AIMessage) -> RunnableResult<T> {
+        Ok(self.parse(&input.content)?)
+    }
+}
+
+#[async_trait]
+impl<T> Runnable<Vec<Message>, T> for ParsedChatModel<T>
+where
+    T: Send + Sync,
+{
+    async fn run(&self, input: Vec<Message>) -> RunnableResult<T> {
+        Ok(self.invoke_parsed(&input).await?)
+    }
+}
+
+#[async_trait]
+impl<R> Runnable<String, Vec<Document>> for R
+where
+    R: Retriever + ?Sized,
+{
+    async fn run(&self, input: String) -> RunnableResult<Vec<Document>> {
+        Ok(self.retrieve(&input).await?)
+    }
+}
+
+#[async_trait]
+impl<T> Runnable<String, Classification<T>> for ClassificationChain<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    async fn run(&self, input: String) -> RunnableResult<Classification<T>> {
+        Ok(self.classify(&input).await?)
+    }
+}
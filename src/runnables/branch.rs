@@ -0,0 +1,171 @@
+//! Conditional routing between runnables.
+
+use crate::runnables::{Pipeline, Runnable, RunnableError, RunnableResult};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+type Predicate<I> = dyn Fn(&I) -> bool + Send + Sync;
+
+enum Case<I> {
+    When(Arc<Predicate<I>>),
+    Label(String),
+}
+
+impl<I> Clone for Case<I> {
+    fn clone(&self) -> Self {
+        match self {
+            Case::When(predicate) => Case::When(predicate.clone()),
+            Case::Label(label) => Case::Label(label.clone()),
+        }
+    }
+}
+
+/// Runnable sending its input to the first branch whose case matches, or to
+/// the default
+///
+/// A case is either a predicate on the input or a label compared, ignoring
+/// case, with the output of a classifier runnable such as a
+/// [`ClassificationChain`](crate::chains::ClassificationChain). Cases are
+/// tried in the order they were added, and the classifier runs at most once,
+/// only when a labeled case is reached.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::chains::ClassificationChain;
+/// use agentic_optio_rs::runnables::{lambda, Branch, Runnable, RunnableResult};
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let llm = Arc::new(OllamaChat::new("llama3.2"));
+///     let answer = |system: &'static str| {
+///         let llm = llm.clone();
+///         lambda(move |question: String| {
+///             let llm = llm.clone();
+///             async move {
+///                 let messages = [Message::system(system), Message::user(question)];
+///                 RunnableResult::Ok(llm.invoke(&messages).await?.content)
+///             }
+///         })
+///     };
+///     let topic = ClassificationChain::new(llm.clone())
+///         .label("math", "Arithmetic and equations", ())
+///         .label("code", "Programming questions", ())
+///         .map(|classification| classification.label);
+///
+///     let chain = Branch::new(answer("You are a helpful assistant."))
+///         .when(|question: &String| question.trim().is_empty(), lambda(|_: String| async {
+///             RunnableResult::Ok("Please ask a question.".to_string())
+///         }))
+///         .classifier(topic)
+///         .when_label("math", answer("Solve the problem step by step."))
+///         .when_label("code", answer("Answer with a short code example."));
+///     println!("{}", chain.run("What is 17 * 23?".to_string()).await?);
+///     Ok(())
+/// }
+/// ```
+pub struct Branch<I, O> {
+    cases: Vec<(Case<I>, Pipeline<I, O>)>,
+    classifier: Option<Pipeline<I, String>>,
+    default: Pipeline<I, O>,
+}
+
+impl<I, O> Branch<I, O> {
+    /// Branch sending everything to `default` until cases are added
+    pub fn new(default: impl Runnable<I, O> + 'static) -> Self {
+        Self {
+            cases: Vec::new(),
+            classifier: None,
+            default: Pipeline::new(default),
+        }
+    }
+
+    /// Send input matching `predicate` to `runnable`
+    pub fn when(
+        mut self,
+        predicate: impl Fn(&I) -> bool + Send + Sync + 'static,
+        runnable: impl Runnable<I, O> + 'static,
+    ) -> Self {
+        self.cases
+            .push((Case::When(Arc::new(predicate)), Pipeline::new(runnable)));
+        self
+    }
+
+    /// Send input the classifier labels `label` to `runnable`
+    pub fn when_label(
+        mut self,
+        label: impl Into<String>,
+        runnable: impl Runnable<I, O> + 'static,
+    ) -> Self {
+        self.cases
+            .push((Case::Label(label.into()), Pipeline::new(runnable)));
+        self
+    }
+
+    /// Runnable labeling the input for [`when_label`](Self::when_label) cases
+    pub fn classifier(mut self, classifier: impl Runnable<I, String> + 'static) -> Self {
+        self.classifier = Some(Pipeline::new(classifier));
+        self
+    }
+}
+
+impl<I, O> Clone for Branch<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            cases: self.cases.clone(),
+            classifier: self.classifier.clone(),
+            default: self.default.clone(),
+        }
+    }
+}
+
+impl<I, O> std::fmt::Debug for Branch<I, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cases: Vec<String> = self
+            .cases
+            .iter()
+            .map(|(case, _)| match case {
+                Case::When(_) => "<predicate>".to_string(),
+                Case::Label(label) => label.clone(),
+            })
+            .collect();
+        f.debug_struct("Branch")
+            .field("cases", &cases)
+            .field("classifier", &self.classifier.is_some())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<I, O> Runnable<I, O> for Branch<I, O>
+where
+    I: Clone + Send + Sync + 'static,
+{
+    async fn run(&self, input: I) -> RunnableResult<O> {
+        let mut label: Option<String> = None;
+        for (case, runnable) in &self.cases {
+            let matched = match case {
+                Case::When(predicate) => predicate(&input),
+                Case::Label(expected) => {
+                    if label.is_none() {
+                        let classifier = self.classifier.as_ref().ok_or_else(|| {
+                            RunnableError::Other(
+                                "branch has labeled cases but no classifier".to_string(),
+                            )
+                        })?;
+                        label = Some(classifier.run(input.clone()).await?);
+                    }
+                    label
+                        .as_deref()
+                        .is_some_and(|label| label.trim().eq_ignore_ascii_case(expected))
+                }
+            };
+            if matched {
+                return runnable.run(input).await;
+            }
+        }
+        self.default.run(input).await
+    }
+}
@@ -0,0 +1,207 @@
+//! Runnables for AgenticOptio.
+//!
+//! A [`Runnable`] is one step of a pipeline: it takes an input and produces an
+//! output. Prompt templates, chat models, output parsers, and retrievers are
+//! all runnables, and [`then`](Runnable::then), [`map`](Runnable::map), and
+//! [`lambda`] chain them into reusable pipelines whose input and output types
+//! are checked at compile time. A [`Pipeline`] is a cloneable, type-erased
+//! runnable; pipelines compose with `|`. [`RunnableParallel`] and tuples of
+//! runnables fan one input out to several steps at once, and a [`Branch`]
+//! picks one of several steps by a predicate or a classifier.
+//!
+//! The crate's parsers take the model's [`AIMessage`](crate::AIMessage); a
+//! parser of your own chains through [`map`](Runnable::map).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use agentic_optio_rs::output_parsers::ListParser;
+//! use agentic_optio_rs::prompts::{ChatPromptTemplate, PromptValues};
+//! use agentic_optio_rs::runnables::Runnable;
+//! use agentic_optio_rs::OllamaChat;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let prompt = ChatPromptTemplate::new()
+//!         .system("Answer with a comma-separated list only.")?
+//!         .user("Name five {topic}.")?;
+//!     let chain = prompt
+//!         .then(OllamaChat::new("llama3.2"))
+//!         .then(ListParser::comma())
+//!         .map(|items| items.len());
+//!     let count = chain.run(PromptValues::new().set("topic", "rivers")).await?;
+//!     println!("{count} rivers");
+//!     Ok(())
+//! }
+//! ```
+
+mod adapters;
+pub mod branch;
+pub mod parallel;
+pub mod sequence;
+
+pub use branch::Branch;
+pub use parallel::RunnableParallel;
+pub use sequence::{lambda, Lambda, Map, Sequence};
+
+use crate::chains::ChainError;
+use crate::models::base::ModelError;
+use crate::output_parsers::ParseError;
+use crate::prompts::PromptError;
+use crate::retrievers::RetrieverError;
+use async_trait::async_trait;
+use std::ops::BitOr;
+use std::sync::Arc;
+
+/// Error type for running a pipeline
+#[derive(Debug, thiserror::Error)]
+pub enum RunnableError {
+    #[error("Prompt error: {0}")]
+    Prompt(#[from] PromptError),
+
+    #[error("Model error: {0}")]
+    Model(#[from] ModelError),
+
+    #[error("Parse error: {0}")]
+    Parse(#[from] ParseError),
+
+    #[error("Retrieval failed: {0}")]
+    Retriever(#[from] RetrieverError),
+
+    #[error("Chain error: {0}")]
+    Chain(#[from] ChainError),
+
+    /// A branch of a [`RunnableParallel`] failed
+    #[error("Branch '{name}' failed: {source}")]
+    Branch {
+        name: String,
+        source: Box<RunnableError>,
+    },
+
+    #[error("{0}")]
+    Other(String),
+}
+
+pub type RunnableResult<T> = Result<T, RunnableError>;
+
+/// A pipeline step turning an `I` into an `O`
+#[async_trait]
+pub trait Runnable<I, O>: Send + Sync {
+    async fn run(&self, input: I) -> RunnableResult<O>;
+
+    /// Pipeline feeding this step's output into `next`
+    fn then<P, R>(self, next: R) -> Sequence<Self, R, O>
+    where
+        Self: Sized,
+        R: Runnable<O, P>,
+    {
+        Sequence::new(self, next)
+    }
+
+    /// Pipeline applying `f` to this step's output
+    fn map<P, F>(self, f: F) -> Map<Self, F, O>
+    where
+        Self: Sized,
+        F: Fn(O) -> P + Send + Sync,
+    {
+        Map::new(self, f)
+    }
+
+    /// This step as a cloneable [`Pipeline`]
+    fn boxed(self) -> Pipeline<I, O>
+    where
+        Self: Sized + 'static,
+    {
+        Pipeline::new(self)
+    }
+}
+
+#[async_trait]
+impl<I, O, R> Runnable<I, O> for Arc<R>
+where
+    I: Send + 'static,
+    R: Runnable<I, O> + ?Sized,
+{
+    async fn run(&self, input: I) -> RunnableResult<O> {
+        self.as_ref().run(input).await
+    }
+}
+
+/// Cloneable, type-erased runnable from `I` to `O`
+///
+/// Pipelines compose with `|`: `a | b` runs `a`, then `b` on its output.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::output_parsers::XmlTagParser;
+/// use agentic_optio_rs::prompts::ChatPromptTemplate;
+/// use agentic_optio_rs::runnables::{Pipeline, Runnable};
+/// use agentic_optio_rs::OllamaChat;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let prompt =
+///     ChatPromptTemplate::new().user("Translate to French inside <answer> tags: {text}")?;
+/// let translate = prompt.boxed()
+///     | OllamaChat::new("llama3.2").boxed()
+///     | XmlTagParser::new("answer").boxed();
+/// # Ok(())
+/// # }
+/// ```
+pub struct Pipeline<I, O> {
+    inner: Arc<dyn Runnable<I, O>>,
+}
+
+impl<I, O> Pipeline<I, O> {
+    pub fn new(runnable: impl Runnable<I, O> + 'static) -> Self {
+        Self {
+            inner: Arc::new(runnable),
+        }
+    }
+}
+
+impl<I, O> Clone for Pipeline<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<I, O> From<Arc<dyn Runnable<I, O>>> for Pipeline<I, O> {
+    fn from(inner: Arc<dyn Runnable<I, O>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<I, O> std::fmt::Debug for Pipeline<I, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("input", &std::any::type_name::<I>())
+            .field("output", &std::any::type_name::<O>())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<I, O> Runnable<I, O> for Pipeline<I, O>
+where
+    I: Send + 'static,
+{
+    async fn run(&self, input: I) -> RunnableResult<O> {
+        self.inner.run(input).await
+    }
+}
+
+impl<I, M, O> BitOr<Pipeline<M, O>> for Pipeline<I, M>
+where
+    I: Send + 'static,
+    M: Send + 'static,
+    O: Send + 'static,
+{
+    type Output = Pipeline<I, O>;
+
+    fn bitor(self, next: Pipeline<M, O>) -> Pipeline<I, O> {
+        Pipeline::new(self.then(next))
+    }
+}
@@ -0,0 +1,175 @@
+//! Concurrent fan-out over one input.
+
+use crate::runnables::{Runnable, RunnableError, RunnableResult};
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Runnable running named branches concurrently on copies of the same input
+/// and collecting their outputs by name
+///
+/// The first branch to fail fails the whole run, and the others are dropped.
+/// For branches with different output types, run a tuple of runnables
+/// instead: `(a, b)` is itself a runnable producing `(A, B)`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::prompts::{ChatPromptTemplate, PromptValues};
+/// use agentic_optio_rs::runnables::{Runnable, RunnableParallel};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let llm = Arc::new(OllamaChat::new("llama3.2"));
+///     let task = |instruction: &str| -> Result<_, Box<dyn std::error::Error>> {
+///         let prompt = ChatPromptTemplate::new().system(instruction)?.user("{text}")?;
+///         Ok(prompt.then(llm.clone()).map(|reply| reply.content))
+///     };
+///     let analysis = RunnableParallel::new()
+///         .branch("summary", task("Summarize the text in one sentence.")?)
+///         .branch("entities", task("List the people and places in the text.")?)
+///         .branch("sentiment", task("Answer positive, negative, or neutral.")?);
+///
+///     let text = "Ada met Charles in London and loved his engine.";
+///     let results = analysis.run(PromptValues::new().set("text", text)).await?;
+///     println!("{}", results["summary"]);
+///     Ok(())
+/// }
+/// ```
+pub struct RunnableParallel<I, O> {
+    branches: Vec<(String, Arc<dyn Runnable<I, O>>)>,
+}
+
+impl<I, O> RunnableParallel<I, O> {
+    pub fn new() -> Self {
+        Self {
+            branches: Vec::new(),
+        }
+    }
+
+    /// Add a branch whose output is collected under `name`, replacing any
+    /// branch of the same name
+    pub fn branch(
+        mut self,
+        name: impl Into<String>,
+        runnable: impl Runnable<I, O> + 'static,
+    ) -> Self {
+        let name = name.into();
+        self.branches.retain(|(existing, _)| *existing != name);
+        self.branches.push((name, Arc::new(runnable)));
+        self
+    }
+
+    /// Branch names, in the order they were added
+    pub fn names(&self) -> Vec<&str> {
+        self.branches
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+impl<I, O> Default for RunnableParallel<I, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, O> Clone for RunnableParallel<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            branches: self.branches.clone(),
+        }
+    }
+}
+
+impl<I, O> std::fmt::Debug for RunnableParallel<I, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunnableParallel")
+            .field("branches", &self.names())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<I, O> Runnable<I, HashMap<String, O>> for RunnableParallel<I, O>
+where
+    I: Clone + Send + Sync + 'static,
+    O: Send,
+{
+    async fn run(&self, input: I) -> RunnableResult<HashMap<String, O>> {
+        let runs = self.branches.iter().map(|(name, runnable)| {
+            let input = input.clone();
+            async move {
+                match runnable.run(input).await {
+                    Ok(output) => Ok((name.clone(), output)),
+                    Err(e) => Err(RunnableError::Branch {
+                        name: name.clone(),
+                        source: Box::new(e),
+                    }),
+                }
+            }
+        });
+        Ok(try_join_all(runs).await?.into_iter().collect())
+    }
+}
+
+#[async_trait]
+impl<I, A, B, RA, RB> Runnable<I, (A, B)> for (RA, RB)
+where
+    I: Clone + Send + 'static,
+    A: Send,
+    B: Send,
+    RA: Runnable<I, A>,
+    RB: Runnable<I, B>,
+{
+    async fn run(&self, input: I) -> RunnableResult<(A, B)> {
+        futures::try_join!(self.0.run(input.clone()), self.1.run(input))
+    }
+}
+
+#[async_trait]
+impl<I, A, B, C, RA, RB, RC> Runnable<I, (A, B, C)> for (RA, RB, RC)
+where
+    I: Clone + Send + 'static,
+    A: Send,
+    B: Send,
+    C: Send,
+    RA: Runnable<I, A>,
+    RB: Runnable<I, B>,
+    RC: Runnable<I, C>,
+{
+    async fn run(&self, input: I) -> RunnableResult<(A, B, C)> {
+        futures::try_join!(
+            self.0.run(input.clone()),
+            self.1.run(input.clone()),
+            self.2.run(input)
+        )
+    }
+}
+
+#[async_trait]
+impl<I, A, B, C, D, RA, RB, RC, RD> Runnable<I, (A, B, C, D)> for (RA, RB, RC, RD)
+where
+    I: Clone + Send + 'static,
+    A: Send,
+    B: Send,
+    C: Send,
+    D: Send,
+    RA: Runnable<I, A>,
+    RB: Runnable<I, B>,
+    RC: Runnable<I, C>,
+    RD: Runnable<I, D>,
+{
+    async fn run(&self, input: I) -> RunnableResult<(A, B, C, D)> {
+        futures::try_join!(
+            self.0.run(input.clone()),
+            self.1.run(input.clone()),
+            self.2.run(input.clone()),
+            self.3.run(input)
+        )
+    }
+}
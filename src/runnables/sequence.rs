@@ -0,0 +1,128 @@
+//! Sequential composition of runnables.
+
+use crate::runnables::{Runnable, RunnableResult};
+use async_trait::async_trait;
+use std::future::Future;
+use std::marker::PhantomData;
+
+/// Runnable feeding the output of `first` into `second`, built by
+/// [`Runnable::then`]
+pub struct Sequence<A, B, M> {
+    first: A,
+    second: B,
+    _middle: PhantomData<fn() -> M>,
+}
+
+impl<A, B, M> Sequence<A, B, M> {
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            _middle: PhantomData,
+        }
+    }
+}
+
+impl<A: Clone, B: Clone, M> Clone for Sequence<A, B, M> {
+    fn clone(&self) -> Self {
+        Self::new(self.first.clone(), self.second.clone())
+    }
+}
+
+#[async_trait]
+impl<I, M, O, A, B> Runnable<I, O> for Sequence<A, B, M>
+where
+    I: Send + 'static,
+    M: Send + 'static,
+    A: Runnable<I, M>,
+    B: Runnable<M, O>,
+{
+    async fn run(&self, input: I) -> RunnableResult<O> {
+        let middle = self.first.run(input).await?;
+        self.second.run(middle).await
+    }
+}
+
+/// Runnable applying a function to the output of another, built by
+/// [`Runnable::map`]
+pub struct Map<R, F, O> {
+    runnable: R,
+    f: F,
+    _output: PhantomData<fn() -> O>,
+}
+
+impl<R, F, O> Map<R, F, O> {
+    pub fn new(runnable: R, f: F) -> Self {
+        Self {
+            runnable,
+            f,
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<R: Clone, F: Clone, O> Clone for Map<R, F, O> {
+    fn clone(&self) -> Self {
+        Self::new(self.runnable.clone(), self.f.clone())
+    }
+}
+
+#[async_trait]
+impl<I, O, P, R, F> Runnable<I, P> for Map<R, F, O>
+where
+    I: Send + 'static,
+    R: Runnable<I, O>,
+    F: Fn(O) -> P + Send + Sync,
+{
+    async fn run(&self, input: I) -> RunnableResult<P> {
+        let output = self.runnable.run(input).await?;
+        Ok((self.f)(output))
+    }
+}
+
+/// Runnable calling an async function, built by [`lambda`]
+#[derive(Clone)]
+pub struct Lambda<F> {
+    f: F,
+}
+
+/// Runnable from an async function, for steps such as formatting retrieved
+/// documents into prompt values
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::prompts::{ChatPromptTemplate, PromptValues};
+/// use agentic_optio_rs::retrievers::Retriever;
+/// use agentic_optio_rs::runnables::{lambda, Runnable, RunnableResult};
+/// use agentic_optio_rs::{Document, OllamaChat};
+///
+/// # fn chain(retriever: impl Retriever + 'static) -> Result<(), Box<dyn std::error::Error>> {
+/// let prompt = ChatPromptTemplate::new()
+///     .system("Answer from this context:\n{context}")?
+///     .user("What does the context say?")?;
+/// let chain = retriever
+///     .then(lambda(|documents: Vec<Document>| async move {
+///         let context: Vec<_> = documents.iter().map(|d| d.content.as_str()).collect();
+///         RunnableResult::Ok(PromptValues::new().set("context", context.join("\n\n")))
+///     }))
+///     .then(prompt)
+///     .then(OllamaChat::new("llama3.2"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn lambda<F>(f: F) -> Lambda<F> {
+    Lambda { f }
+}
+
+#[async_trait]
+impl<I, O, F, Fut> Runnable<I, O> for Lambda<F>
+where
+    I: Send + 'static,
+    F: Fn(I) -> Fut + Send + Sync,
+    Fut: Future<Output = RunnableResult<O>> + Send,
+{
+    async fn run(&self, input: I) -> RunnableResult<O> {
+        (self.f)(input).await
+    }
+}
@@ -0,0 +1,184 @@
+//! Conversation records.
+
+use crate::core::messages::{messages_to_dict, AIMessage, Message, ToolCall};
+use crate::sessions::{SessionError, SessionResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+/// Messages of one conversation, with metadata and bookkeeping for storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatHistory {
+    pub id: String,
+    pub messages: Vec<Message>,
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Times the history has been saved; 0 until it is first stored
+    #[serde(default)]
+    pub version: u64,
+}
+
+impl ChatHistory {
+    pub fn new(id: impl Into<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: id.into(),
+            messages: Vec::new(),
+            metadata: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+            version: 0,
+        }
+    }
+
+    pub fn push(&mut self, message: Message) {
+        self.messages.push(message);
+        self.updated_at = Utc::now();
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Write the conversation as one JSONL line, `{"messages": [...]}` in the
+    /// OpenAI chat format that fine-tuning APIs accept
+    ///
+    /// Call it for each history on the same writer to build a dataset. The
+    /// id, metadata, and timestamps are not written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use agentic_optio_rs::sessions::{InMemorySessionStore, SessionStore};
+    /// use std::fs::File;
+    /// use std::io::BufWriter;
+    ///
+    /// # async fn export(store: &InMemorySessionStore) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = BufWriter::new(File::create("train.jsonl")?);
+    /// for id in store.list().await? {
+    ///     if let Some(history) = store.load(&id).await? {
+    ///         history.export_jsonl(&mut file)?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_jsonl(&self, mut writer: impl Write) -> SessionResult<()> {
+        let line = serde_json::json!({ "messages": messages_to_dict(&self.messages) });
+        serde_json::to_writer(&mut writer, &line)?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Read conversations in the OpenAI chat format, one
+    /// `{"messages": [...]}` object per line
+    ///
+    /// Each conversation takes the line's `id` if it has one and a new random
+    /// id otherwise. Blank lines are skipped, `developer` messages become
+    /// system messages, and content given as a list of parts keeps its text.
+    pub fn import_jsonl(reader: impl BufRead) -> SessionResult<Vec<ChatHistory>> {
+        let mut histories = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid = |reason: String| SessionError::InvalidImport {
+                line: index + 1,
+                reason,
+            };
+            let record: Value = serde_json::from_str(&line).map_err(|e| invalid(e.to_string()))?;
+            let messages = record
+                .get("messages")
+                .and_then(Value::as_array)
+                .ok_or_else(|| invalid("missing \"messages\" array".to_string()))?;
+
+            let id = record
+                .get("id")
+                .and_then(Value::as_str)
+                .map_or_else(|| uuid::Uuid::new_v4().to_string(), str::to_string);
+            let mut history = ChatHistory::new(id);
+            for (position, message) in messages.iter().enumerate() {
+                let message = message_from_openai(message)
+                    .map_err(|reason| invalid(format!("message {}: {reason}", position + 1)))?;
+                history.messages.push(message);
+            }
+            histories.push(history);
+        }
+        Ok(histories)
+    }
+}
+
+/// Message from its OpenAI chat format, the inverse of [`Message::to_dict`]
+fn message_from_openai(message: &Value) -> Result<Message, String> {
+    let content = match message.get("content") {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(other) => return Err(format!("unsupported content {other}")),
+    };
+    match message.get("role").and_then(Value::as_str) {
+        Some("system" | "developer") => Ok(Message::system(content)),
+        Some("user") => Ok(Message::user(content)),
+        Some("assistant") => {
+            let tool_calls = match message.get("tool_calls") {
+                None | Some(Value::Null) => Vec::new(),
+                Some(Value::Array(calls)) => calls
+                    .iter()
+                    .map(tool_call_from_openai)
+                    .collect::<Result<_, _>>()?,
+                Some(_) => return Err("\"tool_calls\" is not an array".to_string()),
+            };
+            Ok(Message::AI(AIMessage::with_tool_calls(content, tool_calls)))
+        }
+        Some("tool") => {
+            let id = message
+                .get("tool_call_id")
+                .and_then(Value::as_str)
+                .ok_or("tool message without \"tool_call_id\"")?;
+            Ok(Message::tool(content, id))
+        }
+        Some(role) => Err(format!("unknown role '{role}'")),
+        None => Err("missing \"role\"".to_string()),
+    }
+}
+
+/// Tool call from its OpenAI format, keeping arguments that are not valid
+/// JSON as a string
+fn tool_call_from_openai(call: &Value) -> Result<ToolCall, String> {
+    let function = call
+        .get("function")
+        .ok_or("tool call without \"function\"")?;
+    let name = function
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or("tool call without a function name")?;
+    let args = match function.get("arguments") {
+        Some(Value::String(arguments)) => {
+            serde_json::from_str(arguments).unwrap_or_else(|_| Value::String(arguments.clone()))
+        }
+        Some(arguments) => arguments.clone(),
+        None => Value::Object(Default::default()),
+    };
+    Ok(ToolCall {
+        id: call
+            .get("id")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        name: name.to_string(),
+        args,
+    })
+}
@@ -0,0 +1,56 @@
+//! In-memory session store.
+
+use crate::sessions::{ChatHistory, SessionError, SessionResult, SessionStore};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Session store keeping histories in process memory, for tests and
+/// single-process apps
+#[derive(Debug, Default)]
+pub struct InMemorySessionStore {
+    sessions: Mutex<HashMap<String, ChatHistory>>,
+}
+
+impl InMemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl SessionStore for InMemorySessionStore {
+    async fn load(&self, id: &str) -> SessionResult<Option<ChatHistory>> {
+        Ok(self.sessions.lock().unwrap().get(id).cloned())
+    }
+
+    async fn save(&self, history: &mut ChatHistory) -> SessionResult<()> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let stored = sessions.get(&history.id).map_or(0, |h| h.version);
+        if stored != history.version {
+            return Err(SessionError::Conflict(history.id.clone()));
+        }
+        history.version += 1;
+        sessions.insert(history.id.clone(), history.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> SessionResult<()> {
+        self.sessions.lock().unwrap().remove(id);
+        Ok(())
+    }
+
+    async fn list(&self) -> SessionResult<Vec<String>> {
+        let mut ids: Vec<String> = self.sessions.lock().unwrap().keys().cloned().collect();
+        ids.sort();
+        Ok(ids)
+    }
+}
@@ -0,0 +1,77 @@
+//! Chat sessions for AgenticOptio.
+//!
+//! A [`ChatSession`] is one conversation with a model: it keeps the history,
+//! decides how much of it each request sends, and saves it to a
+//! [`SessionStore`] after every turn. A [`SessionManager`] creates and loads
+//! sessions by id, so an app only passes ids around. [`InMemorySessionStore`]
+//! keeps sessions for the life of the process; with the `sqlite` feature,
+//! `SqliteSessionStore` keeps them in a database file, and with the `redis`
+//! feature, `RedisSessionStore` shares them between server instances.
+
+pub mod history;
+pub mod memory;
+#[cfg(feature = "redis")]
+pub mod redis;
+pub mod session;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use history::ChatHistory;
+pub use memory::InMemorySessionStore;
+#[cfg(feature = "redis")]
+pub use redis::{RedisSessionStore, RedisSessionStoreBuilder};
+pub use session::{ChatSession, SessionManager, SessionMemory};
+#[cfg(feature = "sqlite")]
+pub use sqlite::{SqliteSessionStore, SqliteSessionStoreBuilder};
+
+use crate::models::base::ModelError;
+use async_trait::async_trait;
+
+/// Error type for session operations
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    #[error("Session not found: {0}")]
+    NotFound(String),
+
+    /// The session was saved by someone else since it was loaded
+    #[error("Session {0} was modified concurrently")]
+    Conflict(String),
+
+    #[error("Session store error: {0}")]
+    Backend(String),
+
+    #[error("Serialization failed: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Model error: {0}")]
+    Model(#[from] ModelError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A JSONL line that is not a conversation in the OpenAI messages format
+    #[error("Invalid conversation on line {line}: {reason}")]
+    InvalidImport { line: usize, reason: String },
+}
+
+pub type SessionResult<T> = Result<T, SessionError>;
+
+/// Persistent storage for chat histories
+///
+/// Saves are optimistic: [`save`](Self::save) only succeeds if the stored
+/// history still has the version the caller loaded, and bumps it, so two
+/// writers cannot silently overwrite each other's turns.
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    async fn load(&self, id: &str) -> SessionResult<Option<ChatHistory>>;
+
+    /// Store `history` and increment its version, or fail with
+    /// [`SessionError::Conflict`] if the stored version is not
+    /// `history.version`; version 0 means the session must not exist yet
+    async fn save(&self, history: &mut ChatHistory) -> SessionResult<()>;
+
+    async fn delete(&self, id: &str) -> SessionResult<()>;
+
+    /// Ids of every stored session
+    async fn list(&self) -> SessionResult<Vec<String>>;
+}
@@ -0,0 +1,207 @@
+//! Redis session store.
+//!
+//! Lets several server instances share sessions, so any of them can serve
+//! the next turn of a conversation. Enabled with the `redis` feature.
+
+use crate::cache::redis::escape_glob;
+use crate::sessions::{ChatHistory, SessionError, SessionResult, SessionStore};
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::time::Duration;
+
+/// Writes the history only if the stored version is the expected one, then
+/// sets or clears the expiry
+///
+/// KEYS[1] is the session key; ARGV is the expected version, the new
+/// version, the history JSON, and the time-to-live in milliseconds, 0 for
+/// none. Returns 1 if written and 0 on a version mismatch.
+const SAVE_SCRIPT: &str = r"
+local stored = tonumber(redis.call('HGET', KEYS[1], 'version') or '0')
+if stored ~= tonumber(ARGV[1]) then
+    return 0
+end
+redis.call('HSET', KEYS[1], 'version', ARGV[2], 'history', ARGV[3])
+if tonumber(ARGV[4]) > 0 then
+    redis.call('PEXPIRE', KEYS[1], ARGV[4])
+else
+    redis.call('PERSIST', KEYS[1])
+end
+return 1
+";
+
+/// Session store kept in Redis
+///
+/// Each session is a hash under `prefix` followed by the session id, holding
+/// its version and its history as JSON. Saves check and bump the version in
+/// a single server-side script, so concurrent writers from different
+/// instances get [`SessionError::Conflict`] instead of losing turns. With a
+/// time-to-live, a session expires once it has gone that long without being
+/// saved.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::sessions::{RedisSessionStore, SessionManager};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = RedisSessionStore::connect("redis://127.0.0.1/")
+///         .await?
+///         .ttl(Duration::from_secs(7 * 24 * 60 * 60))
+///         .build();
+///     let sessions = SessionManager::new(Arc::new(OllamaChat::new("llama3.2")), Arc::new(store));
+///
+///     let mut session = sessions.open("user-42").await?;
+///     session.send("Where did we leave off?").await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RedisSessionStore {
+    connection: ConnectionManager,
+    prefix: String,
+    ttl: Option<Duration>,
+}
+
+impl RedisSessionStore {
+    pub fn builder(connection: ConnectionManager) -> RedisSessionStoreBuilder {
+        RedisSessionStoreBuilder::new(connection)
+    }
+
+    /// Connect to the server at `url` and start a builder
+    pub async fn connect(url: &str) -> SessionResult<RedisSessionStoreBuilder> {
+        let client = redis::Client::open(url).map_err(backend_error)?;
+        let connection = ConnectionManager::new(client)
+            .await
+            .map_err(backend_error)?;
+        Ok(RedisSessionStoreBuilder::new(connection))
+    }
+
+    pub fn connection(&self) -> &ConnectionManager {
+        &self.connection
+    }
+
+    fn redis_key(&self, id: &str) -> String {
+        format!("{}{}", self.prefix, id)
+    }
+}
+
+#[async_trait]
+impl SessionStore for RedisSessionStore {
+    async fn load(&self, id: &str) -> SessionResult<Option<ChatHistory>> {
+        let mut connection = self.connection.clone();
+        let history: Option<String> = connection
+            .hget(self.redis_key(id), "history")
+            .await
+            .map_err(backend_error)?;
+        history
+            .map(|history| serde_json::from_str(&history))
+            .transpose()
+            .map_err(SessionError::from)
+    }
+
+    async fn save(&self, history: &mut ChatHistory) -> SessionResult<()> {
+        let mut saved = history.clone();
+        saved.version += 1;
+        let json = serde_json::to_string(&saved)?;
+        let ttl = self.ttl.map_or(0, |ttl| (ttl.as_millis() as u64).max(1));
+
+        let mut connection = self.connection.clone();
+        let written: i64 = redis::cmd("EVAL")
+            .arg(SAVE_SCRIPT)
+            .arg(1)
+            .arg(self.redis_key(&history.id))
+            .arg(history.version)
+            .arg(saved.version)
+            .arg(json)
+            .arg(ttl)
+            .query_async(&mut connection)
+            .await
+            .map_err(backend_error)?;
+        if written == 0 {
+            return Err(SessionError::Conflict(history.id.clone()));
+        }
+        history.version = saved.version;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> SessionResult<()> {
+        let mut connection = self.connection.clone();
+        connection
+            .del(self.redis_key(id))
+            .await
+            .map_err(backend_error)
+    }
+
+    /// Ids under this store's prefix, found with `SCAN`
+    async fn list(&self) -> SessionResult<Vec<String>> {
+        let mut connection = self.connection.clone();
+        let mut iter = connection
+            .scan_match::<_, String>(format!("{}*", escape_glob(&self.prefix)))
+            .await
+            .map_err(backend_error)?;
+        let mut ids = Vec::new();
+        while let Some(key) = iter.next_item().await {
+            if let Some(id) = key.strip_prefix(&self.prefix) {
+                ids.push(id.to_string());
+            }
+        }
+        ids.sort();
+        ids.dedup();
+        Ok(ids)
+    }
+}
+
+impl std::fmt::Debug for RedisSessionStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisSessionStore")
+            .field("prefix", &self.prefix)
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+fn backend_error(err: redis::RedisError) -> SessionError {
+    SessionError::Backend(err.to_string())
+}
+
+/// Builder for RedisSessionStore
+pub struct RedisSessionStoreBuilder {
+    connection: ConnectionManager,
+    prefix: String,
+    ttl: Option<Duration>,
+}
+
+impl RedisSessionStoreBuilder {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self {
+            connection,
+            prefix: "agentic_optio:session:".to_string(),
+            ttl: None,
+        }
+    }
+
+    /// Namespace prepended to every key (default: `agentic_optio:session:`)
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Expire sessions this long after they were last saved (default: never)
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn build(self) -> RedisSessionStore {
+        RedisSessionStore {
+            connection: self.connection,
+            prefix: self.prefix,
+            ttl: self.ttl,
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Chat sessions bound to a model and a store.
+
+use crate::core::messages::{AIMessage, Message};
+use crate::models::base::BaseChatModel;
+use crate::models::{TrimStrategy, TrimmingChatModel};
+use crate::sessions::{ChatHistory, SessionError, SessionResult, SessionStore};
+use std::sync::Arc;
+
+/// How much of a session's history each request sends
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SessionMemory {
+    /// The whole history
+    #[default]
+    Full,
+    /// Only the latest turns, each a user message with everything after it
+    LastTurns(usize),
+}
+
+impl SessionMemory {
+    /// The part of `messages` to send
+    fn window<'a>(&self, messages: &'a [Message]) -> &'a [Message] {
+        let SessionMemory::LastTurns(turns) = *self else {
+            return messages;
+        };
+        let starts: Vec<usize> = messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| matches!(m, Message::Human(_)))
+            .map(|(i, _)| i)
+            .collect();
+        match starts.len().checked_sub(turns.max(1)) {
+            Some(first) => &messages[starts[first]..],
+            None => messages,
+        }
+    }
+}
+
+/// Creates and loads [`ChatSession`]s sharing a model, a store, and settings
+///
+/// Requests send the system prompt followed by the part of the history the
+/// [`SessionMemory`] selects. If the model still rejects a request as too
+/// long, it is shortened with the trim strategy, dropping the oldest turns
+/// by default; the stored history is never shortened.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::sessions::{InMemorySessionStore, SessionManager, SessionMemory};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let sessions = SessionManager::new(
+///         Arc::new(OllamaChat::new("llama3.2")),
+///         Arc::new(InMemorySessionStore::new()),
+///     )
+///     .system_prompt("You are a friendly travel agent.")
+///     .memory(SessionMemory::LastTurns(20));
+///
+///     let mut session = sessions.create().await?;
+///     session.send("I want to visit Japan in April.").await?;
+///     let id = session.id().to_string();
+///
+///     // Later, in another request handler
+///     let mut session = sessions.load(&id).await?;
+///     let reply = session.send("What should I pack?").await?;
+///     println!("{}", reply.content);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SessionManager {
+    model: Arc<dyn BaseChatModel>,
+    store: Arc<dyn SessionStore>,
+    system_prompt: Option<String>,
+    memory: SessionMemory,
+    trim: Option<TrimStrategy>,
+}
+
+impl SessionManager {
+    pub fn new(model: Arc<dyn BaseChatModel>, store: Arc<dyn SessionStore>) -> Self {
+        Self {
+            model,
+            store,
+            system_prompt: None,
+            memory: SessionMemory::Full,
+            trim: Some(TrimStrategy::DropOldest),
+        }
+    }
+
+    /// System prompt sent ahead of every request, not stored in the history
+    pub fn system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    pub fn memory(mut self, memory: SessionMemory) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// How to shorten a request the model rejects as too long
+    pub fn trim_strategy(mut self, strategy: TrimStrategy) -> Self {
+        self.trim = Some(strategy);
+        self
+    }
+
+    /// Return context-length errors instead of shortening the request
+    pub fn without_trimming(mut self) -> Self {
+        self.trim = None;
+        self
+    }
+
+    pub fn store(&self) -> &Arc<dyn SessionStore> {
+        &self.store
+    }
+
+    /// Start and store a session with a new random id
+    pub async fn create(&self) -> SessionResult<ChatSession> {
+        self.create_with_id(uuid::Uuid::new_v4().to_string()).await
+    }
+
+    /// Start and store a session under `id`, failing with
+    /// [`SessionError::Conflict`] if it exists
+    pub async fn create_with_id(&self, id: impl Into<String>) -> SessionResult<ChatSession> {
+        let mut history = ChatHistory::new(id);
+        self.store.save(&mut history).await?;
+        Ok(self.session(history))
+    }
+
+    /// The stored session `id`
+    pub async fn load(&self, id: &str) -> SessionResult<ChatSession> {
+        match self.store.load(id).await? {
+            Some(history) => Ok(self.session(history)),
+            None => Err(SessionError::NotFound(id.to_string())),
+        }
+    }
+
+    /// The stored session `id`, or a new one under that id
+    pub async fn open(&self, id: &str) -> SessionResult<ChatSession> {
+        match self.store.load(id).await? {
+            Some(history) => Ok(self.session(history)),
+            None => self.create_with_id(id).await,
+        }
+    }
+
+    pub async fn delete(&self, id: &str) -> SessionResult<()> {
+        self.store.delete(id).await
+    }
+
+    pub async fn list(&self) -> SessionResult<Vec<String>> {
+        self.store.list().await
+    }
+
+    fn session(&self, history: ChatHistory) -> ChatSession {
+        ChatSession {
+            manager: self.clone(),
+            history,
+        }
+    }
+}
+
+impl std::fmt::Debug for SessionManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionManager")
+            .field("model", &self.model.model_name())
+            .field("memory", &self.memory)
+            .field("trim", &self.trim)
+            .finish()
+    }
+}
+
+/// One conversation, saved to its store after every turn
+///
+/// Created by a [`SessionManager`]. If another process saved the session
+/// since it was loaded, saving fails with [`SessionError::Conflict`]; call
+/// [`reload`](Self::reload) and try the turn again.
+#[derive(Clone)]
+pub struct ChatSession {
+    manager: SessionManager,
+    history: ChatHistory,
+}
+
+impl ChatSession {
+    pub fn id(&self) -> &str {
+        &self.history.id
+    }
+
+    pub fn history(&self) -> &ChatHistory {
+        &self.history
+    }
+
+    pub fn messages(&self) -> &[Message] {
+        &self.history.messages
+    }
+
+    pub fn into_history(self) -> ChatHistory {
+        self.history
+    }
+
+    pub fn set_metadata(&mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) {
+        self.history.metadata.insert(key.into(), value.into());
+    }
+
+    /// Add a message without invoking the model; it is stored on the next
+    /// save
+    pub fn append(&mut self, message: Message) {
+        self.history.push(message);
+    }
+
+    pub async fn save(&mut self) -> SessionResult<()> {
+        self.manager.store.save(&mut self.history).await
+    }
+
+    /// Replace the history with the stored one, dropping unsaved changes
+    pub async fn reload(&mut self) -> SessionResult<()> {
+        self.history = self
+            .manager
+            .store
+            .load(self.id())
+            .await?
+            .ok_or_else(|| SessionError::NotFound(self.id().to_string()))?;
+        Ok(())
+    }
+
+    /// Messages the next request would send
+    pub fn request(&self) -> Vec<Message> {
+        let mut messages = Vec::new();
+        if let Some(prompt) = &self.manager.system_prompt {
+            messages.push(Message::system(prompt.clone()));
+        }
+        messages.extend_from_slice(self.manager.memory.window(&self.history.messages));
+        messages
+    }
+
+    /// Send a user message, record the reply, and save the session
+    ///
+    /// If the model fails, the user message is not kept.
+    pub async fn send(&mut self, input: impl Into<String>) -> SessionResult<AIMessage> {
+        self.history.push(Message::user(input));
+        let request = self.request();
+        let result = match &self.manager.trim {
+            Some(strategy) => {
+                TrimmingChatModel::new(self.manager.model.clone())
+                    .strategy(strategy.clone())
+                    .invoke(&request)
+                    .await
+            }
+            None => self.manager.model.invoke(&request).await,
+        };
+        let reply = match result {
+            Ok(reply) => reply,
+            Err(e) => {
+                self.history.messages.pop();
+                return Err(e.into());
+            }
+        };
+        self.history.push(Message::AI(reply.clone()));
+        self.save().await?;
+        Ok(reply)
+    }
+}
+
+impl std::fmt::Debug for ChatSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChatSession")
+            .field("id", &self.history.id)
+            .field("messages", &self.history.messages.len())
+            .field("version", &self.history.version)
+            .finish()
+    }
+}
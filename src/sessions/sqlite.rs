@@ -0,0 +1,376 @@
+//! SQLite session store.
+//!
+//! Sessions persist in a single database file, one row per session and one
+//! per message, so conversations survive restarts and can be inspected with
+//! any SQLite client. Enabled with the `sqlite` feature.
+
+use crate::core::messages::Message;
+use crate::sessions::{ChatHistory, SessionError, SessionResult, SessionStore};
+use crate::vectorstores::is_sql_identifier;
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::Value;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::Row;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Schema changes in the order they are applied, each a list of statements
+/// with `{prefix}` standing for the table prefix
+///
+/// Append new migrations; never edit one that has been released.
+const MIGRATIONS: &[&[&str]] = &[&[
+    "CREATE TABLE {prefix}_sessions (\
+     id TEXT PRIMARY KEY, \
+     metadata TEXT NOT NULL, \
+     created_at INTEGER NOT NULL, \
+     updated_at INTEGER NOT NULL, \
+     version INTEGER NOT NULL)",
+    "CREATE TABLE {prefix}_messages (\
+     session_id TEXT NOT NULL, \
+     position INTEGER NOT NULL, \
+     role TEXT NOT NULL, \
+     content TEXT NOT NULL, \
+     tool_calls TEXT, \
+     tool_call_id TEXT, \
+     usage TEXT, \
+     PRIMARY KEY (session_id, position))",
+    "CREATE INDEX {prefix}_sessions_updated_at ON {prefix}_sessions (updated_at)",
+]];
+
+/// Session store persisted in a SQLite database file
+///
+/// The tables are created, and later schema changes applied, the first time
+/// the store is used; [`migrate`](Self::migrate) applies them up front.
+/// Messages are stored with their role, content, tool calls, and tool call
+/// id in separate columns, and sessions with their metadata as JSON and
+/// their timestamps in milliseconds since the Unix epoch.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::sessions::{SessionManager, SqliteSessionStore};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = SqliteSessionStore::open("chats.db").await?.build();
+///     let sessions = SessionManager::new(Arc::new(OllamaChat::new("llama3.2")), Arc::new(store));
+///
+///     let mut session = sessions.open("support-42").await?;
+///     session.send("My order has not arrived.").await?;
+///     Ok(())
+/// }
+/// ```
+pub struct SqliteSessionStore {
+    pool: SqlitePool,
+    prefix: String,
+    schema_ready: AtomicBool,
+}
+
+impl SqliteSessionStore {
+    pub fn builder(pool: SqlitePool) -> SqliteSessionStoreBuilder {
+        SqliteSessionStoreBuilder::new(pool)
+    }
+
+    /// Open (creating if needed) the database file at `path` and start a builder
+    pub async fn open(path: impl AsRef<Path>) -> SessionResult<SqliteSessionStoreBuilder> {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .connect_with(options)
+            .await
+            .map_err(backend_error)?;
+        Ok(SqliteSessionStoreBuilder::new(pool))
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Apply any migrations not yet applied, returning the schema version
+    pub async fn migrate(&self) -> SessionResult<usize> {
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {}_migrations (\
+             version INTEGER PRIMARY KEY, \
+             applied_at INTEGER NOT NULL)",
+            self.prefix
+        ))
+        .execute(&self.pool)
+        .await
+        .map_err(backend_error)?;
+
+        let mut tx = self.pool.begin().await.map_err(backend_error)?;
+        let applied: i64 = sqlx::query(&format!(
+            "SELECT COALESCE(MAX(version), 0) FROM {}_migrations",
+            self.prefix
+        ))
+        .fetch_one(&mut *tx)
+        .await
+        .and_then(|row| row.try_get(0))
+        .map_err(backend_error)?;
+        for (index, statements) in MIGRATIONS.iter().enumerate().skip(applied as usize) {
+            for statement in *statements {
+                sqlx::query(&statement.replace("{prefix}", &self.prefix))
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(backend_error)?;
+            }
+            sqlx::query(&format!(
+                "INSERT INTO {}_migrations (version, applied_at) VALUES (?, ?)",
+                self.prefix
+            ))
+            .bind(index as i64 + 1)
+            .bind(Utc::now().timestamp_millis())
+            .execute(&mut *tx)
+            .await
+            .map_err(backend_error)?;
+        }
+        tx.commit().await.map_err(backend_error)?;
+        self.schema_ready.store(true, Ordering::Release);
+        Ok(MIGRATIONS.len().max(applied as usize))
+    }
+
+    async fn ensure_schema(&self) -> SessionResult<()> {
+        if !self.schema_ready.load(Ordering::Acquire) {
+            self.migrate().await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SessionStore for SqliteSessionStore {
+    async fn load(&self, id: &str) -> SessionResult<Option<ChatHistory>> {
+        self.ensure_schema().await?;
+        let mut tx = self.pool.begin().await.map_err(backend_error)?;
+        let row = sqlx::query(&format!(
+            "SELECT metadata, created_at, updated_at, version FROM {}_sessions WHERE id = ?",
+            self.prefix
+        ))
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(backend_error)?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let messages = sqlx::query(&format!(
+            "SELECT role, content, tool_calls, tool_call_id, usage FROM {}_messages \
+             WHERE session_id = ? ORDER BY position",
+            self.prefix
+        ))
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(backend_error)?
+        .iter()
+        .map(message_from_row)
+        .collect::<SessionResult<Vec<_>>>()?;
+        tx.commit().await.map_err(backend_error)?;
+
+        let metadata: String = row.try_get("metadata").map_err(backend_error)?;
+        let version: i64 = row.try_get("version").map_err(backend_error)?;
+        Ok(Some(ChatHistory {
+            id: id.to_string(),
+            messages,
+            metadata: serde_json::from_str(&metadata)?,
+            created_at: timestamp(row.try_get("created_at").map_err(backend_error)?),
+            updated_at: timestamp(row.try_get("updated_at").map_err(backend_error)?),
+            version: version as u64,
+        }))
+    }
+
+    async fn save(&self, history: &mut ChatHistory) -> SessionResult<()> {
+        self.ensure_schema().await?;
+        let version = history.version as i64;
+        let metadata = serde_json::to_string(&history.metadata)?;
+        let mut tx = self.pool.begin().await.map_err(backend_error)?;
+
+        let stored = if version == 0 {
+            let sql = format!(
+                "INSERT OR IGNORE INTO {}_sessions \
+                 (id, metadata, created_at, updated_at, version) VALUES (?, ?, ?, ?, 1)",
+                self.prefix
+            );
+            sqlx::query(&sql)
+                .bind(&history.id)
+                .bind(&metadata)
+                .bind(history.created_at.timestamp_millis())
+                .bind(history.updated_at.timestamp_millis())
+                .execute(&mut *tx)
+                .await
+        } else {
+            let sql = format!(
+                "UPDATE {}_sessions SET metadata = ?, updated_at = ?, version = version + 1 \
+                 WHERE id = ? AND version = ?",
+                self.prefix
+            );
+            sqlx::query(&sql)
+                .bind(&metadata)
+                .bind(history.updated_at.timestamp_millis())
+                .bind(&history.id)
+                .bind(version)
+                .execute(&mut *tx)
+                .await
+        }
+        .map_err(backend_error)?;
+        if stored.rows_affected() == 0 {
+            return Err(SessionError::Conflict(history.id.clone()));
+        }
+
+        sqlx::query(&format!(
+            "DELETE FROM {}_messages WHERE session_id = ?",
+            self.prefix
+        ))
+        .bind(&history.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(backend_error)?;
+        let insert = format!(
+            "INSERT INTO {}_messages \
+             (session_id, position, role, content, tool_calls, tool_call_id, usage) \
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            self.prefix
+        );
+        for (position, message) in history.messages.iter().enumerate() {
+            let (tool_calls, tool_call_id, usage) = match message {
+                Message::AI(m) => (
+                    (!m.tool_calls.is_empty())
+                        .then(|| serde_json::to_string(&m.tool_calls))
+                        .transpose()?,
+                    None,
+                    m.usage.as_ref().map(serde_json::to_string).transpose()?,
+                ),
+                Message::Tool(m) => (None, Some(m.tool_call_id.as_str()), None),
+                _ => (None, None, None),
+            };
+            sqlx::query(&insert)
+                .bind(&history.id)
+                .bind(position as i64)
+                .bind(message.role())
+                .bind(message.content())
+                .bind(tool_calls)
+                .bind(tool_call_id)
+                .bind(usage)
+                .execute(&mut *tx)
+                .await
+                .map_err(backend_error)?;
+        }
+
+        tx.commit().await.map_err(backend_error)?;
+        history.version += 1;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> SessionResult<()> {
+        self.ensure_schema().await?;
+        let mut tx = self.pool.begin().await.map_err(backend_error)?;
+        for sql in [
+            format!("DELETE FROM {}_messages WHERE session_id = ?", self.prefix),
+            format!("DELETE FROM {}_sessions WHERE id = ?", self.prefix),
+        ] {
+            sqlx::query(&sql)
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(backend_error)?;
+        }
+        tx.commit().await.map_err(backend_error)?;
+        Ok(())
+    }
+
+    async fn list(&self) -> SessionResult<Vec<String>> {
+        self.ensure_schema().await?;
+        sqlx::query(&format!(
+            "SELECT id FROM {}_sessions ORDER BY id",
+            self.prefix
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(backend_error)?
+        .iter()
+        .map(|row| row.try_get("id").map_err(backend_error))
+        .collect()
+    }
+}
+
+impl std::fmt::Debug for SqliteSessionStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteSessionStore")
+            .field("prefix", &self.prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Rebuild a message from its columns
+fn message_from_row(row: &SqliteRow) -> SessionResult<Message> {
+    let mut message = serde_json::Map::new();
+    for column in ["role", "content", "tool_call_id"] {
+        if let Some(value) = row
+            .try_get::<Option<String>, _>(column)
+            .map_err(backend_error)?
+        {
+            message.insert(column.to_string(), Value::String(value));
+        }
+    }
+    for column in ["tool_calls", "usage"] {
+        if let Some(value) = row
+            .try_get::<Option<String>, _>(column)
+            .map_err(backend_error)?
+        {
+            message.insert(column.to_string(), serde_json::from_str(&value)?);
+        }
+    }
+    Ok(serde_json::from_value(Value::Object(message))?)
+}
+
+fn timestamp(millis: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(millis)
+        .single()
+        .unwrap_or_default()
+}
+
+fn backend_error(err: sqlx::Error) -> SessionError {
+    SessionError::Backend(err.to_string())
+}
+
+/// Builder for SqliteSessionStore
+pub struct SqliteSessionStoreBuilder {
+    pool: SqlitePool,
+    prefix: String,
+}
+
+impl SqliteSessionStoreBuilder {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            prefix: "chat".to_string(),
+        }
+    }
+
+    /// Prefix of the table names, `chat` by default for `chat_sessions` and
+    /// `chat_messages`; must be a plain SQL identifier
+    pub fn table_prefix(mut self, prefix: impl Into<String>) -> SessionResult<Self> {
+        let prefix = prefix.into();
+        if !is_sql_identifier(&prefix) {
+            return Err(SessionError::Backend(format!(
+                "invalid table prefix '{}'",
+                prefix
+            )));
+        }
+        self.prefix = prefix;
+        Ok(self)
+    }
+
+    pub fn build(self) -> SqliteSessionStore {
+        SqliteSessionStore {
+            pool: self.pool,
+            prefix: self.prefix,
+            schema_ready: AtomicBool::new(false),
+        }
+    }
+}
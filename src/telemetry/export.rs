@@ -0,0 +1,527 @@
+//! Trace export to Langfuse and LangSmith.
+//!
+//! [`TraceExporter`] is a [`CallbackHandler`] that turns agent runs, model
+//! calls, and tool executions into a tree of runs and ships them to an LLM
+//! observability platform through its ingestion API. Nested work is parented to
+//! the run that started it: a sub-agent called as a tool appears under that
+//! tool call, and its model calls under the sub-agent.
+//!
+//! Completed runs are queued and sent in batches: automatically once
+//! [`batch_size`](TraceExporter::batch_size) runs are waiting (when a Tokio
+//! runtime is available), and on [`flush`](TraceExporter::flush), which should
+//! be awaited before the process exits.
+//!
+//! Runs take their ids from the [`RunContext`] of each hook, so concurrent runs
+//! can share one exporter.
+
+use crate::agents::{AgentResult, AgentRun};
+use crate::callbacks::CallbackHandler;
+use crate::core::messages::{messages_to_dict, AIMessage, BaseMessage, Message, ToolCall, Usage};
+use crate::core::run::RunContext;
+use crate::credentials::{CredentialError, CredentialProvider};
+use crate::models::http::shared_client;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+const DEFAULT_LANGFUSE_HOST: &str = "https://cloud.langfuse.com";
+const DEFAULT_LANGSMITH_ENDPOINT: &str = "https://api.smith.langchain.com";
+
+/// Error type for trace export
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    #[error("Environment variable {0} is not set")]
+    MissingEnv(&'static str),
+
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Credential lookup failed: {0}")]
+    Credential(#[from] CredentialError),
+
+    #[error("Ingestion API returned {status}: {body}")]
+    Api { status: u16, body: String },
+}
+
+pub type ExportResult<T> = Result<T, ExportError>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunKind {
+    Agent,
+    Llm,
+    Tool,
+}
+
+/// Node of the run tree
+#[derive(Debug, Clone)]
+struct Run {
+    id: String,
+    trace_id: String,
+    parent_id: Option<String>,
+    /// LangSmith ordering key: the start time and id of every ancestor
+    dotted_order: String,
+    kind: RunKind,
+    name: String,
+    input: Value,
+    output: Option<Value>,
+    error: Option<String>,
+    usage: Option<Usage>,
+    metadata: BTreeMap<String, String>,
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+enum Backend {
+    Langfuse {
+        host: String,
+        public_key: String,
+        secret_key: String,
+    },
+    LangSmith {
+        endpoint: String,
+        api_key: String,
+        project: String,
+    },
+}
+
+#[derive(Default)]
+struct State {
+    open: Vec<Run>,
+    finished: Vec<Run>,
+}
+
+/// Callback handler exporting run trees to Langfuse or LangSmith
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::agents::Agent;
+/// use agentic_optio_rs::telemetry::TraceExporter;
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // LANGFUSE_PUBLIC_KEY, LANGFUSE_SECRET_KEY, and optionally LANGFUSE_HOST
+///     let exporter = TraceExporter::langfuse_from_env()?;
+///     let agent = Agent::builder(Arc::new(OllamaChat::new("llama3.2")))
+///         .callback(exporter.clone())
+///         .build();
+///
+///     agent.run("What is 2 + 2?").await?;
+///     exporter.flush().await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TraceExporter {
+    backend: Backend,
+    client: Client,
+    batch_size: usize,
+    state: Arc<Mutex<State>>,
+}
+
+impl TraceExporter {
+    /// Export to a Langfuse instance with a project's API key pair
+    pub fn langfuse(
+        host: impl Into<String>,
+        public_key: impl Into<String>,
+        secret_key: impl Into<String>,
+    ) -> Self {
+        Self::with_backend(Backend::Langfuse {
+            host: host.into().trim_end_matches('/').to_string(),
+            public_key: public_key.into(),
+            secret_key: secret_key.into(),
+        })
+    }
+
+    /// Configure Langfuse from `LANGFUSE_PUBLIC_KEY`, `LANGFUSE_SECRET_KEY`, and
+    /// `LANGFUSE_HOST` (default `https://cloud.langfuse.com`)
+    pub fn langfuse_from_env() -> ExportResult<Self> {
+        Ok(Self::langfuse(
+            std::env::var("LANGFUSE_HOST").unwrap_or_else(|_| DEFAULT_LANGFUSE_HOST.to_string()),
+            env("LANGFUSE_PUBLIC_KEY")?,
+            env("LANGFUSE_SECRET_KEY")?,
+        ))
+    }
+
+    /// Configure Langfuse like [`langfuse_from_env`](Self::langfuse_from_env),
+    /// looking the same names up in `credentials`
+    pub async fn langfuse_from_credentials(
+        credentials: &dyn CredentialProvider,
+    ) -> ExportResult<Self> {
+        Ok(Self::langfuse(
+            credentials
+                .get("LANGFUSE_HOST")
+                .await?
+                .unwrap_or_else(|| DEFAULT_LANGFUSE_HOST.to_string()),
+            credentials.require("LANGFUSE_PUBLIC_KEY").await?,
+            credentials.require("LANGFUSE_SECRET_KEY").await?,
+        ))
+    }
+
+    /// Export to LangSmith, recording runs in `project`
+    pub fn langsmith(
+        endpoint: impl Into<String>,
+        api_key: impl Into<String>,
+        project: impl Into<String>,
+    ) -> Self {
+        Self::with_backend(Backend::LangSmith {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            api_key: api_key.into(),
+            project: project.into(),
+        })
+    }
+
+    /// Configure LangSmith from `LANGSMITH_API_KEY`, `LANGSMITH_ENDPOINT`
+    /// (default `https://api.smith.langchain.com`), and `LANGSMITH_PROJECT`
+    /// (default `default`)
+    pub fn langsmith_from_env() -> ExportResult<Self> {
+        Ok(Self::langsmith(
+            std::env::var("LANGSMITH_ENDPOINT")
+                .unwrap_or_else(|_| DEFAULT_LANGSMITH_ENDPOINT.to_string()),
+            env("LANGSMITH_API_KEY")?,
+            std::env::var("LANGSMITH_PROJECT").unwrap_or_else(|_| "default".to_string()),
+        ))
+    }
+
+    /// Configure LangSmith like [`langsmith_from_env`](Self::langsmith_from_env),
+    /// looking the same names up in `credentials`
+    pub async fn langsmith_from_credentials(
+        credentials: &dyn CredentialProvider,
+    ) -> ExportResult<Self> {
+        Ok(Self::langsmith(
+            credentials
+                .get("LANGSMITH_ENDPOINT")
+                .await?
+                .unwrap_or_else(|| DEFAULT_LANGSMITH_ENDPOINT.to_string()),
+            credentials.require("LANGSMITH_API_KEY").await?,
+            credentials
+                .get("LANGSMITH_PROJECT")
+                .await?
+                .unwrap_or_else(|| "default".to_string()),
+        ))
+    }
+
+    fn with_backend(backend: Backend) -> Self {
+        Self {
+            backend,
+            client: shared_client(),
+            batch_size: 20,
+            state: Arc::default(),
+        }
+    }
+
+    /// Number of finished runs that triggers a background flush (default 20)
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Send batches through `client` instead of the shared default one
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Number of finished runs waiting to be sent
+    pub fn pending(&self) -> usize {
+        self.state.lock().unwrap().finished.len()
+    }
+
+    /// Send every finished run, returning how many were sent
+    ///
+    /// Runs in a failed batch are dropped rather than retried.
+    pub async fn flush(&self) -> ExportResult<usize> {
+        let mut runs = std::mem::take(&mut self.state.lock().unwrap().finished);
+        if runs.is_empty() {
+            return Ok(0);
+        }
+        // Parents finish after their children but are sent first
+        runs.sort_by(|a, b| a.dotted_order.cmp(&b.dotted_order));
+
+        let request = match &self.backend {
+            Backend::Langfuse {
+                host,
+                public_key,
+                secret_key,
+            } => self
+                .client
+                .post(format!("{}/api/public/ingestion", host))
+                .basic_auth(public_key, Some(secret_key))
+                .json(&json!({ "batch": langfuse_events(&runs) })),
+            Backend::LangSmith {
+                endpoint,
+                api_key,
+                project,
+            } => {
+                let post: Vec<Value> = runs.iter().map(|r| langsmith_run(r, project)).collect();
+                self.client
+                    .post(format!("{}/runs/batch", endpoint))
+                    .header("x-api-key", api_key)
+                    .json(&json!({ "post": post }))
+            }
+        };
+
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ExportError::Api {
+                status: status.as_u16(),
+                body: response.text().await.unwrap_or_default(),
+            });
+        }
+        Ok(runs.len())
+    }
+
+    fn start(&self, kind: RunKind, name: &str, input: Value) {
+        let context = RunContext::current().unwrap_or_else(RunContext::root);
+        let mut state = self.state.lock().unwrap();
+        let id = context.run_id;
+        let start = Utc::now();
+        let key = format!("{}{}", start.format("%Y%m%dT%H%M%S%6fZ"), id);
+        // Runs whose parent was not exported become roots
+        let parent = context
+            .parent_run_id
+            .and_then(|parent| state.open.iter().find(|r| r.id == parent));
+        let (trace_id, parent_id, dotted_order) = match parent {
+            Some(parent) => (
+                parent.trace_id.clone(),
+                Some(parent.id.clone()),
+                format!("{}.{}", parent.dotted_order, key),
+            ),
+            None => (id.clone(), None, key),
+        };
+        state.open.push(Run {
+            id,
+            trace_id,
+            parent_id,
+            dotted_order,
+            kind,
+            name: name.to_string(),
+            input,
+            output: None,
+            error: None,
+            usage: None,
+            metadata: context.metadata,
+            start,
+            end: None,
+        });
+    }
+
+    /// Close the current run, or failing that the latest open run of `kind`
+    fn end(
+        &self,
+        kind: RunKind,
+        output: Option<Value>,
+        error: Option<String>,
+        usage: Option<Usage>,
+    ) {
+        let ready = {
+            let mut state = self.state.lock().unwrap();
+            let current = RunContext::current().map(|c| c.run_id);
+            let position = state
+                .open
+                .iter()
+                .rposition(|r| r.kind == kind && Some(&r.id) == current.as_ref())
+                .or_else(|| state.open.iter().rposition(|r| r.kind == kind));
+            let Some(position) = position else {
+                return;
+            };
+            let mut run = state.open.remove(position);
+            run.end = Some(Utc::now());
+            run.output = output;
+            run.error = error;
+            run.usage = usage;
+            state.finished.push(run);
+            state.finished.len() >= self.batch_size
+        };
+
+        if ready {
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                let exporter = self.clone();
+                runtime.spawn(async move {
+                    let _ = exporter.flush().await;
+                });
+            }
+        }
+    }
+}
+
+impl CallbackHandler for TraceExporter {
+    fn on_agent_start(&self, agent: &str, messages: &[Message]) {
+        self.start(
+            RunKind::Agent,
+            agent,
+            json!({ "messages": messages_to_dict(messages) }),
+        );
+    }
+
+    fn on_agent_end(&self, _agent: &str, result: &AgentResult<AgentRun>) {
+        match result {
+            Ok(run) => self.end(
+                RunKind::Agent,
+                Some(json!({ "output": run.output })),
+                None,
+                Some(run.usage),
+            ),
+            Err(e) => self.end(RunKind::Agent, None, Some(e.to_string()), None),
+        }
+    }
+
+    fn on_llm_start(&self, model: &str, messages: &[Message]) {
+        self.start(
+            RunKind::Llm,
+            model,
+            json!({ "messages": messages_to_dict(messages) }),
+        );
+    }
+
+    fn on_llm_end(&self, _model: &str, response: &AIMessage) {
+        self.end(RunKind::Llm, Some(response.to_dict()), None, response.usage);
+    }
+
+    fn on_tool_start(&self, call: &ToolCall) {
+        self.start(RunKind::Tool, &call.name, call.args.clone());
+    }
+
+    fn on_tool_end(&self, _call: &ToolCall, output: &str) {
+        self.end(RunKind::Tool, Some(json!({ "output": output })), None, None);
+    }
+
+    /// Fails the current model call or tool execution
+    fn on_error(&self, error: &dyn std::error::Error) {
+        let Some(current) = RunContext::current() else {
+            return;
+        };
+        let kind = self
+            .state
+            .lock()
+            .unwrap()
+            .open
+            .iter()
+            .find(|run| run.id == current.run_id)
+            .map(|run| run.kind)
+            .filter(|kind| *kind != RunKind::Agent);
+        if let Some(kind) = kind {
+            self.end(kind, None, Some(error.to_string()), None);
+        }
+    }
+}
+
+impl std::fmt::Debug for TraceExporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let backend = match &self.backend {
+            Backend::Langfuse { host, .. } => format!("Langfuse({})", host),
+            Backend::LangSmith { endpoint, .. } => format!("LangSmith({})", endpoint),
+        };
+        f.debug_struct("TraceExporter")
+            .field("backend", &backend)
+            .field("batch_size", &self.batch_size)
+            .finish_non_exhaustive()
+    }
+}
+
+fn env(name: &'static str) -> ExportResult<String> {
+    std::env::var(name).map_err(|_| ExportError::MissingEnv(name))
+}
+
+/// Langfuse ingestion events: a trace per root run, an observation per run
+fn langfuse_events(runs: &[Run]) -> Vec<Value> {
+    let event = |kind: &str, body: Value| {
+        json!({
+            "id": Uuid::new_v4().to_string(),
+            "type": kind,
+            "timestamp": Utc::now(),
+            "body": body,
+        })
+    };
+
+    let mut events = Vec::new();
+    for run in runs {
+        if run.parent_id.is_none() {
+            events.push(event(
+                "trace-create",
+                json!({
+                    "id": run.trace_id,
+                    "name": run.name,
+                    "timestamp": run.start,
+                    "input": run.input,
+                    "output": run.output,
+                    "metadata": run.metadata,
+                }),
+            ));
+        }
+
+        let mut body = json!({
+            "id": run.id,
+            "traceId": run.trace_id,
+            "parentObservationId": run.parent_id,
+            "name": run.name,
+            "startTime": run.start,
+            "endTime": run.end,
+            "input": run.input,
+            "output": run.output,
+            "level": if run.error.is_some() { "ERROR" } else { "DEFAULT" },
+            "statusMessage": run.error,
+            "metadata": run.metadata,
+        });
+        if run.kind == RunKind::Llm {
+            body["model"] = json!(run.name);
+            if let Some(usage) = run.usage {
+                body["usage"] = json!({
+                    "input": usage.input_tokens,
+                    "output": usage.output_tokens,
+                    "total": usage.total_tokens,
+                    "unit": "TOKENS",
+                });
+            }
+        }
+        let kind = match run.kind {
+            RunKind::Llm => "generation-create",
+            RunKind::Agent | RunKind::Tool => "span-create",
+        };
+        events.push(event(kind, body));
+    }
+    events
+}
+
+/// LangSmith run in the shape accepted by `/runs/batch`
+fn langsmith_run(run: &Run, project: &str) -> Value {
+    let run_type = match run.kind {
+        RunKind::Agent => "chain",
+        RunKind::Llm => "llm",
+        RunKind::Tool => "tool",
+    };
+    let mut outputs = run.output.clone();
+    if let (Some(Value::Object(fields)), Some(usage)) = (&mut outputs, run.usage) {
+        fields.insert(
+            "usage_metadata".to_string(),
+            json!({
+                "input_tokens": usage.input_tokens,
+                "output_tokens": usage.output_tokens,
+                "total_tokens": usage.total_tokens,
+            }),
+        );
+    }
+
+    json!({
+        "id": run.id,
+        "trace_id": run.trace_id,
+        "parent_run_id": run.parent_id,
+        "dotted_order": run.dotted_order,
+        "name": run.name,
+        "run_type": run_type,
+        "inputs": run.input,
+        "outputs": outputs,
+        "error": run.error,
+        "start_time": run.start,
+        "end_time": run.end,
+        "session_name": project,
+        "extra": { "metadata": run.metadata },
+    })
+}
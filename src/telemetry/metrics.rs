@@ -0,0 +1,399 @@
+//! Prometheus metrics for model calls.
+//!
+//! [`Metrics`] owns a set of counters and histograms labelled by provider and
+//! model. Wrap chat and embedding models with [`MetricsChatModel`] and
+//! [`MetricsEmbedding`] to feed it, then serve [`Metrics::gather`] from a
+//! `/metrics` endpoint.
+//!
+//! | Metric | Type | Labels |
+//! |---|---|---|
+//! | `agentic_optio_requests_total` | counter | provider, model, operation |
+//! | `agentic_optio_errors_total` | counter | provider, model, operation, kind |
+//! | `agentic_optio_retries_total` | counter | provider, model |
+//! | `agentic_optio_request_duration_seconds` | histogram | provider, model, operation |
+//! | `agentic_optio_time_to_first_token_seconds` | histogram | provider, model |
+//! | `agentic_optio_stream_tokens_per_second` | histogram | provider, model |
+//! | `agentic_optio_tokens_total` | counter | provider, model, direction |
+//! | `agentic_optio_cost_total` | counter | provider, model |
+
+use crate::core::messages::{AIMessage, Message, StreamStats, Usage};
+use crate::models::base::{
+    BaseChatModel, BaseEmbedding, BoxStream, ModelError, ModelResult, StreamMeter,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use prometheus::{CounterVec, Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+const NAMESPACE: &str = "agentic_optio";
+
+/// Latency buckets in seconds, from fast local calls to long generations
+const DURATION_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0];
+
+/// Generation speed buckets in tokens per second
+const THROUGHPUT_BUCKETS: &[f64] = &[1.0, 5.0, 10.0, 20.0, 40.0, 60.0, 100.0, 150.0, 250.0, 500.0];
+
+struct Inner {
+    registry: Registry,
+    requests: IntCounterVec,
+    errors: IntCounterVec,
+    retries: IntCounterVec,
+    duration: HistogramVec,
+    time_to_first_token: HistogramVec,
+    tokens_per_second: HistogramVec,
+    tokens: IntCounterVec,
+    cost: CounterVec,
+    /// Cost per 1K input and output tokens, by model
+    pricing: RwLock<HashMap<String, (f64, f64)>>,
+}
+
+/// Model call metrics registered in a Prometheus registry
+///
+/// Cloning yields another handle to the same metrics.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::telemetry::{Metrics, MetricsChatModel};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// let metrics = Metrics::new().pricing("gpt-4o", 0.0025, 0.01);
+/// let llm = MetricsChatModel::new(Arc::new(OllamaChat::new("llama3.2")), metrics.clone());
+///
+/// // In an axum handler: `async move { metrics.gather() }`
+/// assert!(metrics.gather().is_empty());
+/// ```
+#[derive(Clone)]
+pub struct Metrics {
+    inner: Arc<Inner>,
+}
+
+impl Metrics {
+    /// Metrics in a fresh registry of their own
+    pub fn new() -> Self {
+        Self::with_registry(Registry::new()).expect("metrics register in an empty registry")
+    }
+
+    /// Register the metrics in an existing registry, e.g. one shared with the
+    /// rest of the application
+    pub fn with_registry(registry: Registry) -> prometheus::Result<Self> {
+        let labels = ["provider", "model", "operation"];
+        let requests = IntCounterVec::new(
+            Opts::new("requests_total", "Model requests started").namespace(NAMESPACE),
+            &labels,
+        )?;
+        let errors = IntCounterVec::new(
+            Opts::new("errors_total", "Model requests that failed").namespace(NAMESPACE),
+            &["provider", "model", "operation", "kind"],
+        )?;
+        let retries = IntCounterVec::new(
+            Opts::new("retries_total", "Model requests retried").namespace(NAMESPACE),
+            &["provider", "model"],
+        )?;
+        let duration = HistogramVec::new(
+            HistogramOpts::new("request_duration_seconds", "Model request latency")
+                .namespace(NAMESPACE)
+                .buckets(DURATION_BUCKETS.to_vec()),
+            &labels,
+        )?;
+        let time_to_first_token = HistogramVec::new(
+            HistogramOpts::new(
+                "time_to_first_token_seconds",
+                "Time from a streaming request to its first token",
+            )
+            .namespace(NAMESPACE)
+            .buckets(DURATION_BUCKETS.to_vec()),
+            &["provider", "model"],
+        )?;
+        let tokens_per_second = HistogramVec::new(
+            HistogramOpts::new(
+                "stream_tokens_per_second",
+                "Generation speed of streamed responses after the first token",
+            )
+            .namespace(NAMESPACE)
+            .buckets(THROUGHPUT_BUCKETS.to_vec()),
+            &["provider", "model"],
+        )?;
+        let tokens = IntCounterVec::new(
+            Opts::new("tokens_total", "Tokens consumed").namespace(NAMESPACE),
+            &["provider", "model", "direction"],
+        )?;
+        let cost = CounterVec::new(
+            Opts::new("cost_total", "Spend computed from configured pricing").namespace(NAMESPACE),
+            &["provider", "model"],
+        )?;
+
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(errors.clone()))?;
+        registry.register(Box::new(retries.clone()))?;
+        registry.register(Box::new(duration.clone()))?;
+        registry.register(Box::new(time_to_first_token.clone()))?;
+        registry.register(Box::new(tokens_per_second.clone()))?;
+        registry.register(Box::new(tokens.clone()))?;
+        registry.register(Box::new(cost.clone()))?;
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                registry,
+                requests,
+                errors,
+                retries,
+                duration,
+                time_to_first_token,
+                tokens_per_second,
+                tokens,
+                cost,
+                pricing: RwLock::new(HashMap::new()),
+            }),
+        })
+    }
+
+    /// Price a model per 1K input and output tokens, enabling its cost metric
+    pub fn pricing(self, model: impl Into<String>, input_per_1k: f64, output_per_1k: f64) -> Self {
+        self.inner
+            .pricing
+            .write()
+            .unwrap()
+            .insert(model.into(), (input_per_1k, output_per_1k));
+        self
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.inner.registry
+    }
+
+    /// Every metric in the Prometheus text exposition format
+    pub fn gather(&self) -> String {
+        let mut buffer = Vec::new();
+        prometheus::TextEncoder::new()
+            .encode(&self.inner.registry.gather(), &mut buffer)
+            .expect("text encoding of gathered metrics");
+        String::from_utf8(buffer).expect("text encoder writes UTF-8")
+    }
+
+    /// Record one finished request
+    pub fn observe(
+        &self,
+        provider: &str,
+        model: &str,
+        operation: &str,
+        elapsed: Duration,
+        error: Option<&ModelError>,
+    ) {
+        let labels = [provider, model, operation];
+        self.inner.requests.with_label_values(&labels).inc();
+        self.inner
+            .duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+        if let Some(error) = error {
+            self.inner
+                .errors
+                .with_label_values(&[provider, model, operation, error.kind()])
+                .inc();
+        }
+    }
+
+    /// Record token usage and, if the model is priced, its cost
+    pub fn observe_usage(&self, provider: &str, model: &str, usage: Usage) {
+        let tokens = &self.inner.tokens;
+        tokens
+            .with_label_values(&[provider, model, "input"])
+            .inc_by(usage.input_tokens as u64);
+        tokens
+            .with_label_values(&[provider, model, "output"])
+            .inc_by(usage.output_tokens as u64);
+
+        if let Some((input, output)) = self.inner.pricing.read().unwrap().get(model) {
+            let cost =
+                (usage.input_tokens as f64 * input + usage.output_tokens as f64 * output) / 1000.0;
+            self.inner
+                .cost
+                .with_label_values(&[provider, model])
+                .inc_by(cost);
+        }
+    }
+
+    /// Record the throughput of a finished response stream
+    pub fn observe_stream(&self, provider: &str, model: &str, stats: &StreamStats) {
+        let labels = [provider, model];
+        self.inner
+            .time_to_first_token
+            .with_label_values(&labels)
+            .observe(stats.time_to_first_token.as_secs_f64());
+        self.inner
+            .tokens_per_second
+            .with_label_values(&labels)
+            .observe(stats.tokens_per_second());
+    }
+
+    /// Record a retried request; called by retrying layers
+    pub fn record_retry(&self, provider: &str, model: &str) {
+        self.inner
+            .retries
+            .with_label_values(&[provider, model])
+            .inc();
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metrics").finish_non_exhaustive()
+    }
+}
+
+/// Chat model wrapper recording every call in [`Metrics`]
+#[derive(Clone)]
+pub struct MetricsChatModel {
+    inner: Arc<dyn BaseChatModel>,
+    metrics: Metrics,
+}
+
+impl MetricsChatModel {
+    pub fn new(inner: Arc<dyn BaseChatModel>, metrics: Metrics) -> Self {
+        Self { inner, metrics }
+    }
+
+    fn record(&self, started: Instant, result: &ModelResult<AIMessage>) {
+        let (provider, model) = (self.provider_name(), self.model_name());
+        self.metrics.observe(
+            provider,
+            model,
+            "chat",
+            started.elapsed(),
+            result.as_ref().err(),
+        );
+        if let Some(usage) = result.as_ref().ok().and_then(|r| r.usage) {
+            self.metrics.observe_usage(provider, model, usage);
+        }
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for MetricsChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        let started = Instant::now();
+        let result = self.inner.invoke(messages).await;
+        self.record(started, &result);
+        result
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        let started = Instant::now();
+        let result = self.inner.invoke_with_tools(messages, tools).await;
+        self.record(started, &result);
+        result
+    }
+
+    /// Latency covers the whole stream; a failed chunk counts as an error
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let started = Instant::now();
+        let (provider, model) = (self.provider_name(), self.model_name());
+        let inner = match self.inner.stream(messages).await {
+            Ok(inner) => inner,
+            Err(e) => {
+                self.metrics
+                    .observe(provider, model, "chat", started.elapsed(), Some(&e));
+                return Err(e);
+            }
+        };
+
+        let meter = StreamMeter::new(started);
+        let stream = futures::stream::unfold(
+            (inner, meter, false),
+            move |(mut inner, mut meter, done)| async move {
+                if done {
+                    return None;
+                }
+                match inner.next().await {
+                    Some(Ok(chunk)) => {
+                        meter.observe(&chunk);
+                        Some((Ok(chunk), (inner, meter, false)))
+                    }
+                    Some(Err(e)) => {
+                        self.metrics
+                            .observe(provider, model, "chat", started.elapsed(), Some(&e));
+                        Some((Err(e), (inner, meter, true)))
+                    }
+                    None => {
+                        self.metrics
+                            .observe(provider, model, "chat", started.elapsed(), None);
+                        self.metrics
+                            .observe_stream(provider, model, &meter.finish());
+                        None
+                    }
+                }
+            },
+        );
+        Ok(Box::pin(stream))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
+
+/// Embedding model wrapper recording every call in [`Metrics`]
+#[derive(Clone)]
+pub struct MetricsEmbedding {
+    inner: Arc<dyn BaseEmbedding>,
+    metrics: Metrics,
+}
+
+impl MetricsEmbedding {
+    pub fn new(inner: Arc<dyn BaseEmbedding>, metrics: Metrics) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl BaseEmbedding for MetricsEmbedding {
+    async fn embed(&self, texts: &[String]) -> ModelResult<Vec<Vec<f32>>> {
+        let started = Instant::now();
+        let result = self.inner.embed(texts).await;
+        self.metrics.observe(
+            self.provider_name(),
+            self.model_name(),
+            "embeddings",
+            started.elapsed(),
+            result.as_ref().err(),
+        );
+        result
+    }
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+}
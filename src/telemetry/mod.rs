@@ -0,0 +1,30 @@
+//! Telemetry for AgenticOptio.
+//!
+//! Integrations that report model calls, embeddings, and tool executions to
+//! observability backends. The [`wire`] logger for audit trails, the
+//! Langfuse/LangSmith [`export`]er, and the [`usage`] ledger are always
+//! available; the rest are behind their own feature flags:
+//!
+//! - `tracing`: spans with structured fields on every call, for any `tracing`
+//!   subscriber
+//! - `otel`: OpenTelemetry spans following the GenAI semantic conventions
+//! - `prometheus`: request, error, latency, token, and cost metrics in the
+//!   Prometheus text format
+
+pub mod export;
+#[cfg(feature = "prometheus")]
+pub mod metrics;
+#[cfg(feature = "otel")]
+pub mod otel;
+#[cfg(feature = "tracing")]
+pub(crate) mod spans;
+pub mod usage;
+pub mod wire;
+
+pub use export::{ExportError, ExportResult, TraceExporter};
+#[cfg(feature = "prometheus")]
+pub use metrics::{Metrics, MetricsChatModel, MetricsEmbedding};
+#[cfg(feature = "otel")]
+pub use otel::{OtelChatModel, OtelEmbedding};
+pub use usage::{LedgerRecorder, UsageDimension, UsageLedger, UsageRecord, UsageSummary};
+pub use wire::{ContentPolicy, JsonLinesSink, WireLoggingChatModel, WireRecord, WireSink};
@@ -0,0 +1,318 @@
+//! OpenTelemetry export following the GenAI semantic conventions.
+//!
+//! [`OtelChatModel`] and [`OtelEmbedding`] wrap any model and emit one client
+//! span per call, named `"{operation} {model}"` and carrying the `gen_ai.*`
+//! attributes (operation, provider, request model, token usage, finish
+//! reasons) that Jaeger, Tempo, Datadog and other GenAI-aware backends
+//! understand. Tool executions through a [`ToolRegistry`](crate::tools::ToolRegistry)
+//! get `execute_tool` spans whenever the `otel` feature is enabled.
+//!
+//! Spans go to the global tracer provider unless a tracer is supplied; install
+//! an exporter with `opentelemetry::global::set_tracer_provider`. Model calls
+//! run inside their span's context, so nested spans link up.
+
+use crate::core::messages::{AIMessage, Message, ToolCall};
+use crate::models::base::{BaseChatModel, BaseEmbedding, BoxStream, ModelResult};
+use crate::tools::ToolResult;
+use async_trait::async_trait;
+use futures::StreamExt;
+use opentelemetry::global::{self, BoxedTracer};
+use opentelemetry::trace::{FutureExt, SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::{Array, Context, KeyValue, StringValue, Value};
+use std::future::Future;
+use std::sync::Arc;
+
+/// Instrumentation scope name for spans from this crate
+pub const TRACER_NAME: &str = "agentic_optio_rs";
+
+/// GenAI semantic-convention attribute keys
+pub mod attributes {
+    pub const OPERATION_NAME: &str = "gen_ai.operation.name";
+    pub const PROVIDER_NAME: &str = "gen_ai.provider.name";
+    /// Older name for [`PROVIDER_NAME`], still read by many backends
+    pub const SYSTEM: &str = "gen_ai.system";
+    pub const REQUEST_MODEL: &str = "gen_ai.request.model";
+    pub const RESPONSE_FINISH_REASONS: &str = "gen_ai.response.finish_reasons";
+    pub const USAGE_INPUT_TOKENS: &str = "gen_ai.usage.input_tokens";
+    pub const USAGE_OUTPUT_TOKENS: &str = "gen_ai.usage.output_tokens";
+    pub const EMBEDDINGS_DIMENSION_COUNT: &str = "gen_ai.embeddings.dimension.count";
+    pub const TOOL_NAME: &str = "gen_ai.tool.name";
+    pub const TOOL_CALL_ID: &str = "gen_ai.tool.call.id";
+    pub const TOOL_TYPE: &str = "gen_ai.tool.type";
+    pub const ERROR_TYPE: &str = "error.type";
+}
+
+use attributes::*;
+
+fn global_tracer() -> Arc<BoxedTracer> {
+    Arc::new(global::tracer(TRACER_NAME))
+}
+
+/// Start a span as a child of the current context and return a context
+/// holding it
+fn start_span(
+    tracer: &BoxedTracer,
+    operation: &'static str,
+    target: &str,
+    kind: SpanKind,
+    attributes: Vec<KeyValue>,
+) -> Context {
+    let mut all = vec![KeyValue::new(OPERATION_NAME, operation)];
+    all.extend(attributes);
+    let span = tracer
+        .span_builder(format!("{} {}", operation, target))
+        .with_kind(kind)
+        .with_attributes(all)
+        .start(tracer);
+    Context::current_with_span(span)
+}
+
+fn model_attributes(provider: &str, model: &str) -> Vec<KeyValue> {
+    vec![
+        KeyValue::new(PROVIDER_NAME, provider.to_string()),
+        KeyValue::new(SYSTEM, provider.to_string()),
+        KeyValue::new(REQUEST_MODEL, model.to_string()),
+    ]
+}
+
+fn finish_reasons(reason: &'static str) -> KeyValue {
+    KeyValue::new(
+        RESPONSE_FINISH_REASONS,
+        Value::Array(Array::String(vec![StringValue::from(reason)])),
+    )
+}
+
+/// Mark the span in `cx` failed and end it
+fn end_with_error(cx: &Context, error_type: &'static str, message: String) {
+    let span = cx.span();
+    span.set_attribute(KeyValue::new(ERROR_TYPE, error_type));
+    span.set_status(Status::error(message));
+    span.end();
+}
+
+fn end_chat(cx: &Context, result: &ModelResult<AIMessage>) {
+    let response = match result {
+        Ok(response) => response,
+        Err(e) => return end_with_error(cx, e.kind(), e.to_string()),
+    };
+    let span = cx.span();
+    if let Some(usage) = response.usage {
+        span.set_attribute(KeyValue::new(USAGE_INPUT_TOKENS, usage.input_tokens as i64));
+        span.set_attribute(KeyValue::new(
+            USAGE_OUTPUT_TOKENS,
+            usage.output_tokens as i64,
+        ));
+    }
+    let reason = if response.tool_calls.is_empty() {
+        "stop"
+    } else {
+        "tool_calls"
+    };
+    span.set_attribute(finish_reasons(reason));
+    span.end();
+}
+
+/// Chat model wrapper emitting a GenAI `chat` span per call
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::telemetry::OtelChatModel;
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Install an exporter first with opentelemetry::global::set_tracer_provider
+///     let llm = OtelChatModel::new(Arc::new(OllamaChat::new("llama3.2")));
+///     llm.invoke(&[Message::user("Hello!")]).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct OtelChatModel {
+    inner: Arc<dyn BaseChatModel>,
+    tracer: Arc<BoxedTracer>,
+}
+
+impl OtelChatModel {
+    /// Wrap `inner`, reporting to the global tracer provider
+    pub fn new(inner: Arc<dyn BaseChatModel>) -> Self {
+        Self {
+            inner,
+            tracer: global_tracer(),
+        }
+    }
+
+    /// Report to a specific tracer instead of the global provider
+    pub fn tracer(mut self, tracer: BoxedTracer) -> Self {
+        self.tracer = Arc::new(tracer);
+        self
+    }
+
+    fn start(&self) -> Context {
+        start_span(
+            &self.tracer,
+            "chat",
+            self.model_name(),
+            SpanKind::Client,
+            model_attributes(self.provider_name(), self.model_name()),
+        )
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for OtelChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        let cx = self.start();
+        let result = self.inner.invoke(messages).with_context(cx.clone()).await;
+        end_chat(&cx, &result);
+        result
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        let cx = self.start();
+        let result = self
+            .inner
+            .invoke_with_tools(messages, tools)
+            .with_context(cx.clone())
+            .await;
+        end_chat(&cx, &result);
+        result
+    }
+
+    /// The span stays open until the stream is exhausted or dropped
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let cx = self.start();
+        let inner = match self.inner.stream(messages).with_context(cx.clone()).await {
+            Ok(inner) => inner,
+            Err(e) => {
+                end_with_error(&cx, e.kind(), e.to_string());
+                return Err(e);
+            }
+        };
+
+        let stream =
+            futures::stream::unfold((inner, cx, false), |(mut inner, cx, failed)| async move {
+                if failed {
+                    return None;
+                }
+                match inner.next().with_context(cx.clone()).await {
+                    Some(Ok(chunk)) => Some((Ok(chunk), (inner, cx, false))),
+                    Some(Err(e)) => {
+                        end_with_error(&cx, e.kind(), e.to_string());
+                        Some((Err(e), (inner, cx, true)))
+                    }
+                    None => {
+                        cx.span().set_attribute(finish_reasons("stop"));
+                        cx.span().end();
+                        None
+                    }
+                }
+            });
+        Ok(Box::pin(stream))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
+
+/// Embedding model wrapper emitting a GenAI `embeddings` span per call
+#[derive(Clone)]
+pub struct OtelEmbedding {
+    inner: Arc<dyn BaseEmbedding>,
+    tracer: Arc<BoxedTracer>,
+}
+
+impl OtelEmbedding {
+    /// Wrap `inner`, reporting to the global tracer provider
+    pub fn new(inner: Arc<dyn BaseEmbedding>) -> Self {
+        Self {
+            inner,
+            tracer: global_tracer(),
+        }
+    }
+
+    /// Report to a specific tracer instead of the global provider
+    pub fn tracer(mut self, tracer: BoxedTracer) -> Self {
+        self.tracer = Arc::new(tracer);
+        self
+    }
+}
+
+#[async_trait]
+impl BaseEmbedding for OtelEmbedding {
+    async fn embed(&self, texts: &[String]) -> ModelResult<Vec<Vec<f32>>> {
+        let cx = start_span(
+            &self.tracer,
+            "embeddings",
+            self.model_name(),
+            SpanKind::Client,
+            model_attributes(self.provider_name(), self.model_name()),
+        );
+        let result = self.inner.embed(texts).with_context(cx.clone()).await;
+        match &result {
+            Ok(embeddings) => {
+                if let Some(first) = embeddings.first() {
+                    let dimension = KeyValue::new(EMBEDDINGS_DIMENSION_COUNT, first.len() as i64);
+                    cx.span().set_attribute(dimension);
+                }
+                cx.span().end();
+            }
+            Err(e) => end_with_error(&cx, e.kind(), e.to_string()),
+        }
+        result
+    }
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+}
+
+/// Run a tool execution inside an `execute_tool` span on the global tracer
+pub(crate) async fn trace_tool(
+    call: &ToolCall,
+    execute: impl Future<Output = ToolResult<String>>,
+) -> ToolResult<String> {
+    let cx = start_span(
+        &global::tracer(TRACER_NAME),
+        "execute_tool",
+        &call.name,
+        SpanKind::Internal,
+        vec![
+            KeyValue::new(TOOL_NAME, call.name.clone()),
+            KeyValue::new(TOOL_CALL_ID, call.id.clone()),
+            KeyValue::new(TOOL_TYPE, "function"),
+        ],
+    );
+    let result = execute.with_context(cx.clone()).await;
+    match &result {
+        Ok(_) => cx.span().end(),
+        Err(e) => end_with_error(&cx, "tool_error", e.to_string()),
+    }
+    result
+}
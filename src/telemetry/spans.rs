@@ -0,0 +1,70 @@
+//! Tracing span helpers.
+//!
+//! Instrumented call sites open spans with empty result fields
+//! (`input_tokens`, `output_tokens`, `duration_ms`, ...) and fill them in
+//! through these helpers once the call finishes. Only compiled with the
+//! `tracing` feature.
+
+use crate::core::messages::AIMessage;
+use crate::models::base::{BoxStream, ModelResult};
+use futures::StreamExt;
+use std::time::Instant;
+use tracing::{Instrument, Span};
+
+/// Record token usage, tool calls, and elapsed time of a chat call on the
+/// current span
+#[cfg_attr(not(any(feature = "ollama", feature = "openai")), allow(dead_code))]
+pub(crate) fn record_chat(result: &ModelResult<AIMessage>, started: Instant) {
+    let span = Span::current();
+    if let Ok(response) = result {
+        if let Some(usage) = response.usage {
+            span.record("input_tokens", usage.input_tokens);
+            span.record("output_tokens", usage.output_tokens);
+        }
+        span.record("tool_calls", response.tool_calls.len());
+    }
+    record_elapsed(started);
+}
+
+/// Record `duration_ms` on the current span
+pub(crate) fn record_elapsed(started: Instant) {
+    Span::current().record("duration_ms", started.elapsed().as_millis() as u64);
+}
+
+/// Keep the current span open for the life of a response stream, recording the
+/// chunk count, throughput, and total duration when it ends
+#[cfg_attr(not(any(feature = "ollama", feature = "openai")), allow(dead_code))]
+pub(crate) fn instrument_stream(
+    stream: BoxStream<'_, ModelResult<AIMessage>>,
+    started: Instant,
+) -> BoxStream<'_, ModelResult<AIMessage>> {
+    let span = Span::current();
+    Box::pin(futures::stream::unfold(
+        (stream, span, 0u64),
+        move |(mut stream, span, chunks)| async move {
+            match stream.next().instrument(span.clone()).await {
+                Some(item) => {
+                    match &item {
+                        Ok(AIMessage {
+                            stream_stats: Some(stats),
+                            ..
+                        }) => {
+                            let ttft = stats.time_to_first_token.as_millis() as u64;
+                            span.record("time_to_first_token_ms", ttft);
+                            span.record("tokens_per_second", stats.tokens_per_second());
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::warn!(parent: &span, error = %e, "stream chunk failed"),
+                    }
+                    Some((item, (stream, span, chunks + 1)))
+                }
+                None => {
+                    span.record("chunks", chunks);
+                    span.record("duration_ms", started.elapsed().as_millis() as u64);
+                    tracing::debug!(parent: &span, chunks, "stream finished");
+                    None
+                }
+            }
+        },
+    ))
+}
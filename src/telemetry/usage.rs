@@ -0,0 +1,309 @@
+//! Usage accounting for AgenticOptio.
+//!
+//! A [`UsageLedger`] records the tokens and cost of every model call, tagged
+//! with the model, the agent that made it, a session, and free-form tags.
+//! Totals can be queried at runtime or exported as JSON or CSV for chargeback
+//! reporting. Attach a [`LedgerRecorder`] to agents or chat models as a
+//! callback to fill the ledger.
+
+use crate::agents::{AgentResult, AgentRun};
+use crate::callbacks::CallbackHandler;
+use crate::core::messages::{AIMessage, Message, Usage};
+use crate::core::run::RunContext;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// One model call in the ledger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub model: String,
+    pub agent: Option<String>,
+    pub session: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub usage: Usage,
+    pub cost: f64,
+}
+
+/// Aggregated usage over a set of calls
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageSummary {
+    pub calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_tokens: u64,
+    pub cost: f64,
+}
+
+impl UsageSummary {
+    fn add(&mut self, record: &UsageRecord) {
+        self.calls += 1;
+        self.input_tokens += record.usage.input_tokens as u64;
+        self.output_tokens += record.usage.output_tokens as u64;
+        self.total_tokens += record.usage.total_tokens as u64;
+        self.cost += record.cost;
+    }
+}
+
+/// Attribute usage is grouped by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageDimension {
+    Model,
+    Agent,
+    Session,
+    /// Calls with several tags count towards each of them
+    Tag,
+}
+
+impl UsageDimension {
+    fn keys(self, record: &UsageRecord) -> Vec<&str> {
+        match self {
+            UsageDimension::Model => vec![record.model.as_str()],
+            UsageDimension::Agent => record.agent.as_deref().into_iter().collect(),
+            UsageDimension::Session => record.session.as_deref().into_iter().collect(),
+            UsageDimension::Tag => record.tags.iter().map(String::as_str).collect(),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            UsageDimension::Model => "model",
+            UsageDimension::Agent => "agent",
+            UsageDimension::Session => "session",
+            UsageDimension::Tag => "tag",
+        }
+    }
+}
+
+/// Shared ledger of model usage
+///
+/// Cloning yields another handle to the same ledger.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::core::messages::Usage;
+/// use agentic_optio_rs::telemetry::{UsageDimension, UsageLedger};
+///
+/// let ledger = UsageLedger::new().pricing("gpt-4o", 0.0025, 0.01);
+/// ledger.record("gpt-4o", Usage::new(2000, 500), Some("billing"), &["team-a"]);
+/// ledger.record("llama3.2", Usage::new(100, 50), None, &["team-a"]);
+///
+/// let by_tag = ledger.summary_by(UsageDimension::Tag);
+/// assert_eq!(by_tag["team-a"].calls, 2);
+/// assert!((ledger.total().cost - 0.01).abs() < 1e-9);
+/// println!("{}", ledger.to_csv(UsageDimension::Model));
+/// ```
+#[derive(Clone, Default)]
+pub struct UsageLedger {
+    records: Arc<Mutex<Vec<UsageRecord>>>,
+    /// Cost per 1K input and output tokens, by model
+    pricing: Arc<RwLock<HashMap<String, (f64, f64)>>>,
+}
+
+impl UsageLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Price a model per 1K input and output tokens; unpriced models cost 0
+    pub fn pricing(self, model: impl Into<String>, input_per_1k: f64, output_per_1k: f64) -> Self {
+        self.pricing
+            .write()
+            .unwrap()
+            .insert(model.into(), (input_per_1k, output_per_1k));
+        self
+    }
+
+    /// Callback handler recording model calls into this ledger
+    pub fn recorder(&self) -> LedgerRecorder {
+        LedgerRecorder {
+            ledger: self.clone(),
+            session: None,
+            tags: Vec::new(),
+            agents: Arc::default(),
+        }
+    }
+
+    /// Record a call made outside an agent
+    pub fn record(&self, model: &str, usage: Usage, session: Option<&str>, tags: &[&str]) {
+        self.push(
+            model,
+            usage,
+            None,
+            session.map(String::from),
+            tags.iter().map(|t| t.to_string()).collect(),
+        );
+    }
+
+    fn push(
+        &self,
+        model: &str,
+        usage: Usage,
+        agent: Option<String>,
+        session: Option<String>,
+        tags: Vec<String>,
+    ) {
+        let cost = match self.pricing.read().unwrap().get(model) {
+            Some((input, output)) => {
+                (usage.input_tokens as f64 * input + usage.output_tokens as f64 * output) / 1000.0
+            }
+            None => 0.0,
+        };
+        self.records.lock().unwrap().push(UsageRecord {
+            model: model.to_string(),
+            agent,
+            session,
+            tags,
+            usage,
+            cost,
+        });
+    }
+
+    /// Every recorded call, oldest first
+    pub fn records(&self) -> Vec<UsageRecord> {
+        self.records.lock().unwrap().clone()
+    }
+
+    /// Usage over all calls
+    pub fn total(&self) -> UsageSummary {
+        let mut total = UsageSummary::default();
+        for record in self.records.lock().unwrap().iter() {
+            total.add(record);
+        }
+        total
+    }
+
+    /// Usage grouped by `dimension`; calls without that attribute are left out
+    pub fn summary_by(&self, dimension: UsageDimension) -> BTreeMap<String, UsageSummary> {
+        let mut groups: BTreeMap<String, UsageSummary> = BTreeMap::new();
+        for record in self.records.lock().unwrap().iter() {
+            for key in dimension.keys(record) {
+                groups.entry(key.to_string()).or_default().add(record);
+            }
+        }
+        groups
+    }
+
+    /// Usage of the calls whose `dimension` attribute is `key`
+    pub fn summary_for(&self, dimension: UsageDimension, key: &str) -> UsageSummary {
+        self.summary_by(dimension).remove(key).unwrap_or_default()
+    }
+
+    /// Report with the total and every grouping, as JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&serde_json::json!({
+            "total": self.total(),
+            "by_model": self.summary_by(UsageDimension::Model),
+            "by_agent": self.summary_by(UsageDimension::Agent),
+            "by_session": self.summary_by(UsageDimension::Session),
+            "by_tag": self.summary_by(UsageDimension::Tag),
+        }))
+    }
+
+    /// Usage grouped by `dimension`, as CSV with a header row
+    pub fn to_csv(&self, dimension: UsageDimension) -> String {
+        let mut csv = format!(
+            "{},calls,input_tokens,output_tokens,total_tokens,cost\n",
+            dimension.name()
+        );
+        for (key, summary) in self.summary_by(dimension) {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{:.6}\n",
+                csv_field(&key),
+                summary.calls,
+                summary.input_tokens,
+                summary.output_tokens,
+                summary.total_tokens,
+                summary.cost
+            ));
+        }
+        csv
+    }
+
+    pub fn clear(&self) {
+        self.records.lock().unwrap().clear();
+    }
+}
+
+impl std::fmt::Debug for UsageLedger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UsageLedger")
+            .field("records", &self.records.lock().unwrap().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Callback handler that records each model call in a [`UsageLedger`]
+///
+/// Calls made by an agent are attributed to it; the session and tags apply to
+/// every call this recorder sees.
+#[derive(Clone)]
+pub struct LedgerRecorder {
+    ledger: UsageLedger,
+    session: Option<String>,
+    tags: Vec<String>,
+    /// Names of the agent runs in progress, by run id
+    agents: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl LedgerRecorder {
+    pub fn session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+}
+
+impl CallbackHandler for LedgerRecorder {
+    fn on_agent_start(&self, agent: &str, _messages: &[Message]) {
+        if let Some(context) = RunContext::current() {
+            self.agents
+                .lock()
+                .unwrap()
+                .insert(context.run_id, agent.to_string());
+        }
+    }
+
+    fn on_agent_end(&self, _agent: &str, _result: &AgentResult<AgentRun>) {
+        if let Some(context) = RunContext::current() {
+            self.agents.lock().unwrap().remove(&context.run_id);
+        }
+    }
+
+    fn on_llm_end(&self, model: &str, response: &AIMessage) {
+        let agent = RunContext::current()
+            .and_then(|context| context.parent_run_id)
+            .and_then(|parent| self.agents.lock().unwrap().get(&parent).cloned());
+        self.ledger.push(
+            model,
+            response.usage.unwrap_or_default(),
+            agent,
+            self.session.clone(),
+            self.tags.clone(),
+        );
+    }
+}
+
+impl std::fmt::Debug for LedgerRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LedgerRecorder")
+            .field("session", &self.session)
+            .field("tags", &self.tags)
+            .finish_non_exhaustive()
+    }
+}
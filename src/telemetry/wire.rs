@@ -0,0 +1,336 @@
+//! Wire logging for AgenticOptio.
+//!
+//! [`WireLoggingChatModel`] records every request and response of a chat model
+//! as a [`WireRecord`] and hands it to a [`WireSink`], for audit trails. Records
+//! are sanitized before they reach the sink: credentials (API keys, bearer
+//! tokens, passwords) are always scrubbed, and message content is kept,
+//! PII-redacted, or omitted according to a [`ContentPolicy`].
+
+use crate::core::messages::{AIMessage, BaseMessage, Message, Usage};
+use crate::core::run::RunContext;
+use crate::guardrails::{PiiRedactor, RedactionMap};
+use crate::models::base::{BaseChatModel, BoxStream, ModelResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Credentials scrubbed from every record regardless of the content policy
+const SECRET_PATTERNS: &[(&str, &str)] = &[
+    ("API_KEY", r"\b(?:sk|pk|rk)-[A-Za-z0-9_-]{16,}"),
+    ("API_KEY", r"\bAKIA[0-9A-Z]{16}\b"),
+    ("TOKEN", r"(?i)\bbearer\s+[A-Za-z0-9._~+/-]+=*"),
+    (
+        "SECRET",
+        r#"(?i)\b(?:api[_-]?key|access[_-]?token|secret|password)\b["']?\s*[:=]\s*["']?[^\s"',}]+"#,
+    ),
+];
+
+/// Sanitized record of one model call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WireRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Run the call was made in, see [`RunContext`]
+    #[serde(default)]
+    pub run_id: Option<String>,
+    #[serde(default)]
+    pub trace_id: Option<String>,
+    pub model: String,
+    pub provider: String,
+    /// Request messages in chat API format
+    pub request: Vec<serde_json::Value>,
+    /// Names of the tools offered to the model
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Response message in chat API format, absent when the call failed
+    pub response: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub usage: Option<Usage>,
+    pub duration_ms: u64,
+}
+
+/// Destination for wire records
+///
+/// Sinks are called inline on the calling task, like
+/// [`CallbackHandler`](crate::callbacks::CallbackHandler)s.
+pub trait WireSink: Send + Sync {
+    fn record(&self, record: &WireRecord);
+}
+
+impl<T: WireSink + ?Sized> WireSink for Arc<T> {
+    fn record(&self, record: &WireRecord) {
+        (**self).record(record)
+    }
+}
+
+/// Sink writing one JSON record per line to a writer
+pub struct JsonLinesSink {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl JsonLinesSink {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+        }
+    }
+
+    /// Append records to the file at `path`, creating it if needed
+    pub fn file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self::new(file))
+    }
+}
+
+impl WireSink for JsonLinesSink {
+    /// Write failures are ignored so logging never fails a model call
+    fn record(&self, record: &WireRecord) {
+        if let Ok(line) = serde_json::to_string(record) {
+            let mut writer = self.writer.lock().unwrap();
+            let _ = writeln!(writer, "{}", line).and_then(|_| writer.flush());
+        }
+    }
+}
+
+impl std::fmt::Debug for JsonLinesSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonLinesSink").finish_non_exhaustive()
+    }
+}
+
+/// How message content and tool arguments appear in wire records
+#[derive(Debug, Clone, Default)]
+pub enum ContentPolicy {
+    /// Log content verbatim, apart from credentials
+    #[default]
+    Keep,
+    /// Replace PII with placeholders such as `[EMAIL_1]`
+    Redact(PiiRedactor),
+    /// Replace content with its length only
+    Omit,
+}
+
+/// Chat model wrapper that logs sanitized requests and responses to a sink
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::guardrails::PiiRedactor;
+/// use agentic_optio_rs::telemetry::{ContentPolicy, JsonLinesSink, WireLoggingChatModel};
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let llm = WireLoggingChatModel::new(
+///         Arc::new(OllamaChat::new("llama3.2")),
+///         JsonLinesSink::file("audit.jsonl")?,
+///     )
+///     .content(ContentPolicy::Redact(PiiRedactor::new()));
+///
+///     llm.invoke(&[Message::user("My email is ada@example.com")]).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct WireLoggingChatModel {
+    inner: Arc<dyn BaseChatModel>,
+    sink: Arc<dyn WireSink>,
+    content: ContentPolicy,
+    secrets: PiiRedactor,
+}
+
+impl WireLoggingChatModel {
+    pub fn new(inner: Arc<dyn BaseChatModel>, sink: impl WireSink + 'static) -> Self {
+        let secrets =
+            SECRET_PATTERNS
+                .iter()
+                .fold(PiiRedactor::empty(), |redactor, (label, pattern)| {
+                    redactor
+                        .with_pattern(*label, pattern)
+                        .expect("built-in secret pattern is valid")
+                });
+        Self {
+            inner,
+            sink: Arc::new(sink),
+            content: ContentPolicy::default(),
+            secrets,
+        }
+    }
+
+    /// Set how message content is logged (default: kept verbatim)
+    pub fn content(mut self, content: ContentPolicy) -> Self {
+        self.content = content;
+        self
+    }
+
+    /// Also scrub matches of `pattern` as credentials labelled `label`
+    pub fn secret_pattern(mut self, label: &str, pattern: &str) -> Result<Self, regex::Error> {
+        self.secrets = self.secrets.with_pattern(label, pattern)?;
+        Ok(self)
+    }
+
+    /// Sanitize every string in a chat API message
+    fn sanitize(&self, value: &mut serde_json::Value, map: &mut RedactionMap) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                for (key, field) in fields.iter_mut() {
+                    match (key.as_str(), field) {
+                        ("content" | "arguments", serde_json::Value::String(text)) => {
+                            *text = self.sanitize_content(text, map);
+                        }
+                        (_, field) => self.sanitize(field, map),
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.sanitize(item, map);
+                }
+            }
+            serde_json::Value::String(text) => *text = self.secrets.redact(text, map),
+            _ => {}
+        }
+    }
+
+    fn sanitize_content(&self, text: &str, map: &mut RedactionMap) -> String {
+        match &self.content {
+            ContentPolicy::Keep => self.secrets.redact(text, map),
+            ContentPolicy::Redact(pii) => pii.redact(&self.secrets.redact(text, map), map),
+            ContentPolicy::Omit => format!("[OMITTED {} chars]", text.chars().count()),
+        }
+    }
+
+    fn log(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+        result: Result<&AIMessage, String>,
+        started: Instant,
+    ) {
+        // Placeholders are numbered per record; the mapping is never kept
+        let mut map = RedactionMap::new();
+        let mut sanitize = |mut value: serde_json::Value| {
+            self.sanitize(&mut value, &mut map);
+            value
+        };
+
+        let request = messages.iter().map(|m| sanitize(m.to_dict())).collect();
+        let (response, usage, error) = match result {
+            Ok(response) => (Some(sanitize(response.to_dict())), response.usage, None),
+            Err(error) => (None, None, Some(self.secrets.redact(&error, &mut map))),
+        };
+        let tools = tools
+            .iter()
+            .filter_map(|t| t["function"]["name"].as_str().map(String::from))
+            .collect();
+
+        let context = RunContext::current();
+        self.sink.record(&WireRecord {
+            timestamp: Utc::now(),
+            run_id: context.as_ref().map(|c| c.run_id.clone()),
+            trace_id: context.map(|c| c.trace_id),
+            model: self.model_name().to_string(),
+            provider: self.provider_name().to_string(),
+            request,
+            tools,
+            response,
+            error,
+            usage,
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+
+    fn finish(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+        result: ModelResult<AIMessage>,
+        started: Instant,
+    ) -> ModelResult<AIMessage> {
+        self.log(
+            messages,
+            tools,
+            result.as_ref().map_err(|e| e.to_string()),
+            started,
+        );
+        result
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for WireLoggingChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        let started = Instant::now();
+        let result = self.inner.invoke(messages).await;
+        self.finish(messages, &[], result, started)
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        let started = Instant::now();
+        let result = self.inner.invoke_with_tools(messages, tools).await;
+        self.finish(messages, tools, result, started)
+    }
+
+    /// Streamed responses are logged once, concatenated, when the stream ends
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let started = Instant::now();
+        let inner = match self.inner.stream(messages).await {
+            Ok(inner) => inner,
+            Err(e) => {
+                self.log(messages, &[], Err(e.to_string()), started);
+                return Err(e);
+            }
+        };
+
+        let stream = futures::stream::unfold(
+            (inner, String::new(), false),
+            move |(mut inner, mut content, failed)| async move {
+                if failed {
+                    return None;
+                }
+                match inner.next().await {
+                    Some(Ok(chunk)) => {
+                        content.push_str(&chunk.content);
+                        Some((Ok(chunk), (inner, content, false)))
+                    }
+                    Some(Err(e)) => {
+                        self.log(messages, &[], Err(e.to_string()), started);
+                        Some((Err(e), (inner, content, true)))
+                    }
+                    None => {
+                        self.log(messages, &[], Ok(&AIMessage::new(content)), started);
+                        None
+                    }
+                }
+            },
+        );
+        Ok(Box::pin(stream))
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn ready(&self) -> ModelResult<()> {
+        self.inner.ready().await
+    }
+}
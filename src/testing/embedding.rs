@@ -0,0 +1,99 @@
+//! Deterministic embedding model.
+
+use crate::cache::fnv1a_128;
+use crate::embeddings::l2_normalize;
+use crate::models::base::{BaseEmbedding, ModelResult};
+use async_trait::async_trait;
+
+/// Embedding model producing stable, hash-derived vectors of a fixed dimension
+///
+/// Each lowercased word hashes to a pseudo-random vector, and a text embeds as
+/// the unit-length sum of its words' vectors. The same text always gets the
+/// same vector on every machine, and texts sharing words are closer than
+/// unrelated ones, so similarity search behaves plausibly in tests. Texts
+/// without words embed as the zero vector.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::testing::FakeEmbedding;
+/// use agentic_optio_rs::BaseEmbedding;
+///
+/// # tokio_test::block_on(async {
+/// let embeddings = FakeEmbedding::new(32);
+/// let a = embeddings.embed_query("the quick brown fox").await.unwrap();
+/// let b = embeddings.embed_query("the quick brown fox").await.unwrap();
+/// assert_eq!(a.len(), 32);
+/// assert_eq!(a, b);
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct FakeEmbedding {
+    dimension: usize,
+}
+
+impl FakeEmbedding {
+    /// # Panics
+    ///
+    /// If `dimension` is 0.
+    pub fn new(dimension: usize) -> Self {
+        assert!(dimension > 0, "embedding dimension must be at least 1");
+        Self { dimension }
+    }
+
+    /// Embed one text
+    pub fn embed_text(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0; self.dimension];
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+        {
+            let hash = fnv1a_128(word.to_lowercase().as_bytes());
+            let mut state = (hash >> 64) as u64 ^ hash as u64;
+            for x in vector.iter_mut() {
+                *x += unit_interval(splitmix64(&mut state)) * 2.0 - 1.0;
+            }
+        }
+        l2_normalize(&mut vector);
+        vector
+    }
+}
+
+impl Default for FakeEmbedding {
+    /// 64 dimensions
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+#[async_trait]
+impl BaseEmbedding for FakeEmbedding {
+    async fn embed(&self, texts: &[String]) -> ModelResult<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| self.embed_text(text)).collect())
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn model_name(&self) -> &str {
+        "fake"
+    }
+
+    fn provider_name(&self) -> &str {
+        "fake"
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Map the top 24 bits of `bits` to `[0, 1)`
+fn unit_interval(bits: u64) -> f32 {
+    (bits >> 40) as f32 / (1u64 << 24) as f32
+}
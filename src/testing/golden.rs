@@ -0,0 +1,384 @@
+//! Golden trajectory snapshots for agent regression tests.
+
+use crate::agents::{AgentRun, Transcript, TranscriptEvent};
+use crate::core::messages::Message;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+/// Environment variable that, when set to `1`, makes [`Golden::check`]
+/// overwrite golden files instead of comparing against them
+pub const UPDATE_GOLDEN_ENV: &str = "AGENTIC_OPTIO_UPDATE_GOLDEN";
+
+/// Error type for golden trajectory checks
+#[derive(Debug, thiserror::Error)]
+pub enum GoldenError {
+    #[error("Golden file I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid golden file: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(
+        "Trajectory differs from {} in {} step(s):\n{}\n(set {UPDATE_GOLDEN_ENV}=1 to accept the new trajectory)",
+        path.display(),
+        differences.len(),
+        differences.join("\n")
+    )]
+    Mismatch {
+        path: PathBuf,
+        differences: Vec<String>,
+    },
+}
+
+pub type GoldenResult<T> = Result<T, GoldenError>;
+
+/// One normalized step of an agent run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TrajectoryStep {
+    /// The model asked for a tool
+    ToolCall {
+        name: String,
+        args: serde_json::Value,
+    },
+    /// A tool's output, as the model saw it
+    ToolResult { name: String, output: String },
+    /// Text the model wrote
+    Message { content: String },
+}
+
+impl std::fmt::Display for TrajectoryStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrajectoryStep::ToolCall { name, args } => write!(f, "tool call {name}({args})"),
+            TrajectoryStep::ToolResult { name, output } => {
+                write!(f, "tool result {name}: {output:?}")
+            }
+            TrajectoryStep::Message { content } => write!(f, "message {content:?}"),
+        }
+    }
+}
+
+/// What an agent did, without the details that change from run to run
+///
+/// Tool call ids, token usage, timings, and the request messages are dropped,
+/// and text is trimmed with runs of whitespace collapsed, so two runs making
+/// the same decisions have equal trajectories.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Trajectory {
+    pub steps: Vec<TrajectoryStep>,
+}
+
+impl Trajectory {
+    /// Trajectory of a recorded run
+    pub fn from_transcript(transcript: &Transcript) -> Self {
+        let mut steps = Vec::new();
+        for event in &transcript.events {
+            match event {
+                TranscriptEvent::ModelCall { response, .. } => {
+                    push_message(&mut steps, &response.content);
+                    for call in &response.tool_calls {
+                        steps.push(TrajectoryStep::ToolCall {
+                            name: call.name.clone(),
+                            args: call.args.clone(),
+                        });
+                    }
+                }
+                TranscriptEvent::ToolCall { call, output, .. } => {
+                    steps.push(TrajectoryStep::ToolResult {
+                        name: call.name.clone(),
+                        output: normalize(output),
+                    });
+                }
+            }
+        }
+        Self { steps }
+    }
+
+    /// Trajectory of the assistant and tool messages of a conversation
+    pub fn from_messages(messages: &[Message]) -> Self {
+        let mut steps = Vec::new();
+        let mut tool_names = HashMap::new();
+        for message in messages {
+            match message {
+                Message::AI(ai) => {
+                    push_message(&mut steps, &ai.content);
+                    for call in &ai.tool_calls {
+                        tool_names.insert(call.id.as_str(), call.name.as_str());
+                        steps.push(TrajectoryStep::ToolCall {
+                            name: call.name.clone(),
+                            args: call.args.clone(),
+                        });
+                    }
+                }
+                Message::Tool(tool) => steps.push(TrajectoryStep::ToolResult {
+                    name: tool_names
+                        .get(tool.tool_call_id.as_str())
+                        .map_or_else(|| tool.tool_call_id.clone(), |n| n.to_string()),
+                    output: normalize(&tool.content),
+                }),
+                Message::System(_) | Message::Human(_) => {}
+            }
+        }
+        Self { steps }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl From<&AgentRun> for Trajectory {
+    fn from(run: &AgentRun) -> Self {
+        Self::from_messages(&run.messages)
+    }
+}
+
+impl From<&Transcript> for Trajectory {
+    fn from(transcript: &Transcript) -> Self {
+        Self::from_transcript(transcript)
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn push_message(steps: &mut Vec<TrajectoryStep>, content: &str) {
+    let content = normalize(content);
+    if !content.is_empty() {
+        steps.push(TrajectoryStep::Message { content });
+    }
+}
+
+/// How model-written text and tool outputs are compared
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextMatch {
+    /// Equal after whitespace normalization
+    Exact,
+    /// Equal ignoring case
+    IgnoreCase,
+    /// Word-set Jaccard similarity of at least the threshold, from 0.0 to 1.0
+    Similar(f32),
+    /// Any text matches
+    Ignore,
+}
+
+impl TextMatch {
+    fn matches(self, golden: &str, actual: &str) -> bool {
+        match self {
+            TextMatch::Exact => golden == actual,
+            TextMatch::IgnoreCase => golden.to_lowercase() == actual.to_lowercase(),
+            TextMatch::Similar(threshold) => jaccard(golden, actual) >= threshold,
+            TextMatch::Ignore => true,
+        }
+    }
+}
+
+fn jaccard(a: &str, b: &str) -> f32 {
+    let words = |text: &str| -> BTreeSet<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f32 / a.union(&b).count() as f32
+}
+
+/// Whether a trajectory was compared against or written to its golden file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoldenOutcome {
+    /// The trajectory matched the golden file
+    Matched,
+    /// No golden file existed, so one was written
+    Created,
+    /// The golden file was overwritten because of [`UPDATE_GOLDEN_ENV`]
+    Updated,
+}
+
+/// Golden file an agent's trajectory must keep matching
+///
+/// The first [`check`](Self::check) writes the file; later checks compare
+/// against it and fail with every differing step. Steps are aligned by edit
+/// distance, so an inserted or dropped step is reported once rather than
+/// shifting everything after it, and up to [`max_edits`](Self::max_edits)
+/// such differences are tolerated.
+///
+/// Tool names always have to match. By default tool arguments, tool outputs,
+/// and model messages must match exactly; loosen the comparison for
+/// nondeterministic models.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::testing::{Golden, TextMatch};
+/// use agentic_optio_rs::{Agent, OllamaChat};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let agent = Agent::builder(Arc::new(OllamaChat::new("llama3.2"))).build();
+///     let run = agent.run("What is 2 + 3?").await?;
+///
+///     Golden::new("tests/golden/add.json")
+///         .messages(TextMatch::Similar(0.6))
+///         .max_edits(1)
+///         .check(&run)?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Golden {
+    path: PathBuf,
+    messages: TextMatch,
+    tool_outputs: TextMatch,
+    compare_args: bool,
+    max_edits: usize,
+}
+
+impl Golden {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            messages: TextMatch::Exact,
+            tool_outputs: TextMatch::Exact,
+            compare_args: true,
+            max_edits: 0,
+        }
+    }
+
+    /// How model-written messages are compared
+    pub fn messages(mut self, messages: TextMatch) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// How tool outputs are compared
+    pub fn tool_outputs(mut self, tool_outputs: TextMatch) -> Self {
+        self.tool_outputs = tool_outputs;
+        self
+    }
+
+    /// Compare tool calls by name only
+    pub fn ignore_args(mut self) -> Self {
+        self.compare_args = false;
+        self
+    }
+
+    /// Differing, missing, or extra steps tolerated before failing
+    pub fn max_edits(mut self, max_edits: usize) -> Self {
+        self.max_edits = max_edits;
+        self
+    }
+
+    /// Compare `trajectory` with the golden file, writing the file if it does
+    /// not exist or [`UPDATE_GOLDEN_ENV`] is `1`
+    pub fn check(&self, trajectory: impl Into<Trajectory>) -> GoldenResult<GoldenOutcome> {
+        let actual = trajectory.into();
+        let update = std::env::var(UPDATE_GOLDEN_ENV).is_ok_and(|v| v == "1");
+        if update || !self.path.exists() {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&self.path, actual.to_json()? + "\n")?;
+            return Ok(if update {
+                GoldenOutcome::Updated
+            } else {
+                GoldenOutcome::Created
+            });
+        }
+
+        let golden: Trajectory = serde_json::from_str(&std::fs::read_to_string(&self.path)?)?;
+        let differences = self.diff(&golden, &actual);
+        if differences.len() > self.max_edits {
+            return Err(GoldenError::Mismatch {
+                path: self.path.clone(),
+                differences,
+            });
+        }
+        Ok(GoldenOutcome::Matched)
+    }
+
+    /// Differences between two trajectories under this comparison, one line
+    /// per edit
+    pub fn diff(&self, golden: &Trajectory, actual: &Trajectory) -> Vec<String> {
+        let (g, a) = (&golden.steps, &actual.steps);
+        // distance[i][j]: edits turning the first i golden steps into the
+        // first j actual ones
+        let mut distance = vec![vec![0usize; a.len() + 1]; g.len() + 1];
+        for (i, row) in distance.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for (j, cell) in distance[0].iter_mut().enumerate() {
+            *cell = j;
+        }
+        for i in 1..=g.len() {
+            for j in 1..=a.len() {
+                let substitute = usize::from(!self.step_matches(&g[i - 1], &a[j - 1]));
+                distance[i][j] = (distance[i - 1][j - 1] + substitute)
+                    .min(distance[i - 1][j] + 1)
+                    .min(distance[i][j - 1] + 1);
+            }
+        }
+
+        let mut differences = Vec::new();
+        let (mut i, mut j) = (g.len(), a.len());
+        while i > 0 || j > 0 {
+            if i > 0 && j > 0 {
+                let same = self.step_matches(&g[i - 1], &a[j - 1]);
+                if distance[i][j] == distance[i - 1][j - 1] + usize::from(!same) {
+                    if !same {
+                        differences.push(format!(
+                            "step {j}: expected {}, got {}",
+                            g[i - 1],
+                            a[j - 1]
+                        ));
+                    }
+                    i -= 1;
+                    j -= 1;
+                    continue;
+                }
+            }
+            if i > 0 && distance[i][j] == distance[i - 1][j] + 1 {
+                differences.push(format!("missing step after {j}: {}", g[i - 1]));
+                i -= 1;
+            } else {
+                differences.push(format!("unexpected step {j}: {}", a[j - 1]));
+                j -= 1;
+            }
+        }
+        differences.reverse();
+        differences
+    }
+
+    fn step_matches(&self, golden: &TrajectoryStep, actual: &TrajectoryStep) -> bool {
+        match (golden, actual) {
+            (
+                TrajectoryStep::ToolCall { name, args },
+                TrajectoryStep::ToolCall {
+                    name: actual_name,
+                    args: actual_args,
+                },
+            ) => name == actual_name && (!self.compare_args || args == actual_args),
+            (
+                TrajectoryStep::ToolResult { name, output },
+                TrajectoryStep::ToolResult {
+                    name: actual_name,
+                    output: actual_output,
+                },
+            ) => name == actual_name && self.tool_outputs.matches(output, actual_output),
+            (
+                TrajectoryStep::Message { content },
+                TrajectoryStep::Message {
+                    content: actual_content,
+                },
+            ) => self.messages.matches(content, actual_content),
+            _ => false,
+        }
+    }
+}
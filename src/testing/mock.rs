@@ -0,0 +1,237 @@
+//! Scripted chat model.
+
+use crate::core::messages::{AIMessage, Message, ToolCall};
+use crate::models::base::{BaseChatModel, BoxStream, ModelError, ModelResult};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Chat model that answers with scripted responses, tool calls, and errors in
+/// order, recording every request it receives
+///
+/// Each call takes the next step of the script; once the script is exhausted
+/// calls fail with [`ModelError::ApiError`]. Streaming yields the response
+/// content a word at a time, with any tool calls and usage on the last chunk.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::agents::Agent;
+/// use agentic_optio_rs::testing::MockChatModel;
+/// use std::sync::Arc;
+///
+/// # tokio_test::block_on(async {
+/// let model = Arc::new(
+///     MockChatModel::new()
+///         .respond_tool_call("search", serde_json::json!({"query": "rust"}))
+///         .respond("Rust is a systems language"),
+/// );
+/// let agent = Agent::builder(model.clone()).build();
+///
+/// let run = agent.run("What is Rust?").await.unwrap();
+/// assert_eq!(run.output, "Rust is a systems language");
+/// assert_eq!(model.calls(), 2);
+/// # });
+/// ```
+pub struct MockChatModel {
+    script: Mutex<VecDeque<Step>>,
+    received: Mutex<Vec<Vec<Message>>>,
+    received_tools: Mutex<Vec<Vec<serde_json::Value>>>,
+    latency: Duration,
+    name: String,
+    tool_calls: usize,
+}
+
+impl MockChatModel {
+    pub fn new() -> Self {
+        Self {
+            script: Mutex::new(VecDeque::new()),
+            received: Mutex::new(Vec::new()),
+            received_tools: Mutex::new(Vec::new()),
+            latency: Duration::ZERO,
+            name: "mock".to_string(),
+            tool_calls: 0,
+        }
+    }
+
+    /// Model answering with each of `responses` in turn
+    pub fn with_responses<I, S>(responses: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        responses
+            .into_iter()
+            .fold(Self::new(), |model, response| model.respond(response))
+    }
+
+    /// Model answering with each of `messages` in turn
+    pub fn with_messages(messages: impl IntoIterator<Item = AIMessage>) -> Self {
+        messages.into_iter().fold(Self::new(), Self::respond_with)
+    }
+
+    /// Answer the next call with `content`
+    pub fn respond(self, content: impl Into<String>) -> Self {
+        self.respond_with(AIMessage::new(content))
+    }
+
+    /// Answer the next call with `message`, e.g. one carrying usage
+    pub fn respond_with(self, message: AIMessage) -> Self {
+        self.script
+            .lock()
+            .unwrap()
+            .push_back(Step::Respond(message));
+        self
+    }
+
+    /// Answer the next call with a single call to tool `name`
+    ///
+    /// Call ids are `call_1`, `call_2`, ... in script order.
+    pub fn respond_tool_call(mut self, name: impl Into<String>, args: serde_json::Value) -> Self {
+        self.tool_calls += 1;
+        let call = ToolCall {
+            id: format!("call_{}", self.tool_calls),
+            name: name.into(),
+            args,
+        };
+        self.respond_with(AIMessage::with_tool_calls("", vec![call]))
+    }
+
+    /// Fail the next call with `error`
+    pub fn fail(self, error: ModelError) -> Self {
+        self.script.lock().unwrap().push_back(Step::Fail(error));
+        self
+    }
+
+    /// Break off the next call with `error` after producing `partial`
+    ///
+    /// A stream yields `partial` a word at a time and then the error; an
+    /// invoke just fails.
+    pub fn interrupt(self, partial: impl Into<String>, error: ModelError) -> Self {
+        self.script
+            .lock()
+            .unwrap()
+            .push_back(Step::Interrupt(partial.into(), error));
+        self
+    }
+
+    /// Wait `latency` before answering each call
+    pub fn latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Model name reported to callbacks and telemetry, `"mock"` by default
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Message lists received by each call, in order
+    pub fn received(&self) -> Vec<Vec<Message>> {
+        self.received.lock().unwrap().clone()
+    }
+
+    /// Messages of the most recent call
+    pub fn last_messages(&self) -> Option<Vec<Message>> {
+        self.received.lock().unwrap().last().cloned()
+    }
+
+    /// Tool schemas offered with each call, empty for calls without tools
+    pub fn received_tools(&self) -> Vec<Vec<serde_json::Value>> {
+        self.received_tools.lock().unwrap().clone()
+    }
+
+    /// Number of calls received so far
+    pub fn calls(&self) -> usize {
+        self.received.lock().unwrap().len()
+    }
+
+    /// Number of scripted steps not yet used
+    pub fn remaining(&self) -> usize {
+        self.script.lock().unwrap().len()
+    }
+
+    async fn next(&self, messages: &[Message], tools: &[serde_json::Value]) -> Step {
+        self.received.lock().unwrap().push(messages.to_vec());
+        self.received_tools.lock().unwrap().push(tools.to_vec());
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+        self.script.lock().unwrap().pop_front().unwrap_or_else(|| {
+            Step::Fail(ModelError::ApiError("mock script exhausted".to_string()))
+        })
+    }
+}
+
+/// One scripted answer
+enum Step {
+    Respond(AIMessage),
+    Fail(ModelError),
+    Interrupt(String, ModelError),
+}
+
+impl Step {
+    fn into_result(self) -> ModelResult<AIMessage> {
+        match self {
+            Step::Respond(message) => Ok(message),
+            Step::Fail(error) | Step::Interrupt(_, error) => Err(error),
+        }
+    }
+}
+
+fn words(content: &str) -> impl Iterator<Item = ModelResult<AIMessage>> + '_ {
+    content
+        .split_inclusive(' ')
+        .map(|word| Ok(AIMessage::new(word)))
+}
+
+impl Default for MockChatModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl BaseChatModel for MockChatModel {
+    async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+        self.next(messages, &[]).await.into_result()
+    }
+
+    async fn invoke_with_tools(
+        &self,
+        messages: &[Message],
+        tools: &[serde_json::Value],
+    ) -> ModelResult<AIMessage> {
+        self.next(messages, tools).await.into_result()
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        let response = match self.next(messages, &[]).await {
+            Step::Interrupt(partial, error) => {
+                let chunks: Vec<_> = words(&partial).chain([Err(error)]).collect();
+                return Ok(Box::pin(futures::stream::iter(chunks)));
+            }
+            step => step.into_result()?,
+        };
+        let mut chunks: Vec<ModelResult<AIMessage>> = words(&response.content).collect();
+        if !response.tool_calls.is_empty() || response.usage.is_some() {
+            let mut last = AIMessage::with_tool_calls("", response.tool_calls);
+            last.usage = response.usage;
+            chunks.push(Ok(last));
+        }
+        Ok(Box::pin(futures::stream::iter(chunks)))
+    }
+
+    fn model_name(&self) -> &str {
+        &self.name
+    }
+
+    fn provider_name(&self) -> &str {
+        "mock"
+    }
+}
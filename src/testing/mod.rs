@@ -0,0 +1,16 @@
+//! Test doubles for AgenticOptio.
+//!
+//! Deterministic stand-ins for model providers, so agents, chains, and tools
+//! can be unit tested without a running Ollama or network access, or against
+//! provider responses recorded once with a [`Vcr`]. [`Golden`] snapshots an
+//! agent's trajectory to catch behavioral regressions.
+
+pub mod embedding;
+pub mod golden;
+pub mod mock;
+pub mod vcr;
+
+pub use embedding::FakeEmbedding;
+pub use golden::{Golden, GoldenError, GoldenOutcome, TextMatch, Trajectory, TrajectoryStep};
+pub use mock::MockChatModel;
+pub use vcr::{Cassette, Vcr, VcrBuilder, VcrError, VcrMode};
@@ -0,0 +1,423 @@
+//! Record/replay HTTP fixtures.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Environment variable overriding the mode of [`Vcr::start`]: `record`,
+/// `replay`, or `auto`
+pub const VCR_MODE_ENV: &str = "AGENTIC_OPTIO_VCR";
+
+/// Error type for record/replay fixtures
+#[derive(Debug, thiserror::Error)]
+pub enum VcrError {
+    #[error("Cassette I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid cassette: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Cassette not found: {0} (record it with {VCR_MODE_ENV}=record)")]
+    MissingCassette(PathBuf),
+}
+
+pub type VcrResult<T> = Result<T, VcrError>;
+
+/// Whether a [`Vcr`] talks to the real upstream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcrMode {
+    /// Forward every request upstream and rewrite the cassette with the
+    /// exchanges
+    Record,
+    /// Answer only from the cassette, failing if it does not exist
+    Replay,
+    /// Replay if the cassette exists, otherwise record it
+    Auto,
+}
+
+impl VcrMode {
+    /// Mode named by [`VCR_MODE_ENV`], or [`Auto`](Self::Auto)
+    pub fn from_env() -> Self {
+        match std::env::var(VCR_MODE_ENV).as_deref() {
+            Ok("record") => VcrMode::Record,
+            Ok("replay") => VcrMode::Replay,
+            _ => VcrMode::Auto,
+        }
+    }
+}
+
+/// One recorded request and its response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path and query string
+    pub path: String,
+    /// JSON bodies are stored parsed, others as a string
+    #[serde(default)]
+    pub body: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Whole body, including every chunk of a streamed response
+    pub body: String,
+}
+
+/// Fixture file of recorded interactions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn load(path: impl AsRef<Path>) -> VcrResult<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> VcrResult<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+}
+
+/// Local HTTP server that records a provider's responses into a cassette file
+/// and replays them later
+///
+/// Point a model's host or base URL at [`url`](Self::url). When recording,
+/// requests are forwarded to the upstream and each exchange is appended to the
+/// cassette as it completes; when replaying, each request is answered with the
+/// first unused recorded interaction with the same method, path, and body, or
+/// the last one once all are used. Unmatched requests get a 400 response
+/// naming the request, which the model reports as an error without retrying.
+///
+/// Request headers are forwarded but never recorded, so API keys stay out of
+/// fixtures. Streamed responses are recorded whole and replayed in one piece.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::testing::Vcr;
+/// use agentic_optio_rs::{BaseChatModel, Message, OllamaChat};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Records from Ollama on the first run, replays offline afterwards
+///     let vcr = Vcr::start("tests/fixtures/hello.json", "http://localhost:11434").await?;
+///     let llm = OllamaChat::builder("llama3.2").host(vcr.url()).build();
+///     let response = llm.invoke(&[Message::user("Hello")]).await?;
+///     println!("{}", response.content);
+///     Ok(())
+/// }
+/// ```
+pub struct Vcr {
+    url: String,
+    mode: VcrMode,
+    state: Arc<Mutex<State>>,
+    server: tokio::task::JoinHandle<()>,
+}
+
+struct State {
+    path: PathBuf,
+    recording: bool,
+    cassette: Cassette,
+    used: Vec<bool>,
+}
+
+impl Vcr {
+    /// Start a server for the cassette at `path` in the mode given by
+    /// [`VcrMode::from_env`]
+    pub async fn start(path: impl Into<PathBuf>, upstream: impl Into<String>) -> VcrResult<Self> {
+        Self::builder(path, upstream).start().await
+    }
+
+    pub fn builder(path: impl Into<PathBuf>, upstream: impl Into<String>) -> VcrBuilder {
+        VcrBuilder {
+            path: path.into(),
+            upstream: upstream.into(),
+            mode: VcrMode::from_env(),
+        }
+    }
+
+    /// Base URL of the local server, e.g. `http://127.0.0.1:41234`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// [`Record`](VcrMode::Record) or [`Replay`](VcrMode::Replay), with
+    /// [`Auto`](VcrMode::Auto) resolved
+    pub fn mode(&self) -> VcrMode {
+        self.mode
+    }
+
+    /// Interactions recorded or loaded so far
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.state.lock().unwrap().cassette.interactions.clone()
+    }
+}
+
+impl Drop for Vcr {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+impl std::fmt::Debug for Vcr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Vcr")
+            .field("url", &self.url)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+pub struct VcrBuilder {
+    path: PathBuf,
+    upstream: String,
+    mode: VcrMode,
+}
+
+impl VcrBuilder {
+    /// Mode to run in, overriding [`VCR_MODE_ENV`]
+    pub fn mode(mut self, mode: VcrMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Load the cassette if replaying and start serving on a free local port
+    pub async fn start(self) -> VcrResult<Vcr> {
+        let mode = match self.mode {
+            VcrMode::Auto if self.path.exists() => VcrMode::Replay,
+            VcrMode::Auto => VcrMode::Record,
+            mode => mode,
+        };
+        let cassette = match mode {
+            VcrMode::Replay if !self.path.exists() => {
+                return Err(VcrError::MissingCassette(self.path))
+            }
+            VcrMode::Replay => Cassette::load(&self.path)?,
+            _ => Cassette::default(),
+        };
+        let used = vec![false; cassette.interactions.len()];
+        let state = Arc::new(Mutex::new(State {
+            path: self.path,
+            recording: mode == VcrMode::Record,
+            cassette,
+            used,
+        }));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let upstream = Arc::new(self.upstream.trim_end_matches('/').to_string());
+        let server_state = state.clone();
+        let server = tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(serve(socket, server_state.clone(), upstream.clone()));
+            }
+        });
+        Ok(Vcr {
+            url,
+            mode,
+            state,
+            server,
+        })
+    }
+}
+
+struct IncomingRequest {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+/// Answer requests on one keep-alive connection until the client closes it
+async fn serve(mut socket: TcpStream, state: Arc<Mutex<State>>, upstream: Arc<String>) {
+    let mut buffer = Vec::new();
+    while let Ok(Some(request)) = read_request(&mut socket, &mut buffer).await {
+        let recording = state.lock().unwrap().recording;
+        let response = if recording {
+            record(&request, &state, &upstream).await
+        } else {
+            replay(&request, &state)
+        };
+        let reason = reqwest::StatusCode::from_u16(response.status)
+            .ok()
+            .and_then(|s| s.canonical_reason())
+            .unwrap_or("");
+        let mut head = format!("HTTP/1.1 {} {}\r\n", response.status, reason);
+        if let Some(content_type) = &response.content_type {
+            head.push_str(&format!("content-type: {content_type}\r\n"));
+        }
+        head.push_str(&format!("content-length: {}\r\n\r\n", response.body.len()));
+        if socket.write_all(head.as_bytes()).await.is_err()
+            || socket.write_all(response.body.as_bytes()).await.is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Read one request, returning `None` once the client closes the connection
+async fn read_request(
+    socket: &mut TcpStream,
+    buffer: &mut Vec<u8>,
+) -> std::io::Result<Option<IncomingRequest>> {
+    let mut chunk = [0u8; 8192];
+    let end = loop {
+        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        let n = socket.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    };
+    let head = String::from_utf8_lossy(&buffer[..end]).to_string();
+    buffer.drain(..end + 4);
+
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or("/").to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+    let length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    while buffer.len() < length {
+        let n = socket.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    }
+    let body = buffer.drain(..length).collect();
+    Ok(Some(IncomingRequest {
+        method,
+        path,
+        headers,
+        body,
+    }))
+}
+
+fn recorded_request(request: &IncomingRequest) -> RecordedRequest {
+    let body = if request.body.is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_slice(&request.body).unwrap_or_else(|_| {
+            serde_json::Value::String(String::from_utf8_lossy(&request.body).to_string())
+        })
+    };
+    RecordedRequest {
+        method: request.method.clone(),
+        path: request.path.clone(),
+        body,
+    }
+}
+
+async fn record(
+    request: &IncomingRequest,
+    state: &Mutex<State>,
+    upstream: &str,
+) -> RecordedResponse {
+    let response = match forward(request, upstream).await {
+        Ok(response) => response,
+        Err(e) => {
+            return RecordedResponse {
+                status: 502,
+                content_type: Some("text/plain".to_string()),
+                body: format!("VCR could not reach {upstream}: {e}"),
+            }
+        }
+    };
+
+    let mut state = state.lock().unwrap();
+    state.cassette.interactions.push(Interaction {
+        request: recorded_request(request),
+        response: response.clone(),
+    });
+    state.used.push(true);
+    if let Err(e) = state.cassette.save(&state.path) {
+        return RecordedResponse {
+            status: 400,
+            content_type: Some("text/plain".to_string()),
+            body: format!("VCR could not save {}: {e}", state.path.display()),
+        };
+    }
+    response
+}
+
+async fn forward(request: &IncomingRequest, upstream: &str) -> reqwest::Result<RecordedResponse> {
+    let method =
+        reqwest::Method::from_bytes(request.method.as_bytes()).unwrap_or(reqwest::Method::GET);
+    let mut builder = crate::models::http::shared_client()
+        .request(method, format!("{upstream}{}", request.path))
+        .body(request.body.clone());
+    for (name, value) in &request.headers {
+        if !matches!(name.as_str(), "host" | "content-length" | "connection") {
+            builder = builder.header(name, value);
+        }
+    }
+    let response = builder.send().await?;
+    let status = response.status().as_u16();
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    let body = String::from_utf8_lossy(&response.bytes().await?).to_string();
+    Ok(RecordedResponse {
+        status,
+        content_type,
+        body,
+    })
+}
+
+fn replay(request: &IncomingRequest, state: &Mutex<State>) -> RecordedResponse {
+    let wanted = recorded_request(request);
+    let mut state = state.lock().unwrap();
+    let matches: Vec<usize> = (0..state.cassette.interactions.len())
+        .filter(|&i| state.cassette.interactions[i].request == wanted)
+        .collect();
+    let index = matches
+        .iter()
+        .copied()
+        .find(|&i| !state.used[i])
+        .or_else(|| matches.last().copied());
+    match index {
+        Some(i) => {
+            state.used[i] = true;
+            state.cassette.interactions[i].response.clone()
+        }
+        None => RecordedResponse {
+            status: 400,
+            content_type: Some("text/plain".to_string()),
+            body: format!(
+                "no recorded interaction for {} {} in {}",
+                wanted.method,
+                wanted.path,
+                state.path.display()
+            ),
+        },
+    }
+}
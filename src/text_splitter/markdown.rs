@@ -0,0 +1,75 @@
+//! Markdown-aware text splitter.
+
+use crate::text_splitter::{RecursiveCharacterSplitter, Span, TextSplitter};
+use regex::Regex;
+
+const MARKDOWN_SEPARATORS: &[&str] = &["\n```", "\n\n", "\n", " ", ""];
+
+/// Splits markdown into heading sections, then sections into chunks on code
+/// fences, paragraphs, lines, and words
+///
+/// Chunks never span two sections. Split documents also get a `section`
+/// metadata entry holding the heading of the section each chunk came from.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::text_splitter::{MarkdownSplitter, TextSplitter};
+/// use agentic_optio_rs::Document;
+///
+/// let doc = Document::new("# Install\nRun cargo add.\n\n# Usage\nCall invoke.");
+/// let chunks = MarkdownSplitter::new(30).split_documents(&[doc]);
+/// assert_eq!(chunks[1].content, "# Usage\nCall invoke.");
+/// assert_eq!(chunks[1].metadata["section"], "Usage");
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarkdownSplitter {
+    inner: RecursiveCharacterSplitter,
+    heading: Regex,
+}
+
+impl MarkdownSplitter {
+    pub fn new(chunk_size: usize) -> Self {
+        Self {
+            inner: RecursiveCharacterSplitter::new(chunk_size)
+                .separators(MARKDOWN_SEPARATORS.iter().copied()),
+            heading: Regex::new(r"(?m)^#{1,6}[ \t]+(.+?)[ \t#]*$").expect("valid heading regex"),
+        }
+    }
+
+    /// Characters shared between consecutive chunks
+    pub fn chunk_overlap(mut self, chunk_overlap: usize) -> Self {
+        self.inner = self.inner.chunk_overlap(chunk_overlap);
+        self
+    }
+}
+
+impl TextSplitter for MarkdownSplitter {
+    fn split_spans(&self, text: &str) -> Vec<Span> {
+        let mut bounds: Vec<usize> = self.heading.find_iter(text).map(|m| m.start()).collect();
+        if bounds.first() != Some(&0) {
+            bounds.insert(0, 0);
+        }
+        bounds.push(text.len());
+
+        bounds
+            .windows(2)
+            .flat_map(|w| {
+                let (start, end) = (w[0], w[1]);
+                self.inner
+                    .split_spans(&text[start..end])
+                    .into_iter()
+                    .map(move |(s, e)| (start + s, start + e))
+            })
+            .collect()
+    }
+
+    fn chunk_metadata(&self, text: &str, span: Span) -> Vec<(String, serde_json::Value)> {
+        self.heading
+            .captures_iter(text)
+            .take_while(|c| c.get(0).is_some_and(|m| m.start() <= span.0))
+            .last()
+            .map(|c| vec![("section".to_string(), c[1].to_string().into())])
+            .unwrap_or_default()
+    }
+}
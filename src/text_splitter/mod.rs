@@ -0,0 +1,212 @@
+//! Text splitters for AgenticOptio.
+//!
+//! Splitters break long text into overlapping chunks sized for embedding and
+//! retrieval. Every chunk records where it came from: split documents carry
+//! `start_index` and `end_index` metadata holding byte offsets into the source
+//! document's content.
+
+pub mod markdown;
+pub mod token;
+
+pub use markdown::MarkdownSplitter;
+pub use token::TokenSplitter;
+
+use crate::core::documents::Document;
+
+/// Byte range of a chunk within the source text
+pub type Span = (usize, usize);
+
+/// Base trait for all text splitters
+pub trait TextSplitter: Send + Sync {
+    /// Byte ranges of each chunk, in order
+    fn split_spans(&self, text: &str) -> Vec<Span>;
+
+    fn split_text(&self, text: &str) -> Vec<String> {
+        self.split_spans(text)
+            .into_iter()
+            .map(|(start, end)| text[start..end].to_string())
+            .collect()
+    }
+
+    /// Extra metadata for the chunk at `span`; none by default
+    fn chunk_metadata(&self, text: &str, span: Span) -> Vec<(String, serde_json::Value)> {
+        let _ = (text, span);
+        Vec::new()
+    }
+
+    /// Split documents into chunks that inherit the source metadata and add
+    /// `start_index` / `end_index` offsets
+    fn split_documents(&self, documents: &[Document]) -> Vec<Document> {
+        let mut chunks = Vec::new();
+        for document in documents {
+            for span in self.split_spans(&document.content) {
+                let mut chunk = Document {
+                    id: None,
+                    content: document.content[span.0..span.1].to_string(),
+                    metadata: document.metadata.clone(),
+                };
+                for (key, value) in self.chunk_metadata(&document.content, span) {
+                    chunk.metadata.insert(key, value);
+                }
+                chunk
+                    .metadata
+                    .insert("start_index".to_string(), span.0.into());
+                chunk
+                    .metadata
+                    .insert("end_index".to_string(), span.1.into());
+                chunks.push(chunk);
+            }
+        }
+        chunks
+    }
+}
+
+const DEFAULT_SEPARATORS: &[&str] = &["\n\n", "\n", " ", ""];
+
+/// Splits on a hierarchy of separators, falling back to finer ones only for
+/// pieces still over the chunk size
+///
+/// Sizes are measured in characters.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::text_splitter::{RecursiveCharacterSplitter, TextSplitter};
+///
+/// let splitter = RecursiveCharacterSplitter::new(40).chunk_overlap(10);
+/// let chunks = splitter.split_text("First paragraph here.\n\nSecond paragraph, a bit longer.");
+/// assert_eq!(chunks, vec!["First paragraph here.", "Second paragraph, a bit longer."]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecursiveCharacterSplitter {
+    chunk_size: usize,
+    chunk_overlap: usize,
+    separators: Vec<String>,
+}
+
+impl RecursiveCharacterSplitter {
+    pub fn new(chunk_size: usize) -> Self {
+        Self {
+            chunk_size: chunk_size.max(1),
+            chunk_overlap: 0,
+            separators: DEFAULT_SEPARATORS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Characters shared between consecutive chunks
+    pub fn chunk_overlap(mut self, chunk_overlap: usize) -> Self {
+        self.chunk_overlap = chunk_overlap;
+        self
+    }
+
+    /// Separators to try, coarsest first; an empty string splits on characters
+    pub fn separators<S: Into<String>>(mut self, separators: impl IntoIterator<Item = S>) -> Self {
+        self.separators = separators.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Split `text[start..end]` into pieces no longer than the chunk size
+    fn atomize(&self, text: &str, start: usize, end: usize, level: usize, out: &mut Vec<Span>) {
+        if char_len(&text[start..end]) <= self.chunk_size {
+            out.push((start, end));
+            return;
+        }
+
+        let separator = match self.separators.get(level) {
+            Some(sep) if !sep.is_empty() => sep.as_str(),
+            _ => {
+                split_chars(text, start, end, self.chunk_size, out);
+                return;
+            }
+        };
+
+        // Separators stay attached to the piece that follows them.
+        let mut cuts: Vec<usize> = text[start..end]
+            .match_indices(separator)
+            .map(|(i, _)| start + i)
+            .filter(|&i| i > start)
+            .collect();
+        if cuts.is_empty() {
+            self.atomize(text, start, end, level + 1, out);
+            return;
+        }
+        cuts.push(end);
+
+        let mut piece_start = start;
+        for cut in cuts {
+            if char_len(&text[piece_start..cut]) <= self.chunk_size {
+                out.push((piece_start, cut));
+            } else {
+                self.atomize(text, piece_start, cut, level + 1, out);
+            }
+            piece_start = cut;
+        }
+    }
+}
+
+impl TextSplitter for RecursiveCharacterSplitter {
+    fn split_spans(&self, text: &str) -> Vec<Span> {
+        let mut pieces = Vec::new();
+        if !text.is_empty() {
+            self.atomize(text, 0, text.len(), 0, &mut pieces);
+        }
+        merge_pieces(text, &pieces, self.chunk_size, self.chunk_overlap)
+    }
+}
+
+fn char_len(s: &str) -> usize {
+    s.chars().count()
+}
+
+/// Split `text[start..end]` into runs of at most `size` characters
+fn split_chars(text: &str, start: usize, end: usize, size: usize, out: &mut Vec<Span>) {
+    let mut piece_start = start;
+    for (count, (i, _)) in text[start..end].char_indices().enumerate() {
+        if count > 0 && count % size == 0 {
+            out.push((piece_start, start + i));
+            piece_start = start + i;
+        }
+    }
+    out.push((piece_start, end));
+}
+
+/// Greedily join contiguous pieces into chunks of at most `size` characters,
+/// starting each chunk with up to `overlap` characters of trailing pieces from
+/// the previous one
+pub(crate) fn merge_pieces(text: &str, pieces: &[Span], size: usize, overlap: usize) -> Vec<Span> {
+    let lengths: Vec<usize> = pieces.iter().map(|&(s, e)| char_len(&text[s..e])).collect();
+    let mut chunks = Vec::new();
+    let mut i = 0;
+
+    while i < pieces.len() {
+        let mut j = i;
+        let mut len = 0;
+        while j < pieces.len() && (j == i || len + lengths[j] <= size) {
+            len += lengths[j];
+            j += 1;
+        }
+        if let Some(span) = trim_span(text, pieces[i].0, pieces[j - 1].1) {
+            chunks.push(span);
+        }
+        if j == pieces.len() {
+            break;
+        }
+
+        let mut k = j;
+        let mut shared = 0;
+        while k > i + 1 && shared + lengths[k - 1] <= overlap {
+            shared += lengths[k - 1];
+            k -= 1;
+        }
+        i = k;
+    }
+    chunks
+}
+
+/// Shrink a span to exclude surrounding whitespace; `None` if nothing remains
+pub(crate) fn trim_span(text: &str, start: usize, end: usize) -> Option<Span> {
+    let slice = &text[start..end];
+    let trimmed_start = start + (slice.len() - slice.trim_start().len());
+    let trimmed_end = end - (slice.len() - slice.trim_end().len());
+    (trimmed_start < trimmed_end).then_some((trimmed_start, trimmed_end))
+}
@@ -0,0 +1,88 @@
+//! Token-based text splitter.
+
+use crate::text_splitter::{trim_span, Span, TextSplitter};
+use regex::Regex;
+use std::sync::Arc;
+
+type Tokenizer = dyn Fn(&str) -> Vec<Span> + Send + Sync;
+
+/// Splits text into windows of a fixed number of tokens
+///
+/// Token boundaries come from a tokenizer returning the byte span of each token.
+/// The default approximates subword tokenizers by treating runs of word
+/// characters and individual punctuation marks as tokens; plug in a model
+/// tokenizer with [`TokenSplitter::tokenizer`] for exact counts.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::text_splitter::{TextSplitter, TokenSplitter};
+///
+/// let splitter = TokenSplitter::new(3).chunk_overlap(1);
+/// let chunks = splitter.split_text("one two three four five");
+/// assert_eq!(chunks, vec!["one two three", "three four five"]);
+/// ```
+#[derive(Clone)]
+pub struct TokenSplitter {
+    chunk_size: usize,
+    chunk_overlap: usize,
+    tokenizer: Arc<Tokenizer>,
+}
+
+impl TokenSplitter {
+    pub fn new(chunk_size: usize) -> Self {
+        let word = Regex::new(r"\w+|[^\w\s]").expect("valid token regex");
+        Self {
+            chunk_size: chunk_size.max(1),
+            chunk_overlap: 0,
+            tokenizer: Arc::new(move |text: &str| {
+                word.find_iter(text).map(|m| (m.start(), m.end())).collect()
+            }),
+        }
+    }
+
+    /// Tokens shared between consecutive chunks
+    pub fn chunk_overlap(mut self, chunk_overlap: usize) -> Self {
+        self.chunk_overlap = chunk_overlap;
+        self
+    }
+
+    /// Use a custom tokenizer returning the byte span of each token
+    pub fn tokenizer(
+        mut self,
+        tokenizer: impl Fn(&str) -> Vec<Span> + Send + Sync + 'static,
+    ) -> Self {
+        self.tokenizer = Arc::new(tokenizer);
+        self
+    }
+}
+
+impl TextSplitter for TokenSplitter {
+    fn split_spans(&self, text: &str) -> Vec<Span> {
+        let tokens = (self.tokenizer)(text);
+        let stride = self.chunk_size.saturating_sub(self.chunk_overlap).max(1);
+
+        let mut chunks = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            let j = (i + self.chunk_size).min(tokens.len());
+            if let Some(span) = trim_span(text, tokens[i].0, tokens[j - 1].1) {
+                chunks.push(span);
+            }
+            if j == tokens.len() {
+                break;
+            }
+            i += stride;
+        }
+        chunks
+    }
+}
+
+impl std::fmt::Debug for TokenSplitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenSplitter")
+            .field("chunk_size", &self.chunk_size)
+            .field("chunk_overlap", &self.chunk_overlap)
+            .finish()
+    }
+}
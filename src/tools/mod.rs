@@ -0,0 +1,214 @@
+//! Tools for AgenticOptio.
+//!
+//! Tools are functions an agent can call. Each tool exposes an OpenAI-compatible
+//! function schema and an async `call` taking the model-generated JSON arguments.
+//! [`sql`] has tools for answering questions from a database.
+
+pub mod sql;
+
+use crate::core::messages::ToolCall;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use std::sync::Arc;
+
+/// Error type for tool operations
+#[derive(Debug, thiserror::Error)]
+pub enum ToolError {
+    #[error("Tool not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid arguments: {0}")]
+    InvalidArguments(String),
+
+    #[error("Tool execution failed: {0}")]
+    ExecutionFailed(String),
+}
+
+pub type ToolResult<T> = Result<T, ToolError>;
+
+/// Base trait for all tools
+#[async_trait]
+pub trait BaseTool: Send + Sync {
+    /// Unique tool name the model uses to call it
+    fn name(&self) -> &str;
+
+    /// Description shown to the model
+    fn description(&self) -> &str;
+
+    /// JSON schema for the tool arguments
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    /// Execute the tool with model-generated arguments
+    async fn call(&self, args: serde_json::Value) -> ToolResult<String>;
+
+    /// OpenAI-compatible function schema
+    fn to_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": self.name(),
+                "description": self.description(),
+                "parameters": self.parameters()
+            }
+        })
+    }
+}
+
+type ToolFn = dyn Fn(serde_json::Value) -> BoxFuture<'static, ToolResult<String>> + Send + Sync;
+
+/// Tool backed by an async closure
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::tools::FunctionTool;
+///
+/// let tool = FunctionTool::new("echo", "Echo the input back", |args| async move {
+///     Ok(args["text"].as_str().unwrap_or_default().to_string())
+/// });
+/// ```
+pub struct FunctionTool {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+    func: Arc<ToolFn>,
+}
+
+impl FunctionTool {
+    pub fn new<F, Fut>(name: impl Into<String>, description: impl Into<String>, func: F) -> Self
+    where
+        F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ToolResult<String>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+            func: Arc::new(move |args| Box::pin(func(args))),
+        }
+    }
+
+    /// Set the JSON schema for the tool arguments
+    pub fn with_parameters(mut self, parameters: serde_json::Value) -> Self {
+        self.parameters = parameters;
+        self
+    }
+}
+
+#[async_trait]
+impl BaseTool for FunctionTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        self.parameters.clone()
+    }
+
+    async fn call(&self, args: serde_json::Value) -> ToolResult<String> {
+        (self.func)(args).await
+    }
+}
+
+/// Collection of tools available to an agent
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: Vec<Arc<dyn BaseTool>>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a tool, replacing any existing tool with the same name
+    pub fn register(&mut self, tool: impl BaseTool + 'static) {
+        self.register_arc(Arc::new(tool));
+    }
+
+    /// Register a shared tool, replacing any existing tool with the same name
+    pub fn register_arc(&mut self, tool: Arc<dyn BaseTool>) {
+        self.tools.retain(|t| t.name() != tool.name());
+        self.tools.push(tool);
+    }
+
+    /// Add every tool from another registry
+    pub fn extend(&mut self, other: ToolRegistry) {
+        for tool in other.tools {
+            self.register_arc(tool);
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn BaseTool>> {
+        self.tools.iter().find(|t| t.name() == name)
+    }
+
+    pub fn tools(&self) -> &[Arc<dyn BaseTool>] {
+        &self.tools
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Function schemas for every registered tool
+    pub fn schemas(&self) -> Vec<serde_json::Value> {
+        self.tools.iter().map(|t| t.to_schema()).collect()
+    }
+
+    /// Execute a model-issued tool call
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "tool",
+            skip_all,
+            err,
+            fields(
+                tool = %call.name,
+                call_id = %call.id,
+                duration_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn call(&self, call: &ToolCall) -> ToolResult<String> {
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+        let execute = async {
+            match self.get(&call.name) {
+                Some(tool) => tool.call(call.args.clone()).await,
+                None => Err(ToolError::NotFound(call.name.clone())),
+            }
+        };
+        #[cfg(feature = "otel")]
+        let result = crate::telemetry::otel::trace_tool(call, execute).await;
+        #[cfg(not(feature = "otel"))]
+        let result = execute.await;
+        #[cfg(feature = "tracing")]
+        crate::telemetry::spans::record_elapsed(started);
+        result
+    }
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.tools.iter().map(|t| t.name()))
+            .finish()
+    }
+}
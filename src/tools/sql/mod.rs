@@ -0,0 +1,377 @@
+//! SQL tools for AgenticOptio.
+//!
+//! A [`SqlToolkit`] gives an agent three tools over a [`SqlDatabase`]: list
+//! the tables, describe some of them, and run a read-only query whose result
+//! is cut off at a row limit. [`SqlToolkit::agent`] starts an agent builder
+//! already set up to answer questions about the database. `sqlite` enables
+//! [`SqliteDatabase`].
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteDatabase;
+
+use crate::agents::{AgentBuilder, AgentRun};
+use crate::core::messages::Message;
+use crate::models::base::BaseChatModel;
+use crate::tools::{FunctionTool, ToolError, ToolRegistry};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Error type for SQL databases
+#[derive(Debug, thiserror::Error)]
+pub enum SqlError {
+    #[error("Query rejected: {0}")]
+    Rejected(String),
+
+    #[error("Unknown table: {0}")]
+    UnknownTable(String),
+
+    #[error("Database error: {0}")]
+    Backend(String),
+}
+
+impl From<SqlError> for ToolError {
+    fn from(err: SqlError) -> Self {
+        ToolError::ExecutionFailed(err.to_string())
+    }
+}
+
+pub type SqlResult<T> = Result<T, SqlError>;
+
+/// Rows returned by a query, up to the row limit
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryRows {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    /// Whether the query returned more rows than were kept
+    pub truncated: bool,
+}
+
+impl QueryRows {
+    /// Rows as a pipe-separated table, with a note when rows were cut off
+    pub fn to_text(&self) -> String {
+        if self.columns.is_empty() {
+            return "The query returned no columns.".to_string();
+        }
+        let mut text = self.columns.join(" | ");
+        for row in &self.rows {
+            text.push('\n');
+            let cells: Vec<String> = row
+                .iter()
+                .map(|value| match value {
+                    Value::Null => "NULL".to_string(),
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect();
+            text.push_str(&cells.join(" | "));
+        }
+        if self.rows.is_empty() {
+            text.push_str("\n(no rows)");
+        } else if self.truncated {
+            text.push_str(&format!(
+                "\n(only the first {} rows are shown; aggregate or add a LIMIT to see the rest)",
+                self.rows.len()
+            ));
+        }
+        text
+    }
+}
+
+/// Database an agent can inspect and query
+#[async_trait]
+pub trait SqlDatabase: Send + Sync {
+    /// SQL dialect name shown to the model, like `SQLite`
+    fn dialect(&self) -> &str;
+
+    /// Names of the tables and views, sorted
+    async fn table_names(&self) -> SqlResult<Vec<String>>;
+
+    /// Definition of `table`, with a few sample rows if the backend gives
+    /// them
+    async fn describe_table(&self, table: &str) -> SqlResult<String>;
+
+    /// Run a read-only query and keep at most `max_rows` rows
+    ///
+    /// Implementations should call [`check_read_only`] first, but must also
+    /// run the query read-only in the database, since the check only catches
+    /// obvious writes.
+    async fn query(&self, sql: &str, max_rows: usize) -> SqlResult<QueryRows>;
+}
+
+/// Statements that write and can follow or sit inside a `WITH` clause, plus
+/// `INTO`, which `SELECT ... INTO` uses to create a table
+const WRITE_KEYWORDS: &[&str] = &["insert", "update", "delete", "merge", "into"];
+
+/// Reject anything but a single `SELECT`, `WITH`, or `VALUES` statement, or
+/// one that writes through a `WITH` clause or `SELECT ... INTO`
+///
+/// String literals, quoted identifiers, and comments are ignored, so a query
+/// may still search for the word `delete` in a column. Other words are not
+/// checked, since functions and columns like `replace` or `set` are common;
+/// backends should also run queries read-only, which is what stops anything
+/// this check misses.
+pub fn check_read_only(sql: &str) -> SqlResult<()> {
+    let code = strip_literals(sql);
+    let statement = code.trim().trim_end_matches(';').trim();
+    if statement.contains(';') {
+        return Err(SqlError::Rejected(
+            "only one statement may be run at a time".to_string(),
+        ));
+    }
+    let words: Vec<String> = statement
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    match words.first().map(String::as_str) {
+        Some("select" | "with" | "values") => {}
+        _ => {
+            return Err(SqlError::Rejected(
+                "only SELECT queries are allowed".to_string(),
+            ))
+        }
+    }
+    match words
+        .iter()
+        .find(|word| WRITE_KEYWORDS.contains(&word.as_str()))
+    {
+        Some(word) => Err(SqlError::Rejected(format!(
+            "{} is not allowed in a read-only query",
+            word.to_uppercase()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// `sql` with string literals, quoted identifiers, and comments blanked out
+fn strip_literals(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                let close = c;
+                while let Some(next) = chars.next() {
+                    if next == close {
+                        // A doubled quote is an escaped one
+                        if chars.peek() == Some(&close) {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                out.push_str(" '' ");
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+                out.push(' ');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Tools for inspecting and querying a [`SqlDatabase`]
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::tools::sql::{SqlDatabase, SqlToolkit};
+/// use agentic_optio_rs::OllamaChat;
+/// use std::sync::Arc;
+///
+/// async fn ask(db: Arc<dyn SqlDatabase>) -> Result<(), Box<dyn std::error::Error>> {
+///     let agent = SqlToolkit::new(db)
+///         .max_rows(20)
+///         .agent(Arc::new(OllamaChat::new("llama3.2")))
+///         .build();
+///
+///     let run = agent.run("Which three customers spent the most last month?").await?;
+///     println!("{}", run.output);
+///     for query in SqlToolkit::queries(&run) {
+///         println!("ran: {query}");
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SqlToolkit {
+    db: Arc<dyn SqlDatabase>,
+    max_rows: usize,
+}
+
+impl SqlToolkit {
+    /// Toolkit returning at most 50 rows per query
+    pub fn new(db: Arc<dyn SqlDatabase>) -> Self {
+        Self { db, max_rows: 50 }
+    }
+
+    /// Most rows a query result shows the model (default 50)
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows.max(1);
+        self
+    }
+
+    /// `sql_list_tables`, `sql_describe_tables`, and `sql_query`
+    pub fn tools(&self) -> ToolRegistry {
+        let mut tools = ToolRegistry::new();
+
+        let db = self.db.clone();
+        tools.register(FunctionTool::new(
+            "sql_list_tables",
+            "List the tables in the database.",
+            move |_| {
+                let db = db.clone();
+                async move { Ok(db.table_names().await?.join(", ")) }
+            },
+        ));
+
+        let db = self.db.clone();
+        tools.register(
+            FunctionTool::new(
+                "sql_describe_tables",
+                "Show the columns and a few sample rows of the given tables. \
+                 Call this before querying a table.",
+                move |args| {
+                    let db = db.clone();
+                    async move {
+                        let tables = tables_arg(&args)?;
+                        let mut descriptions = Vec::with_capacity(tables.len());
+                        for table in tables {
+                            descriptions.push(db.describe_table(&table).await?);
+                        }
+                        Ok(descriptions.join("\n\n"))
+                    }
+                },
+            )
+            .with_parameters(json!({
+                "type": "object",
+                "properties": {
+                    "tables": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Table names from sql_list_tables"
+                    }
+                },
+                "required": ["tables"]
+            })),
+        );
+
+        let db = self.db.clone();
+        let max_rows = self.max_rows;
+        tools.register(
+            FunctionTool::new(
+                "sql_query",
+                format!(
+                    "Run one read-only {} SELECT query and return the result rows, \
+                     at most {max_rows} of them.",
+                    self.db.dialect()
+                ),
+                move |args| {
+                    let db = db.clone();
+                    async move {
+                        let sql = args["query"].as_str().ok_or_else(|| {
+                            ToolError::InvalidArguments("\"query\" must be a string".to_string())
+                        })?;
+                        Ok(db.query(sql, max_rows).await?.to_text())
+                    }
+                },
+            )
+            .with_parameters(json!({
+                "type": "object",
+                "properties": {
+                    "query": {"type": "string", "description": "A single SELECT statement"}
+                },
+                "required": ["query"]
+            })),
+        );
+
+        tools
+    }
+
+    /// System prompt for an agent answering questions with these tools
+    pub fn system_prompt(&self) -> String {
+        format!(
+            "You answer questions about a {dialect} database. List the tables, describe \
+             the ones you need, then write read-only SELECT queries to find the answer. \
+             Never try to change data. Query results show at most {max_rows} rows, so let \
+             the database do the work with WHERE, GROUP BY, COUNT, and SUM instead of \
+             reading rows one by one. If a query fails, fix it and try again.\n\n\
+             Answer with a short plain-language summary of what the results show, \
+             including the numbers that answer the question. Do not show SQL unless \
+             asked, and say so if the data cannot answer the question.",
+            dialect = self.db.dialect(),
+            max_rows = self.max_rows
+        )
+    }
+
+    /// Agent builder with these tools and [`system_prompt`](Self::system_prompt)
+    pub fn agent(&self, model: Arc<dyn BaseChatModel>) -> AgentBuilder {
+        AgentBuilder::new(model)
+            .name("sql")
+            .system_prompt(self.system_prompt())
+            .tools(self.tools())
+            .max_iterations(12)
+    }
+
+    /// Queries the agent ran during `run`, in order
+    pub fn queries(run: &AgentRun) -> Vec<String> {
+        run.messages
+            .iter()
+            .filter_map(|message| match message {
+                Message::AI(ai) => Some(&ai.tool_calls),
+                _ => None,
+            })
+            .flatten()
+            .filter(|call| call.name == "sql_query")
+            .filter_map(|call| call.args["query"].as_str().map(str::to_string))
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for SqlToolkit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqlToolkit")
+            .field("dialect", &self.db.dialect())
+            .field("max_rows", &self.max_rows)
+            .finish()
+    }
+}
+
+fn tables_arg(args: &Value) -> Result<Vec<String>, ToolError> {
+    let invalid = || ToolError::InvalidArguments("\"tables\" must be a list of names".to_string());
+    match &args["tables"] {
+        Value::Array(tables) => tables
+            .iter()
+            .map(|t| t.as_str().map(str::to_string).ok_or_else(invalid))
+            .collect(),
+        // Some models send a single comma-separated string
+        Value::String(tables) => Ok(tables
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()),
+        _ => Err(invalid()),
+    }
+}
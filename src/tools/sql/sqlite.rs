@@ -0,0 +1,194 @@
+//! SQLite database for the SQL tools.
+//!
+//! Enabled with the `sqlite` feature.
+
+use crate::tools::sql::{check_read_only, QueryRows, SqlDatabase, SqlError, SqlResult};
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use serde_json::Value;
+use sqlx::pool::PoolConnection;
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteConnection, SqlitePool, SqlitePoolOptions, SqliteRow,
+};
+use sqlx::{Column, Connection, Executor, Row, Sqlite, Statement, TypeInfo, ValueRef};
+use std::path::Path;
+
+/// SQLite database the SQL tools can inspect and query
+///
+/// Queries must pass [`check_read_only`] and run with `PRAGMA query_only`
+/// set, in a transaction that is rolled back afterwards; a database opened
+/// with [`open`](Self::open) is also opened read-only. A query cancelled
+/// midway closes its connection rather than return it to the pool read-only.
+#[derive(Debug, Clone)]
+pub struct SqliteDatabase {
+    pool: SqlitePool,
+    sample_rows: usize,
+}
+
+impl SqliteDatabase {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            sample_rows: 3,
+        }
+    }
+
+    /// Open the existing database file at `path` read-only
+    pub async fn open(path: impl AsRef<Path>) -> SqlResult<Self> {
+        let options = SqliteConnectOptions::new().filename(path).read_only(true);
+        let pool = SqlitePoolOptions::new()
+            .connect_with(options)
+            .await
+            .map_err(backend_error)?;
+        Ok(Self::new(pool))
+    }
+
+    /// Rows shown with each table description (default 3)
+    pub fn sample_rows(mut self, rows: usize) -> Self {
+        self.sample_rows = rows;
+        self
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    async fn fetch(&self, sql: &str, max_rows: usize) -> SqlResult<QueryRows> {
+        let mut guard = QueryOnly {
+            connection: self.pool.acquire().await.map_err(backend_error)?,
+            restored: false,
+        };
+        sqlx::query("PRAGMA query_only = ON")
+            .execute(&mut *guard.connection)
+            .await
+            .map_err(backend_error)?;
+        let result = fetch_rolled_back(&mut guard.connection, sql, max_rows).await;
+        sqlx::query("PRAGMA query_only = OFF")
+            .execute(&mut *guard.connection)
+            .await
+            .map_err(backend_error)?;
+        guard.restored = true;
+        result
+    }
+}
+
+/// Pooled connection that is closed instead of returned to the pool unless
+/// `query_only` was switched back off, e.g. when a query is cancelled
+struct QueryOnly {
+    connection: PoolConnection<Sqlite>,
+    restored: bool,
+}
+
+impl Drop for QueryOnly {
+    fn drop(&mut self) {
+        if !self.restored {
+            self.connection.close_on_drop();
+        }
+    }
+}
+
+/// Rows of `sql` read in a transaction that is then rolled back
+async fn fetch_rolled_back(
+    connection: &mut SqliteConnection,
+    sql: &str,
+    max_rows: usize,
+) -> SqlResult<QueryRows> {
+    let mut tx = connection.begin().await.map_err(backend_error)?;
+    let statement = (&mut *tx).prepare(sql).await.map_err(backend_error)?;
+    let mut result = QueryRows {
+        columns: statement
+            .columns()
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect(),
+        rows: Vec::new(),
+        truncated: false,
+    };
+    {
+        let mut rows = statement.query().fetch(&mut *tx);
+        while let Some(row) = rows.try_next().await.map_err(backend_error)? {
+            if result.rows.len() == max_rows {
+                result.truncated = true;
+                break;
+            }
+            result.rows.push(values(&row));
+        }
+    }
+    tx.rollback().await.map_err(backend_error)?;
+    Ok(result)
+}
+
+#[async_trait]
+impl SqlDatabase for SqliteDatabase {
+    fn dialect(&self) -> &str {
+        "SQLite"
+    }
+
+    async fn table_names(&self) -> SqlResult<Vec<String>> {
+        sqlx::query_scalar(
+            "SELECT name FROM sqlite_master \
+             WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(backend_error)
+    }
+
+    async fn describe_table(&self, table: &str) -> SqlResult<String> {
+        let definition: Option<String> = sqlx::query_scalar(
+            "SELECT sql FROM sqlite_master \
+             WHERE type IN ('table', 'view') AND name = ? AND name NOT LIKE 'sqlite_%'",
+        )
+        .bind(table)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(backend_error)?;
+        let mut description =
+            definition.ok_or_else(|| SqlError::UnknownTable(table.to_string()))?;
+
+        if self.sample_rows > 0 {
+            let quoted = format!("\"{}\"", table.replace('"', "\"\""));
+            let sample = self
+                .fetch(&format!("SELECT * FROM {quoted}"), self.sample_rows)
+                .await?;
+            if !sample.rows.is_empty() {
+                description.push_str(&format!(
+                    "\n\nSample rows from {table}:\n{}",
+                    sample.to_text()
+                ));
+            }
+        }
+        Ok(description)
+    }
+
+    async fn query(&self, sql: &str, max_rows: usize) -> SqlResult<QueryRows> {
+        check_read_only(sql)?;
+        self.fetch(sql, max_rows).await
+    }
+}
+
+/// Every column of `row` as JSON, by the type of the stored value
+fn values(row: &SqliteRow) -> Vec<Value> {
+    (0..row.len())
+        .map(|i| {
+            let kind = match row.try_get_raw(i) {
+                Ok(raw) if raw.is_null() => return Value::Null,
+                Ok(raw) => raw.type_info().name().to_string(),
+                Err(_) => return Value::Null,
+            };
+            match kind.as_str() {
+                "INTEGER" | "BOOLEAN" => row.try_get::<i64, _>(i).map(Value::from),
+                "REAL" => row.try_get::<f64, _>(i).map(Value::from),
+                "BLOB" => row
+                    .try_get::<Vec<u8>, _>(i)
+                    .map(|bytes| Value::from(format!("<{} bytes>", bytes.len()))),
+                _ => row.try_get::<String, _>(i).map(Value::from),
+            }
+            .unwrap_or(Value::Null)
+        })
+        .collect()
+}
+
+fn backend_error(e: sqlx::Error) -> SqlError {
+    SqlError::Backend(e.to_string())
+}
@@ -0,0 +1,333 @@
+//! Hierarchical navigable small world (HNSW) graph index.
+//!
+//! Approximate nearest-neighbour search by cosine similarity in roughly
+//! logarithmic time, following Malkov & Yashunin (2016). The index stores only
+//! graph links; vectors stay with the caller and are passed in on every call,
+//! addressed by their position.
+
+use crate::embeddings::similarity::cosine;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+/// HNSW tuning parameters
+///
+/// Larger `m` and `ef_construction` build a better graph more slowly and with
+/// more memory; larger `ef_search` trades query speed for recall.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::vectorstores::HnswConfig;
+///
+/// let config = HnswConfig::default().m(32).ef_search(128);
+/// assert_eq!(config.ef_construction, 200);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HnswConfig {
+    /// Links per node on upper layers; layer 0 keeps twice as many
+    pub m: usize,
+    /// Candidate list size while inserting
+    pub ef_construction: usize,
+    /// Candidate list size while searching; raised to `k` when smaller
+    pub ef_search: usize,
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 64,
+        }
+    }
+}
+
+impl HnswConfig {
+    pub fn m(mut self, m: usize) -> Self {
+        self.m = m.max(2);
+        self
+    }
+
+    pub fn ef_construction(mut self, ef_construction: usize) -> Self {
+        self.ef_construction = ef_construction.max(1);
+        self
+    }
+
+    pub fn ef_search(mut self, ef_search: usize) -> Self {
+        self.ef_search = ef_search.max(1);
+        self
+    }
+}
+
+/// Node id paired with its distance to the current query
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    distance: f32,
+    node: usize,
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+#[derive(Debug, Clone)]
+struct Node {
+    /// Neighbour lists, one per layer from 0 up to the node's level
+    links: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct HnswIndex {
+    config: HnswConfig,
+    nodes: Vec<Node>,
+    entry_point: Option<usize>,
+    level_mult: f64,
+    rng: u64,
+}
+
+impl HnswIndex {
+    pub(crate) fn new(config: HnswConfig) -> Self {
+        Self {
+            config,
+            nodes: Vec::new(),
+            entry_point: None,
+            level_mult: 1.0 / (config.m as f64).ln(),
+            rng: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    pub(crate) fn config(&self) -> HnswConfig {
+        self.config
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Link the next vector, `vectors[self.len()]`, into the graph
+    pub(crate) fn insert<V: AsRef<[f32]>>(&mut self, vectors: &[V]) {
+        let node = self.nodes.len();
+        let level = self.random_level();
+        self.nodes.push(Node {
+            links: vec![Vec::new(); level + 1],
+        });
+
+        let Some(mut entry) = self.entry_point else {
+            self.entry_point = Some(node);
+            return;
+        };
+        let query = vectors[node].as_ref();
+        let top = self.nodes[entry].links.len() - 1;
+
+        // Greedy descent through layers above the new node's level
+        for layer in (level + 1..=top).rev() {
+            entry = self.greedy_closest(query, entry, layer, vectors);
+        }
+
+        let mut entries = vec![entry];
+        for layer in (0..=level.min(top)).rev() {
+            let found =
+                self.search_layer(query, &entries, self.config.ef_construction, layer, vectors);
+            let neighbours = self.select_neighbours(&found, self.max_links(layer), vectors);
+            self.nodes[node].links[layer] = neighbours.clone();
+
+            for neighbour in neighbours {
+                self.nodes[neighbour].links[layer].push(node);
+                if self.nodes[neighbour].links[layer].len() > self.max_links(layer) {
+                    self.prune(neighbour, layer, vectors);
+                }
+            }
+            entries = found.iter().map(|c| c.node).collect();
+        }
+
+        if level > top {
+            self.entry_point = Some(node);
+        }
+    }
+
+    /// Up to `k` nearest nodes accepted by `keep`, closest first, as
+    /// `(node, similarity)`
+    ///
+    /// Rejected nodes are still traversed, so filtering never disconnects the
+    /// graph, but a selective filter may return fewer than `k` results.
+    pub(crate) fn search<V: AsRef<[f32]>>(
+        &self,
+        query: &[f32],
+        k: usize,
+        vectors: &[V],
+        keep: impl Fn(usize) -> bool,
+    ) -> Vec<(usize, f32)> {
+        let Some(mut entry) = self.entry_point else {
+            return Vec::new();
+        };
+        for layer in (1..self.nodes[entry].links.len()).rev() {
+            entry = self.greedy_closest(query, entry, layer, vectors);
+        }
+
+        self.search_layer(query, &[entry], k.max(self.config.ef_search), 0, vectors)
+            .into_iter()
+            .filter(|c| keep(c.node))
+            .take(k)
+            .map(|c| (c.node, 1.0 - c.distance))
+            .collect()
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.config.m * 2
+        } else {
+            self.config.m
+        }
+    }
+
+    /// Geometric level draw from a deterministic xorshift generator
+    fn random_level(&mut self) -> usize {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        let uniform = ((self.rng >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        (-uniform.ln() * self.level_mult) as usize
+    }
+
+    fn greedy_closest<V: AsRef<[f32]>>(
+        &self,
+        query: &[f32],
+        mut current: usize,
+        layer: usize,
+        vectors: &[V],
+    ) -> usize {
+        let mut best = distance(query, vectors[current].as_ref());
+        loop {
+            let mut improved = false;
+            for &neighbour in &self.nodes[current].links[layer] {
+                let d = distance(query, vectors[neighbour].as_ref());
+                if d < best {
+                    best = d;
+                    current = neighbour;
+                    improved = true;
+                }
+            }
+            if !improved {
+                return current;
+            }
+        }
+    }
+
+    /// Best-first search of one layer, returning up to `ef` nodes closest first
+    fn search_layer<V: AsRef<[f32]>>(
+        &self,
+        query: &[f32],
+        entries: &[usize],
+        ef: usize,
+        layer: usize,
+        vectors: &[V],
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = entries.iter().copied().collect();
+        // Min-heap of nodes to expand and max-heap of the best found so far
+        let mut frontier = BinaryHeap::new();
+        let mut found = BinaryHeap::new();
+        for &node in entries {
+            let candidate = Candidate {
+                distance: distance(query, vectors[node].as_ref()),
+                node,
+            };
+            frontier.push(std::cmp::Reverse(candidate));
+            found.push(candidate);
+        }
+
+        while let Some(std::cmp::Reverse(current)) = frontier.pop() {
+            let worst = found
+                .peek()
+                .map_or(f32::INFINITY, |c: &Candidate| c.distance);
+            if current.distance > worst && found.len() >= ef {
+                break;
+            }
+            for &neighbour in &self.nodes[current.node].links[layer] {
+                if !visited.insert(neighbour) {
+                    continue;
+                }
+                let d = distance(query, vectors[neighbour].as_ref());
+                let worst = found.peek().map_or(f32::INFINITY, |c| c.distance);
+                if found.len() < ef || d < worst {
+                    let candidate = Candidate {
+                        distance: d,
+                        node: neighbour,
+                    };
+                    frontier.push(std::cmp::Reverse(candidate));
+                    found.push(candidate);
+                    if found.len() > ef {
+                        found.pop();
+                    }
+                }
+            }
+        }
+
+        found.into_sorted_vec()
+    }
+
+    /// Neighbour selection heuristic: prefer candidates closer to the query than
+    /// to any already selected neighbour, then top up with the nearest skipped
+    /// ones so sparse regions stay connected
+    fn select_neighbours<V: AsRef<[f32]>>(
+        &self,
+        candidates: &[Candidate],
+        max: usize,
+        vectors: &[V],
+    ) -> Vec<usize> {
+        let mut selected: Vec<usize> = Vec::with_capacity(max);
+        let mut skipped = Vec::new();
+        for candidate in candidates {
+            if selected.len() >= max {
+                break;
+            }
+            let vector = vectors[candidate.node].as_ref();
+            let diverse = selected
+                .iter()
+                .all(|&s| distance(vector, vectors[s].as_ref()) > candidate.distance);
+            if diverse {
+                selected.push(candidate.node);
+            } else {
+                skipped.push(candidate.node);
+            }
+        }
+        let room = max - selected.len();
+        selected.extend(skipped.into_iter().take(room));
+        selected
+    }
+
+    fn prune<V: AsRef<[f32]>>(&mut self, node: usize, layer: usize, vectors: &[V]) {
+        let vector = vectors[node].as_ref();
+        let mut candidates: Vec<Candidate> = self.nodes[node].links[layer]
+            .iter()
+            .map(|&n| Candidate {
+                distance: distance(vector, vectors[n].as_ref()),
+                node: n,
+            })
+            .collect();
+        candidates.sort();
+        self.nodes[node].links[layer] =
+            self.select_neighbours(&candidates, self.max_links(layer), vectors);
+    }
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - cosine(a, b)
+}
@@ -0,0 +1,323 @@
+//! In-memory vector store.
+
+use crate::core::documents::Document;
+use crate::embeddings::similarity::{cosine, maximal_marginal_relevance, select_top_k};
+use crate::models::base::BaseEmbedding;
+use crate::vectorstores::hnsw::{HnswConfig, HnswIndex};
+use crate::vectorstores::{
+    document_id, MetadataFilter, MmrParams, VectorStore, VectorStoreError, VectorStoreResult,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Tombstones tolerated before deleted entries are dropped and the index rebuilt
+const MIN_COMPACTION: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    document: Document,
+    embedding: Vec<f32>,
+    /// Replaced or deleted; kept so HNSW node ids stay stable until compaction
+    #[serde(skip)]
+    deleted: bool,
+}
+
+impl AsRef<[f32]> for Entry {
+    fn as_ref(&self) -> &[f32] {
+        &self.embedding
+    }
+}
+
+/// Entries in insertion order plus the live-id lookup and optional graph index
+#[derive(Debug, Default)]
+struct Entries {
+    entries: Vec<Entry>,
+    live: HashMap<String, usize>,
+    index: Option<HnswIndex>,
+}
+
+impl Entries {
+    fn new(entries: Vec<Entry>, config: Option<HnswConfig>) -> Self {
+        let mut this = Self {
+            entries,
+            live: HashMap::new(),
+            index: config.map(HnswIndex::new),
+        };
+        this.compact();
+        this
+    }
+
+    fn push(&mut self, entry: Entry) {
+        let id = entry.document.id.clone().unwrap_or_default();
+        self.remove(&id);
+        self.live.insert(id, self.entries.len());
+        self.entries.push(entry);
+        if let Some(index) = &mut self.index {
+            index.insert(&self.entries);
+        }
+    }
+
+    fn remove(&mut self, id: &str) {
+        if let Some(i) = self.live.remove(id) {
+            self.entries[i].deleted = true;
+        }
+    }
+
+    /// Drop tombstones once they outnumber live entries
+    fn maybe_compact(&mut self) {
+        let tombstones = self.entries.len() - self.live.len();
+        if tombstones >= MIN_COMPACTION && tombstones > self.live.len() {
+            self.compact();
+        }
+    }
+
+    fn compact(&mut self) {
+        self.entries.retain(|e| !e.deleted);
+        self.live = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| e.document.id.clone().map(|id| (id, i)))
+            .collect();
+        if let Some(index) = &mut self.index {
+            *index = HnswIndex::new(index.config());
+            while index.len() < self.entries.len() {
+                index.insert(&self.entries);
+            }
+        }
+    }
+
+    /// Indices and scores of the `k` live entries matching `filter` closest to
+    /// `embedding`
+    fn nearest(
+        &self,
+        embedding: &[f32],
+        k: usize,
+        filter: &MetadataFilter,
+    ) -> VectorStoreResult<Vec<(usize, f32)>> {
+        if let Some(entry) = self.entries.first() {
+            if entry.embedding.len() != embedding.len() {
+                return Err(VectorStoreError::DimensionMismatch {
+                    expected: entry.embedding.len(),
+                    actual: embedding.len(),
+                });
+            }
+        }
+
+        let keep = |i: usize| {
+            let entry = &self.entries[i];
+            !entry.deleted && filter.matches(&entry.document.metadata)
+        };
+
+        if let Some(index) = &self.index {
+            let found = index.search(embedding, k, &self.entries, keep);
+            // A selective filter can starve the graph search; fall back to exact
+            if found.len() >= k.min(self.live.len()) {
+                return Ok(found);
+            }
+        }
+
+        Ok(select_top_k(
+            (0..self.entries.len())
+                .filter(|&i| keep(i))
+                .map(|i| (i, cosine(embedding, &self.entries[i].embedding))),
+            k,
+        ))
+    }
+
+    fn live_entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().filter(|e| !e.deleted)
+    }
+}
+
+/// Vector store kept in memory with cosine search
+///
+/// Search is exact brute force by default. For large stores, enable an
+/// approximate HNSW index with [`InMemoryVectorStore::with_hnsw`]; the API is
+/// unchanged. Optionally persisted to a JSON file with [`VectorStore::persist`]
+/// and reloaded with [`InMemoryVectorStore::load`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::vectorstores::{HnswConfig, InMemoryVectorStore, VectorStore};
+/// use agentic_optio_rs::{Document, OllamaEmbedding};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = InMemoryVectorStore::new(Arc::new(OllamaEmbedding::new("nomic-embed-text")))
+///         .with_hnsw(HnswConfig::default().ef_search(128));
+///     store
+///         .add_documents(vec![
+///             Document::new("Rust guarantees memory safety without a garbage collector."),
+///             Document::new("Python is dynamically typed."),
+///         ])
+///         .await?;
+///
+///     let hits = store.similarity_search("memory safety", 1).await?;
+///     println!("{}", hits[0].content);
+///     Ok(())
+/// }
+/// ```
+pub struct InMemoryVectorStore {
+    embeddings: Arc<dyn BaseEmbedding>,
+    entries: RwLock<Entries>,
+    path: Option<PathBuf>,
+}
+
+impl InMemoryVectorStore {
+    pub fn new(embeddings: Arc<dyn BaseEmbedding>) -> Self {
+        Self {
+            embeddings,
+            entries: RwLock::new(Entries::default()),
+            path: None,
+        }
+    }
+
+    /// File that [`VectorStore::persist`] writes to; without one, persisting is
+    /// a no-op
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Search with an approximate HNSW graph index instead of brute force
+    ///
+    /// Existing documents are indexed immediately; later ones as they are added.
+    pub fn with_hnsw(mut self, config: HnswConfig) -> Self {
+        let entries = self.entries.get_mut().unwrap();
+        *entries = Entries::new(std::mem::take(&mut entries.entries), Some(config));
+        self
+    }
+
+    /// Load a store previously persisted to `path`; later persists write back to it
+    pub fn load(
+        path: impl AsRef<Path>,
+        embeddings: Arc<dyn BaseEmbedding>,
+    ) -> VectorStoreResult<Self> {
+        let json = std::fs::read_to_string(path.as_ref())?;
+        let entries: Vec<Entry> = serde_json::from_str(&json)?;
+        Ok(Self {
+            embeddings,
+            entries: RwLock::new(Entries::new(entries, None)),
+            path: Some(path.as_ref().to_path_buf()),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().live.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stored document by id
+    pub fn get(&self, id: &str) -> Option<Document> {
+        let entries = self.entries.read().unwrap();
+        entries
+            .live
+            .get(id)
+            .map(|&i| entries.entries[i].document.clone())
+    }
+}
+
+#[async_trait]
+impl VectorStore for InMemoryVectorStore {
+    fn embeddings(&self) -> &dyn BaseEmbedding {
+        self.embeddings.as_ref()
+    }
+
+    async fn add_documents(&self, documents: Vec<Document>) -> VectorStoreResult<Vec<String>> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let texts: Vec<String> = documents.iter().map(|d| d.content.clone()).collect();
+        let embeddings = self.embeddings.embed(&texts).await?;
+
+        let mut entries = self.entries.write().unwrap();
+        let expected = entries
+            .entries
+            .first()
+            .map(|e| e.embedding.len())
+            .or_else(|| embeddings.first().map(Vec::len));
+        if let Some(expected) = expected {
+            if let Some(bad) = embeddings.iter().find(|e| e.len() != expected) {
+                return Err(VectorStoreError::DimensionMismatch {
+                    expected,
+                    actual: bad.len(),
+                });
+            }
+        }
+
+        let mut ids = Vec::with_capacity(documents.len());
+        for (mut document, embedding) in documents.into_iter().zip(embeddings) {
+            let id = document_id(&document);
+            document.id = Some(id.clone());
+            entries.push(Entry {
+                document,
+                embedding,
+                deleted: false,
+            });
+            ids.push(id);
+        }
+        entries.maybe_compact();
+        Ok(ids)
+    }
+
+    async fn similarity_search_by_vector_with_filter(
+        &self,
+        embedding: &[f32],
+        k: usize,
+        filter: &MetadataFilter,
+    ) -> VectorStoreResult<Vec<(Document, f32)>> {
+        let entries = self.entries.read().unwrap();
+        Ok(entries
+            .nearest(embedding, k, filter)?
+            .into_iter()
+            .map(|(i, score)| (entries.entries[i].document.clone(), score))
+            .collect())
+    }
+
+    async fn max_marginal_relevance_search_by_vector(
+        &self,
+        embedding: &[f32],
+        params: &MmrParams,
+    ) -> VectorStoreResult<Vec<Document>> {
+        // Reuse stored vectors instead of re-embedding the candidates
+        let entries = self.entries.read().unwrap();
+        let candidates = entries.nearest(embedding, params.fetch_k, &params.filter)?;
+        let vectors: Vec<&[f32]> = candidates
+            .iter()
+            .map(|&(i, _)| entries.entries[i].embedding.as_slice())
+            .collect();
+        let chosen = maximal_marginal_relevance(embedding, &vectors, params.k, params.lambda);
+        Ok(chosen
+            .into_iter()
+            .map(|c| entries.entries[candidates[c].0].document.clone())
+            .collect())
+    }
+
+    async fn delete(&self, ids: &[String]) -> VectorStoreResult<()> {
+        let mut entries = self.entries.write().unwrap();
+        for id in ids {
+            entries.remove(id);
+        }
+        entries.maybe_compact();
+        Ok(())
+    }
+
+    async fn persist(&self) -> VectorStoreResult<()> {
+        if let Some(path) = &self.path {
+            let entries = self.entries.read().unwrap();
+            let json = serde_json::to_string(&entries.live_entries().collect::<Vec<_>>())?;
+            std::fs::write(path, json)?;
+        }
+        Ok(())
+    }
+}
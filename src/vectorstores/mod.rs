@@ -0,0 +1,329 @@
+//! Vector stores for AgenticOptio.
+//!
+//! A [`VectorStore`] embeds documents with a [`BaseEmbedding`] model, stores them
+//! alongside their vectors, and returns the documents closest to a query.
+//! [`InMemoryVectorStore`] needs no external services, so a complete local RAG
+//! loop runs with just Ollama. Server-backed stores are behind cargo features:
+//! `qdrant` enables `QdrantVectorStore`, `pgvector` enables `PgVectorStore`, and
+//! `sqlite` enables the embedded, file-backed `SqliteVectorStore`.
+
+pub mod hnsw;
+pub mod memory;
+#[cfg(feature = "pgvector")]
+pub mod pgvector;
+#[cfg(feature = "qdrant")]
+pub mod qdrant;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use hnsw::HnswConfig;
+pub use memory::InMemoryVectorStore;
+#[cfg(feature = "pgvector")]
+pub use pgvector::{PgDistance, PgIndex, PgVectorStore, PgVectorStoreBuilder};
+#[cfg(feature = "qdrant")]
+pub use qdrant::{QdrantDistance, QdrantVectorStore, QdrantVectorStoreBuilder};
+#[cfg(feature = "sqlite")]
+pub use sqlite::{SqliteVectorStore, SqliteVectorStoreBuilder};
+
+use crate::core::documents::Document;
+use crate::credentials::CredentialError;
+use crate::embeddings::maximal_marginal_relevance;
+use crate::models::base::{BaseEmbedding, ModelError};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// Error type for vector store operations
+#[derive(Debug, thiserror::Error)]
+pub enum VectorStoreError {
+    #[error("Embedding failed: {0}")]
+    Embedding(#[from] ModelError),
+
+    #[error("Embedding dimension mismatch: expected {expected}, got {actual}")]
+    DimensionMismatch { expected: usize, actual: usize },
+
+    #[error("HTTP request failed: {0}")]
+    HttpError(#[from] reqwest::Error),
+
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON serialization failed: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Credential lookup failed: {0}")]
+    Credential(#[from] CredentialError),
+}
+
+pub type VectorStoreResult<T> = Result<T, VectorStoreError>;
+
+/// Allowed values for one metadata field
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataMatch {
+    /// Field equals the value
+    Value(serde_json::Value),
+    /// Field equals any of the values
+    Any(Vec<serde_json::Value>),
+}
+
+/// Metadata conditions a document must all satisfy to be returned
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::vectorstores::MetadataFilter;
+/// use agentic_optio_rs::Document;
+///
+/// let filter = MetadataFilter::new()
+///     .eq("lang", "rust")
+///     .any_of("source", ["book.md", "faq.md"]);
+/// let doc = Document::new("...").with_metadata("lang", "rust").with_metadata("source", "faq.md");
+/// assert!(filter.matches(&doc.metadata));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetadataFilter {
+    pub conditions: Vec<(String, MetadataMatch)>,
+}
+
+impl MetadataFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require `key` to equal `value`
+    pub fn eq(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.conditions
+            .push((key.into(), MetadataMatch::Value(value.into())));
+        self
+    }
+
+    /// Require `key` to equal one of `values`
+    pub fn any_of<V: Into<serde_json::Value>>(
+        mut self,
+        key: impl Into<String>,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        let values = values.into_iter().map(Into::into).collect();
+        self.conditions
+            .push((key.into(), MetadataMatch::Any(values)));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    /// Whether document metadata satisfies every condition
+    pub fn matches(&self, metadata: &HashMap<String, serde_json::Value>) -> bool {
+        self.conditions.iter().all(|(key, condition)| {
+            let Some(value) = metadata.get(key) else {
+                return false;
+            };
+            match condition {
+                MetadataMatch::Value(expected) => value == expected,
+                MetadataMatch::Any(options) => options.contains(value),
+            }
+        })
+    }
+}
+
+/// Parameters for maximal marginal relevance search
+///
+/// MMR fetches the `fetch_k` nearest documents, then greedily keeps `k` of them
+/// that are relevant to the query but dissimilar to each other.
+///
+/// # Examples
+///
+/// ```
+/// use agentic_optio_rs::vectorstores::{MetadataFilter, MmrParams};
+///
+/// let params = MmrParams::new(4)
+///     .fetch_k(30)
+///     .lambda(0.3)
+///     .filter(MetadataFilter::new().eq("lang", "en"));
+/// assert_eq!(params.k, 4);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MmrParams {
+    /// Documents to return
+    pub k: usize,
+    /// Nearest candidates to choose from
+    pub fetch_k: usize,
+    /// Relevance versus diversity, from 0.0 (most diverse) to 1.0 (most relevant)
+    pub lambda: f32,
+    pub filter: MetadataFilter,
+}
+
+impl Default for MmrParams {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl MmrParams {
+    /// Return `k` documents chosen from the nearest `5 * k` with lambda 0.5
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            fetch_k: k * 5,
+            lambda: 0.5,
+            filter: MetadataFilter::default(),
+        }
+    }
+
+    pub fn fetch_k(mut self, fetch_k: usize) -> Self {
+        self.fetch_k = fetch_k;
+        self
+    }
+
+    pub fn lambda(mut self, lambda: f32) -> Self {
+        self.lambda = lambda.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn filter(mut self, filter: MetadataFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+}
+
+/// Base trait for all vector stores
+///
+/// Scores are similarities: higher means closer to the query.
+#[async_trait]
+pub trait VectorStore: Send + Sync {
+    /// Embedding model used for documents and queries
+    fn embeddings(&self) -> &dyn BaseEmbedding;
+
+    /// Embed and store documents, returning their ids.
+    ///
+    /// Documents keep their `id` if set (replacing any stored document with the
+    /// same id); others get a fresh UUID.
+    async fn add_documents(&self, documents: Vec<Document>) -> VectorStoreResult<Vec<String>>;
+
+    /// Documents matching `filter` closest to an embedding, with their scores
+    async fn similarity_search_by_vector_with_filter(
+        &self,
+        embedding: &[f32],
+        k: usize,
+        filter: &MetadataFilter,
+    ) -> VectorStoreResult<Vec<(Document, f32)>>;
+
+    /// Remove documents by id; unknown ids are ignored
+    async fn delete(&self, ids: &[String]) -> VectorStoreResult<()>;
+
+    /// Flush to durable storage. A no-op for stores that write through.
+    async fn persist(&self) -> VectorStoreResult<()> {
+        Ok(())
+    }
+
+    /// Documents closest to an embedding, with their scores
+    async fn similarity_search_by_vector(
+        &self,
+        embedding: &[f32],
+        k: usize,
+    ) -> VectorStoreResult<Vec<(Document, f32)>> {
+        self.similarity_search_by_vector_with_filter(embedding, k, &MetadataFilter::default())
+            .await
+    }
+
+    /// Documents matching `filter` closest to a query, with their scores
+    async fn similarity_search_with_filter(
+        &self,
+        query: &str,
+        k: usize,
+        filter: &MetadataFilter,
+    ) -> VectorStoreResult<Vec<(Document, f32)>> {
+        let embedding = self.embeddings().embed_query(query).await?;
+        self.similarity_search_by_vector_with_filter(&embedding, k, filter)
+            .await
+    }
+
+    /// Documents closest to a query, with their scores
+    async fn similarity_search_with_score(
+        &self,
+        query: &str,
+        k: usize,
+    ) -> VectorStoreResult<Vec<(Document, f32)>> {
+        let embedding = self.embeddings().embed_query(query).await?;
+        self.similarity_search_by_vector(&embedding, k).await
+    }
+
+    /// Relevant but diverse documents for an embedding, via MMR.
+    ///
+    /// The default implementation re-embeds the candidates; stores that keep
+    /// vectors in memory should override it.
+    async fn max_marginal_relevance_search_by_vector(
+        &self,
+        embedding: &[f32],
+        params: &MmrParams,
+    ) -> VectorStoreResult<Vec<Document>> {
+        let candidates: Vec<Document> = self
+            .similarity_search_by_vector_with_filter(embedding, params.fetch_k, &params.filter)
+            .await?
+            .into_iter()
+            .map(|(doc, _)| doc)
+            .collect();
+        if candidates.is_empty() {
+            return Ok(candidates);
+        }
+
+        let texts: Vec<String> = candidates.iter().map(|d| d.content.clone()).collect();
+        let vectors = self.embeddings().embed(&texts).await?;
+        let chosen = maximal_marginal_relevance(embedding, &vectors, params.k, params.lambda);
+        Ok(chosen.into_iter().map(|i| candidates[i].clone()).collect())
+    }
+
+    /// Relevant but diverse documents for a query, via MMR
+    async fn max_marginal_relevance_search(
+        &self,
+        query: &str,
+        params: &MmrParams,
+    ) -> VectorStoreResult<Vec<Document>> {
+        let embedding = self.embeddings().embed_query(query).await?;
+        self.max_marginal_relevance_search_by_vector(&embedding, params)
+            .await
+    }
+
+    /// Documents closest to a query
+    async fn similarity_search(&self, query: &str, k: usize) -> VectorStoreResult<Vec<Document>> {
+        Ok(self
+            .similarity_search_with_score(query, k)
+            .await?
+            .into_iter()
+            .map(|(doc, _)| doc)
+            .collect())
+    }
+}
+
+/// Id for a document being added: its own, or a new UUID
+pub(crate) fn document_id(document: &Document) -> String {
+    document
+        .id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Validate a table name for interpolation into SQL
+#[cfg(any(feature = "pgvector", feature = "sqlite"))]
+pub(crate) fn sql_identifier(name: String) -> VectorStoreResult<String> {
+    if is_sql_identifier(&name) {
+        Ok(name)
+    } else {
+        Err(VectorStoreError::Storage(format!(
+            "invalid table name '{}'",
+            name
+        )))
+    }
+}
+
+/// Whether `name` is a plain SQL identifier, safe to interpolate into SQL
+#[cfg(any(feature = "pgvector", feature = "sqlite"))]
+pub(crate) fn is_sql_identifier(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,359 @@
+//! Postgres/pgvector vector store.
+//!
+//! Stores documents in a Postgres table with a `vector` column via sqlx.
+//! Enabled with the `pgvector` feature.
+
+use crate::core::documents::Document;
+use crate::models::base::BaseEmbedding;
+use crate::vectorstores::{
+    document_id, sql_identifier, MetadataFilter, MetadataMatch, VectorStore, VectorStoreError,
+    VectorStoreResult,
+};
+use async_trait::async_trait;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::Row;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Similarity measure used for search and indexing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PgDistance {
+    /// Cosine similarity; scores range from -1 to 1
+    #[default]
+    Cosine,
+    /// Inner product; best for normalized embeddings
+    InnerProduct,
+}
+
+impl PgDistance {
+    fn operator(&self) -> &'static str {
+        match self {
+            PgDistance::Cosine => "<=>",
+            PgDistance::InnerProduct => "<#>",
+        }
+    }
+
+    fn ops_class(&self) -> &'static str {
+        match self {
+            PgDistance::Cosine => "vector_cosine_ops",
+            PgDistance::InnerProduct => "vector_ip_ops",
+        }
+    }
+
+    /// SQL turning the operator's distance into a higher-is-closer score
+    fn score_sql(&self, distance: &str) -> String {
+        match self {
+            PgDistance::Cosine => format!("1 - ({})", distance),
+            PgDistance::InnerProduct => format!("-({})", distance),
+        }
+    }
+}
+
+/// Approximate nearest-neighbour index created with the table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PgIndex {
+    /// Exact search with no vector index
+    None,
+    #[default]
+    Hnsw,
+    IvfFlat {
+        lists: u32,
+    },
+}
+
+/// Vector store backed by a Postgres table using pgvector
+///
+/// The `vector` extension, table, and indexes are created on the first insert
+/// if they do not exist, sized to the embeddings being stored. Metadata lives in
+/// a `JSONB` column with a GIN index for filtering.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::vectorstores::{PgDistance, PgVectorStore, VectorStore};
+/// use agentic_optio_rs::{Document, OllamaEmbedding};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let embeddings = Arc::new(OllamaEmbedding::new("nomic-embed-text"));
+///     let store = PgVectorStore::connect("postgres://localhost/app", embeddings)
+///         .await?
+///         .table("knowledge")?
+///         .distance(PgDistance::Cosine)
+///         .build();
+///
+///     store.add_documents(vec![Document::new("pgvector adds vector search to Postgres")]).await?;
+///     let hits = store.similarity_search("vector search", 3).await?;
+///     println!("{} hits", hits.len());
+///     Ok(())
+/// }
+/// ```
+pub struct PgVectorStore {
+    pool: PgPool,
+    table: String,
+    distance: PgDistance,
+    index: PgIndex,
+    embeddings: Arc<dyn BaseEmbedding>,
+    table_ready: AtomicBool,
+}
+
+impl PgVectorStore {
+    pub fn builder(pool: PgPool, embeddings: Arc<dyn BaseEmbedding>) -> PgVectorStoreBuilder {
+        PgVectorStoreBuilder::new(pool, embeddings)
+    }
+
+    /// Connect to `database_url` and start a builder
+    pub async fn connect(
+        database_url: &str,
+        embeddings: Arc<dyn BaseEmbedding>,
+    ) -> VectorStoreResult<PgVectorStoreBuilder> {
+        let pool = PgPoolOptions::new()
+            .connect(database_url)
+            .await
+            .map_err(storage_error)?;
+        Ok(PgVectorStoreBuilder::new(pool, embeddings))
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    /// Create the extension, table, and indexes for vectors of `dimension`
+    pub async fn ensure_table(&self, dimension: usize) -> VectorStoreResult<()> {
+        if self.table_ready.load(Ordering::Acquire) {
+            return Ok(());
+        }
+
+        let mut statements = vec![
+            "CREATE EXTENSION IF NOT EXISTS vector".to_string(),
+            format!(
+                "CREATE TABLE IF NOT EXISTS {} (\
+                 id TEXT PRIMARY KEY, \
+                 content TEXT NOT NULL, \
+                 metadata JSONB NOT NULL DEFAULT '{{}}', \
+                 embedding vector({}) NOT NULL)",
+                self.table, dimension
+            ),
+            format!(
+                "CREATE INDEX IF NOT EXISTS {0}_metadata_idx ON {0} USING gin (metadata)",
+                self.table
+            ),
+        ];
+        let ops = self.distance.ops_class();
+        let index = match self.index {
+            PgIndex::None => None,
+            PgIndex::Hnsw => Some(format!("hnsw (embedding {})", ops)),
+            PgIndex::IvfFlat { lists } => Some(format!(
+                "ivfflat (embedding {}) WITH (lists = {})",
+                ops, lists
+            )),
+        };
+        if let Some(index) = index {
+            statements.push(format!(
+                "CREATE INDEX IF NOT EXISTS {0}_embedding_idx ON {0} USING {1}",
+                self.table, index
+            ));
+        }
+
+        for statement in statements {
+            sqlx::query(&statement)
+                .execute(&self.pool)
+                .await
+                .map_err(storage_error)?;
+        }
+
+        self.table_ready.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Drop the table and every document in it
+    pub async fn drop_table(&self) -> VectorStoreResult<()> {
+        sqlx::query(&format!("DROP TABLE IF EXISTS {}", self.table))
+            .execute(&self.pool)
+            .await
+            .map_err(storage_error)?;
+        self.table_ready.store(false, Ordering::Release);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VectorStore for PgVectorStore {
+    fn embeddings(&self) -> &dyn BaseEmbedding {
+        self.embeddings.as_ref()
+    }
+
+    async fn add_documents(&self, documents: Vec<Document>) -> VectorStoreResult<Vec<String>> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let texts: Vec<String> = documents.iter().map(|d| d.content.clone()).collect();
+        let embeddings = self.embeddings.embed(&texts).await?;
+        if let Some(first) = embeddings.first() {
+            self.ensure_table(first.len()).await?;
+        }
+
+        let sql = format!(
+            "INSERT INTO {} (id, content, metadata, embedding) \
+             VALUES ($1, $2, $3::jsonb, $4::vector) \
+             ON CONFLICT (id) DO UPDATE SET content = EXCLUDED.content, \
+             metadata = EXCLUDED.metadata, embedding = EXCLUDED.embedding",
+            self.table
+        );
+
+        let mut tx = self.pool.begin().await.map_err(storage_error)?;
+        let mut ids = Vec::with_capacity(documents.len());
+        for (document, embedding) in documents.iter().zip(&embeddings) {
+            let id = document_id(document);
+            sqlx::query(&sql)
+                .bind(&id)
+                .bind(&document.content)
+                .bind(serde_json::to_string(&document.metadata)?)
+                .bind(vector_literal(embedding))
+                .execute(&mut *tx)
+                .await
+                .map_err(storage_error)?;
+            ids.push(id);
+        }
+        tx.commit().await.map_err(storage_error)?;
+
+        Ok(ids)
+    }
+
+    async fn similarity_search_by_vector_with_filter(
+        &self,
+        embedding: &[f32],
+        k: usize,
+        filter: &MetadataFilter,
+    ) -> VectorStoreResult<Vec<(Document, f32)>> {
+        let distance = format!("embedding {} $1::vector", self.distance.operator());
+
+        // $1 is the query vector and $2 the limit; filter values follow.
+        let mut params = Vec::new();
+        let mut clauses = Vec::new();
+        for (key, condition) in &filter.conditions {
+            match condition {
+                MetadataMatch::Value(value) => {
+                    params.push(serde_json::json!({ key.as_str(): value }).to_string());
+                    clauses.push(format!("metadata @> ${}::jsonb", params.len() + 2));
+                }
+                MetadataMatch::Any(values) => {
+                    params.push(key.clone());
+                    params.push(serde_json::to_string(values)?);
+                    clauses.push(format!(
+                        "metadata -> ${}::text IN (SELECT jsonb_array_elements(${}::jsonb))",
+                        params.len() + 1,
+                        params.len() + 2
+                    ));
+                }
+            }
+        }
+        let where_clause = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+
+        let sql = format!(
+            "SELECT id, content, metadata::text AS metadata, ({})::float4 AS score \
+             FROM {} {} ORDER BY {} LIMIT $2",
+            self.distance.score_sql(&distance),
+            self.table,
+            where_clause,
+            distance
+        );
+
+        let mut query = sqlx::query(&sql)
+            .bind(vector_literal(embedding))
+            .bind(k as i64);
+        for param in params {
+            query = query.bind(param);
+        }
+        let rows = query.fetch_all(&self.pool).await.map_err(storage_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                let metadata: String = row.try_get("metadata").map_err(storage_error)?;
+                let document = Document {
+                    id: Some(row.try_get("id").map_err(storage_error)?),
+                    content: row.try_get("content").map_err(storage_error)?,
+                    metadata: serde_json::from_str(&metadata)?,
+                };
+                Ok((document, row.try_get("score").map_err(storage_error)?))
+            })
+            .collect()
+    }
+
+    async fn delete(&self, ids: &[String]) -> VectorStoreResult<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        sqlx::query(&format!("DELETE FROM {} WHERE id = ANY($1)", self.table))
+            .bind(ids)
+            .execute(&self.pool)
+            .await
+            .map_err(storage_error)?;
+        Ok(())
+    }
+}
+
+/// pgvector text representation, e.g. `[0.1,0.2]`
+fn vector_literal(embedding: &[f32]) -> String {
+    let values: Vec<String> = embedding.iter().map(|v| v.to_string()).collect();
+    format!("[{}]", values.join(","))
+}
+
+fn storage_error(err: sqlx::Error) -> VectorStoreError {
+    VectorStoreError::Storage(err.to_string())
+}
+
+/// Builder for PgVectorStore
+pub struct PgVectorStoreBuilder {
+    pool: PgPool,
+    embeddings: Arc<dyn BaseEmbedding>,
+    table: String,
+    distance: PgDistance,
+    index: PgIndex,
+}
+
+impl PgVectorStoreBuilder {
+    pub fn new(pool: PgPool, embeddings: Arc<dyn BaseEmbedding>) -> Self {
+        Self {
+            pool,
+            embeddings,
+            table: "documents".to_string(),
+            distance: PgDistance::default(),
+            index: PgIndex::default(),
+        }
+    }
+
+    /// Table to store documents in; must be a plain SQL identifier
+    pub fn table(mut self, table: impl Into<String>) -> VectorStoreResult<Self> {
+        self.table = sql_identifier(table.into())?;
+        Ok(self)
+    }
+
+    pub fn distance(mut self, distance: PgDistance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Vector index created with the table
+    pub fn index(mut self, index: PgIndex) -> Self {
+        self.index = index;
+        self
+    }
+
+    pub fn build(self) -> PgVectorStore {
+        PgVectorStore {
+            pool: self.pool,
+            table: self.table,
+            distance: self.distance,
+            index: self.index,
+            embeddings: self.embeddings,
+            table_ready: AtomicBool::new(false),
+        }
+    }
+}
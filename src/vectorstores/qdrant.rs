@@ -0,0 +1,402 @@
+//! Qdrant vector store.
+//!
+//! Talks to Qdrant's REST API. Enabled with the `qdrant` feature.
+
+use crate::core::documents::Document;
+use crate::credentials::{ApiKey, CredentialProvider};
+use crate::models::base::BaseEmbedding;
+use crate::models::http;
+use crate::vectorstores::{
+    document_id, MetadataFilter, MetadataMatch, VectorStore, VectorStoreError, VectorStoreResult,
+};
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_URL: &str = "http://localhost:6333";
+
+/// Qdrant distance metric for new collections
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QdrantDistance {
+    #[default]
+    Cosine,
+    Dot,
+    Euclid,
+}
+
+impl QdrantDistance {
+    fn as_str(&self) -> &'static str {
+        match self {
+            QdrantDistance::Cosine => "Cosine",
+            QdrantDistance::Dot => "Dot",
+            QdrantDistance::Euclid => "Euclid",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    result: Vec<ScoredPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoredPoint {
+    score: f32,
+    #[serde(default)]
+    payload: Payload,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Payload {
+    #[serde(default)]
+    document_id: Option<String>,
+    #[serde(default)]
+    page_content: String,
+    #[serde(default)]
+    metadata: HashMap<String, serde_json::Value>,
+}
+
+/// Vector store backed by a Qdrant collection
+///
+/// The collection is created on the first insert if it does not exist, sized to
+/// the embeddings being stored. Document ids that are not UUIDs are mapped to
+/// deterministic UUIDs; the original id is kept in the point payload.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::vectorstores::{MetadataFilter, QdrantVectorStore, VectorStore};
+/// use agentic_optio_rs::{Document, OllamaEmbedding};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let embeddings = Arc::new(OllamaEmbedding::new("nomic-embed-text"));
+///     let store = QdrantVectorStore::builder("docs", embeddings)
+///         .url("http://localhost:6333")
+///         .build();
+///
+///     store
+///         .add_documents(vec![Document::new("Qdrant stores vectors").with_metadata("lang", "en")])
+///         .await?;
+///     let hits = store
+///         .similarity_search_with_filter("vectors", 3, &MetadataFilter::new().eq("lang", "en"))
+///         .await?;
+///     println!("{} hits", hits.len());
+///     Ok(())
+/// }
+/// ```
+pub struct QdrantVectorStore {
+    client: Client,
+    url: String,
+    collection: String,
+    api_key: ApiKey,
+    distance: QdrantDistance,
+    batch_size: usize,
+    embeddings: Arc<dyn BaseEmbedding>,
+    collection_ready: AtomicBool,
+}
+
+impl QdrantVectorStore {
+    /// Create a store for `collection` on a local Qdrant, or `QDRANT_URL` if set
+    pub fn new(collection: impl Into<String>, embeddings: Arc<dyn BaseEmbedding>) -> Self {
+        Self::builder(collection, embeddings).build()
+    }
+
+    pub fn builder(
+        collection: impl Into<String>,
+        embeddings: Arc<dyn BaseEmbedding>,
+    ) -> QdrantVectorStoreBuilder {
+        QdrantVectorStoreBuilder::new(collection, embeddings)
+    }
+
+    pub fn collection(&self) -> &str {
+        &self.collection
+    }
+
+    /// Create the collection for vectors of `dimension` if it does not exist
+    pub async fn ensure_collection(&self, dimension: usize) -> VectorStoreResult<()> {
+        if self.collection_ready.load(Ordering::Acquire) {
+            return Ok(());
+        }
+
+        let response = self
+            .request(self.client.get(self.collection_url("")))
+            .await?
+            .send()
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            let body = json!({
+                "vectors": {"size": dimension, "distance": self.distance.as_str()}
+            });
+            check(
+                self.request(self.client.put(self.collection_url("")))
+                    .await?
+                    .json(&body)
+                    .send()
+                    .await?,
+            )
+            .await?;
+        } else {
+            check(response).await?;
+        }
+
+        self.collection_ready.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Delete the collection and every point in it
+    pub async fn delete_collection(&self) -> VectorStoreResult<()> {
+        check(
+            self.request(self.client.delete(self.collection_url("")))
+                .await?
+                .send()
+                .await?,
+        )
+        .await?;
+        self.collection_ready.store(false, Ordering::Release);
+        Ok(())
+    }
+
+    fn collection_url(&self, path: &str) -> String {
+        format!(
+            "{}/collections/{}{}",
+            self.url.trim_end_matches('/'),
+            self.collection,
+            path
+        )
+    }
+
+    async fn request(&self, builder: RequestBuilder) -> VectorStoreResult<RequestBuilder> {
+        Ok(match self.api_key.resolve().await? {
+            Some(key) => builder.header("api-key", key),
+            None => builder,
+        })
+    }
+}
+
+#[async_trait]
+impl VectorStore for QdrantVectorStore {
+    fn embeddings(&self) -> &dyn BaseEmbedding {
+        self.embeddings.as_ref()
+    }
+
+    async fn add_documents(&self, documents: Vec<Document>) -> VectorStoreResult<Vec<String>> {
+        let mut ids = Vec::with_capacity(documents.len());
+
+        for batch in documents.chunks(self.batch_size) {
+            let texts: Vec<String> = batch.iter().map(|d| d.content.clone()).collect();
+            let embeddings = self.embeddings.embed(&texts).await?;
+            if let Some(first) = embeddings.first() {
+                self.ensure_collection(first.len()).await?;
+            }
+
+            let points: Vec<serde_json::Value> = batch
+                .iter()
+                .zip(embeddings)
+                .map(|(document, vector)| {
+                    let id = document_id(document);
+                    let point = json!({
+                        "id": point_id(&id),
+                        "vector": vector,
+                        "payload": {
+                            "document_id": id,
+                            "page_content": document.content,
+                            "metadata": document.metadata,
+                        }
+                    });
+                    ids.push(id);
+                    point
+                })
+                .collect();
+
+            check(
+                self.request(self.client.put(self.collection_url("/points?wait=true")))
+                    .await?
+                    .json(&json!({ "points": points }))
+                    .send()
+                    .await?,
+            )
+            .await?;
+        }
+
+        Ok(ids)
+    }
+
+    async fn similarity_search_by_vector_with_filter(
+        &self,
+        embedding: &[f32],
+        k: usize,
+        filter: &MetadataFilter,
+    ) -> VectorStoreResult<Vec<(Document, f32)>> {
+        let mut body = json!({
+            "vector": embedding,
+            "limit": k,
+            "with_payload": true,
+        });
+        if !filter.is_empty() {
+            body["filter"] = to_qdrant_filter(filter);
+        }
+
+        let response = check(
+            self.request(self.client.post(self.collection_url("/points/search")))
+                .await?
+                .json(&body)
+                .send()
+                .await?,
+        )
+        .await?;
+        let response: SearchResponse = response.json().await?;
+
+        Ok(response
+            .result
+            .into_iter()
+            .map(|point| {
+                let document = Document {
+                    id: point.payload.document_id,
+                    content: point.payload.page_content,
+                    metadata: point.payload.metadata,
+                };
+                (document, point.score)
+            })
+            .collect())
+    }
+
+    async fn delete(&self, ids: &[String]) -> VectorStoreResult<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let points: Vec<String> = ids.iter().map(|id| point_id(id)).collect();
+        check(
+            self.request(
+                self.client
+                    .post(self.collection_url("/points/delete?wait=true")),
+            )
+            .await?
+            .json(&json!({ "points": points }))
+            .send()
+            .await?,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Qdrant point id for a document id: the id itself if it is a UUID, otherwise
+/// a UUID derived from it
+fn point_id(id: &str) -> String {
+    match Uuid::parse_str(id) {
+        Ok(uuid) => uuid.to_string(),
+        Err(_) => Uuid::new_v5(&Uuid::NAMESPACE_OID, id.as_bytes()).to_string(),
+    }
+}
+
+fn to_qdrant_filter(filter: &MetadataFilter) -> serde_json::Value {
+    let must: Vec<serde_json::Value> = filter
+        .conditions
+        .iter()
+        .map(|(key, condition)| {
+            let matcher = match condition {
+                MetadataMatch::Value(value) => json!({ "value": value }),
+                MetadataMatch::Any(values) => json!({ "any": values }),
+            };
+            json!({ "key": format!("metadata.{}", key), "match": matcher })
+        })
+        .collect();
+    json!({ "must": must })
+}
+
+/// Turn a non-success response into a storage error carrying Qdrant's message
+async fn check(response: reqwest::Response) -> VectorStoreResult<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(VectorStoreError::Storage(format!(
+        "Qdrant returned {}: {}",
+        status, body
+    )))
+}
+
+/// Builder for QdrantVectorStore
+pub struct QdrantVectorStoreBuilder {
+    collection: String,
+    embeddings: Arc<dyn BaseEmbedding>,
+    url: String,
+    api_key: ApiKey,
+    distance: QdrantDistance,
+    batch_size: usize,
+    http: http::ClientOptions,
+}
+
+impl QdrantVectorStoreBuilder {
+    pub fn new(collection: impl Into<String>, embeddings: Arc<dyn BaseEmbedding>) -> Self {
+        Self {
+            collection: collection.into(),
+            embeddings,
+            url: std::env::var("QDRANT_URL").unwrap_or_else(|_| DEFAULT_URL.to_string()),
+            api_key: ApiKey::env("QDRANT_API_KEY"),
+            distance: QdrantDistance::default(),
+            batch_size: 64,
+            http: http::ClientOptions::default(),
+        }
+    }
+
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key.set_key(api_key.into());
+        self
+    }
+
+    /// Look up `QDRANT_API_KEY` in `credentials` on every request instead of
+    /// the environment; a key set with [`api_key`](Self::api_key) wins
+    pub fn credentials(mut self, credentials: impl CredentialProvider + 'static) -> Self {
+        self.api_key.set_credentials(Arc::new(credentials));
+        self
+    }
+
+    /// Distance metric used when the store creates the collection
+    pub fn distance(mut self, distance: QdrantDistance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Documents embedded and upserted per request
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Proxy, connection pool, and TLS settings of the HTTP client; see
+    /// [`ClientOptions`](crate::models::ClientOptions)
+    pub fn http_options(
+        mut self,
+        configure: impl FnOnce(http::ClientOptions) -> http::ClientOptions,
+    ) -> Self {
+        self.http = configure(std::mem::take(&mut self.http));
+        self
+    }
+
+    pub fn build(self) -> QdrantVectorStore {
+        QdrantVectorStore {
+            client: self.http.build(),
+            url: self.url,
+            collection: self.collection,
+            api_key: self.api_key,
+            distance: self.distance,
+            batch_size: self.batch_size,
+            embeddings: self.embeddings,
+            collection_ready: AtomicBool::new(false),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! SQLite vector store.
+//!
+//! A single-file, serverless store for desktop and edge apps. Enabled with the
+//! `sqlite` feature.
+
+use crate::core::documents::Document;
+use crate::embeddings::similarity::{cosine, select_top_k};
+use crate::models::base::BaseEmbedding;
+use crate::vectorstores::{
+    document_id, sql_identifier, MetadataFilter, VectorStore, VectorStoreError, VectorStoreResult,
+};
+use async_trait::async_trait;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Vector store persisted in a SQLite database file
+///
+/// Embeddings are stored as little-endian `f32` blobs and searched by brute-force
+/// cosine similarity, which comfortably handles the tens of thousands of chunks
+/// typical of a local knowledge base. Every write goes straight to disk.
+///
+/// # Examples
+///
+/// ```no_run
+/// use agentic_optio_rs::vectorstores::{SqliteVectorStore, VectorStore};
+/// use agentic_optio_rs::{Document, OllamaEmbedding};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let embeddings = Arc::new(OllamaEmbedding::new("nomic-embed-text"));
+///     let store = SqliteVectorStore::open("knowledge.db", embeddings).await?.build();
+///
+///     store.add_documents(vec![Document::new("Notes survive restarts")]).await?;
+///     let hits = store.similarity_search("restarts", 1).await?;
+///     println!("{}", hits[0].content);
+///     Ok(())
+/// }
+/// ```
+pub struct SqliteVectorStore {
+    pool: SqlitePool,
+    table: String,
+    embeddings: Arc<dyn BaseEmbedding>,
+    table_ready: AtomicBool,
+}
+
+impl SqliteVectorStore {
+    pub fn builder(
+        pool: SqlitePool,
+        embeddings: Arc<dyn BaseEmbedding>,
+    ) -> SqliteVectorStoreBuilder {
+        SqliteVectorStoreBuilder::new(pool, embeddings)
+    }
+
+    /// Open (creating if needed) the database file at `path` and start a builder
+    pub async fn open(
+        path: impl AsRef<Path>,
+        embeddings: Arc<dyn BaseEmbedding>,
+    ) -> VectorStoreResult<SqliteVectorStoreBuilder> {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .connect_with(options)
+            .await
+            .map_err(storage_error)?;
+        Ok(SqliteVectorStoreBuilder::new(pool, embeddings))
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    async fn ensure_table(&self) -> VectorStoreResult<()> {
+        if self.table_ready.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (\
+             id TEXT PRIMARY KEY, \
+             content TEXT NOT NULL, \
+             metadata TEXT NOT NULL, \
+             embedding BLOB NOT NULL)",
+            self.table
+        ))
+        .execute(&self.pool)
+        .await
+        .map_err(storage_error)?;
+        self.table_ready.store(true, Ordering::Release);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VectorStore for SqliteVectorStore {
+    fn embeddings(&self) -> &dyn BaseEmbedding {
+        self.embeddings.as_ref()
+    }
+
+    async fn add_documents(&self, documents: Vec<Document>) -> VectorStoreResult<Vec<String>> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.ensure_table().await?;
+
+        let texts: Vec<String> = documents.iter().map(|d| d.content.clone()).collect();
+        let embeddings = self.embeddings.embed(&texts).await?;
+
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} (id, content, metadata, embedding) VALUES (?, ?, ?, ?)",
+            self.table
+        );
+        let mut tx = self.pool.begin().await.map_err(storage_error)?;
+        let mut ids = Vec::with_capacity(documents.len());
+        for (document, embedding) in documents.iter().zip(&embeddings) {
+            let id = document_id(document);
+            sqlx::query(&sql)
+                .bind(&id)
+                .bind(&document.content)
+                .bind(serde_json::to_string(&document.metadata)?)
+                .bind(encode(embedding))
+                .execute(&mut *tx)
+                .await
+                .map_err(storage_error)?;
+            ids.push(id);
+        }
+        tx.commit().await.map_err(storage_error)?;
+
+        Ok(ids)
+    }
+
+    async fn similarity_search_by_vector_with_filter(
+        &self,
+        embedding: &[f32],
+        k: usize,
+        filter: &MetadataFilter,
+    ) -> VectorStoreResult<Vec<(Document, f32)>> {
+        self.ensure_table().await?;
+        let rows = sqlx::query(&format!(
+            "SELECT id, content, metadata, embedding FROM {}",
+            self.table
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(storage_error)?;
+
+        let mut scored = Vec::new();
+        for row in rows {
+            let metadata: String = row.try_get("metadata").map_err(storage_error)?;
+            let document = Document {
+                id: Some(row.try_get("id").map_err(storage_error)?),
+                content: row.try_get("content").map_err(storage_error)?,
+                metadata: serde_json::from_str(&metadata)?,
+            };
+            if !filter.matches(&document.metadata) {
+                continue;
+            }
+
+            let stored = decode(row.try_get("embedding").map_err(storage_error)?);
+            if stored.len() != embedding.len() {
+                return Err(VectorStoreError::DimensionMismatch {
+                    expected: stored.len(),
+                    actual: embedding.len(),
+                });
+            }
+            scored.push((document, cosine(embedding, &stored)));
+        }
+
+        Ok(select_top_k(scored, k))
+    }
+
+    async fn delete(&self, ids: &[String]) -> VectorStoreResult<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.ensure_table().await?;
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let sql = format!("DELETE FROM {} WHERE id IN ({})", self.table, placeholders);
+        let mut query = sqlx::query(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        query.execute(&self.pool).await.map_err(storage_error)?;
+        Ok(())
+    }
+}
+
+fn encode(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn decode(bytes: Vec<u8>) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn storage_error(err: sqlx::Error) -> VectorStoreError {
+    VectorStoreError::Storage(err.to_string())
+}
+
+/// Builder for SqliteVectorStore
+pub struct SqliteVectorStoreBuilder {
+    pool: SqlitePool,
+    embeddings: Arc<dyn BaseEmbedding>,
+    table: String,
+}
+
+impl SqliteVectorStoreBuilder {
+    pub fn new(pool: SqlitePool, embeddings: Arc<dyn BaseEmbedding>) -> Self {
+        Self {
+            pool,
+            embeddings,
+            table: "documents".to_string(),
+        }
+    }
+
+    /// Table to store documents in; must be a plain SQL identifier
+    pub fn table(mut self, table: impl Into<String>) -> VectorStoreResult<Self> {
+        self.table = sql_identifier(table.into())?;
+        Ok(self)
+    }
+
+    pub fn build(self) -> SqliteVectorStore {
+        SqliteVectorStore {
+            pool: self.pool,
+            table: self.table,
+            embeddings: self.embeddings,
+            table_ready: AtomicBool::new(false),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Response cache tests for agentic_optio_rs
+
+use agentic_optio_rs::cache::{cache_key, Cache, CachedChatModel, LruCache, SemanticCache};
+use agentic_optio_rs::core::messages::AIMessage;
+use agentic_optio_rs::testing::MockChatModel;
+use agentic_optio_rs::{BaseChatModel, Message};
+use futures::StreamExt;
+use std::sync::Arc;
+
+mod common;
+use common::LetterEmbedding;
+
+#[tokio::test]
+async fn test_lru_cache_evicts_least_recently_used() {
+    let cache = LruCache::new(2);
+    cache.put("a", &AIMessage::new("1")).await.unwrap();
+    cache.put("b", &AIMessage::new("2")).await.unwrap();
+    assert_eq!(cache.get("a").await.unwrap().unwrap().content, "1");
+
+    cache.put("c", &AIMessage::new("3")).await.unwrap();
+    assert!(cache.get("b").await.unwrap().is_none());
+    assert!(cache.get("a").await.unwrap().is_some());
+    assert_eq!(cache.len(), 2);
+
+    cache.put("a", &AIMessage::new("updated")).await.unwrap();
+    assert_eq!(cache.get("a").await.unwrap().unwrap().content, "updated");
+    cache.remove("a").await.unwrap();
+    assert_eq!(cache.len(), 1);
+    cache.clear().await.unwrap();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_cache_key_covers_model_messages_and_options() {
+    let messages = [Message::user("hi")];
+    let options = serde_json::json!({"temperature": 0.0});
+    let key = cache_key("m", "p", &messages, &options);
+
+    assert_eq!(key, cache_key("m", "p", &messages, &options));
+    assert_eq!(key.len(), 32);
+    assert_ne!(key, cache_key("other", "p", &messages, &options));
+    assert_ne!(key, cache_key("m", "p", &[Message::user("hey")], &options));
+    assert_ne!(
+        key,
+        cache_key(
+            "m",
+            "p",
+            &messages,
+            &serde_json::json!({"temperature": 1.0})
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_cached_model_serves_identical_prompts() {
+    let model = Arc::new(MockChatModel::with_responses(["Paris", "Berlin"]));
+    let cached = CachedChatModel::new(model.clone(), Arc::new(LruCache::new(10)));
+
+    let question = [Message::user("Capital of France?")];
+    assert_eq!(cached.invoke(&question).await.unwrap().content, "Paris");
+    assert_eq!(cached.invoke(&question).await.unwrap().content, "Paris");
+    assert_eq!(model.received().len(), 1);
+
+    let other = [Message::user("Capital of Germany?")];
+    assert_eq!(cached.invoke(&other).await.unwrap().content, "Berlin");
+    assert_eq!(model.received().len(), 2);
+}
+
+#[tokio::test]
+async fn test_cached_model_keys_on_tools_and_options() {
+    let model = Arc::new(MockChatModel::with_responses(["a", "b"]));
+    let cache: Arc<dyn Cache> = Arc::new(LruCache::new(10));
+    let messages = [Message::user("hi")];
+    let tools = [serde_json::json!({"type": "function", "function": {"name": "search"}})];
+
+    let cached = CachedChatModel::new(model.clone(), cache.clone());
+    cached.invoke(&messages).await.unwrap();
+    cached.invoke_with_tools(&messages, &tools).await.unwrap();
+    assert_eq!(model.received().len(), 2);
+
+    let warm = CachedChatModel::new(model.clone(), cache.clone())
+        .options(serde_json::json!({"temperature": 0.7}));
+    // Different options miss, and the script is exhausted
+    assert!(warm.invoke(&messages).await.is_err());
+}
+
+#[tokio::test]
+async fn test_cached_model_caches_finished_streams() {
+    let model = Arc::new(MockChatModel::with_messages(vec![AIMessage::new(
+        "hello there world",
+    )]));
+    let cached = CachedChatModel::new(model.clone(), Arc::new(LruCache::new(10)));
+    let messages = [Message::user("hi")];
+
+    let chunks: Vec<_> = cached.stream(&messages).await.unwrap().collect().await;
+    assert_eq!(chunks.len(), 3);
+
+    let mut replay = cached.stream(&messages).await.unwrap();
+    assert_eq!(
+        replay.next().await.unwrap().unwrap().content,
+        "hello there world"
+    );
+    assert!(replay.next().await.is_none());
+    assert_eq!(
+        cached.invoke(&messages).await.unwrap().content,
+        "hello there world"
+    );
+    assert_eq!(model.received().len(), 1);
+}
+
+#[tokio::test]
+async fn test_semantic_cache_matches_similar_prompts() {
+    let model = Arc::new(MockChatModel::with_responses([
+        "Use the reset link",
+        "Sunny",
+        "Other namespace",
+    ]));
+    let cache = Arc::new(SemanticCache::in_memory(Arc::new(LetterEmbedding)).threshold(0.95));
+    let support = CachedChatModel::semantic(model.clone(), cache.clone()).namespace("support");
+
+    let reset = [Message::user("How do I reset my password?")];
+    support.invoke(&reset).await.unwrap();
+    let similar = [Message::user("How can I reset my password?")];
+    assert_eq!(
+        support.invoke(&similar).await.unwrap().content,
+        "Use the reset link"
+    );
+    assert_eq!(model.received().len(), 1);
+
+    let unrelated = [Message::user("What is the weather in Paris today?")];
+    assert_eq!(support.invoke(&unrelated).await.unwrap().content, "Sunny");
+
+    let billing = CachedChatModel::semantic(model.clone(), cache.clone()).namespace("billing");
+    assert_eq!(
+        billing.invoke(&reset).await.unwrap().content,
+        "Other namespace"
+    );
+    assert_eq!(model.received().len(), 3);
+
+    assert!(cache
+        .lookup("elsewhere", "user: How do I reset my password?")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_sqlite_cache_survives_reopen() {
+    use agentic_optio_rs::cache::SqliteCache;
+
+    let path = std::env::temp_dir().join(format!("optio-cache-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let model = Arc::new(MockChatModel::with_responses(["Paris"]));
+    let cache = SqliteCache::open(&path).await.unwrap().build();
+    let pool = cache.pool().clone();
+    let cached = CachedChatModel::new(model.clone(), Arc::new(cache));
+    let question = [Message::user("Capital of France?")];
+    cached.invoke(&question).await.unwrap();
+    pool.close().await;
+
+    let reopened = SqliteCache::open(&path).await.unwrap().build();
+    let cached = CachedChatModel::new(model.clone(), Arc::new(reopened));
+    assert_eq!(cached.invoke(&question).await.unwrap().content, "Paris");
+    assert_eq!(model.received().len(), 1);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_sqlite_cache_ttl_and_size_limit() {
+    use agentic_optio_rs::cache::SqliteCache;
+    use std::time::Duration;
+
+    let path = std::env::temp_dir().join(format!("optio-cache-ttl-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let cache = SqliteCache::open(&path)
+        .await
+        .unwrap()
+        .table("short_lived")
+        .unwrap()
+        .ttl(Duration::from_millis(50))
+        .build();
+    cache.put("a", &AIMessage::new("1")).await.unwrap();
+    assert!(cache.get("a").await.unwrap().is_some());
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert!(cache.get("a").await.unwrap().is_none());
+    cache.put("b", &AIMessage::new("2")).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert_eq!(cache.purge_expired().await.unwrap(), 1);
+    assert!(cache.is_empty().await.unwrap());
+
+    let bounded = SqliteCache::builder(cache.pool().clone())
+        .max_entries(2)
+        .build();
+    bounded.put("a", &AIMessage::new("1")).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    bounded.put("b", &AIMessage::new("2")).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    bounded.get("a").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    bounded.put("c", &AIMessage::new("3")).await.unwrap();
+    assert_eq!(bounded.len().await.unwrap(), 2);
+    assert!(bounded.get("b").await.unwrap().is_none());
+    assert_eq!(bounded.get("a").await.unwrap().unwrap().content, "1");
+
+    assert!(SqliteCache::builder(cache.pool().clone())
+        .table("bad name")
+        .is_err());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[cfg(feature = "redis")]
+#[tokio::test]
+#[ignore] // Requires Redis at REDIS_URL
+async fn test_redis_cache_round_trip() {
+    use agentic_optio_rs::cache::RedisCache;
+    use std::time::Duration;
+
+    let url = std::env::var("REDIS_URL").unwrap();
+    let cache = RedisCache::connect(&url)
+        .await
+        .unwrap()
+        .prefix("optio_test:")
+        .ttl(Duration::from_millis(200))
+        .build();
+    cache.clear().await.unwrap();
+
+    let model = Arc::new(MockChatModel::with_responses(["Paris"]));
+    let question = [Message::user("Capital of France?")];
+    let worker = CachedChatModel::new(model.clone(), Arc::new(cache.clone()));
+    worker.invoke(&question).await.unwrap();
+    // A second worker sharing the server hits the same entry
+    let other = CachedChatModel::new(model.clone(), Arc::new(cache.clone()));
+    assert_eq!(other.invoke(&question).await.unwrap().content, "Paris");
+    assert_eq!(model.received().len(), 1);
+
+    cache.put("a", &AIMessage::new("1")).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(cache.get("a").await.unwrap().is_none());
+
+    cache.put("b", &AIMessage::new("2")).await.unwrap();
+    cache.clear().await.unwrap();
+    assert!(cache.get("b").await.unwrap().is_none());
+}
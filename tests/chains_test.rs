@@ -0,0 +1,170 @@
+//! Chain tests for agentic_optio_rs
+
+use agentic_optio_rs::chains::{
+    ChainError, ClassificationChain, ExtractionChain, SummarizeChain, SummaryProgress,
+};
+use agentic_optio_rs::output_parsers::ParseError;
+use agentic_optio_rs::testing::MockChatModel;
+use agentic_optio_rs::text_splitter::TokenSplitter;
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn test_summarize_chain_reduces_until_it_fits() {
+    let model = Arc::new(MockChatModel::with_responses([
+        "s1 a",
+        "s2 b",
+        "s3 c",
+        "r1 x",
+        "r2 y",
+        "final summary",
+    ]));
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let recorded = progress.clone();
+    let chain = SummarizeChain::new(model.clone())
+        .splitter(TokenSplitter::new(3))
+        .token_budget(4)
+        .concurrency(1)
+        .on_progress(move |p| recorded.lock().unwrap().push(*p));
+
+    let summary = chain
+        .invoke("one two three four five six seven eight nine")
+        .await
+        .unwrap();
+    assert_eq!(summary.summary, "final summary");
+    assert_eq!(summary.chunk_summaries, ["s1 a", "s2 b", "s3 c"]);
+    assert_eq!(summary.rounds, 2);
+    assert_eq!(model.calls(), 6);
+    assert!(model.received()[0][0].content().ends_with("one two three"));
+    assert!(model.received()[3][0].content().ends_with("s1 a\n\ns2 b"));
+    assert_eq!(
+        *progress.lock().unwrap(),
+        [
+            SummaryProgress::Split { chunks: 3 },
+            SummaryProgress::Mapped { done: 1, total: 3 },
+            SummaryProgress::Mapped { done: 2, total: 3 },
+            SummaryProgress::Mapped { done: 3, total: 3 },
+            SummaryProgress::Reduced {
+                round: 1,
+                summaries: 2
+            },
+            SummaryProgress::Reduced {
+                round: 2,
+                summaries: 1
+            },
+        ]
+    );
+
+    let model = Arc::new(MockChatModel::with_responses(["s1 a", "s2 b", "s3 c"]));
+    let chain = SummarizeChain::new(model.clone())
+        .splitter(TokenSplitter::new(3))
+        .token_budget(4)
+        .max_rounds(0);
+    assert!(matches!(
+        chain
+            .invoke("one two three four five six seven eight nine")
+            .await,
+        Err(ChainError::Other(_))
+    ));
+    assert_eq!(model.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_summarize_chain_skips_reduce_for_short_text() {
+    let model = Arc::new(MockChatModel::new().respond("  A short note.  "));
+    let summary = SummarizeChain::new(model.clone())
+        .invoke("Meeting moved to Friday.")
+        .await
+        .unwrap();
+    assert_eq!(summary.summary, "A short note.");
+    assert_eq!(summary.rounds, 0);
+    assert_eq!(model.calls(), 1);
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Contract {
+    parties: Vec<String>,
+    effective_date: Option<String>,
+    amount: Option<u32>,
+}
+
+#[tokio::test]
+async fn test_extraction_chain_merges_chunks() {
+    let model = Arc::new(MockChatModel::with_responses([
+        r#"{"parties": ["Acme", "Globex"], "effective_date": null, "amount": 100}"#,
+        "Sure! I found these.",
+        r#"{"parties": ["Globex", "Initech"], "effective_date": "2024-01-01", "amount": 200}"#,
+    ]));
+    let chain = ExtractionChain::new(model.clone())
+        .splitter(TokenSplitter::new(3))
+        .schema(r#"{"parties": ["..."], "effective_date": "YYYY-MM-DD", "amount": 0}"#)
+        .concurrency(1);
+
+    let contract: Contract = chain.extract("one two three four five six").await.unwrap();
+    assert_eq!(
+        contract,
+        Contract {
+            parties: vec!["Acme".into(), "Globex".into(), "Initech".into()],
+            effective_date: Some("2024-01-01".into()),
+            amount: Some(100),
+        }
+    );
+    // The second chunk was asked again after its reply did not parse
+    assert_eq!(model.calls(), 3);
+    assert!(model.received()[0][0].content().contains("YYYY-MM-DD"));
+    assert_eq!(model.received()[2][1].content(), "four five six");
+
+    let model = Arc::new(MockChatModel::new().respond(r#"{"parties": "Acme"}"#));
+    let result = ExtractionChain::new(model)
+        .extract::<Contract>("Acme signs.")
+        .await;
+    assert!(matches!(
+        result,
+        Err(ChainError::Parse(ParseError::Invalid { .. }))
+    ));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Queue {
+    Billing,
+    Technical,
+}
+
+#[tokio::test]
+async fn test_classification_chain_returns_label_and_rationale() {
+    let model = Arc::new(MockChatModel::with_responses([
+        r#"{"label": "shipping", "confidence": 0.9, "rationale": "About a parcel."}"#,
+        r#"{"label": "Technical", "confidence": "75%", "rationale": "The export is broken."}"#,
+    ]));
+    let chain = ClassificationChain::new(model.clone())
+        .instructions("Route the support ticket.")
+        .label("billing", "Invoices and refunds", Queue::Billing)
+        .label("technical", "Bugs and outages", Queue::Technical)
+        .example("I was charged twice", "billing");
+
+    let ticket = chain
+        .classify("The export button does nothing")
+        .await
+        .unwrap();
+    assert_eq!(ticket.label, "technical");
+    assert_eq!(ticket.value, Queue::Technical);
+    assert_eq!(ticket.confidence, 0.75);
+    assert_eq!(ticket.rationale, "The export is broken.");
+
+    let received = model.received();
+    let system = received[0][0].content();
+    assert!(system.contains("- billing: Invoices and refunds"));
+    assert!(system.contains("Text: I was charged twice\nLabel: billing"));
+    assert!(received[1][3]
+        .content()
+        .contains("expected one of: billing, technical"));
+
+    let model = Arc::new(MockChatModel::new().respond(r#"{"label": "billing"}"#));
+    let ticket = ClassificationChain::new(model)
+        .label("billing", "Invoices and refunds", Queue::Billing)
+        .classify("Refund please")
+        .await
+        .unwrap();
+    assert_eq!(ticket.confidence, 1.0);
+    assert_eq!(ticket.rationale, "");
+}
@@ -0,0 +1,153 @@
+//! Shared test helpers.
+
+#![allow(dead_code)]
+
+use agentic_optio_rs::core::messages::{AIMessage, ToolCall};
+use agentic_optio_rs::models::base::{BaseEmbedding, ModelResult};
+use async_trait::async_trait;
+
+pub fn tool_call(name: &str, args: serde_json::Value) -> AIMessage {
+    AIMessage::with_tool_calls(
+        "",
+        vec![ToolCall {
+            id: "call_1".to_string(),
+            name: name.to_string(),
+            args,
+        }],
+    )
+}
+
+/// Deterministic embedding counting each letter a-z
+pub struct LetterEmbedding;
+
+#[async_trait]
+impl BaseEmbedding for LetterEmbedding {
+    async fn embed(&self, texts: &[String]) -> ModelResult<Vec<Vec<f32>>> {
+        Ok(texts
+            .iter()
+            .map(|text| {
+                let mut counts = vec![0.0; 26];
+                for c in text.to_lowercase().chars().filter(char::is_ascii_lowercase) {
+                    counts[(c as u8 - b'a') as usize] += 1.0;
+                }
+                counts
+            })
+            .collect())
+    }
+
+    fn dimension(&self) -> usize {
+        26
+    }
+}
+
+/// Accept one HTTP request, answer 200 with `response`, and return the request
+/// head and JSON body
+pub async fn capture_request(
+    response: &'static str,
+) -> (String, tokio::task::JoinHandle<(String, serde_json::Value)>) {
+    capture_request_with("200 OK", "", response).await
+}
+
+/// Like [`capture_request`], answering with `status` (e.g. `"429 Too Many
+/// Requests"`) and extra `headers`, each ending in `\r\n`
+pub async fn capture_request_with(
+    status: &'static str,
+    headers: &'static str,
+    response: &'static str,
+) -> (String, tokio::task::JoinHandle<(String, serde_json::Value)>) {
+    let (url, handle) = capture_requests(vec![(status, headers, response)]).await;
+    let handle = tokio::spawn(async move { handle.await.unwrap().remove(0) });
+    (url, handle)
+}
+
+/// Answer one request per connection with each `(status, headers, body)` in
+/// turn, closing the connection after each, and return every request head and
+/// JSON body (null for requests without one)
+pub async fn capture_requests(
+    replies: Vec<(&'static str, &'static str, &'static str)>,
+) -> (
+    String,
+    tokio::task::JoinHandle<Vec<(String, serde_json::Value)>>,
+) {
+    use tokio::io::AsyncWriteExt;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let mut captured = Vec::new();
+        for (status, headers, response) in replies {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            captured.push(read_request(&mut socket).await);
+            let reply = format!(
+                "HTTP/1.1 {}\r\n{}connection: close\r\ncontent-length: {}\r\n\r\n{}",
+                status,
+                headers,
+                response.len(),
+                response
+            );
+            socket.write_all(reply.as_bytes()).await.unwrap();
+        }
+        captured
+    });
+    (url, handle)
+}
+
+/// Answer 200 with each of `responses` in turn on a single kept-alive
+/// connection, refusing any other, and return the number of requests served
+pub async fn serve_one_connection(
+    responses: Vec<&'static str>,
+) -> (String, tokio::task::JoinHandle<usize>) {
+    use tokio::io::AsyncWriteExt;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        drop(listener);
+        for response in &responses {
+            read_request(&mut socket).await;
+            let reply = format!(
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+                response.len(),
+                response
+            );
+            socket.write_all(reply.as_bytes()).await.unwrap();
+        }
+        responses.len()
+    });
+    (url, handle)
+}
+
+/// Read one request from `socket`, returning its head and JSON body (null if
+/// it has none)
+async fn read_request(socket: &mut tokio::net::TcpStream) -> (String, serde_json::Value) {
+    use tokio::io::AsyncReadExt;
+
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    let (head, length) = loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        assert!(n > 0, "connection closed before the request was complete");
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request);
+        if let Some(end) = text.find("\r\n\r\n") {
+            let head = text[..end].to_string();
+            let length = head
+                .lines()
+                .find_map(|l| {
+                    l.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(String::from)
+                })
+                .map_or(0, |v| v.trim().parse::<usize>().unwrap());
+            request.drain(..end + 4);
+            break (head, length);
+        }
+    };
+    while request.len() < length {
+        let n = socket.read(&mut buf).await.unwrap();
+        assert!(n > 0, "connection closed before the request was complete");
+        request.extend_from_slice(&buf[..n]);
+    }
+    (head, serde_json::from_slice(&request).unwrap_or_default())
+}
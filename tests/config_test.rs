@@ -0,0 +1,151 @@
+//! Tests for declarative configuration files.
+
+#![cfg(feature = "ollama")]
+
+use agentic_optio_rs::config::{Config, ConfigError, MemoryConfig};
+use agentic_optio_rs::tools::{FunctionTool, ToolRegistry};
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn config_path(test: &str, name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("optio-config-{}-{}", test, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn tools() -> ToolRegistry {
+    let mut tools = ToolRegistry::new();
+    for name in ["search", "shell"] {
+        tools.register(FunctionTool::new(name, "Test tool", |_| async move {
+            Ok(String::new())
+        }));
+    }
+    tools
+}
+
+const CONFIG: &str = r#"{
+    "models": {
+        "default": {"model": "ollama:llama3.2", "temperature": 0.2},
+        "local": {
+            "model": "ollama:qwen2.5:7b",
+            "base_url": "${OPTIO_CONFIG_TEST_HOST:-http://gpu:11434}"
+        }
+    },
+    "tools": {"shell": {"enabled": false}},
+    "budgets": {"standard": {"max_tokens": 50000, "max_duration_secs": 120}},
+    "memory": {"notes": {"backend": "in_memory", "embedding": "nomic-embed-text"}},
+    "agents": {
+        "researcher": {"model": "local", "tools": ["search", "shell"], "budget": "standard"},
+        "writer": {"system_prompt": "You write."}
+    }
+}"#;
+
+#[tokio::test]
+async fn test_config_materializes_declared_objects() {
+    let path = config_path("materialize", "optio.json", CONFIG);
+    let config = Config::from_path(&path).unwrap();
+    assert_eq!(
+        config.models["local"].base_url.as_deref(),
+        Some("http://gpu:11434")
+    );
+    assert!(matches!(
+        config.memory["notes"],
+        MemoryConfig::InMemory { .. }
+    ));
+
+    let runtime = config.materialize(&tools()).await.unwrap();
+    assert_eq!(runtime.agent_names(), ["researcher", "writer"]);
+    assert_eq!(runtime.model("local").unwrap().model_name(), "qwen2.5:7b");
+    assert_eq!(runtime.model("default").unwrap().provider_name(), "ollama");
+
+    let budget = runtime.budget("standard").unwrap();
+    assert_eq!(budget.max_tokens, Some(50_000));
+    assert_eq!(budget.max_duration, Some(Duration::from_secs(120)));
+
+    // The disabled tool is left out
+    let researcher = runtime.agent("researcher").unwrap();
+    assert_eq!(researcher.tools().len(), 1);
+    assert!(researcher.tools().get("search").is_some());
+    assert!(runtime.agent("writer").unwrap().tools().is_empty());
+    assert!(runtime.memory("notes").is_some());
+}
+
+#[tokio::test]
+async fn test_config_rejects_unknown_references() {
+    let cases = [
+        r#"{"agents": {"a": {}}}"#,
+        r#"{"models": {"default": {"model": "ollama:llama3.2"}},
+            "agents": {"a": {"budget": "missing"}}}"#,
+        r#"{"models": {"default": {"model": "ollama:llama3.2"}},
+            "agents": {"a": {"tools": ["missing"]}}}"#,
+        r#"{"tools": {"missing": {}}}"#,
+    ];
+    for (i, case) in cases.iter().enumerate() {
+        let path = config_path(&format!("unknown-{i}"), "optio.json", case);
+        let err = Config::from_path(&path)
+            .unwrap()
+            .materialize(&tools())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::Invalid(_)), "{case}: {err}");
+    }
+
+    let path = config_path(
+        "provider",
+        "optio.json",
+        r#"{"models": {"m": {"model": "x:y"}}}"#,
+    );
+    let err = Config::from_path(&path)
+        .unwrap()
+        .materialize(&tools())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::Model(_)));
+}
+
+#[test]
+fn test_config_load_errors() {
+    let path = config_path("typo", "optio.json", r#"{"agent": {}}"#);
+    assert!(matches!(
+        Config::from_path(&path),
+        Err(ConfigError::Load(_))
+    ));
+
+    let path = config_path(
+        "env",
+        "optio.json",
+        r#"{"models": {"m": {"model": "${OPTIO_CONFIG_TEST_UNSET}"}}}"#,
+    );
+    let err = Config::from_path(&path).unwrap_err().to_string();
+    assert!(err.contains("OPTIO_CONFIG_TEST_UNSET"));
+}
+
+#[cfg(feature = "toml")]
+#[tokio::test]
+async fn test_config_from_toml() {
+    let path = config_path(
+        "toml",
+        "optio.toml",
+        r#"
+[models.default]
+model = "ollama:llama3.2"
+
+[budgets.small]
+max_model_calls = 3
+
+[agents.helper]
+budget = "small"
+max_iterations = 2
+"#,
+    );
+    let runtime = Config::from_path(&path)
+        .unwrap()
+        .materialize(&ToolRegistry::new())
+        .await
+        .unwrap();
+    assert_eq!(runtime.agent("helper").unwrap().name(), "helper");
+    assert_eq!(runtime.budget("small").unwrap().max_model_calls, Some(3));
+}
@@ -0,0 +1,121 @@
+//! Tests for credential providers.
+
+mod common;
+
+use agentic_optio_rs::credentials::{
+    CredentialChain, CredentialError, CredentialProvider, DotenvCredentials, EnvCredentials,
+    FnCredentials,
+};
+
+const DOTENV: &str = r#"
+# Service keys
+OPENAI_API_KEY=sk-plain # rotated monthly
+export QDRANT_API_KEY='qd # literal'
+GREETING="hello\n\"world\""
+EMPTY=
+"#;
+
+#[tokio::test]
+async fn test_dotenv_credentials_parse_values() {
+    let dotenv = DotenvCredentials::parse(DOTENV).unwrap();
+    assert_eq!(
+        dotenv.names(),
+        ["EMPTY", "GREETING", "OPENAI_API_KEY", "QDRANT_API_KEY"]
+    );
+    assert_eq!(
+        dotenv.get("OPENAI_API_KEY").await.unwrap().unwrap(),
+        "sk-plain"
+    );
+    assert_eq!(
+        dotenv.get("QDRANT_API_KEY").await.unwrap().unwrap(),
+        "qd # literal"
+    );
+    assert_eq!(
+        dotenv.get("GREETING").await.unwrap().unwrap(),
+        "hello\n\"world\""
+    );
+    assert_eq!(dotenv.get("EMPTY").await.unwrap().unwrap(), "");
+    assert!(dotenv.get("MISSING").await.unwrap().is_none());
+    assert!(!format!("{dotenv:?}").contains("sk-plain"));
+
+    assert!(DotenvCredentials::parse("NO_EQUALS").is_err());
+    assert!(DotenvCredentials::parse("KEY=\"open").is_err());
+}
+
+#[tokio::test]
+async fn test_credential_chain_returns_first_match() {
+    let chain = CredentialChain::new()
+        .with(DotenvCredentials::parse("A=from-dotenv").unwrap())
+        .with(FnCredentials::new(|name| async move {
+            Ok(Some(format!("vault-{}", name.to_lowercase())))
+        }));
+    assert_eq!(chain.require("A").await.unwrap(), "from-dotenv");
+    assert_eq!(chain.require("B").await.unwrap(), "vault-b");
+
+    let err = EnvCredentials
+        .require("OPTIO_CREDENTIALS_TEST_UNSET")
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, CredentialError::NotFound(name) if name == "OPTIO_CREDENTIALS_TEST_UNSET")
+    );
+
+    let failing = CredentialChain::new().with(FnCredentials::new(|_| async move {
+        Err(CredentialError::Backend("vault sealed".to_string()))
+    }));
+    assert!(failing.get("A").await.is_err());
+}
+
+#[cfg(feature = "openai")]
+#[tokio::test]
+async fn test_openai_chat_resolves_key_per_request() {
+    use agentic_optio_rs::models::OpenAIChat;
+    use agentic_optio_rs::{BaseChatModel, Message};
+    use common::capture_request;
+
+    let (url, server) = capture_request(r#"{"choices": [{"message": {"content": "hi"}}]}"#).await;
+    let llm = OpenAIChat::builder("gpt-4o-mini")
+        .base_url(url)
+        .credentials(DotenvCredentials::parse("OPENAI_API_KEY=sk-dotenv").unwrap())
+        .build();
+    assert!(!format!("{llm:?}").contains("sk-dotenv"));
+
+    let response = llm.invoke(&[Message::user("Hello")]).await.unwrap();
+    assert_eq!(response.content, "hi");
+    let (head, _) = server.await.unwrap();
+    assert!(head
+        .to_lowercase()
+        .contains("authorization: bearer sk-dotenv"));
+}
+
+#[tokio::test]
+async fn test_langsmith_exporter_from_credentials() {
+    use agentic_optio_rs::telemetry::export::{ExportError, TraceExporter};
+
+    let credentials = DotenvCredentials::parse("LANGSMITH_API_KEY=ls-key").unwrap();
+    assert!(TraceExporter::langsmith_from_credentials(&credentials)
+        .await
+        .is_ok());
+    let err = TraceExporter::langfuse_from_credentials(&credentials)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ExportError::Credential(CredentialError::NotFound(_))
+    ));
+}
+
+#[cfg(feature = "keyring")]
+#[tokio::test]
+#[ignore] // Needs an OS keyring
+async fn test_keyring_credentials_round_trip() {
+    use agentic_optio_rs::credentials::KeyringCredentials;
+
+    let keyring = KeyringCredentials::new("agentic-optio-test");
+    keyring.set("OPTIO_TEST_KEY", "sk-keyring").await.unwrap();
+    assert_eq!(
+        keyring.require("OPTIO_TEST_KEY").await.unwrap(),
+        "sk-keyring"
+    );
+    assert!(keyring.get("OPTIO_TEST_MISSING").await.unwrap().is_none());
+}
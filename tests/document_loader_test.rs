@@ -0,0 +1,180 @@
+//! Document loader tests for agentic_optio_rs
+
+use agentic_optio_rs::document_loaders::{
+    DirectoryLoader, DocumentLoader, HtmlLoader, MarkdownLoader, TextLoader,
+};
+use agentic_optio_rs::text_splitter::RecursiveCharacterSplitter;
+use std::path::PathBuf;
+
+fn fixture_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("optio-loaders-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("guides/drafts")).unwrap();
+    std::fs::write(dir.join("notes.txt"), "Plain notes.\n\nSecond paragraph.").unwrap();
+    std::fs::write(
+        dir.join("guides/intro.md"),
+        "---\nauthor: sam\n---\n# Getting Started\n\nInstall the crate.",
+    )
+    .unwrap();
+    std::fs::write(dir.join("guides/drafts/wip.md"), "# Draft\n\nNot ready.").unwrap();
+    std::fs::write(
+        dir.join("guides/page.html"),
+        "<html><head><title>Home &amp; Away</title><style>p{}</style></head>\
+         <body><h1>Welcome</h1><p>Hello <b>there</b></p><!-- hidden --></body></html>",
+    )
+    .unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_text_and_markdown_loaders() {
+    let dir = fixture_dir("single");
+
+    let text = TextLoader::new(dir.join("notes.txt")).load().await.unwrap();
+    assert_eq!(text.len(), 1);
+    assert_eq!(text[0].content, "Plain notes.\n\nSecond paragraph.");
+    assert!(text[0].metadata["source"]
+        .as_str()
+        .unwrap()
+        .ends_with("notes.txt"));
+
+    let md = MarkdownLoader::new(dir.join("guides/intro.md"))
+        .load()
+        .await
+        .unwrap();
+    assert_eq!(md[0].content, "# Getting Started\n\nInstall the crate.");
+    assert_eq!(md[0].metadata["title"], "Getting Started");
+
+    let missing = TextLoader::new(dir.join("missing.txt")).load().await;
+    assert!(missing.unwrap_err().to_string().contains("missing.txt"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_html_loader_strips_markup() {
+    let dir = fixture_dir("html");
+
+    let docs = HtmlLoader::new(dir.join("guides/page.html"))
+        .load()
+        .await
+        .unwrap();
+    assert_eq!(docs[0].content, "Welcome\n\nHello there");
+    assert_eq!(docs[0].metadata["title"], "Home & Away");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_directory_loader_globs() {
+    let dir = fixture_dir("walk");
+
+    let all = DirectoryLoader::new(&dir).load().await.unwrap();
+    assert_eq!(all.len(), 4);
+
+    let loader = DirectoryLoader::new(&dir)
+        .glob("**/*.md")
+        .unwrap()
+        .exclude("**/drafts/**")
+        .unwrap();
+    let docs = loader.load().await.unwrap();
+    assert_eq!(docs.len(), 1);
+    assert_eq!(docs[0].metadata["title"], "Getting Started");
+
+    let top_level = DirectoryLoader::new(&dir).glob("*.txt").unwrap();
+    assert_eq!(
+        top_level.paths().await.unwrap(),
+        vec![dir.join("notes.txt")]
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_load_and_split_keeps_source() {
+    let dir = fixture_dir("split");
+
+    let splitter = RecursiveCharacterSplitter::new(20);
+    let chunks = TextLoader::new(dir.join("notes.txt"))
+        .load_and_split(&splitter)
+        .await
+        .unwrap();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[1].content, "Second paragraph.");
+    assert!(chunks.iter().all(|c| c.metadata.contains_key("source")));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+/// Minimal PDF with one line of Helvetica text per page
+#[cfg(feature = "pdf")]
+fn write_pdf(path: &std::path::Path, pages: &[&str]) {
+    let n = pages.len();
+    let font_id = 3 + 2 * n;
+    let kids: Vec<String> = (0..n).map(|i| format!("{} 0 R", 3 + 2 * i)).collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), n),
+    ];
+    for (i, text) in pages.iter().enumerate() {
+        let stream = format!("BT /F1 12 Tf 72 720 Td ({}) Tj ET", text);
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+             /Resources << /Font << /F1 {} 0 R >> >> /Contents {} 0 R >>",
+            font_id,
+            4 + 2 * i
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            stream.len(),
+            stream
+        ));
+    }
+    objects.push("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string());
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, object));
+    }
+    let xref = pdf.len();
+    pdf.push_str(&format!(
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    ));
+    for offset in offsets {
+        pdf.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    ));
+    std::fs::write(path, pdf).unwrap();
+}
+
+#[cfg(feature = "pdf")]
+#[tokio::test]
+async fn test_pdf_loader_splits_pages() {
+    use agentic_optio_rs::document_loaders::PdfLoader;
+
+    let dir = fixture_dir("pdf");
+    let path = dir.join("handbook.pdf");
+    write_pdf(&path, &["First page text", "Second page text"]);
+
+    let pages = PdfLoader::new(&path).load().await.unwrap();
+    assert_eq!(pages.len(), 2);
+    assert!(pages[0].content.contains("First page text"));
+    assert!(pages[1].content.contains("Second page text"));
+    assert_eq!(pages[1].metadata["page"], 2);
+    assert_eq!(pages[1].metadata["total_pages"], 2);
+
+    let via_directory = DirectoryLoader::new(&dir).glob("*.pdf").unwrap();
+    assert_eq!(via_directory.load().await.unwrap().len(), 2);
+
+    std::fs::write(&path, "not a pdf").unwrap();
+    assert!(PdfLoader::new(&path).load().await.is_err());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,172 @@
+//! Evaluation tests for agentic_optio_rs
+//!
+//! Judges are mock models, so these run without Ollama.
+
+use agentic_optio_rs::eval::{
+    Dataset, EmbeddingSimilarity, EvalCase, EvalError, EvalRunner, Evaluator, ExactMatch, Example,
+    Grader, JudgeGrader, Rubric,
+};
+use agentic_optio_rs::testing::{FakeEmbedding, MockChatModel};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_judge_grader_scales_rubric_score() {
+    let judge = Arc::new(MockChatModel::with_responses([
+        r#"Looks right. {"reasoning": "Matches the reference", "score": 5}"#,
+        "Score: 2",
+        r#"{"reasoning": "off the scale", "score": 9}"#,
+    ]));
+    let grader = JudgeGrader::correctness(judge.clone());
+    let case = EvalCase::new("Capital of France?", "Paris").with_expected("Paris");
+
+    let score = grader.grade(&case).await.unwrap();
+    assert_eq!(score.grader, "correctness");
+    assert_eq!(score.value, 1.0);
+    assert_eq!(score.reasoning, "Matches the reference");
+    let prompt = judge.last_messages().unwrap()[1].content().to_string();
+    assert!(prompt.contains("Reference answer:\nParis"));
+
+    assert_eq!(grader.grade(&case).await.unwrap().value, 0.25);
+    assert!(matches!(
+        grader.grade(&case).await,
+        Err(EvalError::InvalidJudgement(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_groundedness_requires_context() {
+    let judge = Arc::new(MockChatModel::new());
+    let grader = JudgeGrader::groundedness(judge.clone());
+
+    let err = grader.grade(&EvalCase::new("q", "a")).await.unwrap_err();
+    assert!(matches!(
+        err,
+        EvalError::MissingField {
+            field: "context",
+            ..
+        }
+    ));
+    assert_eq!(judge.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_evaluator_aggregates_scores_and_errors() {
+    let judge = Arc::new(MockChatModel::with_responses([
+        r#"{"score": 3}"#,
+        r#"{"score": 1}"#,
+        r#"{"score": 2}"#,
+        "no idea",
+    ]));
+    let rubric = Rubric::new("helpful", "The response helps").levels(["no", "somewhat", "yes"]);
+    let evaluator = Evaluator::new()
+        .grader(JudgeGrader::new(judge.clone(), rubric))
+        .grader(JudgeGrader::tone(judge, "formal"));
+
+    let cases = [EvalCase::new("a", "1"), EvalCase::new("b", "2")];
+    let report = evaluator.evaluate(&cases).await;
+
+    assert_eq!(report.results[0].score("helpful"), Some(1.0));
+    assert_eq!(report.results[0].score("tone"), Some(0.0));
+    assert_eq!(report.results[1].score("helpful"), Some(0.5));
+    assert!(report.results[1].errors.contains_key("tone"));
+    assert_eq!(report.error_count(), 1);
+    let summary = report.summary();
+    assert_eq!(summary["helpful"].count, 2);
+    assert_eq!(summary["helpful"].mean, 0.75);
+    assert_eq!(summary["tone"].count, 1);
+    assert_eq!(report.pass_rate("helpful", 0.6), Some(0.5));
+    assert_eq!(report.pass_rate("missing", 0.5), None);
+}
+
+#[test]
+fn test_dataset_parses_jsonl_and_reports_bad_lines() {
+    let dataset = Dataset::from_jsonl(
+        r#"{"input": "Capital of France?", "expected": "Paris"}
+
+{"input": "Cite it", "context": ["Rust 1.0 shipped in 2015"]}
+"#,
+    )
+    .unwrap();
+    assert_eq!(dataset.len(), 2);
+    assert_eq!(dataset.examples[0].expected.as_deref(), Some("Paris"));
+    assert_eq!(dataset.examples[1].context.len(), 1);
+
+    let err = Dataset::from_jsonl("{\"input\": \"ok\"}\n{\"expected\": \"x\"}").unwrap_err();
+    assert!(matches!(err, EvalError::Dataset { line: 2, .. }));
+}
+
+#[tokio::test]
+async fn test_eval_runner_answers_and_grades_in_order() {
+    let target = Arc::new(MockChatModel::new().respond(" PARIS ").respond("5").fail(
+        agentic_optio_rs::models::base::ModelError::ApiError("down".to_string()),
+    ));
+    let dataset = Dataset::new(vec![
+        Example::new("Capital of France?").with_expected("Paris"),
+        Example::new("2 + 2?").with_expected("4"),
+        Example::new("Unanswered").with_expected("x"),
+    ]);
+
+    let report = EvalRunner::new(target)
+        .grader(ExactMatch::new())
+        .grader(EmbeddingSimilarity::new(Arc::new(FakeEmbedding::new(16))))
+        .concurrency(1)
+        .run(&dataset)
+        .await;
+
+    assert_eq!(report.results[0].case.output, " PARIS ");
+    assert_eq!(report.results[0].score("exact_match"), Some(1.0));
+    assert!(report.results[0].score("embedding_similarity").unwrap() > 0.99);
+    assert_eq!(report.results[1].score("exact_match"), Some(0.0));
+    assert!(report.results[2].scores.is_empty());
+    assert!(report.results[2].errors["target"].contains("down"));
+    assert_eq!(report.summary()["exact_match"].mean, 0.5);
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["summary"]["exact_match"]["count"], 2);
+    assert_eq!(json["results"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_benchmark_compares_models() {
+    use agentic_optio_rs::core::messages::{AIMessage, Usage};
+    use agentic_optio_rs::eval::{Benchmark, Percentiles};
+    use std::time::Duration;
+
+    let fast = MockChatModel::new()
+        .respond_with(AIMessage::new("one two").with_usage(Usage::new(10, 2)))
+        .respond_with(AIMessage::new("three").with_usage(Usage::new(10, 1)));
+    let slow = MockChatModel::new()
+        .respond("ok")
+        .latency(Duration::from_millis(30));
+
+    let report = Benchmark::new()
+        .model("fast", Arc::new(fast))
+        .model("slow", Arc::new(slow))
+        .pricing("fast", 1.0, 2.0)
+        .prompt("hi")
+        .repetitions(2)
+        .run()
+        .await;
+
+    let fast = report.model("fast").unwrap();
+    assert_eq!((fast.requests, fast.failures), (2, 0));
+    assert_eq!((fast.input_tokens, fast.output_tokens), (20, 3));
+    assert!((fast.cost - 0.026).abs() < 1e-9);
+
+    let slow = report.model("slow").unwrap();
+    assert_eq!(slow.failures, 1);
+    assert_eq!(slow.failure_rate(), 0.5);
+    assert!(slow.errors[0].contains("exhausted"));
+    assert!(slow.latency.p50 >= Duration::from_millis(30));
+    assert_eq!(slow.output_tokens, 1);
+    assert!(report.to_table().contains("| slow |"));
+
+    let ms = |v: &[u64]| {
+        v.iter()
+            .map(|&m| Duration::from_millis(m))
+            .collect::<Vec<_>>()
+    };
+    let p = Percentiles::from_durations(&ms(&(1..=100).collect::<Vec<_>>()));
+    assert_eq!(p.p50, Duration::from_millis(50));
+    assert_eq!(p.p99, Duration::from_millis(99));
+}
@@ -0,0 +1,101 @@
+//! Guardrail tests for agentic_optio_rs
+
+use agentic_optio_rs::core::messages::AIMessage;
+use agentic_optio_rs::guardrails::{
+    GuardedChatModel, Guardrail, GuardrailAction, GuardrailVerdict, JsonSchemaGuardrail,
+    RegexGuardrail,
+};
+use agentic_optio_rs::models::base::ModelError;
+use agentic_optio_rs::testing::MockChatModel;
+use agentic_optio_rs::{BaseChatModel, Message};
+use std::sync::Arc;
+
+mod common;
+
+#[tokio::test]
+async fn test_regex_guardrail_redacts() {
+    let model = Arc::new(MockChatModel::with_messages(vec![AIMessage::new(
+        "the password is hunter2",
+    )]));
+    let guarded = GuardedChatModel::new(model).guardrail(
+        RegexGuardrail::new([r"hunter\d"]).unwrap(),
+        GuardrailAction::Redact,
+    );
+
+    let response = guarded.invoke(&[Message::user("hi")]).await.unwrap();
+
+    assert_eq!(response.content, "the password is [REDACTED]");
+}
+
+#[tokio::test]
+async fn test_guardrail_blocks() {
+    let model = Arc::new(MockChatModel::with_messages(vec![AIMessage::new(
+        "not json",
+    )]));
+    let guarded = GuardedChatModel::new(model).guardrail(
+        JsonSchemaGuardrail::new(serde_json::json!({"type": "object"})),
+        GuardrailAction::Block,
+    );
+
+    let result = guarded.invoke(&[Message::user("hi")]).await;
+
+    assert!(matches!(result, Err(ModelError::GuardrailViolation(_))));
+}
+
+#[tokio::test]
+async fn test_guardrail_retries_with_feedback() {
+    let model = Arc::new(MockChatModel::with_messages(vec![
+        AIMessage::new(r#"{"name": "Ada"}"#),
+        AIMessage::new("```json\n{\"name\": \"Ada\", \"age\": 36}\n```"),
+    ]));
+    let schema = serde_json::json!({
+        "type": "object",
+        "required": ["name", "age"],
+        "properties": {"age": {"type": "integer", "minimum": 0}}
+    });
+    let guarded = GuardedChatModel::new(model.clone()).guardrail(
+        JsonSchemaGuardrail::new(schema),
+        GuardrailAction::Retry { max_retries: 1 },
+    );
+
+    let response = guarded.invoke(&[Message::user("Who?")]).await.unwrap();
+
+    assert!(response.content.contains("36"));
+    let second_call = &model.received()[1];
+    assert!(second_call.last().unwrap().content().contains("age"));
+}
+
+#[tokio::test]
+async fn test_json_schema_rejects_wrong_type() {
+    let guardrail = JsonSchemaGuardrail::new(serde_json::json!({
+        "type": "array",
+        "items": {"type": "string"}
+    }));
+
+    assert!(guardrail.validate(r#"["a", "b"]"#).await.is_pass());
+    assert!(matches!(
+        guardrail.validate(r#"["a", 1]"#).await,
+        GuardrailVerdict::Fail { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_pii_redaction_round_trip() {
+    use agentic_optio_rs::guardrails::PiiRedactingChatModel;
+
+    let model = Arc::new(MockChatModel::with_messages(vec![AIMessage::new(
+        "I will email [EMAIL_1] today",
+    )]));
+    let redacting = PiiRedactingChatModel::new(model.clone()).rehydrate_responses(true);
+
+    let response = redacting
+        .invoke(&[Message::user(
+            "Contact ada@example.com or 555-123-4567, card 4111 1111 1111 1111",
+        )])
+        .await
+        .unwrap();
+
+    let sent = model.received()[0][0].content().to_string();
+    assert_eq!(sent, "Contact [EMAIL_1] or [PHONE_1], card [CREDIT_CARD_1]");
+    assert_eq!(response.content, "I will email ada@example.com today");
+}
@@ -1,14 +1,30 @@
 //! Integration tests for agentic_optio_rs
 //!
-//! Note: These tests require Ollama to be running with the appropriate models.
-//! Run: ollama pull llama3.2 && ollama pull nomic-embed-text
+//! Note: the Ollama tests require Ollama to be running with the appropriate
+//! models until their responses are recorded into `tests/fixtures`. Record
+//! them with:
+//! ollama pull llama3.2 && ollama pull nomic-embed-text
+//! AGENTIC_OPTIO_VCR=record cargo test --features ollama --test integration_test -- --ignored
 
+#![cfg(feature = "ollama")]
+
+use agentic_optio_rs::testing::Vcr;
 use agentic_optio_rs::{BaseChatModel, BaseEmbedding, Message, OllamaChat, OllamaEmbedding};
 
+/// Replay server for the fixture `name`, recording from `OLLAMA_HOST` if it is
+/// missing
+async fn vcr(name: &str) -> Vcr {
+    let upstream =
+        std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".to_string());
+    let path = format!("{}/tests/fixtures/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    Vcr::start(path, upstream).await.unwrap()
+}
+
 #[tokio::test]
 #[ignore] // Ignore by default since it requires Ollama running
 async fn test_ollama_chat_invoke() {
-    let llm = OllamaChat::new("llama3.2");
+    let vcr = vcr("ollama_chat_invoke").await;
+    let llm = OllamaChat::builder("llama3.2").host(vcr.url()).build();
     let messages = vec![Message::user("Say 'test passed' and nothing else")];
 
     let result = llm.invoke(&messages).await;
@@ -21,7 +37,9 @@ async fn test_ollama_chat_invoke() {
 #[tokio::test]
 #[ignore]
 async fn test_ollama_chat_builder() {
+    let vcr = vcr("ollama_chat_builder").await;
     let llm = OllamaChat::builder("llama3.2")
+        .host(vcr.url())
         .temperature(0.7)
         .max_tokens(100)
         .build();
@@ -34,7 +52,10 @@ async fn test_ollama_chat_builder() {
 #[tokio::test]
 #[ignore]
 async fn test_ollama_embedding() {
-    let embedder = OllamaEmbedding::new("nomic-embed-text");
+    let vcr = vcr("ollama_embedding").await;
+    let embedder = OllamaEmbedding::builder("nomic-embed-text")
+        .host(vcr.url())
+        .build();
     let texts = vec!["Hello world".to_string(), "Test embedding".to_string()];
 
     let result = embedder.embed(&texts).await;
@@ -43,18 +64,27 @@ async fn test_ollama_embedding() {
     let embeddings = result.unwrap();
     assert_eq!(embeddings.len(), 2, "Should return 2 embeddings");
     assert!(!embeddings[0].is_empty(), "Embeddings should not be empty");
+    assert_eq!(embedder.dimension(), embeddings[0].len());
 }
 
 #[tokio::test]
 #[ignore]
 async fn test_ollama_embedding_query() {
-    let embedder = OllamaEmbedding::new("nomic-embed-text");
+    let vcr = vcr("ollama_embedding_query").await;
+    let embedder = OllamaEmbedding::builder("nomic-embed-text")
+        .host(vcr.url())
+        .build();
 
     let result = embedder.embed_query("Single query test").await;
     assert!(result.is_ok(), "Single query embedding should succeed");
 
     let embedding = result.unwrap();
     assert!(!embedding.is_empty(), "Embedding should not be empty");
+
+    let fresh = OllamaEmbedding::builder("nomic-embed-text")
+        .host(vcr.url())
+        .build();
+    assert_eq!(fresh.detect_dimension().await.unwrap(), embedding.len());
 }
 
 #[tokio::test]
@@ -0,0 +1,992 @@
+//! Tests for chat model providers and construction from a model spec.
+
+#![cfg(all(feature = "ollama", feature = "openai"))]
+
+mod common;
+
+use agentic_optio_rs::models::base::ModelError;
+use agentic_optio_rs::models::OpenAIChat;
+use agentic_optio_rs::{init_chat_model, AIMessage, BaseChatModel, ChatModel, Message};
+use common::{capture_request, capture_request_with, capture_requests};
+use futures::StreamExt;
+use std::sync::Arc;
+
+const COMPLETION: &str = r#"{
+    "choices": [{"message": {
+        "content": "",
+        "tool_calls": [{"id": "call_1", "type": "function",
+            "function": {"name": "search", "arguments": "{\"query\": \"rust\"}"}}]
+    }}],
+    "usage": {"prompt_tokens": 12, "completion_tokens": 5}
+}"#;
+
+#[test]
+fn test_init_chat_model_infers_provider() {
+    let llm = init_chat_model("ollama:llama3.2:8b").unwrap();
+    assert_eq!(llm.provider_name(), "ollama");
+    assert_eq!(llm.model_name(), "llama3.2:8b");
+}
+
+#[test]
+fn test_init_chat_model_rejects_bad_specs() {
+    for spec in ["llama3.2", "ollama:", "mistral:large"] {
+        let err = init_chat_model(spec).err().unwrap();
+        assert!(matches!(err, ModelError::ConfigError(_)), "{spec}: {err}");
+        assert_eq!(err.kind(), "config");
+    }
+}
+
+#[test]
+fn test_try_build_rejects_invalid_settings() {
+    use agentic_optio_rs::{OllamaChat, OllamaEmbedding};
+
+    let ollama = || OllamaChat::builder("llama3.2").host("http://gpu:11434");
+    assert!(ollama().try_build().is_ok());
+    for builder in [
+        ollama().host("gpu:11434"),
+        ollama().host("ftp://gpu"),
+        ollama().temperature(2.5),
+        ollama().temperature(f32::NAN),
+    ] {
+        let err = builder.try_build().unwrap_err();
+        assert!(matches!(err, ModelError::ConfigError(_)), "{err}");
+    }
+    let err = ollama()
+        .http_options(|http| http.proxy("not a proxy"))
+        .try_build()
+        .unwrap_err();
+    assert!(matches!(err, ModelError::HttpError(_)), "{err}");
+
+    let err = OpenAIChat::builder("gpt-4o-mini")
+        .base_url("/v1")
+        .try_build()
+        .unwrap_err();
+    assert!(matches!(err, ModelError::ConfigError(_)), "{err}");
+
+    let embedder = || OllamaEmbedding::builder("nomic-embed-text").host("http://gpu:11434");
+    assert!(embedder().try_build().is_ok());
+    assert!(embedder().batch_size(0).try_build().is_err());
+    assert!(embedder().dimensions(0).try_build().is_err());
+}
+
+#[test]
+fn test_model_error_classifies_provider_errors() {
+    use std::time::Duration;
+
+    let openai = |code: &str, message: &str| {
+        serde_json::json!({"error": {"message": message, "type": "invalid_request_error", "code": code}})
+            .to_string()
+    };
+    let err = ModelError::from_http_status(429, "{}", Some(Duration::from_secs(3)));
+    assert!(
+        matches!(err, ModelError::RateLimited { retry_after: Some(d), .. } if d.as_secs() == 3)
+    );
+    let err =
+        ModelError::from_http_status(401, &openai("invalid_api_key", "Incorrect API key"), None);
+    assert!(matches!(err, ModelError::AuthFailed(m) if m == "Incorrect API key"));
+    let err = ModelError::from_http_status(
+        400,
+        &openai(
+            "context_length_exceeded",
+            "This model's maximum context length is 8192 tokens",
+        ),
+        None,
+    );
+    assert!(matches!(err, ModelError::ContextLengthExceeded(_)));
+    let err = ModelError::from_http_status(404, r#"{"error": "model 'llama9' not found"}"#, None);
+    assert!(matches!(err, ModelError::ModelNotFound(m) if m == "model 'llama9' not found"));
+    let err = ModelError::from_http_status(400, &openai("content_filter", "Blocked"), None);
+    assert!(matches!(err, ModelError::ContentFiltered(_)));
+    let err = ModelError::from_http_status(504, "gateway timeout", None);
+    assert_eq!(err.kind(), "timeout");
+    let err = ModelError::from_http_status(500, "boom", None);
+    assert!(matches!(err, ModelError::Server { status: 500, message } if message == "boom"));
+    let err = ModelError::from_http_status(418, "teapot", None);
+    assert!(matches!(err, ModelError::ApiError(m) if m == "HTTP 418: teapot"));
+}
+
+#[tokio::test]
+async fn test_provider_errors_carry_status_details() {
+    let (url, _server) = capture_request_with(
+        "429 Too Many Requests",
+        "retry-after: 7\r\n",
+        r#"{"error": {"message": "Slow down", "type": "rate_limit_error"}}"#,
+    )
+    .await;
+    let llm = OpenAIChat::builder("gpt-4o-mini")
+        .base_url(format!("{url}/v1"))
+        .api_key("sk-test")
+        .max_retries(0)
+        .build();
+    let err = llm.invoke(&[Message::user("hi")]).await.unwrap_err();
+    assert!(
+        matches!(&err, ModelError::RateLimited { message, retry_after: Some(d) }
+            if message == "Slow down" && d.as_secs() == 7),
+        "{err}"
+    );
+
+    let (url, _server) = capture_request_with(
+        "404 Not Found",
+        "",
+        r#"{"error": {"message": "model \"llama9\" not found, try pulling it first"}}"#,
+    )
+    .await;
+    let llm = agentic_optio_rs::OllamaChat::builder("llama9")
+        .host(url)
+        .max_retries(0)
+        .build();
+    let err = llm.stream(&[Message::user("hi")]).await.err().unwrap();
+    assert!(matches!(err, ModelError::ModelNotFound(_)), "{err}");
+
+    let (url, _server) = capture_request(
+        r#"{"choices": [{"message": {"content": null}, "finish_reason": "content_filter"}]}"#,
+    )
+    .await;
+    let llm = OpenAIChat::builder("gpt-4o-mini")
+        .base_url(format!("{url}/v1"))
+        .api_key("sk-test")
+        .build();
+    let err = llm.invoke(&[Message::user("hi")]).await.unwrap_err();
+    assert_eq!(err.kind(), "content_filtered");
+}
+
+#[tokio::test]
+async fn test_retries_server_errors() {
+    let (url, server) = capture_requests(vec![
+        (
+            "502 Bad Gateway",
+            "",
+            r#"{"error": {"message": "upstream down"}}"#,
+        ),
+        ("200 OK", "", COMPLETION),
+    ])
+    .await;
+    let llm = OpenAIChat::builder("gpt-4o-mini")
+        .base_url(format!("{url}/v1"))
+        .api_key("sk-test")
+        .build();
+    llm.invoke(&[Message::user("Find rust")]).await.unwrap();
+    assert_eq!(server.await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_retries_honor_retry_after_and_track_rate_limits() {
+    let (url, server) = capture_requests(vec![
+        (
+            "429 Too Many Requests",
+            "retry-after-ms: 300\r\nx-ratelimit-remaining-requests: 0\r\n",
+            r#"{"error": {"message": "Slow down", "type": "rate_limit_error"}}"#,
+        ),
+        (
+            "200 OK",
+            "x-ratelimit-limit-requests: 60\r\nx-ratelimit-remaining-requests: 59\r\n\
+             x-ratelimit-reset-requests: 1s\r\nx-ratelimit-limit-tokens: 150000\r\n\
+             x-ratelimit-remaining-tokens: 149000\r\nx-ratelimit-reset-tokens: 6m0.5s\r\n",
+            COMPLETION,
+        ),
+    ])
+    .await;
+    let llm = OpenAIChat::builder("gpt-4o-mini")
+        .base_url(format!("{url}/v1"))
+        .api_key("sk-test")
+        .build();
+    assert_eq!(llm.rate_limits(), None);
+
+    let started = std::time::Instant::now();
+    let response = llm.invoke(&[Message::user("Find rust")]).await.unwrap();
+    assert!(started.elapsed() >= std::time::Duration::from_millis(300));
+    assert_eq!(response.tool_calls[0].name, "search");
+    assert_eq!(server.await.unwrap().len(), 2);
+
+    let limits = llm.rate_limits().unwrap();
+    assert_eq!(limits.remaining_requests, Some(59));
+    assert_eq!(limits.limit_tokens, Some(150_000));
+    assert_eq!(
+        limits.reset_tokens,
+        Some(std::time::Duration::from_millis(360_500))
+    );
+    assert!(!limits.is_exhausted());
+
+    // Waits longer than a minute are not slept through
+    let (url, _server) = capture_request_with(
+        "429 Too Many Requests",
+        "x-ratelimit-remaining-tokens: 0\r\nx-ratelimit-reset-tokens: 2m\r\n",
+        r#"{"error": {"message": "Token quota used up"}}"#,
+    )
+    .await;
+    let llm = agentic_optio_rs::OllamaChat::builder("llama3.2")
+        .host(url)
+        .build();
+    let err = llm.invoke(&[Message::user("hi")]).await.unwrap_err();
+    assert!(
+        matches!(err, ModelError::RateLimited { retry_after: Some(d), .. } if d.as_secs() == 120),
+        "{err}"
+    );
+    assert!(llm.rate_limits().unwrap().is_exhausted());
+}
+
+#[tokio::test]
+async fn test_openai_chat_sends_completion_request() {
+    let (url, server) = capture_request(COMPLETION).await;
+    let llm = OpenAIChat::builder("gpt-4o-mini")
+        .base_url(format!("{url}/v1"))
+        .api_key("sk-test")
+        .max_tokens(64)
+        .build();
+    assert_eq!(llm.provider_name(), "openai");
+    assert!(!format!("{llm:?}").contains("sk-test"));
+
+    let tools = [serde_json::json!({"type": "function", "function": {"name": "search"}})];
+    let response = llm
+        .invoke_with_tools(&[Message::user("Find rust")], &tools)
+        .await
+        .unwrap();
+    assert_eq!(response.tool_calls[0].name, "search");
+    assert_eq!(response.tool_calls[0].args["query"], "rust");
+    assert_eq!(response.usage.unwrap().input_tokens, 12);
+
+    let (head, body) = server.await.unwrap();
+    assert!(head.starts_with("POST /v1/chat/completions"));
+    assert!(head
+        .to_lowercase()
+        .contains("authorization: bearer sk-test"));
+    assert_eq!(body["model"], "gpt-4o-mini");
+    assert_eq!(body["max_tokens"], 64);
+    assert_eq!(body["messages"][0]["content"], "Find rust");
+    assert_eq!(body["tools"][0]["function"]["name"], "search");
+}
+
+#[tokio::test]
+async fn test_chat_model_handle_streams_from_spawned_task() {
+    use agentic_optio_rs::testing::MockChatModel;
+
+    let scripted = Arc::new(MockChatModel::with_responses(["one two three", "done"]));
+    let llm = ChatModel::from(scripted.clone() as Arc<dyn BaseChatModel>);
+    let copy = llm.clone();
+
+    let stream = llm.stream(vec![Message::user("Count")]);
+    let chunks = tokio::spawn(async move {
+        stream
+            .map(|chunk| chunk.unwrap().content)
+            .collect::<Vec<_>>()
+            .await
+    })
+    .await
+    .unwrap();
+    assert_eq!(chunks, ["one ", "two ", "three"]);
+    assert_eq!(
+        copy.invoke(&[Message::user("Again")])
+            .await
+            .unwrap()
+            .content,
+        "done"
+    );
+    assert_eq!(scripted.received().len(), 2);
+
+    // A stream that fails to start yields the error
+    let mut failed = copy.stream(vec![Message::user("More")]);
+    assert!(failed.next().await.unwrap().is_err());
+    assert!(failed.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_model_registry_resolves_names_at_call_time() {
+    use agentic_optio_rs::models::ModelRegistry;
+    use agentic_optio_rs::testing::MockChatModel;
+
+    let registry = ModelRegistry::new();
+    registry.register_chat(
+        "smart",
+        Arc::new(MockChatModel::with_responses(["first"])) as Arc<dyn BaseChatModel>,
+    );
+    let smart = registry.named("smart");
+    assert_eq!(smart.model_name(), "smart");
+    assert_eq!(
+        smart.invoke(&[Message::user("Hi")]).await.unwrap().content,
+        "first"
+    );
+
+    // Swapping the backend redirects existing handles
+    registry.register_chat(
+        "smart",
+        Arc::new(MockChatModel::with_responses(["second"])) as Arc<dyn BaseChatModel>,
+    );
+    assert_eq!(
+        smart.invoke(&[Message::user("Hi")]).await.unwrap().content,
+        "second"
+    );
+
+    let err = registry.named("missing").invoke(&[]).await.unwrap_err();
+    assert_eq!(err.kind(), "config");
+}
+
+#[tokio::test]
+async fn test_model_registry_scopes_shadow_parent() {
+    use agentic_optio_rs::models::ModelRegistry;
+
+    let parent = ModelRegistry::new();
+    parent.register("fast", agentic_optio_rs::OllamaChat::new("llama3.2"));
+    parent.register("smart", agentic_optio_rs::OllamaChat::new("qwen2.5:32b"));
+    parent.register_embedding("embedder", common::LetterEmbedding);
+
+    let tenant = parent.scope();
+    tenant.register("smart", agentic_optio_rs::OllamaChat::new("mistral"));
+    assert_eq!(tenant.chat("smart").unwrap().model_name(), "mistral");
+    assert_eq!(tenant.chat("fast").unwrap().model_name(), "llama3.2");
+    assert_eq!(parent.chat("smart").unwrap().model_name(), "qwen2.5:32b");
+    assert_eq!(tenant.chat_names(), ["fast", "smart"]);
+    assert!(tenant.embedding("embedder").is_some());
+    assert!(tenant.chat("missing").is_none());
+}
+
+#[tokio::test]
+async fn test_builders_accept_a_shared_client() {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-gateway", "team-a".parse().unwrap());
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    let (url, server) = capture_request(COMPLETION).await;
+    let llm = agentic_optio_rs::OllamaChat::builder("llama3.2")
+        .host(url)
+        .http_options(|http| http.client(client.clone()))
+        .build();
+    llm.invoke(&[Message::user("Hi")]).await.unwrap();
+    let (head, _) = server.await.unwrap();
+    assert!(head.contains("x-gateway: team-a"));
+}
+
+#[tokio::test]
+async fn test_proxy_and_no_proxy() {
+    // Requests to the unresolvable host go through the proxy
+    let (proxy, server) = capture_request(COMPLETION).await;
+    let llm = OpenAIChat::builder("gpt-4o-mini")
+        .base_url("http://api.optio.invalid/v1")
+        .api_key("sk-test")
+        .http_options(|http| http.proxy(proxy))
+        .build();
+    llm.invoke(&[Message::user("Hi")]).await.unwrap();
+    let (head, _) = server.await.unwrap();
+    assert!(head.starts_with("POST http://api.optio.invalid/v1/chat/completions"));
+
+    // Excluded hosts bypass the (unreachable) proxy
+    let (url, server) = capture_request(COMPLETION).await;
+    let llm = agentic_optio_rs::OllamaChat::builder("llama3.2")
+        .host(url)
+        .http_options(|http| {
+            http.proxy("http://127.0.0.1:9")
+                .no_proxy("localhost,127.0.0.1")
+        })
+        .build();
+    llm.invoke(&[Message::user("Hi")]).await.unwrap();
+    let (head, _) = server.await.unwrap();
+    assert!(head.starts_with("POST /v1/chat/completions"));
+}
+
+#[tokio::test]
+async fn test_pool_settings_reuse_connections() {
+    use std::time::Duration;
+
+    // The server accepts a single connection, so the second call only
+    // succeeds on the pooled one
+    let (url, server) = common::serve_one_connection(vec![COMPLETION, COMPLETION]).await;
+    let llm = OpenAIChat::builder("gpt-4o-mini")
+        .base_url(format!("{url}/v1"))
+        .api_key("sk-test")
+        .http_options(|http| {
+            http.pool_idle_timeout(Duration::from_secs(30))
+                .pool_max_idle_per_host(1)
+                .tcp_keepalive(Duration::from_secs(15))
+                .http2_keep_alive_interval(Duration::from_secs(15))
+        })
+        .max_retries(0)
+        .build();
+    llm.invoke(&[Message::user("Hi")]).await.unwrap();
+    llm.invoke(&[Message::user("Again")]).await.unwrap();
+    assert_eq!(server.await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_ollama_sends_custom_headers_and_bearer_token() {
+    use agentic_optio_rs::{BaseEmbedding, OllamaChat, OllamaEmbedding};
+
+    let (url, server) = capture_request(COMPLETION).await;
+    let llm = OllamaChat::builder("llama3.2")
+        .host(url)
+        .header("X-Tenant", "blue")
+        .bearer_token("gateway-secret")
+        .build();
+    assert!(!format!("{llm:?}").contains("gateway-secret"));
+    llm.invoke(&[Message::user("Hi")]).await.unwrap();
+    let (head, _) = server.await.unwrap();
+    let head = head.to_lowercase();
+    assert!(head.contains("x-tenant: blue"));
+    assert!(head.contains("authorization: bearer gateway-secret"));
+
+    let (url, server) =
+        capture_request(r#"{"data": [{"embedding": [0.5, 0.5], "index": 0}]}"#).await;
+    let embedder = OllamaEmbedding::builder("nomic-embed-text")
+        .host(url)
+        .bearer_token("old")
+        .header("authorization", "Bearer embed-secret")
+        .build();
+    assert_eq!(embedder.embed_query("hi").await.unwrap(), [0.5, 0.5]);
+    let (head, _) = server.await.unwrap();
+    let head = head.to_lowercase();
+    assert!(head.contains("authorization: bearer embed-secret"));
+    assert!(!head.contains("bearer old"));
+}
+
+#[tokio::test]
+async fn test_large_responses_are_parsed_incrementally() {
+    use agentic_optio_rs::{BaseEmbedding, OllamaEmbedding};
+
+    // Well over the size parsed in one piece
+    let texts: Vec<String> = (0..64).map(|i| format!("text {i}")).collect();
+    let data: Vec<_> = (0..64)
+        .rev()
+        .map(|i| serde_json::json!({"embedding": vec![i as f32 / 64.0; 768], "index": i}))
+        .collect();
+    let body = serde_json::json!({ "data": data }).to_string();
+    assert!(body.len() > 256 * 1024);
+    let (url, server) = capture_request(Box::leak(body.into_boxed_str())).await;
+    let embedder = OllamaEmbedding::builder("nomic-embed-text")
+        .host(url)
+        .batch_size(64)
+        .build();
+    let embeddings = embedder.embed(&texts).await.unwrap();
+    server.await.unwrap();
+    assert_eq!(embeddings.len(), 64);
+    assert_eq!(embeddings[32], vec![0.5; 768]);
+
+    // A truncated body is a JSON error, not a hang
+    let body = format!("{{\"data\": [{}", "0.125, ".repeat(60_000));
+    let (url, server) = capture_request(Box::leak(body.into_boxed_str())).await;
+    let embedder = OllamaEmbedding::builder("nomic-embed-text")
+        .host(url)
+        .max_retries(0)
+        .build();
+    let error = embedder.embed_query("hi").await.unwrap_err();
+    server.await.unwrap();
+    assert_eq!(error.kind(), "json");
+}
+
+/// Self-signed CA certificate, standing in for an internal PKI root
+#[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+const INTERNAL_CA: &str = "\
+-----BEGIN CERTIFICATE-----\n\
+MIIDEzCCAfugAwIBAgIUZED90MwQ2xLjs0ZG2ekycZ6y13wwDQYJKoZIhvcNAQEL\n\
+BQAwGDEWMBQGA1UEAwwNb3B0aW8tdGVzdC1jYTAgFw0yNjEwMTYyMDQzNTlaGA8y\n\
+MTI2MDkyMjIwNDM1OVowGDEWMBQGA1UEAwwNb3B0aW8tdGVzdC1jYTCCASIwDQYJ\n\
+KoZIhvcNAQEBBQADggEPADCCAQoCggEBAK7BkqQJlipLND2YIU9B4imWsRoU8P2R\n\
+zBfF2VY6+IgC6b1asAfgFVOSo5gZ8hmo7L6mhuVmytBgYYTovkMMrHF3otmRRrap\n\
+3rhvE4Y9Z0d6HBHzhy6pZgY93uU3ChzlcSevjVFjmaQPvoSTwYfwfpZXbS7KFk0g\n\
+CFBS2RZKFQQzUQsB9e4T99iWqgjo4wdjTjSdTrBy8GRXC5/cfQZFU7aLVQqRNfwt\n\
+eJXJ/gHjssHQAaqcPZex8Vo/ZssRLnXMYA3cB5IRiFVyaLsgHnghBQuJs/RdZGfe\n\
+OR2Tr+/FHl8twjgCoE2aLRF8HMSyqj4gaWUzR7kF2HC/keztT4AcZnkCAwEAAaNT\n\
+MFEwHQYDVR0OBBYEFAcZgp63IUBsxzRdGlaBl5AjTKpjMB8GA1UdIwQYMBaAFAcZ\n\
+gp63IUBsxzRdGlaBl5AjTKpjMA8GA1UdEwEB/wQFMAMBAf8wDQYJKoZIhvcNAQEL\n\
+BQADggEBAHXqlxj51eI00r8h9F4IFotV0m5YQ5eDTWxYZW9/k/4kGp0ANXYcH1jw\n\
+M142qBYcNm3KXx8m3GB/C0tJ8WY0LYTrgH9ch3xHu0EOtXDJO9Bgic1VTNToydZe\n\
+Og/KxfCVNdYODl+rSCrHyh5R/qoUHwpzi6xtqiYK7DBu8cVr5H4fGbHA35vOiW2l\n\
+wro/YpPAB/qgl8Lxci4mokrSke6y2BCBWmhvcxhGjhoXNN/WRHEtFs79Wv9RrRg6\n\
+qKlh6ZYEQbaIkA8iQ3H8YvXUMOrRP5hXA5MmNdT2REBvseprRj5XHQ1yikiH8t38\n\
+cxNXu9puTp3n9/FwESvoGzVpSNjlQ5c=\n\
+-----END CERTIFICATE-----";
+
+#[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+#[tokio::test]
+async fn test_tls_options_build_a_dedicated_client() {
+    let certificates = reqwest::Certificate::from_pem_bundle(INTERNAL_CA.as_bytes()).unwrap();
+    assert_eq!(certificates.len(), 1);
+
+    let (url, server) = capture_request(COMPLETION).await;
+    let llm = OpenAIChat::builder("gpt-4o-mini")
+        .base_url(url)
+        .api_key("sk-test")
+        .http_options(|http| {
+            http.root_certificate(certificates[0].clone())
+                .danger_accept_invalid_certs(true)
+        })
+        .build();
+    llm.invoke(&[Message::user("Hi")]).await.unwrap();
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_resuming_model_continues_interrupted_streams() {
+    use agentic_optio_rs::models::ResumingChatModel;
+    use agentic_optio_rs::testing::MockChatModel;
+    use std::time::{Duration, Instant};
+
+    let dropped = || ModelError::Timeout("connection dropped".to_string());
+    let mock = Arc::new(
+        MockChatModel::new()
+            .interrupt("Once upon ", dropped())
+            .respond("a time."),
+    );
+    let llm = ResumingChatModel::new(mock.clone());
+    let chunks: Vec<_> = llm
+        .stream(&[Message::user("Tell a story")])
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    let content: String = chunks
+        .iter()
+        .map(|c| c.as_ref().unwrap().content.clone())
+        .collect();
+    assert_eq!(content, "Once upon a time.");
+    let resumed = &mock.received()[1];
+    assert_eq!(resumed.len(), 3);
+    assert_eq!(resumed[1].role(), "assistant");
+    assert_eq!(resumed[1].content(), "Once upon ");
+
+    // Out of resumes, the partial content is kept with the error
+    let mock = Arc::new(
+        MockChatModel::new()
+            .interrupt("Once ", dropped())
+            .interrupt("upon ", dropped()),
+    );
+    let llm = ResumingChatModel::new(mock).prefill_only();
+    let chunks: Vec<_> = llm
+        .stream(&[Message::user("Tell a story")])
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 3);
+    assert!(
+        matches!(chunks.last().unwrap(), Err(ModelError::StreamInterrupted { partial, source })
+            if partial == "Once upon " && source.kind() == "timeout")
+    );
+
+    // Errors a retry cannot fix are not resumed
+    let mock = Arc::new(
+        MockChatModel::new().interrupt("Once ", ModelError::ContentFiltered("policy".to_string())),
+    );
+    let llm = ResumingChatModel::new(mock.clone());
+    let messages = [Message::user("Tell a story")];
+    let mut stream = llm.stream(&messages).await.unwrap();
+    stream.next().await.unwrap().unwrap();
+    assert_eq!(
+        stream.next().await.unwrap().unwrap_err().kind(),
+        "stream_interrupted"
+    );
+    assert!(stream.next().await.is_none());
+    assert_eq!(mock.calls(), 1);
+
+    let mock = Arc::new(
+        MockChatModel::new().interrupt("Once ", ModelError::ApiError("HTTP 400: bad".to_string())),
+    );
+    let llm = ResumingChatModel::new(mock.clone());
+    let chunks: Vec<_> = llm.stream(&messages).await.unwrap().collect().await;
+    assert!(matches!(
+        chunks.last().unwrap(),
+        Err(ModelError::StreamInterrupted { source, .. }) if source.kind() == "api"
+    ));
+    assert_eq!(mock.calls(), 1);
+
+    // Rate limits are resumed once the provider's wait is over
+    let limited = ModelError::RateLimited {
+        message: "slow down".to_string(),
+        retry_after: Some(Duration::from_millis(200)),
+    };
+    let mock = Arc::new(
+        MockChatModel::new()
+            .interrupt("Once ", limited)
+            .respond("upon a time."),
+    );
+    let llm = ResumingChatModel::new(mock.clone());
+    let started = Instant::now();
+    let chunks: Vec<_> = llm.stream(&messages).await.unwrap().collect().await;
+    assert!(chunks.iter().all(Result::is_ok));
+    assert!(started.elapsed() >= Duration::from_millis(200));
+    assert_eq!(mock.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_trimming_model_retries_context_length_errors() {
+    use agentic_optio_rs::models::{TrimStrategy, TrimmingChatModel};
+    use agentic_optio_rs::testing::MockChatModel;
+
+    let too_long = || ModelError::ContextLengthExceeded("too many tokens".to_string());
+    let messages = [
+        Message::system("Be brief"),
+        Message::user("First"),
+        Message::assistant("One"),
+        Message::user("Second"),
+        Message::assistant("Two"),
+        Message::user("Third"),
+    ];
+
+    let mock = Arc::new(MockChatModel::new().fail(too_long()).respond("Three"));
+    let llm = TrimmingChatModel::new(mock.clone());
+    assert_eq!(llm.invoke(&messages).await.unwrap().content, "Three");
+    let retried = &mock.received()[1];
+    let contents: Vec<_> = retried.iter().map(|m| m.content()).collect();
+    assert_eq!(contents, ["Be brief", "Second", "Two", "Third"]);
+
+    // Summarized turns are replaced by a system message
+    let summarizer = Arc::new(MockChatModel::new().respond("The user counted to one."));
+    let mock = Arc::new(MockChatModel::new().fail(too_long()).respond("Three"));
+    let llm =
+        TrimmingChatModel::new(mock.clone()).strategy(TrimStrategy::Summarize(summarizer.clone()));
+    let chunks: Vec<_> = llm.stream(&messages).await.unwrap().collect().await;
+    assert_eq!(chunks[0].as_ref().unwrap().content, "Three");
+    assert!(summarizer.received()[0][1]
+        .content()
+        .contains("user: First"));
+    let retried = &mock.received()[1];
+    assert_eq!(retried.len(), 5);
+    assert_eq!(retried[1].role(), "system");
+    assert!(retried[1].content().ends_with("The user counted to one."));
+
+    // A lone turn cannot be shortened
+    let mock = Arc::new(MockChatModel::new().fail(too_long()));
+    let llm = TrimmingChatModel::new(mock.clone());
+    let error = llm.invoke(&messages[..2]).await.unwrap_err();
+    assert_eq!(error.kind(), "context_length_exceeded");
+    assert_eq!(mock.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_buffered_streams_read_ahead_within_capacity() {
+    use agentic_optio_rs::models::StreamBufferExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    let pulled = Arc::new(AtomicUsize::new(0));
+    let counter = pulled.clone();
+    let source = futures::stream::repeat_with(move || counter.fetch_add(1, Ordering::SeqCst));
+
+    let mut stream = source.buffer(4);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    // Four waiting, one being handed over
+    assert_eq!(pulled.load(Ordering::SeqCst), 5);
+    assert_eq!(stream.next().await, Some(0));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pulled.load(Ordering::SeqCst), 6);
+
+    // Dropping the stream drops the source
+    drop(stream);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(Arc::strong_count(&pulled), 1);
+}
+
+#[tokio::test]
+async fn test_map_invoke_keeps_order_and_collects_errors() {
+    use agentic_optio_rs::models::base::{BoxStream, ModelResult};
+    use agentic_optio_rs::models::map_invoke;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Echoes the prompt, later items answering sooner
+    #[derive(Default)]
+    struct Echo {
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl BaseChatModel for Echo {
+        async fn invoke(&self, messages: &[Message]) -> ModelResult<AIMessage> {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            let n: u64 = messages[0].content().parse().unwrap_or(0);
+            tokio::time::sleep(Duration::from_millis(50 - n * 5)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            match messages[0].content() {
+                "3" => Err(ModelError::ApiError("bad item".to_string())),
+                content => Ok(AIMessage::new(content)),
+            }
+        }
+
+        async fn stream<'a>(
+            &'a self,
+            _messages: &'a [Message],
+        ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+            unimplemented!()
+        }
+    }
+
+    let model = Echo::default();
+    let results = map_invoke(&model, 0..8, |n| vec![Message::user(n.to_string())], 3).await;
+    assert_eq!(
+        results.contents(),
+        [
+            Some("0"),
+            Some("1"),
+            Some("2"),
+            None,
+            Some("4"),
+            Some("5"),
+            Some("6"),
+            Some("7")
+        ]
+    );
+    assert_eq!(results.succeeded(), 7);
+    let errors = results.errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, 3);
+    assert_eq!(model.peak.load(Ordering::SeqCst), 3);
+    assert!(results.into_result().is_err());
+}
+
+#[tokio::test]
+async fn test_hedged_model_returns_the_first_answer() {
+    use agentic_optio_rs::models::HedgedChatModel;
+    use agentic_optio_rs::testing::MockChatModel;
+    use std::time::{Duration, Instant};
+
+    let primary = Arc::new(
+        MockChatModel::new()
+            .latency(Duration::from_millis(500))
+            .respond("slow")
+            .respond("slow stream"),
+    );
+    let backup = Arc::new(MockChatModel::new().respond("fast").respond("fast stream"));
+    let llm = HedgedChatModel::new(primary.clone())
+        .backup(backup.clone())
+        .delay(Duration::from_millis(50));
+
+    let started = Instant::now();
+    let response = llm.invoke(&[Message::user("hi")]).await.unwrap();
+    assert_eq!(response.content, "fast");
+    assert!(started.elapsed() < Duration::from_millis(400));
+
+    let messages = [Message::user("hi")];
+    let chunks: Vec<_> = llm.stream(&messages).await.unwrap().collect().await;
+    let content: String = chunks
+        .iter()
+        .map(|c| c.as_ref().unwrap().content.clone())
+        .collect();
+    assert_eq!(content, "fast stream");
+    assert_eq!((primary.calls(), backup.calls()), (2, 2));
+
+    // A failure hedges at once, and only the last failure is reported
+    let primary = Arc::new(MockChatModel::new().fail(ModelError::Timeout("down".to_string())));
+    let backup = Arc::new(
+        MockChatModel::new()
+            .respond("backup")
+            .fail(ModelError::ApiError("also down".to_string())),
+    );
+    let llm = HedgedChatModel::new(primary)
+        .backup(backup)
+        .delay(Duration::from_secs(10));
+    let started = Instant::now();
+    assert_eq!(
+        llm.invoke(&[Message::user("hi")]).await.unwrap().content,
+        "backup"
+    );
+    assert!(started.elapsed() < Duration::from_secs(1));
+    let err = llm.invoke(&[Message::user("hi")]).await.unwrap_err();
+    assert_eq!(err.kind(), "api");
+}
+
+#[tokio::test]
+async fn test_ready_checks_the_model_is_served() {
+    let (url, server) =
+        capture_request(r#"{"models": [{"name": "llama3.2:latest"}, {"name": "qwen2.5:7b"}]}"#)
+            .await;
+    let llm = agentic_optio_rs::OllamaChat::builder("llama3.2")
+        .host(url)
+        .build();
+    llm.ready().await.unwrap();
+    assert!(server.await.unwrap().0.starts_with("GET /api/tags"));
+
+    let (url, _server) = capture_request(r#"{"data": [{"id": "gpt-4o"}]}"#).await;
+    let llm = OpenAIChat::builder("gpt-4o-mini")
+        .base_url(format!("{url}/v1"))
+        .api_key("sk-test")
+        .build();
+    let err = llm.ready().await.unwrap_err();
+    assert!(matches!(err, ModelError::ModelNotFound(_)), "{err}");
+}
+
+#[tokio::test]
+async fn test_health_monitor_reports_state_changes() {
+    use agentic_optio_rs::models::base::{BoxStream, ModelResult};
+    use agentic_optio_rs::models::{Health, HealthMonitor};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Model whose server is up while the flag is set
+    struct Flaky(AtomicBool);
+
+    #[async_trait]
+    impl BaseChatModel for Flaky {
+        async fn invoke(&self, _: &[Message]) -> ModelResult<AIMessage> {
+            Ok(AIMessage::new("hi"))
+        }
+
+        async fn stream<'a>(
+            &'a self,
+            _: &'a [Message],
+        ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+            Ok(Box::pin(futures::stream::empty()))
+        }
+
+        async fn ready(&self) -> ModelResult<()> {
+            match self.0.load(Ordering::SeqCst) {
+                true => Ok(()),
+                false => Err(ModelError::ApiError("connection refused".to_string())),
+            }
+        }
+    }
+
+    let model = Arc::new(Flaky(AtomicBool::new(false)));
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let recorded = changes.clone();
+    let monitor = HealthMonitor::new(model.clone())
+        .interval(Duration::from_millis(10))
+        .on_change(move |health| recorded.lock().unwrap().push(health.clone()))
+        .start();
+
+    let mut updates = monitor.subscribe();
+    updates
+        .wait_for(|h| matches!(h, Health::Unavailable(_)))
+        .await
+        .unwrap();
+    assert!(!monitor.is_ready());
+
+    model.0.store(true, Ordering::SeqCst);
+    tokio::time::timeout(Duration::from_secs(5), monitor.wait_ready())
+        .await
+        .unwrap();
+    assert_eq!(monitor.health(), Health::Ready);
+
+    // Unchanged probes do not call back again
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let changes = changes.lock().unwrap().clone();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(
+        changes[0],
+        Health::Unavailable("API error: connection refused".to_string())
+    );
+    monitor.stop();
+}
+
+#[tokio::test]
+async fn test_concurrency_limiter_adapts_to_overload() {
+    use agentic_optio_rs::models::{ConcurrencyLimiter, LimitedChatModel};
+    use agentic_optio_rs::testing::MockChatModel;
+    use std::time::{Duration, Instant};
+
+    let limiter = ConcurrencyLimiter::new().initial_limit(2).max_limit(2);
+    let mock = MockChatModel::with_responses(["ok"; 6]).latency(Duration::from_millis(50));
+    let llm = LimitedChatModel::new(Arc::new(mock), limiter.clone());
+    let messages = [Message::user("hi")];
+    let started = Instant::now();
+    let results = futures::future::join_all((0..6).map(|_| llm.invoke(&messages))).await;
+    assert!(results.iter().all(Result::is_ok));
+    assert!(started.elapsed() >= Duration::from_millis(150));
+    assert_eq!((limiter.limit(), limiter.in_flight()), (2, 0));
+
+    let limiter = ConcurrencyLimiter::new().initial_limit(8);
+    let mock = MockChatModel::new()
+        .fail(ModelError::RateLimited {
+            message: "busy".to_string(),
+            retry_after: None,
+        })
+        .fail(ModelError::AuthFailed("bad key".to_string()))
+        .respond("ok");
+    let llm = LimitedChatModel::new(Arc::new(mock), limiter.clone());
+    llm.invoke(&messages).await.unwrap_err();
+    assert_eq!(limiter.limit(), 4);
+    // Errors that are not overload leave the limit alone
+    llm.invoke(&messages).await.unwrap_err();
+    assert_eq!(limiter.limit(), 4);
+    llm.invoke(&messages).await.unwrap();
+    assert_eq!(limiter.limit(), 4);
+    assert!(format!("{limiter:?}").contains("limit: 4"));
+}
+
+#[tokio::test]
+async fn test_idle_timeout_ends_stalled_streams() {
+    use std::time::{Duration, Instant};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // Sends one chunk of a longer body, then stalls
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let _server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = socket.read(&mut buf).await.unwrap();
+        let chunk = "data: {\"choices\": [{\"delta\": {\"content\": \"Hello\"}}]}\n\n";
+        let head =
+            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncontent-length: 1000\r\n\r\n";
+        socket.write_all(head.as_bytes()).await.unwrap();
+        socket.write_all(chunk.as_bytes()).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(10)).await;
+    });
+
+    let llm = agentic_optio_rs::OllamaChat::builder("llama3.2")
+        .host(url)
+        .timeout(Duration::from_millis(50))
+        .idle_timeout(Duration::from_millis(300))
+        .max_retries(0)
+        .build();
+    let started = Instant::now();
+    let messages = [Message::user("hi")];
+    let mut stream = llm.stream(&messages).await.unwrap();
+    // The total timeout does not apply to streams
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(stream.next().await.unwrap().unwrap().content, "Hello");
+    let err = stream.next().await.unwrap().unwrap_err();
+    assert!(matches!(err, ModelError::Timeout(_)), "{err}");
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_router_picks_by_rule_then_classifier() {
+    use agentic_optio_rs::models::Router;
+    use agentic_optio_rs::testing::MockChatModel;
+
+    let chat = Arc::new(MockChatModel::new().name("llama3.2").respond("hi"));
+    let coder = Arc::new(
+        MockChatModel::new()
+            .respond("fn main() {}")
+            .respond("fixed"),
+    );
+    let long = Arc::new(MockChatModel::new().respond("summary"));
+    let classifier = Arc::new(
+        MockChatModel::new()
+            .respond(r#"{"label": "code", "confidence": 0.8}"#)
+            .fail(ModelError::Timeout("slow".into())),
+    );
+    let router = Router::new(chat.clone())
+        .route("code", "Writing or fixing code", coder.clone())
+        .route("long", "Long documents", long.clone())
+        .rule("code", |messages| {
+            messages.iter().any(|m| m.content().contains("```"))
+        })
+        .long_context("long", 100)
+        .classifier(classifier.clone());
+    assert_eq!(router.routes(), ["default", "code", "long"]);
+    assert_eq!(router.model_name(), "llama3.2");
+
+    let fenced = [Message::user("Why?\n```\nlet x = 1\n```")];
+    assert_eq!(
+        router.invoke(&fenced).await.unwrap().content,
+        "fn main() {}"
+    );
+    let document = [Message::user("word ".repeat(100))];
+    assert_eq!(router.invoke(&document).await.unwrap().content, "summary");
+    assert_eq!(classifier.calls(), 0);
+
+    // The classifier picks the route, and the default takes over when it fails
+    let question = [Message::user("Write a hello world in Rust")];
+    assert_eq!(router.invoke(&question).await.unwrap().content, "fixed");
+    assert!(classifier.received()[0][0]
+        .content()
+        .contains("- code: Writing or fixing code"));
+    assert_eq!(router.invoke(&question).await.unwrap().content, "hi");
+    assert_eq!(coder.calls(), 2);
+    assert_eq!(chat.calls(), 1);
+}
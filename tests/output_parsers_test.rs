@@ -0,0 +1,377 @@
+//! Output parser tests for agentic_optio_rs
+
+use agentic_optio_rs::output_parsers::{
+    JsonParser, ListParser, MarkdownTableParser, OutputParser, ParseError, ParsedChatModel,
+    RegexParser, XmlTagParser,
+};
+use agentic_optio_rs::testing::MockChatModel;
+use agentic_optio_rs::{BaseChatModel, ChatModel, Message};
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct City {
+    name: String,
+    population: u64,
+}
+
+#[test]
+fn test_json_parser_ignores_surrounding_text() {
+    let parser = JsonParser::<City>::new();
+    let city = City {
+        name: "Oslo".to_string(),
+        population: 709000,
+    };
+    assert_eq!(
+        parser
+            .parse(r#"{"name": "Oslo", "population": 709000}"#)
+            .unwrap(),
+        city
+    );
+    assert_eq!(
+        parser
+            .parse("Here you go:\n{\"name\": \"Oslo\", \"population\": 709000}\nAnything else?")
+            .unwrap(),
+        city
+    );
+    assert!(matches!(
+        parser.parse("Oslo, about 700k people"),
+        Err(ParseError::Invalid { output, .. }) if output == "Oslo, about 700k people"
+    ));
+    let numbers = JsonParser::<Vec<u32>>::new()
+        .parse("Primes: [2, 3, 5]")
+        .unwrap();
+    assert_eq!(numbers, [2, 3, 5]);
+}
+
+#[test]
+fn test_list_parsers() {
+    assert_eq!(
+        ListParser::comma().parse(" red, green ,,blue ").unwrap(),
+        ["red", "green", "blue"]
+    );
+    assert_eq!(
+        ListParser::newline()
+            .parse("- red\n* green\n\n1. blue\n2) cyan\nplain")
+            .unwrap(),
+        ["red", "green", "blue", "cyan", "plain"]
+    );
+}
+
+#[test]
+fn test_regex_and_xml_parsers() {
+    let parser =
+        RegexParser::new(r"(?P<label>\w+) \((?P<confidence>[\d.]+)\)(?P<note> !)?").unwrap();
+    let captures = parser.parse("Label: spam (0.93)").unwrap();
+    assert_eq!(captures["label"], "spam");
+    assert_eq!(captures["confidence"], "0.93");
+    assert!(!captures.contains_key("note"));
+    assert!(parser.parse("no idea").is_err());
+
+    let parser = XmlTagParser::new("answer");
+    assert_eq!(
+        parser
+            .parse("<thinking>2 + 2</thinking>\n<answer>\n4\n</answer>")
+            .unwrap(),
+        "4"
+    );
+    assert!(parser.parse("<answer>4").is_err());
+    assert!(parser.parse("4").is_err());
+}
+
+#[test]
+fn test_markdown_table_parser() {
+    let text = "Here are the results:\n\n\
+        | City | Country | Population |\n\
+        |:-----|---------|-----------:|\n\
+        | Oslo | Norway | 709000 |\n\
+        | Bergen | Norway |\n\
+        \n\
+        Let me know if you need more.";
+    let table = MarkdownTableParser::new().parse(text).unwrap();
+    assert_eq!(table.headers, ["City", "Country", "Population"]);
+    assert_eq!(table.rows.len(), 2);
+    assert_eq!(table.rows[1], ["Bergen", "Norway", ""]);
+    assert_eq!(table.column("City").unwrap(), ["Oslo", "Bergen"]);
+    assert!(table.column("Area").is_none());
+    assert!(MarkdownTableParser::new().parse("no table here").is_err());
+}
+
+#[tokio::test]
+async fn test_parsed_chat_model_adds_instructions_and_parses() {
+    let mock = Arc::new(
+        MockChatModel::new()
+            .respond("apples, pears")
+            .respond("nope"),
+    );
+    let llm = ParsedChatModel::new(mock.clone(), ListParser::comma());
+
+    let fruit = llm
+        .invoke_parsed(&[
+            Message::system("Be brief."),
+            Message::user("Name two fruits"),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(fruit, ["apples", "pears"]);
+    let sent = &mock.received()[0];
+    assert_eq!(sent.len(), 2);
+    assert!(sent[0].content().starts_with("Be brief.\n\n"));
+    assert!(sent[0].content().contains("comma-separated"));
+
+    let llm = ChatModel::from(mock.clone() as Arc<dyn BaseChatModel>)
+        .with_parser(XmlTagParser::new("answer"));
+    let error = llm.invoke_parsed(&[Message::user("Hi")]).await.unwrap_err();
+    assert!(matches!(error, ParseError::Invalid { output, .. } if output == "nope"));
+    assert_eq!(mock.received()[1][0].role(), "system");
+
+    // Model errors pass through
+    let error = llm.invoke_parsed(&[Message::user("Hi")]).await.unwrap_err();
+    assert!(matches!(error, ParseError::Model(_)));
+}
+
+#[test]
+fn test_parse_partial_json_completes_what_has_arrived() {
+    use agentic_optio_rs::output_parsers::parse_partial_json;
+    use serde_json::json;
+
+    let cases = [
+        ("Sure! ", None),
+        ("```json\n{", Some(json!({}))),
+        (r#"{"tit"#, Some(json!({}))),
+        (r#"{"title": "Ru"#, Some(json!({"title": "Ru"}))),
+        (r#"{"title": "Rust\"#, Some(json!({"title": "Rust"}))),
+        (r#"{"title": "Rust\u00e"#, Some(json!({"title": "Rust"}))),
+        (
+            r#"{"title": "Rusté", "n": 4"#,
+            Some(json!({"title": "Rusté"})),
+        ),
+        (
+            r#"{"title": "Ünï", "n": 42, "ok": tr"#,
+            Some(json!({"title": "Ünï", "n": 42})),
+        ),
+        (
+            r#"{"a": [1, {"b": [true, nul"#,
+            Some(json!({"a": [1, {"b": [true]}]})),
+        ),
+        (r#"[{"x": "}]\"{"}, "#, Some(json!([{"x": "}]\"{"}]))),
+        (r#"{"done": true} and more {"#, Some(json!({"done": true}))),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(parse_partial_json(text), expected, "{text}");
+    }
+}
+
+#[tokio::test]
+async fn test_stream_parsed_yields_growing_values() {
+    use futures::StreamExt;
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    #[serde(default)]
+    struct Outline {
+        title: String,
+        points: Vec<String>,
+    }
+
+    let mock = Arc::new(
+        MockChatModel::new()
+            .respond(r#"{"title": "Rust talk", "points": ["fast code", "safe code"]}"#)
+            .respond(r#"{"title": "cut off"#),
+    );
+    let llm = ParsedChatModel::new(mock, JsonParser::<Outline>::new().strict());
+    let values: Vec<_> = llm
+        .stream_parsed(&[Message::user("Outline a talk")])
+        .collect()
+        .await;
+    let values: Vec<Outline> = values.into_iter().map(Result::unwrap).collect();
+    assert_eq!(values[0], Outline::default());
+    assert!(values
+        .iter()
+        .any(|v| v.title == "Rust " && v.points.is_empty()));
+    assert!(values
+        .iter()
+        .any(|v| v.title == "Rust talk" && v.points == ["fast "]));
+    let last = values.last().unwrap();
+    assert_eq!(last.points, ["fast code", "safe code"]);
+
+    // A strict final parse reports a response that never completes
+    let values: Vec<_> = llm
+        .stream_parsed(&[Message::user("Outline a talk")])
+        .collect()
+        .await;
+    assert_eq!(values[values.len() - 2].as_ref().unwrap().title, "cut off");
+    assert!(values.last().unwrap().is_err());
+}
+
+#[test]
+fn test_repair_json_fixes_common_slips() {
+    use agentic_optio_rs::output_parsers::repair_json;
+    use serde_json::json;
+
+    let cases = [
+        ("{\"a\": 1,}", json!({"a": 1})),
+        ("[1, 2, 3, ]", json!([1, 2, 3])),
+        (
+            "{a: 1, b_c: 'two', \"d\": [True, False, None]}",
+            json!({"a": 1, "b_c": "two", "d": [true, false, null]}),
+        ),
+        ("{'it\\'s': 'say \"hi\"'}", json!({"it's": "say \"hi\""})),
+        (
+            "{\"a\": 1, // one\n /* two */ \"b\": 2e3}",
+            json!({"a": 1, "b": 2000.0}),
+        ),
+        (
+            "{\"text\": \"line one\nline two\"}",
+            json!({"text": "line one\nline two"}),
+        ),
+        (
+            "Here it is:\n```json\n{\"a\": [1, 2]}\n```\nAnything else?",
+            json!({"a": [1, 2]}),
+        ),
+        (
+            "{\"items\": [{\"name\": \"x\"}, {\"name\": \"y",
+            json!({"items": [{"name": "x"}, {"name": "y"}]}),
+        ),
+        (
+            "{\"status\": ok, \"count\": 3}",
+            json!({"status": "ok", "count": 3}),
+        ),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(repair_json(text), Some(expected), "{text}");
+    }
+    assert_eq!(repair_json("no JSON here"), None);
+}
+
+#[test]
+fn test_json_parser_repairs_unless_strict() {
+    let text = "```json\n{name: 'Oslo', population: 709000,}\n```";
+    let city = JsonParser::<City>::new().parse(text).unwrap();
+    assert_eq!(city.name, "Oslo");
+    assert!(JsonParser::<City>::new().strict().parse(text).is_err());
+    assert!(JsonParser::<City>::new()
+        .strict()
+        .parse(" {\"name\": \"Oslo\", \"population\": 709000}\n")
+        .is_ok());
+    // Strict mode does not extract JSON from prose either
+    assert!(JsonParser::<Vec<u32>>::new()
+        .strict()
+        .parse("Primes: [2, 3, 5]")
+        .is_err());
+}
+
+#[test]
+fn test_code_block_extraction() {
+    use agentic_optio_rs::output_parsers::{extract_code_blocks, CodeBlockParser};
+
+    let text = "Here's the query:\n\n\
+        ```SQL\nSELECT *\nFROM users;\n```\n\n\
+        And a script, which uses ``` in a string:\n\n\
+        ````python title=\"run.py\"\nprint(\"```\")\n````\n\n\
+        ~~~\nplain\n~~~\n\
+        Hope that helps!";
+    let blocks = extract_code_blocks(text);
+    assert_eq!(blocks.len(), 3);
+    assert!(blocks[0].is_language("sql"));
+    assert_eq!(blocks[0].code, "SELECT *\nFROM users;");
+    assert_eq!(blocks[1].language.as_deref(), Some("python"));
+    assert_eq!(blocks[1].code, "print(\"```\")");
+    assert_eq!(blocks[2].language, None);
+
+    assert_eq!(
+        CodeBlockParser::new()
+            .language("python")
+            .parse(text)
+            .unwrap(),
+        "print(\"```\")"
+    );
+    assert_eq!(
+        CodeBlockParser::new().parse(text).unwrap(),
+        "SELECT *\nFROM users;"
+    );
+    assert!(CodeBlockParser::new().language("rust").parse(text).is_err());
+    // Bare code and truncated blocks
+    assert_eq!(CodeBlockParser::new().parse("\nx = 1\n").unwrap(), "x = 1");
+    assert_eq!(
+        CodeBlockParser::new()
+            .parse("```js\nlet a = 1;\nlet b")
+            .unwrap(),
+        "let a = 1;\nlet b"
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Sentiment {
+    Positive,
+    Negative,
+    Neutral,
+}
+
+#[test]
+fn test_label_parser_matches_leniently() {
+    use agentic_optio_rs::output_parsers::LabelParser;
+
+    let parser = LabelParser::new([
+        ("positive", Sentiment::Positive),
+        ("negative", Sentiment::Negative),
+        ("very negative", Sentiment::Negative),
+    ]);
+    assert_eq!(parser.parse("Positive").unwrap(), Sentiment::Positive);
+    assert_eq!(
+        parser.parse(" **negative**.\n").unwrap(),
+        Sentiment::Negative
+    );
+    assert_eq!(
+        parser.parse("The review is positive overall.").unwrap(),
+        Sentiment::Positive
+    );
+    // Ambiguous and unknown answers
+    assert!(parser.parse("positive or negative").is_err());
+    assert!(parser.parse("nonpositive").is_err());
+    let error = parser.parse("mixed").unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("positive, negative, very negative"));
+
+    let parser = LabelParser::yes_no();
+    assert!(parser.parse("Yes.").unwrap());
+    assert!(!parser.parse("false").unwrap());
+    assert!(!parser.format_instructions().unwrap().contains("true"));
+}
+
+#[tokio::test]
+async fn test_classifier_retries_with_feedback() {
+    use agentic_optio_rs::output_parsers::Classifier;
+
+    let mock = Arc::new(
+        MockChatModel::new()
+            .respond("It's hard to say, somewhat mixed.")
+            .respond("neutral"),
+    );
+    let classifier = Classifier::new(
+        mock.clone(),
+        [
+            ("positive", Sentiment::Positive),
+            ("negative", Sentiment::Negative),
+            ("neutral", Sentiment::Neutral),
+        ],
+    )
+    .instructions("Classify the sentiment of the review.");
+    assert_eq!(
+        classifier.classify("It's fine I guess").await.unwrap(),
+        Sentiment::Neutral
+    );
+    let retried = &mock.received()[1];
+    assert_eq!(retried.len(), 4);
+    assert!(retried[0].content().starts_with("Classify the sentiment"));
+    assert!(retried[0].content().contains("positive, negative, neutral"));
+    assert_eq!(retried[2].role(), "assistant");
+    assert!(retried[3].content().contains("expected one of"));
+
+    // Out of retries, the last parse error is returned
+    let mock = Arc::new(MockChatModel::new().respond("maybe").respond("perhaps"));
+    let classifier = Classifier::yes_no(mock.clone(), "Is this spam?").max_retries(1);
+    let error = classifier.classify("WIN A PRIZE").await.unwrap_err();
+    assert!(matches!(error, ParseError::Invalid { output, .. } if output == "perhaps"));
+    assert_eq!(mock.calls(), 2);
+}
@@ -0,0 +1,480 @@
+//! Prompt template tests for agentic_optio_rs
+
+use agentic_optio_rs::prompts::{ChatPromptTemplate, PromptError, PromptTemplate, PromptValues};
+use agentic_optio_rs::Message;
+
+mod common;
+use std::collections::HashMap;
+
+#[test]
+fn test_prompt_template_substitutes_variables() {
+    let template = PromptTemplate::new("Hello {name}, welcome to {place}. Bye {name}!").unwrap();
+    assert_eq!(template.input_variables(), vec!["name", "place"]);
+
+    let values = HashMap::from([
+        ("name".to_string(), "Ada".to_string()),
+        ("place".to_string(), "Rome".to_string()),
+    ]);
+    assert_eq!(
+        template.format(&values).unwrap(),
+        "Hello Ada, welcome to Rome. Bye Ada!"
+    );
+    // Values that aren't variables are ignored
+    assert_eq!(
+        template
+            .format([("name", "Bo"), ("place", "Oslo"), ("extra", "x")])
+            .unwrap(),
+        "Hello Bo, welcome to Oslo. Bye Bo!"
+    );
+}
+
+#[test]
+fn test_prompt_template_missing_variable_is_an_error() {
+    let template = PromptTemplate::new("{greeting}, {name}").unwrap();
+    assert_eq!(
+        template.format([("greeting", "Hi")]),
+        Err(PromptError::MissingVariable("name".to_string()))
+    );
+}
+
+#[test]
+fn test_prompt_template_partials_and_escapes() {
+    let template = PromptTemplate::new("Return {{\"answer\": {count}}} as {format}")
+        .unwrap()
+        .partial("format", "JSON");
+    assert_eq!(template.input_variables(), vec!["count"]);
+    assert_eq!(
+        template.format([("count", 3)]).unwrap(),
+        "Return {\"answer\": 3} as JSON"
+    );
+    assert_eq!(
+        template
+            .format([("count", "4"), ("format", "YAML")])
+            .unwrap(),
+        "Return {\"answer\": 4} as YAML"
+    );
+}
+
+#[test]
+fn test_prompt_template_rejects_malformed_templates() {
+    assert!(matches!(
+        PromptTemplate::new("Hello {name"),
+        Err(PromptError::Parse(_))
+    ));
+    assert!(matches!(
+        PromptTemplate::new("Hello name}"),
+        Err(PromptError::Parse(_))
+    ));
+    assert!(matches!(
+        PromptTemplate::new("Hello {first name}"),
+        Err(PromptError::Parse(_))
+    ));
+    assert!(matches!(
+        PromptTemplate::new("Hello {}"),
+        Err(PromptError::Parse(_))
+    ));
+}
+
+#[test]
+fn test_chat_prompt_template_renders_messages() {
+    let prompt = ChatPromptTemplate::new()
+        .system("You answer in {language}.")
+        .unwrap()
+        .placeholder("history")
+        .user("{question}")
+        .unwrap()
+        .assistant("Answer ({language}):")
+        .unwrap()
+        .partial("language", "Latin");
+    assert_eq!(prompt.input_variables(), vec!["question"]);
+    assert_eq!(prompt.placeholders(), vec!["history"]);
+
+    let values = PromptValues::new().set("question", "Hello?").messages(
+        "history",
+        vec![Message::user("Hi"), Message::assistant("Salve")],
+    );
+    let messages = prompt.format_messages(&values).unwrap();
+    let rendered: Vec<(&str, &str)> = messages.iter().map(|m| (m.role(), m.content())).collect();
+    assert_eq!(
+        rendered,
+        vec![
+            ("system", "You answer in Latin."),
+            ("user", "Hi"),
+            ("assistant", "Salve"),
+            ("user", "Hello?"),
+            ("assistant", "Answer (Latin):"),
+        ]
+    );
+}
+
+#[test]
+fn test_chat_prompt_template_requires_placeholders_and_variables() {
+    let prompt = ChatPromptTemplate::new()
+        .placeholder("history")
+        .user("{question}")
+        .unwrap();
+
+    assert_eq!(
+        prompt
+            .format_messages(&PromptValues::new().set("question", "?"))
+            .unwrap_err(),
+        PromptError::MissingVariable("history".to_string())
+    );
+    assert_eq!(
+        prompt
+            .format_messages(&PromptValues::new().messages("history", Vec::new()))
+            .unwrap_err(),
+        PromptError::MissingVariable("question".to_string())
+    );
+    assert!(ChatPromptTemplate::new().system("{unclosed").is_err());
+}
+
+#[cfg(feature = "jinja")]
+#[test]
+fn test_jinja_template_conditionals_loops_and_filters() {
+    use agentic_optio_rs::prompts::JinjaTemplate;
+    use serde_json::json;
+
+    let template = JinjaTemplate::new(
+        "{% if tools %}Tools: {{ tools | join(', ') }}.{% else %}No tools.{% endif %} \
+         Hi {{ user.name | title }}",
+    )
+    .unwrap();
+    assert_eq!(template.input_variables(), vec!["tools", "user"]);
+    assert_eq!(
+        template
+            .render(json!({"tools": ["search", "calc"], "user": {"name": "ada lovelace"}}))
+            .unwrap(),
+        "Tools: search, calc. Hi Ada Lovelace"
+    );
+    assert_eq!(
+        template
+            .render(json!({"tools": [], "user": {"name": "bo"}}))
+            .unwrap(),
+        "No tools. Hi Bo"
+    );
+
+    assert!(matches!(
+        template.render(json!({"tools": []})),
+        Err(PromptError::Render(_))
+    ));
+    assert!(matches!(
+        JinjaTemplate::new("{% if x %}unterminated"),
+        Err(PromptError::Parse(_))
+    ));
+}
+
+/// Fresh directory under the system temp dir for one test's prompt files
+fn prompt_dir(test: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("optio-prompts-{}-{}", test, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("shared")).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_prompt_repository_loads_includes_and_env() {
+    use agentic_optio_rs::prompts::PromptRepository;
+    use agentic_optio_rs::testing::MockChatModel;
+    use agentic_optio_rs::tools::{FunctionTool, ToolRegistry};
+    use std::sync::Arc;
+
+    let dir = prompt_dir("json");
+    std::fs::write(
+        dir.join("shared/base.json"),
+        r#"{
+            "templates": {"greet": "Hello {name}", "summarize": "Old"},
+            "personas": {"reviewer": {"role": "Senior ${OPTIO_TEST_UNSET_LANG:-Rust} reviewer"}}
+        }"#,
+    )
+    .unwrap();
+    std::env::set_var("OPTIO_TEST_SUMMARY_STYLE", "bullet points");
+    std::fs::write(
+        dir.join("main.json"),
+        r#"{
+            "include": ["shared/base.json"],
+            "templates": {"summarize": "Summarize as ${OPTIO_TEST_SUMMARY_STYLE}: {text}"},
+            "agents": {
+                "review-bot": {"persona": "reviewer", "tools": ["lint"], "max_iterations": 3}
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let prompts = PromptRepository::load_dir(&dir).unwrap();
+    assert_eq!(prompts.template_names(), vec!["greet", "summarize"]);
+    assert_eq!(
+        prompts
+            .template("summarize")
+            .unwrap()
+            .format([("text", "notes")])
+            .unwrap(),
+        "Summarize as bullet points: notes"
+    );
+    let reviewer = prompts.persona("reviewer").unwrap();
+    assert_eq!(reviewer.name, "reviewer");
+    assert_eq!(reviewer.role, "Senior Rust reviewer");
+
+    let mut tools = ToolRegistry::new();
+    tools.register(FunctionTool::new("lint", "Run the linter", |_| async {
+        Ok("clean".to_string())
+    }));
+    tools.register(FunctionTool::new("deploy", "Ship it", |_| async {
+        Ok("shipped".to_string())
+    }));
+    let model = Arc::new(MockChatModel::with_responses(["LGTM"]));
+    let agent = prompts
+        .agent("review-bot", model.clone(), &tools)
+        .unwrap()
+        .build();
+    assert_eq!(agent.name(), "review-bot");
+    assert_eq!(agent.tools().len(), 1);
+
+    agent.run("Review this").await.unwrap();
+    assert_eq!(
+        model.received()[0][0].content(),
+        reviewer.system_prompt().as_str()
+    );
+    assert!(prompts.agent("missing", model.clone(), &tools).is_err());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_prompt_repository_reports_bad_files() {
+    use agentic_optio_rs::prompts::PromptRepository;
+
+    let dir = prompt_dir("errors");
+    std::fs::write(dir.join("a.json"), r#"{"include": ["b.json"]}"#).unwrap();
+    std::fs::write(dir.join("b.json"), r#"{"include": ["a.json"]}"#).unwrap();
+    assert!(matches!(
+        PromptRepository::new().load_file(dir.join("a.json")),
+        Err(PromptError::Load(message)) if message.contains("include cycle")
+    ));
+
+    std::fs::write(
+        dir.join("a.json"),
+        r#"{"templates": {"x": "${OPTIO_TEST_SURELY_UNSET}"}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        PromptRepository::new()
+            .load_file(dir.join("a.json"))
+            .unwrap_err(),
+        PromptError::MissingVariable("OPTIO_TEST_SURELY_UNSET".to_string())
+    );
+
+    std::fs::write(dir.join("a.json"), r#"{"tempaltes": {}}"#).unwrap();
+    assert!(PromptRepository::new()
+        .load_file(dir.join("a.json"))
+        .is_err());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[cfg(all(feature = "toml", feature = "yaml"))]
+#[test]
+fn test_prompt_repository_reads_toml_and_yaml() {
+    use agentic_optio_rs::prompts::PromptRepository;
+
+    let dir = prompt_dir("formats");
+    std::fs::write(
+        dir.join("shared/personas.yaml"),
+        "personas:\n  analyst:\n    role: Data analyst\n    goals:\n      - Cite numbers\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("agents.toml"),
+        "include = [\"shared/personas.yaml\"]\n\n\
+         [templates]\nreport = \"Report on {topic}\"\n\n\
+         [agents.analyst-bot]\npersona = \"analyst\"\nsystem_prompt = \"Be brief.\"\n",
+    )
+    .unwrap();
+
+    let prompts = PromptRepository::load_dir(&dir).unwrap();
+    assert_eq!(
+        prompts.persona("analyst").unwrap().goals,
+        vec!["Cite numbers"]
+    );
+    assert_eq!(prompts.agent_names(), vec!["analyst-bot"]);
+    let definition = prompts.agent_definition("analyst-bot").unwrap();
+    assert_eq!(definition.persona.as_deref(), Some("analyst"));
+    assert_eq!(definition.system_prompt.as_deref(), Some("Be brief."));
+    assert_eq!(
+        prompts
+            .template("report")
+            .unwrap()
+            .format([("topic", "sales")])
+            .unwrap(),
+        "Report on sales"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_prompt_registry_selects_versions() {
+    use agentic_optio_rs::prompts::{PromptRegistry, VersionSelection};
+
+    let mut prompts = PromptRegistry::new();
+    prompts.register("greet", "1.9.0", "Hi {name}").unwrap();
+    prompts.register("greet", "1.10.0", "Hello {name}").unwrap();
+    prompts.register("greet", "1.2.0", "Hey {name}").unwrap();
+    // Re-registering identical text is fine, changing it is not
+    prompts.register("greet", "1.2.0", "Hey {name}").unwrap();
+    assert!(matches!(
+        prompts.register("greet", "1.2.0", "Yo {name}"),
+        Err(PromptError::Version(_))
+    ));
+
+    assert_eq!(prompts.versions("greet").len(), 3);
+    assert_eq!(prompts.select("greet").unwrap().version, "1.10.0");
+    assert_eq!(
+        prompts
+            .select("greet")
+            .unwrap()
+            .format([("name", "Ada")])
+            .unwrap(),
+        "Hello Ada"
+    );
+
+    prompts.strategy("greet", VersionSelection::Pinned("1.9.0".to_string()));
+    let pinned = prompts.select("greet").unwrap();
+    assert_eq!(
+        pinned.tag(),
+        ("prompt.greet".to_string(), "1.9.0".to_string())
+    );
+    prompts.strategy("greet", VersionSelection::Pinned("2.0.0".to_string()));
+    assert!(prompts.select("greet").is_err());
+    assert!(prompts.select("unknown").is_err());
+
+    let hash = prompts.register_hashed("farewell", "Bye {name}").unwrap();
+    assert_eq!(hash.len(), 12);
+    assert_eq!(prompts.latest("farewell").unwrap().version, hash);
+    assert_eq!(prompts.get("farewell", &hash).unwrap().hash, hash);
+}
+
+#[test]
+fn test_prompt_registry_weighted_selection() {
+    use agentic_optio_rs::prompts::{PromptRegistry, VersionSelection};
+
+    let mut prompts = PromptRegistry::new();
+    prompts.register("ask", "1.0.0", "A").unwrap();
+    prompts.register("ask", "2.0.0", "B").unwrap();
+    prompts.strategy(
+        "ask",
+        VersionSelection::Weighted(vec![("1.0.0".to_string(), 1.0), ("2.0.0".to_string(), 3.0)]),
+    );
+
+    let draws: Vec<String> = (0..2000)
+        .map(|i| {
+            prompts
+                .select_for("ask", &format!("user-{}", i))
+                .unwrap()
+                .version
+                .clone()
+        })
+        .collect();
+    let old = draws.iter().filter(|v| *v == "1.0.0").count();
+    assert!((350..650).contains(&old), "old version drawn {} times", old);
+    // Keys stick to their version
+    assert_eq!(
+        prompts.select_for("ask", "user-7").unwrap().version,
+        draws[7]
+    );
+
+    prompts.strategy(
+        "ask",
+        VersionSelection::Weighted(vec![("2.0.0".to_string(), 0.0), ("1.0.0".to_string(), 1.0)]),
+    );
+    for _ in 0..20 {
+        assert_eq!(prompts.select("ask").unwrap().version, "1.0.0");
+    }
+}
+
+#[tokio::test]
+async fn test_prompt_version_recorded_in_run_metadata() {
+    use agentic_optio_rs::agents::Agent;
+    use agentic_optio_rs::callbacks::CallbackHandler;
+    use agentic_optio_rs::core::RunContext;
+    use agentic_optio_rs::prompts::PromptRegistry;
+    use agentic_optio_rs::testing::MockChatModel;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct LlmMetadata(Mutex<Vec<Option<String>>>);
+
+    impl CallbackHandler for LlmMetadata {
+        fn on_llm_start(&self, _model: &str, _messages: &[Message]) {
+            let version =
+                RunContext::current().and_then(|c| c.metadata.get("prompt.system").cloned());
+            self.0.lock().unwrap().push(version);
+        }
+    }
+
+    let mut prompts = PromptRegistry::new();
+    prompts
+        .register("system", "3.1.0", "You are {persona}.")
+        .unwrap();
+    let prompt = prompts.select("system").unwrap();
+    let (key, version) = prompt.tag();
+
+    let seen = Arc::new(LlmMetadata::default());
+    let agent = Agent::builder(Arc::new(MockChatModel::with_responses(["ok"])))
+        .system_prompt(prompt.format([("persona", "terse")]).unwrap())
+        .metadata(key, version)
+        .callback(seen.clone())
+        .build();
+    let run = agent.run("hi").await.unwrap();
+
+    assert_eq!(run.context.metadata["prompt.system"], "3.1.0");
+    assert_eq!(*seen.0.lock().unwrap(), vec![Some("3.1.0".to_string())]);
+}
+
+#[test]
+fn test_system_prompt_builder_orders_sections() {
+    use agentic_optio_rs::agents::Persona;
+    use agentic_optio_rs::prompts::system::{DATE_ORDER, TOOLS_ORDER};
+    use agentic_optio_rs::prompts::SystemPromptBuilder;
+    use agentic_optio_rs::tools::{FunctionTool, ToolRegistry};
+    use chrono::NaiveDate;
+
+    let mut tools = ToolRegistry::new();
+    tools.register(
+        FunctionTool::new("weather", "Forecast for a city", |_| async {
+            Ok(String::new())
+        })
+        .with_parameters(serde_json::json!({
+            "type": "object",
+            "properties": {"city": {"type": "string"}, "days": {"type": "integer"}},
+            "required": ["city"]
+        })),
+    );
+
+    let prompt = SystemPromptBuilder::new()
+        .section_at(DATE_ORDER + 1, "Reminder", "Be kind.")
+        .date(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+        .tools(&tools)
+        .section("Style", "Be brief.")
+        .section_at(TOOLS_ORDER - 1, "Safety", "Never guess forecasts.")
+        .persona(&Persona::new("Nimbus", "a weather assistant"))
+        .build();
+
+    assert_eq!(
+        prompt,
+        "You are Nimbus, a weather assistant.\n\n\
+         Style:\nBe brief.\n\n\
+         Safety:\nNever guess forecasts.\n\n\
+         Tools:\nCall a tool when it helps answer the request. Available tools:\n\
+         - weather(city, days?): Forecast for a city\n\n\
+         Current date: 2024-02-29\n\n\
+         Reminder:\nBe kind."
+    );
+    assert_eq!(
+        SystemPromptBuilder::new()
+            .identity("Hi")
+            .tools(&ToolRegistry::new())
+            .build(),
+        "Hi"
+    );
+}
@@ -0,0 +1,206 @@
+//! Retriever and RAG chain tests for agentic_optio_rs
+
+mod common;
+
+use agentic_optio_rs::chains::RagChain;
+use agentic_optio_rs::core::messages::AIMessage;
+use agentic_optio_rs::models::LlmReranker;
+use agentic_optio_rs::retrievers::{
+    Bm25Index, Bm25Retriever, HybridRetriever, ParentDocumentRetriever, RerankingRetriever,
+    Retriever, VectorStoreRetriever,
+};
+use agentic_optio_rs::testing::MockChatModel;
+use agentic_optio_rs::text_splitter::RecursiveCharacterSplitter;
+use agentic_optio_rs::vectorstores::{InMemoryVectorStore, MetadataFilter, VectorStore};
+use agentic_optio_rs::Document;
+use common::LetterEmbedding;
+use std::sync::Arc;
+
+async fn store() -> Arc<InMemoryVectorStore> {
+    let store = Arc::new(InMemoryVectorStore::new(Arc::new(LetterEmbedding)));
+    store
+        .add_documents(vec![
+            Document::new("aaaa aaaa").with_metadata("source", "a.txt"),
+            Document::new("bbbb bbbb").with_metadata("source", "b.txt"),
+            Document::new("aaab").with_metadata("lang", "fr"),
+        ])
+        .await
+        .unwrap();
+    store
+}
+
+#[tokio::test]
+async fn test_vector_store_retriever_k_and_filter() {
+    let store = store().await;
+
+    let docs = VectorStoreRetriever::new(store.clone())
+        .k(2)
+        .retrieve("aaaa")
+        .await
+        .unwrap();
+    assert_eq!(docs.len(), 2);
+    assert_eq!(docs[0].content, "aaaa aaaa");
+    assert!(docs[0].metadata["score"].as_f64().unwrap() > 0.99);
+
+    let filtered = VectorStoreRetriever::new(store.clone())
+        .filter(MetadataFilter::new().eq("lang", "fr"))
+        .retrieve("aaaa")
+        .await
+        .unwrap();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].content, "aaab");
+
+    let thresholded = VectorStoreRetriever::new(store)
+        .score_threshold(0.5)
+        .retrieve("bbbb")
+        .await
+        .unwrap();
+    assert_eq!(thresholded.len(), 1);
+}
+
+#[tokio::test]
+async fn test_rag_chain_grounds_prompt_and_returns_sources() {
+    let model = Arc::new(MockChatModel::with_messages(vec![AIMessage::new(
+        "It is mostly a's [1].",
+    )]));
+    let retriever = VectorStoreRetriever::new(store().await).k(2);
+    let chain = RagChain::new(Arc::new(retriever), model.clone())
+        .system_prompt("Be brief.")
+        .prompt("Sources:\n{context}\n\nQ: {question}");
+
+    let response = chain.invoke("aaaa").await.unwrap();
+    assert_eq!(response.answer.content, "It is mostly a's [1].");
+    assert_eq!(response.sources.len(), 2);
+    assert_eq!(response.sources[0].metadata["source"], "a.txt");
+
+    let received = model.received();
+    assert_eq!(received[0].len(), 2);
+    assert_eq!(received[0][0].content(), "Be brief.");
+    assert_eq!(
+        received[0][1].content(),
+        "Sources:\n[1] (source: a.txt)\naaaa aaaa\n\n[2]\naaab\n\nQ: aaaa"
+    );
+}
+
+#[tokio::test]
+async fn test_llm_reranker_reorders_candidates() {
+    // Candidates arrive as "aaaa aaaa", "aaab", "bbbb bbbb"
+    let model = Arc::new(MockChatModel::with_responses([
+        "2",
+        "Score: 9/10",
+        "no idea",
+    ]));
+    let reranker = LlmReranker::new(model.clone());
+    let retriever = RerankingRetriever::new(
+        Arc::new(VectorStoreRetriever::new(store().await).k(3)),
+        Arc::new(reranker),
+    )
+    .top_n(2);
+
+    let docs = retriever.retrieve("aaaa").await.unwrap();
+    assert_eq!(docs.len(), 2);
+    assert_eq!(docs[0].content, "aaab");
+    assert!((docs[0].metadata["rerank_score"].as_f64().unwrap() - 0.9).abs() < 1e-6);
+    assert_eq!(docs[1].content, "aaaa aaaa");
+    assert!(model.received()[1][1].content().contains("Document:\naaab"));
+}
+
+#[tokio::test]
+async fn test_bm25_ranks_exact_terms() {
+    let index = Bm25Index::from_documents(vec![
+        Document::new("The deploy failed with ERR_4012 after the token expired."),
+        Document::new("Deploy steps: build, test, deploy, deploy again."),
+        Document::new("Unrelated note about lunch."),
+    ]);
+    assert_eq!(index.len(), 3);
+
+    let results = index.search("ERR_4012", 5);
+    assert_eq!(results.len(), 1);
+    assert!(results[0].0.content.contains("ERR_4012"));
+
+    let results = index.search("deploy", 5);
+    assert_eq!(results.len(), 2);
+    assert!(results[0].0.content.starts_with("Deploy steps"));
+    assert!(index.search("", 5).is_empty());
+}
+
+#[tokio::test]
+async fn test_hybrid_retriever_fuses_rankings() {
+    let docs = vec![
+        Document::new("aaaa aaaa").with_id("a"),
+        Document::new("aaab x_9").with_id("ab"),
+        Document::new("bbbb aaaa").with_id("b"),
+    ];
+    let store = Arc::new(InMemoryVectorStore::new(Arc::new(LetterEmbedding)));
+    store.add_documents(docs.clone()).await.unwrap();
+
+    // Vector search ranks "a" first; BM25 ranks "ab" first on the rare identifier.
+    let hybrid = HybridRetriever::new()
+        .retriever(Arc::new(VectorStoreRetriever::new(store).k(3)))
+        .weighted(Arc::new(Bm25Retriever::from_documents(docs).k(3)), 2.0)
+        .k(2);
+    let results = hybrid.retrieve("aaaa x_9").await.unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].id.as_deref(), Some("ab"));
+    assert_eq!(results[1].id.as_deref(), Some("a"));
+    let top = results[0].metadata["hybrid_score"].as_f64().unwrap();
+    assert!((top - (1.0 / 62.0 + 2.0 / 61.0)).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_parent_document_retriever_returns_parents() {
+    let store = Arc::new(InMemoryVectorStore::new(Arc::new(LetterEmbedding)));
+    let retriever =
+        ParentDocumentRetriever::new(store.clone(), Arc::new(RecursiveCharacterSplitter::new(5)))
+            .k(2);
+    let ids = retriever
+        .add_documents(vec![
+            Document::new("aaaa bbbb").with_id("ab"),
+            Document::new("cccc dddd").with_id("cd"),
+            Document::new("eeee bbbb").with_id("eb"),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(ids, vec!["ab", "cd", "eb"]);
+    assert_eq!(store.len(), 6);
+
+    let docs = retriever.retrieve("bbbb").await.unwrap();
+    let contents: Vec<&str> = docs.iter().map(|d| d.content.as_str()).collect();
+    assert_eq!(contents, vec!["aaaa bbbb", "eeee bbbb"]);
+    assert!(docs[0].metadata["score"].as_f64().unwrap() > 0.99);
+
+    // Re-adding a parent replaces its chunks; deleting removes them
+    retriever
+        .add_documents(vec![Document::new("dddd").with_id("cd")])
+        .await
+        .unwrap();
+    assert_eq!(store.len(), 5);
+    retriever.delete(&["ab".to_string()]).await.unwrap();
+    assert_eq!(store.len(), 3);
+    assert!(retriever.get("ab").is_none());
+    let docs = retriever.retrieve("bbbb").await.unwrap();
+    assert_eq!(docs[0].content, "eeee bbbb");
+}
+
+#[tokio::test]
+async fn test_parent_document_retriever_context_headers() {
+    let store = Arc::new(InMemoryVectorStore::new(Arc::new(LetterEmbedding)));
+    let retriever =
+        ParentDocumentRetriever::new(store, Arc::new(RecursiveCharacterSplitter::new(5)))
+            .parent_splitter(Arc::new(RecursiveCharacterSplitter::new(10)))
+            .context_header(|parent| Some(format!("[{}]", parent.metadata["title"].as_str()?)))
+            .return_chunks(true)
+            .k(1);
+    retriever
+        .add_documents(vec![Document::new("xxxx yyyy\n\nxxxx zzzz")
+            .with_id("doc")
+            .with_metadata("title", "qq")])
+        .await
+        .unwrap();
+    assert_eq!(retriever.get("doc#1").unwrap().content, "xxxx zzzz");
+
+    let chunks = retriever.retrieve("qq zzzz").await.unwrap();
+    assert_eq!(chunks[0].content, "[qq]\n\nzzzz");
+    assert_eq!(chunks[0].metadata["parent_id"], "doc#1");
+}
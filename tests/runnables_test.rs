@@ -0,0 +1,175 @@
+//! Runnable composition tests for agentic_optio_rs
+
+use agentic_optio_rs::chains::ClassificationChain;
+use agentic_optio_rs::models::base::ModelError;
+use agentic_optio_rs::output_parsers::{JsonParser, ListParser, ParseError};
+use agentic_optio_rs::prompts::{ChatPromptTemplate, PromptTemplate, PromptValues};
+use agentic_optio_rs::runnables::{
+    lambda, Branch, Pipeline, Runnable, RunnableError, RunnableParallel, RunnableResult,
+};
+use agentic_optio_rs::testing::MockChatModel;
+use agentic_optio_rs::{BaseChatModel, Message};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn topic_prompt() -> ChatPromptTemplate {
+    ChatPromptTemplate::new()
+        .system("Answer with a comma-separated list only.")
+        .unwrap()
+        .user("Name three {topic}.")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_prompt_model_parser_chain() {
+    let model = Arc::new(MockChatModel::new().respond("Nile, Amazon, Yangtze"));
+    let chain = topic_prompt()
+        .then(model.clone())
+        .then(ListParser::comma())
+        .map(|rivers| rivers.len());
+
+    let count = chain
+        .run(PromptValues::new().set("topic", "rivers"))
+        .await
+        .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(model.received()[0][1].content(), "Name three rivers.");
+}
+
+#[tokio::test]
+async fn test_pipelines_compose_with_pipe() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Answer {
+        value: u32,
+    }
+
+    let question = PromptTemplate::new("What is {a} + {b}?").unwrap();
+    let chain = question.boxed()
+        | lambda(|text: String| async move { RunnableResult::Ok(vec![Message::user(text)]) })
+            .boxed()
+        | MockChatModel::new().respond(r#"{"value": 4}"#).boxed()
+        | JsonParser::<Answer>::new().boxed();
+
+    let input = HashMap::from([
+        ("a".to_string(), "2".to_string()),
+        ("b".to_string(), "2".to_string()),
+    ]);
+    assert_eq!(chain.clone().run(input).await.unwrap(), Answer { value: 4 });
+}
+
+#[tokio::test]
+async fn test_errors_stop_the_chain() {
+    let model = Arc::new(MockChatModel::new().respond("unused"));
+    let chain = topic_prompt().then(model.clone());
+    assert!(matches!(
+        chain.run(PromptValues::new()).await,
+        Err(RunnableError::Prompt(_))
+    ));
+    assert_eq!(model.calls(), 0);
+
+    let failing = Arc::new(MockChatModel::new().fail(ModelError::Timeout("slow".into())));
+    let chain: Pipeline<Vec<Message>, Vec<String>> = failing.then(ListParser::comma()).boxed();
+    assert!(matches!(
+        chain.run(vec![Message::user("hi")]).await,
+        Err(RunnableError::Model(ModelError::Timeout(_)))
+    ));
+
+    let model: Arc<dyn BaseChatModel> = Arc::new(MockChatModel::new().respond("no json here"));
+    let chain = model.then(JsonParser::<serde_json::Value>::new().strict());
+    assert!(matches!(
+        chain.run(vec![Message::user("hi")]).await,
+        Err(RunnableError::Parse(ParseError::Invalid { .. }))
+    ));
+}
+
+#[tokio::test]
+async fn test_parallel_branches_run_concurrently() {
+    let branch = |reply: &str| {
+        let model = MockChatModel::new()
+            .respond(reply)
+            .latency(Duration::from_millis(200));
+        topic_prompt().then(model).map(|reply| reply.content)
+    };
+    let parallel = RunnableParallel::new()
+        .branch("short", branch("Nile"))
+        .branch("long", branch("Nile, Amazon, Yangtze"))
+        .branch("none", branch(""));
+    assert_eq!(parallel.names(), ["short", "long", "none"]);
+
+    let started = Instant::now();
+    let outputs = parallel
+        .run(PromptValues::new().set("topic", "rivers"))
+        .await
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_millis(500));
+    assert_eq!(outputs.len(), 3);
+    assert_eq!(outputs["long"], "Nile, Amazon, Yangtze");
+
+    let parallel = parallel.branch(
+        "none",
+        topic_prompt()
+            .then(MockChatModel::new().fail(ModelError::Timeout("slow".into())))
+            .map(|reply| reply.content),
+    );
+    match parallel
+        .run(PromptValues::new().set("topic", "rivers"))
+        .await
+    {
+        Err(RunnableError::Branch { name, source }) => {
+            assert_eq!(name, "none");
+            assert!(matches!(
+                *source,
+                RunnableError::Model(ModelError::Timeout(_))
+            ));
+        }
+        other => panic!("expected a branch error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_tuple_branches_keep_their_types() {
+    let summarize = lambda(|text: String| async move {
+        RunnableResult::Ok(text.split('.').next().unwrap_or_default().to_string())
+    });
+    let count =
+        lambda(|text: String| async move { RunnableResult::Ok(text.split_whitespace().count()) });
+    let (summary, words) = (summarize, count)
+        .run("Rust is fast. It is also safe.".to_string())
+        .await
+        .unwrap();
+    assert_eq!(summary, "Rust is fast");
+    assert_eq!(words, 7);
+}
+
+#[tokio::test]
+async fn test_branch_routes_by_predicate_then_label() {
+    let reply =
+        |text: &'static str| lambda(move |_: String| async move { RunnableResult::Ok(text) });
+    let classifier = Arc::new(
+        MockChatModel::new()
+            .respond(r#"{"label": "math"}"#)
+            .respond(r#"{"label": "other"}"#),
+    );
+    let topic = ClassificationChain::new(classifier.clone())
+        .label("math", "Arithmetic", ())
+        .label("other", "Anything else", ())
+        .map(|classification| classification.label);
+    let branch = Branch::new(reply("general"))
+        .when(|question: &String| question.is_empty(), reply("empty"))
+        .classifier(topic)
+        .when_label("MATH", reply("math"));
+
+    assert_eq!(branch.run(String::new()).await.unwrap(), "empty");
+    assert_eq!(classifier.calls(), 0);
+    assert_eq!(branch.run("2 + 2?".to_string()).await.unwrap(), "math");
+    assert_eq!(branch.run("Hello".to_string()).await.unwrap(), "general");
+    assert_eq!(classifier.calls(), 2);
+
+    let unclassified = Branch::new(reply("general")).when_label("math", reply("math"));
+    assert!(matches!(
+        unclassified.run("2 + 2?".to_string()).await,
+        Err(RunnableError::Other(_))
+    ));
+}
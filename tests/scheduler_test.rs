@@ -0,0 +1,118 @@
+//! Scheduler tests for agentic_optio_rs
+
+use agentic_optio_rs::agents::{
+    Agent, CronSchedule, OverlapPolicy, Schedule, ScheduledJob, Scheduler,
+};
+use agentic_optio_rs::core::messages::{AIMessage, Message};
+use agentic_optio_rs::models::base::{BaseChatModel, BoxStream, ModelError, ModelResult};
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Model that answers "ok" after a delay
+struct SlowModel {
+    delay: Duration,
+}
+
+#[async_trait]
+impl BaseChatModel for SlowModel {
+    async fn invoke(&self, _messages: &[Message]) -> ModelResult<AIMessage> {
+        tokio::time::sleep(self.delay).await;
+        Ok(AIMessage::new("ok"))
+    }
+
+    async fn stream<'a>(
+        &'a self,
+        _messages: &'a [Message],
+    ) -> ModelResult<BoxStream<'a, ModelResult<AIMessage>>> {
+        Err(ModelError::ApiError("not supported".to_string()))
+    }
+}
+
+fn slow_agent(delay_ms: u64) -> Agent {
+    Agent::builder(Arc::new(SlowModel {
+        delay: Duration::from_millis(delay_ms),
+    }))
+    .build()
+}
+
+#[test]
+fn test_cron_next_after() {
+    let at = |d, h, m| Utc.with_ymd_and_hms(2024, 1, d, h, m, 0).unwrap();
+
+    let quarter_hours: CronSchedule = "*/15 9-17 * * *".parse().unwrap();
+    assert_eq!(quarter_hours.next_after(at(10, 9, 7)), Some(at(10, 9, 15)));
+    assert_eq!(quarter_hours.next_after(at(10, 17, 45)), Some(at(11, 9, 0)));
+
+    // 2024-01-13 is a Saturday; either day field may match.
+    let first_or_monday: CronSchedule = "0 0 1 * 1".parse().unwrap();
+    assert_eq!(
+        first_or_monday.next_after(at(13, 12, 0)),
+        Some(at(15, 0, 0))
+    );
+
+    assert!("61 * * * *".parse::<CronSchedule>().is_err());
+    assert!("* * *".parse::<CronSchedule>().is_err());
+}
+
+#[tokio::test]
+async fn test_skip_policy_drops_overlapping_runs() {
+    let handle = Scheduler::new()
+        .job(
+            ScheduledJob::new(
+                "monitor",
+                Schedule::every(Duration::from_millis(20)),
+                slow_agent(100),
+                "check",
+            )
+            .overlap(OverlapPolicy::Skip),
+        )
+        .start()
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    let state = handle.state("monitor").unwrap();
+    handle.shutdown().await;
+
+    assert!(state.runs >= 1);
+    assert!(state.skipped >= 1);
+    assert!(state.runs < 5);
+}
+
+#[tokio::test]
+async fn test_state_file_persists_last_run() {
+    let path = std::env::temp_dir().join(format!("optio-scheduler-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let job = || {
+        ScheduledJob::new(
+            "digest",
+            Schedule::every(Duration::from_millis(30)),
+            slow_agent(0),
+            "summarize",
+        )
+        .overlap(OverlapPolicy::Queue)
+    };
+
+    let handle = Scheduler::new()
+        .job(job())
+        .state_file(&path)
+        .start()
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    handle.shutdown().await;
+
+    let restarted = Scheduler::new()
+        .job(job())
+        .state_file(&path)
+        .start()
+        .unwrap();
+    let state = restarted.state("digest").unwrap();
+    restarted.shutdown().await;
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(state.runs >= 1);
+    assert!(state.last_run.is_some());
+    assert_eq!(state.last_output.as_deref(), Some("ok"));
+}
@@ -0,0 +1,284 @@
+//! Session tests for agentic_optio_rs
+
+use agentic_optio_rs::core::messages::Message;
+use agentic_optio_rs::models::base::ModelError;
+use agentic_optio_rs::sessions::{
+    InMemorySessionStore, SessionError, SessionManager, SessionMemory, SessionStore,
+};
+use agentic_optio_rs::testing::MockChatModel;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_session_round_trip_through_store() {
+    let model = Arc::new(MockChatModel::with_responses(["Hello!", "Paris."]));
+    let store = Arc::new(InMemorySessionStore::new());
+    let sessions = SessionManager::new(model.clone(), store.clone()).system_prompt("Be brief.");
+
+    let mut session = sessions.create().await.unwrap();
+    session.set_metadata("user", "ana");
+    let reply = session.send("Hi").await.unwrap();
+    assert_eq!(reply.content, "Hello!");
+    let id = session.id().to_string();
+
+    let mut session = sessions.load(&id).await.unwrap();
+    assert_eq!(session.messages().len(), 2);
+    assert_eq!(session.history().metadata["user"], "ana");
+    session.send("Capital of France?").await.unwrap();
+
+    let sent = &model.received()[1];
+    let roles: Vec<&str> = sent.iter().map(|m| m.role()).collect();
+    assert_eq!(roles, ["system", "user", "assistant", "user"]);
+    assert_eq!(sent[0].content(), "Be brief.");
+
+    let stored = store.load(&id).await.unwrap().unwrap();
+    assert_eq!(stored.messages.len(), 4);
+    assert_eq!(stored.version, 3);
+    assert_eq!(sessions.list().await.unwrap(), vec![id.clone()]);
+
+    sessions.delete(&id).await.unwrap();
+    assert!(matches!(
+        sessions.load(&id).await,
+        Err(SessionError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_last_turns_memory_sends_recent_turns_only() {
+    let model = Arc::new(MockChatModel::with_responses(["a1", "a2", "a3"]));
+    let sessions = SessionManager::new(model.clone(), Arc::new(InMemorySessionStore::new()))
+        .memory(SessionMemory::LastTurns(2));
+
+    let mut session = sessions.open("chat-1").await.unwrap();
+    assert_eq!(session.id(), "chat-1");
+    for input in ["q1", "q2", "q3"] {
+        session.send(input).await.unwrap();
+    }
+
+    let sent: Vec<String> = model.received()[2]
+        .iter()
+        .map(|m| m.content().to_string())
+        .collect();
+    assert_eq!(sent, ["q2", "a2", "q3"]);
+    assert_eq!(session.messages().len(), 6);
+}
+
+#[tokio::test]
+async fn test_concurrent_save_conflicts_until_reload() {
+    let model = Arc::new(MockChatModel::with_responses(["one", "two", "three"]));
+    let sessions = SessionManager::new(model, Arc::new(InMemorySessionStore::new()));
+
+    let mut first = sessions.create().await.unwrap();
+    let mut second = sessions.load(first.id()).await.unwrap();
+    first.send("from first").await.unwrap();
+
+    assert!(matches!(
+        second.send("from second").await,
+        Err(SessionError::Conflict(_))
+    ));
+    second.reload().await.unwrap();
+    second.send("from second").await.unwrap();
+    assert_eq!(second.messages().len(), 4);
+    assert!(matches!(
+        sessions.create_with_id(first.id()).await,
+        Err(SessionError::Conflict(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_model_failure_keeps_history_unchanged() {
+    let model = Arc::new(MockChatModel::new().fail(ModelError::ApiError("offline".into())));
+    let store = Arc::new(InMemorySessionStore::new());
+    let sessions = SessionManager::new(model, store.clone());
+
+    let mut session = sessions.create().await.unwrap();
+    session.append(Message::user("earlier"));
+    session.save().await.unwrap();
+
+    assert!(matches!(
+        session.send("Hi").await,
+        Err(SessionError::Model(_))
+    ));
+    assert_eq!(session.messages().len(), 1);
+    let stored = store.load(session.id()).await.unwrap().unwrap();
+    assert_eq!(stored.messages.len(), 1);
+}
+
+#[test]
+fn test_jsonl_export_import_round_trip() {
+    use agentic_optio_rs::core::messages::{AIMessage, ToolCall};
+    use agentic_optio_rs::sessions::ChatHistory;
+
+    let mut history = ChatHistory::new("chat-1");
+    history.push(Message::system("You check the weather."));
+    history.push(Message::user("Weather in Oslo?"));
+    history.push(Message::AI(AIMessage::with_tool_calls(
+        "",
+        vec![ToolCall {
+            id: "call_1".into(),
+            name: "weather".into(),
+            args: serde_json::json!({ "city": "Oslo" }),
+        }],
+    )));
+    history.push(Message::tool("-3C, snow", "call_1"));
+    history.push(Message::assistant("It is snowing."));
+
+    let mut out = Vec::new();
+    history.export_jsonl(&mut out).unwrap();
+    history.export_jsonl(&mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let first: serde_json::Value = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+    let call = &first["messages"][2]["tool_calls"][0];
+    assert_eq!(call["function"]["name"], "weather");
+    assert_eq!(call["function"]["arguments"], r#"{"city":"Oslo"}"#);
+
+    let imported = ChatHistory::import_jsonl(text.as_bytes()).unwrap();
+    assert_eq!(imported.len(), 2);
+    let messages = &imported[0].messages;
+    let roles: Vec<&str> = messages.iter().map(|m| m.role()).collect();
+    assert_eq!(roles, ["system", "user", "assistant", "tool", "assistant"]);
+    let Message::AI(call) = &messages[2] else {
+        panic!("expected an assistant message");
+    };
+    assert_eq!(call.tool_calls[0].args["city"], "Oslo");
+    let Message::Tool(result) = &messages[3] else {
+        panic!("expected a tool message");
+    };
+    assert_eq!(result.tool_call_id, "call_1");
+    assert_ne!(imported[0].id, imported[1].id);
+}
+
+#[test]
+fn test_jsonl_import_accepts_other_tools_output() {
+    use agentic_optio_rs::sessions::ChatHistory;
+
+    let jsonl = r#"{"id": "t1", "messages": [{"role": "developer", "content": "Be terse."}, {"role": "user", "content": [{"type": "text", "text": "Hi"}]}, {"role": "assistant", "content": null, "tool_calls": [{"id": "c1", "type": "function", "function": {"name": "f", "arguments": "not json"}}]}]}
+
+{"messages": [{"role": "narrator", "content": "x"}]}
+"#;
+    match ChatHistory::import_jsonl(jsonl.as_bytes()) {
+        Err(SessionError::InvalidImport { line, reason }) => {
+            assert_eq!(line, 3);
+            assert!(reason.contains("narrator"), "{reason}");
+        }
+        other => panic!("expected an import error, got {other:?}"),
+    }
+
+    let first = jsonl.lines().next().unwrap();
+    let imported = ChatHistory::import_jsonl(first.as_bytes()).unwrap();
+    assert_eq!(imported[0].id, "t1");
+    let messages = &imported[0].messages;
+    assert_eq!(messages[0].role(), "system");
+    assert_eq!(messages[1].content(), "Hi");
+    let Message::AI(call) = &messages[2] else {
+        panic!("expected an assistant message");
+    };
+    assert_eq!(call.tool_calls[0].args, "not json");
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_sqlite_session_store_survives_reopen() {
+    use agentic_optio_rs::core::messages::{AIMessage, ToolCall, Usage};
+    use agentic_optio_rs::sessions::{ChatHistory, SqliteSessionStore};
+
+    let path = std::env::temp_dir().join(format!("optio-sessions-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let store = SqliteSessionStore::open(&path).await.unwrap().build();
+    let mut history = ChatHistory::new("chat-1");
+    history.metadata.insert("user".into(), "ana".into());
+    history.push(Message::user("Weather in Oslo?"));
+    let mut call = AIMessage::new("");
+    call.tool_calls.push(ToolCall {
+        id: "call_1".into(),
+        name: "weather".into(),
+        args: serde_json::json!({ "city": "Oslo" }),
+    });
+    call.usage = Some(Usage::new(12, 5));
+    history.push(Message::AI(call));
+    history.push(Message::tool("-3C, snow", "call_1"));
+    history.push(Message::assistant("It is snowing."));
+    store.save(&mut history).await.unwrap();
+    store.save(&mut history).await.unwrap();
+    assert_eq!(history.version, 2);
+    store.pool().close().await;
+
+    let store = SqliteSessionStore::open(&path).await.unwrap().build();
+    assert_eq!(store.migrate().await.unwrap(), 1);
+    let loaded = store.load("chat-1").await.unwrap().unwrap();
+    assert_eq!(loaded.version, 2);
+    assert_eq!(loaded.metadata["user"], "ana");
+    assert_eq!(
+        loaded.created_at.timestamp_millis(),
+        history.created_at.timestamp_millis()
+    );
+    let roles: Vec<&str> = loaded.messages.iter().map(|m| m.role()).collect();
+    assert_eq!(roles, ["user", "assistant", "tool", "assistant"]);
+    let Message::AI(call) = &loaded.messages[1] else {
+        panic!("expected an assistant message");
+    };
+    assert_eq!(call.tool_calls[0].args["city"], "Oslo");
+    assert_eq!(call.usage, Some(Usage::new(12, 5)));
+    let Message::Tool(result) = &loaded.messages[2] else {
+        panic!("expected a tool message");
+    };
+    assert_eq!(result.tool_call_id, "call_1");
+
+    let mut stale = ChatHistory::new("chat-1");
+    assert!(matches!(
+        store.save(&mut stale).await,
+        Err(SessionError::Conflict(_))
+    ));
+    stale.version = 1;
+    assert!(matches!(
+        store.save(&mut stale).await,
+        Err(SessionError::Conflict(_))
+    ));
+    assert_eq!(store.list().await.unwrap(), ["chat-1"]);
+    store.delete("chat-1").await.unwrap();
+    assert!(store.load("chat-1").await.unwrap().is_none());
+
+    store.pool().close().await;
+    let _ = std::fs::remove_file(&path);
+}
+
+#[cfg(feature = "redis")]
+#[tokio::test]
+#[ignore] // Requires Redis at REDIS_URL
+async fn test_redis_session_store_shared_between_instances() {
+    use agentic_optio_rs::sessions::{ChatHistory, RedisSessionStore};
+    use std::time::Duration;
+
+    let url = std::env::var("REDIS_URL").unwrap();
+    let connect = || async {
+        RedisSessionStore::connect(&url)
+            .await
+            .unwrap()
+            .prefix("optio_test:session:")
+            .ttl(Duration::from_millis(300))
+            .build()
+    };
+    let (first, second) = (connect().await, connect().await);
+    for id in first.list().await.unwrap() {
+        first.delete(&id).await.unwrap();
+    }
+
+    let mut history = ChatHistory::new("chat-1");
+    history.push(Message::user("Hi"));
+    first.save(&mut history).await.unwrap();
+    let mut other = second.load("chat-1").await.unwrap().unwrap();
+    assert_eq!(other.messages[0].content(), "Hi");
+    assert_eq!(second.list().await.unwrap(), ["chat-1"]);
+
+    history.push(Message::assistant("Hello!"));
+    first.save(&mut history).await.unwrap();
+    other.push(Message::assistant("Hey!"));
+    assert!(matches!(
+        second.save(&mut other).await,
+        Err(SessionError::Conflict(_))
+    ));
+    assert_eq!(second.load("chat-1").await.unwrap().unwrap().version, 2);
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    assert!(first.load("chat-1").await.unwrap().is_none());
+}
@@ -0,0 +1,215 @@
+//! Similarity and embedding transform tests for agentic_optio_rs
+
+use agentic_optio_rs::embeddings::similarity::{
+    cosine, dot, euclidean, select_top_k, top_k, Metric,
+};
+use agentic_optio_rs::embeddings::{
+    elbow, elbow_point, l2_normalize, silhouette_score, ClusterError, EmbeddingTransform, KMeans,
+    NearDuplicateDetector,
+};
+
+/// Deterministic pseudo-random vector
+fn vector(seed: u32, len: usize) -> Vec<f32> {
+    let mut state = seed.wrapping_mul(2654435761).wrapping_add(1);
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state % 2000) as f32 / 1000.0 - 1.0
+        })
+        .collect()
+}
+
+#[test]
+fn test_kernels_match_scalar_reference() {
+    // Lengths around the 8-lane boundary exercise both the SIMD body and tail.
+    for len in [0, 1, 7, 8, 9, 31, 768] {
+        let (a, b) = (vector(len as u32, len), vector(len as u32 + 99, len));
+        let ref_dot: f32 = a.iter().zip(&b).map(|(x, y)| x * y).sum();
+        let ref_dist: f32 = a
+            .iter()
+            .zip(&b)
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum::<f32>()
+            .sqrt();
+        let norms: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt()
+            * b.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let ref_cos = if norms == 0.0 { 0.0 } else { ref_dot / norms };
+
+        assert!((dot(&a, &b) - ref_dot).abs() < 1e-3, "dot len {}", len);
+        assert!(
+            (euclidean(&a, &b) - ref_dist).abs() < 1e-3,
+            "euclidean len {}",
+            len
+        );
+        assert!(
+            (cosine(&a, &b) - ref_cos).abs() < 1e-4,
+            "cosine len {}",
+            len
+        );
+    }
+}
+
+#[test]
+fn test_top_k_orders_by_metric() {
+    let candidates = vec![
+        vec![3.0, 0.0],
+        vec![0.5, 0.5],
+        vec![1.0, 0.0],
+        vec![-1.0, 0.0],
+    ];
+    let query = [1.0, 0.0];
+
+    let by_dot: Vec<usize> = top_k(&query, &candidates, 2, Metric::Dot)
+        .into_iter()
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(by_dot, vec![0, 2]);
+
+    let by_distance = top_k(&query, &candidates, 2, Metric::Euclidean);
+    assert_eq!(by_distance[0], (2, 0.0));
+    assert_eq!(by_distance[1].0, 1);
+}
+
+#[test]
+fn test_select_top_k_keeps_input_order_for_ties() {
+    let items = vec![
+        ("a", 0.5),
+        ("b", 0.9),
+        ("c", 0.5),
+        ("d", f32::NAN),
+        ("e", 0.5),
+    ];
+    let top: Vec<&str> = select_top_k(items, 3).into_iter().map(|(s, _)| s).collect();
+    assert_eq!(top, vec!["b", "a", "c"]);
+    assert!(select_top_k(vec![("x", 1.0)], 0).is_empty());
+}
+
+#[test]
+fn test_embedding_transform_truncates_then_normalizes() {
+    let mut unit = vec![0.0, 3.0, 4.0];
+    l2_normalize(&mut unit);
+    assert!((dot(&unit, &unit) - 1.0).abs() < 1e-6);
+
+    let mut zero = vec![0.0; 4];
+    l2_normalize(&mut zero);
+    assert_eq!(zero, vec![0.0; 4]);
+
+    let truncate = EmbeddingTransform::default().dimensions(2);
+    assert_eq!(truncate.apply(vec![1.0, 2.0, 3.0]).unwrap(), vec![1.0, 2.0]);
+    assert!(truncate.apply(vec![1.0]).is_err());
+
+    let both = truncate.normalize(true);
+    let batch = both
+        .apply_all(vec![vec![6.0, 8.0, 1.0], vec![0.0, 2.0, 9.0]])
+        .unwrap();
+    assert_eq!(batch, vec![vec![0.6, 0.8], vec![0.0, 1.0]]);
+    assert!(EmbeddingTransform::default().is_identity());
+}
+
+/// Three tight 2-D blobs of 20 points each
+fn blobs() -> Vec<Vec<f32>> {
+    let centers = [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)];
+    centers
+        .iter()
+        .enumerate()
+        .flat_map(|(c, &(x, y))| {
+            (0..20).map(move |i| {
+                let jitter = vector((c * 20 + i) as u32, 2);
+                vec![x + jitter[0], y + jitter[1]]
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_kmeans_recovers_blobs() {
+    let points = blobs();
+    let clustering = KMeans::new(3).fit(&points).unwrap();
+
+    let members = clustering.members();
+    assert!(members.iter().all(|m| m.len() == 20));
+    for blob in 0..3 {
+        let cluster = clustering.assignments[blob * 20];
+        assert!(clustering.assignments[blob * 20..blob * 20 + 20]
+            .iter()
+            .all(|&c| c == cluster));
+    }
+
+    let good = silhouette_score(&points, &clustering.assignments);
+    let two = KMeans::new(2).fit(&points).unwrap();
+    assert!(good > 0.8);
+    assert!(good > silhouette_score(&points, &two.assignments));
+
+    let curve = elbow(&points, 1..=6, KMeans::new(1)).unwrap();
+    assert_eq!(curve.len(), 6);
+    assert!(curve.windows(2).all(|w| w[1].1 <= w[0].1 + 1e-3));
+    assert_eq!(elbow_point(&curve), Some(3));
+
+    assert_eq!(
+        KMeans::new(5).fit(&points[..3]),
+        Err(ClusterError::TooFewPoints { points: 3, k: 5 })
+    );
+    assert_eq!(KMeans::new(0).fit(&points), Err(ClusterError::ZeroClusters));
+}
+
+#[test]
+fn test_spherical_kmeans_groups_by_direction() {
+    let points = vec![
+        vec![1.0, 0.1],
+        vec![10.0, 0.5],
+        vec![0.1, 1.0],
+        vec![0.4, 9.0],
+    ];
+    let clustering = KMeans::new(2).spherical(true).fit(&points).unwrap();
+    assert_eq!(clustering.assignments[0], clustering.assignments[1]);
+    assert_eq!(clustering.assignments[2], clustering.assignments[3]);
+    assert_ne!(clustering.assignments[0], clustering.assignments[2]);
+    for centroid in &clustering.centroids {
+        assert!((dot(centroid, centroid) - 1.0).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_near_duplicates_with_blocking_match_exhaustive() {
+    // 200 random vectors plus a slightly perturbed copy of every tenth one
+    let mut vectors: Vec<Vec<f32>> = (0..200).map(|i| vector(i, 64)).collect();
+    for i in (0..200).step_by(10) {
+        let mut copy = vectors[i].clone();
+        copy[0] += 0.01;
+        vectors.push(copy);
+    }
+
+    let blocked = NearDuplicateDetector::new(0.98).find_duplicates(&vectors);
+    let exhaustive = NearDuplicateDetector::new(0.98)
+        .bands(0)
+        .find_duplicates(&vectors);
+    assert_eq!(exhaustive.len(), 20);
+    assert_eq!(blocked, exhaustive);
+    assert!(exhaustive.iter().all(|p| p.second == 200 + p.first / 10));
+
+    let detector = NearDuplicateDetector::new(0.98);
+    assert_eq!(detector.duplicate_groups(&vectors)[1], vec![10, 201]);
+    assert_eq!(
+        detector.unique_indices(&vectors),
+        (0..200).collect::<Vec<_>>()
+    );
+}
+
+#[cfg(feature = "ollama")]
+#[test]
+fn test_ollama_embedding_declared_dimension() {
+    use agentic_optio_rs::{BaseEmbedding, OllamaEmbedding};
+
+    assert_eq!(OllamaEmbedding::new("nomic-embed-text").dimension(), 0);
+    let declared = OllamaEmbedding::builder("nomic-embed-text")
+        .embedding_dimension(768)
+        .build();
+    assert_eq!(declared.dimension(), 768);
+    let truncated = OllamaEmbedding::builder("nomic-embed-text")
+        .embedding_dimension(768)
+        .dimensions(256)
+        .build();
+    assert_eq!(truncated.dimension(), 256);
+}
@@ -0,0 +1,216 @@
+//! SQL tool tests for agentic_optio_rs
+
+use agentic_optio_rs::tools::sql::{check_read_only, QueryRows, SqlError};
+use serde_json::json;
+
+#[test]
+fn test_check_read_only() {
+    assert!(check_read_only("SELECT * FROM orders;").is_ok());
+    assert!(check_read_only("with t as (select 1) select * from t").is_ok());
+    assert!(check_read_only("SELECT * FROM notes WHERE body LIKE '%delete%'").is_ok());
+    assert!(check_read_only("SELECT \"update\" FROM logs -- drop later").is_ok());
+    assert!(check_read_only("SELECT replace(name, 'a', 'b') FROM t").is_ok());
+    assert!(check_read_only("SELECT s.value FROM settings s WHERE s.set = 1").is_ok());
+    assert!(check_read_only("SELECT lock, call, analyze FROM jobs").is_ok());
+
+    for sql in [
+        "DELETE FROM orders",
+        "SELECT 1; DROP TABLE orders",
+        "WITH gone AS (DELETE FROM orders RETURNING *) SELECT * FROM gone",
+        "WITH t AS (SELECT 1) REPLACE INTO orders SELECT * FROM t",
+        "SELECT * INTO backup FROM orders",
+        "PRAGMA table_info(orders)",
+        "ATTACH DATABASE 'other.db' AS other",
+        "",
+    ] {
+        assert!(
+            matches!(check_read_only(sql), Err(SqlError::Rejected(_))),
+            "accepted {sql:?}"
+        );
+    }
+}
+
+#[test]
+fn test_query_rows_text_notes_truncation() {
+    let rows = QueryRows {
+        columns: vec!["name".into(), "total".into()],
+        rows: vec![
+            vec![json!("Ada"), json!(42.5)],
+            vec![json!("Bob"), json!(null)],
+        ],
+        truncated: true,
+    };
+    assert_eq!(
+        rows.to_text(),
+        "name | total\nAda | 42.5\nBob | NULL\n\
+         (only the first 2 rows are shown; aggregate or add a LIMIT to see the rest)"
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_sql_agent_answers_from_sqlite() {
+    use agentic_optio_rs::testing::MockChatModel;
+    use agentic_optio_rs::tools::sql::{SqlDatabase, SqlToolkit, SqliteDatabase};
+    use std::sync::Arc;
+
+    let path = std::env::temp_dir().join(format!("optio-sql-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .connect_with(
+            sqlx::sqlite::SqliteConnectOptions::new()
+                .filename(&path)
+                .create_if_missing(true),
+        )
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE orders (id INTEGER PRIMARY KEY, customer TEXT, total REAL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    for (customer, total) in [("Ada", 30.0), ("Bob", 12.5), ("Ada", 20.0)] {
+        sqlx::query("INSERT INTO orders (customer, total) VALUES (?, ?)")
+            .bind(customer)
+            .bind(total)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    pool.close().await;
+
+    let db = SqliteDatabase::open(&path).await.unwrap();
+    assert_eq!(db.table_names().await.unwrap(), ["orders"]);
+    let description = db.describe_table("orders").await.unwrap();
+    assert!(description.starts_with("CREATE TABLE orders"));
+    assert!(description.contains("1 | Ada | 30.0"));
+    assert!(matches!(
+        db.describe_table("missing").await,
+        Err(SqlError::UnknownTable(_))
+    ));
+
+    let rows = db.query("SELECT customer FROM orders", 2).await.unwrap();
+    assert_eq!(rows.rows, [[json!("Ada")], [json!("Bob")]]);
+    assert!(rows.truncated);
+    assert!(matches!(
+        db.query("UPDATE orders SET total = 0", 10).await,
+        Err(SqlError::Rejected(_))
+    ));
+
+    let query =
+        "SELECT customer, SUM(total) AS spent FROM orders GROUP BY customer ORDER BY spent DESC";
+    let model = Arc::new(
+        MockChatModel::new()
+            .respond_tool_call("sql_list_tables", json!({}))
+            .respond_tool_call("sql_describe_tables", json!({"tables": ["orders"]}))
+            .respond_tool_call("sql_query", json!({"query": "DROP TABLE orders"}))
+            .respond_tool_call("sql_query", json!({ "query": query }))
+            .respond("Ada spent the most, 50.0 in total."),
+    );
+    let agent = SqlToolkit::new(Arc::new(db))
+        .max_rows(10)
+        .agent(model.clone())
+        .build();
+    let run = agent.run("Who spent the most?").await.unwrap();
+
+    assert_eq!(run.output, "Ada spent the most, 50.0 in total.");
+    assert_eq!(SqlToolkit::queries(&run), ["DROP TABLE orders", query]);
+    let tool_outputs: Vec<String> = model.received()[4]
+        .iter()
+        .filter(|m| m.role() == "tool")
+        .map(|m| m.content().to_string())
+        .collect();
+    assert_eq!(tool_outputs[0], "orders");
+    assert!(tool_outputs[2].contains("Query rejected"));
+    assert_eq!(tool_outputs[3], "customer | spent\nAda | 50.0\nBob | 12.5");
+    assert!(model.received()[0][0].content().contains("SQLite database"));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_sqlite_query_without_rows_keeps_columns() {
+    use agentic_optio_rs::tools::sql::{SqlDatabase, SqliteDatabase};
+
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE orders (id INTEGER PRIMARY KEY, customer TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let db = SqliteDatabase::new(pool);
+
+    let rows = db
+        .query("SELECT id, customer FROM orders WHERE 1=0", 10)
+        .await
+        .unwrap();
+    assert_eq!(rows.columns, ["id", "customer"]);
+    assert!(rows.rows.is_empty());
+    assert_eq!(rows.to_text(), "id | customer\n(no rows)");
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_sqlite_queries_run_read_only() {
+    use agentic_optio_rs::tools::sql::{SqlDatabase, SqliteDatabase};
+
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE jobs (id INTEGER PRIMARY KEY, lock TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let db = SqliteDatabase::new(pool.clone());
+
+    assert!(db.query("SELECT lock FROM jobs", 10).await.is_ok());
+    // Queries see the connection in query-only mode
+    assert!(matches!(
+        db.query("SELECT * FROM pragma_query_only", 1).await,
+        Ok(rows) if rows.rows == [[json!(1)]]
+    ));
+    // The connection is writable again afterwards
+    sqlx::query("INSERT INTO jobs (lock) VALUES ('a')")
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_sqlite_cancelled_query_leaves_pool_writable() {
+    use agentic_optio_rs::tools::sql::{SqlDatabase, SqliteDatabase};
+    use std::time::Duration;
+
+    let path = std::env::temp_dir().join(format!("sql-{}.db", uuid::Uuid::new_v4()));
+    let options = sqlx::sqlite::SqliteConnectOptions::new()
+        .filename(&path)
+        .create_if_missing(true);
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE jobs (id INTEGER PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let db = SqliteDatabase::new(pool.clone());
+
+    let slow = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 1000000) \
+                SELECT count(*) FROM n";
+    let cancelled = tokio::time::timeout(Duration::from_millis(20), db.query(slow, 1)).await;
+    assert!(cancelled.is_err());
+
+    sqlx::query("INSERT INTO jobs DEFAULT VALUES")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+    std::fs::remove_file(&path).unwrap();
+}
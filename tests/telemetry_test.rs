@@ -0,0 +1,521 @@
+//! Telemetry tests for agentic_optio_rs
+
+mod common;
+
+use agentic_optio_rs::agents::Agent;
+use agentic_optio_rs::callbacks::CallbackChatModel;
+use agentic_optio_rs::core::messages::{AIMessage, StreamStats, Usage};
+use agentic_optio_rs::guardrails::PiiRedactor;
+use agentic_optio_rs::models::base::{measure_stream, ModelError};
+use agentic_optio_rs::telemetry::{
+    ContentPolicy, ExportError, JsonLinesSink, TraceExporter, UsageDimension, UsageLedger,
+    WireLoggingChatModel, WireRecord, WireSink,
+};
+use agentic_optio_rs::testing::MockChatModel;
+use agentic_optio_rs::tools::FunctionTool;
+use agentic_optio_rs::{BaseChatModel, Message};
+use common::{capture_request, tool_call};
+use futures::StreamExt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+struct MemorySink(Mutex<Vec<WireRecord>>);
+
+impl WireSink for MemorySink {
+    fn record(&self, record: &WireRecord) {
+        self.0.lock().unwrap().push(record.clone());
+    }
+}
+
+#[tokio::test]
+async fn test_wire_logger_scrubs_secrets_and_redacts_pii() {
+    let model = Arc::new(MockChatModel::with_messages(vec![AIMessage::new(
+        "Reply to ada@example.com",
+    )
+    .with_usage(Usage::new(4, 2))]));
+    let sink = Arc::new(MemorySink::default());
+    let llm = WireLoggingChatModel::new(model, sink.clone())
+        .content(ContentPolicy::Redact(PiiRedactor::new()));
+
+    let messages = [
+        Message::system("Authorization: Bearer abc.def-123"),
+        Message::user("Use api_key=sk-0123456789abcdefXYZ and mail ada@example.com"),
+    ];
+    let response = llm.invoke(&messages).await.unwrap();
+    assert_eq!(response.content, "Reply to ada@example.com");
+
+    let records = sink.0.lock().unwrap();
+    let record = &records[0];
+    assert_eq!(record.request[0]["content"], "Authorization: [TOKEN_1]");
+    assert_eq!(
+        record.request[1]["content"],
+        "Use [SECRET_1] and mail [EMAIL_1]"
+    );
+    assert_eq!(
+        record.response.as_ref().unwrap()["content"],
+        "Reply to [EMAIL_1]"
+    );
+    assert_eq!(record.usage, Some(Usage::new(4, 2)));
+    assert!(record.error.is_none());
+}
+
+#[tokio::test]
+async fn test_wire_logger_omits_content_and_records_errors() {
+    let model = Arc::new(MockChatModel::with_responses(["fine"]));
+    let path = std::env::temp_dir().join(format!("wire-{}.jsonl", uuid::Uuid::new_v4()));
+    let llm = WireLoggingChatModel::new(model, JsonLinesSink::file(&path).unwrap())
+        .content(ContentPolicy::Omit);
+
+    let messages = [Message::user("secret plans")];
+    llm.invoke(&messages).await.unwrap();
+    assert!(llm.invoke(&messages).await.is_err());
+
+    let lines = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let records: Vec<WireRecord> = lines
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].request[0]["content"], "[OMITTED 12 chars]");
+    assert_eq!(
+        records[0].response.as_ref().unwrap()["content"],
+        "[OMITTED 4 chars]"
+    );
+    assert!(records[1].response.is_none());
+    assert!(records[1]
+        .error
+        .as_ref()
+        .unwrap()
+        .contains("script exhausted"));
+}
+
+#[tokio::test]
+async fn test_langsmith_export_nests_runs() {
+    let (url, server) = capture_request("").await;
+    let exporter = TraceExporter::langsmith(url, "ls-key", "tests");
+    let model = Arc::new(MockChatModel::with_messages(vec![
+        tool_call("echo", serde_json::json!({"text": "hi"})),
+        AIMessage::new("done").with_usage(Usage::new(7, 3)),
+    ]));
+    let agent = Agent::builder(model)
+        .name("tester")
+        .tool(echo_tool())
+        .callback(exporter.clone())
+        .metadata("prompt.tester", "1.2.0")
+        .build();
+
+    agent.run("say hi").await.unwrap();
+    assert_eq!(exporter.pending(), 4);
+    assert_eq!(exporter.flush().await.unwrap(), 4);
+    assert_eq!(exporter.pending(), 0);
+
+    let (head, body) = server.await.unwrap();
+    assert!(head.starts_with("POST /runs/batch"));
+    assert!(head.contains("x-api-key: ls-key"));
+    let runs = body["post"].as_array().unwrap();
+    let kinds: Vec<&str> = runs
+        .iter()
+        .map(|r| r["run_type"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, vec!["chain", "llm", "tool", "llm"]);
+    let root = &runs[0];
+    assert_eq!(root["name"], "tester");
+    assert!(root["parent_run_id"].is_null());
+    assert_eq!(root["outputs"]["output"], "done");
+    for run in &runs[1..] {
+        assert_eq!(run["parent_run_id"], root["id"]);
+        assert_eq!(run["trace_id"], root["id"]);
+        assert_eq!(run["session_name"], "tests");
+        assert_eq!(run["extra"]["metadata"]["prompt.tester"], "1.2.0");
+    }
+    assert_eq!(runs[3]["outputs"]["usage_metadata"]["total_tokens"], 10);
+}
+
+#[tokio::test]
+async fn test_langfuse_export_creates_trace_and_observations() {
+    let (url, server) = capture_request("").await;
+    let exporter = TraceExporter::langfuse(url, "pk-lf", "sk-lf");
+    let llm = CallbackChatModel::new(Arc::new(MockChatModel::new())).callback(exporter.clone());
+
+    assert!(llm.invoke(&[Message::user("hi")]).await.is_err());
+    assert_eq!(exporter.flush().await.unwrap(), 1);
+
+    let (head, body) = server.await.unwrap();
+    assert!(head.starts_with("POST /api/public/ingestion"));
+    assert!(head.to_lowercase().contains("authorization: basic"));
+    let events = body["batch"].as_array().unwrap();
+    assert_eq!(events[0]["type"], "trace-create");
+    assert_eq!(events[1]["type"], "generation-create");
+    let generation = &events[1]["body"];
+    assert_eq!(generation["traceId"], events[0]["body"]["id"]);
+    assert_eq!(generation["level"], "ERROR");
+    assert!(generation["statusMessage"]
+        .as_str()
+        .unwrap()
+        .contains("script exhausted"));
+}
+
+#[test]
+fn test_exporter_from_env_requires_keys() {
+    std::env::remove_var("LANGSMITH_API_KEY");
+    assert!(matches!(
+        TraceExporter::langsmith_from_env(),
+        Err(ExportError::MissingEnv("LANGSMITH_API_KEY"))
+    ));
+}
+
+#[tokio::test]
+async fn test_usage_ledger_attributes_agent_calls() {
+    let ledger = UsageLedger::new().pricing("mock", 1.0, 2.0);
+    let model = Arc::new(MockChatModel::with_messages(vec![
+        tool_call("echo", serde_json::json!({"text": "hi"})),
+        AIMessage::new("done").with_usage(Usage::new(1000, 500)),
+    ]));
+    let agent = Agent::builder(model)
+        .name("support, tier 1")
+        .tool(echo_tool())
+        .callback(ledger.recorder().session("s-1").tag("team-a"))
+        .build();
+    agent.run("say hi").await.unwrap();
+    ledger.record(
+        "gpt-4o",
+        Usage::new(10, 5),
+        Some("s-2"),
+        &["team-a", "eval"],
+    );
+
+    let total = ledger.total();
+    assert_eq!(total.calls, 3);
+    assert_eq!(total.total_tokens, 1515);
+    assert!((total.cost - 2.0).abs() < 1e-9);
+
+    let agent = ledger.summary_for(UsageDimension::Agent, "support, tier 1");
+    assert_eq!(agent.calls, 2);
+    assert_eq!(ledger.summary_for(UsageDimension::Tag, "team-a").calls, 3);
+    assert_eq!(ledger.summary_for(UsageDimension::Session, "s-2").calls, 1);
+
+    assert_eq!(
+        ledger.to_csv(UsageDimension::Agent),
+        "agent,calls,input_tokens,output_tokens,total_tokens,cost\n\
+         \"support, tier 1\",2,1000,500,1500,2.000000\n"
+    );
+    let report: serde_json::Value = serde_json::from_str(&ledger.to_json().unwrap()).unwrap();
+    assert_eq!(report["by_model"]["gpt-4o"]["calls"], 1);
+    assert_eq!(report["by_tag"]["eval"]["total_tokens"], 15);
+}
+
+#[tokio::test]
+async fn test_measured_stream_reports_throughput() {
+    let model = Arc::new(MockChatModel::with_messages(vec![AIMessage::new(
+        "three short words",
+    )]));
+    let messages = [Message::user("hi")];
+    let started = Instant::now();
+    let stream = measure_stream(model.stream(&messages).await.unwrap(), started);
+    let chunks: Vec<AIMessage> = stream.map(Result::unwrap).collect().await;
+
+    assert_eq!(chunks.len(), 4);
+    assert!(chunks[..3].iter().all(|c| c.stream_stats.is_none()));
+    let last = chunks.last().unwrap();
+    assert_eq!(last.content, "");
+    let stats = last.stream_stats.unwrap();
+    assert_eq!(stats.output_tokens, 3);
+    assert!(stats.time_to_first_token <= stats.duration);
+
+    // A stream ending in an error still reports its stats after it
+    let failing = futures::stream::iter(vec![
+        Ok(AIMessage::new("partial")),
+        Err(ModelError::Timeout("idle".to_string())),
+    ]);
+    let chunks: Vec<_> = measure_stream(Box::pin(failing), Instant::now())
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks[1].is_err());
+    assert_eq!(
+        chunks[2]
+            .as_ref()
+            .unwrap()
+            .stream_stats
+            .unwrap()
+            .output_tokens,
+        1
+    );
+
+    let stats = StreamStats {
+        time_to_first_token: Duration::from_millis(500),
+        duration: Duration::from_millis(2500),
+        output_tokens: 40,
+    };
+    assert!((stats.tokens_per_second() - 20.0).abs() < 1e-9);
+}
+
+fn echo_tool() -> FunctionTool {
+    FunctionTool::new("echo", "Echo text", |args| async move {
+        Ok(args["text"].as_str().unwrap_or_default().to_string())
+    })
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_spans {
+    use super::*;
+    use agentic_optio_rs::models::{BaseReranker, HttpReranker};
+    use agentic_optio_rs::Document;
+    use std::collections::BTreeMap;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+
+    /// Span name and fields, in creation order
+    type Spans = Arc<Mutex<Vec<(String, BTreeMap<String, String>)>>>;
+
+    /// Layer capturing spans and their recorded fields
+    struct Capture(Spans);
+
+    /// Position of a span in the capture list, stored in the span's extensions
+    struct Index(usize);
+
+    struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+    impl Visit for Fields<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            let value = format!("{:?}", value).trim_matches('"').to_string();
+            self.0.insert(field.name().to_string(), value);
+        }
+    }
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> tracing_subscriber::Layer<S> for Capture {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+            let mut fields = BTreeMap::new();
+            attrs.record(&mut Fields(&mut fields));
+            let mut spans = self.0.lock().unwrap();
+            spans.push((attrs.metadata().name().to_string(), fields));
+            let span = ctx.span(id).unwrap();
+            span.extensions_mut().insert(Index(spans.len() - 1));
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+            let span = ctx.span(id).unwrap();
+            let index = span.extensions().get::<Index>().unwrap().0;
+            values.record(&mut Fields(&mut self.0.lock().unwrap()[index].1));
+        }
+    }
+
+    /// Layer capturing wire debug events by message
+    struct WireEvents(Spans);
+
+    impl<S: Subscriber> tracing_subscriber::Layer<S> for WireEvents {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            if event.metadata().target() != "agentic_optio_rs::wire" {
+                return;
+            }
+            let mut fields = BTreeMap::new();
+            event.record(&mut Fields(&mut fields));
+            let message = fields.remove("message").unwrap_or_default();
+            self.0.lock().unwrap().push((message, fields));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_debug_wire_logs_raw_bodies() {
+        let (url, server) =
+            capture_request(r#"{"results":[{"index":0,"relevance_score":0.5}]}"#).await;
+        let reranker = HttpReranker::builder(url, "rerank-test")
+            .debug_wire(true)
+            .build();
+
+        let events: Spans = Arc::default();
+        let subscriber = tracing_subscriber::registry().with(WireEvents(events.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let ranked = reranker
+            .rerank("query", vec![Document::new("doc")])
+            .await
+            .unwrap();
+        assert_eq!(ranked.len(), 1);
+        server.await.unwrap();
+
+        let events = events.lock().unwrap();
+        let (message, request) = &events[0];
+        assert_eq!(message, "request");
+        assert_eq!(
+            request["body"],
+            r#"{"model":"rerank-test","query":"query","documents":["doc"]}"#
+        );
+        let (message, response) = &events[1];
+        assert_eq!(message, "response");
+        assert_eq!(response["status"], "200");
+        assert!(response["body"].contains("relevance_score"));
+    }
+
+    #[tokio::test]
+    async fn test_agent_and_tool_spans_record_fields() {
+        let model = Arc::new(MockChatModel::with_messages(vec![
+            tool_call("echo", serde_json::json!({"text": "hi"})),
+            AIMessage::new("done").with_usage(Usage::new(7, 3)),
+        ]));
+        let agent = Agent::builder(model)
+            .name("tester")
+            .tool(echo_tool())
+            .build();
+
+        let spans: Spans = Arc::default();
+        let subscriber = tracing_subscriber::registry().with(Capture(spans.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+        agent.run("say hi").await.unwrap();
+
+        let spans = spans.lock().unwrap();
+        let names: Vec<&str> = spans.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["agent_run", "tool"]);
+
+        let run = &spans[0].1;
+        assert_eq!(run["agent"], "tester");
+        assert_eq!(run["iterations"], "2");
+        assert_eq!(run["input_tokens"], "7");
+        assert_eq!(run["stop_reason"], "Completed");
+        assert!(run.contains_key("duration_ms"));
+
+        let tool = &spans[1].1;
+        assert_eq!(tool["tool"], "echo");
+        assert!(tool.contains_key("duration_ms"));
+    }
+}
+
+#[cfg(feature = "otel")]
+mod otel_spans {
+    use super::*;
+    use agentic_optio_rs::telemetry::OtelChatModel;
+    use opentelemetry::global::BoxedTracer;
+    use opentelemetry::trace::{Status, TracerProvider};
+    use opentelemetry::{KeyValue, Value};
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+
+    fn attribute<'a>(span: &'a SpanData, key: &str) -> Option<&'a Value> {
+        span.attributes
+            .iter()
+            .find(|kv: &&KeyValue| kv.key.as_str() == key)
+            .map(|kv| &kv.value)
+    }
+
+    #[tokio::test]
+    async fn test_otel_spans_follow_genai_conventions() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        // Tool spans always go to the global provider
+        opentelemetry::global::set_tracer_provider(provider.clone());
+
+        let model = Arc::new(MockChatModel::with_messages(vec![
+            tool_call("echo", serde_json::json!({"text": "hi"})),
+            AIMessage::new("done").with_usage(Usage::new(7, 3)),
+        ]));
+        let tracer = BoxedTracer::new(Box::new(provider.tracer("test")));
+        let traced = OtelChatModel::new(model).tracer(tracer);
+        let agent = Agent::builder(std::sync::Arc::new(traced.clone()))
+            .tool(echo_tool())
+            .build();
+        agent.run("say hi").await.unwrap();
+        assert!(traced.invoke(&[Message::user("again")]).await.is_err());
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let names: Vec<&str> = spans.iter().map(|s| s.name.as_ref()).collect();
+        assert_eq!(
+            names,
+            vec!["chat mock", "execute_tool echo", "chat mock", "chat mock"]
+        );
+
+        let answer = &spans[2];
+        assert_eq!(
+            attribute(answer, "gen_ai.operation.name"),
+            Some(&Value::from("chat"))
+        );
+        assert_eq!(
+            attribute(answer, "gen_ai.request.model"),
+            Some(&Value::from("mock"))
+        );
+        assert_eq!(
+            attribute(answer, "gen_ai.usage.input_tokens"),
+            Some(&Value::I64(7))
+        );
+        assert_eq!(
+            attribute(answer, "gen_ai.usage.output_tokens"),
+            Some(&Value::I64(3))
+        );
+        let reasons = attribute(&spans[0], "gen_ai.response.finish_reasons").unwrap();
+        assert_eq!(reasons.as_str(), "[\"tool_calls\"]");
+
+        let tool = &spans[1];
+        assert_eq!(
+            attribute(tool, "gen_ai.tool.name"),
+            Some(&Value::from("echo"))
+        );
+        assert_eq!(
+            attribute(tool, "gen_ai.tool.call.id"),
+            Some(&Value::from("call_1"))
+        );
+
+        let failed = &spans[3];
+        assert_eq!(attribute(failed, "error.type"), Some(&Value::from("api")));
+        assert!(matches!(failed.status, Status::Error { .. }));
+    }
+}
+
+#[cfg(feature = "prometheus")]
+mod prometheus_metrics {
+    use super::*;
+    use agentic_optio_rs::telemetry::{Metrics, MetricsChatModel};
+
+    #[tokio::test]
+    async fn test_metrics_count_requests_tokens_and_cost() {
+        let model = Arc::new(MockChatModel::with_messages(vec![
+            AIMessage::new("one").with_usage(Usage::new(1000, 500)),
+            AIMessage::new("two words"),
+        ]));
+        let metrics = Metrics::new().pricing("mock", 0.5, 2.0);
+        let llm = MetricsChatModel::new(model, metrics.clone());
+
+        let messages = [Message::user("hi")];
+        llm.invoke(&messages).await.unwrap();
+        let mut stream = llm.stream(&messages).await.unwrap();
+        while stream.next().await.is_some() {}
+        drop(stream);
+        assert!(llm.invoke(&messages).await.is_err());
+        metrics.record_retry("mock", "mock");
+
+        let model = r#"model="mock",provider="mock""#;
+        let labels = r#"model="mock",operation="chat",provider="mock""#;
+        let text = metrics.gather();
+        assert!(text.contains(&format!("agentic_optio_requests_total{{{}}} 3", labels)));
+        assert!(text.contains(&format!(
+            r#"agentic_optio_errors_total{{kind="api",{}}} 1"#,
+            labels
+        )));
+        assert!(text.contains(&format!(
+            "agentic_optio_request_duration_seconds_count{{{}}} 3",
+            labels
+        )));
+        assert!(text.contains(&format!(
+            r#"agentic_optio_tokens_total{{direction="input",{}}} 1000"#,
+            model
+        )));
+        assert!(text.contains(r#"agentic_optio_cost_total{model="mock",provider="mock"} 1.5"#));
+        assert!(text.contains(r#"agentic_optio_retries_total{model="mock",provider="mock"} 1"#));
+    }
+
+    #[test]
+    fn test_metrics_share_an_existing_registry() {
+        let registry = prometheus::Registry::new();
+        let metrics = Metrics::with_registry(registry.clone()).unwrap();
+        metrics.record_retry("ollama", "llama3.2");
+        assert_eq!(registry.gather().len(), 1);
+        assert!(Metrics::with_registry(registry).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! Test double tests for agentic_optio_rs
+
+use agentic_optio_rs::core::messages::{Message, Usage};
+use agentic_optio_rs::models::base::ModelError;
+use agentic_optio_rs::testing::{FakeEmbedding, MockChatModel};
+use agentic_optio_rs::vectorstores::{InMemoryVectorStore, VectorStore};
+use agentic_optio_rs::{BaseChatModel, BaseEmbedding, Document};
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+mod common;
+
+#[tokio::test]
+async fn test_mock_chat_model_follows_script_and_records_calls() {
+    let model = MockChatModel::new()
+        .respond("first")
+        .respond_tool_call("add", serde_json::json!({"a": 1}))
+        .fail(ModelError::ApiError("overloaded".to_string()));
+    let tools = [serde_json::json!({"type": "function"})];
+
+    assert_eq!(
+        model.invoke(&[Message::user("one")]).await.unwrap().content,
+        "first"
+    );
+    let call = model
+        .invoke_with_tools(&[Message::user("two")], &tools)
+        .await
+        .unwrap();
+    assert_eq!(call.tool_calls[0].id, "call_1");
+    assert_eq!(call.tool_calls[0].name, "add");
+    assert!(matches!(
+        model.invoke(&[]).await,
+        Err(ModelError::ApiError(e)) if e == "overloaded"
+    ));
+    assert!(model.invoke(&[]).await.is_err());
+
+    assert_eq!(model.calls(), 4);
+    assert_eq!(model.remaining(), 0);
+    assert_eq!(model.received()[1][0].content(), "two");
+    assert_eq!(model.received_tools()[1].len(), 1);
+    assert!(model.received_tools()[0].is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_chat_model_streams_words_and_waits() {
+    let model = MockChatModel::new()
+        .respond_with(
+            agentic_optio_rs::AIMessage::new("hello mock world").with_usage(Usage::new(3, 3)),
+        )
+        .latency(Duration::from_millis(20));
+
+    let started = Instant::now();
+    let chunks: Vec<_> = model
+        .stream(&[Message::user("hi")])
+        .await
+        .unwrap()
+        .map(|chunk| chunk.unwrap())
+        .collect()
+        .await;
+
+    assert!(started.elapsed() >= Duration::from_millis(20));
+    let text: String = chunks.iter().map(|c| c.content.as_str()).collect();
+    assert_eq!(text, "hello mock world");
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(chunks[3].usage, Some(Usage::new(3, 3)));
+}
+
+#[tokio::test]
+async fn test_fake_embedding_is_stable_and_searchable() {
+    let embeddings = FakeEmbedding::new(48);
+    let vectors = embeddings
+        .embed(&[
+            "Rust memory safety".to_string(),
+            "rust  MEMORY safety!".to_string(),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(vectors[0].len(), 48);
+    assert_eq!(vectors[0], vectors[1]);
+    let norm: f32 = vectors[0].iter().map(|x| x * x).sum();
+    assert!((norm - 1.0).abs() < 1e-5);
+    assert_eq!(embeddings.embed_text("..."), vec![0.0; 48]);
+
+    let store = InMemoryVectorStore::new(Arc::new(embeddings));
+    store
+        .add_documents(vec![
+            Document::new("Python is dynamically typed"),
+            Document::new("Rust guarantees memory safety"),
+            Document::new("Bananas are yellow"),
+        ])
+        .await
+        .unwrap();
+    let hits = store
+        .similarity_search("memory safety in rust", 1)
+        .await
+        .unwrap();
+    assert_eq!(hits[0].content, "Rust guarantees memory safety");
+}
+
+#[cfg(feature = "ollama")]
+#[tokio::test]
+async fn test_vcr_records_then_replays_offline() {
+    use agentic_optio_rs::testing::{Vcr, VcrError, VcrMode};
+    use agentic_optio_rs::OllamaChat;
+
+    let path = std::env::temp_dir().join(format!("vcr-{}.json", uuid::Uuid::new_v4()));
+    let (upstream, server) =
+        common::capture_request(r#"{"choices": [{"message": {"content": "recorded"}}]}"#).await;
+    let ask = |url: &str| {
+        let llm = OllamaChat::builder("llama3.2").host(url).build();
+        async move { llm.invoke(&[Message::user("hi")]).await }
+    };
+
+    let recorder = Vcr::builder(&path, upstream)
+        .mode(VcrMode::Auto)
+        .start()
+        .await
+        .unwrap();
+    assert_eq!(recorder.mode(), VcrMode::Record);
+    assert_eq!(ask(recorder.url()).await.unwrap().content, "recorded");
+    let (head, _) = server.await.unwrap();
+    assert!(head.starts_with("POST /v1/chat/completions"));
+    drop(recorder);
+
+    // The upstream is gone, so these answers come from the cassette
+    let player = Vcr::builder(&path, "http://127.0.0.1:9")
+        .mode(VcrMode::Auto)
+        .start()
+        .await
+        .unwrap();
+    assert_eq!(player.mode(), VcrMode::Replay);
+    assert_eq!(player.interactions().len(), 1);
+    assert_eq!(ask(player.url()).await.unwrap().content, "recorded");
+    assert_eq!(ask(player.url()).await.unwrap().content, "recorded");
+    let llm = OllamaChat::builder("llama3.2").host(player.url()).build();
+    let started = Instant::now();
+    let unrecorded = llm.invoke(&[Message::user("unrecorded")]).await;
+    assert!(matches!(
+        unrecorded,
+        Err(ModelError::ApiError(e))
+            if e.contains("HTTP 400") && e.contains("POST /v1/chat/completions")
+    ));
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    std::fs::remove_file(&path).unwrap();
+    let missing = Vcr::builder(&path, "http://127.0.0.1:9")
+        .mode(VcrMode::Replay)
+        .start()
+        .await;
+    assert!(matches!(missing, Err(VcrError::MissingCassette(_))));
+}
+
+fn add_tool() -> agentic_optio_rs::tools::FunctionTool {
+    agentic_optio_rs::tools::FunctionTool::new("add", "Add two numbers", |args| async move {
+        Ok(
+            (args["a"].as_i64().unwrap_or_default() + args["b"].as_i64().unwrap_or_default())
+                .to_string(),
+        )
+    })
+}
+
+async fn add_run(answer: &str) -> agentic_optio_rs::AgentRun {
+    let model = MockChatModel::new()
+        .respond_tool_call("add", serde_json::json!({"a": 2, "b": 3}))
+        .respond(answer);
+    let agent = agentic_optio_rs::Agent::builder(Arc::new(model))
+        .tool(add_tool())
+        .build();
+    agent.run("What is 2 + 3?").await.unwrap()
+}
+
+#[tokio::test]
+async fn test_golden_trajectory_detects_regressions() {
+    use agentic_optio_rs::testing::{Golden, GoldenError, GoldenOutcome, TextMatch, Trajectory};
+
+    let path = std::env::temp_dir().join(format!("golden-{}.json", uuid::Uuid::new_v4()));
+    let run = add_run("The answer is 5").await;
+    let trajectory = Trajectory::from(&run);
+    assert_eq!(trajectory.steps.len(), 3);
+
+    assert_eq!(
+        Golden::new(&path).check(&run).unwrap(),
+        GoldenOutcome::Created
+    );
+    assert_eq!(
+        Golden::new(&path).check(&run).unwrap(),
+        GoldenOutcome::Matched
+    );
+
+    let reworded = add_run(" The  answer\nis 5").await;
+    assert_eq!(
+        Golden::new(&path).check(&reworded).unwrap(),
+        GoldenOutcome::Matched
+    );
+    let reworded = add_run("So the answer is 5").await;
+    assert!(Golden::new(&path).check(&reworded).is_err());
+    assert!(Golden::new(&path)
+        .messages(TextMatch::Similar(0.7))
+        .check(&reworded)
+        .is_ok());
+
+    // Skipping the tool is one missing call and one missing result
+    let model = MockChatModel::new().respond("The answer is 5");
+    let agent = agentic_optio_rs::Agent::builder(Arc::new(model)).build();
+    let skipped = agent.run("What is 2 + 3?").await.unwrap();
+    match Golden::new(&path).check(&skipped) {
+        Err(GoldenError::Mismatch { differences, .. }) => {
+            assert_eq!(differences.len(), 2, "{differences:?}");
+            assert!(differences[0].contains("missing step"));
+        }
+        other => panic!("expected a mismatch, got {other:?}"),
+    }
+    assert!(Golden::new(&path).max_edits(2).check(&skipped).is_ok());
+    std::fs::remove_file(&path).unwrap();
+}
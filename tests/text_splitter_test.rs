@@ -0,0 +1,73 @@
+//! Text splitter tests for agentic_optio_rs
+
+use agentic_optio_rs::text_splitter::{
+    MarkdownSplitter, RecursiveCharacterSplitter, TextSplitter, TokenSplitter,
+};
+use agentic_optio_rs::Document;
+
+#[test]
+fn test_recursive_chunks_respect_size_and_overlap() {
+    let text = "alpha beta gamma delta epsilon zeta eta theta iota kappa";
+    let splitter = RecursiveCharacterSplitter::new(20).chunk_overlap(6);
+    let chunks = splitter.split_text(text);
+
+    assert!(chunks.iter().all(|c| c.chars().count() <= 20));
+    // Overlap carries whole words only while they fit in 6 characters.
+    assert_eq!(chunks[0], "alpha beta gamma");
+    assert_eq!(chunks[1], "gamma delta epsilon");
+    assert_eq!(chunks[2], "zeta eta theta iota");
+}
+
+#[test]
+fn test_recursive_falls_back_to_characters() {
+    let text = "ééééééééééééé";
+    let chunks = RecursiveCharacterSplitter::new(5).split_text(text);
+    assert_eq!(chunks, vec!["ééééé", "ééééé", "ééé"]);
+}
+
+#[test]
+fn test_split_documents_records_offsets() {
+    let doc = Document::new("Intro line.\n\nBody paragraph one.\n\nBody paragraph two.")
+        .with_metadata("source", "notes.txt");
+    let chunks = RecursiveCharacterSplitter::new(25).split_documents(std::slice::from_ref(&doc));
+
+    assert_eq!(chunks.len(), 3);
+    for chunk in &chunks {
+        let start = chunk.metadata["start_index"].as_u64().unwrap() as usize;
+        let end = chunk.metadata["end_index"].as_u64().unwrap() as usize;
+        assert_eq!(&doc.content[start..end], chunk.content);
+        assert_eq!(chunk.metadata["source"], "notes.txt");
+    }
+}
+
+#[test]
+fn test_markdown_section_metadata() {
+    let text = "# Guide\nOverview text.\n\n## Setup\nInstall the crate and configure it.\n\n\
+                ## Usage\nCall the model.";
+    let chunks = MarkdownSplitter::new(50).split_documents(&[Document::new(text)]);
+
+    let sections: Vec<&str> = chunks
+        .iter()
+        .map(|c| c.metadata["section"].as_str().unwrap())
+        .collect();
+    assert_eq!(sections, vec!["Guide", "Setup", "Usage"]);
+    assert!(chunks[1].content.starts_with("## Setup"));
+}
+
+#[test]
+fn test_token_splitter_custom_tokenizer() {
+    // Whitespace tokenizer
+    let splitter = TokenSplitter::new(2).tokenizer(|text| {
+        let mut spans = Vec::new();
+        let mut offset = 0;
+        for word in text.split(' ') {
+            spans.push((offset, offset + word.len()));
+            offset += word.len() + 1;
+        }
+        spans
+    });
+    assert_eq!(
+        splitter.split_text("can't stop won't stop"),
+        vec!["can't stop", "won't stop"]
+    );
+}
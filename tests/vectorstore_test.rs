@@ -0,0 +1,302 @@
+//! Vector store tests for agentic_optio_rs
+
+use agentic_optio_rs::retrievers::{Retriever, VectorStoreRetriever};
+use agentic_optio_rs::vectorstores::{
+    HnswConfig, InMemoryVectorStore, MetadataFilter, MmrParams, VectorStore,
+};
+use agentic_optio_rs::Document;
+use std::sync::Arc;
+
+mod common;
+use common::LetterEmbedding;
+
+fn store() -> InMemoryVectorStore {
+    InMemoryVectorStore::new(Arc::new(LetterEmbedding))
+}
+
+#[tokio::test]
+async fn test_in_memory_search_ranks_by_similarity() {
+    let store = store();
+    let ids = store
+        .add_documents(vec![
+            Document::new("aaaa").with_metadata("tag", "a"),
+            Document::new("zzzz").with_metadata("tag", "z"),
+            Document::new("aazz"),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(ids.len(), 3);
+
+    let hits = store.similarity_search_with_score("aaa", 2).await.unwrap();
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].0.content, "aaaa");
+    assert_eq!(hits[0].0.metadata["tag"], "a");
+    assert_eq!(hits[0].0.id.as_deref(), Some(ids[0].as_str()));
+    assert!((hits[0].1 - 1.0).abs() < 1e-6);
+    assert_eq!(hits[1].0.content, "aazz");
+}
+
+#[tokio::test]
+async fn test_in_memory_upsert_and_delete() {
+    let store = store();
+    store
+        .add_documents(vec![Document::new("old").with_id("doc-1")])
+        .await
+        .unwrap();
+    store
+        .add_documents(vec![Document::new("new").with_id("doc-1")])
+        .await
+        .unwrap();
+    assert_eq!(store.len(), 1);
+    assert_eq!(store.get("doc-1").unwrap().content, "new");
+
+    store.delete(&["doc-1".to_string()]).await.unwrap();
+    assert!(store.is_empty());
+}
+
+#[tokio::test]
+async fn test_in_memory_persist_and_load() {
+    let path = std::env::temp_dir().join(format!("optio-vectors-{}.json", std::process::id()));
+    let store = store().with_path(&path);
+    store
+        .add_documents(vec![Document::new("persist me").with_id("keep")])
+        .await
+        .unwrap();
+    store.persist().await.unwrap();
+
+    let loaded = InMemoryVectorStore::load(&path, Arc::new(LetterEmbedding)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let hits = loaded.similarity_search("persist me", 1).await.unwrap();
+    assert_eq!(hits[0].id.as_deref(), Some("keep"));
+}
+
+#[tokio::test]
+async fn test_in_memory_metadata_filter() {
+    let store = store();
+    store
+        .add_documents(vec![
+            Document::new("aaaa").with_metadata("lang", "en"),
+            Document::new("aaab").with_metadata("lang", "fr"),
+        ])
+        .await
+        .unwrap();
+
+    let hits = store
+        .similarity_search_with_filter("aaaa", 5, &MetadataFilter::new().eq("lang", "fr"))
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].0.content, "aaab");
+}
+
+#[cfg(feature = "qdrant")]
+#[tokio::test]
+#[ignore] // Requires Qdrant running
+async fn test_qdrant_round_trip() {
+    use agentic_optio_rs::vectorstores::{MetadataFilter, QdrantVectorStore};
+
+    let store = QdrantVectorStore::new("optio_test", Arc::new(LetterEmbedding));
+    let _ = store.delete_collection().await;
+    let ids = store
+        .add_documents(vec![
+            Document::new("aaaa")
+                .with_id("first")
+                .with_metadata("lang", "en"),
+            Document::new("zzzz").with_metadata("lang", "de"),
+        ])
+        .await
+        .unwrap();
+
+    let hits = store
+        .similarity_search_with_filter("aaa", 5, &MetadataFilter::new().eq("lang", "en"))
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].0.id.as_deref(), Some("first"));
+
+    store.delete(&ids).await.unwrap();
+    store.delete_collection().await.unwrap();
+}
+
+#[cfg(feature = "pgvector")]
+#[tokio::test]
+#[ignore] // Requires Postgres with pgvector at DATABASE_URL
+async fn test_pgvector_round_trip() {
+    use agentic_optio_rs::vectorstores::{MetadataFilter, PgVectorStore};
+
+    let url = std::env::var("DATABASE_URL").unwrap();
+    let store = PgVectorStore::connect(&url, Arc::new(LetterEmbedding))
+        .await
+        .unwrap()
+        .table("optio_test_documents")
+        .unwrap()
+        .build();
+    store.drop_table().await.unwrap();
+
+    store
+        .add_documents(vec![
+            Document::new("aaaa")
+                .with_id("a")
+                .with_metadata("lang", "en"),
+            Document::new("aaab")
+                .with_id("b")
+                .with_metadata("lang", "fr"),
+            Document::new("zzzz")
+                .with_id("z")
+                .with_metadata("lang", "de"),
+        ])
+        .await
+        .unwrap();
+
+    let filter = MetadataFilter::new().any_of("lang", ["fr", "de"]);
+    let hits = store
+        .similarity_search_with_filter("aaaa", 5, &filter)
+        .await
+        .unwrap();
+    let ids: Vec<_> = hits.iter().map(|(d, _)| d.id.clone().unwrap()).collect();
+    assert_eq!(ids, vec!["b", "z"]);
+
+    store.drop_table().await.unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_sqlite_store_survives_reopen() {
+    use agentic_optio_rs::vectorstores::{MetadataFilter, SqliteVectorStore};
+
+    let path = std::env::temp_dir().join(format!("optio-vectors-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let store = SqliteVectorStore::open(&path, Arc::new(LetterEmbedding))
+        .await
+        .unwrap()
+        .build();
+    store
+        .add_documents(vec![
+            Document::new("aaaa")
+                .with_id("a")
+                .with_metadata("lang", "en"),
+            Document::new("aaab")
+                .with_id("b")
+                .with_metadata("lang", "fr"),
+        ])
+        .await
+        .unwrap();
+    store.delete(&["a".to_string()]).await.unwrap();
+    store.pool().close().await;
+
+    let reopened = SqliteVectorStore::open(&path, Arc::new(LetterEmbedding))
+        .await
+        .unwrap()
+        .build();
+    let hits = reopened
+        .similarity_search_with_filter("aaaa", 5, &MetadataFilter::new().eq("lang", "fr"))
+        .await
+        .unwrap();
+    reopened.pool().close().await;
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].0.id.as_deref(), Some("b"));
+    assert_eq!(hits[0].0.metadata["lang"], "fr");
+}
+
+#[tokio::test]
+async fn test_mmr_skips_near_duplicates() {
+    let store = Arc::new(InMemoryVectorStore::new(Arc::new(LetterEmbedding)));
+    store
+        .add_documents(vec![
+            Document::new("aaa"),
+            Document::new("aaa aaa"),
+            Document::new("bbb"),
+            Document::new("ccc"),
+        ])
+        .await
+        .unwrap();
+
+    let plain = store.similarity_search("ab", 2).await.unwrap();
+    assert_eq!(plain[0].content, "aaa");
+    assert_eq!(plain[1].content, "aaa aaa");
+
+    let diverse = store
+        .max_marginal_relevance_search("ab", &MmrParams::new(2).lambda(0.5))
+        .await
+        .unwrap();
+    let contents: Vec<&str> = diverse.iter().map(|d| d.content.as_str()).collect();
+    assert_eq!(contents, vec!["aaa", "bbb"]);
+
+    // lambda 1.0 is plain relevance ranking
+    let relevant = store
+        .max_marginal_relevance_search("ab", &MmrParams::new(2).lambda(1.0))
+        .await
+        .unwrap();
+    assert_eq!(relevant[1].content, "aaa aaa");
+
+    let retriever = VectorStoreRetriever::new(store).k(2).mmr(4, 0.5);
+    let docs = retriever.retrieve("ab").await.unwrap();
+    assert_eq!(docs[1].content, "bbb");
+}
+
+/// Deterministic pseudo-random lowercase word
+fn word(seed: u32) -> String {
+    let mut state = seed.wrapping_mul(2654435761).wrapping_add(12345);
+    (0..12)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (b'a' + (state % 26) as u8) as char
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_hnsw_matches_brute_force() {
+    let docs: Vec<Document> = (0..400)
+        .map(|i| {
+            Document::new(word(i))
+                .with_id(i.to_string())
+                .with_metadata("even", i % 2 == 0)
+        })
+        .collect();
+    let exact = store();
+    exact.add_documents(docs.clone()).await.unwrap();
+    let approx = store().with_hnsw(HnswConfig::default().m(8).ef_search(48));
+    approx.add_documents(docs).await.unwrap();
+
+    let k = 5;
+    let mut hits = 0;
+    for q in 1000..1020 {
+        let query = word(q);
+        let truth = exact.similarity_search_with_score(&query, k).await.unwrap();
+        let found = approx
+            .similarity_search_with_score(&query, k)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), k);
+        let cutoff = truth[k - 1].1 - 1e-6;
+        hits += found.iter().filter(|(_, score)| *score >= cutoff).count();
+    }
+    assert!(hits >= 95, "recall {}/100", hits);
+
+    // Deletes and upserts hide stale entries; filters stay exact
+    approx.delete(&["0".to_string()]).await.unwrap();
+    approx
+        .add_documents(vec![Document::new("zzzz").with_id("1")])
+        .await
+        .unwrap();
+    assert_eq!(approx.len(), 399);
+    let top = approx.similarity_search(&word(0), 400).await.unwrap();
+    assert!(top.iter().all(|d| d.id.as_deref() != Some("0")));
+    assert_eq!(
+        top.iter().filter(|d| d.id.as_deref() == Some("1")).count(),
+        1
+    );
+
+    let filter = MetadataFilter::new().eq("even", true);
+    let evens = approx
+        .similarity_search_with_filter(&word(7), 300, &filter)
+        .await
+        .unwrap();
+    assert_eq!(evens.len(), 199);
+}